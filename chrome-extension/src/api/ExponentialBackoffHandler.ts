@@ -8,6 +8,7 @@
  *
  * リトライ可能エラー: 408, 429, 500, 502, 503, 504
  * バックオフ: 初回1秒、最大60秒、Jitter付き
+ * Retry-After: 429/503応答が指定した待ち時間を優先（最大60秒）
  * 最大リトライ: 5回
  */
 
@@ -21,7 +22,9 @@ import type { IExponentialBackoffHandler } from './IExponentialBackoffHandler';
 export class RetryableError extends Error {
   constructor(
     message: string,
-    public readonly statusCode: number
+    public readonly statusCode: number,
+    /** Retry-Afterヘッダーが指定した待ち時間（ミリ秒） */
+    public readonly retryAfterMs?: number
   ) {
     super(message);
     this.name = 'RetryableError';
@@ -67,7 +70,10 @@ export class ExponentialBackoffHandler implements IExponentialBackoffHandler {
         lastError = error;
 
         if (attempt < this.maxRetries) {
-          const delayMs = this.calculateDelay(attempt);
+          const delayMs =
+            error.retryAfterMs !== undefined
+              ? Math.min(error.retryAfterMs, this.maxDelayMs)
+              : this.calculateDelay(attempt);
           await this.sleep(delayMs);
         }
       }
@@ -101,4 +107,17 @@ export class ExponentialBackoffHandler implements IExponentialBackoffHandler {
   static isRetryableStatus(statusCode: number): boolean {
     return [408, 429, 500, 502, 503, 504].includes(statusCode);
   }
+
+  /**
+   * Retry-Afterヘッダー（秒数またはHTTP日付）を待ち時間（ミリ秒）に変換
+   */
+  static parseRetryAfter(value: string | null, now = Date.now()): number | undefined {
+    if (value === null) return undefined;
+    const trimmed = value.trim();
+    if (/^\d+$/.test(trimmed)) {
+      return Number(trimmed) * 1000;
+    }
+    const at = Date.parse(trimmed);
+    return Number.isNaN(at) ? undefined : Math.max(0, at - now);
+  }
 }
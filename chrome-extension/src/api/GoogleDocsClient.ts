@@ -272,7 +272,8 @@ export class GoogleDocsClient implements IGoogleDocsClient {
       if (ExponentialBackoffHandler.isRetryableStatus(response.status)) {
         throw new RetryableError(
           `Retryable error (HTTP ${response.status})`,
-          response.status
+          response.status,
+          ExponentialBackoffHandler.parseRetryAfter(response.headers.get('Retry-After'))
         );
       }
 
@@ -25,6 +25,11 @@ export interface TranscriptionMessage {
   confidence?: number;
   language?: string;
   processingTimeMs?: number;
+  /** Part number (1-based) when a long message is split; parts share messageId */
+  part?: number;
+  totalParts?: number;
+  /** Text beyond the server's per-segment limit was not sent */
+  truncated?: boolean;
 }
 
 export interface ErrorMessage {
@@ -45,6 +50,17 @@ export interface NotificationMessage {
   data?: Record<string, unknown>;
 }
 
+/** `data` of `docs_budget_warning` / `docs_budget_exceeded` notifications */
+export interface DocsBudgetStatus {
+  sessionCharacters: number;
+  documentCharacters: number;
+  limitCharacters: number;
+  remainingCharacters: number;
+  documentId?: string;
+  warning: boolean;
+  exceeded: boolean;
+}
+
 export type InboundWebSocketMessage =
   | ConnectedMessage
   | TranscriptionMessage
@@ -68,6 +68,24 @@ describe('ExponentialBackoffHandler', () => {
       expect(sleepSpy).toHaveBeenCalledTimes(2);
     });
 
+    it('should wait for Retry-After instead of the backoff delay', async () => {
+      const handler = new ExponentialBackoffHandler(3, 1, 5000);
+      const sleepSpy = vi
+        .spyOn(handler as unknown as { sleep: (ms: number) => Promise<void> }, 'sleep')
+        .mockResolvedValue();
+      const fn = vi
+        .fn()
+        .mockRejectedValueOnce(new RetryableError('retry', 429, 2000))
+        .mockRejectedValueOnce(new RetryableError('retry', 429, 60000))
+        .mockResolvedValue('ok');
+
+      const result = await handler.executeWithBackoff(fn);
+
+      expect(result.ok).toBe(true);
+      expect(sleepSpy).toHaveBeenNthCalledWith(1, 2000);
+      expect(sleepSpy).toHaveBeenNthCalledWith(2, 5000);
+    });
+
     it('should throw non-retryable errors', async () => {
       const handler = new ExponentialBackoffHandler(3, 1, 10);
       const fn = vi.fn(async () => {
@@ -95,4 +113,16 @@ describe('ExponentialBackoffHandler', () => {
       expect(ExponentialBackoffHandler.isRetryableStatus(404)).toBe(false);
     });
   });
+
+  describe('parseRetryAfter', () => {
+    it('should parse delay seconds and HTTP dates', () => {
+      const now = Date.parse('Wed, 21 Oct 2015 07:27:30 GMT');
+      expect(ExponentialBackoffHandler.parseRetryAfter('120', now)).toBe(120000);
+      expect(
+        ExponentialBackoffHandler.parseRetryAfter('Wed, 21 Oct 2015 07:28:00 GMT', now)
+      ).toBe(30000);
+      expect(ExponentialBackoffHandler.parseRetryAfter('soon', now)).toBeUndefined();
+      expect(ExponentialBackoffHandler.parseRetryAfter(null, now)).toBeUndefined();
+    });
+  });
 });
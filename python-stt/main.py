@@ -15,15 +15,19 @@ Related Requirements:
 
 import sys
 import os
+import argparse
 import asyncio
 import logging
 import time
-from typing import Dict, Any
+from collections import OrderedDict
+from typing import Dict, Any, List, Optional
 
-from stt_engine.ipc_handler import IpcHandler
+from stt_engine.ipc_handler import IpcHandler, IpcProtocolError
 from stt_engine.audio_pipeline import AudioPipeline
 from stt_engine.transcription.voice_activity_detector import VoiceActivityDetector
 from stt_engine.transcription.whisper_client import WhisperSTTEngine
+from stt_engine.transcription.worker_pool import TranscriptionWorkerPool
+from stt_engine.transcription.cloud_client import API_KEY_ENV, PROVIDERS, CloudSTTEngine
 
 # ログ設定
 logging.basicConfig(
@@ -33,6 +37,22 @@ logging.basicConfig(
 )
 logger = logging.getLogger(__name__)
 
+# Pipelines kept for concurrent recording sessions (MAX_CONCURRENT_SESSIONS in Rust)
+MAX_SESSION_PIPELINES = 4
+
+
+def request_session(request_id: str) -> Optional[str]:
+    """
+    Session part of a '{session}-{stream}-{seq}' request ID (request_id.rs).
+
+    Returns:
+        None for IDs in another format (legacy requests, tests)
+    """
+    parts = request_id.rsplit('-', 2)
+    if len(parts) != 3 or not parts[0] or not parts[2].isdigit():
+        return None
+    return parts[0]
+
 
 class AudioProcessor:
     """
@@ -53,8 +73,16 @@ class AudioProcessor:
         self.vad = VoiceActivityDetector(sample_rate=16000, aggressiveness=2)
         # TEMPORARY: Force tiny model for multi-input testing
         self.stt_engine = WhisperSTTEngine(model_size="tiny", auto_select_model=False)
+        # Requests without a session; sessions get their own (see _pipeline_for)
         self.pipeline = AudioPipeline(vad=self.vad, stt_engine=self.stt_engine)
+        self.session_pipelines = OrderedDict()
         self.ipc = None
+        # Processing time per audio time of recent requests (health checks)
+        self.real_time_factor = None
+        # Worker-pool mode (--workers N): final transcriptions in flight, in segment order
+        self.final_pool = None
+        self._pending_finals = None
+        self._final_emitter = None
 
         # Phase 1.2: Initialize ResourceMonitor with dependencies (STT-REQ-006)
         # Note: ipc will be set later via set_ipc_handler()
@@ -80,9 +108,11 @@ class AudioProcessor:
           - method=process_audio: Process audio frames through VAD→Pipeline→STT
           - method=process_audio_stream: Real-time event streaming (Task 7.1.6)
           - method=approve_upgrade: User-approved model upgrade
+          - method=open_audio_channel: Connect the binary audio channel (raw PCM)
+          - method=hello: Negotiate the protocol version right after ready
         - process_audio (legacy): Direct process_audio for backward compatibility
         - approve_upgrade (legacy): Direct approve_upgrade for backward compatibility
-        - ping: Health check (respond with pong)
+        - ping: Health check (respond with pong carrying health metrics)
         - shutdown: Graceful shutdown
         """
         msg_type = msg.get('type')
@@ -101,9 +131,54 @@ class AudioProcessor:
 
                 elif method == 'process_audio_stream':
                     # Task 7.1.6: Real-time event streaming
-                    msg_with_audio = {'id': msg_id, 'audio_data': params.get('audio_data')}
+                    # PCM follows on the binary audio channel when audio_bytes is given
+                    if 'audio_bytes' in params:
+                        audio_data = await self.ipc.read_audio(int(params['audio_bytes']))
+                    else:
+                        audio_data = params.get('audio_data')
+                    msg_with_audio = {'id': msg_id, 'audio_data': audio_data}
                     await self._handle_process_audio_stream(msg_with_audio)
 
+                elif method == 'hello':
+                    # Startup handshake: agree on the highest common protocol version
+                    offered = params.get('versions', [])
+                    version = self.ipc.negotiate_version(offered)
+                    if version is None:
+                        await self.ipc.send_message({
+                            'type': 'error',
+                            'id': msg_id,
+                            'errorCode': 'UNSUPPORTED_VERSION',
+                            'errorMessage': (
+                                f"No common protocol version: offered {offered}, "
+                                f"supported {list(self.ipc.SUPPORTED_PROTOCOL_VERSIONS)}"
+                            ),
+                            'recoverable': False
+                        })
+                    else:
+                        await self.ipc.send_message({
+                            'type': 'response',
+                            'id': msg_id,
+                            'result': {
+                                'version': version,
+                                'versions': list(self.ipc.SUPPORTED_PROTOCOL_VERSIONS)
+                            }
+                        })
+
+                elif method == 'open_audio_channel':
+                    # Binary audio channel offered by Rust (raw PCM instead of JSON arrays)
+                    try:
+                        self.ipc.open_audio_channel(params.get('kind'), params.get('path'))
+                        accepted = True
+                    except IpcProtocolError as e:
+                        logger.warning(f"Audio channel declined: {e}")
+                        accepted = False
+                    await self.ipc.send_message({
+                        'type': 'response',
+                        'id': msg_id,
+                        'version': '1.0',
+                        'result': {'accepted': accepted}
+                    })
+
                 elif method == 'approve_upgrade':
                     # Extract target_model from params (new format)
                     params = msg.get('params', {})
@@ -121,6 +196,21 @@ class AudioProcessor:
                         'result': {'status': 'acknowledged'}
                     })
 
+                elif method == 'flush':
+                    # Control lane: answered once the speech so far is transcribed
+                    await self._handle_flush(msg_id)
+
+                elif method == 'cancel':
+                    # Control lane: the speech in progress is dropped untranscribed
+                    for pipeline in self._pipelines():
+                        pipeline.cancel()
+                    await self.ipc.send_message({
+                        'type': 'response',
+                        'id': msg_id,
+                        'version': '1.0',
+                        'result': {'status': 'cancelled', 'method': method}
+                    })
+
                 else:
                     logger.warning(f"Unknown request method: {method}")
                     await self.ipc.send_message({
@@ -143,7 +233,8 @@ class AudioProcessor:
             elif msg_type == 'ping':
                 await self.ipc.send_message({
                     'type': 'pong',
-                    'id': msg_id
+                    'id': msg_id,
+                    'health': self._health_snapshot()
                 })
 
             elif msg_type == 'shutdown':
@@ -221,6 +312,9 @@ class AudioProcessor:
             result = await self.pipeline.process_audio_frame_with_partial(frame)
 
             if result:
+                if result.get('event') == 'final_pending':
+                    # One response per request: wait for the worker
+                    result = await result['pending']
                 events.append(result)
                 event_type = result.get('event')
                 logger.debug(f"VAD event collected: {event_type}")
@@ -291,6 +385,7 @@ class AudioProcessor:
 
         if not audio_data:
             logger.warning("Empty audio_data received for stream")
+            await self._send_audio_ack(msg_id, 0, t_start)
             return
 
         # Convert audio data (u8 array from Rust) to bytes
@@ -300,6 +395,8 @@ class AudioProcessor:
         # Split into 10ms frames (STT-REQ-003.2)
         frames = self.vad.split_into_frames(audio_bytes)
         t_split = time.perf_counter()
+        # Concurrent sessions are segmented and transcribed independently
+        pipeline = self._pipeline_for(msg_id)
 
         # DIAGNOSTIC: Log pipeline timing (also to file for easier analysis)
         diag_msg = f"📊 DIAG: received {len(audio_bytes)} bytes, {len(frames)} frames, convert={int((t_convert-t_start)*1000)}ms, split={int((t_split-t_convert)*1000)}ms"
@@ -319,8 +416,8 @@ class AudioProcessor:
         # Performance: 2 min recording now processes in seconds instead of 2 min.
         for frame in frames:
             # Use process_audio_frame_with_partial for partial text support
-            result = await self.pipeline.process_audio_frame_with_partial(frame)
-            if self.pipeline.vad and self.pipeline.vad.is_in_speech:
+            result = await pipeline.process_audio_frame_with_partial(frame)
+            if pipeline.vad and pipeline.vad.is_in_speech:
                 vad_speech_count += 1
 
             if result:
@@ -366,43 +463,11 @@ class AudioProcessor:
                     })
 
                 elif event_type == 'final_text':
-                    transcription = result['transcription']
-                    # Send final_text event
-                    # Task 11.1: Include latency_metrics for E2E validation
-                    data = {
-                        'requestId': msg_id,
-                        'text': transcription['text'],
-                        'is_final': True,  # STT-REQ-003.9
-                        'confidence': transcription.get('confidence'),
-                        'language': transcription.get('language'),
-                        'processing_time_ms': transcription.get('processing_time_ms'),
-                        'model_size': self.stt_engine.model_size
-                    }
-                    # Include latency_metrics if available (Task 11.1)
-                    if 'latency_metrics' in result:
-                        data['latency_metrics'] = result['latency_metrics']
+                    await self._send_final_text(msg_id, result)
 
-                    await self.ipc.send_message({
-                        'type': 'event',
-                        'version': '1.0',
-                        'eventType': 'final_text',
-                        'data': data
-                    })
-
-                    # FIXED: Immediately send speech_end after final_text
-                    # AudioPipeline._handle_speech_end() never returns speech_end event
-                    # when STT engine is active (it returns final_text instead).
-                    # We must derive and send speech_end here to satisfy the API contract.
-                    await self.ipc.send_message({
-                        'type': 'event',
-                        'version': '1.0',
-                        'eventType': 'speech_end',
-                        'data': {
-                            'requestId': msg_id,
-                            'timestamp': int(time.time() * 1000)  # Current timestamp
-                        }
-                    })
-                    logger.debug(f"Sent speech_end after final_text for {msg_id}")
+                elif event_type == 'final_pending':
+                    # Worker-pool mode: sent by _emit_pending_finals once transcribed
+                    self._pending_finals.put_nowait((msg_id, result['pending']))
 
                 elif event_type == 'speech_end':
                     # This branch is only hit when STT engine is disabled
@@ -421,17 +486,7 @@ class AudioProcessor:
                 elif event_type == 'error':
                     # P0 FIX: Handle error events to prevent Rust-side hang
                     # Without this, Rust's receive_message() will block forever
-                    # CRITICAL: IpcMessage::Error requires 'id' field (ipc_protocol.rs L104)
-                    error_msg = result.get('message', 'Unknown error')
-                    logger.error(f"AudioPipeline error: {error_msg}")
-                    await self.ipc.send_message({
-                        'type': 'error',
-                        'id': msg_id,  # FIXED: id field is mandatory for IpcMessage::Error
-                        'version': '1.0',
-                        'errorCode': 'AUDIO_PIPELINE_ERROR',
-                        'errorMessage': error_msg,
-                        'recoverable': True
-                    })
+                    await self._send_pipeline_error(msg_id, result.get('message', 'Unknown error'))
                     # Exit loop after sending error
                     break
 
@@ -462,7 +517,7 @@ class AudioProcessor:
         # Only send no_speech if VAD confirms silence.
         if not speech_detected:
             # Check VAD state to confirm silence (ADR-009 requirement)
-            if not self.pipeline.is_in_speech() and not self.pipeline.has_buffered_speech():
+            if not pipeline.is_in_speech() and not pipeline.has_buffered_speech():
                 logger.debug(f"No speech detected (VAD confirmed silence) for {msg_id}")
                 await self.ipc.send_message({
                     'type': 'event',
@@ -478,8 +533,251 @@ class AudioProcessor:
                 # Rust's Receiver Task will keep waiting for next event (ADR-009)
                 logger.debug(f"Speech in progress (VAD active, no event yet) for {msg_id}")
 
+        await self._send_audio_ack(msg_id, len(audio_bytes) // 32, t_start)
         logger.info(f"Stream processing complete for request {msg_id}")
 
+    async def _send_final_text(self, msg_id: str, result: Dict[str, Any]) -> None:
+        """
+        Send a final_text event followed by speech_end.
+
+        Args:
+            msg_id: Request ID during which the speech ended
+            result: AudioPipeline final_text result
+        """
+        transcription = result['transcription']
+        # Task 11.1: Include latency_metrics for E2E validation
+        data = {
+            'requestId': msg_id,
+            'text': transcription['text'],
+            'is_final': True,  # STT-REQ-003.9
+            'confidence': transcription.get('confidence'),
+            'language': transcription.get('language'),
+            'processing_time_ms': transcription.get('processing_time_ms'),
+            'model_size': self.stt_engine.model_size
+        }
+        # Include latency_metrics if available (Task 11.1)
+        if 'latency_metrics' in result:
+            data['latency_metrics'] = result['latency_metrics']
+
+        await self.ipc.send_message({
+            'type': 'event',
+            'version': '1.0',
+            'eventType': 'final_text',
+            'data': data
+        })
+
+        # FIXED: Immediately send speech_end after final_text
+        # AudioPipeline._handle_speech_end() never returns speech_end event
+        # when STT engine is active (it returns final_text instead).
+        # We must derive and send speech_end here to satisfy the API contract.
+        await self.ipc.send_message({
+            'type': 'event',
+            'version': '1.0',
+            'eventType': 'speech_end',
+            'data': {
+                'requestId': msg_id,
+                'timestamp': int(time.time() * 1000)  # Current timestamp
+            }
+        })
+        logger.debug(f"Sent speech_end after final_text for {msg_id}")
+
+    async def _send_pipeline_error(self, msg_id: str, error_msg: str) -> None:
+        """
+        Report an AudioPipeline error for a request.
+
+        Args:
+            msg_id: Request ID (IpcMessage::Error requires the id field)
+            error_msg: Error description
+        """
+        logger.error(f"AudioPipeline error: {error_msg}")
+        await self.ipc.send_message({
+            'type': 'error',
+            'id': msg_id,
+            'version': '1.0',
+            'errorCode': 'AUDIO_PIPELINE_ERROR',
+            'errorMessage': error_msg,
+            'recoverable': True
+        })
+
+    async def enable_worker_pool(self, workers: int) -> None:
+        """
+        Run final transcription in a pool of worker processes (--workers N).
+
+        Each VAD segment goes to the next free worker; _emit_pending_finals
+        sends the results in segment order.
+
+        Args:
+            workers: Number of worker processes (at least 2)
+
+        Raises:
+            Exception: If the workers fail to start (the pool is stopped again)
+        """
+        pool = TranscriptionWorkerPool(
+            workers,
+            self.stt_engine.model_size,
+            offline_mode=self.stt_engine.offline_mode
+        )
+        try:
+            await pool.start()
+        except Exception:
+            pool.shutdown()
+            raise
+        self.final_pool = pool
+        for pipeline in self._pipelines():
+            pipeline.final_pool = pool
+        self._pending_finals = asyncio.Queue()
+        self._final_emitter = asyncio.create_task(self._emit_pending_finals())
+
+    async def use_cloud_engine(self, engine: CloudSTTEngine) -> None:
+        """
+        Transcribe with a cloud engine instead of the local model (--cloud-stt).
+
+        Args:
+            engine: Engine to initialize and hand to the pipeline
+        """
+        await engine.initialize()
+        self.stt_engine = engine
+        for pipeline in self._pipelines():
+            pipeline.stt_engine = engine
+        self.resource_monitor.stt_engine = engine
+
+    def _pipeline_for(self, request_id: str) -> AudioPipeline:
+        """
+        Pipeline of the request's recording session, created on its first audio.
+
+        Each session has its own VAD and speech buffer and shares the engine
+        (and worker pool). The least recently used pipeline is dropped once
+        more sessions than MAX_SESSION_PIPELINES were seen.
+        """
+        session = request_session(request_id)
+        if session is None:
+            return self.pipeline
+        pipeline = self.session_pipelines.get(session)
+        if pipeline is not None:
+            self.session_pipelines.move_to_end(session)
+            return pipeline
+
+        pipeline = AudioPipeline(
+            vad=VoiceActivityDetector(sample_rate=16000, aggressiveness=2),
+            stt_engine=self.stt_engine,
+            final_pool=self.final_pool
+        )
+        self.session_pipelines[session] = pipeline
+        while len(self.session_pipelines) > MAX_SESSION_PIPELINES:
+            dropped, _ = self.session_pipelines.popitem(last=False)
+            logger.info(f"Dropped pipeline of session {dropped}")
+        return pipeline
+
+    def _pipelines(self) -> List[AudioPipeline]:
+        """The default pipeline and those of the sessions."""
+        return [self.pipeline, *self.session_pipelines.values()]
+
+    async def _emit_pending_finals(self) -> None:
+        """
+        Send worker-pool final transcriptions in segment order.
+
+        A result that is ready early waits for the segments before it.
+        """
+        while True:
+            msg_id, pending = await self._pending_finals.get()
+            try:
+                result = await pending
+                if result.get('event') == 'final_text':
+                    await self._send_final_text(msg_id, result)
+                else:
+                    await self._send_pipeline_error(msg_id, result.get('error', 'Unknown error'))
+            except Exception as e:
+                logger.error(f"Failed to send final transcription: {e}", exc_info=True)
+            finally:
+                self._pending_finals.task_done()
+
+    async def _handle_flush(self, msg_id: str) -> None:
+        """
+        Transcribe the speech in progress and answer once its result is sent.
+
+        The speech so far is finalized without waiting for the VAD offset.
+        In worker-pool mode the answer also waits for every final still in
+        flight, so all results of the audio sent before the flush precede it.
+
+        Args:
+            msg_id: ID of the flush request
+        """
+        for pipeline in self._pipelines():
+            result = await pipeline.flush()
+            if not result:
+                continue
+            event_type = result.get('event')
+            if event_type == 'final_text':
+                await self._send_final_text(msg_id, result)
+            elif event_type == 'final_pending':
+                self._pending_finals.put_nowait((msg_id, result['pending']))
+            elif event_type == 'error':
+                await self._send_pipeline_error(msg_id, result.get('error', 'Unknown error'))
+
+        if self._pending_finals is not None:
+            await self._pending_finals.join()
+
+        await self.ipc.send_message({
+            'type': 'response',
+            'id': msg_id,
+            'version': '1.0',
+            'result': {'status': 'flushed', 'method': 'flush'}
+        })
+
+    async def _send_audio_ack(self, msg_id: str, processed_ms: int, t_start: float) -> None:
+        """
+        Acknowledge a finished process_audio_stream request (flow control).
+
+        Rust paces its audio batches by these acks: it slows down or holds
+        audio back while the acknowledged audio lags behind what it sent.
+
+        Args:
+            msg_id: Request ID (acks every earlier request too)
+            processed_ms: Audio in the request in milliseconds
+            t_start: perf_counter() at the start of processing
+        """
+        processing_ms = int((time.perf_counter() - t_start) * 1000)
+        if processed_ms > 0:
+            rtf = processing_ms / processed_ms
+            if self.real_time_factor is None:
+                self.real_time_factor = rtf
+            else:
+                # Exponential moving average over recent requests
+                self.real_time_factor += 0.2 * (rtf - self.real_time_factor)
+
+        await self.ipc.send_message({
+            'type': 'event',
+            'version': '1.0',
+            'eventType': 'audio_ack',
+            'data': {
+                'requestId': msg_id,
+                'processed_ms': processed_ms,
+                'buffered_ms': self._pipeline_for(msg_id).buffered_speech_ms(),
+                'processing_ms': processing_ms
+            }
+        })
+
+    def _health_snapshot(self) -> Dict[str, Any]:
+        """
+        Health metrics for the pong answering Rust's periodic ping.
+
+        Returns:
+            Dict with model_loaded, model, real_time_factor (None before the
+            first processed request), queue_depth_ms and memory_mb.
+        """
+        health = {
+            'model_loaded': self.stt_engine.model is not None,
+            'model': self.stt_engine.model_size,
+            'queue_depth_ms': sum(p.buffered_speech_ms() for p in self._pipelines()),
+        }
+        if self.real_time_factor is not None:
+            health['real_time_factor'] = round(self.real_time_factor, 3)
+        try:
+            health['memory_mb'] = int(self.resource_monitor.get_current_memory_usage() * 1024)
+        except Exception as e:
+            logger.debug(f"Memory usage unavailable: {e}")
+        return health
+
 
     async def _handle_model_downgrade(self, old_model: str, new_model: str) -> None:
         """
@@ -487,8 +785,9 @@ class AudioProcessor:
 
         This method:
         1. Triggers WhisperSTTEngine to load the new model
-        2. Updates ResourceMonitor's current_model (only on success)
-        3. Sends IPC notification to UI
+        2. Restarts the worker pool (if any) with that model
+        3. Updates ResourceMonitor's current_model (only on success)
+        4. Sends IPC notification to UI
 
         Args:
             old_model: Previous model size
@@ -502,7 +801,9 @@ class AudioProcessor:
         try:
             # Load new model in WhisperSTTEngine
             # Returns actual loaded model (may differ due to bundled fallback)
+            previous_model = self.stt_engine.model_size
             actual_model = await self.stt_engine.load_model(new_model)
+            await self._reload_worker_pool(actual_model, previous_model)
 
             # Update ResourceMonitor state with ACTUAL loaded model
             self.resource_monitor.current_model = actual_model
@@ -528,6 +829,26 @@ class AudioProcessor:
             # Re-raise to signal failure to monitoring loop
             raise
 
+    async def _reload_worker_pool(self, model_size: str, previous_model: str) -> None:
+        """
+        Restart the worker pool (--workers N) with the main engine's new model.
+
+        Args:
+            model_size: Model the main engine switched to
+            previous_model: Model it used before (restored if the pool fails)
+
+        Raises:
+            Exception: If the workers fail to load the model; the pool and the
+                main engine are back on previous_model
+        """
+        if not self.final_pool or model_size == self.final_pool.model_size:
+            return
+        try:
+            await self.final_pool.reload(model_size)
+        except Exception:
+            await self.stt_engine.load_model(previous_model)
+            raise
+
     async def _handle_upgrade_proposal(self, current_model: str, proposed_model: str) -> None:
         """
         Handle upgrade proposal callback from ResourceMonitor (Task 5.2, STT-REQ-006.10).
@@ -591,8 +912,10 @@ class AudioProcessor:
 
         This method:
         1. Loads the approved target model via WhisperSTTEngine
-        2. Updates ResourceMonitor's current_model (only on success)
-        3. Sends success/failure IPC notification to UI
+        2. Restarts the worker pool (if any) with that model; the response
+           is sent only once every worker has loaded it
+        3. Updates ResourceMonitor's current_model (only on success)
+        4. Sends success/failure IPC notification to UI
 
         Args:
             msg: IPC message containing 'target_model' field
@@ -627,7 +950,9 @@ class AudioProcessor:
         try:
             # Load new model in WhisperSTTEngine
             # Returns actual loaded model (may differ from target_model due to bundled fallback)
+            previous_model = self.stt_engine.model_size
             actual_model = await self.stt_engine.load_model(target_model)
+            await self._reload_worker_pool(actual_model, previous_model)
 
             # Update ResourceMonitor state with ACTUAL loaded model (STT-REQ-006.9/006.12)
             self.resource_monitor.current_model = actual_model
@@ -689,12 +1014,71 @@ class AudioProcessor:
                 })
 
 
+def parse_args(argv):
+    """
+    Parse sidecar command line arguments.
+
+    Args:
+        argv: Arguments without the program name
+
+    Returns:
+        argparse.Namespace with workers (1 = no worker pool) and the cloud
+        STT options (cloud_stt: off, fallback or always)
+    """
+    parser = argparse.ArgumentParser(description="Meeting Minutes Automator STT sidecar")
+    parser.add_argument(
+        '--workers',
+        type=int,
+        default=1,
+        help="Transcription worker processes for final results (1 = none)"
+    )
+    parser.add_argument(
+        '--cloud-stt',
+        choices=['off', 'fallback', 'always'],
+        default='off',
+        help="Cloud STT: when the local model can't be loaded, or instead of it"
+    )
+    parser.add_argument('--cloud-provider', choices=PROVIDERS, default='openai')
+    parser.add_argument('--cloud-endpoint', default=None, help="Base URL (required for azure)")
+    parser.add_argument('--cloud-model', default=None, help="Model or deployment name")
+    args = parser.parse_args(argv)
+    if args.workers < 1:
+        parser.error("--workers must be at least 1")
+    return args
+
+
+def create_cloud_engine(args) -> Optional[CloudSTTEngine]:
+    """
+    Cloud STT engine configured on the command line, if any.
+
+    Args:
+        args: parse_args() result
+
+    Returns:
+        CloudSTTEngine, or None when cloud STT is off or misconfigured
+        (e.g. no API key in the environment)
+    """
+    if args.cloud_stt == 'off':
+        return None
+    try:
+        return CloudSTTEngine(
+            args.cloud_provider,
+            os.environ.get(API_KEY_ENV, ''),
+            endpoint=args.cloud_endpoint,
+            model=args.cloud_model
+        )
+    except ValueError as e:
+        logger.error(f"Cloud STT disabled: {e}")
+        return None
+
+
 async def main():
     """
     Main entry point for Python sidecar process.
 
     Initializes AudioProcessor and IpcHandler, then starts IPC event loop.
     """
+    args = parse_args(sys.argv[1:])
     processor = None
     monitoring_task = None
 
@@ -746,26 +1130,47 @@ async def main():
             await processor.ipc.start()
             return
 
+        cloud_engine = create_cloud_engine(args)
+
         # CRITICAL: Initialize WhisperSTTEngine before sending ready signal
         # Without this, transcribe() will raise "WhisperSTTEngine not initialized"
-        # Decision: Fail fast on initialization errors (no graceful degradation)
-        logger.info("Initializing WhisperSTTEngine (may take a few seconds)...")
-        try:
-            await processor.stt_engine.initialize()
-            logger.info(f"WhisperSTTEngine initialized with model: {processor.stt_engine.model_size}")
-
-            # Sync ResourceMonitor.current_model after initialization
-            # (model_size may have changed due to bundled fallback)
-            # IMPORTANT: Keep initial_model unchanged - it represents the resource-based
-            # recommendation and serves as the upgrade ceiling (STT-REQ-006.10/006.12)
-            processor.resource_monitor.current_model = processor.stt_engine.model_size
-            logger.info(f"ResourceMonitor.current_model synced to: {processor.stt_engine.model_size}")
-            logger.info(f"ResourceMonitor.initial_model (upgrade ceiling): {processor.resource_monitor.initial_model}")
-        except Exception as e:
-            logger.error(f"Failed to initialize WhisperSTTEngine: {e}", exc_info=True)
-            logger.error("Cannot start sidecar without STT engine. Aborting.")
-            # Do NOT send ready signal - let Rust side detect failure
-            sys.exit(1)
+        # Decision: Fail fast on initialization errors, unless cloud STT is the fallback
+        if cloud_engine and args.cloud_stt == 'always':
+            # Opted in: the local model isn't loaded at all
+            logger.info(f"Using cloud STT ({cloud_engine.provider}) instead of the local model")
+            await processor.use_cloud_engine(cloud_engine)
+        else:
+            logger.info("Initializing WhisperSTTEngine (may take a few seconds)...")
+            try:
+                await processor.stt_engine.initialize()
+                logger.info(f"WhisperSTTEngine initialized with model: {processor.stt_engine.model_size}")
+
+                # Sync ResourceMonitor.current_model after initialization
+                # (model_size may have changed due to bundled fallback)
+                # IMPORTANT: Keep initial_model unchanged - it represents the resource-based
+                # recommendation and serves as the upgrade ceiling (STT-REQ-006.10/006.12)
+                processor.resource_monitor.current_model = processor.stt_engine.model_size
+                logger.info(f"ResourceMonitor.current_model synced to: {processor.stt_engine.model_size}")
+                logger.info(f"ResourceMonitor.initial_model (upgrade ceiling): {processor.resource_monitor.initial_model}")
+            except Exception as e:
+                logger.error(f"Failed to initialize WhisperSTTEngine: {e}", exc_info=True)
+                if cloud_engine is None:
+                    logger.error("Cannot start sidecar without STT engine. Aborting.")
+                    # Do NOT send ready signal - let Rust side detect failure
+                    sys.exit(1)
+                logger.warning(f"Falling back to cloud STT ({cloud_engine.provider})")
+                await processor.use_cloud_engine(cloud_engine)
+
+        local_engine = processor.stt_engine is not cloud_engine
+
+        # Worker-pool mode: final transcription in N processes (one model each)
+        if args.workers > 1 and local_engine:
+            logger.info(f"Starting {args.workers} transcription workers...")
+            try:
+                await processor.enable_worker_pool(args.workers)
+            except Exception as e:
+                # Slower, but transcription still works in this process
+                logger.error(f"Failed to start transcription workers, continuing without: {e}", exc_info=True)
 
         # Send ready signal to Rust backend (only after successful initialization)
         await processor.ipc.send_message({
@@ -774,15 +1179,17 @@ async def main():
         })
 
         # Start resource monitoring loop (Task 5.2, STT-REQ-006)
-        logger.info("Starting resource monitoring loop...")
-        monitoring_task = asyncio.create_task(
-            processor.resource_monitor.start_monitoring(
-                interval_seconds=30.0,  # STT-NFR-001.6
-                on_downgrade=processor._handle_model_downgrade,
-                on_upgrade_proposal=processor._handle_upgrade_proposal,
-                on_pause_recording=processor._handle_pause_recording
+        # Model switching doesn't apply to cloud STT
+        if local_engine:
+            logger.info("Starting resource monitoring loop...")
+            monitoring_task = asyncio.create_task(
+                processor.resource_monitor.start_monitoring(
+                    interval_seconds=30.0,  # STT-NFR-001.6
+                    on_downgrade=processor._handle_model_downgrade,
+                    on_upgrade_proposal=processor._handle_upgrade_proposal,
+                    on_pause_recording=processor._handle_pause_recording
+                )
             )
-        )
 
         logger.info("Starting IPC event loop...")
 
@@ -799,6 +1206,9 @@ async def main():
         sys.exit(1)
 
     finally:
+        if processor and processor.final_pool:
+            processor.final_pool.shutdown()
+
         # Cleanup: Stop monitoring loop
         if processor and processor.resource_monitor:
             logger.info("Stopping resource monitoring loop...")
@@ -87,7 +87,8 @@ class AudioPipeline:
         self,
         vad=None,
         stt_engine=None,
-        sample_rate: int = 16000
+        sample_rate: int = 16000,
+        final_pool=None
     ):
         """
         Initialize audio pipeline.
@@ -96,10 +97,13 @@ class AudioPipeline:
             vad: Voice Activity Detector instance (optional)
             stt_engine: STT Engine instance (optional)
             sample_rate: Audio sample rate in Hz
+            final_pool: TranscriptionWorkerPool for final transcription (optional)
         """
         self.vad = vad
         self.stt_engine = stt_engine
         self.sample_rate = sample_rate
+        # Worker-pool mode: final transcription runs in worker processes
+        self.final_pool = final_pool
 
         # Pipeline state
         self._running = False
@@ -309,7 +313,8 @@ class AudioPipeline:
             timestamp_ms: VAD detection timestamp for latency measurement (Task 11.1)
 
         Returns:
-            Final transcription result or None
+            Final transcription result or None. In worker-pool mode a
+            'final_pending' event whose 'pending' task resolves to the result.
         """
         # Task 11.1: Record VAD speech_end detection timestamp
         self._speech_end_timestamp_ms = timestamp_ms if timestamp_ms else int(time.time() * 1000)
@@ -329,6 +334,21 @@ class AudioPipeline:
                 logger.warning("No audio data in segment")
                 return None
 
+            if self.final_pool:
+                # Transcribed by the next free worker; VAD continues meanwhile
+                pending = asyncio.ensure_future(self._complete_pooled_final(
+                    self.final_pool.submit(audio_data, self.sample_rate),
+                    segment_data,
+                    self._speech_end_timestamp_ms
+                ))
+                self._current_speech_buffer = bytearray()
+                self._speech_start_time = None
+                self._last_partial_time = None
+                return {
+                    'event': 'final_pending',
+                    'pending': pending
+                }
+
             # Transcribe with STT engine (final)
             start_time = time.time()
 
@@ -344,48 +364,93 @@ class AudioPipeline:
             if isinstance(transcription, dict):
                 transcription['processing_time_ms'] = processing_time_ms
 
-            # Task 11.1: Calculate end-to-end latency (VAD speech_end → final_text delivery)
-            # Target: < 2000ms (STT-NFR-001 implied requirement)
-            current_time_ms = int(time.time() * 1000)
-            end_to_end_latency_ms = current_time_ms - self._speech_end_timestamp_ms
-
-            # Update statistics
-            self.stats["transcriptions_generated"] += 1
-
             # Reset state
             self._current_speech_buffer = bytearray()
             self._speech_start_time = None
             self._last_partial_time = None
 
-            # Task 11.1: Log latency metrics (structured logging for analysis)
-            logger.info(
-                f"Final transcription generated: "
-                f"text='{transcription.get('text', '')[:50]}...', "
-                f"whisper_time={processing_time_ms}ms, "
-                f"end_to_end_latency={end_to_end_latency_ms}ms "
-                f"(target: <2000ms, {'✅ PASS' if end_to_end_latency_ms < 2000 else '❌ FAIL'})"
+            return self._final_result(
+                transcription, processing_time_ms, segment_data, self._speech_end_timestamp_ms
             )
 
+        except Exception as e:
+            self.stats["errors"] += 1
+            logger.error(f"Failed to generate final transcription: {e}")
             return {
-                'event': 'final_text',
-                'transcription': transcription,
-                'segment': segment_data,
-                'latency_metrics': {
-                    'whisper_processing_ms': processing_time_ms,
-                    'end_to_end_latency_ms': end_to_end_latency_ms,
-                    'vad_speech_end_timestamp_ms': self._speech_end_timestamp_ms,
-                    'delivery_timestamp_ms': current_time_ms
-                }
+                'event': 'error',
+                'error': str(e),
+                'segment': segment_data
             }
 
+    async def _complete_pooled_final(
+        self,
+        transcription_future: asyncio.Future,
+        segment_data: Dict,
+        speech_end_timestamp_ms: int
+    ) -> Dict[str, Any]:
+        """
+        Wait for a worker's final transcription (worker-pool mode).
+
+        Args:
+            transcription_future: Future from TranscriptionWorkerPool.submit()
+            segment_data: Segment data from VAD
+            speech_end_timestamp_ms: VAD speech_end detection time of the segment
+
+        Returns:
+            Final transcription result, or an error event
+        """
+        try:
+            transcription = await transcription_future
         except Exception as e:
             self.stats["errors"] += 1
-            logger.error(f"Failed to generate final transcription: {e}")
+            logger.error(f"Worker failed to generate final transcription: {e}")
             return {
                 'event': 'error',
                 'error': str(e),
                 'segment': segment_data
             }
+        # Whisper time in the worker (excludes waiting for a free worker)
+        processing_time_ms = transcription.get('processing_time_ms', 0)
+        return self._final_result(
+            transcription, processing_time_ms, segment_data, speech_end_timestamp_ms
+        )
+
+    def _final_result(
+        self,
+        transcription: Dict[str, Any],
+        processing_time_ms: int,
+        segment_data: Dict,
+        speech_end_timestamp_ms: int
+    ) -> Dict[str, Any]:
+        """Build the final_text event and log its latency."""
+        # Task 11.1: Calculate end-to-end latency (VAD speech_end → final_text delivery)
+        # Target: < 2000ms (STT-NFR-001 implied requirement)
+        current_time_ms = int(time.time() * 1000)
+        end_to_end_latency_ms = current_time_ms - speech_end_timestamp_ms
+
+        # Update statistics
+        self.stats["transcriptions_generated"] += 1
+
+        # Task 11.1: Log latency metrics (structured logging for analysis)
+        logger.info(
+            f"Final transcription generated: "
+            f"text='{transcription.get('text', '')[:50]}...', "
+            f"whisper_time={processing_time_ms}ms, "
+            f"end_to_end_latency={end_to_end_latency_ms}ms "
+            f"(target: <2000ms, {'✅ PASS' if end_to_end_latency_ms < 2000 else '❌ FAIL'})"
+        )
+
+        return {
+            'event': 'final_text',
+            'transcription': transcription,
+            'segment': segment_data,
+            'latency_metrics': {
+                'whisper_processing_ms': processing_time_ms,
+                'end_to_end_latency_ms': end_to_end_latency_ms,
+                'vad_speech_end_timestamp_ms': speech_end_timestamp_ms,
+                'delivery_timestamp_ms': current_time_ms
+            }
+        }
 
     async def _generate_partial_transcription(self) -> Optional[Dict[str, Any]]:
         """
@@ -397,6 +462,10 @@ class AudioPipeline:
         if not self.stt_engine or not self._current_speech_buffer:
             return None
 
+        # Cloud engines without a streaming API transcribe finals only
+        if not getattr(self.stt_engine, 'supports_partials', True):
+            return None
+
         try:
             # Convert buffer to bytes
             audio_data = bytes(self._current_speech_buffer)
@@ -513,6 +582,41 @@ class AudioPipeline:
         self._running = False
         logger.info("Pipeline stop requested")
 
+    async def flush(self) -> Optional[Dict[str, Any]]:
+        """
+        Transcribe the speech in progress without waiting for its offset.
+
+        Returns:
+            Final transcription result (as from a VAD speech_end, so a
+            'final_pending' event in worker-pool mode), or None if no speech
+            is in progress
+        """
+        if not self.vad:
+            return None
+
+        vad_result = self.vad.finish_segment()
+        if not vad_result:
+            self._current_speech_buffer = bytearray()
+            return None
+        return await self._handle_speech_end(
+            vad_result.get('segment', {}),
+            vad_result.get('timestamp_ms')
+        )
+
+    def cancel(self) -> None:
+        """
+        Drop the speech in progress without transcribing it.
+
+        Segments already handed to workers (worker-pool mode) still complete.
+        """
+        if self.vad:
+            self.vad.reset()
+        self._current_speech_buffer = bytearray()
+        self._speech_start_time = None
+        self._last_partial_time = None
+        self._frame_count_since_partial = 0
+        logger.info("Speech in progress cancelled")
+
     def get_stats(self) -> Dict[str, Any]:
         """Get pipeline statistics."""
         return self.stats.copy()
@@ -536,10 +640,26 @@ class AudioPipeline:
         Check if there are speech frames buffered for STT processing.
         
         Returns:
-            bool: True if speech buffer contains audio data, False otherwise.
+            bool: True if speech buffer contains audio data (or, in
+            worker-pool mode, segments await final transcription), False otherwise.
         
         Requirements:
             - ADR-009: VAD-based no_speech detection
             - Prevents false no_speech when frames are queued for STT
         """
-        return len(self._current_speech_buffer) > 0
+        pooled = self.final_pool is not None and self.final_pool.pending > 0
+        return len(self._current_speech_buffer) > 0 or pooled
+
+    def buffered_speech_ms(self) -> int:
+        """
+        Duration of the speech audio buffered for STT processing.
+
+        Returns:
+            int: Buffered speech in milliseconds (16kHz mono 16-bit PCM),
+            including segments waiting for a worker in worker-pool mode.
+
+        Requirements:
+            - Reported to Rust in audio_ack events (flow control)
+        """
+        pooled_ms = self.final_pool.pending_ms if self.final_pool else 0
+        return len(self._current_speech_buffer) // 32 + pooled_ms
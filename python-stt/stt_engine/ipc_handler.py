@@ -11,6 +11,7 @@ Requirements:
 
 import asyncio
 import json
+import socket
 import sys
 import logging
 from typing import Dict, Any, Optional, Callable, Awaitable
@@ -45,6 +46,9 @@ class IpcHandler:
     # Protocol version (STT-REQ-007.2)
     PROTOCOL_VERSION = "1.0"
 
+    # Protocol versions this sidecar speaks, oldest first (hello handshake)
+    SUPPORTED_PROTOCOL_VERSIONS = ("1.0",)
+
     # Buffer limits to prevent overflow
     MAX_MESSAGE_SIZE = 1024 * 1024  # 1MB max per message
     READ_BUFFER_SIZE = 8192  # 8KB chunks
@@ -70,6 +74,10 @@ class IpcHandler:
         self._reader: Optional[StreamReader] = None
         self._writer: Optional[StreamWriter] = None
         self._buffer = bytearray()
+        # Binary audio channel (open_audio_channel); None = audio on stdin
+        self._audio_channel = None
+        # Version agreed in the hello handshake (PROTOCOL_VERSION until then)
+        self.protocol_version = self.PROTOCOL_VERSION
 
         # Statistics for monitoring
         self.stats = {
@@ -95,7 +103,7 @@ class IpcHandler:
         try:
             # Add protocol version if not present (STT-REQ-007.2)
             if "version" not in message:
-                message["version"] = self.PROTOCOL_VERSION
+                message["version"] = self.protocol_version
 
             # Add timestamp for latency tracking
             if "timestamp" not in message:
@@ -156,9 +164,9 @@ class IpcHandler:
             # Log version mismatch as warning but continue (STT-REQ-007.3)
             if "version" in message:
                 msg_version = message["version"]
-                if msg_version != self.PROTOCOL_VERSION:
+                if msg_version != self.protocol_version:
                     logger.warning(
-                        f"Protocol version mismatch: received {msg_version}, expected {self.PROTOCOL_VERSION}"
+                        f"Protocol version mismatch: received {msg_version}, expected {self.protocol_version}"
                     )
 
             self.stats["messages_received"] += 1
@@ -200,6 +208,102 @@ class IpcHandler:
         # Decode and strip newline
         return line_bytes.decode('utf-8').rstrip('\n\r')
 
+    def negotiate_version(self, offered: Any) -> Optional[str]:
+        """
+        Pick the protocol version for a hello request from Rust.
+
+        Args:
+            offered: Versions Rust supports (list of "major.minor" strings)
+
+        Returns:
+            The highest version both sides support (now used for outgoing
+            messages), or None when there is none
+        """
+        def key(version: str):
+            major, minor = version.split(".")[:2]
+            return int(major), int(minor)
+
+        common = [
+            version for version in self.SUPPORTED_PROTOCOL_VERSIONS
+            if isinstance(offered, list) and version in offered
+        ]
+        if not common:
+            return None
+        self.protocol_version = max(common, key=key)
+        logger.info(f"Protocol version negotiated: {self.protocol_version}")
+        return self.protocol_version
+
+    def open_audio_channel(self, kind: str, path: str) -> None:
+        """
+        Connect to the binary audio channel offered by Rust.
+
+        Raw PCM of process_audio_stream requests carrying `audio_bytes`
+        then arrives on this channel instead of stdin.
+
+        Args:
+            kind: "unix_socket" or "named_pipe"
+            path: Socket path or pipe name
+
+        Raises:
+            IpcProtocolError: If the kind is unsupported or connecting fails
+        """
+        try:
+            if kind == "unix_socket" and hasattr(socket, "AF_UNIX"):
+                sock = socket.socket(socket.AF_UNIX, socket.SOCK_STREAM)
+                sock.connect(path)
+                channel = sock.makefile("rb", buffering=0)
+                # The file object keeps the connection open
+                sock.close()
+            elif kind == "named_pipe" and sys.platform == "win32":
+                channel = open(path, "rb", buffering=0)
+            else:
+                raise IpcProtocolError(f"Unsupported audio channel: {kind}")
+        except OSError as e:
+            raise IpcProtocolError(f"Failed to connect audio channel: {e}") from e
+
+        self.close_audio_channel()
+        self._audio_channel = channel
+        logger.info(f"Audio channel open: {kind}")
+
+    def close_audio_channel(self) -> None:
+        """Close the binary audio channel (audio falls back to stdin)."""
+        if self._audio_channel is not None:
+            try:
+                self._audio_channel.close()
+            except OSError:
+                pass
+            self._audio_channel = None
+
+    async def read_audio(self, size: int) -> bytes:
+        """
+        Read exactly `size` bytes of PCM from the binary audio channel.
+
+        Raises:
+            IpcProtocolError: If no channel is open or it closed mid-batch
+                (the channel is then closed; Rust falls back to stdin)
+        """
+        channel = self._audio_channel
+        if channel is None:
+            raise IpcProtocolError("No audio channel open")
+
+        def read_exact() -> bytes:
+            data = bytearray()
+            while len(data) < size:
+                chunk = channel.read(size - len(data))
+                if not chunk:
+                    break
+                data.extend(chunk)
+            return bytes(data)
+
+        loop = asyncio.get_event_loop()
+        data = await loop.run_in_executor(None, read_exact)
+        if len(data) < size:
+            self.close_audio_channel()
+            raise IpcProtocolError(
+                f"Audio channel closed after {len(data)} of {size} bytes"
+            )
+        return data
+
     async def start(self) -> None:
         """
         Start the IPC handler event loop.
@@ -1,9 +1,11 @@
 """
 Transcription module for audio-to-text conversion.
 
-This module contains the WhisperSTTEngine implementation using faster-whisper.
+This module contains the WhisperSTTEngine implementation using faster-whisper
+and CloudSTTEngine, a cloud provider behind the same interface.
 """
 
 from .whisper_client import WhisperSTTEngine, ModelSize
+from .cloud_client import CloudSTTEngine
 
-__all__ = ["WhisperSTTEngine", "ModelSize"]
+__all__ = ["WhisperSTTEngine", "ModelSize", "CloudSTTEngine"]
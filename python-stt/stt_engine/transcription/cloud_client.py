@@ -0,0 +1,547 @@
+"""
+CloudSTTEngine: cloud speech-to-text with the WhisperSTTEngine interface.
+
+Used instead of the local model when the user opts in (`--cloud-stt always`)
+or when the local model can't be loaded (`--cloud-stt fallback`). The
+AudioPipeline drives it like the local engine: every partial interval it
+passes the speech so far (partial_text), and the whole segment once speech
+ends (final_text).
+
+Streaming providers keep one WebSocket per utterance open across those
+calls and send only the audio added since the previous call, so every
+second of speech is uploaded once: partials are the provider's interim
+results, the final is what it settles on after the stream is closed.
+Providers without a streaming API transcribe the final segment only (one
+upload per segment) and produce no partials.
+
+Providers:
+- openai: OpenAI audio transcriptions API (whisper-1 by default), finals only
+- azure: Azure OpenAI realtime transcription, streaming (a
+  gpt-4o-mini-transcribe deployment by default, under the configured endpoint)
+- deepgram: Deepgram live streaming API (nova-2 by default)
+
+The API key is passed in the MMA_CLOUD_STT_API_KEY environment variable
+(never on the command line).
+"""
+
+import asyncio
+import base64
+import io
+import json
+import logging
+import math
+import time
+import urllib.error
+import urllib.parse
+import urllib.request
+import uuid
+import wave
+from typing import Any, Awaitable, Callable, Dict, List, Optional, Tuple
+
+import numpy as np
+
+from stt_engine.transcription import websocket_client
+
+logger = logging.getLogger(__name__)
+
+API_KEY_ENV = "MMA_CLOUD_STT_API_KEY"
+
+PROVIDERS = ("openai", "azure", "deepgram")
+
+# Providers transcribed over a WebSocket (partials and finals)
+STREAMING_PROVIDERS = ("azure", "deepgram")
+
+DEFAULT_MODELS = {
+    "openai": "whisper-1",
+    "azure": "gpt-4o-mini-transcribe",
+    "deepgram": "nova-2",
+}
+
+AZURE_REALTIME_API_VERSION = "2025-04-01-preview"
+
+# The realtime API takes 24kHz 16-bit mono PCM
+AZURE_REALTIME_SAMPLE_RATE = 24000
+
+# Per request; a long final segment takes a while to upload
+REQUEST_TIMEOUT_SECONDS = 30
+
+# Time a partial waits for the provider's answer to the audio just sent
+PARTIAL_WAIT_SECONDS = 0.5
+
+# Time the final waits for the provider to settle after the stream ends
+FINAL_TIMEOUT_SECONDS = 10
+
+
+def _wav_bytes(audio_data: bytes, sample_rate: int) -> bytes:
+    """Wrap 16-bit mono PCM in a WAV container."""
+    buffer = io.BytesIO()
+    with wave.open(buffer, 'wb') as wav:
+        wav.setnchannels(1)
+        wav.setsampwidth(2)
+        wav.setframerate(sample_rate)
+        wav.writeframes(audio_data)
+    return buffer.getvalue()
+
+
+def _multipart(fields: Dict[str, str], wav: bytes) -> Tuple[bytes, str]:
+    """Encode form fields and the audio file as multipart/form-data."""
+    boundary = uuid.uuid4().hex
+    parts = []
+    for name, value in fields.items():
+        parts.append(
+            f'--{boundary}\r\nContent-Disposition: form-data; name="{name}"\r\n\r\n{value}\r\n'
+            .encode('utf-8')
+        )
+    parts.append(
+        f'--{boundary}\r\nContent-Disposition: form-data; name="file"; filename="audio.wav"\r\n'
+        f'Content-Type: audio/wav\r\n\r\n'.encode('utf-8')
+    )
+    parts.append(wav)
+    parts.append(f'\r\n--{boundary}--\r\n'.encode('utf-8'))
+    return b''.join(parts), f'multipart/form-data; boundary={boundary}'
+
+
+def _resample(audio_data: bytes, from_rate: int, to_rate: int) -> bytes:
+    """Linear resampling of 16-bit mono PCM."""
+    if from_rate == to_rate or not audio_data:
+        return audio_data
+    samples = np.frombuffer(audio_data, dtype='<i2').astype(np.float32)
+    count = len(samples) * to_rate // from_rate
+    positions = np.arange(count) * (from_rate / to_rate)
+    resampled = np.interp(positions, np.arange(len(samples)), samples)
+    return np.round(resampled).astype('<i2').tobytes()
+
+
+def _websocket_url(base: str) -> str:
+    """https:// → wss://, http:// → ws:// (other schemes unchanged)."""
+    if base.startswith('https://'):
+        return 'wss://' + base[len('https://'):]
+    if base.startswith('http://'):
+        return 'ws://' + base[len('http://'):]
+    return base
+
+
+def _join(parts: List[str], language: str) -> str:
+    """Join transcript pieces (no spaces between Japanese or Chinese text)."""
+    separator = '' if language in ('ja', 'zh') else ' '
+    return separator.join(part for part in parts if part).strip()
+
+
+class _StreamingSession:
+    """
+    One utterance streamed to a provider.
+
+    A background task reads the provider's messages; `sent` is the audio
+    uploaded so far, so the next call only sends what was added.
+    """
+
+    def __init__(self, ws, language: str):
+        self.sent = bytearray()
+        self.language = language
+        self.error: Optional[str] = None
+        self._ws = ws
+        self._updated = asyncio.Event()
+        self._receiver = asyncio.create_task(self._receive())
+
+    def continues(self, audio_data: bytes) -> bool:
+        """Whether `audio_data` extends the audio sent so far (same utterance)."""
+        return audio_data.startswith(self.sent)
+
+    async def send(self, audio_data: bytes, sample_rate: int) -> None:
+        """Upload the part of `audio_data` not sent yet."""
+        added = audio_data[len(self.sent):]
+        if added:
+            await self._send_audio(added, sample_rate)
+            self.sent.extend(added)
+
+    async def partial(self, wait: float) -> Tuple[str, float]:
+        """Transcript so far, after up to `wait` seconds for an update."""
+        self._updated.clear()
+        try:
+            await asyncio.wait_for(self._updated.wait(), wait)
+        except asyncio.TimeoutError:
+            pass
+        self._raise_error()
+        return self.transcript()
+
+    async def finish(self, timeout: float) -> Tuple[str, float]:
+        """End the stream and return the provider's settled transcript."""
+        try:
+            await self._end_stream()
+            await asyncio.wait_for(self._settled(), timeout)
+        finally:
+            await self.close()
+        self._raise_error()
+        return self.transcript()
+
+    async def close(self) -> None:
+        self._receiver.cancel()
+        await self._ws.close()
+
+    async def _receive(self) -> None:
+        try:
+            while True:
+                message = await self._ws.recv()
+                if isinstance(message, str):
+                    self._handle(json.loads(message))
+                    self._updated.set()
+        except websocket_client.WebSocketClosed:
+            pass
+        except Exception as e:
+            self.error = str(e)
+        finally:
+            self._updated.set()
+
+    def _raise_error(self) -> None:
+        if self.error:
+            raise RuntimeError(self.error)
+
+    async def _settled(self) -> None:
+        """Resolves once every result of the ended stream has arrived."""
+        await asyncio.shield(self._receiver)
+
+    async def _send_audio(self, audio_data: bytes, sample_rate: int) -> None:
+        raise NotImplementedError
+
+    async def _end_stream(self) -> None:
+        raise NotImplementedError
+
+    def _handle(self, message: Dict[str, Any]) -> None:
+        raise NotImplementedError
+
+    def transcript(self) -> Tuple[str, float]:
+        """(text, confidence) of what was recognized so far."""
+        raise NotImplementedError
+
+
+class _DeepgramStream(_StreamingSession):
+    """
+    Deepgram live streaming: raw PCM in binary messages, interim and final
+    results as JSON; CloseStream makes it send the rest and close.
+    """
+
+    def __init__(self, ws, language: str):
+        super().__init__(ws, language)
+        self._finals: List[str] = []
+        self._confidences: List[float] = []
+        self._interim = ''
+
+    async def _send_audio(self, audio_data: bytes, sample_rate: int) -> None:
+        await self._ws.send_binary(audio_data)
+
+    async def _end_stream(self) -> None:
+        await self._ws.send_text(json.dumps({'type': 'CloseStream'}))
+
+    def _handle(self, message: Dict[str, Any]) -> None:
+        if message.get('type') != 'Results':
+            return
+        alternative = message['channel']['alternatives'][0]
+        transcript = alternative.get('transcript', '').strip()
+        if message.get('is_final'):
+            if transcript:
+                self._finals.append(transcript)
+                self._confidences.append(float(alternative.get('confidence', 0.0)))
+            self._interim = ''
+        else:
+            self._interim = transcript
+
+    def transcript(self) -> Tuple[str, float]:
+        confidence = (
+            sum(self._confidences) / len(self._confidences) if self._confidences else 0.0
+        )
+        return _join(self._finals + [self._interim], self.language), confidence
+
+
+class _AzureRealtimeStream(_StreamingSession):
+    """
+    Azure OpenAI realtime transcription: base64 PCM in
+    input_audio_buffer.append events. The server commits the buffer at
+    pauses (server VAD) and transcribes each committed item; ending the
+    stream commits the rest. No confidence is reported (0.0).
+    """
+
+    def __init__(self, ws, language: str):
+        super().__init__(ws, language)
+        self._items: List[str] = []
+        self._texts: Dict[str, str] = {}
+        self._pending = set()
+        self._ended = False
+        self._last_committed = asyncio.Event()
+
+    async def configure(self, model: str) -> None:
+        await self._ws.send_text(json.dumps({
+            'type': 'transcription_session.update',
+            'session': {
+                'input_audio_format': 'pcm16',
+                'input_audio_transcription': {'model': model, 'language': self.language},
+                'turn_detection': {'type': 'server_vad'},
+            },
+        }))
+
+    async def _send_audio(self, audio_data: bytes, sample_rate: int) -> None:
+        pcm = _resample(audio_data, sample_rate, AZURE_REALTIME_SAMPLE_RATE)
+        await self._ws.send_text(json.dumps({
+            'type': 'input_audio_buffer.append',
+            'audio': base64.b64encode(pcm).decode('ascii'),
+        }))
+
+    async def _end_stream(self) -> None:
+        self._ended = True
+        await self._ws.send_text(json.dumps({'type': 'input_audio_buffer.commit'}))
+
+    def _handle(self, message: Dict[str, Any]) -> None:
+        event = message.get('type', '')
+        item_id = message.get('item_id')
+        if event == 'input_audio_buffer.committed':
+            self._items.append(item_id)
+            self._texts.setdefault(item_id, '')
+            self._pending.add(item_id)
+            if self._ended:
+                self._last_committed.set()
+        elif event == 'conversation.item.input_audio_transcription.delta':
+            self._texts[item_id] = self._texts.get(item_id, '') + message.get('delta', '')
+        elif event == 'conversation.item.input_audio_transcription.completed':
+            self._texts[item_id] = message.get('transcript', '').strip()
+            self._pending.discard(item_id)
+        elif event == 'conversation.item.input_audio_transcription.failed':
+            self._pending.discard(item_id)
+            self.error = (message.get('error') or {}).get('message', 'Transcription failed')
+        elif event == 'error':
+            error = message.get('error') or {}
+            if self._ended and error.get('code') == 'input_audio_buffer_commit_empty':
+                # The server had already committed everything
+                self._last_committed.set()
+            else:
+                self.error = error.get('message', 'Realtime API error')
+
+    async def _settled(self) -> None:
+        while not (self._last_committed.is_set() and not self._pending) and not self.error:
+            if self._receiver.done():
+                raise RuntimeError("azure closed the stream before the transcript was complete")
+            self._updated.clear()
+            await self._updated.wait()
+
+    def transcript(self) -> Tuple[str, float]:
+        return _join([self._texts[item_id] for item_id in self._items], self.language), 0.0
+
+
+class CloudSTTEngine:
+    """
+    Transcription by a cloud provider.
+
+    Attributes:
+        provider: "openai", "azure" or "deepgram"
+        model_size: Engine name reported in health checks ("cloud:<provider>")
+        model: Truthy once initialized (mirrors WhisperSTTEngine.model)
+        offline_mode: Always False
+        supports_partials: Whether partial transcription is available
+            (streaming providers only; AudioPipeline skips it otherwise)
+    """
+
+    def __init__(
+        self,
+        provider: str,
+        api_key: str,
+        endpoint: Optional[str] = None,
+        model: Optional[str] = None,
+        language: str = "ja",
+        opener: Optional[Any] = None,
+        connector: Optional[Callable[[str, Dict[str, str]], Awaitable[Any]]] = None
+    ):
+        """
+        Args:
+            provider: One of PROVIDERS
+            api_key: Provider API key
+            endpoint: Base URL (required for azure; overrides the default otherwise)
+            model: Model (azure: deployment) name; provider default if None
+            language: Language hint
+            opener: Callable(request, timeout) -> response, instead of urlopen (testing)
+            connector: Coroutine(url, headers) -> WebSocket, instead of
+                websocket_client.connect (testing)
+        """
+        if provider not in PROVIDERS:
+            raise ValueError(f"Unknown cloud STT provider: {provider}")
+        if not api_key:
+            raise ValueError("Cloud STT needs an API key")
+        if provider == "azure" and not endpoint:
+            raise ValueError("Azure cloud STT needs an endpoint")
+        self.provider = provider
+        self.api_key = api_key
+        self.endpoint = endpoint.rstrip('/') if endpoint else None
+        self.cloud_model = model or DEFAULT_MODELS[provider]
+        self.language = language
+        self.model_size = f"cloud:{provider}"
+        self.model = None
+        self.offline_mode = False
+        self.supports_partials = provider in STREAMING_PROVIDERS
+        self._opener = opener or urllib.request.urlopen
+        self._connector = connector or websocket_client.connect
+        # Stream of the utterance in progress (streaming providers)
+        self._stream: Optional[_StreamingSession] = None
+
+    async def initialize(self) -> None:
+        """Nothing to load; the first request checks the key."""
+        self.model = self.cloud_model
+        logger.info(f"CloudSTTEngine ready: provider={self.provider}, model={self.cloud_model}")
+
+    async def load_model(self, new_model_size: str) -> str:
+        """Model switching (resource monitor) applies to local models only."""
+        raise RuntimeError("Cloud STT does not switch models")
+
+    def _build_request(self, wav: bytes) -> urllib.request.Request:
+        """Transcription request of a non-streaming provider (openai)."""
+        body, content_type = _multipart({
+            'model': self.cloud_model,
+            'language': self.language,
+            'response_format': 'verbose_json',
+        }, wav)
+        base = self.endpoint or "https://api.openai.com"
+        return urllib.request.Request(
+            f"{base}/v1/audio/transcriptions",
+            data=body,
+            headers={'Authorization': f"Bearer {self.api_key}", 'Content-Type': content_type},
+            method='POST'
+        )
+
+    def _parse_response(self, payload: Dict[str, Any]) -> Tuple[str, float, str]:
+        """Return (text, confidence, language) of a provider response."""
+        # verbose_json: same confidence as the local engine (avg_logprob)
+        segments = payload.get('segments') or []
+        if segments:
+            avg_logprob = sum(s.get('avg_logprob', 0.0) for s in segments) / len(segments)
+            confidence = min(1.0, max(0.0, math.exp(avg_logprob)))
+        else:
+            confidence = 0.0
+        return payload.get('text', '').strip(), confidence, payload.get('language') or self.language
+
+    def _post(self, wav: bytes) -> Dict[str, Any]:
+        request = self._build_request(wav)
+        try:
+            with self._opener(request, timeout=REQUEST_TIMEOUT_SECONDS) as response:
+                return json.loads(response.read().decode('utf-8'))
+        except urllib.error.HTTPError as e:
+            # The body says why (bad key, quota, unsupported audio)
+            detail = e.read().decode('utf-8', errors='replace')[:200]
+            raise RuntimeError(f"{self.provider} returned HTTP {e.code}: {detail}") from e
+
+    async def _open_stream(self, sample_rate: int) -> _StreamingSession:
+        """Open a WebSocket for a new utterance."""
+        if self.provider == "deepgram":
+            query = urllib.parse.urlencode({
+                'model': self.cloud_model,
+                'language': self.language,
+                'punctuate': 'true',
+                'interim_results': 'true',
+                'encoding': 'linear16',
+                'sample_rate': sample_rate,
+                'channels': 1,
+            })
+            base = _websocket_url(self.endpoint or "wss://api.deepgram.com")
+            url = f"{base}/v1/listen?{query}"
+            headers = {'Authorization': f"Token {self.api_key}"}
+        else:
+            query = urllib.parse.urlencode({
+                'api-version': AZURE_REALTIME_API_VERSION,
+                'intent': 'transcription',
+                'deployment': self.cloud_model,
+            })
+            url = f"{_websocket_url(self.endpoint)}/openai/realtime?{query}"
+            headers = {'api-key': self.api_key}
+
+        try:
+            ws = await self._connector(url, headers)
+        except websocket_client.HandshakeError as e:
+            raise RuntimeError(f"{self.provider} returned {e}") from e
+        if self.provider == "deepgram":
+            return _DeepgramStream(ws, self.language)
+        stream = _AzureRealtimeStream(ws, self.language)
+        await stream.configure(self.cloud_model)
+        return stream
+
+    async def _transcribe_streaming(
+        self,
+        audio_data: bytes,
+        sample_rate: int,
+        is_final: bool
+    ) -> Tuple[str, float]:
+        """
+        Send the audio added since the last call of this utterance.
+
+        A call whose audio doesn't extend the stream's (a new utterance, or
+        the last one was cancelled) starts a new stream. The final ends it.
+        """
+        stream = self._stream
+        self._stream = None
+        if stream and not stream.continues(audio_data):
+            await stream.close()
+            stream = None
+        if stream is None:
+            stream = await self._open_stream(sample_rate)
+
+        try:
+            await stream.send(audio_data, sample_rate)
+            if is_final:
+                return await stream.finish(FINAL_TIMEOUT_SECONDS)
+            result = await stream.partial(PARTIAL_WAIT_SECONDS)
+        except BaseException:
+            await stream.close()
+            raise
+        self._stream = stream
+        return result
+
+    async def transcribe(self, audio_data: bytes, sample_rate: int = 16000, is_final: bool = False) -> dict:
+        """
+        Transcribe audio with the provider (same result as WhisperSTTEngine.transcribe).
+
+        Args:
+            audio_data: Raw audio data as bytes (16-bit PCM)
+            sample_rate: Audio sample rate (default: 16000 Hz)
+            is_final: Whether this is a final transcription or partial
+
+        Returns:
+            dict: text, confidence, language, is_final, processing_time_ms
+            (plus error on failure)
+
+        Raises:
+            RuntimeError: If not initialized
+        """
+        if self.model is None:
+            raise RuntimeError("CloudSTTEngine not initialized. Call initialize() first.")
+
+        start_time = time.time()
+        result = {
+            "text": "",
+            "confidence": 0.0,
+            "language": self.language,
+            "is_final": is_final,
+        }
+
+        if not audio_data:
+            logger.warning("Empty audio data received")
+            result["processing_time_ms"] = 0
+            result["error"] = "INVALID_AUDIO"
+            return result
+
+        if not is_final and not self.supports_partials:
+            # Would upload the speech so far again; finals only
+            result["processing_time_ms"] = 0
+            return result
+
+        try:
+            if self.provider in STREAMING_PROVIDERS:
+                text, confidence = await self._transcribe_streaming(audio_data, sample_rate, is_final)
+                language = self.language
+            else:
+                payload = await asyncio.to_thread(self._post, _wav_bytes(audio_data, sample_rate))
+                text, confidence, language = self._parse_response(payload)
+            result.update({
+                "text": text,
+                "confidence": round(confidence, 3),
+                "language": language,
+            })
+        except Exception as e:
+            logger.error(f"Cloud transcription error ({self.provider}): {e}")
+            result["error"] = str(e)
+
+        result["processing_time_ms"] = int((time.time() - start_time) * 1000)
+        return result
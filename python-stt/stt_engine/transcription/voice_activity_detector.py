@@ -226,3 +226,43 @@ class VoiceActivityDetector:
                 self.speech_frames += 1
 
         return None
+
+    def finish_segment(self) -> Optional[dict]:
+        """
+        End the speech in progress as if its offset had been detected.
+
+        Used to flush: the audio so far is returned as a segment instead of
+        waiting for 0.5s of silence that may never come.
+
+        Returns:
+            {'event': 'speech_end', 'segment': {...}, 'timestamp_ms': int},
+            or None if no speech is in progress
+        """
+        import time
+
+        if not self.is_in_speech:
+            self.reset()
+            return None
+
+        segment_audio = b''.join(self.current_segment)
+        duration_ms = len(self.current_segment) * self.frame_duration_ms
+        timestamp_ms = int(time.time() * 1000)
+        logger.info(f"Speech segment finished early: segment duration={duration_ms}ms at {timestamp_ms}")
+        self.reset()
+
+        return {
+            'event': 'speech_end',
+            'segment': {
+                'audio_data': segment_audio,
+                'duration_ms': duration_ms
+            },
+            'timestamp_ms': timestamp_ms
+        }
+
+    def reset(self) -> None:
+        """Drop the speech in progress and the pre-roll (back to silence)."""
+        self.is_in_speech = False
+        self.speech_frames = 0
+        self.silence_frames = 0
+        self.current_segment = []
+        self.pre_roll_buffer.clear()
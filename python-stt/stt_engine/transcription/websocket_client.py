@@ -0,0 +1,230 @@
+"""
+Minimal WebSocket client (RFC 6455) on asyncio streams.
+
+Just enough for the streaming cloud STT APIs (cloud_client.py): one
+connection with extra handshake headers, text and binary messages, pings
+answered, close. No extensions (no compression) and no fragmented sends;
+fragmented messages from the server are reassembled.
+
+Standard library only, like the HTTP requests of the cloud client.
+"""
+
+import asyncio
+import base64
+import hashlib
+import os
+import ssl
+import struct
+import urllib.parse
+from typing import Dict, Optional, Union
+
+# Appended to the key to compute Sec-WebSocket-Accept (RFC 6455 §1.3)
+HANDSHAKE_GUID = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11"
+
+OP_CONTINUATION = 0x0
+OP_TEXT = 0x1
+OP_BINARY = 0x2
+OP_CLOSE = 0x8
+OP_PING = 0x9
+OP_PONG = 0xA
+
+# Largest handshake response read
+MAX_HANDSHAKE_BYTES = 64 * 1024
+
+
+class WebSocketClosed(Exception):
+    """The connection is closed (by either side, or dropped)."""
+
+
+class HandshakeError(Exception):
+    """The server refused the upgrade (e.g. bad key: HTTP 401)."""
+
+    def __init__(self, status: int, detail: str):
+        super().__init__(f"HTTP {status}: {detail}")
+        self.status = status
+
+
+def _mask(payload: bytes, key: bytes) -> bytes:
+    """XOR `payload` with the 4-byte masking key."""
+    if not payload:
+        return payload
+    repeated = (key * (len(payload) // 4 + 1))[:len(payload)]
+    masked = int.from_bytes(payload, 'little') ^ int.from_bytes(repeated, 'little')
+    return masked.to_bytes(len(payload), 'little')
+
+
+def encode_frame(opcode: int, payload: bytes, key: Optional[bytes] = None) -> bytes:
+    """Single (final) client frame, masked with `key` (random if None)."""
+    key = key or os.urandom(4)
+    length = len(payload)
+    if length < 126:
+        header = struct.pack('!BB', 0x80 | opcode, 0x80 | length)
+    elif length < 1 << 16:
+        header = struct.pack('!BBH', 0x80 | opcode, 0x80 | 126, length)
+    else:
+        header = struct.pack('!BBQ', 0x80 | opcode, 0x80 | 127, length)
+    return header + key + _mask(payload, key)
+
+
+class WebSocket:
+    """Open connection; messages are read by a single task."""
+
+    def __init__(self, reader: asyncio.StreamReader, writer: asyncio.StreamWriter):
+        self._reader = reader
+        self._writer = writer
+        self.closed = False
+
+    async def send_text(self, text: str) -> None:
+        await self._send(OP_TEXT, text.encode('utf-8'))
+
+    async def send_binary(self, data: bytes) -> None:
+        await self._send(OP_BINARY, data)
+
+    async def _send(self, opcode: int, payload: bytes) -> None:
+        if self.closed:
+            raise WebSocketClosed("WebSocket is closed")
+        try:
+            # One write per frame: frames of concurrent senders don't interleave
+            self._writer.write(encode_frame(opcode, payload))
+            await self._writer.drain()
+        except (ConnectionError, OSError) as e:
+            self.closed = True
+            raise WebSocketClosed(str(e)) from e
+
+    async def recv(self) -> Union[str, bytes]:
+        """
+        Next message (str for text, bytes for binary).
+
+        Raises:
+            WebSocketClosed: Once the server closed or the connection dropped
+        """
+        message = bytearray()
+        message_opcode = None
+        while True:
+            try:
+                opcode, fin, payload = await self._read_frame()
+            except (asyncio.IncompleteReadError, ConnectionError, OSError) as e:
+                self.closed = True
+                raise WebSocketClosed("Connection lost") from e
+
+            if opcode == OP_CLOSE:
+                code = struct.unpack('!H', payload[:2])[0] if len(payload) >= 2 else 1005
+                reason = payload[2:].decode('utf-8', errors='replace')
+                if not self.closed:
+                    await self._close_quietly(payload[:2])
+                raise WebSocketClosed(f"Closed by server ({code}{': ' + reason if reason else ''})")
+            if opcode == OP_PING:
+                await self._send(OP_PONG, payload)
+                continue
+            if opcode == OP_PONG:
+                continue
+
+            if opcode != OP_CONTINUATION:
+                message_opcode = opcode
+                message = bytearray()
+            message.extend(payload)
+            if fin:
+                if message_opcode == OP_TEXT:
+                    return message.decode('utf-8')
+                return bytes(message)
+
+    async def _read_frame(self):
+        first, second = await self._reader.readexactly(2)
+        length = second & 0x7F
+        if length == 126:
+            length = struct.unpack('!H', await self._reader.readexactly(2))[0]
+        elif length == 127:
+            length = struct.unpack('!Q', await self._reader.readexactly(8))[0]
+        key = await self._reader.readexactly(4) if second & 0x80 else None
+        payload = await self._reader.readexactly(length)
+        if key:
+            payload = _mask(payload, key)
+        return first & 0x0F, bool(first & 0x80), payload
+
+    async def close(self) -> None:
+        """Send a normal close and drop the connection (no wait for the reply)."""
+        if not self.closed:
+            await self._close_quietly(struct.pack('!H', 1000))
+        self._writer.close()
+        try:
+            await self._writer.wait_closed()
+        except (ConnectionError, OSError):
+            pass
+
+    async def _close_quietly(self, payload: bytes) -> None:
+        try:
+            await self._send(OP_CLOSE, payload)
+        except WebSocketClosed:
+            pass
+        self.closed = True
+
+
+async def connect(url: str, headers: Dict[str, str], timeout: float = 10.0) -> WebSocket:
+    """
+    Open a ws:// or wss:// connection.
+
+    Args:
+        url: Endpoint with query string
+        headers: Extra handshake headers (e.g. authorization)
+        timeout: Seconds allowed for connecting and the handshake
+
+    Raises:
+        HandshakeError: If the server answers anything but 101
+        OSError / asyncio.TimeoutError: If the server can't be reached
+    """
+    parts = urllib.parse.urlsplit(url)
+    if parts.scheme not in ('ws', 'wss'):
+        raise ValueError(f"Not a WebSocket URL: {url}")
+    secure = parts.scheme == 'wss'
+    port = parts.port or (443 if secure else 80)
+    target = (parts.path or '/') + (f"?{parts.query}" if parts.query else '')
+
+    reader, writer = await asyncio.wait_for(
+        asyncio.open_connection(
+            parts.hostname,
+            port,
+            ssl=ssl.create_default_context() if secure else None,
+            limit=MAX_HANDSHAKE_BYTES
+        ),
+        timeout
+    )
+    try:
+        key = base64.b64encode(os.urandom(16)).decode('ascii')
+        lines = [
+            f"GET {target} HTTP/1.1",
+            f"Host: {parts.netloc}",
+            "Upgrade: websocket",
+            "Connection: Upgrade",
+            f"Sec-WebSocket-Key: {key}",
+            "Sec-WebSocket-Version: 13",
+        ] + [f"{name}: {value}" for name, value in headers.items()]
+        writer.write(('\r\n'.join(lines) + '\r\n\r\n').encode('utf-8'))
+        await writer.drain()
+
+        response = await asyncio.wait_for(reader.readuntil(b'\r\n\r\n'), timeout)
+        status_line, *header_lines = response.decode('iso-8859-1').split('\r\n')
+        fields = status_line.split(' ', 2)
+        status = int(fields[1]) if len(fields) > 1 and fields[1].isdigit() else 0
+        response_headers = {}
+        for line in header_lines:
+            name, _, value = line.partition(':')
+            response_headers[name.strip().lower()] = value.strip()
+
+        if status != 101:
+            # The body says why (bad key, quota, unknown model)
+            detail = fields[2] if len(fields) > 2 else ''
+            length = int(response_headers.get('content-length') or 0)
+            if length:
+                body = await asyncio.wait_for(reader.read(min(length, 200)), timeout)
+                detail = body.decode('utf-8', errors='replace')
+            raise HandshakeError(status, detail)
+
+        expected = base64.b64encode(
+            hashlib.sha1((key + HANDSHAKE_GUID).encode('ascii')).digest()
+        ).decode('ascii')
+        if response_headers.get('sec-websocket-accept') != expected:
+            raise HandshakeError(status, "Invalid Sec-WebSocket-Accept")
+    except BaseException:
+        writer.close()
+        raise
+    return WebSocket(reader, writer)
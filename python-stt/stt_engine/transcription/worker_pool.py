@@ -0,0 +1,222 @@
+"""
+Transcription Worker Pool
+
+Final transcription of a speech segment holds the event loop for as long as
+Whisper runs. With a large model, the next segment arrives before the last
+one is transcribed and the sidecar falls behind, while other cores idle.
+
+In worker-pool mode (`main.py --workers N`) final transcriptions run in N
+worker processes, each with its own WhisperSTTEngine. Work is distributed
+per VAD segment: each finished segment goes to the next free worker. VAD and
+partial transcription stay in the main process. The caller keeps results in
+segment order (AudioProcessor awaits them in submission order).
+
+Workers load the model the main engine uses. A model switch (resource
+monitor downgrade, approved upgrade) restarts them with the new model.
+
+Requirements:
+- Segments transcribed in parallel on multi-core machines
+- Final results delivered in the order speech ended
+"""
+
+import asyncio
+import logging
+import multiprocessing
+import os
+import sys
+from concurrent.futures import ProcessPoolExecutor
+from typing import Any, Callable, Dict, Optional, Tuple
+
+logger = logging.getLogger(__name__)
+
+# Engine of the current worker process (set by _init_worker)
+_engine = None
+
+
+def _init_worker(model_size: str, offline_mode: bool, ready) -> None:
+    """
+    Load the model in a new worker process, then wait at `ready`.
+
+    Every worker and the main process meet at the barrier, so the pool only
+    counts as started once each worker has loaded its model. A worker that
+    fails to load breaks the barrier.
+
+    stdout carries IPC in the main process and is inherited: the worker's
+    output (e.g. whisper_model_ready) goes to stderr instead.
+    """
+    global _engine
+    os.dup2(sys.stderr.fileno(), sys.stdout.fileno())
+    sys.stdout = sys.stderr
+    logging.basicConfig(
+        level=logging.INFO,
+        format='%(asctime)s - %(name)s - %(levelname)s - %(message)s',
+        stream=sys.stderr
+    )
+
+    try:
+        from stt_engine.transcription.whisper_client import WhisperSTTEngine
+        _engine = WhisperSTTEngine(model_size=model_size, offline_mode=offline_mode)
+        asyncio.run(_engine.initialize())
+    except BaseException:
+        ready.abort()
+        raise
+    ready.wait()
+
+
+def _warm_up() -> int:
+    """Return the worker's PID (submitted once per worker to spawn them)."""
+    return os.getpid()
+
+
+def _transcribe(audio_data: bytes, sample_rate: int) -> Dict[str, Any]:
+    """Final transcription of one segment (runs in a worker process)."""
+    return asyncio.run(_engine.transcribe(audio_data, sample_rate=sample_rate, is_final=True))
+
+
+class TranscriptionWorkerPool:
+    """
+    Final transcription in a pool of worker processes.
+
+    Attributes:
+        workers: Number of worker processes
+        model_size: Whisper model size the workers have loaded
+        pending: Segments submitted but not transcribed yet
+        pending_ms: Audio of those segments in milliseconds
+    """
+
+    def __init__(
+        self,
+        workers: int,
+        model_size: str,
+        offline_mode: bool = False,
+        executor_factory: Optional[Callable[[str], Any]] = None
+    ):
+        """
+        Args:
+            workers: Number of worker processes (at least 2)
+            model_size: Whisper model size each worker loads
+            offline_mode: Passed on to each worker's WhisperSTTEngine
+            executor_factory: Builds an executor for a model size instead of
+                a process pool (testing)
+        """
+        if workers < 2:
+            raise ValueError(f"A worker pool needs at least 2 workers, got {workers}")
+        self.workers = workers
+        self.model_size = model_size
+        self.pending = 0
+        self.pending_ms = 0
+        self._offline_mode = offline_mode
+        self._executor_factory = executor_factory
+        self._executor = None
+        # Cleared while the workers restart; new segments wait for it
+        self._ready = asyncio.Event()
+
+    def _new_executor(self, model_size: str) -> Tuple[Any, Optional[Any]]:
+        """Executor loading `model_size`, with the barrier its workers meet at."""
+        if self._executor_factory:
+            return self._executor_factory(model_size), None
+        # spawn: forking a process with a running event loop and threads is unsafe
+        context = multiprocessing.get_context('spawn')
+        ready = context.Barrier(self.workers + 1)
+        executor = ProcessPoolExecutor(
+            max_workers=self.workers,
+            mp_context=context,
+            initializer=_init_worker,
+            initargs=(model_size, self._offline_mode, ready)
+        )
+        return executor, ready
+
+    async def start(self) -> None:
+        """
+        Start all workers and wait until their models are loaded.
+
+        Raises:
+            Exception: If a worker fails to start (e.g. model not found)
+        """
+        await self._start_executor(self.model_size)
+        self._ready.set()
+
+    async def _start_executor(self, model_size: str) -> None:
+        """Start workers loading `model_size`; returns once all have loaded it."""
+        executor, ready = self._new_executor(model_size)
+        loop = asyncio.get_running_loop()
+        # Workers spawn as tasks arrive; each blocks at the barrier once loaded,
+        # so no worker takes a second warm-up and all `workers` processes start
+        try:
+            warm_ups = [loop.run_in_executor(executor, _warm_up) for _ in range(self.workers)]
+            barrier = [loop.run_in_executor(None, ready.wait)] if ready else []
+            results = await asyncio.gather(*barrier, *warm_ups)
+        except Exception:
+            if ready:
+                ready.abort()
+            executor.shutdown(wait=False, cancel_futures=True)
+            raise
+        self._executor = executor
+        self.model_size = model_size
+        pids = results[len(barrier):]
+        logger.info(
+            f"Transcription worker pool ready: {self.workers} workers, "
+            f"model={model_size}, pids={sorted(set(pids))}"
+        )
+
+    async def reload(self, model_size: str) -> None:
+        """
+        Restart the workers with another model size.
+
+        Segments already submitted finish on the old model; new ones wait
+        until the new workers are up. Returns once every worker has loaded
+        `model_size`.
+
+        Raises:
+            Exception: If the new workers fail to start (the pool restarts
+                with the previous model)
+        """
+        previous = self.model_size
+        logger.info(f"Restarting transcription workers: {previous} → {model_size}")
+        self._ready.clear()
+        try:
+            await asyncio.get_running_loop().run_in_executor(None, self._executor.shutdown, True)
+            try:
+                await self._start_executor(model_size)
+            except Exception:
+                logger.error(f"Transcription workers failed to load {model_size}, restarting with {previous}")
+                await self._start_executor(previous)
+                raise
+        finally:
+            self._ready.set()
+
+    def submit(self, audio_data: bytes, sample_rate: int = 16000) -> asyncio.Future:
+        """
+        Queue a segment for final transcription on the next free worker.
+
+        Args:
+            audio_data: Segment audio (16-bit PCM)
+            sample_rate: Audio sample rate in Hz
+
+        Returns:
+            Future resolving to the WhisperSTTEngine.transcribe() result
+        """
+        audio_ms = len(audio_data) * 1000 // (sample_rate * 2)
+        self.pending += 1
+        self.pending_ms += audio_ms
+
+        def done(_future):
+            self.pending -= 1
+            self.pending_ms -= audio_ms
+
+        future = asyncio.ensure_future(self._transcribe(audio_data, sample_rate))
+        future.add_done_callback(done)
+        return future
+
+    async def _transcribe(self, audio_data: bytes, sample_rate: int) -> Dict[str, Any]:
+        """Run one segment on the pool once the workers are up."""
+        await self._ready.wait()
+        return await asyncio.get_running_loop().run_in_executor(
+            self._executor, _transcribe, audio_data, sample_rate
+        )
+
+    def shutdown(self) -> None:
+        """Stop the workers; pending transcriptions are dropped."""
+        if self._executor:
+            self._executor.shutdown(wait=False, cancel_futures=True)
+        logger.info("Transcription worker pool stopped")
@@ -910,6 +910,83 @@ class TestEventStreamProtocol:
             speech_end_idx = next(i for i, msg in enumerate(sent_messages) if msg.get('eventType') == 'speech_end')
             assert speech_end_idx > final_text_idx, "speech_end must come after final_text"
 
+    @pytest.mark.asyncio
+    async def test_concurrent_sessions_use_separate_pipelines(self):
+        """
+        GIVEN two recording sessions streaming to one sidecar
+        WHEN their requests interleave
+        THEN each session's audio should go through its own pipeline (own VAD),
+        acks should report that pipeline, and flush should reach all of them
+        """
+        from unittest.mock import AsyncMock, MagicMock, patch
+        from main import AudioProcessor, MAX_SESSION_PIPELINES
+
+        sent_messages = []
+
+        async def capture_message(msg):
+            sent_messages.append(msg)
+
+        def new_pipeline(vad, stt_engine, final_pool=None):
+            pipeline = MagicMock()
+            pipeline.vad = None
+            pipeline.process_audio_frame_with_partial = AsyncMock(return_value=None)
+            pipeline.flush = AsyncMock(return_value=None)
+            pipeline.is_in_speech.return_value = False
+            pipeline.has_buffered_speech.return_value = False
+            pipeline.buffered_speech_ms.return_value = 0
+            return pipeline
+
+        with patch('main.WhisperSTTEngine') as mock_stt_class, \
+             patch('stt_engine.resource_monitor.ResourceMonitor'), \
+             patch('main.VoiceActivityDetector') as mock_vad_class, \
+             patch('main.AudioPipeline', side_effect=new_pipeline):
+
+            mock_stt_class.return_value = AsyncMock(model_size='small')
+            mock_vad = MagicMock()
+            mock_vad.split_into_frames.return_value = [[0] * 320 for _ in range(3)]
+            mock_vad_class.return_value = mock_vad
+
+            processor = AudioProcessor()
+            processor.ipc = AsyncMock()
+            processor.ipc.send_message.side_effect = capture_message
+
+            request_ids = ['room-a-audio-1', 'room-b-audio-1', 'room-a-audio-2']
+            for request_id in request_ids:
+                await processor.handle_message({
+                    'id': request_id,
+                    'type': 'request',
+                    'method': 'process_audio_stream',
+                    'params': {'audio_data': [0] * 9600}
+                })
+
+            room_a = processor.session_pipelines['room-a']
+            room_b = processor.session_pipelines['room-b']
+            assert room_a is not room_b
+            assert room_a.process_audio_frame_with_partial.await_count == 6
+            assert room_b.process_audio_frame_with_partial.await_count == 3
+            assert processor.pipeline.process_audio_frame_with_partial.await_count == 0
+
+            room_b.buffered_speech_ms.return_value = 700
+            await processor.handle_message({
+                'id': 'room-b-audio-2',
+                'type': 'request',
+                'method': 'process_audio_stream',
+                'params': {'audio_data': [0] * 9600}
+            })
+            acks = [m['data'] for m in sent_messages if m.get('eventType') == 'audio_ack']
+            assert [ack['requestId'] for ack in acks] == request_ids + ['room-b-audio-2']
+            assert [ack['buffered_ms'] for ack in acks] == [0, 0, 0, 700]
+
+            await processor.handle_message({'id': 'ctl-1', 'type': 'request', 'method': 'flush'})
+            for pipeline in (processor.pipeline, room_a, room_b):
+                pipeline.flush.assert_awaited_once()
+
+            # Pipelines of sessions no longer seen are dropped
+            for i in range(MAX_SESSION_PIPELINES):
+                processor._pipeline_for(f"room-{i}-audio-1")
+            assert len(processor.session_pipelines) == MAX_SESSION_PIPELINES
+            assert 'room-a' not in processor.session_pipelines
+
     @pytest.mark.asyncio
     async def test_process_audio_still_works_for_backward_compatibility(self):
         """
@@ -44,6 +44,24 @@ class MockVAD:
             }
         return None
 
+    def finish_segment(self):
+        """Simulate a flush: end the speech in progress early"""
+        if not self.is_in_speech:
+            return None
+        self.reset()
+        return {
+            'event': 'speech_end',
+            'segment': {
+                'audio_data': b'fake_audio_data',
+                'duration_ms': 500
+            },
+            'timestamp_ms': 1000
+        }
+
+    def reset(self):
+        """Simulate dropping the speech in progress"""
+        self.is_in_speech = False
+
 
 class MockSTTEngine:
     """Mock STT that only transcribes audio"""
@@ -173,6 +191,19 @@ class TestPartialTranscription:
                 assert result['transcription']['text'] == 'Partial transcription'
                 assert result['transcription']['is_final'] is False
 
+    @pytest.mark.asyncio
+    async def test_no_partials_without_engine_support(self):
+        """WHEN the engine doesn't support partials (non-streaming cloud STT)
+        THEN no partial transcription should be requested"""
+        stt = MockSTTEngine()
+        stt.supports_partials = False
+        stt.transcribe = AsyncMock()
+        pipeline = AudioPipeline(vad=MockVAD(), stt_engine=stt)
+        pipeline._current_speech_buffer = bytearray(b'\x00\x00' * 1600)
+
+        assert await pipeline._generate_partial_transcription() is None
+        stt.transcribe.assert_not_called()
+
 
 class TestErrorHandling:
     """Test error handling in pipeline"""
@@ -250,6 +281,148 @@ class TestStatistics:
         assert 'modified' not in stats2
 
 
+class TestWorkerPoolMode:
+    """Test final transcription in a worker pool (--workers N)"""
+
+    @pytest.mark.asyncio
+    async def test_final_transcription_runs_in_pool(self):
+        """WHEN a worker pool is configured
+        THEN speech end should hand the segment to a worker and resolve to final_text"""
+        from concurrent.futures import ThreadPoolExecutor
+        from stt_engine.transcription import worker_pool
+        from stt_engine.transcription.worker_pool import TranscriptionWorkerPool
+
+        # Threads share the module, so one engine serves both "workers"
+        worker_pool._engine = MockSTTEngine()
+        pool = TranscriptionWorkerPool(
+            2, 'tiny', executor_factory=lambda model_size: ThreadPoolExecutor(max_workers=2)
+        )
+        await pool.start()
+        pipeline = AudioPipeline(vad=MockVAD(), stt_engine=MockSTTEngine(), final_pool=pool)
+        try:
+            for i in range(180):
+                result = await pipeline.process_audio_frame(b'frame')
+
+            assert result['event'] == 'final_pending'
+            assert pipeline.has_buffered_speech()  # No no_speech while a worker is busy
+
+            final = await result['pending']
+            assert final['event'] == 'final_text'
+            assert final['transcription']['text'] == 'Final transcription text'
+            assert 'latency_metrics' in final
+            assert pool.pending == 0
+            assert not pipeline.has_buffered_speech()
+        finally:
+            pool.shutdown()
+            worker_pool._engine = None
+
+    def test_pool_needs_two_workers(self):
+        """WHEN fewer than 2 workers are requested
+        THEN the pool should refuse (single process mode has no pool)"""
+        from stt_engine.transcription.worker_pool import TranscriptionWorkerPool
+
+        with pytest.raises(ValueError):
+            TranscriptionWorkerPool(1, 'tiny', executor_factory=MagicMock())
+
+    @pytest.mark.asyncio
+    async def test_reload_restarts_workers_with_new_model(self):
+        """WHEN the model is switched
+        THEN the workers should restart with it before reload returns"""
+        from concurrent.futures import ThreadPoolExecutor
+        from stt_engine.transcription import worker_pool
+        from stt_engine.transcription.worker_pool import TranscriptionWorkerPool
+
+        started = []
+
+        def executor_factory(model_size):
+            started.append(model_size)
+            return ThreadPoolExecutor(max_workers=2)
+
+        worker_pool._engine = MockSTTEngine()
+        pool = TranscriptionWorkerPool(2, 'small', executor_factory=executor_factory)
+        try:
+            await pool.start()
+            await pool.reload('base')
+
+            assert started == ['small', 'base']
+            assert pool.model_size == 'base'
+            final = await pool.submit(b'\x00\x00' * 1600)
+            assert final['text'] == 'Final transcription text'
+            assert pool.pending == 0
+        finally:
+            pool.shutdown()
+            worker_pool._engine = None
+
+    @pytest.mark.asyncio
+    async def test_failed_reload_keeps_previous_model(self):
+        """WHEN the workers fail to load the new model
+        THEN the pool should restart with the previous one and report the failure"""
+        from concurrent.futures import ThreadPoolExecutor
+        from stt_engine.transcription.worker_pool import TranscriptionWorkerPool
+
+        def executor_factory(model_size):
+            if model_size == 'large-v3':
+                broken = MagicMock()
+                broken.submit.side_effect = RuntimeError("model not found")
+                return broken
+            return ThreadPoolExecutor(max_workers=2)
+
+        pool = TranscriptionWorkerPool(2, 'small', executor_factory=executor_factory)
+        try:
+            await pool.start()
+            with pytest.raises(RuntimeError):
+                await pool.reload('large-v3')
+            assert pool.model_size == 'small'
+        finally:
+            pool.shutdown()
+
+
+class TestFlushAndCancel:
+    """Test flushing and cancelling the speech in progress"""
+
+    @pytest.mark.asyncio
+    async def test_flush_transcribes_speech_in_progress(self):
+        """WHEN flush is requested during speech
+        THEN the speech so far should be transcribed as a final"""
+        vad = MockVAD()
+        pipeline = AudioPipeline(vad=vad, stt_engine=MockSTTEngine())
+        for i in range(40):
+            await pipeline.process_audio_frame(b'frame')
+        assert pipeline.is_in_speech()
+
+        result = await pipeline.flush()
+
+        assert result['event'] == 'final_text'
+        assert result['transcription']['text'] == 'Final transcription text'
+        assert not pipeline.is_in_speech()
+        assert not pipeline.has_buffered_speech()
+
+    @pytest.mark.asyncio
+    async def test_flush_without_speech(self):
+        """WHEN flush is requested in silence
+        THEN there should be nothing to transcribe"""
+        pipeline = AudioPipeline(vad=MockVAD(), stt_engine=MockSTTEngine())
+
+        assert await pipeline.flush() is None
+
+    @pytest.mark.asyncio
+    async def test_cancel_drops_speech_in_progress(self):
+        """WHEN cancel is requested during speech
+        THEN the speech so far should be dropped untranscribed"""
+        stt = MockSTTEngine()
+        stt.transcribe = AsyncMock()
+        pipeline = AudioPipeline(vad=MockVAD(), stt_engine=stt)
+        for i in range(40):
+            await pipeline.process_audio_frame(b'frame')
+
+        pipeline.cancel()
+
+        assert not pipeline.is_in_speech()
+        assert not pipeline.has_buffered_speech()
+        assert await pipeline.flush() is None
+        stt.transcribe.assert_not_called()
+
+
 class TestPartialTextPreRollIntegrity:
     """
     Test that partial_text includes VAD pre-roll frames from speech onset.
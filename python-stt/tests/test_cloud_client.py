@@ -0,0 +1,313 @@
+"""
+Unit tests for CloudSTTEngine (cloud STT fallback).
+
+Requests go to a fake opener, streams to a fake WebSocket; no network
+access (the WebSocket client is tested against a local server).
+"""
+
+import asyncio
+import base64
+import hashlib
+import io
+import json
+import struct
+import urllib.error
+
+import pytest
+
+from stt_engine.transcription import websocket_client
+from stt_engine.transcription.cloud_client import CloudSTTEngine
+
+
+class FakeOpener:
+    """Records requests and answers with a fixed JSON payload"""
+
+    def __init__(self, payload=None, error=None):
+        self.payload = payload
+        self.error = error
+        self.requests = []
+
+    def __call__(self, request, timeout):
+        self.requests.append(request)
+        if self.error:
+            raise self.error
+        return io.BytesIO(json.dumps(self.payload).encode('utf-8'))
+
+
+class FakeWebSocket:
+    """Records sent messages; `respond(ws, message)` queues the replies"""
+
+    def __init__(self, respond):
+        self.respond = respond
+        self.sent = []
+        self.closed = False
+        self._inbox = asyncio.Queue()
+
+    def reply(self, message):
+        self._inbox.put_nowait(json.dumps(message))
+
+    def hang_up(self):
+        self._inbox.put_nowait(None)
+
+    async def send_text(self, text):
+        self.sent.append(json.loads(text))
+        self.respond(self, self.sent[-1])
+
+    async def send_binary(self, data):
+        self.sent.append(data)
+        self.respond(self, data)
+
+    async def recv(self):
+        message = await self._inbox.get()
+        if message is None:
+            raise websocket_client.WebSocketClosed("Closed by server")
+        return message
+
+    async def close(self):
+        self.closed = True
+
+
+class FakeConnector:
+    """Opens a FakeWebSocket per stream and records the URLs and headers"""
+
+    def __init__(self, respond):
+        self.respond = respond
+        self.sockets = []
+        self.connections = []
+
+    async def __call__(self, url, headers):
+        self.connections.append((url, headers))
+        self.sockets.append(FakeWebSocket(self.respond))
+        return self.sockets[-1]
+
+
+def deepgram_server(ws, message):
+    """Interim result per audio chunk; the final one when the stream closes"""
+    heard = sum(len(m) for m in ws.sent if isinstance(m, bytes))
+    result = lambda text, is_final: {
+        'type': 'Results',
+        'is_final': is_final,
+        'channel': {'alternatives': [{'transcript': text, 'confidence': 0.9}]},
+    }
+    if isinstance(message, bytes):
+        ws.reply(result(f"heard {heard}", False))
+    elif message == {'type': 'CloseStream'}:
+        ws.reply(result(f"final {heard}", True))
+        ws.hang_up()
+
+
+class TestCloudSTTEngine:
+    """Test provider requests and the WhisperSTTEngine-compatible results"""
+
+    @pytest.mark.asyncio
+    async def test_openai_transcribes_finals_only(self):
+        """WHEN the provider has no streaming API
+        THEN partials should be skipped without a request and finals uploaded once"""
+        openai = FakeOpener({
+            'text': ' こんにちは ',
+            'language': 'japanese',
+            'segments': [{'avg_logprob': 0.0}],
+        })
+        engine = CloudSTTEngine('openai', 'sk-test', opener=openai)
+        await engine.initialize()
+        assert engine.supports_partials is False
+
+        result = await engine.transcribe(b'\x00\x00' * 1600, is_final=False)
+        assert result['text'] == ''
+        assert 'error' not in result
+        assert openai.requests == []
+
+        result = await engine.transcribe(b'\x00\x00' * 1600, is_final=True)
+        assert result['text'] == 'こんにちは'
+        assert result['confidence'] == 1.0
+        assert result['is_final'] is True
+        request = openai.requests[0]
+        assert request.full_url == 'https://api.openai.com/v1/audio/transcriptions'
+        assert request.get_header('Authorization') == 'Bearer sk-test'
+        assert b'RIFF' in request.data  # WAV upload
+
+    @pytest.mark.asyncio
+    async def test_deepgram_streams_only_new_audio(self):
+        """WHEN partials and the final of one utterance are transcribed
+        THEN each second of audio should be sent once over one stream,
+        and the next utterance should open a new stream"""
+        connector = FakeConnector(deepgram_server)
+        engine = CloudSTTEngine('deepgram', 'dg-test', connector=connector)
+        await engine.initialize()
+        assert engine.supports_partials is True
+
+        first = b'\x01\x00' * 1600
+        second = first + b'\x02\x00' * 1600
+        result = await engine.transcribe(first, is_final=False)
+        assert (result['text'], result['is_final']) == ('heard 3200', False)
+        result = await engine.transcribe(second, is_final=False)
+        assert result['text'] == 'heard 6400'
+        result = await engine.transcribe(second, is_final=True)
+        assert (result['text'], result['confidence'], result['is_final']) == ('final 6400', 0.9, True)
+
+        ws = connector.sockets[0]
+        assert ws.sent == [first, b'\x02\x00' * 1600, {'type': 'CloseStream'}]
+        assert ws.closed
+        url, headers = connector.connections[0]
+        assert url.startswith('wss://api.deepgram.com/v1/listen?model=nova-2')
+        assert 'interim_results=true' in url and 'sample_rate=16000' in url
+        assert headers == {'Authorization': 'Token dg-test'}
+
+        # New utterance: doesn't extend the sent audio
+        result = await engine.transcribe(b'\x03\x00' * 1600, is_final=True)
+        assert result['text'] == 'final 3200'
+        assert len(connector.sockets) == 2
+
+    @pytest.mark.asyncio
+    async def test_azure_realtime_transcription(self):
+        """WHEN azure transcribes a final segment
+        THEN the session should be configured, audio appended at 24 kHz,
+        and the transcripts of the committed items joined"""
+        def azure_server(ws, message):
+            if message['type'] == 'input_audio_buffer.commit':
+                ws.reply({'type': 'input_audio_buffer.committed', 'item_id': 'a'})
+                ws.reply({
+                    'type': 'conversation.item.input_audio_transcription.delta',
+                    'item_id': 'a', 'delta': '議事',
+                })
+                ws.reply({
+                    'type': 'conversation.item.input_audio_transcription.completed',
+                    'item_id': 'a', 'transcript': '議事録',
+                })
+
+        connector = FakeConnector(azure_server)
+        engine = CloudSTTEngine('azure', 'key', endpoint='https://res.openai.azure.com/', connector=connector)
+        await engine.initialize()
+
+        result = await engine.transcribe(b'\x00\x00' * 1600, is_final=True)
+        assert (result['text'], result['is_final']) == ('議事録', True)
+        assert 'error' not in result
+
+        url, headers = connector.connections[0]
+        assert url.startswith('wss://res.openai.azure.com/openai/realtime?api-version=')
+        assert 'deployment=gpt-4o-mini-transcribe' in url
+        assert headers == {'api-key': 'key'}
+        update, append, commit = connector.sockets[0].sent
+        assert update['session']['input_audio_transcription']['model'] == 'gpt-4o-mini-transcribe'
+        assert len(base64.b64decode(append['audio'])) == 2 * 2400  # 0.1 s at 24 kHz
+        assert commit == {'type': 'input_audio_buffer.commit'}
+
+    @pytest.mark.asyncio
+    async def test_errors_are_reported_in_result(self):
+        """WHEN the provider rejects a request, or the configuration is incomplete
+        THEN the result should carry the error (construction should fail)"""
+        rejected = urllib.error.HTTPError(
+            'https://example.test', 401, 'Unauthorized', {}, io.BytesIO(b'invalid key')
+        )
+        engine = CloudSTTEngine('openai', 'key', opener=FakeOpener(error=rejected))
+        await engine.initialize()
+
+        result = await engine.transcribe(b'\x00\x00' * 1600, is_final=True)
+        assert result['text'] == ''
+        assert 'HTTP 401' in result['error']
+        assert 'invalid key' in result['error']
+
+        async def refuse(url, headers):
+            raise websocket_client.HandshakeError(401, 'invalid key')
+
+        engine = CloudSTTEngine('deepgram', 'key', connector=refuse)
+        await engine.initialize()
+        result = await engine.transcribe(b'\x00\x00' * 1600, is_final=False)
+        assert 'HTTP 401' in result['error']
+
+        with pytest.raises(ValueError):
+            CloudSTTEngine('azure', 'key')  # No endpoint
+        with pytest.raises(ValueError):
+            CloudSTTEngine('openai', '')
+
+
+class TestWebSocketClient:
+    """Test the handshake and framing against a local server"""
+
+    @pytest.mark.asyncio
+    async def test_handshake_and_messages(self):
+        """WHEN a client connects, sends and receives
+        THEN headers should be passed, frames unmasked and pings answered"""
+        received = []
+        served = asyncio.Event()
+
+        async def serve(reader, writer):
+            request = (await reader.readuntil(b'\r\n\r\n')).decode('ascii')
+            received.append(request)
+            key = next(
+                line.split(': ', 1)[1] for line in request.split('\r\n')
+                if line.startswith('Sec-WebSocket-Key')
+            )
+            accept = base64.b64encode(
+                hashlib.sha1((key + websocket_client.HANDSHAKE_GUID).encode('ascii')).digest()
+            ).decode('ascii')
+            writer.write((
+                'HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\n'
+                f'Connection: Upgrade\r\nSec-WebSocket-Accept: {accept}\r\n\r\n'
+            ).encode('ascii'))
+
+            # Client frame: masked text
+            header = await reader.readexactly(2)
+            mask = await reader.readexactly(4)
+            payload = websocket_client._mask(await reader.readexactly(header[1] & 0x7F), mask)
+            received.append((header[0], payload))
+
+            # Ping, then a text message in two fragments, then close
+            writer.write(bytes([0x89, 0]))
+            writer.write(bytes([0x01, 2]) + b'he')
+            writer.write(bytes([0x80, 3]) + b'llo')
+            writer.write(bytes([0x88, 2]) + struct.pack('!H', 1000))
+            pong = await reader.readexactly(6)
+            received.append(pong[0])
+            writer.close()
+            served.set()
+
+        server = await asyncio.start_server(serve, '127.0.0.1', 0)
+        port = server.sockets[0].getsockname()[1]
+        try:
+            ws = await websocket_client.connect(
+                f"ws://127.0.0.1:{port}/v1/listen?model=x", {'Authorization': 'Token t'}
+            )
+            await ws.send_text('hi')
+            assert await ws.recv() == 'hello'
+            with pytest.raises(websocket_client.WebSocketClosed):
+                await ws.recv()
+            await ws.close()
+            await asyncio.wait_for(served.wait(), 5)
+        finally:
+            server.close()
+            await server.wait_closed()
+
+        assert received[0].startswith('GET /v1/listen?model=x HTTP/1.1')
+        assert 'Authorization: Token t' in received[0]
+        assert received[1] == (0x81, b'hi')
+        assert received[2] == 0x8A  # Pong
+
+    @pytest.mark.asyncio
+    async def test_refused_handshake(self):
+        """WHEN the server doesn't upgrade
+        THEN connect should raise HandshakeError with the status and body"""
+        async def serve(reader, writer):
+            await reader.readuntil(b'\r\n\r\n')
+            writer.write(b'HTTP/1.1 401 Unauthorized\r\nContent-Length: 11\r\n\r\ninvalid key')
+            await writer.drain()
+            writer.close()
+
+        server = await asyncio.start_server(serve, '127.0.0.1', 0)
+        port = server.sockets[0].getsockname()[1]
+        try:
+            with pytest.raises(websocket_client.HandshakeError) as error:
+                await websocket_client.connect(f"ws://127.0.0.1:{port}/", {})
+        finally:
+            server.close()
+            await server.wait_closed()
+        assert error.value.status == 401
+        assert 'invalid key' in str(error.value)
+
+    def test_encode_frame_lengths(self):
+        """Frame headers for the three payload length encodings"""
+        key = b'\x01\x02\x03\x04'
+        assert websocket_client.encode_frame(websocket_client.OP_TEXT, b'a', key)[:2] == bytes([0x81, 0x81])
+        assert websocket_client.encode_frame(websocket_client.OP_BINARY, b'a' * 200, key)[:4] == \
+            bytes([0x82, 0xFE, 0, 200])
+        assert websocket_client.encode_frame(websocket_client.OP_BINARY, b'a' * 70000, key)[1] == 0xFF
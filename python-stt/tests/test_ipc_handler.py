@@ -12,6 +12,7 @@ Requirements:
 import pytest
 import asyncio
 import json
+import socket
 import sys
 from io import StringIO, BytesIO
 from unittest.mock import MagicMock, patch, AsyncMock
@@ -320,5 +321,69 @@ class TestIpcStatistics:
         assert "modified" not in handler.stats
 
 
+
+class TestIpcVersionNegotiation:
+    """Test the hello handshake (protocol version negotiation)"""
+
+    def test_negotiate_version_picks_highest_common(self):
+        """WHEN Rust offers versions including ours
+        THEN the highest common one is chosen and used for outgoing messages"""
+        handler = IpcHandler()
+        handler.SUPPORTED_PROTOCOL_VERSIONS = ("1.0", "1.1")
+
+        assert handler.negotiate_version(["0.9", "1.0", "1.1", "2.0"]) == "1.1"
+        assert handler.protocol_version == "1.1"
+
+    def test_negotiate_version_without_common_version(self):
+        """WHEN Rust offers no version we support
+        THEN None is returned and the version stays unchanged"""
+        handler = IpcHandler()
+
+        assert handler.negotiate_version(["2.0"]) is None
+        assert handler.negotiate_version("1.0") is None
+        assert handler.protocol_version == IpcHandler.PROTOCOL_VERSION
+
+
+@pytest.mark.skipif(not hasattr(socket, "AF_UNIX"), reason="Unix sockets only")
+class TestIpcAudioChannel:
+    """Test the binary audio channel (open_audio_channel)"""
+
+    @pytest.mark.asyncio
+    async def test_read_audio_from_unix_socket(self, tmp_path):
+        """WHEN Rust writes PCM to the offered socket
+        THEN read_audio should return exactly the requested bytes"""
+        path = str(tmp_path / "audio.sock")
+        server = socket.socket(socket.AF_UNIX, socket.SOCK_STREAM)
+        server.bind(path)
+        server.listen(1)
+
+        handler = IpcHandler()
+        handler.open_audio_channel("unix_socket", path)
+        rust_side, _ = server.accept()
+        rust_side.sendall(bytes([1, 0, 2, 0, 3, 0]))
+
+        assert await handler.read_audio(4) == bytes([1, 0, 2, 0])
+        assert await handler.read_audio(2) == bytes([3, 0])
+
+        # Closed mid-batch: error, and the channel is dropped
+        rust_side.sendall(bytes([4]))
+        rust_side.close()
+        with pytest.raises(IpcProtocolError):
+            await handler.read_audio(2)
+        with pytest.raises(IpcProtocolError):
+            await handler.read_audio(2)
+        server.close()
+
+    def test_unsupported_channel_is_rejected(self):
+        """WHEN the offered channel kind is unknown
+        THEN open_audio_channel should raise (Rust keeps audio on stdin)"""
+        handler = IpcHandler()
+
+        with pytest.raises(IpcProtocolError):
+            handler.open_audio_channel("shared_memory", "/dev/shm/x")
+        with pytest.raises(IpcProtocolError):
+            handler.open_audio_channel("unix_socket", "/nonexistent/audio.sock")
+
+
 if __name__ == "__main__":
     pytest.main([__file__, "-v"])
\ No newline at end of file
@@ -14,7 +14,7 @@ import asyncio
 import tempfile
 import json
 from pathlib import Path
-from unittest.mock import patch, MagicMock
+from unittest.mock import patch, MagicMock, AsyncMock
 
 
 @pytest.mark.asyncio
@@ -216,6 +216,10 @@ async def test_upgrade_success_when_model_available():
             processor.resource_monitor = resource_monitor
             processor.ipc = IpcHandler()
 
+            # Worker pool mode (--workers N): workers must follow the switch
+            processor.final_pool = MagicMock(model_size='base')
+            processor.final_pool.reload = AsyncMock()
+
             # Capture IPC messages
             sent_messages = []
             original_send = processor.ipc.send_message
@@ -239,6 +243,7 @@ async def test_upgrade_success_when_model_available():
             # Verify successful upgrade
             assert stt_engine.model_size == "small", "Should upgrade to 'small'"
             assert resource_monitor.current_model == "small", "ResourceMonitor should reflect 'small'"
+            processor.final_pool.reload.assert_awaited_once_with('small')
 
             # Verify IPC response
             response_msgs = [m for m in sent_messages if m.get('type') == 'response']
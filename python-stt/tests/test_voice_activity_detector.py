@@ -448,6 +448,34 @@ class TestSegmentFinalization:
             # Total: 30 + 20 + 50 = 100 frames = 1000ms
             assert result['segment']['duration_ms'] == 1000
 
+    def test_finish_segment_ends_speech_early(self):
+        """WHEN the speech in progress is finished before its offset (flush)
+        THEN should return the segment so far and return to silence."""
+        from stt_engine.transcription.voice_activity_detector import VoiceActivityDetector
+
+        with patch('stt_engine.transcription.voice_activity_detector.webrtcvad.Vad') as mock_vad_class:
+            mock_vad_instance = MagicMock()
+            mock_vad_class.return_value = mock_vad_instance
+
+            detector = VoiceActivityDetector()
+            assert detector.finish_segment() is None
+
+            speech_frame = np.random.randint(-32768, 32767, 160, dtype=np.int16).tobytes()
+            mock_vad_instance.is_speech.return_value = True
+            for i in range(50):
+                detector.process_frame(speech_frame)
+
+            result = detector.finish_segment()
+
+            assert result['event'] == 'speech_end'
+            # 30 pre-roll frames + 20 frames after onset
+            assert result['segment']['duration_ms'] == 500
+            assert len(result['segment']['audio_data']) == 50 * len(speech_frame)
+            assert 'timestamp_ms' in result
+            assert detector.is_in_speech is False
+            assert detector.current_segment == []
+            assert detector.finish_segment() is None
+
 
 class TestPreRollBufferIntegrity:
     """
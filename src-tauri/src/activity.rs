@@ -0,0 +1,363 @@
+//! Activity Timeline
+//!
+//! Per-second audio energy and voice activity, computed while recording so
+//! the UI can draw a waveform/heatmap of long sessions without decoding
+//! `audio.wav` in the webview.
+//!
+//! Stored as `activity.bin` in the session directory: the 4-byte magic
+//! `ACT1` followed by one 2-byte record per second of audio
+//! (`level`, `voiced`). Records are appended as each second completes, so a
+//! crashed session keeps its timeline up to the last full second.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::Path;
+
+pub const ACTIVITY_FILENAME: &str = "activity.bin";
+const MAGIC: &[u8; 4] = b"ACT1";
+
+/// Session audio format (see `storage::AudioWriter`)
+const SAMPLE_RATE: usize = 16000;
+/// 20ms voice activity frames
+const FRAME_SAMPLES: usize = SAMPLE_RATE / 50;
+const FRAMES_PER_SECOND: usize = SAMPLE_RATE / FRAME_SAMPLES;
+
+/// Energy floor mapped to level 0
+const FLOOR_DBFS: f64 = -60.0;
+/// Frames louder than this count as voiced
+const VOICE_THRESHOLD_DBFS: f64 = -40.0;
+
+/// Activity of one time bucket
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActivityPoint {
+    /// RMS energy, 0 (≤ -60 dBFS) to 255 (0 dBFS)
+    pub level: u8,
+    /// Share of voiced 20ms frames, 0-100 (%)
+    pub voiced: u8,
+}
+
+/// Activity timeline returned to the UI
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActivityTimeline {
+    /// Duration covered by each point
+    pub resolution_ms: u64,
+    pub points: Vec<ActivityPoint>,
+}
+
+fn dbfs(sum_squares: f64, samples: usize) -> f64 {
+    if samples == 0 || sum_squares <= 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    let rms = (sum_squares / samples as f64).sqrt() / i16::MAX as f64;
+    20.0 * rms.log10()
+}
+
+// ============================================================================
+// Accumulator
+// ============================================================================
+
+/// Turns a stream of 16kHz samples into per-second points
+#[derive(Debug, Default)]
+pub struct ActivityAccumulator {
+    second_sum_squares: f64,
+    second_samples: usize,
+    frame_sum_squares: f64,
+    frame_samples: usize,
+    voiced_frames: usize,
+}
+
+impl ActivityAccumulator {
+    /// Feed samples, returning the points of every second completed by them
+    pub fn push(&mut self, samples: &[i16]) -> Vec<ActivityPoint> {
+        let mut points = Vec::new();
+        for &sample in samples {
+            let square = (sample as f64) * (sample as f64);
+            self.second_sum_squares += square;
+            self.second_samples += 1;
+            self.frame_sum_squares += square;
+            self.frame_samples += 1;
+
+            if self.frame_samples == FRAME_SAMPLES {
+                self.close_frame();
+            }
+            if self.second_samples == SAMPLE_RATE {
+                points.push(self.take_point(FRAMES_PER_SECOND));
+            }
+        }
+        points
+    }
+
+    /// Point for a trailing partial second, if any samples are pending
+    pub fn finish(&mut self) -> Option<ActivityPoint> {
+        if self.second_samples == 0 {
+            return None;
+        }
+        let frames = self.second_samples.div_ceil(FRAME_SAMPLES);
+        if self.frame_samples > 0 {
+            self.close_frame();
+        }
+        Some(self.take_point(frames))
+    }
+
+    fn close_frame(&mut self) {
+        if dbfs(self.frame_sum_squares, self.frame_samples) >= VOICE_THRESHOLD_DBFS {
+            self.voiced_frames += 1;
+        }
+        self.frame_sum_squares = 0.0;
+        self.frame_samples = 0;
+    }
+
+    fn take_point(&mut self, frames: usize) -> ActivityPoint {
+        let db = dbfs(self.second_sum_squares, self.second_samples);
+        let level = ((db - FLOOR_DBFS) / -FLOOR_DBFS * 255.0).clamp(0.0, 255.0);
+        let voiced = (self.voiced_frames * 100 / frames.max(1)).min(100);
+        *self = Self::default();
+        ActivityPoint {
+            level: level.round() as u8,
+            voiced: voiced as u8,
+        }
+    }
+}
+
+// ============================================================================
+// Recorder
+// ============================================================================
+
+/// Appends per-second points to `activity.bin` during recording
+pub struct ActivityRecorder {
+    file: std::fs::File,
+    accumulator: ActivityAccumulator,
+    pending_byte: Option<u8>,
+}
+
+impl ActivityRecorder {
+    /// Create `activity.bin` in the session directory (truncating any old one)
+    pub fn create(session_dir: &Path) -> Result<Self> {
+        let path = session_dir.join(ACTIVITY_FILENAME);
+        let mut file = std::fs::File::create(&path)
+            .with_context(|| format!("Failed to create activity file: {:?}", path))?;
+        file.write_all(MAGIC)?;
+        Ok(Self {
+            file,
+            accumulator: ActivityAccumulator::default(),
+            pending_byte: None,
+        })
+    }
+
+    /// Feed 16-bit LE PCM bytes (same batches as `AudioWriter::write_pcm_bytes`)
+    pub fn write_pcm_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        let mut samples = Vec::with_capacity(bytes.len() / 2 + 1);
+        let mut rest = bytes;
+        if let Some(low) = self.pending_byte.take() {
+            if let Some((&high, tail)) = rest.split_first() {
+                samples.push(i16::from_le_bytes([low, high]));
+                rest = tail;
+            } else {
+                self.pending_byte = Some(low);
+            }
+        }
+        let mut chunks = rest.chunks_exact(2);
+        samples.extend(chunks.by_ref().map(|c| i16::from_le_bytes([c[0], c[1]])));
+        if let [odd] = chunks.remainder() {
+            self.pending_byte = Some(*odd);
+        }
+
+        let points = self.accumulator.push(&samples);
+        self.append(&points)
+    }
+
+    /// Flush the trailing partial second
+    pub fn finish(mut self) -> Result<()> {
+        if let Some(point) = self.accumulator.finish() {
+            self.append(&[point])?;
+        }
+        self.file.sync_all()?;
+        Ok(())
+    }
+
+    fn append(&mut self, points: &[ActivityPoint]) -> Result<()> {
+        if points.is_empty() {
+            return Ok(());
+        }
+        let bytes: Vec<u8> = points.iter().flat_map(|p| [p.level, p.voiced]).collect();
+        self.file.write_all(&bytes)?;
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Loading
+// ============================================================================
+
+/// Parse `activity.bin` contents (a trailing partial record is ignored)
+pub fn decode(bytes: &[u8]) -> Result<Vec<ActivityPoint>> {
+    let records = bytes
+        .strip_prefix(MAGIC)
+        .context("Not an activity file (bad magic)")?;
+    Ok(records
+        .chunks_exact(2)
+        .map(|r| ActivityPoint {
+            level: r[0],
+            voiced: r[1],
+        })
+        .collect())
+}
+
+/// Compute activity from a 16kHz mono 16-bit `audio.wav`
+///
+/// Used for sessions recorded before `activity.bin` existed.
+pub fn compute_from_wav(wav_path: &Path) -> Result<Vec<ActivityPoint>> {
+    let mut accumulator = ActivityAccumulator::default();
+    let mut points = Vec::new();
+    crate::storage::read_wav_samples(wav_path, |samples| points.extend(accumulator.push(samples)))
+        .with_context(|| format!("Failed to read audio file: {:?}", wav_path))?;
+    points.extend(accumulator.finish());
+    Ok(points)
+}
+
+/// Write `activity.bin` for a session recorded without one
+pub fn write_activity(session_dir: &Path, points: &[ActivityPoint]) -> Result<()> {
+    let mut bytes = MAGIC.to_vec();
+    bytes.extend(points.iter().flat_map(|p| [p.level, p.voiced]));
+    let path = session_dir.join(ACTIVITY_FILENAME);
+    std::fs::write(&path, bytes)
+        .with_context(|| format!("Failed to write activity file: {:?}", path))
+}
+
+/// Compute activity from the session's audio (WAV, FLAC or Opus)
+pub fn compute_from_session_audio(session_dir: &Path) -> Result<Vec<ActivityPoint>> {
+    let mut accumulator = ActivityAccumulator::default();
+    let mut points = Vec::new();
+    crate::storage::read_session_audio(session_dir, |samples| {
+        points.extend(accumulator.push(samples))
+    })
+    .with_context(|| format!("Failed to read session audio: {:?}", session_dir))?;
+    points.extend(accumulator.finish());
+    Ok(points)
+}
+
+/// Load the session activity, falling back to computing it from the audio
+pub fn load_activity(session_dir: &Path) -> Result<Vec<ActivityPoint>> {
+    let path = session_dir.join(ACTIVITY_FILENAME);
+    if path.exists() {
+        let bytes = std::fs::read(&path)
+            .with_context(|| format!("Failed to read activity file: {:?}", path))?;
+        return decode(&bytes);
+    }
+    compute_from_session_audio(session_dir)
+}
+
+/// Merge per-second points into at most `max_points` buckets
+///
+/// Each bucket keeps the loudest level (so short bursts stay visible) and
+/// the average voiced share.
+pub fn downsample(points: &[ActivityPoint], max_points: usize) -> ActivityTimeline {
+    let bucket = points.len().div_ceil(max_points.max(1)).max(1);
+    ActivityTimeline {
+        resolution_ms: bucket as u64 * 1000,
+        points: points
+            .chunks(bucket)
+            .map(|chunk| ActivityPoint {
+                level: chunk.iter().map(|p| p.level).max().unwrap_or(0),
+                voiced: (chunk.iter().map(|p| p.voiced as usize).sum::<usize>() / chunk.len())
+                    as u8,
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn pcm(samples: &[i16]) -> Vec<u8> {
+        samples.iter().flat_map(|s| s.to_le_bytes()).collect()
+    }
+
+    /// 1.5s: 0.5s silence, 0.5s loud, then 0.5s silence
+    fn test_samples() -> Vec<i16> {
+        let mut samples = vec![0i16; SAMPLE_RATE / 2];
+        samples.extend((0..SAMPLE_RATE / 2).map(|i| if i % 2 == 0 { 8000 } else { -8000 }));
+        samples.extend(vec![0i16; SAMPLE_RATE / 2]);
+        samples
+    }
+
+    #[test]
+    fn test_accumulator_levels_and_voicing() {
+        let mut accumulator = ActivityAccumulator::default();
+        let points = accumulator.push(&test_samples());
+        assert_eq!(points.len(), 1);
+        // Half the second at ~-12 dBFS: RMS over the second is ~-15 dBFS
+        assert!(points[0].level > 180 && points[0].level < 200);
+        assert_eq!(points[0].voiced, 50);
+
+        let tail = accumulator.finish().unwrap();
+        assert_eq!(
+            tail,
+            ActivityPoint {
+                level: 0,
+                voiced: 0
+            }
+        );
+        assert!(accumulator.finish().is_none());
+    }
+
+    #[test]
+    fn test_recorder_matches_wav_fallback() {
+        let temp_dir = TempDir::new().unwrap();
+        let bytes = pcm(&test_samples());
+
+        let mut recorder = ActivityRecorder::create(temp_dir.path()).unwrap();
+        // Odd-sized batches split samples across calls
+        for batch in bytes.chunks(321) {
+            recorder.write_pcm_bytes(batch).unwrap();
+        }
+        recorder.finish().unwrap();
+        let recorded = load_activity(temp_dir.path()).unwrap();
+        assert_eq!(recorded.len(), 2);
+
+        let mut wav = vec![0u8; 44];
+        wav.extend(&bytes);
+        let wav_path = temp_dir.path().join("audio.wav");
+        std::fs::write(&wav_path, wav).unwrap();
+        assert_eq!(compute_from_wav(&wav_path).unwrap(), recorded);
+
+        write_activity(temp_dir.path(), &recorded).unwrap();
+        assert_eq!(load_activity(temp_dir.path()).unwrap(), recorded);
+
+        assert!(decode(b"WAVE\x01\x02").is_err());
+    }
+
+    #[test]
+    fn test_downsample() {
+        let points: Vec<ActivityPoint> = (0..10)
+            .map(|i| ActivityPoint {
+                level: i * 10,
+                voiced: if i % 2 == 0 { 100 } else { 0 },
+            })
+            .collect();
+
+        let timeline = downsample(&points, 4);
+        assert_eq!(timeline.resolution_ms, 3000);
+        assert_eq!(timeline.points.len(), 4);
+        assert_eq!(
+            timeline.points[0],
+            ActivityPoint {
+                level: 20,
+                voiced: 66
+            }
+        );
+        assert_eq!(
+            timeline.points[3],
+            ActivityPoint {
+                level: 90,
+                voiced: 0
+            }
+        );
+
+        assert_eq!(downsample(&points, 100).points, points);
+        assert_eq!(downsample(&[], 10).points, Vec::new());
+    }
+}
@@ -0,0 +1,372 @@
+//! Agenda Tracking
+//!
+//! Records time-coded progress of meeting agenda items ("started"/"finished"
+//! markers) and slices the session transcript by agenda item for exports.
+//!
+//! All timestamps are milliseconds relative to the recording session start,
+//! matching `TranscriptionEvent::timestamp_ms`.
+//!
+//! Persisted to `recordings/<session_id>/agenda.json`.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use thiserror::Error;
+
+use crate::storage::TranscriptionEvent;
+
+const AGENDA_FILENAME: &str = "agenda.json";
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum AgendaError {
+    #[error("Unknown agenda item: {0}")]
+    UnknownItem(String),
+
+    #[error("Agenda item already started: {0}")]
+    AlreadyStarted(String),
+
+    #[error("Agenda item not started: {0}")]
+    NotStarted(String),
+
+    #[error("Agenda item already finished: {0}")]
+    AlreadyFinished(String),
+}
+
+// ============================================================================
+// Agenda Items
+// ============================================================================
+
+/// Single agenda item with its recorded progress
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgendaItem {
+    pub id: String,
+    pub title: String,
+    /// Session-relative start time (None until marked started)
+    #[serde(default)]
+    pub started_at_ms: Option<u64>,
+    /// Session-relative finish time (None until marked finished)
+    #[serde(default)]
+    pub finished_at_ms: Option<u64>,
+}
+
+impl AgendaItem {
+    /// Duration of a finished item
+    pub fn duration_ms(&self) -> Option<u64> {
+        match (self.started_at_ms, self.finished_at_ms) {
+            (Some(start), Some(end)) => Some(end.saturating_sub(start)),
+            _ => None,
+        }
+    }
+
+    /// Whether the item has started but not finished yet
+    pub fn is_active(&self) -> bool {
+        self.started_at_ms.is_some() && self.finished_at_ms.is_none()
+    }
+}
+
+/// Agenda progress tracker
+///
+/// Only one item is active at a time: starting an item finishes the
+/// previously active one at the same timestamp.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgendaTracker {
+    #[serde(default)]
+    pub items: Vec<AgendaItem>,
+}
+
+impl AgendaTracker {
+    /// Create tracker from agenda titles (ids: `item-1`, `item-2`, ...)
+    pub fn from_titles<I, S>(titles: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let items = titles
+            .into_iter()
+            .enumerate()
+            .map(|(i, title)| AgendaItem {
+                id: format!("item-{}", i + 1),
+                title: title.into(),
+                started_at_ms: None,
+                finished_at_ms: None,
+            })
+            .collect();
+        Self { items }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Approximate heap usage (bytes), for the memory sentinel
+    pub fn approx_bytes(&self) -> usize {
+        self.items
+            .iter()
+            .map(|item| {
+                std::mem::size_of::<AgendaItem>() + item.id.capacity() + item.title.capacity()
+            })
+            .sum()
+    }
+
+    /// Currently active item (started, not finished)
+    pub fn active_item(&self) -> Option<&AgendaItem> {
+        self.items.iter().find(|item| item.is_active())
+    }
+
+    /// Mark item as started at `at_ms`
+    ///
+    /// Finishes the currently active item (if any) at the same time.
+    pub fn mark_started(&mut self, item_id: &str, at_ms: u64) -> Result<(), AgendaError> {
+        let index = self.index_of(item_id)?;
+        if self.items[index].started_at_ms.is_some() {
+            return Err(AgendaError::AlreadyStarted(item_id.to_string()));
+        }
+
+        for item in self.items.iter_mut().filter(|item| item.is_active()) {
+            item.finished_at_ms = Some(at_ms.max(item.started_at_ms.unwrap_or(0)));
+        }
+
+        self.items[index].started_at_ms = Some(at_ms);
+        Ok(())
+    }
+
+    /// Mark item as finished at `at_ms`
+    pub fn mark_finished(&mut self, item_id: &str, at_ms: u64) -> Result<(), AgendaError> {
+        let index = self.index_of(item_id)?;
+        let item = &mut self.items[index];
+        let started = item
+            .started_at_ms
+            .ok_or_else(|| AgendaError::NotStarted(item_id.to_string()))?;
+        if item.finished_at_ms.is_some() {
+            return Err(AgendaError::AlreadyFinished(item_id.to_string()));
+        }
+
+        item.finished_at_ms = Some(at_ms.max(started));
+        Ok(())
+    }
+
+    /// Finish the active item (used when the recording session ends)
+    pub fn finish_active(&mut self, at_ms: u64) {
+        for item in self.items.iter_mut().filter(|item| item.is_active()) {
+            item.finished_at_ms = Some(at_ms.max(item.started_at_ms.unwrap_or(0)));
+        }
+    }
+
+    /// Clear recorded progress, keeping the agenda items
+    pub fn reset_progress(&mut self) {
+        for item in &mut self.items {
+            item.started_at_ms = None;
+            item.finished_at_ms = None;
+        }
+    }
+
+    /// Slice transcript by agenda item
+    ///
+    /// A segment belongs to an item when `started_at_ms <= timestamp_ms < finished_at_ms`.
+    /// Active items extend to `end_ms`. Segments outside every item are returned
+    /// in `unassigned`.
+    pub fn slice_transcript(&self, events: &[TranscriptionEvent], end_ms: u64) -> AgendaReport {
+        let mut slices: Vec<AgendaSlice> = self
+            .items
+            .iter()
+            .map(|item| {
+                let finished = item
+                    .started_at_ms
+                    .map(|start| item.finished_at_ms.unwrap_or(end_ms.max(start)));
+                AgendaSlice {
+                    item_id: item.id.clone(),
+                    title: item.title.clone(),
+                    started_at_ms: item.started_at_ms,
+                    finished_at_ms: finished,
+                    duration_ms: item
+                        .started_at_ms
+                        .zip(finished)
+                        .map(|(start, end)| end.saturating_sub(start)),
+                    segments: Vec::new(),
+                }
+            })
+            .collect();
+
+        let mut unassigned = Vec::new();
+        for event in events.iter().filter(|e| e.is_final) {
+            let slot = slices.iter_mut().find(|slice| {
+                matches!(
+                    (slice.started_at_ms, slice.finished_at_ms),
+                    (Some(start), Some(end)) if event.timestamp_ms >= start && event.timestamp_ms < end
+                )
+            });
+            match slot {
+                Some(slice) => slice.segments.push(event.clone()),
+                None => unassigned.push(event.clone()),
+            }
+        }
+
+        AgendaReport {
+            items: slices,
+            unassigned,
+        }
+    }
+
+    fn index_of(&self, item_id: &str) -> Result<usize, AgendaError> {
+        self.items
+            .iter()
+            .position(|item| item.id == item_id)
+            .ok_or_else(|| AgendaError::UnknownItem(item_id.to_string()))
+    }
+}
+
+// ============================================================================
+// Report
+// ============================================================================
+
+/// Transcript segments for a single agenda item
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgendaSlice {
+    pub item_id: String,
+    pub title: String,
+    pub started_at_ms: Option<u64>,
+    pub finished_at_ms: Option<u64>,
+    pub duration_ms: Option<u64>,
+    pub segments: Vec<TranscriptionEvent>,
+}
+
+/// Per-item duration and transcript slices for a session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgendaReport {
+    pub items: Vec<AgendaSlice>,
+    /// Final segments not covered by any agenda item
+    pub unassigned: Vec<TranscriptionEvent>,
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+/// Save agenda progress to `<session_dir>/agenda.json`
+pub fn save_agenda(session_dir: &Path, tracker: &AgendaTracker) -> Result<()> {
+    let path = session_dir.join(AGENDA_FILENAME);
+    let json = serde_json::to_string_pretty(tracker).context("Failed to serialize agenda")?;
+    crate::storage::write_file_owner_only(&path, json.as_bytes())
+        .with_context(|| format!("Failed to write agenda file: {:?}", path))
+}
+
+/// Load agenda progress from `<session_dir>/agenda.json`
+///
+/// Returns an empty agenda if the file doesn't exist.
+pub fn load_agenda(session_dir: &Path) -> Result<AgendaTracker> {
+    let path = session_dir.join(AGENDA_FILENAME);
+    if !path.exists() {
+        return Ok(AgendaTracker::default());
+    }
+
+    let json = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read agenda file: {:?}", path))?;
+    serde_json::from_str(&json).context("Failed to parse agenda file")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn final_event(timestamp_ms: u64, text: &str) -> TranscriptionEvent {
+        TranscriptionEvent {
+            timestamp_ms,
+            text: text.to_string(),
+            is_final: true,
+            speaker: None,
+        }
+    }
+
+    #[test]
+    fn test_from_titles_assigns_ids() {
+        let tracker = AgendaTracker::from_titles(["Opening", "Budget"]);
+        assert_eq!(tracker.items.len(), 2);
+        assert_eq!(tracker.items[0].id, "item-1");
+        assert_eq!(tracker.items[1].title, "Budget");
+        assert!(tracker.active_item().is_none());
+    }
+
+    #[test]
+    fn test_starting_next_item_finishes_active() {
+        let mut tracker = AgendaTracker::from_titles(["Opening", "Budget"]);
+        tracker.mark_started("item-1", 1_000).unwrap();
+        tracker.mark_started("item-2", 61_000).unwrap();
+
+        assert_eq!(tracker.items[0].duration_ms(), Some(60_000));
+        assert_eq!(tracker.active_item().unwrap().id, "item-2");
+    }
+
+    #[test]
+    fn test_mark_errors() {
+        let mut tracker = AgendaTracker::from_titles(["Opening"]);
+        assert_eq!(
+            tracker.mark_finished("item-1", 10),
+            Err(AgendaError::NotStarted("item-1".to_string()))
+        );
+        assert_eq!(
+            tracker.mark_started("item-9", 10),
+            Err(AgendaError::UnknownItem("item-9".to_string()))
+        );
+
+        tracker.mark_started("item-1", 10).unwrap();
+        assert_eq!(
+            tracker.mark_started("item-1", 20),
+            Err(AgendaError::AlreadyStarted("item-1".to_string()))
+        );
+        tracker.mark_finished("item-1", 30).unwrap();
+        assert_eq!(
+            tracker.mark_finished("item-1", 40),
+            Err(AgendaError::AlreadyFinished("item-1".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_slice_transcript_by_item() {
+        let mut tracker = AgendaTracker::from_titles(["Opening", "Budget", "Wrap-up"]);
+        tracker.mark_started("item-1", 1_000).unwrap();
+        tracker.mark_started("item-2", 5_000).unwrap();
+
+        let events = vec![
+            final_event(500, "before agenda"),
+            final_event(1_000, "welcome"),
+            TranscriptionEvent {
+                timestamp_ms: 2_000,
+                text: "partial".to_string(),
+                is_final: false,
+                speaker: None,
+            },
+            final_event(5_000, "budget review"),
+            final_event(9_000, "still budget"),
+        ];
+
+        let report = tracker.slice_transcript(&events, 10_000);
+        assert_eq!(report.items[0].segments.len(), 1);
+        assert_eq!(report.items[0].duration_ms, Some(4_000));
+        // Active item extends to end of session
+        assert_eq!(report.items[1].segments.len(), 2);
+        assert_eq!(report.items[1].duration_ms, Some(5_000));
+        // Not started item has no duration
+        assert!(report.items[2].duration_ms.is_none());
+        assert_eq!(report.unassigned.len(), 1);
+        assert_eq!(report.unassigned[0].text, "before agenda");
+    }
+
+    #[test]
+    fn test_save_and_load_agenda() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut tracker = AgendaTracker::from_titles(["Opening"]);
+        tracker.mark_started("item-1", 100).unwrap();
+
+        save_agenda(temp_dir.path(), &tracker).unwrap();
+        let loaded = load_agenda(temp_dir.path()).unwrap();
+        assert_eq!(loaded, tracker);
+    }
+
+    #[test]
+    fn test_load_agenda_missing_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let loaded = load_agenda(temp_dir.path()).unwrap();
+        assert!(loaded.is_empty());
+    }
+}
@@ -0,0 +1,384 @@
+//! Application Health and Watchdog
+//!
+//! For running the app unattended under a service supervisor (systemd,
+//! launchd). The health of the process is evaluated from a few checks:
+//!
+//! | Check     | ok                      | degraded                     | unhealthy                      |
+//! |-----------|-------------------------|------------------------------|--------------------------------|
+//! | `sidecar` | ready                   | starting, or falling behind  | failed to start, or exited     |
+//! | `storage` | recordings dir writable | not initialized, disk low    | recordings dir not writable    |
+//!
+//! In viewer mode the sidecar isn't started and its check is skipped. The
+//! sidecar is started once, so a failed or exited sidecar doesn't recover
+//! without a restart of the app.
+//!
+//! The watchdog re-evaluates every `CHECK_INTERVAL`, logs status changes and
+//! keeps the last report for `GET /health` on the local HTTP API (200 for
+//! ok and degraded, 503 for unhealthy). With `exit_on_unhealthy`, a status
+//! that stays unhealthy for `unhealthy_grace_secs` ends the process with
+//! `EXIT_UNHEALTHY` so the supervisor restarts it.
+//!
+//! Settings are machine-wide; persisted to `settings/health.json` in app
+//! data directory.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::storage::DiskSpaceStatus;
+
+/// Interval between watchdog evaluations
+pub const CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Exit code when the watchdog gives up (EX_SOFTWARE)
+pub const EXIT_UNHEALTHY: i32 = 70;
+
+/// File written and removed by the storage check
+const PROBE_FILENAME: &str = ".health_probe";
+
+/// Watchdog configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HealthSettings {
+    /// Exit when unhealthy for longer than the grace period
+    #[serde(default)]
+    pub exit_on_unhealthy: bool,
+    /// Seconds the status may stay unhealthy before exiting
+    #[serde(default = "default_unhealthy_grace_secs")]
+    pub unhealthy_grace_secs: u64,
+    /// Settings version for future migrations
+    #[serde(default = "default_version")]
+    pub version: u32,
+}
+
+fn default_unhealthy_grace_secs() -> u64 {
+    60
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+impl Default for HealthSettings {
+    fn default() -> Self {
+        Self {
+            exit_on_unhealthy: false,
+            unhealthy_grace_secs: default_unhealthy_grace_secs(),
+            version: 1,
+        }
+    }
+}
+
+impl HealthSettings {
+    pub fn validate(&self) -> Result<()> {
+        // A few checks must be able to run before giving up
+        if self.unhealthy_grace_secs < CHECK_INTERVAL.as_secs() {
+            anyhow::bail!(
+                "unhealthy_grace_secs must be at least {}",
+                CHECK_INTERVAL.as_secs()
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Status of a check or of the whole process (worst check)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Ok,
+    Degraded,
+    Unhealthy,
+}
+
+impl HealthStatus {
+    /// HTTP status of `GET /health`
+    pub fn http_status(&self) -> u16 {
+        match self {
+            HealthStatus::Ok | HealthStatus::Degraded => 200,
+            HealthStatus::Unhealthy => 503,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HealthCheck {
+    pub name: String,
+    pub status: HealthStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl HealthCheck {
+    fn new(name: &str, status: HealthStatus, detail: Option<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status,
+            detail,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HealthReport {
+    pub status: HealthStatus,
+    pub checks: Vec<HealthCheck>,
+    /// Unix ms of the evaluation
+    pub checked_at: u64,
+}
+
+impl HealthReport {
+    /// Report before the first evaluation
+    pub fn starting(now_ms: u64) -> Self {
+        Self {
+            status: HealthStatus::Degraded,
+            checks: vec![HealthCheck::new(
+                "startup",
+                HealthStatus::Degraded,
+                Some("not checked yet".to_string()),
+            )],
+            checked_at: now_ms,
+        }
+    }
+}
+
+/// Sidecar state as far as the bootstrap got
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum SidecarStartup {
+    #[default]
+    Pending,
+    Ready,
+    Failed(String),
+}
+
+/// Result of the storage check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StorageProbe {
+    NotInitialized,
+    Writable(Option<DiskSpaceStatus>),
+    Unwritable(String),
+}
+
+/// State the report is evaluated from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HealthInputs {
+    pub viewer_mode: bool,
+    pub sidecar_startup: SidecarStartup,
+    /// The started sidecar process has exited
+    pub sidecar_exited: bool,
+    /// Last health check reply says the sidecar is falling behind
+    pub sidecar_slow: bool,
+    pub storage: StorageProbe,
+}
+
+pub fn evaluate(inputs: &HealthInputs, now_ms: u64) -> HealthReport {
+    let mut checks = Vec::new();
+
+    if !inputs.viewer_mode {
+        let (status, detail) = match &inputs.sidecar_startup {
+            SidecarStartup::Failed(reason) => (HealthStatus::Unhealthy, Some(reason.clone())),
+            SidecarStartup::Pending => (HealthStatus::Degraded, Some("starting".to_string())),
+            SidecarStartup::Ready if inputs.sidecar_exited => {
+                (HealthStatus::Unhealthy, Some("process exited".to_string()))
+            }
+            SidecarStartup::Ready if inputs.sidecar_slow => (
+                HealthStatus::Degraded,
+                Some("transcription is falling behind".to_string()),
+            ),
+            SidecarStartup::Ready => (HealthStatus::Ok, None),
+        };
+        checks.push(HealthCheck::new("sidecar", status, detail));
+    }
+
+    let (status, detail) = match &inputs.storage {
+        StorageProbe::NotInitialized => (
+            HealthStatus::Degraded,
+            Some("storage not initialized".to_string()),
+        ),
+        StorageProbe::Unwritable(reason) => (HealthStatus::Unhealthy, Some(reason.clone())),
+        StorageProbe::Writable(Some(
+            disk @ (DiskSpaceStatus::Warning | DiskSpaceStatus::Critical),
+        )) => (HealthStatus::Degraded, Some(disk.to_string())),
+        StorageProbe::Writable(_) => (HealthStatus::Ok, None),
+    };
+    checks.push(HealthCheck::new("storage", status, detail));
+
+    HealthReport {
+        status: checks
+            .iter()
+            .map(|check| check.status)
+            .max()
+            .unwrap_or(HealthStatus::Ok),
+        checks,
+        checked_at: now_ms,
+    }
+}
+
+/// Write and remove a probe file in `dir` (created if missing)
+pub fn probe_writable(dir: &Path) -> Result<()> {
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create directory: {:?}", dir))?;
+    let probe = dir.join(PROBE_FILENAME);
+    std::fs::write(&probe, b"ok")
+        .with_context(|| format!("Failed to write probe file: {:?}", probe))?;
+    std::fs::remove_file(&probe)
+        .with_context(|| format!("Failed to remove probe file: {:?}", probe))?;
+    Ok(())
+}
+
+/// Tracks how long the process has been unhealthy
+#[derive(Debug, Default)]
+pub struct Watchdog {
+    last_status: Option<HealthStatus>,
+    unhealthy_since: Option<Instant>,
+}
+
+/// Outcome of one watchdog evaluation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WatchdogVerdict {
+    /// The status differs from the previous evaluation (or from ok)
+    pub changed: bool,
+    /// Unhealthy beyond the grace period with `exit_on_unhealthy` set
+    pub exit: bool,
+}
+
+impl Watchdog {
+    pub fn observe(
+        &mut self,
+        status: HealthStatus,
+        now: Instant,
+        settings: &HealthSettings,
+    ) -> WatchdogVerdict {
+        // Starting out as anything but ok counts as a change
+        let changed = self.last_status.unwrap_or(HealthStatus::Ok) != status;
+        self.last_status = Some(status);
+        if status != HealthStatus::Unhealthy {
+            self.unhealthy_since = None;
+            return WatchdogVerdict {
+                changed,
+                exit: false,
+            };
+        }
+        let since = *self.unhealthy_since.get_or_insert(now);
+        let grace = Duration::from_secs(settings.unhealthy_grace_secs);
+        WatchdogVerdict {
+            changed,
+            exit: settings.exit_on_unhealthy && now.duration_since(since) >= grace,
+        }
+    }
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+pub const SETTINGS_STEM: &str = "health";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn inputs(sidecar_startup: SidecarStartup, storage: StorageProbe) -> HealthInputs {
+        HealthInputs {
+            viewer_mode: false,
+            sidecar_startup,
+            sidecar_exited: false,
+            sidecar_slow: false,
+            storage,
+        }
+    }
+
+    #[test]
+    fn test_evaluate_takes_worst_check() {
+        let writable = StorageProbe::Writable(Some(DiskSpaceStatus::Sufficient));
+        let ok = evaluate(&inputs(SidecarStartup::Ready, writable.clone()), 1);
+        assert_eq!(ok.status, HealthStatus::Ok);
+        assert_eq!(ok.checks.len(), 2);
+
+        let mut slow = inputs(SidecarStartup::Ready, writable.clone());
+        slow.sidecar_slow = true;
+        assert_eq!(evaluate(&slow, 1).status, HealthStatus::Degraded);
+        let low_disk = StorageProbe::Writable(Some(DiskSpaceStatus::Warning));
+        assert_eq!(
+            evaluate(&inputs(SidecarStartup::Ready, low_disk), 1).status,
+            HealthStatus::Degraded
+        );
+
+        let mut exited = inputs(SidecarStartup::Ready, StorageProbe::NotInitialized);
+        exited.sidecar_exited = true;
+        let report = evaluate(&exited, 1);
+        assert_eq!(report.status, HealthStatus::Unhealthy);
+        assert_eq!(report.status.http_status(), 503);
+        assert_eq!(report.checks[0].detail.as_deref(), Some("process exited"));
+
+        let unwritable = StorageProbe::Unwritable("read-only".to_string());
+        assert_eq!(
+            evaluate(&inputs(SidecarStartup::Pending, unwritable), 1).status,
+            HealthStatus::Unhealthy
+        );
+
+        // Viewer mode has no sidecar to check
+        let mut viewer = inputs(SidecarStartup::Failed("no python".to_string()), writable);
+        viewer.viewer_mode = true;
+        let report = evaluate(&viewer, 1);
+        assert_eq!(report.status, HealthStatus::Ok);
+        assert_eq!(report.checks.len(), 1);
+
+        let dir = TempDir::new().unwrap();
+        probe_writable(&dir.path().join("recordings")).unwrap();
+        assert!(!dir.path().join("recordings").join(PROBE_FILENAME).exists());
+    }
+
+    #[test]
+    fn test_watchdog_exits_after_grace_period() {
+        let now = Instant::now();
+        let settings = HealthSettings {
+            exit_on_unhealthy: true,
+            unhealthy_grace_secs: 30,
+            ..Default::default()
+        };
+        settings.validate().unwrap();
+        let mut watchdog = Watchdog::default();
+
+        let first = watchdog.observe(HealthStatus::Ok, now, &settings);
+        assert!(!first.changed && !first.exit);
+        let down = watchdog.observe(HealthStatus::Unhealthy, now, &settings);
+        assert!(down.changed && !down.exit);
+        let later = now + Duration::from_secs(20);
+        assert!(
+            !watchdog
+                .observe(HealthStatus::Unhealthy, later, &settings)
+                .exit
+        );
+
+        // Recovery restarts the grace period
+        watchdog.observe(HealthStatus::Degraded, later, &settings);
+        let again = now + Duration::from_secs(40);
+        assert!(
+            !watchdog
+                .observe(HealthStatus::Unhealthy, again, &settings)
+                .exit
+        );
+        let verdict = watchdog.observe(
+            HealthStatus::Unhealthy,
+            again + Duration::from_secs(30),
+            &settings,
+        );
+        assert!(verdict.exit && !verdict.changed);
+
+        // Without exit_on_unhealthy the watchdog only reports
+        let report_only = HealthSettings::default();
+        assert!(
+            !Watchdog::default()
+                .observe(HealthStatus::Unhealthy, now, &report_only)
+                .exit
+        );
+        assert!(HealthSettings {
+            unhealthy_grace_secs: 1,
+            ..Default::default()
+        }
+        .validate()
+        .is_err());
+    }
+}
@@ -192,6 +192,7 @@ impl FakeAudioDevice {
                 name: "Fake Microphone".to_string(),
                 sample_rate: 16000,
                 channels: 1,
+                max_channels: 1,
                 is_loopback: false,
             },
             AudioDeviceInfo {
@@ -199,6 +200,7 @@ impl FakeAudioDevice {
                 name: "Fake BlackHole 2ch".to_string(),
                 sample_rate: 16000,
                 channels: 2,
+                max_channels: 2,
                 is_loopback: true,
             },
         ])
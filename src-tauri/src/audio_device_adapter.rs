@@ -3,19 +3,12 @@
 // Requirement: STT-REQ-001 (Real Audio Device Management)
 
 use anyhow::{anyhow, Result};
+use bytes::Bytes;
 use serde::{Deserialize, Serialize};
 use std::sync::{mpsc, Arc, Mutex};
 use std::thread::JoinHandle;
 use std::time::{Duration, Instant};
 
-// OS-specific imports
-#[cfg(target_os = "macos")]
-use cpal::traits::{DeviceTrait, HostTrait};
-
-#[cfg(target_os = "windows")]
-use cpal::traits::{DeviceTrait, HostTrait};
-
-#[cfg(target_os = "linux")]
 use cpal::traits::{DeviceTrait, HostTrait};
 
 // ============================================================================
@@ -60,14 +53,20 @@ pub struct AudioDeviceInfo {
     pub sample_rate: u32,
     /// Number of channels (1 = mono, 2 = stereo)
     pub channels: u16,
+    /// Input channels available for channel selection (largest supported
+    /// config; 0 when unknown)
+    #[serde(default)]
+    pub max_channels: u16,
     /// Whether this is a loopback/virtual device
     /// Requirement: STT-REQ-004.6, STT-REQ-004.7, STT-REQ-004.8
     pub is_loopback: bool,
 }
 
 /// Audio chunk callback type
-/// Receives Vec<u8> containing 16kHz mono PCM audio data (320 samples = 20ms)
-pub type AudioChunkCallback = Box<dyn Fn(Vec<u8>) + Send + Sync>;
+/// Receives 16kHz mono PCM audio data (320 samples = 20ms)
+/// `Bytes` is reference-counted: consumers that fan out a chunk clone the
+/// handle instead of the samples
+pub type AudioChunkCallback = Box<dyn Fn(Bytes) + Send + Sync>;
 
 /// Check if a device name indicates a loopback/virtual audio device
 /// Requirement: STT-REQ-004.6, STT-REQ-004.7, STT-REQ-004.8
@@ -123,13 +122,170 @@ pub trait AudioDeviceAdapter: Send + Sync {
 }
 
 // ============================================================================
-// OS-Specific Implementations
+// Capture Host Abstraction
 // ============================================================================
 
-/// macOS CoreAudio adapter with device monitoring
-/// Task 2.5: Device disconnection detection and auto-reconnect
-#[cfg(target_os = "macos")]
-pub struct CoreAudioAdapter {
+/// Data callback of a host input stream (interleaved `f32` samples)
+pub type HostDataCallback = Box<dyn FnMut(&[f32]) + Send>;
+
+/// Error callback of a host input stream
+pub type HostErrorCallback = Box<dyn FnMut(String) + Send>;
+
+/// Parameters of an opened host input stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HostStreamConfig {
+    pub sample_rate: u32,
+    pub channels: u16,
+}
+
+/// Input stream opened by a `CaptureHost`, not yet running
+pub trait HostInput: Send {
+    fn config(&self) -> HostStreamConfig;
+
+    /// Run the stream on the calling thread until `shutdown` receives a
+    /// message or its sender is dropped
+    fn run(
+        self: Box<Self>,
+        on_data: HostDataCallback,
+        on_error: HostErrorCallback,
+        shutdown: mpsc::Receiver<()>,
+    ) -> Result<()>;
+}
+
+/// Audio host the adapters capture through
+///
+/// `CpalHost` in production; tests script a fake host so conversion,
+/// watchdog and shutdown run on CI without audio hardware.
+pub trait CaptureHost: Send + Sync {
+    fn enumerate_devices(&self) -> Result<Vec<AudioDeviceInfo>>;
+
+    /// Open `device_id` with at least `min_channels` input channels
+    fn open_input(&self, device_id: &str, min_channels: u16) -> Result<Box<dyn HostInput>>;
+
+    /// Whether `device_id` is still present (device polling)
+    fn device_exists(&self, device_id: &str) -> bool {
+        self.enumerate_devices()
+            .map(|devices| devices.iter().any(|device| device.id == device_id))
+            .unwrap_or(false)
+    }
+}
+
+/// The platform's default cpal host (CoreAudio, WASAPI or ALSA)
+pub struct CpalHost;
+
+impl CpalHost {
+    fn find_device(device_id: &str) -> Result<cpal::Device> {
+        cpal::default_host()
+            .input_devices()?
+            .find(|d| d.name().ok().as_deref() == Some(device_id))
+            .ok_or_else(|| anyhow!("Device not found: {}", device_id))
+    }
+}
+
+impl CaptureHost for CpalHost {
+    fn enumerate_devices(&self) -> Result<Vec<AudioDeviceInfo>> {
+        let host = cpal::default_host();
+        let mut devices = Vec::new();
+
+        for device in host.input_devices()? {
+            let name = device.name()?;
+            let default_config = device.default_input_config()?;
+
+            devices.push(AudioDeviceInfo {
+                id: name.clone(), // Use name as ID for now
+                name: name.clone(),
+                sample_rate: default_config.sample_rate().0,
+                channels: default_config.channels(),
+                max_channels: crate::input_format::max_input_channels(&device)
+                    .unwrap_or_else(|_| default_config.channels()),
+                is_loopback: is_loopback_device(&name),
+            });
+        }
+
+        Ok(devices)
+    }
+
+    fn open_input(&self, device_id: &str, min_channels: u16) -> Result<Box<dyn HostInput>> {
+        let device = Self::find_device(device_id)?;
+        // Default rate and channels (enough for the selected inputs), in a
+        // sample format the device supports
+        let config = crate::input_format::select_input_config(&device, min_channels)?;
+        Ok(Box::new(CpalInput { device, config }))
+    }
+
+    fn device_exists(&self, device_id: &str) -> bool {
+        Self::find_device(device_id).is_ok()
+    }
+}
+
+struct CpalInput {
+    device: cpal::Device,
+    config: cpal::SupportedStreamConfig,
+}
+
+impl HostInput for CpalInput {
+    fn config(&self) -> HostStreamConfig {
+        HostStreamConfig {
+            sample_rate: self.config.sample_rate().0,
+            channels: self.config.channels(),
+        }
+    }
+
+    fn run(
+        self: Box<Self>,
+        on_data: HostDataCallback,
+        mut on_error: HostErrorCallback,
+        shutdown: mpsc::Receiver<()>,
+    ) -> Result<()> {
+        use cpal::traits::StreamTrait;
+
+        let stream = crate::input_format::build_f32_input_stream(
+            &self.device,
+            &self.config,
+            on_data,
+            move |err| on_error(format!("{:?}", err)),
+        )?;
+        stream.play()?;
+        // Stream is dropped on return (reliable cleanup)
+        shutdown.recv().ok();
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Shared Capture (conversion, watchdog, shutdown)
+// ============================================================================
+
+/// Watchdog and device polling intervals
+#[derive(Debug, Clone, Copy)]
+pub struct CaptureTimings {
+    /// How often the watchdog checks the last callback time
+    pub watchdog_interval: Duration,
+    /// Silence after which the stream counts as stalled
+    pub stall_threshold: Duration,
+    /// How often the device's presence is checked
+    pub poll_interval: Duration,
+}
+
+impl Default for CaptureTimings {
+    fn default() -> Self {
+        Self {
+            watchdog_interval: Duration::from_millis(250),
+            stall_threshold: Duration::from_millis(1200),
+            poll_interval: Duration::from_secs(3),
+        }
+    }
+}
+
+/// Recording through a `CaptureHost`, shared by the OS adapters
+///
+/// Converts the host's audio to 16kHz mono chunks (STTMIX-REQ-003), runs
+/// the liveness watchdog and device polling (Task 2.5) and joins every
+/// thread on stop.
+pub struct HostCapture {
+    host: Arc<dyn CaptureHost>,
+    timings: CaptureTimings,
+
     /// Recording state
     is_recording: bool,
 
@@ -157,10 +313,11 @@ pub struct CoreAudioAdapter {
     polling_shutdown_tx: Option<mpsc::Sender<()>>,
 }
 
-#[cfg(target_os = "macos")]
-impl CoreAudioAdapter {
-    pub fn new() -> Self {
+impl HostCapture {
+    pub fn new(host: Arc<dyn CaptureHost>) -> Self {
         Self {
+            host,
+            timings: CaptureTimings::default(),
             is_recording: false,
             device_id: None,
             stream_thread: None,
@@ -174,35 +331,114 @@ impl CoreAudioAdapter {
         }
     }
 
+    pub fn with_timings(mut self, timings: CaptureTimings) -> Self {
+        self.timings = timings;
+        self
+    }
+
     /// Set event sender for device monitoring
-    /// Must be called before start_recording to enable monitoring
+    /// Must be called before start to enable monitoring
     pub fn set_event_sender(&mut self, tx: AudioEventSender) {
         self.event_tx = Some(tx);
     }
 
-    /// Start liveness watchdog thread (Task 2.5: STT-REQ-004.9)
-    /// Monitors last_callback timestamp and fires Stalled event if threshold exceeded
-    /// Returns shutdown sender for thread coordination
-    fn start_watchdog(&mut self) -> mpsc::Sender<()> {
-        let last_cb = Arc::clone(&self.last_callback);
-        let (shutdown_tx, shutdown_rx) = mpsc::channel();
+    pub fn event_sender(&self) -> Option<AudioEventSender> {
+        self.event_tx.clone()
+    }
+
+    /// Liveness timestamp, for streams run outside the host
+    pub fn liveness(&self) -> Arc<Mutex<Instant>> {
+        Arc::clone(&self.last_callback)
+    }
+
+    pub fn enumerate_devices(&self) -> Result<Vec<AudioDeviceInfo>> {
+        self.host.enumerate_devices()
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.is_recording
+    }
+
+    /// Open `device_id` on the host and deliver its audio to `callback`
+    ///
+    /// Returns the stream parameters.
+    pub fn start(
+        &mut self,
+        device_id: &str,
+        callback: AudioChunkCallback,
+    ) -> Result<HostStreamConfig> {
+        if self.is_recording {
+            return Err(anyhow!("Already recording"));
+        }
+
+        // Inputs chosen on multi-channel interfaces (empty: downmix all)
+        let inputs = crate::channel_selection::inputs_for(device_id);
+        let input = self.host.open_input(
+            device_id,
+            crate::channel_selection::required_channels(&inputs),
+        )?;
+        let config = input.config();
+
+        // Validate sample rate supports accurate 16kHz downsampling
+        crate::resampler::validate_sample_rate(config.sample_rate)
+            .map_err(|e| anyhow!("Unsupported audio device '{}': {}", device_id, e))?;
+
+        eprintln!(
+            "📊 Audio config: {}Hz, {} channel(s), inputs {:?} -> 16kHz mono",
+            config.sample_rate, config.channels, inputs
+        );
+
+        // Liveness tracking (Task 2.5)
+        *self.last_callback.lock().unwrap() = Instant::now();
+
+        let on_data = chunk_converter(config, &inputs, self.liveness(), callback);
         let event_tx = self.event_tx.clone();
+        let on_error: HostErrorCallback = Box::new(move |err| {
+            // Send error event (Task 2.5: STT-REQ-004.9)
+            if let Some(tx) = &event_tx {
+                tx.send(AudioDeviceEvent::StreamError(err.clone())).ok();
+            }
+            eprintln!("❌ Audio stream error: {}", err);
+        });
 
-        let handle = std::thread::spawn(move || {
-            const CHECK_INTERVAL_MS: u64 = 250;
-            const STALL_THRESHOLD_MS: u128 = 1200;
+        // Spawn stream thread (Task 2.5: reliable cleanup)
+        let (shutdown_tx, shutdown_rx) = mpsc::channel();
+        let stream_thread = std::thread::spawn(move || {
+            if let Err(e) = input.run(on_data, on_error, shutdown_rx) {
+                eprintln!("❌ Failed to run audio stream: {:#}", e);
+            }
+        });
+        self.stream_thread = Some(stream_thread);
+        self.stream_shutdown_tx = Some(shutdown_tx);
 
-            loop {
-                // Check for shutdown signal
-                if shutdown_rx.try_recv().is_ok() {
-                    break;
-                }
+        self.start_monitoring(device_id);
+        Ok(config)
+    }
+
+    /// Watch a stream run outside the host (it must touch `liveness`)
+    pub fn start_monitoring(&mut self, device_id: &str) {
+        self.device_id = Some(device_id.to_string());
+        self.watchdog_shutdown_tx = Some(self.start_watchdog());
+        self.polling_shutdown_tx = Some(self.start_device_polling());
+        self.is_recording = true;
+    }
 
-                std::thread::sleep(Duration::from_millis(CHECK_INTERVAL_MS));
+    /// Start liveness watchdog thread (Task 2.5: STT-REQ-004.9)
+    /// Monitors last_callback timestamp and fires one Stalled event when the
+    /// threshold is exceeded
+    fn start_watchdog(&mut self) -> mpsc::Sender<()> {
+        let last_cb = self.liveness();
+        let event_tx = self.event_tx.clone();
+        let timings = self.timings;
+        let (shutdown_tx, shutdown_rx) = mpsc::channel();
 
+        let handle = std::thread::spawn(move || {
+            // Ends on the shutdown signal (or when the sender is dropped)
+            while let Err(mpsc::RecvTimeoutError::Timeout) =
+                shutdown_rx.recv_timeout(timings.watchdog_interval)
+            {
                 let elapsed = last_cb.lock().unwrap().elapsed();
-
-                if elapsed.as_millis() > STALL_THRESHOLD_MS {
+                if elapsed > timings.stall_threshold {
                     if let Some(tx) = &event_tx {
                         tx.send(AudioDeviceEvent::Stalled {
                             elapsed_ms: elapsed.as_millis() as u64,
@@ -224,34 +460,17 @@ impl CoreAudioAdapter {
 
     /// Start device polling thread (Task 2.5: STT-REQ-004.9)
     /// Periodically checks if device still exists, fires DeviceGone event if not found
-    /// Returns shutdown sender for thread coordination
     fn start_device_polling(&mut self) -> mpsc::Sender<()> {
-        let device_id = self.device_id.clone().unwrap();
-        let (shutdown_tx, shutdown_rx) = mpsc::channel();
+        let device_id = self.device_id.clone().unwrap_or_default();
+        let host = Arc::clone(&self.host);
         let event_tx = self.event_tx.clone();
+        let poll_interval = self.timings.poll_interval;
+        let (shutdown_tx, shutdown_rx) = mpsc::channel();
 
         let handle = std::thread::spawn(move || {
-            const POLL_INTERVAL_SEC: u64 = 3;
-
-            loop {
-                // Check for shutdown signal
-                if shutdown_rx.try_recv().is_ok() {
-                    break;
-                }
-
-                std::thread::sleep(Duration::from_secs(POLL_INTERVAL_SEC));
-
-                // Check device existence
-                let host = cpal::default_host();
-                let exists = host
-                    .input_devices()
-                    .ok()
-                    .and_then(|mut devices| {
-                        devices.find(|d| d.name().ok().as_ref() == Some(&device_id))
-                    })
-                    .is_some();
-
-                if !exists {
+            while let Err(mpsc::RecvTimeoutError::Timeout) = shutdown_rx.recv_timeout(poll_interval)
+            {
+                if !host.device_exists(&device_id) {
                     if let Some(tx) = &event_tx {
                         tx.send(AudioDeviceEvent::DeviceGone {
                             device_id: device_id.clone(),
@@ -267,33 +486,112 @@ impl CoreAudioAdapter {
         self.polling_handle = Some(handle);
         shutdown_tx
     }
+
+    /// Stop the stream and monitoring, joining their threads
+    pub fn stop(&mut self) {
+        // Signal all threads to stop (Task 2.5: reliable cleanup)
+        for tx in [
+            self.stream_shutdown_tx.take(),
+            self.watchdog_shutdown_tx.take(),
+            self.polling_shutdown_tx.take(),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            tx.send(()).ok();
+        }
+
+        // Join stream thread (stream will be dropped), watchdog and polling
+        for handle in [
+            self.stream_thread.take(),
+            self.watchdog_handle.take(),
+            self.polling_handle.take(),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            handle.join().ok();
+        }
+
+        // Reset state
+        self.is_recording = false;
+        self.device_id = None;
+    }
 }
 
+/// Data callback turning host audio into 16kHz mono chunks for `callback`
+fn chunk_converter(
+    config: HostStreamConfig,
+    inputs: &[u16],
+    last_callback: Arc<Mutex<Instant>>,
+    callback: AudioChunkCallback,
+) -> HostDataCallback {
+    // The resampler's downmix only understands stereo: mix wider streams here
+    let mut selector = if inputs.is_empty() && config.channels > 2 {
+        let all: Vec<u16> = (1..=config.channels).collect();
+        crate::channel_selection::ChannelSelector::new(&all)
+    } else {
+        crate::channel_selection::ChannelSelector::new(inputs)
+    };
+
+    Box::new(move |data: &[f32]| {
+        // Update liveness timestamp (Task 2.5)
+        *last_callback.lock().unwrap() = Instant::now();
+
+        // Selected inputs only, already mono
+        let (data, channels) = match selector.as_mut() {
+            Some(selector) => (selector.apply(data, config.channels), 1),
+            None => (data, config.channels),
+        };
+
+        // STTMIX-REQ-003: Normalize to 16kHz mono using resampler module
+        let pcm_data =
+            crate::resampler::process_audio_to_16khz_mono(data, channels, config.sample_rate);
+
+        callback(Bytes::from(pcm_data));
+    })
+}
+
+// ============================================================================
+// OS-Specific Implementations
+// ============================================================================
+
+/// macOS CoreAudio adapter with device monitoring
+/// Task 2.5: Device disconnection detection and auto-reconnect
 #[cfg(target_os = "macos")]
-impl AudioDeviceAdapter for CoreAudioAdapter {
-    fn enumerate_devices(&self) -> Result<Vec<AudioDeviceInfo>> {
-        let host = cpal::default_host();
-        let mut devices = Vec::new();
+pub struct CoreAudioAdapter {
+    /// Stream, liveness watchdog and device polling
+    capture: HostCapture,
 
-        for device in host.input_devices()? {
-            let name = device.name()?;
-            let default_config = device.default_input_config()?;
+    /// Set by the `start_recording` stub (Task 2.3)
+    stub_recording: bool,
+}
 
-            devices.push(AudioDeviceInfo {
-                id: name.clone(), // Use name as ID for now
-                name: name.clone(),
-                sample_rate: default_config.sample_rate().0,
-                channels: default_config.channels(),
-                is_loopback: is_loopback_device(&name),
-            });
+#[cfg(target_os = "macos")]
+impl CoreAudioAdapter {
+    pub fn new() -> Self {
+        Self {
+            capture: HostCapture::new(Arc::new(CpalHost)),
+            stub_recording: false,
         }
+    }
 
-        Ok(devices)
+    /// Set event sender for device monitoring
+    /// Must be called before start_recording to enable monitoring
+    pub fn set_event_sender(&mut self, tx: AudioEventSender) {
+        self.capture.set_event_sender(tx);
+    }
+}
+
+#[cfg(target_os = "macos")]
+impl AudioDeviceAdapter for CoreAudioAdapter {
+    fn enumerate_devices(&self) -> Result<Vec<AudioDeviceInfo>> {
+        self.capture.enumerate_devices()
     }
 
     fn start_recording(&mut self, _device_id: &str) -> Result<()> {
         // TODO: Implement CoreAudio recording (Task 2.3)
-        self.is_recording = true;
+        self.stub_recording = true;
         Ok(())
     }
 
@@ -302,167 +600,18 @@ impl AudioDeviceAdapter for CoreAudioAdapter {
         device_id: &str,
         callback: AudioChunkCallback,
     ) -> Result<()> {
-        if self.is_recording {
-            return Err(anyhow!("Already recording"));
-        }
-
-        let host = cpal::default_host();
-
-        // Find device by ID
-        let device = host
-            .input_devices()?
-            .find(|d| d.name().ok().as_ref() == Some(&device_id.to_string()))
-            .ok_or_else(|| anyhow!("Device not found: {}", device_id))?;
-
-        let config = device.default_input_config()?;
-
-        // Extract sample rate and channels for resampling (STTMIX-REQ-003)
-        let native_sample_rate = config.sample_rate().0;
-        let channels = config.channels();
-
-        // Validate sample rate supports accurate 16kHz downsampling
-        crate::resampler::validate_sample_rate(native_sample_rate).map_err(|e| {
-            anyhow!(
-                "Unsupported audio device '{}': {}",
-                device_id,
-                e
-            )
-        })?;
-
-        eprintln!(
-            "📊 Audio config: {}Hz, {} channel(s) -> 16kHz mono",
-            native_sample_rate, channels
-        );
-
-        // Liveness tracking (Task 2.5)
-        let last_cb = Arc::clone(&self.last_callback);
-        *last_cb.lock().unwrap() = Instant::now();
-
-        // Error event channel (Task 2.5)
-        let event_tx_clone = self.event_tx.clone();
-
-        // Create shutdown channel for stream thread
-        let (shutdown_tx, shutdown_rx) = mpsc::channel();
-
-        // Debug: Track callback invocations
-        use std::sync::atomic::{AtomicU64, Ordering};
-        let callback_count = Arc::new(AtomicU64::new(0));
-        let callback_count_clone = Arc::clone(&callback_count);
-
-        // Spawn stream thread (Task 2.5: reliable cleanup)
-        let stream_thread = std::thread::spawn(move || {
-            let stream = device.build_input_stream(
-                &config.into(),
-                move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                    let count = callback_count_clone.fetch_add(1, Ordering::SeqCst) + 1;
-                    // Debug: Log every 50th callback (approx every second)
-                    if count % 50 == 1 {
-                        eprintln!(
-                            "🎤 Audio callback #{}: {} samples",
-                            count,
-                            data.len()
-                        );
-                    }
-
-                    // Update liveness timestamp (Task 2.5)
-                    *last_cb.lock().unwrap() = Instant::now();
-
-                    // STTMIX-REQ-003: Normalize to 16kHz mono using resampler module
-                    // Handles stereo-to-mono downmix and averaging downsampling
-                    let pcm_data = crate::resampler::process_audio_to_16khz_mono(
-                        data,
-                        channels,
-                        native_sample_rate,
-                    );
-
-                    callback(pcm_data);
-                },
-                move |err| {
-                    // Send error event (Task 2.5: STT-REQ-004.9)
-                    if let Some(tx) = &event_tx_clone {
-                        tx.send(AudioDeviceEvent::StreamError(format!("{:?}", err)))
-                            .ok();
-                    }
-                    eprintln!("❌ Audio stream error: {:?}", err);
-                },
-            );
-
-            match stream {
-                Ok(stream) => {
-                    use cpal::traits::StreamTrait;
-                    eprintln!("✅ Audio stream created successfully");
-                    match stream.play() {
-                        Ok(_) => {
-                            eprintln!("✅ Audio stream started playing");
-                            // Wait for shutdown signal
-                            shutdown_rx.recv().ok();
-                            // Stream will be dropped here (reliable cleanup)
-                        }
-                        Err(e) => {
-                            eprintln!("❌ Failed to play audio stream: {:?}", e);
-                        }
-                    }
-                }
-                Err(e) => {
-                    eprintln!("❌ Failed to build audio stream: {:?}", e);
-                }
-            }
-        });
-
-        self.stream_thread = Some(stream_thread);
-        self.stream_shutdown_tx = Some(shutdown_tx);
-
-        // Store device_id for monitoring
-        self.device_id = Some(device_id.to_string());
-
-        // Start watchdog & polling (Task 2.5)
-        self.watchdog_shutdown_tx = Some(self.start_watchdog());
-        self.polling_shutdown_tx = Some(self.start_device_polling());
-
-        self.is_recording = true;
+        self.capture.start(device_id, callback)?;
         Ok(())
     }
 
     fn stop_recording(&mut self) -> Result<()> {
-        if !self.is_recording {
-            return Ok(());
-        }
-
-        // Signal all threads to stop (Task 2.5: reliable cleanup)
-        if let Some(tx) = self.stream_shutdown_tx.take() {
-            tx.send(()).ok();
-        }
-        if let Some(tx) = self.watchdog_shutdown_tx.take() {
-            tx.send(()).ok();
-        }
-        if let Some(tx) = self.polling_shutdown_tx.take() {
-            tx.send(()).ok();
-        }
-
-        // Join stream thread (stream will be dropped)
-        if let Some(handle) = self.stream_thread.take() {
-            handle.join().ok();
-        }
-
-        // Join watchdog thread (Task 2.5)
-        if let Some(handle) = self.watchdog_handle.take() {
-            handle.join().ok();
-        }
-
-        // Join polling thread (Task 2.5)
-        if let Some(handle) = self.polling_handle.take() {
-            handle.join().ok();
-        }
-
-        // Reset state
-        self.is_recording = false;
-        self.device_id = None;
-
+        self.capture.stop();
+        self.stub_recording = false;
         Ok(())
     }
 
     fn is_recording(&self) -> bool {
-        self.is_recording
+        self.stub_recording || self.capture.is_recording()
     }
 
     fn check_permission(&self) -> Result<()> {
@@ -497,137 +646,49 @@ impl AudioDeviceAdapter for CoreAudioAdapter {
     }
 
     fn set_event_sender(&mut self, tx: AudioEventSender) {
-        self.event_tx = Some(tx);
+        self.capture.set_event_sender(tx);
     }
 }
 
 /// Windows WASAPI adapter
 #[cfg(target_os = "windows")]
 pub struct WasapiAdapter {
-    is_recording: bool,
-    device_id: Option<String>,
-    stream_thread: Option<JoinHandle<()>>,
-    last_callback: Arc<Mutex<Instant>>,
-    event_tx: Option<AudioEventSender>,
-    watchdog_handle: Option<JoinHandle<()>>,
-    polling_handle: Option<JoinHandle<()>>,
-    stream_shutdown_tx: Option<mpsc::Sender<()>>,
-    watchdog_shutdown_tx: Option<mpsc::Sender<()>>,
-    polling_shutdown_tx: Option<mpsc::Sender<()>>,
+    /// Stream, liveness watchdog and device polling
+    capture: HostCapture,
+
+    /// Set by the `start_recording` stub (Task 2.3)
+    stub_recording: bool,
+
+    /// Direct WASAPI capture (exclusive mode or custom buffer), if in use
+    native_capture: Option<crate::wasapi_capture::native::NativeCapture>,
 }
 
 #[cfg(target_os = "windows")]
 impl WasapiAdapter {
     pub fn new() -> Self {
         Self {
-            is_recording: false,
-            device_id: None,
-            stream_thread: None,
-            last_callback: Arc::new(Mutex::new(Instant::now())),
-            event_tx: None,
-            watchdog_handle: None,
-            polling_handle: None,
-            stream_shutdown_tx: None,
-            watchdog_shutdown_tx: None,
-            polling_shutdown_tx: None,
+            capture: HostCapture::new(Arc::new(CpalHost)),
+            stub_recording: false,
+            native_capture: None,
         }
     }
 
-    pub fn set_event_sender(&mut self, tx: AudioEventSender) {
-        self.event_tx = Some(tx);
-    }
-
-    fn start_watchdog(&mut self) -> mpsc::Sender<()> {
-        let last_cb = Arc::clone(&self.last_callback);
-        let event_tx = self.event_tx.clone();
-        let (shutdown_tx, shutdown_rx) = mpsc::channel();
-
-        let handle = std::thread::spawn(move || loop {
-            std::thread::sleep(Duration::from_millis(250));
-
-            if shutdown_rx.try_recv().is_ok() {
-                break;
-            }
-
-            let elapsed = last_cb.lock().unwrap().elapsed();
-            if elapsed > Duration::from_millis(1200) {
-                if let Some(tx) = &event_tx {
-                    tx.send(AudioDeviceEvent::Stalled {
-                        elapsed_ms: elapsed.as_millis() as u64,
-                    })
-                    .ok();
-                }
-            }
-        });
-
-        self.watchdog_handle = Some(handle);
-        shutdown_tx
-    }
-
-    fn start_device_polling(&mut self) -> mpsc::Sender<()> {
-        let device_id = self.device_id.clone();
-        let event_tx = self.event_tx.clone();
-        let (shutdown_tx, shutdown_rx) = mpsc::channel();
-
-        let handle = std::thread::spawn(move || loop {
-            std::thread::sleep(Duration::from_secs(3));
-
-            if shutdown_rx.try_recv().is_ok() {
-                break;
-            }
-
-            if let Some(ref dev_id) = device_id {
-                let host = cpal::default_host();
-                let device_exists = host
-                    .input_devices()
-                    .ok()
-                    .and_then(|mut devices| {
-                        devices.find(|d| d.name().ok().as_ref() == Some(&dev_id))
-                    })
-                    .is_some();
-
-                if !device_exists {
-                    if let Some(tx) = &event_tx {
-                        tx.send(AudioDeviceEvent::DeviceGone {
-                            device_id: dev_id.clone(),
-                        })
-                        .ok();
-                    }
-                    break;
-                }
-            }
-        });
-
-        self.polling_handle = Some(handle);
-        shutdown_tx
+    /// Set event sender for device monitoring
+    /// Must be called before start_recording to enable monitoring
+    pub fn set_event_sender(&mut self, tx: AudioEventSender) {
+        self.capture.set_event_sender(tx);
     }
 }
 
 #[cfg(target_os = "windows")]
 impl AudioDeviceAdapter for WasapiAdapter {
     fn enumerate_devices(&self) -> Result<Vec<AudioDeviceInfo>> {
-        let host = cpal::default_host();
-        let mut devices = Vec::new();
-
-        for device in host.input_devices()? {
-            let name = device.name()?;
-            let default_config = device.default_input_config()?;
-
-            devices.push(AudioDeviceInfo {
-                id: name.clone(),
-                name: name.clone(),
-                sample_rate: default_config.sample_rate().0,
-                channels: default_config.channels(),
-                is_loopback: is_loopback_device(&name),
-            });
-        }
-
-        Ok(devices)
+        self.capture.enumerate_devices()
     }
 
     fn start_recording(&mut self, _device_id: &str) -> Result<()> {
         // TODO: Implement WASAPI recording (Task 2.3)
-        self.is_recording = true;
+        self.stub_recording = true;
         Ok(())
     }
 
@@ -636,116 +697,105 @@ impl AudioDeviceAdapter for WasapiAdapter {
         device_id: &str,
         callback: AudioChunkCallback,
     ) -> Result<()> {
-        if self.is_recording {
+        if self.is_recording() {
             return Err(anyhow!("Already recording"));
         }
 
-        self.device_id = Some(device_id.to_string());
-
-        let host = cpal::default_host();
-
-        let device = host
-            .input_devices()?
-            .find(|d| d.name().ok().as_ref() == Some(&device_id.to_string()))
-            .ok_or_else(|| anyhow!("Device not found: {}", device_id))?;
-
-        let config = device.default_input_config()?;
-
-        // Extract sample rate and channels for resampling (STTMIX-REQ-003)
-        let native_sample_rate = config.sample_rate().0;
-        let channels = config.channels();
-
-        // Validate sample rate supports accurate 16kHz downsampling
-        crate::resampler::validate_sample_rate(native_sample_rate).map_err(|e| {
-            anyhow!(
-                "Unsupported audio device '{}': {}",
+        // Inputs chosen on multi-channel interfaces (empty: downmix all)
+        let inputs = crate::channel_selection::inputs_for(device_id);
+        let required_channels = crate::channel_selection::required_channels(&inputs);
+
+        // Exclusive mode / custom buffer first, falling back to the default stream
+        let callback: Arc<AudioChunkCallback> = Arc::new(callback);
+        let mut fallback_reasons = Vec::new();
+        let settings = crate::wasapi_capture::current_settings();
+        for request in crate::wasapi_capture::capture_attempts(&settings) {
+            let last_cb = self.capture.liveness();
+            *last_cb.lock().unwrap() = Instant::now();
+            let data_callback = Arc::clone(&callback);
+            let event_tx = self.capture.event_sender();
+            let mut selector = crate::channel_selection::ChannelSelector::new(&inputs);
+
+            let started = crate::wasapi_capture::native::start(
                 device_id,
-                e
-            )
-        })?;
-
-        // Initialize liveness timestamp
-        let last_cb = Arc::clone(&self.last_callback);
-        *last_cb.lock().unwrap() = Instant::now();
-
-        let event_tx_clone = self.event_tx.clone();
-        let (shutdown_tx, shutdown_rx) = mpsc::channel();
-
-        let stream_thread = std::thread::spawn(move || {
-            let stream = device.build_input_stream(
-                &config.into(),
-                move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                    // Update liveness timestamp
+                request,
+                Box::new(move |data, channels, sample_rate| {
                     *last_cb.lock().unwrap() = Instant::now();
-
-                    // STTMIX-REQ-003: Normalize to 16kHz mono using resampler module
-                    let pcm_data = crate::resampler::process_audio_to_16khz_mono(
-                        data,
-                        channels,
-                        native_sample_rate,
-                    );
-
-                    callback(pcm_data);
-                },
-                move |err| {
-                    eprintln!("Audio stream error: {:?}", err);
-                    if let Some(tx) = &event_tx_clone {
-                        tx.send(AudioDeviceEvent::StreamError(format!("{:?}", err)))
-                            .ok();
+                    let (data, channels) = match selector.as_mut() {
+                        Some(selector) => (selector.apply(data, channels), 1),
+                        None => (data, channels),
+                    };
+                    let pcm_data =
+                        crate::resampler::process_audio_to_16khz_mono(data, channels, sample_rate);
+                    data_callback(Bytes::from(pcm_data));
+                }),
+                Box::new(move |err| {
+                    eprintln!("Audio stream error: {}", err);
+                    if let Some(tx) = &event_tx {
+                        tx.send(AudioDeviceEvent::StreamError(err)).ok();
                     }
-                },
+                }),
             );
-
-            if let Ok(stream) = stream {
-                use cpal::traits::StreamTrait;
-                if stream.play().is_ok() {
-                    shutdown_rx.recv().ok();
+            match started {
+                Ok((capture, effective)) if effective.channels < required_channels => {
+                    // Mix format lacks the selected input; try the default stream
+                    capture.stop();
+                    let reason = format!(
+                        "{:?} {} ms: {} channel(s), input {} selected",
+                        request.mode, request.buffer_ms, effective.channels, required_channels
+                    );
+                    log_warn!("audio_device_adapter", "wasapi_capture_fallback", &reason);
+                    fallback_reasons.push(reason);
+                }
+                Ok((capture, mut effective)) => {
+                    effective.fallback_reasons = fallback_reasons;
+                    log_info_details!(
+                        "audio_device_adapter",
+                        "wasapi_capture_started",
+                        serde_json::json!(effective)
+                    );
+                    crate::wasapi_capture::record_effective(effective);
+                    self.native_capture = Some(capture);
+                    self.capture.start_monitoring(device_id);
+                    return Ok(());
+                }
+                Err(e) => {
+                    let reason = format!("{:?} {} ms: {:#}", request.mode, request.buffer_ms, e);
+                    log_warn!("audio_device_adapter", "wasapi_capture_fallback", &reason);
+                    fallback_reasons.push(reason);
                 }
             }
-        });
+        }
 
-        self.stream_thread = Some(stream_thread);
-        self.stream_shutdown_tx = Some(shutdown_tx);
-        self.watchdog_shutdown_tx = Some(self.start_watchdog());
-        self.polling_shutdown_tx = Some(self.start_device_polling());
-        self.is_recording = true;
+        // Default stream through cpal
+        let config = self
+            .capture
+            .start(device_id, Box::new(move |chunk| callback(chunk)))?;
+        crate::wasapi_capture::record_effective(crate::wasapi_capture::EffectiveCapture {
+            device: device_id.to_string(),
+            native: false,
+            mode: crate::wasapi_capture::CaptureMode::Shared,
+            buffer_frames: None,
+            buffer_ms: None,
+            sample_rate: config.sample_rate,
+            channels: config.channels,
+            sample_format: None,
+            fallback_reasons,
+        });
         Ok(())
     }
 
     fn stop_recording(&mut self) -> Result<()> {
-        if !self.is_recording {
-            return Ok(());
-        }
-
-        // Signal all threads to stop
-        if let Some(tx) = self.stream_shutdown_tx.take() {
-            tx.send(()).ok();
-        }
-        if let Some(tx) = self.watchdog_shutdown_tx.take() {
-            tx.send(()).ok();
-        }
-        if let Some(tx) = self.polling_shutdown_tx.take() {
-            tx.send(()).ok();
-        }
-
-        // Join all threads
-        if let Some(handle) = self.stream_thread.take() {
-            handle.join().ok();
-        }
-        if let Some(handle) = self.watchdog_handle.take() {
-            handle.join().ok();
+        if let Some(capture) = self.native_capture.take() {
+            capture.stop();
         }
-        if let Some(handle) = self.polling_handle.take() {
-            handle.join().ok();
-        }
-
-        self.is_recording = false;
-        self.device_id = None;
+        self.capture.stop();
+        self.stub_recording = false;
         Ok(())
     }
 
     fn is_recording(&self) -> bool {
-        self.is_recording
+        self.stub_recording || self.capture.is_recording()
     }
 
     fn check_permission(&self) -> Result<()> {
@@ -777,137 +827,45 @@ impl AudioDeviceAdapter for WasapiAdapter {
     }
 
     fn set_event_sender(&mut self, tx: AudioEventSender) {
-        self.event_tx = Some(tx);
+        self.capture.set_event_sender(tx);
     }
 }
 
 /// Linux ALSA adapter
 #[cfg(target_os = "linux")]
 pub struct AlsaAdapter {
-    is_recording: bool,
-    device_id: Option<String>,
-    stream_thread: Option<JoinHandle<()>>,
-    last_callback: Arc<Mutex<Instant>>,
-    event_tx: Option<AudioEventSender>,
-    watchdog_handle: Option<JoinHandle<()>>,
-    polling_handle: Option<JoinHandle<()>>,
-    stream_shutdown_tx: Option<mpsc::Sender<()>>,
-    watchdog_shutdown_tx: Option<mpsc::Sender<()>>,
-    polling_shutdown_tx: Option<mpsc::Sender<()>>,
+    /// Stream, liveness watchdog and device polling
+    capture: HostCapture,
+
+    /// Set by the `start_recording` stub (Task 2.3)
+    stub_recording: bool,
 }
 
 #[cfg(target_os = "linux")]
 impl AlsaAdapter {
     pub fn new() -> Self {
         Self {
-            is_recording: false,
-            device_id: None,
-            stream_thread: None,
-            last_callback: Arc::new(Mutex::new(Instant::now())),
-            event_tx: None,
-            watchdog_handle: None,
-            polling_handle: None,
-            stream_shutdown_tx: None,
-            watchdog_shutdown_tx: None,
-            polling_shutdown_tx: None,
+            capture: HostCapture::new(Arc::new(CpalHost)),
+            stub_recording: false,
         }
     }
 
+    /// Set event sender for device monitoring
+    /// Must be called before start_recording to enable monitoring
     pub fn set_event_sender(&mut self, tx: AudioEventSender) {
-        self.event_tx = Some(tx);
-    }
-
-    fn start_watchdog(&mut self) -> mpsc::Sender<()> {
-        let last_cb = Arc::clone(&self.last_callback);
-        let event_tx = self.event_tx.clone();
-        let (shutdown_tx, shutdown_rx) = mpsc::channel();
-
-        let handle = std::thread::spawn(move || loop {
-            std::thread::sleep(Duration::from_millis(250));
-
-            if shutdown_rx.try_recv().is_ok() {
-                break;
-            }
-
-            let elapsed = last_cb.lock().unwrap().elapsed();
-            if elapsed > Duration::from_millis(1200) {
-                if let Some(tx) = &event_tx {
-                    tx.send(AudioDeviceEvent::Stalled {
-                        elapsed_ms: elapsed.as_millis() as u64,
-                    })
-                    .ok();
-                }
-            }
-        });
-
-        self.watchdog_handle = Some(handle);
-        shutdown_tx
-    }
-
-    fn start_device_polling(&mut self) -> mpsc::Sender<()> {
-        let device_id = self.device_id.clone();
-        let event_tx = self.event_tx.clone();
-        let (shutdown_tx, shutdown_rx) = mpsc::channel();
-
-        let handle = std::thread::spawn(move || loop {
-            std::thread::sleep(Duration::from_secs(3));
-
-            if shutdown_rx.try_recv().is_ok() {
-                break;
-            }
-
-            if let Some(ref dev_id) = device_id {
-                let host = cpal::default_host();
-                let device_exists = host
-                    .input_devices()
-                    .ok()
-                    .and_then(|mut devices| {
-                        devices.find(|d| d.name().ok().as_ref() == Some(&dev_id))
-                    })
-                    .is_some();
-
-                if !device_exists {
-                    if let Some(tx) = &event_tx {
-                        tx.send(AudioDeviceEvent::DeviceGone {
-                            device_id: dev_id.clone(),
-                        })
-                        .ok();
-                    }
-                    break;
-                }
-            }
-        });
-
-        self.polling_handle = Some(handle);
-        shutdown_tx
+        self.capture.set_event_sender(tx);
     }
 }
 
 #[cfg(target_os = "linux")]
 impl AudioDeviceAdapter for AlsaAdapter {
     fn enumerate_devices(&self) -> Result<Vec<AudioDeviceInfo>> {
-        let host = cpal::default_host();
-        let mut devices = Vec::new();
-
-        for device in host.input_devices()? {
-            let name = device.name()?;
-            let default_config = device.default_input_config()?;
-
-            devices.push(AudioDeviceInfo {
-                id: name.clone(),
-                name: name.clone(),
-                sample_rate: default_config.sample_rate().0,
-                channels: default_config.channels(),
-                is_loopback: is_loopback_device(&name),
-            });
-        }
-
-        Ok(devices)
+        self.capture.enumerate_devices()
     }
 
     fn start_recording(&mut self, _device_id: &str) -> Result<()> {
         // TODO: Implement ALSA recording (Task 2.3)
-        self.is_recording = true;
+        self.stub_recording = true;
         Ok(())
     }
 
@@ -916,116 +874,18 @@ impl AudioDeviceAdapter for AlsaAdapter {
         device_id: &str,
         callback: AudioChunkCallback,
     ) -> Result<()> {
-        if self.is_recording {
-            return Err(anyhow!("Already recording"));
-        }
-
-        self.device_id = Some(device_id.to_string());
-
-        let host = cpal::default_host();
-
-        let device = host
-            .input_devices()?
-            .find(|d| d.name().ok().as_ref() == Some(&device_id.to_string()))
-            .ok_or_else(|| anyhow!("Device not found: {}", device_id))?;
-
-        let config = device.default_input_config()?;
-
-        // Extract sample rate and channels for resampling (STTMIX-REQ-003)
-        let native_sample_rate = config.sample_rate().0;
-        let channels = config.channels();
-
-        // Validate sample rate supports accurate 16kHz downsampling
-        crate::resampler::validate_sample_rate(native_sample_rate).map_err(|e| {
-            anyhow!(
-                "Unsupported audio device '{}': {}",
-                device_id,
-                e
-            )
-        })?;
-
-        // Initialize liveness timestamp
-        let last_cb = Arc::clone(&self.last_callback);
-        *last_cb.lock().unwrap() = Instant::now();
-
-        let event_tx_clone = self.event_tx.clone();
-        let (shutdown_tx, shutdown_rx) = mpsc::channel();
-
-        let stream_thread = std::thread::spawn(move || {
-            let stream = device.build_input_stream(
-                &config.into(),
-                move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                    // Update liveness timestamp
-                    *last_cb.lock().unwrap() = Instant::now();
-
-                    // STTMIX-REQ-003: Normalize to 16kHz mono using resampler module
-                    let pcm_data = crate::resampler::process_audio_to_16khz_mono(
-                        data,
-                        channels,
-                        native_sample_rate,
-                    );
-
-                    callback(pcm_data);
-                },
-                move |err| {
-                    eprintln!("Audio stream error: {:?}", err);
-                    if let Some(tx) = &event_tx_clone {
-                        tx.send(AudioDeviceEvent::StreamError(format!("{:?}", err)))
-                            .ok();
-                    }
-                },
-            );
-
-            if let Ok(stream) = stream {
-                use cpal::traits::StreamTrait;
-                if stream.play().is_ok() {
-                    shutdown_rx.recv().ok();
-                }
-            }
-        });
-
-        self.stream_thread = Some(stream_thread);
-        self.stream_shutdown_tx = Some(shutdown_tx);
-        self.watchdog_shutdown_tx = Some(self.start_watchdog());
-        self.polling_shutdown_tx = Some(self.start_device_polling());
-        self.is_recording = true;
+        self.capture.start(device_id, callback)?;
         Ok(())
     }
 
     fn stop_recording(&mut self) -> Result<()> {
-        if !self.is_recording {
-            return Ok(());
-        }
-
-        // Signal all threads to stop
-        if let Some(tx) = self.stream_shutdown_tx.take() {
-            tx.send(()).ok();
-        }
-        if let Some(tx) = self.watchdog_shutdown_tx.take() {
-            tx.send(()).ok();
-        }
-        if let Some(tx) = self.polling_shutdown_tx.take() {
-            tx.send(()).ok();
-        }
-
-        // Join all threads
-        if let Some(handle) = self.stream_thread.take() {
-            handle.join().ok();
-        }
-        if let Some(handle) = self.watchdog_handle.take() {
-            handle.join().ok();
-        }
-        if let Some(handle) = self.polling_handle.take() {
-            handle.join().ok();
-        }
-
-        self.is_recording = false;
-        self.device_id = None;
+        self.capture.stop();
+        self.stub_recording = false;
         Ok(())
     }
 
     fn is_recording(&self) -> bool {
-        self.is_recording
+        self.stub_recording || self.capture.is_recording()
     }
 
     fn check_permission(&self) -> Result<()> {
@@ -1057,7 +917,7 @@ impl AudioDeviceAdapter for AlsaAdapter {
     }
 
     fn set_event_sender(&mut self, tx: AudioEventSender) {
-        self.event_tx = Some(tx);
+        self.capture.set_event_sender(tx);
     }
 }
 
@@ -1116,6 +976,7 @@ mod tests {
                     name: "Test Microphone".to_string(),
                     sample_rate: 16000,
                     channels: 1,
+                    max_channels: 1,
                     is_loopback: false,
                 }],
             }
@@ -1130,6 +991,7 @@ mod tests {
                         name: "Test Microphone".to_string(),
                         sample_rate: 16000,
                         channels: 1,
+                        max_channels: 1,
                         is_loopback: false,
                     },
                     AudioDeviceInfo {
@@ -1137,6 +999,7 @@ mod tests {
                         name: "BlackHole 2ch".to_string(),
                         sample_rate: 48000,
                         channels: 2,
+                        max_channels: 2,
                         is_loopback: true,
                     },
                     AudioDeviceInfo {
@@ -1144,6 +1007,7 @@ mod tests {
                         name: "Monitor of Built-in Audio".to_string(),
                         sample_rate: 44100,
                         channels: 2,
+                        max_channels: 2,
                         is_loopback: true,
                     },
                 ],
@@ -1278,7 +1142,7 @@ mod tests {
         let chunks_received = Arc::new(Mutex::new(Vec::new()));
         let chunks_received_clone = chunks_received.clone();
 
-        let callback: AudioChunkCallback = Box::new(move |chunk: Vec<u8>| {
+        let callback: AudioChunkCallback = Box::new(move |chunk: Bytes| {
             chunks_received_clone.lock().unwrap().push(chunk);
         });
 
@@ -1312,6 +1176,181 @@ mod tests {
         assert!(!adapter.is_recording());
     }
 
+    // Scripted capture host: replays its buffers, then waits for shutdown
+    struct FakeHost {
+        devices: Mutex<Vec<AudioDeviceInfo>>,
+        config: HostStreamConfig,
+        buffers: Vec<Vec<f32>>,
+        stream_error: Option<String>,
+    }
+
+    impl FakeHost {
+        fn new(config: HostStreamConfig, buffers: Vec<Vec<f32>>) -> Self {
+            Self {
+                devices: Mutex::new(vec![AudioDeviceInfo {
+                    id: "fake-mic".to_string(),
+                    name: "Fake Microphone".to_string(),
+                    sample_rate: config.sample_rate,
+                    channels: config.channels,
+                    max_channels: config.channels,
+                    is_loopback: false,
+                }]),
+                config,
+                buffers,
+                stream_error: None,
+            }
+        }
+
+        fn unplug(&self) {
+            self.devices.lock().unwrap().clear();
+        }
+    }
+
+    impl CaptureHost for FakeHost {
+        fn enumerate_devices(&self) -> Result<Vec<AudioDeviceInfo>> {
+            Ok(self.devices.lock().unwrap().clone())
+        }
+
+        fn open_input(&self, device_id: &str, min_channels: u16) -> Result<Box<dyn HostInput>> {
+            if !self.device_exists(device_id) {
+                return Err(anyhow!("Device not found: {}", device_id));
+            }
+            if min_channels > self.config.channels {
+                return Err(anyhow!("Device has fewer than {} input channels", min_channels));
+            }
+            Ok(Box::new(FakeInput {
+                config: self.config,
+                buffers: self.buffers.clone(),
+                stream_error: self.stream_error.clone(),
+            }))
+        }
+    }
+
+    struct FakeInput {
+        config: HostStreamConfig,
+        buffers: Vec<Vec<f32>>,
+        stream_error: Option<String>,
+    }
+
+    impl HostInput for FakeInput {
+        fn config(&self) -> HostStreamConfig {
+            self.config
+        }
+
+        fn run(
+            self: Box<Self>,
+            mut on_data: HostDataCallback,
+            mut on_error: HostErrorCallback,
+            shutdown: mpsc::Receiver<()>,
+        ) -> Result<()> {
+            for buffer in &self.buffers {
+                on_data(buffer);
+            }
+            if let Some(err) = self.stream_error {
+                on_error(err);
+            }
+            shutdown.recv().ok();
+            Ok(())
+        }
+    }
+
+    fn fast_timings() -> CaptureTimings {
+        CaptureTimings {
+            watchdog_interval: Duration::from_millis(10),
+            stall_threshold: Duration::from_millis(100),
+            poll_interval: Duration::from_millis(10),
+        }
+    }
+
+    /// Record 20ms of `frame` repeated at 48kHz; returns the chunks delivered
+    fn capture_chunks(frame: &[f32]) -> Vec<Bytes> {
+        let config = HostStreamConfig {
+            sample_rate: 48000,
+            channels: frame.len() as u16,
+        };
+        let buffer = frame.repeat(960);
+        let host = Arc::new(FakeHost::new(config, vec![buffer.clone(), buffer]));
+        let mut capture = HostCapture::new(host).with_timings(fast_timings());
+
+        let chunks = Arc::new(Mutex::new(Vec::new()));
+        let chunks_clone = Arc::clone(&chunks);
+        let started = capture
+            .start(
+                "fake-mic",
+                Box::new(move |chunk| chunks_clone.lock().unwrap().push(chunk)),
+            )
+            .unwrap();
+        assert_eq!(started, config);
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while chunks.lock().unwrap().len() < 2 && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        capture.stop();
+        let chunks = chunks.lock().unwrap().clone();
+        chunks
+    }
+
+    #[test]
+    fn test_host_capture_converts_to_16khz_mono() {
+        // Stereo and a 4-channel interface, both averaging to 0.5
+        for frame in [vec![0.25, 0.75], vec![0.2, 0.4, 0.6, 0.8]] {
+            let chunks = capture_chunks(&frame);
+            assert_eq!(chunks.len(), 2, "{} channel(s)", frame.len());
+            for chunk in chunks {
+                // 20ms at 16kHz, i16 little-endian
+                assert_eq!(chunk.len(), 320 * 2);
+                let sample = i16::from_le_bytes([chunk[0], chunk[1]]);
+                assert!((sample - 16383).abs() <= 1, "sample {}", sample);
+            }
+        }
+    }
+
+    #[test]
+    fn test_host_capture_watchdog_polling_and_shutdown() {
+        let config = HostStreamConfig {
+            sample_rate: 48000,
+            channels: 1,
+        };
+        let mut host = FakeHost::new(config, Vec::new());
+        host.stream_error = Some("buffer overrun".to_string());
+        let host = Arc::new(host);
+
+        let (tx, rx) = mpsc::channel();
+        let mut capture = HostCapture::new(host.clone()).with_timings(fast_timings());
+        capture.set_event_sender(tx);
+        capture.start("fake-mic", Box::new(|_| {})).unwrap();
+        assert!(capture.is_recording());
+        assert!(capture.start("fake-mic", Box::new(|_| {})).is_err());
+
+        // Stream error right away, then a stall (no audio arrives)
+        let timeout = Duration::from_secs(5);
+        assert!(matches!(
+            rx.recv_timeout(timeout).unwrap(),
+            AudioDeviceEvent::StreamError(err) if err == "buffer overrun"
+        ));
+        assert!(matches!(
+            rx.recv_timeout(timeout).unwrap(),
+            AudioDeviceEvent::Stalled { elapsed_ms } if elapsed_ms >= 100
+        ));
+
+        host.unplug();
+        assert!(matches!(
+            rx.recv_timeout(timeout).unwrap(),
+            AudioDeviceEvent::DeviceGone { device_id } if device_id == "fake-mic"
+        ));
+
+        // Every thread ends promptly
+        let stopping = Instant::now();
+        capture.stop();
+        assert!(stopping.elapsed() < Duration::from_secs(1));
+        assert!(!capture.is_recording());
+        capture.stop();
+
+        assert!(capture.start("fake-mic", Box::new(|_| {})).is_err());
+        assert!(!capture.is_recording());
+    }
+
     #[test]
     fn test_is_loopback_device_detection() {
         // macOS devices
@@ -16,12 +16,14 @@
 //! Design: meeting-minutes-stt-multi-input/design.md §4.0
 
 use anyhow::Result;
+use std::path::PathBuf;
 use std::sync::mpsc;
 use std::sync::Arc;
 use std::thread::JoinHandle;
 
 use crate::audio_device_adapter::{AudioChunkCallback, AudioDeviceAdapter, AudioDeviceInfo};
 use crate::input_mixer::{InputMixer, MixerMetrics, FRAME_DURATION_MS};
+use crate::input_tracks::InputTrackWriters;
 use crate::multi_input_manager::{
     DeviceErrorReceiver, InputConfig, InputRole, InputStatus, MultiInputEvent,
     MultiInputEventReceiver, MultiInputManager,
@@ -64,6 +66,9 @@ pub struct MixerConfig {
     /// Continue recording if one input fails
     /// Requirement: STTMIX-REQ-006.2
     pub continue_on_partial_failure: bool,
+    /// Also write each input to its own WAV in this directory (the session
+    /// directory); None records the mixed feed only
+    pub input_tracks_dir: Option<PathBuf>,
 }
 
 impl Default for MixerConfig {
@@ -73,10 +78,18 @@ impl Default for MixerConfig {
             // Requirement: STTMIX-REQ-005.2
             gains: vec![-6.0, -6.0],
             continue_on_partial_failure: true,
+            input_tracks_dir: None,
         }
     }
 }
 
+impl MixerConfig {
+    /// Gain of input `index` in dB (-6.0 when not configured)
+    pub fn input_gain_db(&self, index: usize) -> f32 {
+        self.gains.get(index).copied().unwrap_or(-6.0)
+    }
+}
+
 // ============================================================================
 // AudioDeviceRecorder
 // ============================================================================
@@ -260,7 +273,7 @@ impl AudioDeviceRecorder {
                         } else {
                             InputRole::Loopback
                         };
-                        let gain = mixer_config.gains.get(i).copied().unwrap_or(-6.0);
+                        let gain = mixer_config.input_gain_db(i);
                         InputConfig::new(device_id.clone(), role).with_gain(gain)
                     })
                     .collect();
@@ -296,9 +309,30 @@ impl AudioDeviceRecorder {
                 let (shutdown_tx, shutdown_rx) = mpsc::channel();
 
                 // Create mixer and get metrics handle
-                let mixer = InputMixer::new();
+                let mut mixer = InputMixer::new();
                 let mixer_metrics = mixer.metrics();
 
+                // Optional per-input tracks (a failure only loses the tracks)
+                if let Some(dir) = &mixer_config.input_tracks_dir {
+                    let inputs: Vec<InputConfig> = active_buffers
+                        .iter()
+                        .map(|(config, _)| config.clone())
+                        .collect();
+                    match InputTrackWriters::create(dir, &inputs) {
+                        Ok(tracks) => mixer.set_input_tracks(tracks),
+                        Err(e) => {
+                            log_warn_details!(
+                                "mixer::tracks",
+                                "create_failed",
+                                serde_json::json!({
+                                    "dir": dir.display().to_string(),
+                                    "error": format!("{:#}", e)
+                                })
+                            );
+                        }
+                    }
+                }
+
                 // Clone buffers for the thread
                 let buffers_for_thread = active_buffers;
 
@@ -547,6 +581,27 @@ impl AudioDeviceRecorder {
             }
         }
 
+        // Finalize the per-input tracks (WAV headers)
+        if let Some(tracks) = mixer.take_input_tracks() {
+            let write_errors = tracks.write_errors();
+            match tracks.close() {
+                Ok(()) => {
+                    log_info_details!(
+                        "mixer::tracks",
+                        "closed",
+                        json!({ "write_errors": write_errors })
+                    );
+                }
+                Err(e) => {
+                    log_warn_details!(
+                        "mixer::tracks",
+                        "close_failed",
+                        json!({ "write_errors": write_errors, "error": format!("{:#}", e) })
+                    );
+                }
+            }
+        }
+
         // Task 9.2: Final summary log with structured metrics
         let metrics = mixer.metrics();
         log_info_details!(
@@ -594,6 +649,7 @@ mod tests {
                         name: "Built-in Microphone".to_string(),
                         sample_rate: 48000,
                         channels: 1,
+                        max_channels: 1,
                         is_loopback: false,
                     },
                     AudioDeviceInfo {
@@ -601,6 +657,7 @@ mod tests {
                         name: "BlackHole 2ch".to_string(),
                         sample_rate: 48000,
                         channels: 2,
+                        max_channels: 2,
                         is_loopback: true,
                     },
                 ],
@@ -1032,6 +1089,7 @@ mod tests {
             mixer_config: MixerConfig {
                 gains: vec![-3.0, -9.0],
                 continue_on_partial_failure: true,
+                input_tracks_dir: None,
             },
         };
 
@@ -1046,4 +1104,29 @@ mod tests {
         let loopback_config = manager.get_config("loopback-1").unwrap();
         assert_eq!(loopback_config.gain_db, -9.0);
     }
+
+    #[test]
+    fn test_recorder_multi_mode_input_tracks() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut recorder = AudioDeviceRecorder::new(mock_adapter_factory());
+
+        let callback: AudioChunkCallback = Box::new(|_| {});
+        let mode = RecordingMode::Multi {
+            device_ids: vec!["mic-1".to_string(), "loopback-1".to_string()],
+            mixer_config: MixerConfig {
+                input_tracks_dir: Some(dir.path().to_path_buf()),
+                ..MixerConfig::default()
+            },
+        };
+
+        recorder.start(mode, callback).unwrap();
+        recorder.stop().unwrap();
+
+        // One finalized WAV per input
+        let tracks = crate::input_tracks::track_paths(dir.path());
+        assert_eq!(tracks.len(), 2);
+        for track in tracks {
+            crate::storage::read_wav_samples(&track, |_| {}).unwrap();
+        }
+    }
 }
@@ -0,0 +1,151 @@
+//! Sent Audio Dump (debugging)
+//!
+//! When enabled, the exact PCM bytes written to the sidecar's stdin are also
+//! written to `debug/sent_audio.wav` in the session directory. Listening to
+//! it tells capture/mixing problems apart from model quality when a
+//! transcript comes out garbled. The dump stops at `max_dump_mb`; the
+//! session's `audio.wav` is unaffected.
+//!
+//! The same settings hold the structured log's minimum level.
+//!
+//! Persisted to `settings/debug.json` in app data directory.
+
+use crate::logger::LogLevel;
+use crate::storage::AudioWriter;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// Default dump cap (~109 minutes of 16kHz mono 16-bit audio)
+pub const DEFAULT_MAX_DUMP_MB: u32 = 200;
+
+/// Debug configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DebugSettings {
+    /// Tee audio sent to the sidecar into `debug/sent_audio.wav`
+    #[serde(default)]
+    pub dump_sent_audio: bool,
+    /// Size cap of the dump per session (MB)
+    #[serde(default = "default_max_dump_mb")]
+    pub max_dump_mb: u32,
+    /// Lowest level written to the structured log
+    #[serde(default = "default_log_level")]
+    pub log_level: LogLevel,
+    /// Settings version for future migrations
+    #[serde(default = "default_version")]
+    pub version: u32,
+}
+
+fn default_max_dump_mb() -> u32 {
+    DEFAULT_MAX_DUMP_MB
+}
+
+fn default_log_level() -> LogLevel {
+    LogLevel::Debug
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+impl Default for DebugSettings {
+    fn default() -> Self {
+        Self {
+            dump_sent_audio: false,
+            max_dump_mb: DEFAULT_MAX_DUMP_MB,
+            log_level: default_log_level(),
+            version: 1,
+        }
+    }
+}
+
+impl DebugSettings {
+    pub fn max_dump_bytes(&self) -> u64 {
+        self.max_dump_mb as u64 * 1024 * 1024
+    }
+}
+
+/// Size-capped WAV dump of the audio sent to the sidecar
+pub struct SentAudioDump {
+    writer: Option<AudioWriter>,
+    max_bytes: u64,
+    written_bytes: u64,
+}
+
+impl SentAudioDump {
+    pub fn new(writer: AudioWriter, max_bytes: u64) -> Self {
+        Self {
+            writer: Some(writer),
+            max_bytes,
+            written_bytes: 0,
+        }
+    }
+
+    /// Append PCM bytes
+    ///
+    /// Returns true when this write reached the cap (the dump is closed and
+    /// later writes are ignored).
+    pub fn write(&mut self, pcm_bytes: &[u8]) -> Result<bool> {
+        let Some(writer) = self.writer.as_mut() else {
+            return Ok(false);
+        };
+
+        let remaining = self.max_bytes.saturating_sub(self.written_bytes);
+        // Whole samples only
+        let usable = (pcm_bytes.len() as u64).min(remaining) as usize & !1;
+        writer.write_pcm_bytes(&pcm_bytes[..usable])?;
+        self.written_bytes += usable as u64;
+
+        if usable < pcm_bytes.len() {
+            self.finish()?;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    pub fn written_bytes(&self) -> u64 {
+        self.written_bytes
+    }
+
+    /// Close the dump (WAV header update); idempotent
+    pub fn finish(&mut self) -> Result<()> {
+        match self.writer.take() {
+            Some(writer) => writer.close(),
+            None => Ok(()),
+        }
+    }
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+pub const SETTINGS_STEM: &str = "debug";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::LocalStorageService;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_dump_is_capped_at_whole_samples() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = LocalStorageService::new(temp_dir.path().to_path_buf());
+        std::fs::create_dir_all(storage.get_session_dir("s1")).unwrap();
+
+        let mut dump = SentAudioDump::new(storage.create_sent_audio_dump("s1").unwrap(), 1_001);
+        assert!(!dump.write(&[1u8; 600]).unwrap());
+        assert!(dump.write(&[2u8; 600]).unwrap());
+        assert!(!dump.write(&[3u8; 600]).unwrap());
+        assert_eq!(dump.written_bytes(), 1_000);
+        dump.finish().unwrap();
+
+        let path = storage
+            .get_session_dir("s1")
+            .join("debug")
+            .join("sent_audio.wav");
+        let mut samples = 0;
+        crate::storage::read_wav_samples(&path, |chunk| samples += chunk.len()).unwrap();
+        assert_eq!(samples, 500);
+    }
+}
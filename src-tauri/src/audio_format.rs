@@ -0,0 +1,71 @@
+//! Session Audio Format Settings
+//!
+//! Hours of 16kHz PCM take about 115MB per hour. Sessions can instead be
+//! recorded to FLAC (lossless) or Opus (lossy speech, `opus` feature); the
+//! audio sent to the STT sidecar is PCM either way, only the saved file
+//! changes. The format is chosen when a session starts: from these settings,
+//! or per session via `start_recording`.
+//!
+//! Persisted to `settings/audio_format.json` in app data directory.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::storage::AudioFormat;
+
+/// Default session audio format
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AudioFormatSettings {
+    /// Format of new sessions' audio file
+    #[serde(default)]
+    pub format: AudioFormat,
+    /// Settings version for future migrations
+    #[serde(default = "default_version")]
+    pub version: u32,
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+impl Default for AudioFormatSettings {
+    fn default() -> Self {
+        Self {
+            format: AudioFormat::Wav,
+            version: 1,
+        }
+    }
+}
+
+/// Check that this build can record `format`
+pub fn validate_format(format: AudioFormat) -> Result<()> {
+    if !format.is_supported() {
+        anyhow::bail!(
+            "Audio format {:?} is not available in this build (enable the `opus` feature)",
+            format
+        );
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+pub const SETTINGS_STEM: &str = "audio_format";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_parsing_and_validation() {
+        let settings: AudioFormatSettings = serde_json::from_str(r#"{"format":"opus"}"#).unwrap();
+        assert_eq!(settings.format, AudioFormat::Opus);
+        assert!(validate_format(AudioFormat::Flac).is_ok());
+        assert_eq!(
+            validate_format(AudioFormat::Opus).is_ok(),
+            cfg!(feature = "opus")
+        );
+    }
+}
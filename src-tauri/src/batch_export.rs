@@ -0,0 +1,380 @@
+//! Batch Export and Re-transcription
+//!
+//! Exports or re-transcribes (see `retranscription`) many finalized
+//! sessions in one call instead of one command per session. Sessions are
+//! picked by ID, by a filter (start date range, tag), or both (IDs narrowed
+//! by the filter). The whole batch runs as a single job (`export` or
+//! `transcription`, see `jobs`), so it is queued, cancellable and reported
+//! with aggregate progress; a failing session is recorded and the batch
+//! moves on.
+//!
+//! Date bounds are `YYYY-MM-DD` (local calendar day, inclusive) or RFC 3339
+//! timestamps, compared with the session's `start_time`.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, FixedOffset, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::furigana::Furigana;
+use crate::jobs::JobError;
+use crate::storage::{LocalStorageService, SessionMetadata};
+
+/// Most sessions per batch
+pub const MAX_BATCH_SESSIONS: usize = 1000;
+
+/// Export written for each session
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchExportFormat {
+    /// `transcript.md`
+    Markdown,
+    /// `transcript.txt`
+    Text,
+    /// `transcript.docx`
+    Docx,
+    /// `transcript.html`
+    Html,
+    /// `audio.trimmed.flac` + `transcript.trimmed.md` (default options)
+    Trimmed,
+    /// `archives/<session>.zip` (default options)
+    Archive,
+}
+
+/// Sessions to process
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionSelection {
+    /// Explicit sessions (empty: every session matching the filter)
+    #[serde(default)]
+    pub session_ids: Vec<String>,
+    /// Earliest start (inclusive)
+    #[serde(default)]
+    pub from: Option<String>,
+    /// Latest start (inclusive)
+    #[serde(default)]
+    pub to: Option<String>,
+    /// Sessions carrying this tag
+    #[serde(default)]
+    pub tag: Option<String>,
+}
+
+/// Outcome for one session
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BatchItem {
+    pub session_id: String,
+    /// Files written
+    pub paths: Vec<String>,
+    pub error: Option<String>,
+}
+
+/// Aggregate progress, reported after every session
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct BatchProgress {
+    pub total: usize,
+    pub completed: usize,
+    pub failed: usize,
+}
+
+impl BatchProgress {
+    /// Share of sessions done (0.0-1.0)
+    pub fn fraction(&self) -> f32 {
+        (self.completed + self.failed) as f32 / self.total.max(1) as f32
+    }
+}
+
+/// Outcome of a finished batch
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct BatchReport {
+    #[serde(flatten)]
+    pub progress: BatchProgress,
+    /// Outcomes in processing order
+    pub items: Vec<BatchItem>,
+}
+
+/// IDs of the sessions in `sessions` matching `selection`, in list order
+///
+/// Explicit IDs must exist. At least one criterion is required, so an empty
+/// selection never processes everything by accident.
+pub fn select_sessions(
+    sessions: &[SessionMetadata],
+    selection: &SessionSelection,
+) -> Result<Vec<String>> {
+    if selection.session_ids.is_empty()
+        && selection.from.is_none()
+        && selection.to.is_none()
+        && selection.tag.is_none()
+    {
+        anyhow::bail!("Select sessions by ID or by a date range or tag");
+    }
+    if let Some(missing) = selection
+        .session_ids
+        .iter()
+        .find(|id| !sessions.iter().any(|s| &s.session_id == *id))
+    {
+        anyhow::bail!("Unknown session: {}", missing);
+    }
+    let from = selection
+        .from
+        .as_deref()
+        .map(|bound| parse_bound(bound, false))
+        .transpose()?;
+    let to = selection
+        .to
+        .as_deref()
+        .map(|bound| parse_bound(bound, true))
+        .transpose()?;
+
+    let selected: Vec<String> = sessions
+        .iter()
+        .filter(|s| {
+            selection.session_ids.is_empty() || selection.session_ids.contains(&s.session_id)
+        })
+        .filter(|s| {
+            selection
+                .tag
+                .as_ref()
+                .is_none_or(|tag| s.tags.iter().any(|t| t == tag))
+        })
+        .filter(|s| {
+            if from.is_none() && to.is_none() {
+                return true;
+            }
+            // Sessions without a readable start can't match a date range
+            let Ok(start) = DateTime::parse_from_rfc3339(&s.start_time) else {
+                return false;
+            };
+            from.is_none_or(|from| start >= from) && to.is_none_or(|to| start <= to)
+        })
+        .map(|s| s.session_id.clone())
+        .collect();
+    if selected.len() > MAX_BATCH_SESSIONS {
+        anyhow::bail!(
+            "{} sessions selected; at most {} can be processed at once",
+            selected.len(),
+            MAX_BATCH_SESSIONS
+        );
+    }
+    Ok(selected)
+}
+
+/// Parse a date bound: RFC 3339, or a local calendar day (its first or,
+/// for `end`, last moment)
+fn parse_bound(bound: &str, end: bool) -> Result<DateTime<FixedOffset>> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(bound) {
+        return Ok(time);
+    }
+    let date = NaiveDate::parse_from_str(bound, "%Y-%m-%d")
+        .with_context(|| format!("Invalid date: {} (use YYYY-MM-DD or RFC 3339)", bound))?;
+    let time = if end {
+        date.and_hms_milli_opt(23, 59, 59, 999)
+    } else {
+        date.and_hms_opt(0, 0, 0)
+    }
+    .context("Invalid date")?;
+    let local = time
+        .and_local_timezone(Local)
+        .earliest()
+        .with_context(|| format!("Date does not exist in the local time zone: {}", bound))?;
+    Ok(local.fixed_offset())
+}
+
+/// Export one session; returns the files written
+pub fn export_session(
+    storage: &LocalStorageService,
+    session_id: &str,
+    format: BatchExportFormat,
+    furigana: Option<&Furigana>,
+) -> Result<Vec<PathBuf>> {
+    Ok(match format {
+        BatchExportFormat::Markdown => vec![storage.export_session_markdown(session_id, furigana)?],
+        BatchExportFormat::Text => vec![storage.export_session_text(session_id, furigana)?],
+        BatchExportFormat::Docx => vec![storage.export_session_docx(session_id, furigana)?],
+        BatchExportFormat::Html => vec![storage.export_session_html(session_id, furigana)?],
+        BatchExportFormat::Trimmed => {
+            let export = crate::silence_trim::export_session(
+                storage,
+                session_id,
+                &Default::default(),
+                furigana,
+            )?;
+            vec![export.audio_path, export.transcript_path]
+        }
+        BatchExportFormat::Archive => {
+            crate::session_id::validate_session_id(session_id)?;
+            let output =
+                crate::session_archive::default_archive_path(storage.app_data_dir(), session_id);
+            let written = crate::session_archive::create_archive(
+                &storage.get_session_dir(session_id),
+                session_id,
+                &output,
+                &Default::default(),
+            )?;
+            vec![PathBuf::from(written.path)]
+        }
+    })
+}
+
+/// Run `process` (export or re-transcription) for every session of
+/// `session_ids` in turn
+///
+/// `process` returns the files written for a session. `checkpoint` runs
+/// before each session (see `JobContext::checkpoint`); cancellation stops
+/// the batch. `on_progress` gets the aggregate progress and the outcome of
+/// each session.
+pub fn run_batch(
+    session_ids: &[String],
+    checkpoint: impl Fn() -> std::result::Result<(), JobError>,
+    mut process: impl FnMut(&str) -> Result<Vec<PathBuf>>,
+    mut on_progress: impl FnMut(&BatchProgress, &BatchItem),
+) -> std::result::Result<BatchReport, JobError> {
+    let mut report = BatchReport {
+        progress: BatchProgress {
+            total: session_ids.len(),
+            ..Default::default()
+        },
+        items: Vec::with_capacity(session_ids.len()),
+    };
+    for session_id in session_ids {
+        checkpoint()?;
+        let item = match process(session_id) {
+            Ok(paths) => {
+                report.progress.completed += 1;
+                BatchItem {
+                    session_id: session_id.clone(),
+                    paths: paths.iter().map(|p| p.display().to_string()).collect(),
+                    error: None,
+                }
+            }
+            Err(e) => {
+                report.progress.failed += 1;
+                BatchItem {
+                    session_id: session_id.clone(),
+                    paths: Vec::new(),
+                    error: Some(format!("{:#}", e)),
+                }
+            }
+        };
+        on_progress(&report.progress, &item);
+        report.items.push(item);
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata(session_id: &str, start_time: &str, tags: &[&str]) -> SessionMetadata {
+        SessionMetadata {
+            session_id: session_id.to_string(),
+            start_time: start_time.to_string(),
+            end_time: start_time.to_string(),
+            duration_seconds: 60,
+            audio_device: "mic".to_string(),
+            model_size: "base".to_string(),
+            total_segments: 0,
+            total_characters: 0,
+            warnings: Vec::new(),
+            session_uuid: None,
+            title: None,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            stats: None,
+            levels: None,
+        }
+    }
+
+    #[test]
+    fn test_select_sessions_by_ids_and_filter() {
+        let sessions = [
+            metadata("s1", "2025-10-01T09:00:00Z", &["weekly"]),
+            metadata("s2", "2025-10-02T09:00:00Z", &["weekly", "sales"]),
+            metadata("s3", "2025-10-03T09:00:00Z", &[]),
+        ];
+        let select = |selection: SessionSelection| select_sessions(&sessions, &selection);
+
+        assert!(select(SessionSelection::default()).is_err());
+        assert_eq!(
+            select(SessionSelection {
+                session_ids: vec!["s3".to_string(), "s1".to_string()],
+                ..Default::default()
+            })
+            .unwrap(),
+            ["s1", "s3"]
+        );
+        assert!(select(SessionSelection {
+            session_ids: vec!["missing".to_string()],
+            ..Default::default()
+        })
+        .is_err());
+        assert_eq!(
+            select(SessionSelection {
+                tag: Some("weekly".to_string()),
+                ..Default::default()
+            })
+            .unwrap(),
+            ["s1", "s2"]
+        );
+        assert_eq!(
+            select(SessionSelection {
+                from: Some("2025-10-02T00:00:00Z".to_string()),
+                to: Some("2025-10-03T09:00:00Z".to_string()),
+                ..Default::default()
+            })
+            .unwrap(),
+            ["s2", "s3"]
+        );
+        assert_eq!(
+            select(SessionSelection {
+                session_ids: vec!["s1".to_string(), "s2".to_string()],
+                tag: Some("sales".to_string()),
+                ..Default::default()
+            })
+            .unwrap(),
+            ["s2"]
+        );
+        assert!(select(SessionSelection {
+            from: Some("October".to_string()),
+            ..Default::default()
+        })
+        .is_err());
+
+        // A calendar day covers the whole day
+        let from = parse_bound("2025-10-02", false).unwrap();
+        let to = parse_bound("2025-10-02", true).unwrap();
+        assert_eq!((to - from).num_milliseconds(), 24 * 3600 * 1000 - 1);
+    }
+
+    #[test]
+    fn test_run_batch_reports_failures_and_cancellation() {
+        use crate::recording_session::RecordingSession;
+        use crate::storage::AudioFormat;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = LocalStorageService::new(dir.path().to_path_buf());
+        let session = RecordingSession::new("s1", "u1", 0);
+        session.start(Some(&storage), AudioFormat::Wav).unwrap();
+        session.stop(Some(&storage), "mic", Vec::new()).unwrap();
+
+        let ids = ["s1".to_string(), "missing".to_string()];
+        let mut updates = Vec::new();
+        let export =
+            |session_id: &str| export_session(&storage, session_id, BatchExportFormat::Text, None);
+        let report = run_batch(
+            &ids,
+            || Ok(()),
+            export,
+            |progress, item| updates.push((progress.fraction(), item.session_id.clone())),
+        )
+        .unwrap();
+        assert_eq!(
+            updates,
+            [(0.5, "s1".to_string()), (1.0, "missing".to_string())]
+        );
+        assert_eq!((report.progress.completed, report.progress.failed), (1, 1));
+        assert!(report.items[0].paths[0].ends_with("transcript.txt"));
+        assert!(report.items[1].error.is_some());
+
+        let cancelled = run_batch(&ids, || Err(JobError::Cancelled), export, |_, _| {});
+        assert_eq!(cancelled.unwrap_err(), JobError::Cancelled);
+    }
+}
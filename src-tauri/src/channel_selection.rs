@@ -0,0 +1,210 @@
+//! Input Channel Selection
+//!
+//! USB audio interfaces expose 4–8 inputs, but a recording usually wants one
+//! of them (the mic on input 3) rather than all of them mixed together. Per
+//! device, the inputs to record can be chosen, numbered from 1 as printed on
+//! the hardware; the adapters average them to mono before resampling. Devices
+//! without a selection keep the downmix of all channels.
+//!
+//! The stream is opened with enough channels for the highest selected input
+//! (`input_format::select_input_config`); `AudioDeviceInfo::max_channels`
+//! tells how many a device offers.
+//!
+//! Settings are machine-wide (the wiring belongs to the hardware) and apply
+//! from the next recording start. Persisted to
+//! `settings/channel_selection.json` in app data directory.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+/// Highest input number accepted
+pub const MAX_INPUT: u16 = 64;
+
+/// Most devices with a selection
+const MAX_DEVICES: usize = 200;
+
+/// Channel selection configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChannelSelectionSettings {
+    /// Device ID → selected inputs (1-based)
+    #[serde(default)]
+    pub devices: BTreeMap<String, Vec<u16>>,
+    /// Settings version for future migrations
+    #[serde(default = "default_version")]
+    pub version: u32,
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+impl Default for ChannelSelectionSettings {
+    fn default() -> Self {
+        Self {
+            devices: BTreeMap::new(),
+            version: 1,
+        }
+    }
+}
+
+impl ChannelSelectionSettings {
+    pub fn validate(&self) -> Result<()> {
+        if self.devices.len() > MAX_DEVICES {
+            anyhow::bail!(
+                "At most {} devices can have a channel selection",
+                MAX_DEVICES
+            );
+        }
+        for (device_id, inputs) in &self.devices {
+            if device_id.trim().is_empty() {
+                anyhow::bail!("Device ID must not be empty");
+            }
+            if inputs.is_empty() {
+                anyhow::bail!("No inputs selected for {}", device_id);
+            }
+            for (i, input) in inputs.iter().enumerate() {
+                if !(1..=MAX_INPUT).contains(input) {
+                    anyhow::bail!("Input {} for {} must be 1-{}", input, device_id, MAX_INPUT);
+                }
+                if inputs[..i].contains(input) {
+                    anyhow::bail!("Input {} selected twice for {}", input, device_id);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Selected inputs of `device_id` (empty: all channels)
+    pub fn inputs_for(&self, device_id: &str) -> &[u16] {
+        self.devices
+            .get(device_id)
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+}
+
+/// Channels a stream needs to deliver `inputs`
+pub fn required_channels(inputs: &[u16]) -> u16 {
+    inputs.iter().copied().max().unwrap_or(1)
+}
+
+/// Mixes the selected inputs of interleaved audio to mono
+#[derive(Debug)]
+pub struct ChannelSelector {
+    /// 0-based channel indices
+    indices: Vec<usize>,
+    // Reused across callbacks (no allocation once warmed up)
+    buffer: Vec<f32>,
+}
+
+impl ChannelSelector {
+    /// Selector for `inputs` (None when empty: keep all channels)
+    pub fn new(inputs: &[u16]) -> Option<Self> {
+        let indices: Vec<usize> = inputs
+            .iter()
+            .filter(|&&input| input >= 1)
+            .map(|&input| usize::from(input - 1))
+            .collect();
+        if indices.is_empty() {
+            return None;
+        }
+        Some(Self {
+            indices,
+            buffer: Vec::new(),
+        })
+    }
+
+    /// Average of the selected inputs per frame of `data` (`channels`
+    /// interleaved); inputs beyond `channels` are ignored
+    pub fn apply(&mut self, data: &[f32], channels: u16) -> &[f32] {
+        let channels = usize::from(channels.max(1));
+        self.buffer.clear();
+        for frame in data.chunks_exact(channels) {
+            let mut sum = 0.0;
+            let mut count = 0;
+            for &index in &self.indices {
+                if let Some(sample) = frame.get(index) {
+                    sum += sample;
+                    count += 1;
+                }
+            }
+            self.buffer
+                .push(if count > 0 { sum / count as f32 } else { 0.0 });
+        }
+        &self.buffer
+    }
+}
+
+static SETTINGS: Mutex<Option<ChannelSelectionSettings>> = Mutex::new(None);
+
+/// Apply settings from the next recording start
+pub fn set_settings(settings: ChannelSelectionSettings) {
+    *SETTINGS.lock().unwrap() = Some(settings);
+}
+
+pub fn current_settings() -> ChannelSelectionSettings {
+    SETTINGS.lock().unwrap().clone().unwrap_or_default()
+}
+
+/// Selected inputs of `device_id` in the current settings (empty: all)
+pub fn inputs_for(device_id: &str) -> Vec<u16> {
+    SETTINGS
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|settings| settings.inputs_for(device_id).to_vec())
+        .unwrap_or_default()
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+pub const SETTINGS_STEM: &str = "channel_selection";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selector_mixes_selected_inputs() {
+        assert!(ChannelSelector::new(&[]).is_none());
+
+        // Two frames of 4 channels
+        let data = [0.1, 0.2, 0.4, 0.8, -0.1, -0.2, -0.4, -0.8];
+        let mut third = ChannelSelector::new(&[3]).unwrap();
+        assert_eq!(third.apply(&data, 4), &[0.4, -0.4]);
+
+        let mut pair = ChannelSelector::new(&[1, 3]).unwrap();
+        let mixed = pair.apply(&data, 4).to_vec();
+        assert!((mixed[0] - 0.25).abs() < 1e-6);
+        assert!((mixed[1] + 0.25).abs() < 1e-6);
+
+        // Input missing from a stereo stream yields silence
+        let mut missing = ChannelSelector::new(&[4]).unwrap();
+        assert_eq!(missing.apply(&[0.5, 0.5], 2), &[0.0]);
+
+        assert_eq!(required_channels(&[1, 3]), 3);
+        assert_eq!(required_channels(&[]), 1);
+    }
+
+    #[test]
+    fn test_settings_validation() {
+        let mut settings = ChannelSelectionSettings::default();
+        assert!(settings.inputs_for("usb-interface").is_empty());
+
+        settings
+            .devices
+            .insert("usb-interface".to_string(), vec![3]);
+        settings.validate().unwrap();
+        assert_eq!(settings.inputs_for("usb-interface"), &[3]);
+
+        for invalid in [vec![], vec![0], vec![MAX_INPUT + 1], vec![2, 2]] {
+            let mut bad = settings.clone();
+            bad.devices.insert("usb-interface".to_string(), invalid);
+            assert!(bad.validate().is_err());
+        }
+    }
+}
@@ -0,0 +1,262 @@
+//! Audio Chapters
+//!
+//! Builds chapter markers for a recorded session from its agenda progress
+//! (and optionally its markers), so listening back in a player allows jumping
+//! between agenda items. Chapters are written next to the session audio
+//! (`audio.wav`, `audio.flac` or `audio.opus`, see `audio_format`) as:
+//!
+//! - `audio.cue`: cue sheet referencing that audio file (WAV/FLAC players
+//!   and converters)
+//! - `chapters.ffmetadata`: FFmpeg metadata for muxing M4A chapters, e.g.
+//!   `ffmpeg -i audio.flac -i chapters.ffmetadata -map_metadata 1 -c:a aac audio.m4a`
+//!
+//! The app doesn't write M4A files itself: that needs an AAC encoder, which
+//! it doesn't ship. Producing the M4A is left to FFmpeg (or any muxer
+//! reading FFmpeg metadata).
+//!
+//! Timestamps are milliseconds relative to session start.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::agenda::AgendaTracker;
+use crate::markers::SessionMarker;
+
+pub const CUE_FILENAME: &str = "audio.cue";
+pub const FFMETADATA_FILENAME: &str = "chapters.ffmetadata";
+
+/// Chapters closer than this to the previous one are dropped
+pub const MIN_CHAPTER_GAP_MS: u64 = 1000;
+
+/// Single chapter
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Chapter {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub title: String,
+}
+
+/// Build chapters from agenda items (and markers when `include_markers`)
+///
+/// Chapters cover the whole recording: an "Introduction" chapter is added
+/// before the first agenda item and each chapter ends where the next begins.
+pub fn build_chapters(
+    agenda: &AgendaTracker,
+    markers: &[SessionMarker],
+    include_markers: bool,
+    duration_ms: u64,
+) -> Vec<Chapter> {
+    let mut starts: Vec<(u64, String)> = agenda
+        .items
+        .iter()
+        .filter_map(|item| item.started_at_ms.map(|at| (at, item.title.clone())))
+        .collect();
+    if include_markers {
+        starts.extend(markers.iter().map(|m| (m.timestamp_ms, m.label.clone())));
+    }
+    starts.retain(|(at, _)| *at < duration_ms);
+    starts.sort_by_key(|(at, _)| *at);
+
+    if starts.is_empty() {
+        return Vec::new();
+    }
+    if starts[0].0 >= MIN_CHAPTER_GAP_MS {
+        starts.insert(0, (0, "Introduction".to_string()));
+    } else {
+        starts[0].0 = 0;
+    }
+
+    let mut deduped: Vec<(u64, String)> = Vec::with_capacity(starts.len());
+    for (at, title) in starts {
+        match deduped.last() {
+            Some((last, _)) if at - last < MIN_CHAPTER_GAP_MS => {}
+            _ => deduped.push((at, title)),
+        }
+    }
+
+    let ends: Vec<u64> = deduped
+        .iter()
+        .skip(1)
+        .map(|(at, _)| *at)
+        .chain(std::iter::once(duration_ms))
+        .collect();
+    deduped
+        .into_iter()
+        .zip(ends)
+        .map(|((start_ms, title), end_ms)| Chapter {
+            start_ms,
+            end_ms,
+            title,
+        })
+        .collect()
+}
+
+/// Cue sheet text for `audio_file_name`
+///
+/// Cue INDEX times are `mm:ss:ff` with 75 frames per second.
+pub fn cue_sheet(audio_file_name: &str, session_title: &str, chapters: &[Chapter]) -> String {
+    let mut cue = format!(
+        "TITLE \"{}\"\nFILE \"{}\" WAVE\n",
+        escape_cue(session_title),
+        escape_cue(audio_file_name)
+    );
+    for (i, chapter) in chapters.iter().enumerate() {
+        let frames = chapter.start_ms * 75 / 1000;
+        cue.push_str(&format!(
+            "  TRACK {:02} AUDIO\n    TITLE \"{}\"\n    INDEX 01 {:02}:{:02}:{:02}\n",
+            i + 1,
+            escape_cue(&chapter.title),
+            frames / 75 / 60,
+            frames / 75 % 60,
+            frames % 75
+        ));
+    }
+    cue
+}
+
+fn escape_cue(text: &str) -> String {
+    text.replace('"', "'").replace(['\n', '\r'], " ")
+}
+
+/// FFmpeg metadata text with one `[CHAPTER]` per chapter
+pub fn ffmetadata(session_title: &str, chapters: &[Chapter]) -> String {
+    let mut meta = format!(";FFMETADATA1\ntitle={}\n", escape_ffmetadata(session_title));
+    for chapter in chapters {
+        meta.push_str(&format!(
+            "\n[CHAPTER]\nTIMEBASE=1/1000\nSTART={}\nEND={}\ntitle={}\n",
+            chapter.start_ms,
+            chapter.end_ms,
+            escape_ffmetadata(&chapter.title)
+        ));
+    }
+    meta
+}
+
+fn escape_ffmetadata(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '=' | ';' | '#' | '\\' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' | '\r' => escaped.push(' '),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Write `audio.cue` and `chapters.ffmetadata` into the session directory
+///
+/// The cue sheet names the session's audio file, whatever its format.
+pub fn write_chapter_files(
+    session_dir: &Path,
+    session_title: &str,
+    chapters: &[Chapter],
+) -> Result<()> {
+    let audio_file_name = crate::storage::session_audio_path(session_dir)
+        .and_then(|path| path.file_name()?.to_str().map(str::to_string))
+        .with_context(|| format!("No audio file in session directory: {:?}", session_dir))?;
+
+    let cue_path = session_dir.join(CUE_FILENAME);
+    std::fs::write(
+        &cue_path,
+        cue_sheet(&audio_file_name, session_title, chapters),
+    )
+    .with_context(|| format!("Failed to write cue sheet: {:?}", cue_path))?;
+
+    let meta_path = session_dir.join(FFMETADATA_FILENAME);
+    std::fs::write(&meta_path, ffmetadata(session_title, chapters))
+        .with_context(|| format!("Failed to write chapter metadata: {:?}", meta_path))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn agenda() -> AgendaTracker {
+        let mut agenda = AgendaTracker::from_titles(["予算", "採用"]);
+        agenda.mark_started("item-1", 65_000).unwrap();
+        agenda.mark_started("item-2", 125_500).unwrap();
+        agenda
+    }
+
+    #[test]
+    fn test_build_chapters_from_agenda() {
+        let chapters = build_chapters(&agenda(), &[], false, 300_000);
+        assert_eq!(
+            chapters,
+            vec![
+                Chapter {
+                    start_ms: 0,
+                    end_ms: 65_000,
+                    title: "Introduction".to_string()
+                },
+                Chapter {
+                    start_ms: 65_000,
+                    end_ms: 125_500,
+                    title: "予算".to_string()
+                },
+                Chapter {
+                    start_ms: 125_500,
+                    end_ms: 300_000,
+                    title: "採用".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_markers_are_merged_and_deduplicated() {
+        let markers = vec![
+            SessionMarker {
+                timestamp_ms: 65_400,
+                label: "duplicate".to_string(),
+                source: "user".to_string(),
+                text: None,
+            },
+            SessionMarker {
+                timestamp_ms: 200_000,
+                label: "Action item".to_string(),
+                source: "user".to_string(),
+                text: None,
+            },
+        ];
+        let chapters = build_chapters(&agenda(), &markers, true, 300_000);
+        let titles: Vec<&str> = chapters.iter().map(|c| c.title.as_str()).collect();
+        assert_eq!(titles, vec!["Introduction", "予算", "採用", "Action item"]);
+
+        assert!(build_chapters(&AgendaTracker::default(), &markers, false, 300_000).is_empty());
+    }
+
+    #[test]
+    fn test_cue_sheet_and_ffmetadata() {
+        let chapters = build_chapters(&agenda(), &[], false, 300_000);
+
+        let cue = cue_sheet("audio.wav", "Weekly \"sync\"", &chapters);
+        assert!(cue.starts_with("TITLE \"Weekly 'sync'\"\nFILE \"audio.wav\" WAVE\n"));
+        assert!(cue.contains("  TRACK 02 AUDIO\n    TITLE \"予算\"\n    INDEX 01 01:05:00\n"));
+        // 125.5s = 02:05 + 37 frames
+        assert!(cue.contains("INDEX 01 02:05:37"));
+
+        let meta = ffmetadata("a=b", &chapters);
+        assert!(meta.starts_with(";FFMETADATA1\ntitle=a\\=b\n"));
+        assert!(meta.contains("[CHAPTER]\nTIMEBASE=1/1000\nSTART=65000\nEND=125500\ntitle=予算\n"));
+    }
+
+    #[test]
+    fn test_cue_sheet_names_the_session_audio() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let chapters = build_chapters(&agenda(), &[], false, 300_000);
+        assert!(write_chapter_files(dir.path(), "s1", &chapters).is_err());
+
+        std::fs::write(dir.path().join("audio.flac"), b"fLaC").unwrap();
+        write_chapter_files(dir.path(), "s1", &chapters).unwrap();
+        let cue = std::fs::read_to_string(dir.path().join(CUE_FILENAME)).unwrap();
+        assert!(cue.contains("FILE \"audio.flac\" WAVE\n"));
+        assert!(dir.path().join(FFMETADATA_FILENAME).is_file());
+    }
+}
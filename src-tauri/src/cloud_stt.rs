@@ -0,0 +1,246 @@
+//! Cloud STT Fallback
+//!
+//! Transcription normally runs on the local Whisper model. With cloud STT
+//! configured, the sidecar uses a cloud provider (OpenAI, Azure OpenAI or
+//! Deepgram) behind the same engine interface instead:
+//!
+//! - `fallback`: only when the local model can't be loaded (missing model
+//!   files offline, broken install); the sidecar starts instead of failing.
+//! - `always`: opted in; the local model isn't loaded at all.
+//!
+//! Partial and final results keep their events. Azure and Deepgram are
+//! streamed: one connection per utterance, each second of audio uploaded
+//! once, partials from the provider's interim results. OpenAI has no
+//! streaming API, so it transcribes the final segment only and produces no
+//! partials. Either way audio leaves the machine and is billed per minute
+//! (see `transcription_estimate`).
+//!
+//! The API key is kept in the OS keychain, one per provider, and passed to
+//! the sidecar in its environment. Read when the sidecar starts.
+//!
+//! Settings are machine-wide; persisted to `settings/cloud_stt.json` in app
+//! data directory.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// Environment variable carrying the API key to the sidecar
+pub const API_KEY_ENV: &str = "MMA_CLOUD_STT_API_KEY";
+
+/// Keychain service of the API keys
+const KEYCHAIN_SERVICE: &str = "meeting-minutes-automator";
+
+/// When the cloud provider transcribes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CloudSttMode {
+    /// Local model only
+    #[default]
+    Off,
+    /// When the local model fails to load
+    Fallback,
+    /// Instead of the local model
+    Always,
+}
+
+impl CloudSttMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CloudSttMode::Off => "off",
+            CloudSttMode::Fallback => "fallback",
+            CloudSttMode::Always => "always",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CloudProvider {
+    #[default]
+    OpenAi,
+    Azure,
+    Deepgram,
+}
+
+impl CloudProvider {
+    /// Every provider the sidecar's cloud client implements
+    pub const ALL: [CloudProvider; 3] = [
+        CloudProvider::OpenAi,
+        CloudProvider::Azure,
+        CloudProvider::Deepgram,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CloudProvider::OpenAi => "openai",
+            CloudProvider::Azure => "azure",
+            CloudProvider::Deepgram => "deepgram",
+        }
+    }
+}
+
+/// Cloud STT configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CloudSttSettings {
+    #[serde(default)]
+    pub mode: CloudSttMode,
+    #[serde(default)]
+    pub provider: CloudProvider,
+    /// Base URL (required for Azure, e.g. `https://<resource>.openai.azure.com`;
+    /// overrides the provider's default otherwise)
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    /// Model (Azure: deployment) name; the provider's default if None
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Settings version for future migrations
+    #[serde(default = "default_version")]
+    pub version: u32,
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+impl Default for CloudSttSettings {
+    fn default() -> Self {
+        Self {
+            mode: CloudSttMode::Off,
+            provider: CloudProvider::OpenAi,
+            endpoint: None,
+            model: None,
+            version: 1,
+        }
+    }
+}
+
+impl CloudSttSettings {
+    pub fn validate(&self) -> Result<()> {
+        if let Some(endpoint) = &self.endpoint {
+            if !endpoint.starts_with("https://") {
+                anyhow::bail!("endpoint must be an https:// URL");
+            }
+        }
+        if self.provider == CloudProvider::Azure && self.endpoint.is_none() {
+            anyhow::bail!("Azure needs the endpoint of its resource");
+        }
+        if self
+            .model
+            .as_deref()
+            .is_some_and(|model| model.trim().is_empty())
+        {
+            anyhow::bail!("model must not be empty");
+        }
+        Ok(())
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.mode != CloudSttMode::Off
+    }
+
+    /// Sidecar command line arguments (the API key goes in `API_KEY_ENV`)
+    pub fn sidecar_args(&self) -> Vec<String> {
+        if !self.is_enabled() {
+            return Vec::new();
+        }
+        let mut args = vec![
+            "--cloud-stt".to_string(),
+            self.mode.as_str().to_string(),
+            "--cloud-provider".to_string(),
+            self.provider.as_str().to_string(),
+        ];
+        if let Some(endpoint) = &self.endpoint {
+            args.extend(["--cloud-endpoint".to_string(), endpoint.clone()]);
+        }
+        if let Some(model) = &self.model {
+            args.extend(["--cloud-model".to_string(), model.clone()]);
+        }
+        args
+    }
+}
+
+// ============================================================================
+// API keys
+// ============================================================================
+
+fn keychain_entry(provider: CloudProvider) -> Result<keyring::Entry> {
+    keyring::Entry::new(
+        KEYCHAIN_SERVICE,
+        &format!("cloud-stt-api-key:{}", provider.as_str()),
+    )
+    .context("Failed to open the OS keychain")
+}
+
+/// API key of `provider`, if one is stored
+pub fn load_api_key(provider: CloudProvider) -> Result<Option<String>> {
+    match keychain_entry(provider)?.get_password() {
+        Ok(key) => Ok(Some(key)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(e).context("Failed to read the cloud STT API key from the OS keychain"),
+    }
+}
+
+/// Store the API key of `provider` (None removes it)
+pub fn store_api_key(provider: CloudProvider, key: Option<&str>) -> Result<()> {
+    let entry = keychain_entry(provider)?;
+    match key {
+        Some(key) => entry
+            .set_password(key)
+            .context("Failed to store the cloud STT API key in the OS keychain"),
+        None => match entry.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(e).context("Failed to remove the cloud STT API key"),
+        },
+    }
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+pub const SETTINGS_STEM: &str = "cloud_stt";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sidecar_args_and_validation() {
+        let off = CloudSttSettings::default();
+        assert!(off.validate().is_ok());
+        assert!(off.sidecar_args().is_empty());
+
+        let azure = CloudSttSettings {
+            mode: CloudSttMode::Fallback,
+            provider: CloudProvider::Azure,
+            endpoint: Some("https://res.openai.azure.com".to_string()),
+            model: Some("whisper".to_string()),
+            ..Default::default()
+        };
+        assert!(azure.validate().is_ok());
+        assert_eq!(
+            azure.sidecar_args(),
+            [
+                "--cloud-stt",
+                "fallback",
+                "--cloud-provider",
+                "azure",
+                "--cloud-endpoint",
+                "https://res.openai.azure.com",
+                "--cloud-model",
+                "whisper"
+            ]
+        );
+
+        let no_endpoint = CloudSttSettings {
+            endpoint: None,
+            ..azure.clone()
+        };
+        assert!(no_endpoint.validate().is_err());
+        let plain_http = CloudSttSettings {
+            endpoint: Some("http://res.openai.azure.com".to_string()),
+            ..azure
+        };
+        assert!(plain_http.validate().is_err());
+    }
+}
@@ -6,10 +6,17 @@
 
 use crate::audio_device_adapter::AudioDeviceEvent;
 use crate::audio_device_recorder::{MixerConfig, RecordingMode};
+use crate::ipc_protocol::{
+    decode_incoming, encode_audio_channel_request, encode_audio_stream_request, IncomingMessage,
+    IpcMessage as ProtocolMessage, VersionCompatibility, PROTOCOL_VERSION,
+};
 use crate::multi_input_manager::InputStatus;
-use crate::ipc_protocol::{IpcMessage as ProtocolMessage, VersionCompatibility, PROTOCOL_VERSION};
+use crate::pipeline::StageKind;
+use crate::recording_session::RecordingSession;
 use crate::ring_buffer::{new_shared_ring_buffer, pop_audio, push_audio_drop_oldest, BufferLevel};
+use crate::settings_file;
 use crate::state::AppState;
+use crate::stdin_writer::{ControlMessage, StdinWriter, StdinWriterError};
 use crate::websocket::WebSocketMessage;
 use once_cell::sync::Lazy;
 use serde_json::json;
@@ -48,6 +55,56 @@ fn request_id_from(data: &serde_json::Value) -> Option<&str> {
     data.get("requestId").and_then(|v| v.as_str())
 }
 
+/// Check an event's `requestId` against the session's namespace (see `request_id`)
+///
+/// Events without a request ID (or outside a session) are accepted; IDs not
+/// issued by this session are logged and the event is skipped.
+fn accept_event_request_id(
+    event_type: &str,
+    data: &serde_json::Value,
+    session_id: &str,
+    app: &tauri::AppHandle,
+) -> bool {
+    let (Some(raw), Some(session)) = (
+        request_id_from(data),
+        app.state::<AppState>().get_session(session_id),
+    ) else {
+        return true;
+    };
+    match session.request_ids().validate(raw) {
+        Ok(_) => true,
+        Err(e) => {
+            log_warn_details!(
+                "commands::ipc_events",
+                "request_id_rejected",
+                json!({
+                    "session": session_id,
+                    "request": raw,
+                    "event_type": event_type,
+                    "error": e.to_string()
+                })
+            );
+            false
+        }
+    }
+}
+
+/// Whether this final text was already finalized for the same request
+fn is_duplicate_final(
+    data: &serde_json::Value,
+    text: &str,
+    session_id: &str,
+    app: &tauri::AppHandle,
+) -> bool {
+    let Some(session) = app.state::<AppState>().get_session(session_id) else {
+        return false;
+    };
+    let namespace = session.request_ids();
+    request_id_from(data)
+        .and_then(|raw| namespace.validate(raw).ok())
+        .is_some_and(|id| namespace.is_duplicate_final(&id, text))
+}
+
 /// Helper: Extract extended fields from IPC event data (STT-REQ-008.1)
 /// Used by both partial_text and final_text branches to avoid code duplication
 fn extract_extended_fields(
@@ -62,6 +119,9 @@ fn extract_extended_fields(
     (confidence, language, processing_time_ms)
 }
 
+/// Maximum automatic restarts of a panicked IPC reader
+const MAX_IPC_READER_RESTARTS: u32 = 3;
+
 /// Background IPC event reader task (ADR-013: Full-Duplex IPC)
 /// Requirement: STT-REQ-007 (non-blocking event stream)
 ///
@@ -70,52 +130,81 @@ fn extract_extended_fields(
 ///
 /// FIXED (Phase 14.5): Now uses separate stdout handle instead of shared sidecar lock.
 /// This eliminates Mutex contention between reader and sender tasks.
-async fn start_ipc_reader_task(
+///
+/// One reader serves all recording sessions: events are routed to the
+/// session whose request they answer (see `SessionRegistry::event_session`)
+/// and handled on its event task. It runs until the sidecar closes stdout.
+fn start_ipc_reader_task(
     stdout: Arc<tokio::sync::Mutex<tokio::io::BufReader<tokio::process::ChildStdout>>>,
     app: tauri::AppHandle,
-    session_id: String,
-    websocket_server: Arc<tokio::sync::Mutex<crate::websocket::WebSocketServer>>,
-    cancel_token: tokio_util::sync::CancellationToken,
-) {
+    restarts_left: u32,
+) -> tokio::task::JoinHandle<()> {
     use tokio::io::AsyncBufReadExt;
 
-    tokio::spawn(async move {
-        loop {
-            // Read from stdout with cancellation support using select!
-            let response = {
-                let mut stdout_guard = stdout.lock().await;
-                let mut line = String::new();
-
-                // Use select! to race between cancellation and read
-                let read_result = tokio::select! {
-                    _ = cancel_token.cancelled() => {
-                        log_info!("commands::ipc_reader", "cancelled");
-                        return; // Exit the entire task
-                    }
-                    result = stdout_guard.read_line(&mut line) => result
-                };
+    // Recovery: restart the reader (stdout stays usable), or stop the
+    // sessions once the restart budget is exhausted
+    let recovery = {
+        let stdout = Arc::clone(&stdout);
+        let app = app.clone();
+        move |panic: String| async move {
+            let state = app.state::<AppState>();
+            let session_id = state.get_session_id().unwrap_or_default();
+            if restarts_left == 0 {
+                report_task_failure(&app, "ipc_reader", &session_id, &panic, "stop_session");
+                for session_id in state.session_ids() {
+                    spawn_recovery_stop(app.clone(), session_id);
+                }
+                return;
+            }
 
-                match read_result {
-                    Ok(0) => {
-                        // EOF - process closed
-                        log_info!("commands::ipc_reader", "stdout_eof");
-                        break;
+            report_task_failure(&app, "ipc_reader", &session_id, &panic, "restart");
+            let handle = start_ipc_reader_task(stdout, app.clone(), restarts_left - 1);
+            state.set_ipc_reader(handle);
+        }
+    };
+
+    let reader = async move {
+        loop {
+            let mut line = String::new();
+            let read_result = stdout.lock().await.read_line(&mut line).await;
+            // Logged with the primary session (events carry their own)
+            let session_id = app.state::<AppState>().get_session_id().unwrap_or_default();
+
+            let response = match read_result {
+                Ok(0) => {
+                    // EOF - process closed
+                    log_info!("commands::ipc_reader", "stdout_eof");
+                    break;
+                }
+                Ok(_) => {
+                    if line.trim().is_empty() {
+                        continue;
                     }
-                    Ok(_) => {
-                        if line.trim().is_empty() {
+                    match serde_json::from_str::<serde_json::Value>(&line) {
+                        Ok(json) => Ok((line, json)),
+                        Err(e) => {
+                            // Malformed line: quarantine and keep reading
+                            let error = format!("JSON parse error: {:?}", e);
+                            if quarantine_ipc_line(&app, &session_id, &line, &error) {
+                                break;
+                            }
                             continue;
                         }
-                        match serde_json::from_str::<serde_json::Value>(&line) {
-                            Ok(json) => Ok(json),
-                            Err(e) => Err(format!("JSON parse error: {:?}", e)),
-                        }
                     }
-                    Err(e) => Err(format!("Read error: {:?}", e)),
                 }
+                Err(e) if e.kind() == std::io::ErrorKind::InvalidData => {
+                    // Invalid UTF-8: the bytes are consumed, so the next line is readable
+                    let error = format!("Read error: {:?}", e);
+                    if quarantine_ipc_line(&app, &session_id, &line, &error) {
+                        break;
+                    }
+                    continue;
+                }
+                Err(e) => Err(format!("Read error: {:?}", e)),
             };
 
             match response {
-                Ok(response) => {
+                Ok((line, response)) => {
                     // Debug: Log received IPC message type
                     log_debug_details!(
                         "commands::ipc_reader",
@@ -125,21 +214,53 @@ async fn start_ipc_reader_task(
                             "message_type": response.get("type").or(response.get("event_type")).unwrap_or(&serde_json::Value::Null)
                         })
                     );
+                    // Health check answers aren't IPC protocol messages
+                    if let Some(pong) = crate::ipc_protocol::Pong::from_message(&response) {
+                        app.state::<AppState>().record_valid_ipc_line();
+                        handle_sidecar_pong(&app, &session_id, &pong);
+                        continue;
+                    }
                     // Parse IPC message
-                    let msg = match serde_json::from_value::<ProtocolMessage>(response.clone()) {
-                        Ok(m) => m,
+                    let msg = match decode_incoming(response) {
+                        Ok(IncomingMessage::Known(m)) => m,
+                        Ok(IncomingMessage::UnknownType {
+                            message_type,
+                            version,
+                        }) => {
+                            // Newer sidecar: count and skip (first occurrence per type is a warning)
+                            let state = app.state::<AppState>();
+                            state.record_valid_ipc_line();
+                            let count = state.record_unknown_ipc_message_type(&message_type);
+                            let details = json!({
+                                "session": session_id,
+                                "message_type": message_type,
+                                "version": version,
+                                "count": count
+                            });
+                            if count == 1 {
+                                log_warn_details!(
+                                    "commands::ipc_reader",
+                                    "unknown_message_type_skipped",
+                                    details
+                                );
+                            } else {
+                                log_debug_details!(
+                                    "commands::ipc_reader",
+                                    "unknown_message_type_skipped",
+                                    details
+                                );
+                            }
+                            continue;
+                        }
                         Err(e) => {
-                            log_error_details!(
-                                "commands::ipc_reader",
-                                "parse_ipc_failed",
-                                json!({
-                                    "session": session_id,
-                                    "error": format!("{:?}", e)
-                                })
-                            );
-                            break;
+                            let error = format!("IPC parse error: {:?}", e);
+                            if quarantine_ipc_line(&app, &session_id, &line, &error) {
+                                break;
+                            }
+                            continue;
                         }
                     };
+                    app.state::<AppState>().record_valid_ipc_line();
 
                     // Version compatibility check
                     match msg.check_version_compatibility() {
@@ -197,20 +318,34 @@ async fn start_ipc_reader_task(
                         }
                     }
 
-                    // Handle events (same logic as before, extracted for brevity)
+                    // Events are handled on their session's event task
                     let session_id_ref = session_id.as_str();
                     match msg {
                         ProtocolMessage::Event {
                             event_type, data, ..
                         } => {
-                            handle_ipc_event(
-                                &event_type,
-                                &data,
-                                session_id_ref,
-                                &websocket_server,
-                                &app,
-                            )
-                            .await;
+                            let Some(session) = app.state::<AppState>().event_session(&data) else {
+                                // Answer to a stopped session (or nothing is recording)
+                                log_debug_details!(
+                                    "commands::ipc_reader",
+                                    "event_without_session",
+                                    json!({
+                                        "event_type": event_type,
+                                        "request": request_id_from(&data)
+                                    })
+                                );
+                                continue;
+                            };
+                            if !session.deliver_ipc_event(&event_type, data) {
+                                log_debug_details!(
+                                    "commands::ipc_reader",
+                                    "session_event_dropped",
+                                    json!({
+                                        "session": session.session_id(),
+                                        "event_type": event_type
+                                    })
+                                );
+                            }
                         }
                         ProtocolMessage::Error { error_message, .. } => {
                             log_error_details!(
@@ -224,6 +359,7 @@ async fn start_ipc_reader_task(
                             break;
                         }
                         _ => {
+                            // Requests/responses aren't expected on this stream: skip them
                             log_warn_details!(
                                 "commands::ipc_reader",
                                 "unexpected_message_type",
@@ -232,7 +368,6 @@ async fn start_ipc_reader_task(
                                     "message": msg
                                 })
                             );
-                            break;
                         }
                     }
                 }
@@ -249,102 +384,697 @@ async fn start_ipc_reader_task(
                 }
             }
         }
+    };
+    crate::task_supervisor::spawn_supervised("ipc_reader", reader, recovery)
+}
+
+/// Record a sidecar `pong` and log the health it reports
+fn handle_sidecar_pong(app: &tauri::AppHandle, session_id: &str, pong: &crate::ipc_protocol::Pong) {
+    let state = app.state::<AppState>();
+    if !state.record_sidecar_pong(pong, now_epoch_ms()) {
+        log_debug_details!(
+            "commands::sidecar_health",
+            "pong_unmatched",
+            json!({ "session": session_id, "id": pong.id })
+        );
+        return;
+    }
+
+    let details = json!({
+        "session": session_id,
+        "round_trip_ms": state.sidecar_status().round_trip_ms,
+        "health": pong.health
     });
+    match &pong.health {
+        Some(health) if crate::sidecar_health::is_slow(health) => {
+            log_warn_details!("commands::sidecar_health", "sidecar_slow", details);
+        }
+        _ => {
+            log_info_details!("commands::sidecar_health", "sidecar_health", details);
+        }
+    }
 }
 
-/// Helper function to handle IPC events (extracted from inline logic)
-/// Reduces code duplication between old audio callback loop and new background reader
-async fn handle_ipc_event(
-    event_type: &str,
-    data: &serde_json::Value,
+/// Quarantine a malformed sidecar line (see `ipc_quarantine`)
+///
+/// Returns true once the consecutive-error threshold is reached; the reader
+/// then terminates and `ipc_reader_failed` tells the user transcription stopped.
+fn quarantine_ipc_line(app: &tauri::AppHandle, session_id: &str, raw: &str, error: &str) -> bool {
+    use crate::ipc_quarantine::QuarantineVerdict;
+
+    let state = app.state::<AppState>();
+    match state.quarantine_ipc_line(raw, error, crate::latency::now_ms()) {
+        QuarantineVerdict::Continue { consecutive } => {
+            log_warn_details!(
+                "commands::ipc_reader",
+                "malformed_line_quarantined",
+                json!({
+                    "session": session_id,
+                    "error": error,
+                    "raw_bytes": raw.len(),
+                    "consecutive": consecutive
+                })
+            );
+            false
+        }
+        QuarantineVerdict::ThresholdExceeded {
+            consecutive,
+            threshold,
+        } => {
+            log_error_details!(
+                "commands::ipc_reader",
+                "malformed_line_threshold_exceeded",
+                json!({
+                    "session": session_id,
+                    "error": error,
+                    "consecutive": consecutive,
+                    "threshold": threshold
+                })
+            );
+            let _ = app.emit(
+                "ipc_reader_failed",
+                json!({
+                    "session_id": session_id,
+                    "message": format!(
+                        "{} consecutive malformed messages from the speech recognition process; transcription stopped",
+                        consecutive
+                    ),
+                    "last_error": error,
+                    "consecutive": consecutive,
+                    "threshold": threshold
+                }),
+            );
+            true
+        }
+    }
+}
+
+/// Minimum interval between `audio_queue_overflow` notifications
+const AUDIO_QUEUE_OVERFLOW_REPORT_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Log dropped audio and notify the frontend (`audio_queue_overflow`)
+fn report_audio_queue_overflow(
+    app: &tauri::AppHandle,
     session_id: &str,
-    websocket_server: &Arc<tokio::sync::Mutex<crate::websocket::WebSocketServer>>,
+    newly_dropped_bytes: u64,
+    snapshot: crate::ring_buffer::AudioQueueSnapshot,
+) {
+    let newly_dropped_ms = crate::ring_buffer::ms_for_bytes(newly_dropped_bytes as usize);
+    log_warn_details!(
+        "commands::recording",
+        "audio_queue_overflow",
+        json!({
+            "session": session_id,
+            "dropped_ms": newly_dropped_ms,
+            "total_dropped_ms": snapshot.dropped_ms,
+            "queued_ms": snapshot.queued_ms
+        })
+    );
+    let _ = app.emit(
+        "audio_queue_overflow",
+        json!({
+            "session_id": session_id,
+            "dropped_ms": newly_dropped_ms,
+            "metrics": snapshot
+        }),
+    );
+}
+
+/// Log a supervised task failure and notify the frontend
+fn report_task_failure(
     app: &tauri::AppHandle,
+    task: &str,
+    session_id: &str,
+    panic: &str,
+    recovery: &str,
 ) {
-    match event_type {
-        "speech_start" => {
-            let request_id = request_id_from(data).unwrap_or("unknown");
-            log_info_details!(
-                "commands::ipc_events",
-                "speech_start",
+    log_error_details!(
+        "commands::supervision",
+        "pipeline_task_failed",
+        json!({
+            "task": task,
+            "session": session_id,
+            "panic": panic,
+            "recovery": recovery
+        })
+    );
+    let _ = app.emit(
+        "pipeline_task_failed",
+        json!({
+            "task": task,
+            "session_id": session_id,
+            "message": panic,
+            "recovery": recovery
+        }),
+    );
+    notify_ops_event(
+        app,
+        crate::ops_webhooks::OpsEventKind::PipelineTaskFailed,
+        panic,
+        json!({ "task": task, "session_id": session_id, "recovery": recovery }),
+    );
+}
+
+/// Stop a session from a recovery path
+/// Runs in its own task: teardown aborts recording tasks, including the caller
+fn spawn_recovery_stop(app: tauri::AppHandle, session_id: String) {
+    tokio::spawn(async move {
+        let state = app.state::<AppState>();
+        if let Err(e) = stop_recording_internal(&state, &session_id).await {
+            log_error_details!(
+                "commands::supervision",
+                "recovery_stop_failed",
+                json!({ "session": session_id, "error": e })
+            );
+        }
+    });
+}
+
+/// Handle the sidecar events routed to a session until it is cancelled
+fn start_ipc_event_task(
+    app: tauri::AppHandle,
+    session: Arc<RecordingSession>,
+    websocket_server: Arc<tokio::sync::Mutex<crate::websocket::WebSocketServer>>,
+    cancel_token: tokio_util::sync::CancellationToken,
+) -> tokio::task::JoinHandle<()> {
+    let mut events = session.open_ipc_channel();
+    let session_id = session.session_id().to_string();
+
+    let app_recovery = app.clone();
+    let session_id_recovery = session_id.clone();
+    let cancel_token_recovery = cancel_token.clone();
+    let event_loop = async move {
+        loop {
+            let event = tokio::select! {
+                _ = cancel_token.cancelled() => break,
+                event = events.recv() => event,
+            };
+            let Some(event) = event else {
+                break;
+            };
+            session.record_ipc_event();
+            handle_ipc_event(
+                &event.event_type,
+                &event.data,
+                &session_id,
+                &websocket_server,
+                &app,
+            )
+            .await;
+        }
+        log_info_details!(
+            "commands::ipc_events",
+            "task_ended",
+            json!({ "session": session_id })
+        );
+    };
+    crate::task_supervisor::spawn_supervised("ipc_events", event_loop, move |panic| async move {
+        // Transcripts can no longer reach clients: stop the session cleanly
+        if !cancel_token_recovery.is_cancelled() {
+            report_task_failure(
+                &app_recovery,
+                "ipc_events",
+                &session_id_recovery,
+                &panic,
+                "stop_session",
+            );
+            spawn_recovery_stop(app_recovery, session_id_recovery);
+        }
+    })
+}
+
+/// Emit `keyword-alert` (Tauri event + WebSocket) for watchlist hits in a final segment
+async fn dispatch_keyword_alerts(
+    text: &str,
+    session_id: &str,
+    ws_server: &crate::websocket::WebSocketServer,
+    app: &tauri::AppHandle,
+) {
+    let matches = app
+        .state::<AppState>()
+        .get_keyword_alert_settings()
+        .find_matches(text);
+
+    for hit in matches {
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+
+        log_info_details!(
+            "commands::keyword_alerts",
+            "keyword_matched",
+            json!({
+                "session": session_id,
+                "watchlist": hit.watchlist,
+                "keyword": hit.keyword
+            })
+        );
+
+        let _ = app.emit(
+            "keyword-alert",
+            json!({
+                "session_id": session_id,
+                "watchlist": hit.watchlist,
+                "keyword": hit.keyword,
+                "context": hit.context,
+                "timestamp": timestamp
+            }),
+        );
+
+        let ws_message = WebSocketMessage::KeywordAlert {
+            message_id: crate::message_id::next_id(crate::message_id::WEBSOCKET, session_id),
+            session_id: session_id.to_string(),
+            watchlist: hit.watchlist,
+            keyword: hit.keyword,
+            context: hit.context,
+            timestamp,
+        };
+        if let Err(e) = ws_server.broadcast(ws_message).await {
+            log_error_details!(
+                "commands::keyword_alerts",
+                "broadcast_keyword_alert_failed",
                 json!({
                     "session": session_id,
-                    "request": request_id
+                    "error": format!("{:?}", e)
                 })
             );
         }
-        "partial_text" => {
-            let request_id = request_id_from(data).unwrap_or("unknown");
-            if let Some(text) = data.get("text").and_then(|v| v.as_str()) {
-                let (confidence, language, processing_time_ms) = if let Some(obj) = data.as_object()
-                {
-                    extract_extended_fields(obj)
-                } else {
-                    (None, None, None)
-                };
+    }
+}
 
-                // Filter out low-confidence hallucinations (common with Whisper on silence)
-                const MIN_CONFIDENCE: f64 = 0.50;
-                if let Some(conf) = confidence {
-                    if conf < MIN_CONFIDENCE {
-                        log_debug_details!(
-                            "commands::ipc_events",
-                            "partial_text_filtered",
-                            json!({
-                                "session": session_id,
-                                "request": request_id,
-                                "confidence": conf,
-                                "threshold": MIN_CONFIDENCE
-                            })
-                        );
-                        return; // Skip this low-confidence transcription
-                    }
+/// Count a broadcast final segment against the Google Docs character budget
+///
+/// Notifies clients once when the current document nears the limit and once
+/// when it reaches it (see `docs_budget`).
+async fn track_docs_budget(
+    text: &str,
+    session_id: &str,
+    ws_server: &crate::websocket::WebSocketServer,
+    app: &tauri::AppHandle,
+) {
+    let state = app.state::<AppState>();
+    let Some(alert) = state.record_docs_inserted(text) else {
+        return;
+    };
+    let status = state.docs_budget_status();
+
+    log_warn_details!(
+        "commands::docs_budget",
+        alert.notification_type(),
+        json!({
+            "session": session_id,
+            "document": status.document_id,
+            "document_characters": status.document_characters,
+            "limit_characters": status.limit_characters
+        })
+    );
+
+    let _ = app.emit("docs-budget", &status);
+
+    let ws_message = WebSocketMessage::Notification {
+        message_id: crate::message_id::next_id(crate::message_id::WEBSOCKET, session_id),
+        session_id: session_id.to_string(),
+        notification_type: alert.notification_type().to_string(),
+        message: alert.message().to_string(),
+        timestamp: now_epoch_ms(),
+        data: serde_json::to_value(&status).ok(),
+    };
+    if let Err(e) = ws_server.broadcast(ws_message).await {
+        log_error_details!(
+            "commands::docs_budget",
+            "broadcast_docs_budget_failed",
+            json!({
+                "session": session_id,
+                "error": format!("{:?}", e)
+            })
+        );
+    }
+}
+
+static ROUTING_WEBHOOK_CLIENT: Lazy<reqwest::Client> = Lazy::new(|| {
+    reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(5))
+        .build()
+        .unwrap_or_default()
+});
+
+/// Dispatch a final segment to the targets of every matching routing rule
+/// Webhooks are sent in the background so the IPC reader is never blocked
+fn dispatch_routing_rules(text: &str, timestamp_ms: u64, session_id: &str, app: &tauri::AppHandle) {
+    use crate::routing::{RoutedSegment, RoutingTarget};
+    use tauri_plugin_clipboard_manager::ClipboardExt;
+
+    let state = app.state::<AppState>();
+    let routes = state.get_routing_engine().route(text);
+    if routes.is_empty() {
+        return;
+    }
+    let session_dir = state
+        .get_storage_service()
+        .map(|storage| storage.get_session_dir(session_id));
+
+    for route in routes {
+        log_info_details!(
+            "commands::routing",
+            "rule_matched",
+            json!({
+                "session": session_id,
+                "rule": route.rule_name,
+                "target_count": route.targets.len()
+            })
+        );
+
+        let segment = RoutedSegment {
+            session_id: session_id.to_string(),
+            rule: route.rule_name.clone(),
+            matched: route.matched.clone(),
+            text: text.to_string(),
+            timestamp_ms,
+        };
+
+        for target in &route.targets {
+            let result = match (target, session_dir.as_deref()) {
+                (RoutingTarget::Webhook { url }, _) => {
+                    spawn_routing_webhook(
+                        OutboundRateLimit::from_state(&state),
+                        url.clone(),
+                        segment.clone(),
+                    );
+                    Ok(())
+                }
+                (RoutingTarget::Clipboard, _) => app
+                    .clipboard()
+                    .write_text(text.to_string())
+                    .map_err(|e| anyhow::anyhow!(e.to_string())),
+                (RoutingTarget::Jsonl { file_name }, Some(dir)) => {
+                    crate::routing::append_jsonl(dir, file_name, &segment)
+                }
+                (RoutingTarget::Marker { label }, Some(dir)) => crate::markers::append_marker(
+                    dir,
+                    &crate::markers::SessionMarker {
+                        timestamp_ms,
+                        label: label.clone().unwrap_or_else(|| route.rule_name.clone()),
+                        source: format!("routing:{}", route.rule_name),
+                        text: Some(text.to_string()),
+                    },
+                ),
+                (RoutingTarget::Jsonl { .. } | RoutingTarget::Marker { .. }, None) => {
+                    Err(anyhow::anyhow!("Storage not initialized"))
                 }
+            };
 
-                let masked = mask_text(text);
-                log_info_details!(
-                    "commands::ipc_events",
-                    "partial_text",
+            if let Err(e) = result {
+                log_warn_details!(
+                    "commands::routing",
+                    "target_failed",
                     json!({
                         "session": session_id,
-                        "request": request_id,
-                        "text_masked": masked,
-                        "confidence": confidence
+                        "rule": route.rule_name,
+                        "target": target,
+                        "error": e.to_string()
                     })
                 );
+            }
+        }
+    }
+}
 
-                // Clone for emit (before move into WebSocketMessage)
-                let emit_language = language.clone();
-
-                let ws_message = WebSocketMessage::Transcription {
-                    message_id: format!(
-                        "ws-{}",
-                        std::time::SystemTime::now()
-                            .duration_since(std::time::UNIX_EPOCH)
-                            .unwrap()
-                            .as_millis()
-                    ),
-                    session_id: session_id.to_string(),
-                    text: text.to_string(),
-                    timestamp: std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH)
-                        .unwrap()
-                        .as_millis() as u64,
-                    is_partial: Some(true),
-                    confidence,
-                    language,
-                    processing_time_ms,
+/// Detect questions and apparent answers in a final segment
+/// Emits `question_detected` / `question_answered` to the frontend
+fn track_questions(text: &str, timestamp_ms: u64, session_id: &str, app: &tauri::AppHandle) {
+    use crate::questions::QuestionUpdate;
+
+    let updates = app
+        .state::<AppState>()
+        .question_tracker
+        .lock()
+        .unwrap()
+        .observe(timestamp_ms, text);
+
+    for update in updates {
+        let (event, question) = match update {
+            QuestionUpdate::Detected(question) => ("question_detected", question),
+            QuestionUpdate::Answered(question) => ("question_answered", question),
+        };
+        log_debug_details!(
+            "commands::questions",
+            event,
+            json!({
+                "session": session_id,
+                "question_at_ms": question.timestamp_ms
+            })
+        );
+        let _ = app.emit(
+            event,
+            json!({
+                "session_id": session_id,
+                "question": question
+            }),
+        );
+    }
+}
+
+/// Feed a final segment to the rolling summary and run the update once it is due
+/// The update runs in the background; segments keep accumulating meanwhile
+fn dispatch_live_summary(
+    text: &str,
+    timestamp_ms: u64,
+    session_id: &str,
+    websocket_server: &Arc<tokio::sync::Mutex<crate::websocket::WebSocketServer>>,
+    app: &tauri::AppHandle,
+) {
+    let state = app.state::<AppState>();
+    let update = state
+        .live_summary
+        .lock()
+        .unwrap()
+        .as_mut()
+        .and_then(|rolling| rolling.push_final(timestamp_ms, text));
+    let (Some(update), Some(storage)) = (update, state.get_storage_service()) else {
+        return;
+    };
+
+    let app = app.clone();
+    let session_id = session_id.to_string();
+    let websocket_server = Arc::clone(websocket_server);
+    tokio::spawn(async move {
+        let prompt =
+            crate::summary::build_rolling_prompt(&update.previous_summary, &update.excerpt);
+        let result =
+            match settings_file::load(storage.app_data_dir(), crate::summary::SETTINGS_STEM) {
+                Ok(settings) => {
+                    crate::summary::stream_completion(&SUMMARY_CLIENT, &settings, &prompt, |_| {})
+                        .await
+                }
+                Err(e) => Err(e),
+            };
+
+        let state = app.state::<AppState>();
+        if state.get_session(&session_id).is_none() {
+            return; // Session ended while the update was running
+        }
+        let text = match result {
+            Ok(text) => text.trim().to_string(),
+            Err(e) => {
+                log_warn_details!(
+                    "commands::summary",
+                    "live_summary_failed",
+                    json!({ "session": session_id, "error": format!("{:#}", e) })
+                );
+                if let Some(rolling) = state.live_summary.lock().unwrap().as_mut() {
+                    rolling.fail(update);
+                }
+                return;
+            }
+        };
+        if let Some(rolling) = state.live_summary.lock().unwrap().as_mut() {
+            rolling.complete(&update, text.clone());
+        }
+
+        log_info_details!(
+            "commands::summary",
+            "live_summary_updated",
+            json!({
+                "session": session_id,
+                "covered_until_ms": update.covered_until_ms,
+                "summary_chars": text.chars().count()
+            })
+        );
+
+        let timestamp = now_epoch_ms();
+        let _ = app.emit(
+            "live_summary",
+            json!({
+                "session_id": session_id,
+                "text": text,
+                "covered_until_ms": update.covered_until_ms
+            }),
+        );
+        let ws_message = WebSocketMessage::LiveSummary {
+            message_id: crate::message_id::next_id(crate::message_id::WEBSOCKET, &session_id),
+            session_id: session_id.clone(),
+            text,
+            covered_until_ms: update.covered_until_ms,
+            timestamp,
+        };
+        if let Err(e) = websocket_server.lock().await.broadcast(ws_message).await {
+            log_error_details!(
+                "commands::summary",
+                "broadcast_live_summary_failed",
+                json!({
+                    "session": session_id,
+                    "error": format!("{:?}", e)
+                })
+            );
+        }
+    });
+}
+
+fn spawn_routing_webhook(
+    rate_limit: OutboundRateLimit,
+    url: String,
+    segment: crate::routing::RoutedSegment,
+) {
+    tokio::spawn(async move {
+        rate_limit.wait(&url).await;
+        let result = ROUTING_WEBHOOK_CLIENT
+            .post(&url)
+            .json(&segment)
+            .send()
+            .await
+            .inspect(|response| rate_limit.note_response(&url, response))
+            .and_then(|response| response.error_for_status());
+
+        if let Err(e) = result {
+            log_warn_details!(
+                "commands::routing",
+                "webhook_failed",
+                json!({
+                    "session": segment.session_id,
+                    "rule": segment.rule,
+                    "error": e.to_string()
+                })
+            );
+        }
+    });
+}
+
+/// Helper function to handle IPC events (extracted from inline logic)
+/// Reduces code duplication between old audio callback loop and new background reader
+async fn handle_ipc_event(
+    event_type: &str,
+    data: &serde_json::Value,
+    session_id: &str,
+    websocket_server: &Arc<tokio::sync::Mutex<crate::websocket::WebSocketServer>>,
+    app: &tauri::AppHandle,
+) {
+    if !accept_event_request_id(event_type, data, session_id, app) {
+        return;
+    }
+
+    match event_type {
+        "speech_start" => {
+            let request_id = request_id_from(data).unwrap_or("unknown");
+            log_info_details!(
+                "commands::ipc_events",
+                "speech_start",
+                json!({
+                    "session": session_id,
+                    "request": request_id
+                })
+            );
+        }
+        "partial_text" => {
+            let request_id = request_id_from(data).unwrap_or("unknown");
+            if let Some(text) = data.get("text").and_then(|v| v.as_str()) {
+                let (confidence, language, processing_time_ms) = if let Some(obj) = data.as_object()
+                {
+                    extract_extended_fields(obj)
+                } else {
+                    (None, None, None)
                 };
 
-                let ws_server = websocket_server.lock().await;
-                if let Err(e) = ws_server.broadcast(ws_message).await {
-                    log_error_details!(
+                // Filter out low-confidence hallucinations (common with Whisper on silence)
+                const MIN_CONFIDENCE: f64 = 0.50;
+                if let Some(conf) = confidence {
+                    if conf < MIN_CONFIDENCE {
+                        log_debug_details!(
+                            "commands::ipc_events",
+                            "partial_text_filtered",
+                            json!({
+                                "session": session_id,
+                                "request": request_id,
+                                "confidence": conf,
+                                "threshold": MIN_CONFIDENCE
+                            })
+                        );
+                        return; // Skip this low-confidence transcription
+                    }
+                }
+
+                app.state::<AppState>().record_latency(|latency| {
+                    latency.partial_received(request_id, crate::latency::now_ms())
+                });
+
+                let masked = mask_text(text);
+                log_info_details!(
+                    "commands::ipc_events",
+                    "partial_text",
+                    json!({
+                        "session": session_id,
+                        "request": request_id,
+                        "text_masked": masked,
+                        "confidence": confidence
+                    })
+                );
+
+                // Clone for emit (before move into WebSocketMessage)
+                let emit_language = language.clone();
+
+                // Granularity gate: WebSocket clients get word- or sentence-level
+                // partials; the app's own live view gets every partial
+                let state = app.state::<AppState>();
+                let forward = state.get_session(session_id).is_none_or(|session| {
+                    session.admit_partial(text, &state.get_partial_granularity_settings())
+                });
+                if forward {
+                    let ws_message = WebSocketMessage::Transcription {
+                        message_id: crate::message_id::next_id(
+                            crate::message_id::WEBSOCKET,
+                            session_id,
+                        ),
+                        session_id: session_id.to_string(),
+                        text: text.to_string(),
+                        timestamp: std::time::SystemTime::now()
+                            .duration_since(std::time::UNIX_EPOCH)
+                            .unwrap()
+                            .as_millis() as u64,
+                        is_partial: Some(true),
+                        confidence,
+                        language,
+                        processing_time_ms,
+                    };
+
+                    let ws_server = websocket_server.lock().await;
+                    if let Err(e) = ws_server.broadcast(ws_message).await {
+                        log_error_details!(
+                            "commands::ipc_events",
+                            "broadcast_partial_failed",
+                            json!({
+                                "session": session_id,
+                                "request": request_id,
+                                "error": format!("{:?}", e)
+                            })
+                        );
+                    }
+                } else {
+                    log_debug_details!(
                         "commands::ipc_events",
-                        "broadcast_partial_failed",
+                        "partial_text_held",
                         json!({
                             "session": session_id,
-                            "request": request_id,
-                            "error": format!("{:?}", e)
+                            "request": request_id
                         })
                     );
                 }
@@ -394,6 +1124,22 @@ async fn handle_ipc_event(
                     }
                 }
 
+                if is_duplicate_final(data, text, session_id, app) {
+                    log_warn_details!(
+                        "commands::ipc_events",
+                        "duplicate_final_skipped",
+                        json!({
+                            "session": session_id,
+                            "request": request_id
+                        })
+                    );
+                    return;
+                }
+
+                let final_ms = crate::latency::now_ms();
+                let mut persisted_ms = None;
+                let mut broadcast_ms = None;
+
                 let masked = mask_text(text);
                 log_info_details!(
                     "commands::ipc_events",
@@ -406,16 +1152,56 @@ async fn handle_ipc_event(
                     })
                 );
 
+                // Persist final text (STT-REQ-005.3), session-relative timestamp
+                let session = app.state::<AppState>().get_session(session_id);
+                let segment_ms = session.as_ref().map_or(0, |s| s.elapsed_ms());
+                if let Some(session) = session.as_ref() {
+                    session.reset_partials();
+                }
+                {
+                    let event = crate::storage::TranscriptionEvent {
+                        timestamp_ms: segment_ms,
+                        text: text.to_string(),
+                        is_final: true,
+                        speaker: None,
+                    };
+                    let appended = session
+                        .as_ref()
+                        .map_or(Ok(()), |s| s.append_transcript_event(&event));
+                    match appended {
+                        Ok(()) => {
+                            persisted_ms = Some(crate::latency::now_ms());
+                            log_debug_details!(
+                                "commands::storage",
+                                "transcript_appended",
+                                json!({
+                                    "session": session_id,
+                                    "request": request_id,
+                                    "timestamp_ms": segment_ms
+                                })
+                            );
+                        }
+                        Err(e) => {
+                            log_warn_details!(
+                                "commands::ipc_events",
+                                "transcript_append_failed",
+                                json!({
+                                    "session": session_id,
+                                    "request": request_id,
+                                    "error": e.to_string()
+                                })
+                            );
+                        }
+                    }
+                }
+
                 // Clone for emit (before move into WebSocketMessage)
                 let emit_language = language.clone();
 
                 let ws_message = WebSocketMessage::Transcription {
-                    message_id: format!(
-                        "ws-{}",
-                        std::time::SystemTime::now()
-                            .duration_since(std::time::UNIX_EPOCH)
-                            .unwrap()
-                            .as_millis()
+                    message_id: crate::message_id::next_id(
+                        crate::message_id::WEBSOCKET,
+                        session_id,
                     ),
                     session_id: session_id.to_string(),
                     text: text.to_string(),
@@ -430,18 +1216,36 @@ async fn handle_ipc_event(
                 };
 
                 let ws_server = websocket_server.lock().await;
-                if let Err(e) = ws_server.broadcast(ws_message).await {
-                    log_error_details!(
-                        "commands::ipc_events",
-                        "broadcast_final_failed",
-                        json!({
-                            "session": session_id,
-                            "request": request_id,
-                            "error": format!("{:?}", e)
-                        })
-                    );
+                match ws_server.broadcast(ws_message).await {
+                    Ok(()) => {
+                        broadcast_ms = Some(crate::latency::now_ms());
+                        log_debug_details!(
+                            "commands::websocket",
+                            "final_text_broadcast",
+                            json!({
+                                "session": session_id,
+                                "request": request_id
+                            })
+                        );
+                        track_docs_budget(text, session_id, &ws_server, app).await;
+                    }
+                    Err(e) => {
+                        log_error_details!(
+                            "commands::ipc_events",
+                            "broadcast_final_failed",
+                            json!({
+                                "session": session_id,
+                                "request": request_id,
+                                "error": format!("{:?}", e)
+                            })
+                        );
+                    }
                 }
 
+                app.state::<AppState>().record_latency(|latency| {
+                    latency.final_completed(request_id, final_ms, persisted_ms, broadcast_ms)
+                });
+
                 // Debug: Emit to Tauri frontend for real-time transcription display
                 let _ = app.emit(
                     "transcription",
@@ -457,6 +1261,48 @@ async fn handle_ipc_event(
                             .as_millis() as u64
                     }),
                 );
+
+                // Post-broadcast stage: configured pipeline (keyword alerts, routing, ...)
+                let pipeline = app.state::<AppState>().get_pipeline();
+                for action in pipeline.plan(text) {
+                    let text = action.text.as_str();
+                    match action.stage {
+                        StageKind::KeywordAlerts => {
+                            dispatch_keyword_alerts(text, session_id, &ws_server, app).await
+                        }
+                        StageKind::Routing => {
+                            dispatch_routing_rules(text, segment_ms, session_id, app)
+                        }
+                        StageKind::LiveSummary => dispatch_live_summary(
+                            text,
+                            segment_ms,
+                            session_id,
+                            websocket_server,
+                            app,
+                        ),
+                        StageKind::QuestionTracking => {
+                            track_questions(text, segment_ms, session_id, app)
+                        }
+                        StageKind::Mqtt => publish_mqtt(
+                            &app.state::<AppState>(),
+                            crate::mqtt::MqttEvent::FinalSegment {
+                                session_id: session_id.to_string(),
+                                text: text.to_string(),
+                                timestamp_ms: segment_ms,
+                            },
+                        ),
+                        StageKind::Webhooks => send_transcript_webhooks(
+                            &app.state::<AppState>(),
+                            crate::transcript_webhooks::TranscriptWebhookBody::FinalText {
+                                session_id: session_id.to_string(),
+                                text: text.to_string(),
+                                timestamp_ms: segment_ms,
+                            },
+                        ),
+                        // Transform stages are applied inside plan()
+                        StageKind::Redaction | StageKind::Punctuation => {}
+                    }
+                }
             }
         }
         "speech_end" => {
@@ -481,6 +1327,36 @@ async fn handle_ipc_event(
                 })
             );
         }
+        crate::ipc_protocol::AUDIO_ACK_EVENT => {
+            let Some(ack) = crate::ipc_protocol::AudioAck::from_event_data(data) else {
+                log_warn_details!(
+                    "commands::ipc_events",
+                    "audio_ack_invalid_schema",
+                    json!({ "session": session_id, "data": data })
+                );
+                return;
+            };
+            let Some(session) = app.state::<AppState>().get_session(session_id) else {
+                return;
+            };
+            let flow_control = session.ipc_flow();
+            let mut flow = flow_control.lock().unwrap();
+            let interval_before = flow.batch_interval();
+            if flow.on_ack(&ack, std::time::Instant::now())
+                && flow.batch_interval() != interval_before
+            {
+                log_info_details!(
+                    "commands::ipc_events",
+                    "audio_batch_interval_changed",
+                    json!({
+                        "session": session_id,
+                        "request": ack.request_id,
+                        "lag_ms": flow.lag_ms(),
+                        "batch_interval_ms": flow.batch_interval().as_millis() as u64
+                    })
+                );
+            }
+        }
         "model_change" => {
             // Validate required fields
             let old_model = data.get("old_model").and_then(|v| v.as_str());
@@ -505,12 +1381,9 @@ async fn handle_ipc_event(
                 let ws_server = websocket_server.lock().await;
                 let _ = ws_server
                     .broadcast(WebSocketMessage::Error {
-                        message_id: format!(
-                            "ws-{}",
-                            std::time::SystemTime::now()
-                                .duration_since(std::time::UNIX_EPOCH)
-                                .unwrap()
-                                .as_millis()
+                        message_id: crate::message_id::next_id(
+                            crate::message_id::WEBSOCKET,
+                            session_id,
                         ),
                         session_id: session_id.to_string(),
                         message: "モデル変更通知のデータ形式が不正です".to_string(),
@@ -552,12 +1425,9 @@ async fn handle_ipc_event(
                 let ws_server = websocket_server.lock().await;
                 let _ = ws_server
                     .broadcast(WebSocketMessage::Notification {
-                        message_id: format!(
-                            "ws-{}",
-                            std::time::SystemTime::now()
-                                .duration_since(std::time::UNIX_EPOCH)
-                                .unwrap()
-                                .as_millis()
+                        message_id: crate::message_id::next_id(
+                            crate::message_id::WEBSOCKET,
+                            session_id,
                         ),
                         session_id: session_id.to_string(),
                         notification_type: "model_change".to_string(),
@@ -576,14 +1446,20 @@ async fn handle_ipc_event(
             }
         }
         _ => {
-            log_warn_details!(
-                "commands::ipc_events",
-                "unknown_event_type",
-                json!({
-                    "session": session_id,
-                    "event_type": event_type
-                })
-            );
+            // Newer sidecar: count and skip (first occurrence per type is a warning)
+            let count = app
+                .state::<AppState>()
+                .record_unknown_ipc_event_type(event_type);
+            let details = json!({
+                "session": session_id,
+                "event_type": event_type,
+                "count": count
+            });
+            if count == 1 {
+                log_warn_details!("commands::ipc_events", "unknown_event_type", details);
+            } else {
+                log_debug_details!("commands::ipc_events", "unknown_event_type", details);
+            }
         }
     }
 }
@@ -682,28 +1558,44 @@ pub(crate) async fn monitor_audio_events(app: AppHandle) {
                 {
                     let state = app.state::<AppState>();
 
-                    // Step 1: Complete cleanup of existing session
-                    if let Err(e) = stop_recording_internal(&state).await {
-                        log_error_details!(
+                    // Step 1: Complete cleanup of the session recording from the device
+                    // (other sessions keep recording)
+                    let session_id = state
+                        .session_using_device(&device_id)
+                        .map(|session| session.session_id().to_string());
+                    if let Some(session_id) = session_id {
+                        if let Err(e) = stop_recording_internal(&state, &session_id).await {
+                            log_error_details!(
+                                "commands::audio_events",
+                                "cleanup_on_disconnect_failed",
+                                json!({
+                                    "device_id": device_id,
+                                    "session": session_id,
+                                    "error": e
+                                })
+                            );
+                        }
+                    }
+
+                    // Step 2: Start reconnection job unless the policy forbids it
+                    let policy = state.get_reconnect_policy_settings();
+                    if policy.policy == crate::reconnect_policy::ReconnectPolicy::Never {
+                        log_info_details!(
                             "commands::audio_events",
-                            "cleanup_on_disconnect_failed",
-                            json!({
-                                "device_id": device_id,
-                                "error": e
-                            })
+                            "reconnection_disabled",
+                            json!({ "device_id": device_id })
                         );
+                        continue;
                     }
-
-                    // Step 2: Start reconnection job (lock held for microseconds only)
                     {
                         let mut reconnection_mgr = state.reconnection_manager.lock().await;
-                        reconnection_mgr.start_job(device_id.clone(), app.clone());
+                        reconnection_mgr.start_job(device_id.clone(), policy.clone(), app.clone());
                     } // Lock released immediately
 
                     log_info_details!(
                         "commands::audio_events",
                         "reconnection_job_started",
-                        json!({ "device_id": device_id })
+                        json!({ "device_id": device_id, "policy": policy.policy })
                     );
                 }
             }
@@ -714,11 +1606,17 @@ pub(crate) async fn monitor_audio_events(app: AppHandle) {
 /// Internal helper for starting recording
 /// Used by start_recording command and reconnection logic
 /// Task 10.4 Phase 2: Reusable session initialization for device reconnection
+///
+/// Starts a new session next to any already recording (up to
+/// `MAX_CONCURRENT_SESSIONS`, each from its own inputs).
+///
+/// # Returns
+/// ID of the started session
 pub(crate) async fn start_recording_internal(
     _app: &AppHandle,
     state: &AppState,
     device_id: String,
-) -> Result<(), String> {
+) -> Result<String, String> {
     let multi_enabled = state.is_multi_input_enabled();
     let device_ids = if multi_enabled {
         state.get_selected_device_ids()
@@ -815,10 +1713,29 @@ pub(crate) async fn start_recording_internal(
         json!({ "device_ids": device_ids.clone() })
     );
 
-    // Task 9.1: Save selected device to AppState (STT-REQ-001.2)
-    let primary_device_id = valid_ids
-        .get(0)
-        .cloned()
+    // Concurrent sessions record from different inputs
+    if let Some(other) = valid_ids
+        .iter()
+        .find_map(|id| state.session_using_device(id))
+    {
+        log_info_details!(
+            "commands::recording",
+            "device_already_recording",
+            json!({
+                "device_ids": valid_ids,
+                "session": other.session_id()
+            })
+        );
+        return Err(format!(
+            "Device already recording in session {}",
+            other.session_id()
+        ));
+    }
+
+    // Task 9.1: Save selected device to AppState (STT-REQ-001.2)
+    let primary_device_id = valid_ids
+        .get(0)
+        .cloned()
         .unwrap_or_else(|| device_id.clone());
     state.set_selected_device_id(primary_device_id);
     log_info_details!(
@@ -829,29 +1746,10 @@ pub(crate) async fn start_recording_internal(
 
     // Device ID is now validated against real device enumeration
 
-    // Check if already recording (Task 10.4 Phase 2 - Permissive for reconnection)
-    {
-        let is_recording = state.is_recording.lock().unwrap();
-        if *is_recording {
-            log_info_details!(
-                "commands::recording",
-                "already_recording",
-                json!({
-                    "device_id": device_id,
-                    "reason": "reconnection_or_manual_restart"
-                })
-            );
-            return Ok(()); // Treat as success (user protection)
-        }
-    }
-
     // Get references to components
-    let audio_recorder = {
-        let recorder_lock = state.audio_recorder.lock().unwrap();
-        recorder_lock
-            .clone()
-            .ok_or_else(|| "Audio recorder not initialized".to_string())?
-    };
+    let recorder_factory = state
+        .get_audio_recorder_factory()
+        .ok_or_else(|| "Audio recorder not initialized".to_string())?;
 
     // Get or initialize sidecar stdin/stdout handles
     // First recording: extract from sidecar and store in AppState
@@ -861,6 +1759,12 @@ pub(crate) async fn start_recording_internal(
         let existing_stdout = state.get_sidecar_stdout();
 
         if let (Some(stdin), Some(stdout)) = (existing_stdin, existing_stdout) {
+            if stdin.has_failed() {
+                return Err(
+                    "Python sidecar stdin failed; restart the app to restart the sidecar"
+                        .to_string(),
+                );
+            }
             // Reuse existing handles
             (stdin, stdout)
         } else {
@@ -880,7 +1784,8 @@ pub(crate) async fn start_recording_internal(
                 .take_stdout()
                 .ok_or_else(|| "Python sidecar stdout not available".to_string())?;
 
-            let stdin_arc = Arc::new(tokio::sync::Mutex::new(stdin));
+            let audio_channel = sidecar.take_audio_channel();
+            let stdin_arc = Arc::new(StdinWriter::spawn_with_audio_channel(stdin, audio_channel));
             let stdout_arc = Arc::new(tokio::sync::Mutex::new(stdout));
 
             // Store in AppState for reuse
@@ -897,43 +1802,98 @@ pub(crate) async fn start_recording_internal(
             .ok_or_else(|| "WebSocket server not initialized".to_string())?
     };
 
-    let session_id = Uuid::new_v4().to_string();
-    state.set_session_id(session_id.clone());
+    // Fails once MAX_CONCURRENT_SESSIONS are recording
+    let identity = generate_session_identity(state);
+    let session_id = identity.session_id;
+    let session = state
+        .open_session(&session_id, &identity.uuid, now_epoch_ms())
+        .map_err(|e| e.to_string())?;
+    let first_session = state.session_ids().len() == 1;
     log_info_details!(
         "commands::recording",
         "session_initialized",
-        json!({ "session": session_id })
+        json!({
+            "session": session_id,
+            "uuid": identity.uuid,
+            "concurrent_sessions": state.session_ids().len()
+        })
     );
 
-    // Set recording state
-    {
-        let mut is_recording = state.is_recording.lock().unwrap();
-        *is_recording = true;
-    }
+    // Each session captures with its own recorder
+    let audio_recorder = Arc::new(tokio::sync::Mutex::new(
+        crate::audio_device_recorder::AudioDeviceRecorder::new(recorder_factory),
+    ));
+    session.set_recorder(Arc::clone(&audio_recorder));
+
+    // Session persistence (STT-REQ-005): best-effort, recording continues without it
+    begin_session_storage(state, &session);
+
+    state.jobs.set_recording_active(true);
 
-    // Cancel any previous recording tasks before starting new ones
-    state.cancel_recording_tasks();
+    // Cancellation token of this recording session (other sessions keep theirs)
+    let cancel_token = session.cancel_token();
 
-    // Create cancellation token for this recording session
-    let cancel_token = state.create_recording_cancel_token();
+    // Heartbeat for crash detection (stopped via cancel token)
+    let heartbeat_task = start_heartbeat_task(
+        _app.clone(),
+        session_id.clone(),
+        state.device_display_name(&device_id),
+        cancel_token.clone(),
+    );
+    session.register_task(heartbeat_task);
+
+    // A failed stdin write may leave half a line on the stream: the writer
+    // has stopped and closed stdin, so stop the session too
+    let stdin_watch = {
+        let app = _app.clone();
+        let writer = Arc::clone(&sidecar_stdin);
+        let session_id = session_id.clone();
+        let cancel_token = cancel_token.clone();
+        tokio::spawn(async move {
+            tokio::select! {
+                _ = cancel_token.cancelled() => {}
+                _ = writer.failed() => {
+                    report_task_failure(
+                        &app,
+                        "sidecar_stdin",
+                        &session_id,
+                        "stdin write failed",
+                        "stop_session",
+                    );
+                    spawn_recovery_stop(app, session_id);
+                }
+            }
+        })
+    };
+    session.register_task(stdin_watch);
 
     // Start background IPC reader task (ADR-013: Full-Duplex IPC)
     // This task runs independently from audio chunk submission, preventing deadlock
     // Now uses separate stdout handle - no Mutex contention with stdin sender
-    start_ipc_reader_task(
-        Arc::clone(&sidecar_stdout),
+    // One reader serves all sessions (restarted here if it has ended)
+    let reader_started = state.ensure_ipc_reader(|| {
+        start_ipc_reader_task(
+            Arc::clone(&sidecar_stdout),
+            _app.clone(),
+            MAX_IPC_READER_RESTARTS,
+        )
+    });
+    if reader_started {
+        log_info_details!(
+            "commands::recording",
+            "ipc_reader_started",
+            json!({ "session": session_id })
+        );
+    }
+
+    // Sidecar events answering this session's requests
+    let event_task = start_ipc_event_task(
         _app.clone(),
-        session_id.clone(),
+        Arc::clone(&session),
         Arc::clone(&websocket_server),
         cancel_token.clone(),
-    )
-    .await;
-
-    log_info_details!(
-        "commands::recording",
-        "ipc_reader_started",
-        json!({ "session": session_id })
     );
+    session.register_task(event_task);
 
     // Create shared ring buffer to decouple audio callback from IPC sending
     // Ring buffer provides:
@@ -944,23 +1904,49 @@ pub(crate) async fn start_recording_internal(
     let ring_buffer_producer = Arc::clone(&ring_buffer);
     let ring_buffer_consumer = Arc::clone(&ring_buffer);
 
+    // Queue accounting + early-drain signal (see AudioQueueMetrics for overflow behavior)
+    let queue_metrics = session.queue_metrics();
+    // Sidecar-wide accounting starts afresh with the first of concurrent sessions
+    if first_session {
+        state.reset_latency();
+        state.reset_memory_sentinel();
+        state.reset_ipc_quarantine();
+        state.reset_ipc_drift();
+        state.reset_docs_budget();
+        state.reset_sidecar_health();
+    }
+    let queue_metrics_producer = Arc::clone(&queue_metrics);
+    let queue_metrics_consumer = Arc::clone(&queue_metrics);
+    let drain_notify = Arc::new(tokio::sync::Notify::new());
+    let drain_notify_producer = Arc::clone(&drain_notify);
+
     // Spawn dedicated audio sender task
     // This task reads from ring buffer and writes to stdin
     // BATCHING: Read from buffer every 250ms to batch audio chunks
+    // (less often, or not at all, while the sidecar falls behind)
     let stdin_sender = Arc::clone(&sidecar_stdin);
+    let app_sender = _app.clone();
     let session_id_sender = session_id.clone();
     let cancel_token_sender = cancel_token.clone();
-    tokio::spawn(async move {
-        use tokio::io::AsyncWriteExt;
-
+    let latency_sender = Arc::clone(&state.latency);
+    let flow_sender = session.ipc_flow();
+    let sent_audio_dump = open_sent_audio_dump(state, &session_id);
+    let request_ids = session.request_ids();
+    let session_sender = Arc::clone(&session);
+    let sender = async move {
         let mut batch_count = 0u64;
         // Read buffer matches ring buffer capacity to drain quickly after backlog
         let mut batch_buffer = vec![0u8; crate::ring_buffer::BUFFER_CAPACITY];
         const MIN_BATCH_BYTES: usize = 4000; // Minimum 125ms to send
-        let mut batch_interval = tokio::time::interval(std::time::Duration::from_millis(250));
+                                             // Paced by sidecar acks (see ipc_flow_control)
+        let mut batch_period = crate::ipc_flow_control::BASE_BATCH_INTERVAL;
+        let mut batch_interval = tokio::time::interval(batch_period);
+        let mut reported_dropped_bytes = 0u64;
+        let mut last_overflow_report: Option<std::time::Instant> = None;
+        let mut holding = false;
 
         loop {
-            // Wait for timer or cancellation
+            // Wait for timer, early-drain request, or cancellation
             tokio::select! {
                 _ = cancel_token_sender.cancelled() => {
                     log_info!("commands::recording", "audio_sender_cancelled");
@@ -969,12 +1955,64 @@ pub(crate) async fn start_recording_internal(
                 _ = batch_interval.tick() => {
                     // Timer fired - read from ring buffer
                 }
+                _ = drain_notify.notified() => {
+                    // Queue above normal level - drain before it overflows
+                }
+            }
+
+            // Notify about audio dropped on overflow (at most every 5s)
+            let dropped_bytes = queue_metrics_consumer.get_dropped_bytes();
+            if dropped_bytes > reported_dropped_bytes
+                && last_overflow_report
+                    .is_none_or(|at| at.elapsed() >= AUDIO_QUEUE_OVERFLOW_REPORT_INTERVAL)
+            {
+                report_audio_queue_overflow(
+                    &app_sender,
+                    &session_id_sender,
+                    dropped_bytes - reported_dropped_bytes,
+                    queue_metrics_consumer.snapshot(),
+                );
+                reported_dropped_bytes = dropped_bytes;
+                last_overflow_report = Some(std::time::Instant::now());
+            }
+
+            // Backpressure: adapt the pace, or leave audio in the ring buffer
+            // while the sidecar is too far behind
+            let (hold, period, lag_ms) = {
+                let mut flow = flow_sender.lock().unwrap();
+                (
+                    flow.poll_hold(std::time::Instant::now()),
+                    flow.batch_interval(),
+                    flow.lag_ms(),
+                )
+            };
+            if hold != holding {
+                holding = hold;
+                log_info_details!(
+                    "commands::recording",
+                    if hold {
+                        "audio_send_held"
+                    } else {
+                        "audio_send_resumed"
+                    },
+                    json!({ "session": session_id_sender, "lag_ms": lag_ms })
+                );
+            }
+            if period != batch_period {
+                batch_period = period;
+                batch_interval =
+                    tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+            }
+            if hold {
+                continue;
             }
 
             // Read available audio from ring buffer
             let bytes_read = {
                 if let Ok(mut rb) = ring_buffer_consumer.lock() {
-                    pop_audio(&mut rb, &mut batch_buffer)
+                    let popped = pop_audio(&mut rb, &mut batch_buffer);
+                    queue_metrics_consumer.record_pop(popped, rb.occupied_len());
+                    popped
                 } else {
                     0 // Lock poisoned, skip this cycle
                 }
@@ -986,7 +2024,25 @@ pub(crate) async fn start_recording_internal(
             }
 
             batch_count += 1;
-            let batch_data = batch_buffer[..bytes_read].to_vec();
+            // Borrowed from the reusable batch buffer - no per-batch copy
+            let batch_data = &batch_buffer[..bytes_read];
+
+            // Persist the exact audio sent to STT (STT-REQ-005.2)
+            match session_sender.append_audio(batch_data) {
+                Ok(true) => {}
+                // Paused: the batch is neither saved nor transcribed
+                Ok(false) => continue,
+                Err(e) => {
+                    log_warn_details!(
+                        "commands::recording",
+                        "audio_persist_failed",
+                        json!({
+                            "session": session_id_sender,
+                            "error": e.to_string()
+                        })
+                    );
+                }
+            }
 
             log_debug_details!(
                 "commands::recording",
@@ -999,20 +2055,20 @@ pub(crate) async fn start_recording_internal(
             );
 
             // Task 7.1.6: Use event stream protocol (STT-REQ-007.3)
-            let message = ProtocolMessage::Request {
-                id: format!(
-                    "audio-{}",
-                    std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH)
-                        .unwrap()
-                        .as_millis()
-                ),
-                version: PROTOCOL_VERSION.to_string(),
-                method: "process_audio_stream".to_string(),
-                params: serde_json::json!({ "audio_data": batch_data }),
+            // Serialized straight from the batch slice (no intermediate Value)
+            let batched_ms = crate::latency::now_ms();
+            let request_id = request_ids
+                .next_id(crate::request_id::AUDIO_STREAM)
+                .to_string();
+
+            // Raw PCM over the binary audio channel when open, else JSON on stdin
+            let over_channel = stdin_sender.has_audio_channel();
+            let encoded = if over_channel {
+                encode_audio_channel_request(&request_id, batch_data.len())
+            } else {
+                encode_audio_stream_request(&request_id, batch_data)
             };
-
-            let json_str = match serde_json::to_string(&message) {
+            let json_str = match encoded {
                 Ok(s) => s,
                 Err(e) => {
                     log_error_details!(
@@ -1027,58 +2083,112 @@ pub(crate) async fn start_recording_internal(
                 }
             };
 
-            // Write directly to stdin - no Mutex contention with stdout reader
-            // Add timeout to prevent blocking forever (increased for larger batches)
-            let write_future = async {
-                let mut stdin = stdin_sender.lock().await;
-                stdin.write_all(json_str.as_bytes()).await?;
-                stdin.write_all(b"\n").await?;
-                stdin.flush().await
+            // Queue for the stdin writer task - never waits on a slow write
+            // (write timeouts/failures are logged by the writer task)
+            let batch_size = json_str.len();
+            // Oldest sample of the batch was captured (at least) one batch duration ago
+            let captured_ms =
+                batched_ms.saturating_sub(crate::ring_buffer::ms_for_bytes(bytes_read));
+            latency_sender
+                .lock()
+                .unwrap()
+                .batch_queued(&request_id, captured_ms, batched_ms);
+            let on_written = {
+                let latency = Arc::clone(&latency_sender);
+                let request_id = request_id.clone();
+                // Dumped only once written, so the file holds exactly what the sidecar got
+                let dump = sent_audio_dump
+                    .as_ref()
+                    .map(|dump| (Arc::clone(dump), batch_data.to_vec()));
+                let session_id = session_id_sender.clone();
+                move || {
+                    latency
+                        .lock()
+                        .unwrap()
+                        .batch_written(&request_id, crate::latency::now_ms());
+                    if let Some((dump, pcm_bytes)) = dump {
+                        write_sent_audio_dump(&dump, &pcm_bytes, &session_id);
+                    }
+                }
             };
-
-            let write_result =
-                tokio::time::timeout(std::time::Duration::from_secs(10), write_future).await;
-
-            match write_result {
-                Ok(Ok(_)) => {
+            let sent = if over_channel {
+                stdin_sender.try_send_audio_frame(json_str, batch_data.to_vec(), on_written)
+            } else {
+                stdin_sender.try_send_audio_with_hook(json_str, on_written)
+            };
+            match sent {
+                Ok(()) => {
+                    flow_sender
+                        .lock()
+                        .unwrap()
+                        .on_sent(&request_id, crate::ring_buffer::ms_for_bytes(bytes_read));
                     log_debug_details!(
                         "commands::recording",
                         "batch_sent_to_python",
                         json!({
                             "session": session_id_sender,
+                            "request": request_id,
                             "batch_count": batch_count,
-                            "batch_size": json_str.len()
+                            "batch_size": batch_size
                         })
                     );
                 }
-                Ok(Err(e)) => {
+                Err(StdinWriterError::AudioQueueFull) => {
                     log_error_details!(
                         "commands::recording",
-                        "send_to_sidecar_failed",
+                        "send_to_sidecar_queue_full",
                         json!({
                             "session": session_id_sender,
-                            "error": format!("{:?}", e)
+                            "request": request_id,
+                            "batch_count": batch_count,
+                            "rejected_total": stdin_sender.audio_rejected()
                         })
                     );
-                    // Continue processing other audio chunks
+                    // Continue processing - don't block on slow writes
                 }
-                Err(_timeout) => {
+                Err(e) => {
                     log_error_details!(
                         "commands::recording",
-                        "send_to_sidecar_timeout",
+                        "send_to_sidecar_failed",
                         json!({
                             "session": session_id_sender,
-                            "batch_count": batch_count,
-                            "timeout_secs": 10
+                            "request": request_id,
+                            "error": e.to_string()
                         })
                     );
-                    // Continue processing - don't block on slow writes
+                    // Continue processing other audio chunks
                 }
             }
-            // Mutex dropped here
+        }
+        if let Some(dump) = &sent_audio_dump {
+            let mut dump = dump.lock().unwrap();
+            let _ = dump.finish();
+            log_info_details!(
+                "commands::recording",
+                "sent_audio_dump_closed",
+                json!({ "session": session_id_sender, "bytes": dump.written_bytes() })
+            );
         }
         log_info!("commands::recording", "audio_sender_task_ended");
-    });
+    };
+    let app_recovery = _app.clone();
+    let session_id_recovery = session_id.clone();
+    let cancel_token_recovery = cancel_token.clone();
+    let sender_task =
+        crate::task_supervisor::spawn_supervised("audio_sender", sender, move |panic| async move {
+            // Audio can no longer reach STT: stop the session cleanly
+            if !cancel_token_recovery.is_cancelled() {
+                report_task_failure(
+                    &app_recovery,
+                    "audio_sender",
+                    &session_id_recovery,
+                    &panic,
+                    "stop_session",
+                );
+                spawn_recovery_stop(app_recovery, session_id_recovery);
+            }
+        });
+    session.register_task(sender_task);
 
     log_info_details!(
         "commands::recording",
@@ -1086,33 +2196,74 @@ pub(crate) async fn start_recording_internal(
         json!({ "session": session_id })
     );
 
+    // Periodic byte accounting of long-lived buffers (leak sentinel)
+    if state.get_memory_sentinel_settings().enabled {
+        let sentinel_task = start_memory_sentinel_task(
+            _app.clone(),
+            Arc::clone(&session),
+            Arc::clone(&websocket_server),
+            cancel_token.clone(),
+        );
+        session.register_task(sentinel_task);
+    }
+
+    // Periodic sidecar health checks (answered on the IPC reader)
+    let health_task =
+        start_sidecar_health_task(_app.clone(), session_id.clone(), cancel_token.clone());
+    session.register_task(health_task);
+
     // Start audio device with callback
     // MVP1: Use AudioDeviceAdapter trait with device_id
     // Callback writes to ring buffer with drop-oldest strategy
     let mut recorder = audio_recorder.lock().await;
+    // PCM monitoring stream (no-op unless enabled and subscribed)
+    let audio_tap = websocket_server.lock().await.audio_tap();
     let callback: crate::audio_device_adapter::AudioChunkCallback =
-        Box::new(move |audio_data: Vec<u8>| {
+        Box::new(move |audio_data: bytes::Bytes| {
+            if let Some(tap) = &audio_tap {
+                tap.send(&audio_data);
+            }
             // Non-blocking write to ring buffer
             // Use try_lock to avoid blocking in real-time audio callback
             if let Ok(mut rb) = ring_buffer_producer.try_lock() {
-                let (_pushed, dropped, level) = push_audio_drop_oldest(&mut rb, &audio_data);
-                if dropped > 0 {
-                    // Old data was dropped to make room for new data
-                    // This is expected when Python is slow - we prioritize latest audio
-                }
-                if level == BufferLevel::Critical {
-                    // Buffer is getting full, Python may be falling behind
+                let (pushed, dropped, level) = push_audio_drop_oldest(&mut rb, &audio_data);
+                // Dropped bytes are reported by the sender task (no I/O in callback)
+                queue_metrics_producer.record_push(pushed, dropped, rb.occupied_len());
+                if level != BufferLevel::Normal {
+                    // Python may be falling behind - wake sender to drain early
+                    drain_notify_producer.notify_one();
                 }
+            } else {
+                // Sender task holds lock briefly - frame is skipped, but counted
+                queue_metrics_producer.record_skipped_frame();
             }
-            // If try_lock fails, skip this frame (sender task holds lock briefly)
         });
 
     let recording_mode = if multi_enabled {
+        let mixer_config = MixerConfig {
+            input_tracks_dir: input_tracks_dir(state, &session_id),
+            ..MixerConfig::default()
+        };
+        session.set_applied_gains(
+            device_ids
+                .iter()
+                .enumerate()
+                .map(|(i, id)| crate::loudness::AppliedGain {
+                    device_id: id.clone(),
+                    gain_db: mixer_config.input_gain_db(i),
+                })
+                .collect(),
+        );
         RecordingMode::Multi {
             device_ids: device_ids.clone(),
-            mixer_config: MixerConfig::default(),
+            mixer_config,
         }
     } else {
+        // Single input is captured unscaled
+        session.set_applied_gains(vec![crate::loudness::AppliedGain {
+            device_id: device_id.clone(),
+            gain_db: 0.0,
+        }]);
         RecordingMode::Single {
             device_id: device_id.clone(),
         }
@@ -1120,11 +2271,14 @@ pub(crate) async fn start_recording_internal(
 
     if let Err(err) = recorder.start(recording_mode, callback) {
         let error_msg = err.to_string();
-        {
-            let mut is_recording = state.is_recording.lock().unwrap();
-            *is_recording = false;
-        }
-        state.clear_session_id();
+        finish_session_storage(
+            state,
+            &session,
+            &state.device_display_name(&device_id),
+            vec![format!("Recording failed to start: {}", error_msg)],
+        );
+        state.close_session(&session_id);
+        state.jobs.set_recording_active(state.is_recording());
         log_error_details!(
             "commands::recording",
             "start_failed",
@@ -1145,447 +2299,6228 @@ pub(crate) async fn start_recording_internal(
             "device_id": device_id
         })
     );
-    Ok(())
-}
-
-/// Start recording command (single device - backward compatible)
-/// Starts audio device and processes audio data through Python sidecar
-/// Task 9.1: Accept device_id to honor user's device selection (STT-REQ-001.2)
-#[tauri::command]
-pub async fn start_recording(
-    app: AppHandle,
-    state: State<'_, AppState>,
-    device_id: String,
-) -> Result<String, String> {
-    // Disable multi-input mode for single device recording
-    state.set_multi_input_enabled(false);
-    start_recording_internal(&app, &state, device_id).await?;
-    Ok("Recording started".to_string())
+    announce_recording(state, &session_id);
+    publish_mqtt(
+        state,
+        crate::mqtt::MqttEvent::SessionStarted {
+            session_id: session_id.clone(),
+        },
+    );
+    Ok(session_id)
 }
 
-/// Start multi-input recording command
-/// Starts recording from multiple audio devices simultaneously
-/// STTMIX Task 1.3: Accept multiple device_ids for multi-input mode (STTMIX-REQ-002.1)
-///
-/// # Arguments
-/// * `device_ids` - Vector of device IDs (max 2 per STTMIX-CON-005)
+/// Tell participants the meeting is being recorded, if enabled
 ///
-/// # Returns
-/// * `Ok(String)` with success message
-/// * `Err(String)` if validation fails or recording cannot start
-#[tauri::command]
-pub async fn start_recording_multi(
-    app: AppHandle,
-    state: State<'_, AppState>,
-    device_ids: Vec<String>,
-) -> Result<String, String> {
-    // STTMIX-CON-004: Multi-input only supported on macOS
-    #[cfg(not(target_os = "macos"))]
-    {
-        return Err("Multi-input recording is only supported on macOS (STTMIX-CON-004)".to_string());
+/// The audio announcement plays in the background; failures are logged and
+/// never stop the recording.
+fn announce_recording(state: &AppState, session_id: &str) {
+    let settings = state.get_consent_announcement_settings();
+    if !settings.enabled {
+        return;
     }
 
-    // Validate input count (STTMIX-CON-005: max 2 inputs)
-    if device_ids.is_empty() {
-        return Err("At least one device ID must be provided".to_string());
+    if settings.play_audio {
+        let previous = state.consent_announcement.lock().unwrap().take();
+        if let Some(player) = previous {
+            player.stop();
+        }
+        let played = crate::consent::announcement_audio(&settings).and_then(|(samples, rate)| {
+            crate::playback::AudioPlayer::play(
+                session_id,
+                samples,
+                rate,
+                0,
+                settings.output_device.as_deref(),
+                || {},
+            )
+        });
+        match played {
+            Ok(player) => *state.consent_announcement.lock().unwrap() = Some(player),
+            Err(e) => {
+                log_warn_details!(
+                    "commands::consent",
+                    "announcement_playback_failed",
+                    json!({
+                        "session": session_id,
+                        "output_device": settings.output_device,
+                        "error": format!("{:#}", e)
+                    })
+                );
+            }
+        }
     }
-    if device_ids.len() > 2 {
-        return Err(format!(
-            "Maximum 2 inputs supported, got {}. (STTMIX-CON-005)",
-            device_ids.len()
-        ));
+
+    let websocket_server = state.websocket_server.lock().unwrap().clone();
+    if let Some(websocket_server) = websocket_server.filter(|_| settings.notify_clients) {
+        let ws_message = WebSocketMessage::Notification {
+            message_id: crate::message_id::next_id(crate::message_id::WEBSOCKET, session_id),
+            session_id: session_id.to_string(),
+            notification_type: crate::consent::NOTIFICATION_TYPE.to_string(),
+            message: settings.message,
+            timestamp: now_epoch_ms(),
+            data: None,
+        };
+        let session_id = session_id.to_string();
+        tokio::spawn(async move {
+            if let Err(e) = websocket_server.lock().await.broadcast(ws_message).await {
+                log_error_details!(
+                    "commands::consent",
+                    "broadcast_consent_notification_failed",
+                    json!({
+                        "session": session_id,
+                        "error": format!("{:?}", e)
+                    })
+                );
+            }
+        });
     }
 
     log_info_details!(
-        "commands::recording",
-        "multi_input_start_requested",
+        "commands::consent",
+        "recording_announced",
         json!({
-            "device_ids": device_ids.clone(),
-            "count": device_ids.len()
+            "session": session_id,
+            "audio": settings.play_audio,
+            "notify_clients": settings.notify_clients
         })
     );
+}
 
-    // Enable multi-input mode and save device IDs
-    state.set_multi_input_enabled(true);
-    state.set_selected_device_ids(device_ids.clone());
-
-    let primary_device = device_ids[0].clone();
-    start_recording_internal(&app, &state, primary_device).await?;
-
-    Ok(format!(
-        "Multi-input recording started with {} device(s)",
-        device_ids.len()
-    ))
+fn now_epoch_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
 }
 
-/// Internal helper for stopping recording
-/// Used by stop_recording command and reconnection logic
-/// Task 10.4 Phase 2: Reusable cleanup for device reconnection
-pub(crate) async fn stop_recording_internal(state: &AppState) -> Result<(), String> {
-    // Check if recording (silent return if already stopped)
-    {
-        let is_recording = state.is_recording.lock().unwrap();
-        if !*is_recording {
-            return Ok(()); // Already stopped
+/// Session directory for per-input tracks, if enabled in the multi-input settings
+fn input_tracks_dir(state: &AppState, session_id: &str) -> Option<std::path::PathBuf> {
+    let storage = state.get_storage_service()?;
+    match settings_file::load::<crate::multi_input_settings::MultiInputSettings>(
+        storage.app_data_dir(),
+        crate::multi_input_settings::SETTINGS_STEM,
+    ) {
+        Ok(settings) if settings.save_input_tracks => Some(storage.get_session_dir(session_id)),
+        Ok(_) => None,
+        Err(e) => {
+            log_warn_details!(
+                "commands::multi_input",
+                "settings_load_failed",
+                json!({ "session": session_id, "error": e.to_string() })
+            );
+            None
         }
     }
+}
 
-    let current_session = state.get_session_id();
-    let selected_device = state.get_selected_device_id();
-
-    // Get audio recorder reference
-    let audio_recorder = {
-        let recorder_lock = state.audio_recorder.lock().unwrap();
-        recorder_lock
-            .clone()
-            .ok_or_else(|| "Audio recorder not initialized".to_string())?
-    };
-
-    // Cancel IPC reader and audio sender tasks
-    state.cancel_recording_tasks();
-    log_info!("commands::recording", "tasks_cancelled");
-
-    // Stop audio recorder (cleanup resources, including mixer thread)
-    let mut recorder = audio_recorder.lock().await;
-    recorder.stop().map_err(|e| e.to_string())?;
-
-    // Clear recording state
-    {
-        let mut is_recording = state.is_recording.lock().unwrap();
-        *is_recording = false;
+/// Start the session, opening its directory and writers
+/// Storage failures are logged and recording continues without persistence
+fn begin_session_storage(state: &AppState, session: &RecordingSession) {
+    let session_id = session.session_id();
+    crate::logger::set_session_context(Some(session_id));
+    state.agenda.lock().unwrap().reset_progress();
+    *state.question_tracker.lock().unwrap() = crate::questions::QuestionTracker::default();
+
+    *state.live_summary.lock().unwrap() = None;
+
+    let storage = state.get_storage_service();
+    match storage.as_ref().map(|s| {
+        settings_file::load::<crate::summary::SummarySettings>(
+            s.app_data_dir(),
+            crate::summary::SETTINGS_STEM,
+        )
+    }) {
+        Some(Ok(settings)) if settings.live_enabled => {
+            *state.live_summary.lock().unwrap() = Some(crate::summary::RollingSummary::new(
+                settings.live_interval_minutes,
+            ));
+        }
+        Some(Ok(_)) => {}
+        Some(Err(e)) => {
+            log_warn_details!(
+                "commands::summary",
+                "summary_settings_load_failed",
+                json!({ "session": session_id, "error": e.to_string() })
+            );
+        }
+        None => {
+            log_warn!(
+                "commands::storage",
+                "storage_not_initialized",
+                format!("session={}", session_id)
+            );
+        }
     }
 
-    state.clear_session_id();
+    let mut audio_format = state.session_audio_format();
+    if !audio_format.is_supported() {
+        log_warn_details!(
+            "commands::storage",
+            "audio_format_unsupported",
+            json!({ "session": session_id, "format": audio_format })
+        );
+        audio_format = crate::storage::AudioFormat::Wav;
+    }
+    match session.start(storage.as_ref(), audio_format) {
+        Ok(()) if storage.is_some() => {
+            log_info_details!(
+                "commands::storage",
+                "session_audio_opened",
+                json!({ "session": session_id, "format": audio_format })
+            );
+        }
+        Ok(()) => {}
+        Err(e) => {
+            log_warn_details!(
+                "commands::storage",
+                "session_storage_unavailable",
+                json!({
+                    "session": session_id,
+                    "error": e.to_string()
+                })
+            );
+        }
+    }
+}
 
-    log_info_details!(
-        "commands::recording",
-        "stopped",
-        json!({
-            "session": current_session,
-            "device_id": selected_device
+/// Generate the session ID from the configured format
+///
+/// Falls back to a bare UUID when the settings cannot be loaded or the
+/// template no longer renders to a valid ID.
+fn generate_session_identity(state: &AppState) -> crate::session_id::SessionIdentity {
+    let settings = state
+        .get_storage_service()
+        .map(|storage| {
+            settings_file::load(storage.app_data_dir(), crate::session_id::SETTINGS_STEM)
         })
-    );
-    Ok(())
-}
+        .unwrap_or_else(|| Ok(Default::default()));
 
-/// Stop recording command
-/// Stops audio device recording
-#[tauri::command]
-pub async fn stop_recording(state: State<'_, AppState>) -> Result<String, String> {
-    // Check if recording (return error if not recording)
+    match settings.and_then(|settings| crate::session_id::generate(&settings)) {
+        Ok(identity) => identity,
+        Err(e) => {
+            log_warn_details!(
+                "commands::recording",
+                "session_id_format_invalid",
+                json!({ "error": format!("{:#}", e) })
+            );
+            let uuid = Uuid::new_v4().to_string();
+            crate::session_id::SessionIdentity {
+                session_id: uuid.clone(),
+                uuid,
+            }
+        }
+    }
+}
+
+/// Stop the session (writers closed, session.json written), then write
+/// agenda.json and clear the heartbeat
+fn finish_session_storage(
+    state: &AppState,
+    session: &RecordingSession,
+    audio_device: &str,
+    warnings: Vec<String>,
+) {
+    let session_id = session.session_id();
+    let elapsed_ms = session.elapsed_ms();
+    // Finalize logs carry the session in their details
+    crate::logger::set_session_context(None);
+
+    let storage = state.get_storage_service();
+    let metadata = match session.stop(storage.as_ref(), audio_device, warnings) {
+        Ok(Some(metadata)) => metadata,
+        Ok(None) => return,
+        Err(e) => {
+            // Heartbeat is kept: crash recovery rebuilds session.json on next start
+            log_warn_details!(
+                "commands::storage",
+                "session_metadata_save_failed",
+                json!({ "session": session_id, "error": e.to_string() })
+            );
+            return;
+        }
+    };
+    let Some(storage) = storage else {
+        return;
+    };
+
+    // Clean stop: heartbeat no longer needed for crash detection
+    if let Err(e) = crate::heartbeat::clear_heartbeat(storage.app_data_dir()) {
+        log_warn_details!(
+            "commands::storage",
+            "heartbeat_clear_failed",
+            json!({ "session": session_id, "error": e.to_string() })
+        );
+    }
+
+    // The partial -> final latency is only known now (partials are not persisted)
+    let partial_to_final = state
+        .latency_snapshot()
+        .stages
+        .remove(&crate::latency::LatencyStage::PartialToFinal);
+    if let Err(e) = crate::session_stats::update_session(&storage, session_id, partial_to_final) {
+        log_warn_details!(
+            "commands::storage",
+            "session_stats_failed",
+            json!({ "session": session_id, "error": e.to_string() })
+        );
+    }
+
+    // Peaks for instant scrubbing; generated as a background job (reads the whole audio)
+    let session_dir = storage.get_session_dir(session_id);
+    state.jobs.spawn(
+        crate::jobs::JobCategory::Maintenance,
+        &format!("Waveform for {}", session_id),
+        move |_| crate::waveform::generate(&session_dir).map(|_| ()),
+    );
+
+    let agenda = {
+        let mut agenda = state.agenda.lock().unwrap();
+        agenda.finish_active(elapsed_ms);
+        agenda.clone()
+    };
+    if !agenda.is_empty() {
+        if let Err(e) = crate::agenda::save_agenda(&storage.get_session_dir(session_id), &agenda) {
+            log_warn_details!(
+                "commands::storage",
+                "agenda_save_failed",
+                json!({ "session": session_id, "error": e.to_string() })
+            );
+        }
+    }
+
+    log_info_details!(
+        "commands::storage",
+        "session_saved",
+        json!({
+            "session": session_id,
+            "duration_seconds": metadata.duration_seconds,
+            "total_segments": metadata.total_segments,
+            "stats": session.stats()
+        })
+    );
+}
+
+/// Periodically write the recording heartbeat until the session is cancelled
+fn start_heartbeat_task(
+    app: AppHandle,
+    session_id: String,
+    audio_device: String,
+    cancel_token: tokio_util::sync::CancellationToken,
+) -> tokio::task::JoinHandle<()> {
+    use crate::heartbeat::{write_heartbeat, Heartbeat, HEARTBEAT_INTERVAL_SECS};
+
+    let app_recovery = app.clone();
+    let session_id_recovery = session_id.clone();
+    let heartbeat_loop = async move {
+        let mut interval =
+            tokio::time::interval(std::time::Duration::from_secs(HEARTBEAT_INTERVAL_SECS));
+
+        loop {
+            tokio::select! {
+                _ = cancel_token.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+
+            let state = app.state::<AppState>();
+            let (Some(storage), Some(session)) =
+                (state.get_storage_service(), state.get_session(&session_id))
+            else {
+                break;
+            };
+
+            // Sync the audio so the offset below is on disk
+            if let Err(e) = session.flush() {
+                log_warn_details!(
+                    "commands::heartbeat",
+                    "audio_flush_failed",
+                    json!({ "session": session_id, "error": e.to_string() })
+                );
+            }
+            // One heartbeat file: concurrent sessions leave it to the primary one
+            if state.get_session_id().as_deref() != Some(session_id.as_str()) {
+                continue;
+            }
+            let heartbeat = Heartbeat {
+                session_id: session_id.clone(),
+                started_at_ms: session.started_at_ms(),
+                updated_at_ms: now_epoch_ms(),
+                last_sample_offset: session.audio_samples(),
+                last_event_seq: session.last_ipc_event_seq(),
+                audio_device: audio_device.clone(),
+                session_uuid: Some(session.session_uuid().to_string()),
+            };
+            if let Err(e) = write_heartbeat(storage.app_data_dir(), &heartbeat) {
+                log_warn_details!(
+                    "commands::heartbeat",
+                    "write_failed",
+                    json!({ "session": session_id, "error": e.to_string() })
+                );
+            }
+        }
+        log_info_details!(
+            "commands::heartbeat",
+            "task_ended",
+            json!({ "session": session_id })
+        );
+    };
+    crate::task_supervisor::spawn_supervised("heartbeat", heartbeat_loop, move |panic| async move {
+        // Crash detection is degraded but recording can continue
+        report_task_failure(
+            &app_recovery,
+            "heartbeat",
+            &session_id_recovery,
+            &panic,
+            "none",
+        );
+    })
+}
+
+/// Open the session's sent audio dump if enabled in the debug settings
+///
+/// Best-effort: recording continues without the dump.
+fn open_sent_audio_dump(
+    state: &AppState,
+    session_id: &str,
+) -> Option<Arc<std::sync::Mutex<crate::audio_dump::SentAudioDump>>> {
+    let settings = state.get_debug_settings();
+    if !settings.dump_sent_audio {
+        return None;
+    }
+    let storage = state.get_storage_service()?;
+    match storage.create_sent_audio_dump(session_id) {
+        Ok(writer) => {
+            log_info_details!(
+                "commands::recording",
+                "sent_audio_dump_opened",
+                json!({ "session": session_id, "max_dump_mb": settings.max_dump_mb })
+            );
+            Some(Arc::new(std::sync::Mutex::new(
+                crate::audio_dump::SentAudioDump::new(writer, settings.max_dump_bytes()),
+            )))
+        }
+        Err(e) => {
+            log_warn_details!(
+                "commands::recording",
+                "sent_audio_dump_open_failed",
+                json!({ "session": session_id, "error": e.to_string() })
+            );
+            None
+        }
+    }
+}
+
+/// Append a written batch to the sent audio dump (runs on the stdin writer task)
+fn write_sent_audio_dump(
+    dump: &std::sync::Mutex<crate::audio_dump::SentAudioDump>,
+    pcm_bytes: &[u8],
+    session_id: &str,
+) {
+    let mut dump = dump.lock().unwrap();
+    match dump.write(pcm_bytes) {
+        Ok(true) => {
+            log_warn_details!(
+                "commands::recording",
+                "sent_audio_dump_capped",
+                json!({ "session": session_id, "bytes": dump.written_bytes() })
+            );
+        }
+        Ok(false) => {}
+        Err(e) => {
+            log_warn_details!(
+                "commands::recording",
+                "sent_audio_dump_write_failed",
+                json!({ "session": session_id, "error": e.to_string() })
+            );
+            // Stop dumping rather than logging every batch
+            let _ = dump.finish();
+        }
+    }
+}
+
+/// Sample buffer sizes until the session is cancelled, warning on leaks
+///
+/// The accounting is shared by concurrent sessions: only the primary
+/// session's sentinel samples.
+fn start_memory_sentinel_task(
+    app: AppHandle,
+    session: Arc<RecordingSession>,
+    websocket_server: Arc<tokio::sync::Mutex<crate::websocket::WebSocketServer>>,
+    cancel_token: tokio_util::sync::CancellationToken,
+) -> tokio::task::JoinHandle<()> {
+    let session_id = session.session_id().to_string();
+    let interval_secs = app
+        .state::<AppState>()
+        .get_memory_sentinel_settings()
+        .sample_interval_secs
+        .max(1);
+
+    let app_recovery = app.clone();
+    let session_id_recovery = session_id.clone();
+    let sentinel_loop = async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(interval_secs));
+
+        loop {
+            tokio::select! {
+                _ = cancel_token.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+
+            if app.state::<AppState>().get_session_id().as_deref() != Some(session_id.as_str()) {
+                continue;
+            }
+            let websocket_bytes = websocket_server.lock().await.queued_bytes().await;
+            let mixer_bytes: u64 = match session.recorder() {
+                Some(recorder) => recorder
+                    .lock()
+                    .await
+                    .get_input_status()
+                    .iter()
+                    .map(|input| input.buffer_level_bytes as u64)
+                    .sum(),
+                None => 0,
+            };
+
+            let state = app.state::<AppState>();
+            let mut sample = state.owned_buffer_sizes();
+            sample.push((crate::memory_sentinel::WEBSOCKET_QUEUES, websocket_bytes));
+            sample.push((crate::memory_sentinel::MIXER_BUFFERS, mixer_bytes));
+
+            for warning in state.observe_memory(&sample, now_epoch_ms()) {
+                log_warn_details!(
+                    "commands::memory_sentinel",
+                    "buffer_growth",
+                    json!({
+                        "session": session_id,
+                        "structure": warning.structure,
+                        "bytes": warning.bytes,
+                        "bound_bytes": warning.bound_bytes,
+                        "growth_bytes": warning.growth_bytes,
+                        "samples": warning.samples
+                    })
+                );
+                let _ = app.emit(
+                    "memory_sentinel_warning",
+                    json!({ "session_id": session_id, "warning": warning }),
+                );
+            }
+        }
+    };
+    crate::task_supervisor::spawn_supervised(
+        "memory_sentinel",
+        sentinel_loop,
+        move |panic| async move {
+            // Accounting only: recording is unaffected
+            report_task_failure(
+                &app_recovery,
+                "memory_sentinel",
+                &session_id_recovery,
+                &panic,
+                "none",
+            );
+        },
+    )
+}
+
+/// Ping the sidecar for its health until the session is cancelled
+///
+/// The sidecar is shared by concurrent sessions: only the primary session's
+/// task pings.
+fn start_sidecar_health_task(
+    app: AppHandle,
+    session_id: String,
+    cancel_token: tokio_util::sync::CancellationToken,
+) -> tokio::task::JoinHandle<()> {
+    let app_recovery = app.clone();
+    let session_id_recovery = session_id.clone();
+    let health_loop = async move {
+        let period = crate::sidecar_health::HEALTH_CHECK_INTERVAL;
+        let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+
+        loop {
+            tokio::select! {
+                _ = cancel_token.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+
+            let state = app.state::<AppState>();
+            if state.get_session_id().as_deref() != Some(session_id.as_str()) {
+                continue;
+            }
+            let Some(writer) = state.get_sidecar_stdin() else {
+                continue;
+            };
+            let id = crate::message_id::next_id(crate::message_id::CONTROL, "sidecar");
+            let Ok(line) = ControlMessage::Ping.encode(&id) else {
+                continue;
+            };
+            // Recorded first: the pong may arrive before the send returns
+            state.record_sidecar_ping(id);
+            if let Err(e) = writer.send_control(line).await {
+                log_warn_details!(
+                    "commands::sidecar_health",
+                    "health_check_send_failed",
+                    json!({
+                        "session": session_id,
+                        "error": e.to_string()
+                    })
+                );
+            }
+        }
+    };
+    crate::task_supervisor::spawn_supervised(
+        "sidecar_health",
+        health_loop,
+        move |panic| async move {
+            // Diagnostics only: recording is unaffected
+            report_task_failure(
+                &app_recovery,
+                "sidecar_health",
+                &session_id_recovery,
+                &panic,
+                "none",
+            );
+        },
+    )
+}
+
+/// Choose the audio file format of the session about to start
+/// None uses the audio format settings
+fn set_session_audio_format(
+    state: &AppState,
+    audio_format: Option<crate::storage::AudioFormat>,
+) -> Result<(), String> {
+    if let Some(format) = audio_format {
+        crate::audio_format::validate_format(format).map_err(|e| e.to_string())?;
+    }
+    state.set_audio_format_override(audio_format);
+    Ok(())
+}
+
+/// Start recording command (single device - backward compatible)
+/// Starts audio device and processes audio data through Python sidecar
+/// Task 9.1: Accept device_id to honor user's device selection (STT-REQ-001.2)
+/// `audio_format` selects the saved audio format for this session only
+#[tauri::command]
+pub async fn start_recording(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    device_id: String,
+    audio_format: Option<crate::storage::AudioFormat>,
+) -> Result<String, String> {
+    set_session_audio_format(&state, audio_format)?;
+    // Disable multi-input mode for single device recording
+    state.set_multi_input_enabled(false);
+    let session_id = start_recording_internal(&app, &state, device_id).await?;
+    Ok(format!("Recording started (session {})", session_id))
+}
+
+/// Start multi-input recording command
+/// Starts recording from multiple audio devices simultaneously
+/// STTMIX Task 1.3: Accept multiple device_ids for multi-input mode (STTMIX-REQ-002.1)
+///
+/// # Arguments
+/// * `device_ids` - Vector of device IDs (max 2 per STTMIX-CON-005)
+/// * `audio_format` - Saved audio format for this session (None = settings)
+///
+/// # Returns
+/// * `Ok(String)` with success message
+/// * `Err(String)` if validation fails or recording cannot start
+#[tauri::command]
+pub async fn start_recording_multi(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    device_ids: Vec<String>,
+    audio_format: Option<crate::storage::AudioFormat>,
+) -> Result<String, String> {
+    // STTMIX-CON-004: Multi-input only supported on macOS
+    #[cfg(not(target_os = "macos"))]
+    {
+        return Err("Multi-input recording is only supported on macOS (STTMIX-CON-004)".to_string());
+    }
+
+    // Validate input count (STTMIX-CON-005: max 2 inputs)
+    if device_ids.is_empty() {
+        return Err("At least one device ID must be provided".to_string());
+    }
+    if device_ids.len() > 2 {
+        return Err(format!(
+            "Maximum 2 inputs supported, got {}. (STTMIX-CON-005)",
+            device_ids.len()
+        ));
+    }
+
+    log_info_details!(
+        "commands::recording",
+        "multi_input_start_requested",
+        json!({
+            "device_ids": device_ids.clone(),
+            "count": device_ids.len()
+        })
+    );
+
+    set_session_audio_format(&state, audio_format)?;
+
+    // Enable multi-input mode and save device IDs
+    state.set_multi_input_enabled(true);
+    state.set_selected_device_ids(device_ids.clone());
+
+    let primary_device = device_ids[0].clone();
+    let session_id = start_recording_internal(&app, &state, primary_device).await?;
+
+    Ok(format!(
+        "Multi-input recording started with {} device(s) (session {})",
+        device_ids.len(),
+        session_id
+    ))
+}
+
+/// Grace period for the audio recorder and pipeline tasks to stop cooperatively
+const STOP_GRACE_PERIOD: std::time::Duration = std::time::Duration::from_secs(3);
+/// Additional wait for the recorder after pipeline tasks were aborted
+const STOP_FORCE_PERIOD: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Internal helper for stopping recording
+/// Used by stop_recording command and reconnection logic
+/// Task 10.4 Phase 2: Reusable cleanup for device reconnection
+///
+/// Teardown is bounded so a hung device thread or sidecar pipe can never
+/// wedge the app: grace period → abort tasks → force-detach recorder →
+/// session finalized with warnings. Always unregisters the session; other
+/// sessions keep recording.
+///
+/// # Returns
+/// Teardown warnings (empty on a clean stop)
+pub(crate) async fn stop_recording_internal(
+    state: &AppState,
+    session_id: &str,
+) -> Result<Vec<String>, String> {
+    // Silent return if already stopped
+    let Some(session) = state.get_session(session_id) else {
+        return Ok(Vec::new());
+    };
+    let current_session = session.session_id().to_string();
+    let device_ids = session.device_ids();
+    let mut warnings = Vec::new();
+
+    // Cancel event, audio sender and other session tasks
+    session.cancel();
+    log_info_details!(
+        "commands::recording",
+        "tasks_cancelled",
+        json!({ "session": current_session })
+    );
+
+    {
+        let queue = session.queue_metrics().snapshot();
+        log_info_details!(
+            "commands::recording",
+            "audio_queue_summary",
+            json!({
+                "session": current_session,
+                "peak_queued_ms": crate::ring_buffer::ms_for_bytes(queue.peak_queued_bytes as usize),
+                "dropped_ms": queue.dropped_ms,
+                "overflow_count": queue.overflow_count,
+                "skipped_frames": queue.skipped_frames
+            })
+        );
+    }
+
+    // Stop audio recorder (cleanup resources, including mixer thread)
+    // Runs on a blocking thread: joining device/mixer threads may hang
+    let deadline = tokio::time::Instant::now() + STOP_GRACE_PERIOD;
+    let recorder_for_stop = session.recorder();
+    let mut stop_handle = tokio::task::spawn_blocking(move || match recorder_for_stop {
+        Some(recorder) => recorder.blocking_lock().stop().map_err(|e| e.to_string()),
+        None => Ok(()),
+    });
+
+    // Stage 1: grace period
+    let mut stop_result = tokio::time::timeout_at(deadline, &mut stop_handle).await;
+    while session.running_tasks() > 0 && tokio::time::Instant::now() < deadline {
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+
+    // Stage 2: abort pipeline tasks that ignored cancellation
+    let aborted = session.abort_tasks();
+    if aborted > 0 {
+        log_warn_details!(
+            "commands::recording",
+            "teardown_tasks_aborted",
+            json!({ "session": current_session, "aborted": aborted })
+        );
+        warnings.push(format!(
+            "{} pipeline task(s) did not stop within {}s and were aborted",
+            aborted,
+            STOP_GRACE_PERIOD.as_secs()
+        ));
+    }
+    if stop_result.is_err() {
+        log_warn_details!(
+            "commands::recording",
+            "teardown_recorder_slow",
+            json!({ "session": current_session, "grace_secs": STOP_GRACE_PERIOD.as_secs() })
+        );
+        stop_result = tokio::time::timeout(STOP_FORCE_PERIOD, &mut stop_handle).await;
+    }
+
+    match stop_result {
+        Ok(Ok(Ok(()))) => {}
+        Ok(Ok(Err(e))) => warnings.push(format!("Audio recorder stop failed: {}", e)),
+        Ok(Err(e)) => warnings.push(format!("Audio recorder stop panicked: {}", e)),
+        Err(_) => {
+            // Stage 3: force-drop - detach the wedged recorder (the blocking
+            // thread keeps it); later sessions create their own
+            log_error_details!(
+                "commands::recording",
+                "teardown_recorder_detached",
+                json!({ "session": current_session })
+            );
+            session.take_recorder();
+            warnings.push("Audio device did not stop; recorder was force-detached".to_string());
+        }
+    }
+
+    // Stage 4: finalize session (with warnings, if any)
+    let audio_device = device_ids
+        .first()
+        .map_or_else(|| "unknown".to_string(), |id| state.device_display_name(id));
+    finish_session_storage(state, &session, &audio_device, warnings.clone());
+    state.close_session(&current_session);
+    state.jobs.set_recording_active(state.is_recording());
+
+    log_info_details!(
+        "commands::recording",
+        "stopped",
+        json!({
+            "session": current_session,
+            "device_ids": device_ids,
+            "warnings": warnings
+        })
+    );
+    send_transcript_webhooks(
+        state,
+        crate::transcript_webhooks::TranscriptWebhookBody::SessionEnd {
+            session_id: current_session.clone(),
+            warnings: warnings.clone(),
+        },
+    );
+    publish_mqtt(
+        state,
+        crate::mqtt::MqttEvent::SessionStopped {
+            session_id: current_session,
+            warnings: warnings.clone(),
+        },
+    );
+    Ok(warnings)
+}
+
+/// Stop recording command
+/// Stops audio device recording of a session (None: the primary session)
+#[tauri::command]
+pub async fn stop_recording(
+    state: State<'_, AppState>,
+    session_id: Option<String>,
+) -> Result<String, String> {
+    // Return error if the session is not recording
+    let Some(session_id) = session_id
+        .or_else(|| state.get_session_id())
+        .filter(|id| state.get_session(id).is_some())
+    else {
+        return Err("Not recording".to_string());
+    };
+
+    let warnings = stop_recording_internal(&state, &session_id).await?;
+    if warnings.is_empty() {
+        Ok("Recording stopped".to_string())
+    } else {
+        Ok(format!(
+            "Recording stopped with warnings: {}",
+            warnings.join("; ")
+        ))
+    }
+}
+
+/// Cancel ongoing reconnection attempts
+/// Task 10.4 Phase 2: User-initiated cancellation of auto-reconnect
+#[tauri::command]
+pub async fn cancel_reconnection(state: State<'_, AppState>) -> Result<String, String> {
+    let mut reconnection_mgr = state.reconnection_manager.lock().await;
+
+    if !reconnection_mgr.is_reconnecting() {
+        log_info_details!(
+            "commands::reconnection",
+            "cancel_no_job",
+            json!({})
+        );
+        return Ok("No reconnection in progress".to_string());
+    }
+
+    reconnection_mgr.cancel();
+
+    log_info_details!(
+        "commands::reconnection",
+        "cancelled_by_user",
+        json!({})
+    );
+
+    Ok("Reconnection cancelled".to_string())
+}
+
+/// Confirm the pending reconnection
+/// Prompt policy: required before recording restarts; auto policy: skips the countdown
+#[tauri::command]
+pub async fn confirm_reconnection(state: State<'_, AppState>) -> Result<String, String> {
+    let reconnection_mgr = state.reconnection_manager.lock().await;
+
+    if !reconnection_mgr.confirm() {
+        log_info_details!("commands::reconnection", "confirm_no_job", json!({}));
+        return Ok("No reconnection in progress".to_string());
+    }
+
+    Ok("Reconnection confirmed".to_string())
+}
+
+/// Validate the reconnect policy and make it current
+fn apply_reconnect_policy_settings(
+    app: &AppHandle,
+    settings: &crate::reconnect_policy::ReconnectPolicySettings,
+) -> Result<(), String> {
+    settings.validate().map_err(|e| e.to_string())?;
+    app.state::<AppState>()
+        .set_reconnect_policy_settings(settings.clone());
+    Ok(())
+}
+
+/// Save the reconnect policy
+#[tauri::command]
+pub async fn save_reconnect_policy_settings(
+    app: AppHandle,
+    settings: crate::reconnect_policy::ReconnectPolicySettings,
+) -> Result<(), String> {
+    apply_reconnect_policy_settings(&app, &settings)?;
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::save(
+        &app_data_dir,
+        crate::reconnect_policy::SETTINGS_STEM,
+        &settings,
+    )
+    .map_err(|e| format!("Failed to save reconnect policy settings: {}", e))?;
+
+    log_info_details!(
+        "commands::settings",
+        "reconnect_policy_settings_saved",
+        json!({
+            "policy": settings.policy,
+            "grace_period_secs": settings.grace_period_secs
+        })
+    );
+    Ok(())
+}
+
+/// Load the reconnect policy from disk
+#[tauri::command]
+pub async fn load_reconnect_policy_settings(
+    app: AppHandle,
+) -> Result<crate::reconnect_policy::ReconnectPolicySettings, String> {
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::load(&app_data_dir, crate::reconnect_policy::SETTINGS_STEM)
+        .map_err(|e| format!("Failed to load reconnect policy settings: {}", e))
+}
+
+// ============================================================================
+// Consent Announcement Commands
+// ============================================================================
+
+/// Validate the consent announcement settings and make them current
+fn apply_consent_announcement_settings(
+    app: &AppHandle,
+    settings: &crate::consent::ConsentAnnouncementSettings,
+) -> Result<(), String> {
+    settings.validate().map_err(|e| format!("{:#}", e))?;
+    app.state::<AppState>()
+        .set_consent_announcement_settings(settings.clone());
+    Ok(())
+}
+
+/// Save the recording consent announcement settings
+#[tauri::command]
+pub async fn save_consent_announcement_settings(
+    app: AppHandle,
+    settings: crate::consent::ConsentAnnouncementSettings,
+) -> Result<(), String> {
+    apply_consent_announcement_settings(&app, &settings)?;
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::save(&app_data_dir, crate::consent::SETTINGS_STEM, &settings)
+        .map_err(|e| format!("Failed to save consent announcement settings: {}", e))?;
+
+    log_info_details!(
+        "commands::settings",
+        "consent_announcement_settings_saved",
+        json!({
+            "enabled": settings.enabled,
+            "play_audio": settings.play_audio,
+            "notify_clients": settings.notify_clients
+        })
+    );
+    Ok(())
+}
+
+/// Load the recording consent announcement settings from disk
+#[tauri::command]
+pub async fn load_consent_announcement_settings(
+    app: AppHandle,
+) -> Result<crate::consent::ConsentAnnouncementSettings, String> {
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::load(&app_data_dir, crate::consent::SETTINGS_STEM)
+        .map_err(|e| format!("Failed to load consent announcement settings: {}", e))
+}
+
+/// List output devices (for the consent announcement and the loopback
+/// routing check)
+#[tauri::command]
+pub fn list_output_devices() -> Result<Vec<String>, String> {
+    crate::playback::output_device_names()
+        .map_err(|e| format!("Failed to list output devices: {}", e))
+}
+
+/// Check that audio played on `output_device` reaches the loopback input
+///
+/// Plays a short chirp on the output (None: default output device) while
+/// capturing `loopback_device_id`, and reports whether the chirp was
+/// captured. Not available while recording.
+#[tauri::command]
+pub async fn validate_loopback_routing(
+    state: State<'_, AppState>,
+    loopback_device_id: String,
+    output_device: Option<String>,
+) -> Result<crate::loopback_check::LoopbackCheckResult, String> {
+    if state.is_recording() {
+        return Err("Cannot check loopback routing while recording".to_string());
+    }
+
+    let device_id = loopback_device_id.clone();
+    let output = output_device.clone();
+    let result =
+        tokio::task::spawn_blocking(move || run_loopback_check(&device_id, output.as_deref()))
+            .await
+            .map_err(|e| format!("Loopback check task failed: {}", e))?
+            .map_err(|e| format!("Loopback check failed: {:#}", e))?;
+
+    log_info_details!(
+        "commands::loopback_check",
+        "check_completed",
+        json!({
+            "loopback_device": loopback_device_id,
+            "output_device": output_device,
+            "status": result.status,
+            "correlation": result.correlation,
+            "latency_ms": result.latency_ms,
+            "peak_dbfs": result.peak_dbfs,
+        })
+    );
+    Ok(result)
+}
+
+/// Capture the loopback input around playing the chirp (blocking)
+fn run_loopback_check(
+    loopback_device_id: &str,
+    output_device: Option<&str>,
+) -> anyhow::Result<crate::loopback_check::LoopbackCheckResult> {
+    use crate::loopback_check::{self, LEAD_IN, TAIL};
+
+    let captured: Arc<std::sync::Mutex<Vec<i16>>> = Arc::default();
+    let sink = Arc::clone(&captured);
+    let mut adapter = crate::audio_device_adapter::create_audio_adapter()?;
+    adapter.start_recording_with_callback(
+        loopback_device_id,
+        Box::new(move |chunk: bytes::Bytes| {
+            sink.lock().unwrap().extend(
+                chunk
+                    .chunks_exact(2)
+                    .map(|c| i16::from_le_bytes([c[0], c[1]])),
+            );
+        }),
+    )?;
+
+    std::thread::sleep(LEAD_IN);
+    let playback_start = captured.lock().unwrap().len();
+    let (done_tx, done_rx) = std::sync::mpsc::channel();
+    let played = crate::playback::AudioPlayer::play(
+        "loopback_check",
+        loopback_check::chirp(),
+        loopback_check::SAMPLE_RATE,
+        0,
+        output_device,
+        move || {
+            done_tx.send(()).ok();
+        },
+    );
+    let player = match played {
+        Ok(player) => player,
+        Err(e) => {
+            adapter.stop_recording().ok();
+            return Err(e);
+        }
+    };
+    // The chirp is 300ms; the timeout only guards against a stuck device
+    if done_rx
+        .recv_timeout(std::time::Duration::from_secs(5))
+        .is_err()
+    {
+        log_warn!(
+            "commands::loopback_check",
+            "playback_timeout",
+            "Chirp playback did not finish"
+        );
+    }
+    player.stop();
+    std::thread::sleep(TAIL);
+    adapter.stop_recording()?;
+
+    let captured = std::mem::take(&mut *captured.lock().unwrap());
+    Ok(loopback_check::analyze(&captured, playback_start))
+}
+
+/// Get available Whisper models and system resources
+/// Task 9.2: Whisper model selection UI
+/// Requirement: STT-REQ-006.1, STT-REQ-006.2, STT-REQ-006.4
+#[tauri::command]
+pub async fn get_whisper_models() -> Result<serde_json::Value, String> {
+    log_info!("commands::models", "request_models");
+
+    // Task 9.2: Available models (STT-REQ-006.2)
+    let models = vec!["tiny", "base", "small", "medium", "large-v3"];
+
+    // Task 9.2: Get system resources (MVP0: static values, MVP1: actual detection)
+    let system_resources = serde_json::json!({
+        "cpu_cores": num_cpus::get(),
+        "total_memory_gb": 8,  // MVP0: static, TODO: actual detection
+        "gpu_available": false,  // MVP0: static, TODO: actual detection
+        "gpu_memory_gb": 0,
+    });
+
+    // Task 9.2: Calculate recommended model based on STT-REQ-006.2
+    let recommended_model = calculate_recommended_model(&system_resources);
+
+    Ok(serde_json::json!({
+        "available_models": models,
+        "system_resources": system_resources,
+        "recommended_model": recommended_model,
+    }))
+}
+
+/// Calculate recommended Whisper model based on system resources
+/// Implements STT-REQ-006.2 model selection rules
+fn calculate_recommended_model(resources: &serde_json::Value) -> String {
+    let memory_gb = resources["total_memory_gb"].as_f64().unwrap_or(4.0);
+    let gpu_available = resources["gpu_available"].as_bool().unwrap_or(false);
+    let gpu_memory_gb = resources["gpu_memory_gb"].as_f64().unwrap_or(0.0);
+
+    if gpu_available && memory_gb >= 8.0 && gpu_memory_gb >= 10.0 {
+        "large-v3".to_string()
+    } else if gpu_available && memory_gb >= 4.0 && gpu_memory_gb >= 5.0 {
+        "medium".to_string()
+    } else if memory_gb >= 4.0 {
+        "small".to_string()
+    } else if memory_gb >= 2.0 {
+        "base".to_string()
+    } else {
+        "tiny".to_string()
+    }
+}
+
+/// List available audio input devices
+/// Task 9.1: Audio device selection UI
+/// Requirement: STT-REQ-001.1, STT-REQ-001.2
+///
+/// IMPORTANT: Decoupled from recorder instance to allow enumeration before recording starts.
+/// This matches the real device adapter pattern (CoreAudio/WASAPI/ALSA perform static host queries).
+#[tauri::command]
+pub async fn list_audio_devices(
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::audio_device_adapter::AudioDeviceInfo>, String> {
+    log_info!("commands::audio_devices", "enumerate_requested");
+
+    // Task 9.1: Use static enumeration (no dependency on initialized recorder)
+    // MVP1: Real device adapter enumeration
+    match crate::audio_device_adapter::enumerate_devices_static() {
+        Ok(mut devices) => {
+            log_info_details!(
+                "commands::audio_devices",
+                "enumerate_success",
+                json!({ "count": devices.len() })
+            );
+            // Friendly names replace the device names (IDs are kept)
+            let aliases = state.get_device_alias_settings();
+            for device in &mut devices {
+                if let Some(alias) = aliases.alias(&device.id) {
+                    device.name = alias.to_string();
+                }
+            }
+            for device in &devices {
+                log_debug_details!(
+                    "commands::audio_devices",
+                    "device_entry",
+                    json!({
+                        "id": device.id,
+                        "name": device.name,
+                        "sample_rate": device.sample_rate,
+                        "channels": device.channels,
+                        "loopback": device.is_loopback
+                    })
+                );
+            }
+            Ok(devices)
+        }
+        Err(e) => {
+            log_error_details!(
+                "commands::audio_devices",
+                "enumerate_failed",
+                json!({ "error": e.to_string() })
+            );
+            Err(format!("Failed to list audio devices: {}", e))
+        }
+    }
+}
+
+/// Validate the audio device aliases and make them current
+fn apply_device_alias_settings(
+    app: &AppHandle,
+    settings: &crate::device_aliases::DeviceAliasSettings,
+) -> Result<(), String> {
+    settings.validate().map_err(|e| e.to_string())?;
+    app.state::<AppState>()
+        .set_device_alias_settings(settings.clone());
+    Ok(())
+}
+
+/// Set the friendly name of an audio device (None or blank removes it)
+///
+/// Returns the updated alias settings.
+#[tauri::command]
+pub async fn set_device_alias(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    device_id: String,
+    alias: Option<String>,
+) -> Result<crate::device_aliases::DeviceAliasSettings, String> {
+    let mut settings = state.get_device_alias_settings();
+    settings.set_alias(&device_id, alias.as_deref());
+    apply_device_alias_settings(&app, &settings)?;
+
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::save(
+        &app_data_dir,
+        crate::device_aliases::SETTINGS_STEM,
+        &settings,
+    )
+    .map_err(|e| format!("Failed to save device aliases: {}", e))?;
+
+    log_info_details!(
+        "commands::settings",
+        "device_alias_set",
+        json!({ "device_id": device_id, "has_alias": settings.alias(&device_id).is_some() })
+    );
+    Ok(settings)
+}
+
+/// Load the audio device aliases
+#[tauri::command]
+pub async fn load_device_alias_settings(
+    app: AppHandle,
+) -> Result<crate::device_aliases::DeviceAliasSettings, String> {
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::load(&app_data_dir, crate::device_aliases::SETTINGS_STEM)
+        .map_err(|e| format!("Failed to load device aliases: {}", e))
+}
+
+// ============================================================================
+// Multi-Input Settings Commands (Task 7)
+// ============================================================================
+
+/// Save multi-input settings to disk
+///
+/// Requirement: STTMIX-REQ-001.2, STTMIX-REQ-005.1
+#[tauri::command]
+pub async fn save_multi_input_settings(
+    app: AppHandle,
+    settings: crate::multi_input_settings::MultiInputSettings,
+) -> Result<(), String> {
+    use crate::multi_input_settings::SETTINGS_STEM;
+
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::save(&app_data_dir, SETTINGS_STEM, &settings)
+        .map_err(|e| format!("Failed to save multi-input settings: {}", e))?;
+
+    log_info_details!(
+        "commands::settings",
+        "multi_input_settings_saved",
+        json!({
+            "device_count": settings.selected_device_ids.len(),
+            "enabled": settings.multi_input_enabled
+        })
+    );
+
+    Ok(())
+}
+
+/// Load multi-input settings from disk
+///
+/// Requirement: STTMIX-REQ-001.2
+#[tauri::command]
+pub async fn load_multi_input_settings(
+    app: AppHandle,
+) -> Result<crate::multi_input_settings::MultiInputSettings, String> {
+    use crate::multi_input_settings::{MultiInputSettings, SETTINGS_STEM};
+
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    let settings: MultiInputSettings = settings_file::load(&app_data_dir, SETTINGS_STEM)
+        .map_err(|e| format!("Failed to load multi-input settings: {}", e))?;
+
+    log_info_details!(
+        "commands::settings",
+        "multi_input_settings_loaded",
+        json!({
+            "device_count": settings.selected_device_ids.len(),
+            "enabled": settings.multi_input_enabled
+        })
+    );
+
+    Ok(settings)
+}
+
+/// Get platform information for feature gating
+///
+/// Returns the current OS platform. Multi-input is only supported on macOS.
+///
+/// Requirement: STTMIX-CON-004
+#[tauri::command]
+pub fn get_platform_info() -> PlatformInfo {
+    PlatformInfo {
+        os: std::env::consts::OS.to_string(),
+        multi_input_supported: cfg!(target_os = "macos"),
+    }
+}
+
+#[derive(serde::Serialize)]
+pub struct PlatformInfo {
+    pub os: String,
+    pub multi_input_supported: bool,
+}
+
+/// Get status of all multi-input channels of a session (None: the primary session)
+///
+/// Returns buffer occupancy, active status, and metrics for each input.
+/// Used by UI to display input health indicators.
+///
+/// Requirement: STTMIX-REQ-008.1, Task 8.3
+#[tauri::command]
+pub async fn get_multi_input_status(
+    state: State<'_, AppState>,
+    session_id: Option<String>,
+) -> Result<MultiInputStatusResponse, String> {
+    let session = match session_id {
+        Some(session_id) => state.get_session(&session_id),
+        None => state.primary_session(),
+    };
+    let Some(recorder_arc) = session.and_then(|session| session.recorder()) else {
+        // Not recording: no inputs
+        return Ok(MultiInputStatusResponse {
+            inputs: Vec::new(),
+            is_recording: false,
+            mixer_metrics: None,
+        });
+    };
+    let recorder = recorder_arc.lock().await;
+
+    let aliases = state.get_device_alias_settings();
+    let mut input_statuses = recorder.get_input_status();
+    for input in &mut input_statuses {
+        input.alias = aliases.alias(&input.device_id).map(str::to_string);
+    }
+    let mixer_metrics = recorder.get_mixer_metrics();
+
+    Ok(MultiInputStatusResponse {
+        inputs: input_statuses,
+        is_recording: recorder.is_recording(),
+        mixer_metrics: mixer_metrics.map(|m| MixerMetricsResponse {
+            drift_correction_count: m.get_drift_correction_count(),
+            clip_count: m.get_clip_count(),
+            silence_insertion_count: m.get_silence_insertion_count(),
+            frames_mixed: m.get_frames_mixed(),
+            max_mix_latency_ms: m.get_max_mix_latency_ms(),
+            avg_mix_latency_ms: m.get_avg_mix_latency_ms(),
+        }),
+    })
+}
+
+#[derive(serde::Serialize)]
+pub struct MultiInputStatusResponse {
+    pub inputs: Vec<InputStatus>,
+    pub is_recording: bool,
+    pub mixer_metrics: Option<MixerMetricsResponse>,
+}
+
+#[derive(serde::Serialize)]
+pub struct MixerMetricsResponse {
+    pub drift_correction_count: u64,
+    pub clip_count: u64,
+    pub silence_insertion_count: u64,
+    pub frames_mixed: u64,
+    // Task 9.1: Latency metrics
+    pub max_mix_latency_ms: f64,
+    pub avg_mix_latency_ms: f64,
+}
+
+/// Validate that selected devices are still available
+///
+/// Returns list of unavailable device IDs
+///
+/// Requirement: STTMIX-REQ-001.2
+#[tauri::command]
+pub async fn validate_multi_input_devices(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Vec<String>, String> {
+    use crate::multi_input_settings::{validate_devices, MultiInputSettings, SETTINGS_STEM};
+
+    // Load current settings
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    let settings: MultiInputSettings = settings_file::load(&app_data_dir, SETTINGS_STEM)
+        .map_err(|e| format!("Failed to load settings: {}", e))?;
+
+    // Get available devices using the recorders' factory
+    let factory = state.get_audio_recorder_factory();
+    let available_devices: Vec<String> = if let Some(factory) = factory {
+        let recorder = crate::audio_device_recorder::AudioDeviceRecorder::new(factory);
+        recorder
+            .enumerate_devices()
+            .map_err(|e| format!("Failed to enumerate devices: {}", e))?
+            .into_iter()
+            .map(|d| d.id)
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    // Find unavailable devices
+    let unavailable = validate_devices(&settings, &available_devices);
+
+    if !unavailable.is_empty() {
+        log_warn_details!(
+            "commands::settings",
+            "unavailable_devices_detected",
+            json!({
+                "unavailable": unavailable,
+                "total_selected": settings.selected_device_ids.len()
+            })
+        );
+    }
+
+    Ok(unavailable)
+}
+
+// ============================================================================
+// WASAPI Capture Commands
+// ============================================================================
+
+/// Validate the WASAPI capture settings and make them current
+fn apply_wasapi_capture_settings(
+    settings: &crate::wasapi_capture::WasapiCaptureSettings,
+) -> Result<(), String> {
+    settings
+        .validate()
+        .map_err(|e| format!("Invalid WASAPI capture settings: {}", e))?;
+    crate::wasapi_capture::set_settings(settings.clone());
+    Ok(())
+}
+
+/// Save the WASAPI capture settings (applied at the next recording start)
+///
+/// Machine-wide; ignored outside Windows.
+#[tauri::command]
+pub async fn save_wasapi_capture_settings(
+    app: AppHandle,
+    settings: crate::wasapi_capture::WasapiCaptureSettings,
+) -> Result<(), String> {
+    apply_wasapi_capture_settings(&settings)?;
+
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::save(
+        &app_data_dir,
+        crate::wasapi_capture::SETTINGS_STEM,
+        &settings,
+    )
+    .map_err(|e| format!("Failed to save WASAPI capture settings: {}", e))?;
+
+    log_info_details!(
+        "commands::settings",
+        "wasapi_capture_settings_saved",
+        json!({
+            "buffer_ms": settings.buffer_ms,
+            "exclusive": settings.exclusive,
+        })
+    );
+    Ok(())
+}
+
+/// Load the WASAPI capture settings from disk
+#[tauri::command]
+pub async fn load_wasapi_capture_settings(
+    app: AppHandle,
+) -> Result<crate::wasapi_capture::WasapiCaptureSettings, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::load(&app_data_dir, crate::wasapi_capture::SETTINGS_STEM)
+        .map_err(|e| format!("Failed to load WASAPI capture settings: {}", e))
+}
+
+// ============================================================================
+// Channel Selection Commands
+// ============================================================================
+
+/// Validate the channel selection and make it current
+fn apply_channel_selection_settings(
+    settings: &crate::channel_selection::ChannelSelectionSettings,
+) -> Result<(), String> {
+    settings
+        .validate()
+        .map_err(|e| format!("Invalid channel selection: {}", e))?;
+    crate::channel_selection::set_settings(settings.clone());
+    Ok(())
+}
+
+/// Save the per-device input channel selection (applied at the next
+/// recording start)
+///
+/// Machine-wide, like the device wiring it describes.
+#[tauri::command]
+pub async fn save_channel_selection_settings(
+    app: AppHandle,
+    settings: crate::channel_selection::ChannelSelectionSettings,
+) -> Result<(), String> {
+    apply_channel_selection_settings(&settings)?;
+
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::save(
+        &app_data_dir,
+        crate::channel_selection::SETTINGS_STEM,
+        &settings,
+    )
+    .map_err(|e| format!("Failed to save channel selection: {}", e))?;
+
+    log_info_details!(
+        "commands::settings",
+        "channel_selection_settings_saved",
+        json!({ "devices": settings.devices })
+    );
+    Ok(())
+}
+
+/// Load the per-device input channel selection from disk
+#[tauri::command]
+pub async fn load_channel_selection_settings(
+    app: AppHandle,
+) -> Result<crate::channel_selection::ChannelSelectionSettings, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::load(&app_data_dir, crate::channel_selection::SETTINGS_STEM)
+        .map_err(|e| format!("Failed to load channel selection: {}", e))
+}
+
+// ============================================================================
+// Keyword Alert Settings Commands
+// ============================================================================
+
+/// Make keyword alert settings current
+fn apply_keyword_alert_settings(
+    app: &AppHandle,
+    settings: &crate::keyword_alerts::KeywordAlertSettings,
+) -> Result<(), String> {
+    app.state::<AppState>()
+        .set_keyword_alert_settings(settings.clone());
+    Ok(())
+}
+
+/// Save keyword alert settings to disk and apply them immediately
+#[tauri::command]
+pub async fn save_keyword_alert_settings(
+    app: AppHandle,
+    settings: crate::keyword_alerts::KeywordAlertSettings,
+) -> Result<(), String> {
+    apply_keyword_alert_settings(&app, &settings)?;
+
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::save(
+        &app_data_dir,
+        crate::keyword_alerts::SETTINGS_STEM,
+        &settings,
+    )
+    .map_err(|e| format!("Failed to save keyword alert settings: {}", e))?;
+
+    log_info_details!(
+        "commands::settings",
+        "keyword_alert_settings_saved",
+        json!({
+            "watchlist_count": settings.watchlists.len(),
+            "enabled": settings.enabled
+        })
+    );
+    Ok(())
+}
+
+/// Load keyword alert settings from disk
+#[tauri::command]
+pub async fn load_keyword_alert_settings(
+    app: AppHandle,
+) -> Result<crate::keyword_alerts::KeywordAlertSettings, String> {
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::load(&app_data_dir, crate::keyword_alerts::SETTINGS_STEM)
+        .map_err(|e| format!("Failed to load keyword alert settings: {}", e))
+}
+
+// ============================================================================
+// Docs Budget Settings Commands
+// ============================================================================
+
+/// Validate docs budget settings and make them current
+fn apply_docs_budget_settings(
+    app: &AppHandle,
+    settings: &crate::docs_budget::DocsBudgetSettings,
+) -> Result<(), String> {
+    settings
+        .validate()
+        .map_err(|e| format!("Invalid docs budget settings: {}", e))?;
+    app.state::<AppState>()
+        .set_docs_budget_settings(settings.clone());
+    Ok(())
+}
+
+/// Save Google Docs character budget settings (limit, warning threshold)
+#[tauri::command]
+pub async fn save_docs_budget_settings(
+    app: AppHandle,
+    settings: crate::docs_budget::DocsBudgetSettings,
+) -> Result<(), String> {
+    apply_docs_budget_settings(&app, &settings)?;
+
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::save(&app_data_dir, crate::docs_budget::SETTINGS_STEM, &settings)
+        .map_err(|e| format!("Failed to save docs budget settings: {}", e))?;
+
+    log_info_details!(
+        "commands::settings",
+        "docs_budget_settings_saved",
+        json!({
+            "enabled": settings.enabled,
+            "limit_characters": settings.limit_characters,
+            "warn_percent": settings.warn_percent
+        })
+    );
+    Ok(())
+}
+
+/// Load Google Docs character budget settings from disk
+#[tauri::command]
+pub async fn load_docs_budget_settings(
+    app: AppHandle,
+) -> Result<crate::docs_budget::DocsBudgetSettings, String> {
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::load(&app_data_dir, crate::docs_budget::SETTINGS_STEM)
+        .map_err(|e| format!("Failed to load docs budget settings: {}", e))
+}
+
+/// Current Google Docs character budget of the session
+#[tauri::command]
+pub async fn get_docs_budget_status(
+    state: State<'_, AppState>,
+) -> Result<crate::docs_budget::DocsBudgetStatus, String> {
+    Ok(state.docs_budget_status())
+}
+
+/// The extension reported syncing to `document_id` (`docsSync`)
+///
+/// A different document than before restarts the document count.
+pub(crate) fn docs_document_synced(app: &AppHandle, document_id: &str) {
+    if app.state::<AppState>().docs_document_synced(document_id) {
+        log_info_details!(
+            "commands::docs_budget",
+            "docs_document_rolled_over",
+            json!({ "document": document_id })
+        );
+    }
+}
+
+// ============================================================================
+// Memory Sentinel Settings Commands
+// ============================================================================
+
+/// Make memory sentinel settings current
+fn apply_memory_sentinel_settings(
+    app: &AppHandle,
+    settings: &crate::memory_sentinel::MemorySentinelSettings,
+) -> Result<(), String> {
+    app.state::<AppState>()
+        .set_memory_sentinel_settings(settings.clone());
+    Ok(())
+}
+
+/// Save memory sentinel settings (sample interval, per-structure bounds)
+///
+/// The sample interval applies from the next recording.
+#[tauri::command]
+pub async fn save_memory_sentinel_settings(
+    app: AppHandle,
+    settings: crate::memory_sentinel::MemorySentinelSettings,
+) -> Result<(), String> {
+    apply_memory_sentinel_settings(&app, &settings)?;
+
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::save(
+        &app_data_dir,
+        crate::memory_sentinel::SETTINGS_STEM,
+        &settings,
+    )
+    .map_err(|e| format!("Failed to save memory sentinel settings: {}", e))?;
+
+    log_info_details!(
+        "commands::settings",
+        "memory_sentinel_settings_saved",
+        json!({
+            "enabled": settings.enabled,
+            "sample_interval_secs": settings.sample_interval_secs,
+            "bound_count": settings.bounds.len()
+        })
+    );
+    Ok(())
+}
+
+/// Load memory sentinel settings from disk
+#[tauri::command]
+pub async fn load_memory_sentinel_settings(
+    app: AppHandle,
+) -> Result<crate::memory_sentinel::MemorySentinelSettings, String> {
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::load(&app_data_dir, crate::memory_sentinel::SETTINGS_STEM)
+        .map_err(|e| format!("Failed to load memory sentinel settings: {}", e))
+}
+
+// ============================================================================
+// Routing Settings Commands
+// ============================================================================
+
+/// Compile transcript routing rules and make them current
+fn apply_routing_settings(
+    app: &AppHandle,
+    settings: &crate::routing::RoutingSettings,
+) -> Result<(), String> {
+    let engine = crate::routing::RoutingEngine::compile(settings)
+        .map_err(|e| format!("Invalid routing rules: {:#}", e))?;
+    app.state::<AppState>().set_routing_engine(engine);
+    Ok(())
+}
+
+/// Save transcript routing rules and apply them immediately
+///
+/// Rules are compiled before saving so invalid patterns are rejected.
+#[tauri::command]
+pub async fn save_routing_settings(
+    app: AppHandle,
+    settings: crate::routing::RoutingSettings,
+) -> Result<(), String> {
+    apply_routing_settings(&app, &settings)?;
+
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::save(&app_data_dir, crate::routing::SETTINGS_STEM, &settings)
+        .map_err(|e| format!("Failed to save routing settings: {}", e))?;
+
+    log_info_details!(
+        "commands::settings",
+        "routing_settings_saved",
+        json!({
+            "rule_count": settings.rules.len(),
+            "enabled": settings.enabled
+        })
+    );
+    Ok(())
+}
+
+/// Load transcript routing rules from disk
+#[tauri::command]
+pub async fn load_routing_settings(
+    app: AppHandle,
+) -> Result<crate::routing::RoutingSettings, String> {
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::load(&app_data_dir, crate::routing::SETTINGS_STEM)
+        .map_err(|e| format!("Failed to load routing settings: {}", e))
+}
+
+// ============================================================================
+// Pipeline Settings Commands
+// ============================================================================
+
+/// Compile the post-processing pipeline and make it current
+fn apply_pipeline_settings(
+    app: &AppHandle,
+    settings: &crate::pipeline::PipelineSettings,
+) -> Result<(), String> {
+    let pipeline = crate::pipeline::Pipeline::compile(settings)
+        .map_err(|e| format!("Invalid pipeline: {:#}", e))?;
+    app.state::<AppState>().set_pipeline(pipeline);
+    Ok(())
+}
+
+/// Save the post-processing pipeline and apply it immediately
+///
+/// The pipeline is compiled before saving so invalid stages are rejected.
+#[tauri::command]
+pub async fn save_pipeline_settings(
+    app: AppHandle,
+    settings: crate::pipeline::PipelineSettings,
+) -> Result<(), String> {
+    apply_pipeline_settings(&app, &settings)?;
+
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::save(&app_data_dir, crate::pipeline::SETTINGS_STEM, &settings)
+        .map_err(|e| format!("Failed to save pipeline settings: {}", e))?;
+
+    log_info_details!(
+        "commands::settings",
+        "pipeline_settings_saved",
+        json!({
+            "stages": settings
+                .stages
+                .iter()
+                .filter(|stage| stage.enabled)
+                .map(|stage| stage.stage)
+                .collect::<Vec<_>>()
+        })
+    );
+    Ok(())
+}
+
+/// Load the post-processing pipeline from disk
+#[tauri::command]
+pub async fn load_pipeline_settings(
+    app: AppHandle,
+) -> Result<crate::pipeline::PipelineSettings, String> {
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::load(&app_data_dir, crate::pipeline::SETTINGS_STEM)
+        .map_err(|e| format!("Failed to load pipeline settings: {}", e))
+}
+
+// ============================================================================
+// Session ID Format Commands
+// ============================================================================
+
+/// Save the session ID format (applies from the next recording)
+///
+/// The template is rendered once before saving so path-unsafe formats are rejected.
+#[tauri::command]
+pub async fn save_session_id_settings(
+    app: AppHandle,
+    settings: crate::session_id::SessionIdSettings,
+) -> Result<(), String> {
+    crate::session_id::validate_template(&settings.template)
+        .map_err(|e| format!("Invalid session ID format: {:#}", e))?;
+
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::save(&app_data_dir, crate::session_id::SETTINGS_STEM, &settings)
+        .map_err(|e| format!("Failed to save session ID settings: {}", e))?;
+
+    log_info_details!(
+        "commands::settings",
+        "session_id_settings_saved",
+        json!({ "template": settings.template })
+    );
+    Ok(())
+}
+
+/// Load the session ID format from disk
+#[tauri::command]
+pub async fn load_session_id_settings(
+    app: AppHandle,
+) -> Result<crate::session_id::SessionIdSettings, String> {
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::load(&app_data_dir, crate::session_id::SETTINGS_STEM)
+        .map_err(|e| format!("Failed to load session ID settings: {}", e))
+}
+
+/// Get audio send queue metrics of the active (or last) session
+///
+/// Returns None before the first recording.
+#[tauri::command]
+pub fn get_audio_queue_metrics(
+    state: State<'_, AppState>,
+) -> Option<crate::ring_buffer::AudioQueueSnapshot> {
+    state.audio_queue_snapshot()
+}
+
+/// Get per-stage latency histograms of the active (or last) session
+///
+/// Stages: buffering, IPC write, first partial, final, persist, broadcast
+/// and end-to-end (see `latency::LatencyStage`).
+#[tauri::command]
+pub fn get_latency_metrics(state: State<'_, AppState>) -> crate::latency::LatencySnapshot {
+    state.latency_snapshot()
+}
+
+/// Get per-structure byte counts of long-lived buffers
+///
+/// Includes the growth warnings raised during the active (or last) session.
+#[tauri::command]
+pub fn get_memory_metrics(state: State<'_, AppState>) -> crate::memory_sentinel::MemorySnapshot {
+    state.memory_snapshot()
+}
+
+/// Get per-lane queue depth metrics of the sidecar stdin writer
+///
+/// Returns None while no sidecar is running.
+#[tauri::command]
+pub fn get_ipc_lane_metrics(
+    state: State<'_, AppState>,
+) -> Option<crate::stdin_writer::IpcLaneMetrics> {
+    state
+        .get_sidecar_stdin()
+        .map(|writer| writer.lane_metrics())
+}
+
+/// Get audio send flow control metrics of the active (or last) session
+///
+/// Lag between audio sent and acknowledged by the sidecar, current batch
+/// interval and ticks held back (see `ipc_flow_control`).
+#[tauri::command]
+pub fn get_ipc_flow_metrics(
+    state: State<'_, AppState>,
+) -> crate::ipc_flow_control::FlowControlSnapshot {
+    state.ipc_flow_snapshot()
+}
+
+/// Get the sidecar health reported to the periodic checks while recording
+///
+/// Model loaded, real-time factor, queued speech audio and memory use of
+/// the last reply, plus the ping round trip (see `sidecar_health`).
+#[tauri::command]
+pub fn get_sidecar_status(state: State<'_, AppState>) -> crate::sidecar_health::SidecarStatus {
+    state.sidecar_status()
+}
+
+/// Send a control message to the sidecar on the priority lane
+///
+/// `method`: "flush", "cancel" or "ping". Returns the message id.
+#[tauri::command]
+pub async fn send_sidecar_control(
+    state: State<'_, AppState>,
+    method: String,
+) -> Result<String, String> {
+    let message = match method.as_str() {
+        "flush" => ControlMessage::Flush,
+        "cancel" => ControlMessage::Cancel,
+        "ping" => ControlMessage::Ping,
+        other => return Err(format!("Unknown control method: {}", other)),
+    };
+    let writer = state
+        .get_sidecar_stdin()
+        .ok_or_else(|| "Python sidecar is not running".to_string())?;
+    writer
+        .send_control_message(message)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Get the recording interrupted by a crash, detected at startup
+///
+/// Returns None when the previous session ended cleanly.
+#[tauri::command]
+pub fn get_interrupted_recording(
+    state: State<'_, AppState>,
+) -> Option<crate::heartbeat::InterruptedRecording> {
+    state.get_interrupted_recording()
+}
+
+/// Get the unfinalized sessions repaired at startup
+///
+/// Same list as the `recovered_sessions` event, which is emitted before the
+/// frontend may be listening.
+#[tauri::command]
+pub fn get_recovered_sessions(
+    state: State<'_, AppState>,
+) -> Vec<crate::crash_recovery::RecoveredSession> {
+    state.get_recovered_sessions()
+}
+
+// ============================================================================
+// Agenda Tracking Commands
+// ============================================================================
+
+/// Set the agenda for the current/next meeting
+///
+/// Replaces any existing agenda (and its recorded progress).
+#[tauri::command]
+pub fn set_agenda(
+    state: State<'_, AppState>,
+    titles: Vec<String>,
+) -> Result<crate::agenda::AgendaTracker, String> {
+    if titles.iter().any(|t| t.trim().is_empty()) {
+        return Err("Agenda item title must not be empty".to_string());
+    }
+
+    let tracker = crate::agenda::AgendaTracker::from_titles(titles);
+    *state.agenda.lock().unwrap() = tracker.clone();
+
+    log_info_details!(
+        "commands::agenda",
+        "agenda_set",
+        json!({ "item_count": tracker.items.len() })
+    );
+    Ok(tracker)
+}
+
+/// Get the current agenda and its progress
+#[tauri::command]
+pub fn get_agenda(state: State<'_, AppState>) -> crate::agenda::AgendaTracker {
+    state.agenda.lock().unwrap().clone()
+}
+
+/// Mark agenda item as started at the current session time
+#[tauri::command]
+pub fn mark_agenda_item_started(
+    state: State<'_, AppState>,
+    item_id: String,
+) -> Result<crate::agenda::AgendaTracker, String> {
+    update_agenda(&state, &item_id, "item_started", |agenda, at_ms| {
+        agenda.mark_started(&item_id, at_ms)
+    })
+}
+
+/// Mark agenda item as finished at the current session time
+#[tauri::command]
+pub fn mark_agenda_item_finished(
+    state: State<'_, AppState>,
+    item_id: String,
+) -> Result<crate::agenda::AgendaTracker, String> {
+    update_agenda(&state, &item_id, "item_finished", |agenda, at_ms| {
+        agenda.mark_finished(&item_id, at_ms)
+    })
+}
+
+/// Apply an agenda mark at the current session time and persist agenda.json
+fn update_agenda<F>(
+    state: &AppState,
+    item_id: &str,
+    event: &str,
+    apply: F,
+) -> Result<crate::agenda::AgendaTracker, String>
+where
+    F: FnOnce(&mut crate::agenda::AgendaTracker, u64) -> Result<(), crate::agenda::AgendaError>,
+{
+    let (session_id, at_ms) = match (state.get_session_id(), state.session_elapsed_ms()) {
+        (Some(session_id), Some(at_ms)) => (session_id, at_ms),
+        _ => return Err("Not recording".to_string()),
+    };
+
+    let tracker = {
+        let mut agenda = state.agenda.lock().unwrap();
+        apply(&mut agenda, at_ms).map_err(|e| e.to_string())?;
+        agenda.clone()
+    };
+
+    // Persist progress immediately so a crash keeps the markers
+    if let Some(storage) = state.get_storage_service() {
+        if let Err(e) = crate::agenda::save_agenda(&storage.get_session_dir(&session_id), &tracker)
+        {
+            log_warn_details!(
+                "commands::agenda",
+                "agenda_save_failed",
+                json!({ "session": session_id, "error": e.to_string() })
+            );
+        }
+    }
+
+    log_info_details!(
+        "commands::agenda",
+        event,
+        json!({
+            "session": session_id,
+            "item_id": item_id,
+            "at_ms": at_ms
+        })
+    );
+    Ok(tracker)
+}
+
+/// Get per-item durations and the transcript sliced by agenda item
+///
+/// Works for both the active session (in-memory agenda) and saved sessions.
+#[tauri::command]
+pub fn get_agenda_report(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<crate::agenda::AgendaReport, String> {
+    crate::session_id::validate_session_id(&session_id).map_err(|e| e.to_string())?;
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+
+    let transcript = storage
+        .load_transcript(&session_id)
+        .map_err(|e| format!("Failed to load transcript: {}", e))?;
+
+    let (agenda, end_ms) = if state.get_session_id().as_deref() == Some(session_id.as_str()) {
+        let agenda = state.agenda.lock().unwrap().clone();
+        (agenda, state.session_elapsed_ms().unwrap_or(0))
+    } else {
+        let session_dir = storage.get_session_dir(&session_id);
+        if !session_dir.exists() {
+            return Err(format!("Session not found: {}", session_id));
+        }
+        let agenda = crate::agenda::load_agenda(&session_dir)
+            .map_err(|e| format!("Failed to load agenda: {}", e))?;
+        let end_ms = storage
+            .load_session(&session_id)
+            .map(|loaded| loaded.metadata.duration_seconds * 1000)
+            .unwrap_or_else(|_| transcript.last().map(|e| e.timestamp_ms + 1).unwrap_or(0));
+        (agenda, end_ms)
+    };
+
+    Ok(agenda.slice_transcript(&transcript, end_ms))
+}
+
+/// Get the questions of a session and which ones are still open
+///
+/// Saved sessions are replayed from their transcript.
+#[tauri::command]
+pub fn get_question_report(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<crate::questions::QuestionReport, String> {
+    crate::session_id::validate_session_id(&session_id).map_err(|e| e.to_string())?;
+    if state.get_session_id().as_deref() == Some(session_id.as_str()) {
+        return Ok(state.question_tracker.lock().unwrap().report());
+    }
+
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+    if !storage.get_session_dir(&session_id).exists() {
+        return Err(format!("Session not found: {}", session_id));
+    }
+    let transcript = storage
+        .load_transcript(&session_id)
+        .map_err(|e| format!("Failed to load transcript: {}", e))?;
+
+    Ok(crate::questions::QuestionTracker::from_transcript(
+        &transcript,
+        crate::questions::DEFAULT_ANSWER_WINDOW_MS,
+    )
+    .report())
+}
+
+/// Compare two transcript versions of a session (e.g. "v1" vs a re-transcribed "v2")
+///
+/// "v1" is the live `transcription.jsonl`, other versions are
+/// `transcription.<version>.jsonl`. With `reference` (e.g. a corrected
+/// version), word error rates and the accuracy delta are included.
+#[tauri::command]
+pub fn diff_transcripts(
+    state: State<'_, AppState>,
+    session_id: String,
+    v1: String,
+    v2: String,
+    reference: Option<String>,
+) -> Result<crate::transcript_diff::TranscriptDiff, String> {
+    crate::session_id::validate_session_id(&session_id).map_err(|e| e.to_string())?;
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+    let load = |version: &str| {
+        storage
+            .load_transcript_version(&session_id, version)
+            .map_err(|e| format!("Failed to load transcript {}: {}", version, e))
+    };
+
+    let a = load(&v1)?;
+    let b = load(&v2)?;
+    let reference_events = reference.as_deref().map(load).transpose()?;
+
+    let diff = crate::transcript_diff::compare(
+        &v1,
+        &a,
+        &v2,
+        &b,
+        reference.as_deref().zip(reference_events.as_deref()),
+    );
+    log_info_details!(
+        "commands::transcript_diff",
+        "transcripts_compared",
+        json!({
+            "session": session_id,
+            "a_version": v1,
+            "b_version": v2,
+            "region_count": diff.regions.len(),
+            "change_rate": diff.change_rate
+        })
+    );
+    Ok(diff)
+}
+
+// ============================================================================
+// Activity Commands
+// ============================================================================
+
+/// Get the per-second activity timeline of a session for waveform/heatmap display
+///
+/// Reads `activity.bin` (computed from `audio.wav` for older sessions) and
+/// merges seconds so at most `max_points` points are returned.
+#[tauri::command]
+pub fn get_session_activity(
+    state: State<'_, AppState>,
+    session_id: String,
+    max_points: Option<usize>,
+) -> Result<crate::activity::ActivityTimeline, String> {
+    crate::session_id::validate_session_id(&session_id).map_err(|e| e.to_string())?;
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+    let session_dir = storage.get_session_dir(&session_id);
+    if !session_dir.exists() {
+        return Err(format!("Session not found: {}", session_id));
+    }
+
+    let points = crate::activity::load_activity(&session_dir)
+        .map_err(|e| format!("Failed to load activity: {}", e))?;
+    Ok(crate::activity::downsample(
+        &points,
+        max_points.unwrap_or(usize::MAX),
+    ))
+}
+
+/// Get waveform peaks of a session for the playback UI
+///
+/// `resolution` is samples per pixel (rounded to a multiple of 256). With
+/// `start_ms`/`end_ms` only that window is returned. `waveform.dat` is
+/// generated on first use for sessions that don't have one yet.
+#[tauri::command]
+pub async fn get_waveform(
+    state: State<'_, AppState>,
+    session_id: String,
+    resolution: u32,
+    start_ms: Option<u64>,
+    end_ms: Option<u64>,
+) -> Result<crate::waveform::WaveformData, String> {
+    crate::session_id::validate_session_id(&session_id).map_err(|e| e.to_string())?;
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+    let session_dir = storage.get_session_dir(&session_id);
+    if !session_dir.exists() {
+        return Err(format!("Session not found: {}", session_id));
+    }
+
+    let waveform =
+        tokio::task::spawn_blocking(move || crate::waveform::load_or_generate(&session_dir))
+            .await
+            .map_err(|e| format!("Waveform task failed: {}", e))?
+            .map_err(|e| format!("Failed to load waveform: {}", e))?;
+    Ok(waveform.resample(resolution, start_ms, end_ms))
+}
+
+/// Get the peak/RMS envelope of a session in `buckets` buckets (default 1000)
+///
+/// Levels are normalized to 0.0..=1.0, for drawing a scrubbable overview.
+#[tauri::command]
+pub async fn get_waveform_envelope(
+    state: State<'_, AppState>,
+    session_id: String,
+    buckets: Option<usize>,
+) -> Result<crate::waveform::WaveformEnvelope, String> {
+    crate::session_id::validate_session_id(&session_id).map_err(|e| e.to_string())?;
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+    let session_dir = storage.get_session_dir(&session_id);
+    if !session_dir.exists() {
+        return Err(format!("Session not found: {}", session_id));
+    }
+
+    let buckets = buckets.unwrap_or(crate::waveform::DEFAULT_ENVELOPE_BUCKETS);
+    tokio::task::spawn_blocking(move || crate::waveform::envelope(&session_dir, buckets))
+        .await
+        .map_err(|e| format!("Waveform task failed: {}", e))?
+        .map_err(|e| format!("Failed to compute waveform envelope: {}", e))
+}
+
+// ============================================================================
+// Playback Commands
+// ============================================================================
+
+/// Play a saved session's audio on the default output device from `start_ms`
+///
+/// Replaces any playback in progress. `playback_finished` is emitted when the
+/// end of the audio is reached.
+#[tauri::command]
+pub async fn play_session_audio(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    session_id: String,
+    start_ms: Option<u64>,
+) -> Result<(), String> {
+    crate::session_id::validate_session_id(&session_id).map_err(|e| e.to_string())?;
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+    let session_dir = storage.get_session_dir(&session_id);
+    if !session_dir.exists() {
+        return Err(format!("Session not found: {}", session_id));
+    }
+
+    // Release the output device before opening it again
+    let previous = state.playback.lock().unwrap().take();
+    if let Some(player) = previous {
+        player.stop();
+    }
+
+    let start_ms = start_ms.unwrap_or(0);
+    let finished_session = session_id.clone();
+    let player = tokio::task::spawn_blocking(move || {
+        crate::playback::AudioPlayer::start(&session_id, &session_dir, start_ms, move || {
+            if let Err(e) = app.emit(
+                "playback_finished",
+                json!({ "session_id": finished_session }),
+            ) {
+                log_warn!("commands::playback", "emit_failed", e.to_string());
+            }
+        })
+    })
+    .await
+    .map_err(|e| format!("Playback task failed: {}", e))?
+    .map_err(|e| format!("Failed to start playback: {:#}", e))?;
+
+    log_info_details!(
+        "commands::playback",
+        "started",
+        json!({ "session": player.id(), "start_ms": start_ms })
+    );
+    *state.playback.lock().unwrap() = Some(player);
+    Ok(())
+}
+
+/// Stop playback and return the position it stopped at (ms)
+///
+/// Returns None when nothing was playing.
+#[tauri::command]
+pub fn stop_playback(state: State<'_, AppState>) -> Option<u64> {
+    let player = state.playback.lock().unwrap().take()?;
+    let session_id = player.id().to_string();
+    let position_ms = player.stop();
+    log_info_details!(
+        "commands::playback",
+        "stopped",
+        json!({ "session": session_id, "position_ms": position_ms })
+    );
+    Some(position_ms)
+}
+
+// ============================================================================
+// Job Commands
+// ============================================================================
+
+/// List queued, running and recently finished background jobs
+#[tauri::command]
+pub fn get_jobs(state: State<'_, AppState>) -> Vec<crate::jobs::JobInfo> {
+    state.jobs.jobs()
+}
+
+/// Cancel a queued or running background job
+#[tauri::command]
+pub fn cancel_job(state: State<'_, AppState>, job_id: String) -> Result<(), String> {
+    state.jobs.cancel(&job_id).map_err(|e| e.to_string())?;
+    log_info_details!(
+        "commands::jobs",
+        "job_cancel_requested",
+        json!({ "job": job_id })
+    );
+    Ok(())
+}
+
+/// Make background job limits current
+fn apply_job_settings(app: &AppHandle, settings: &crate::jobs::JobSettings) -> Result<(), String> {
+    app.state::<AppState>().jobs.set_settings(settings.clone());
+    Ok(())
+}
+
+/// Save background job limits and apply them immediately
+#[tauri::command]
+pub async fn save_job_settings(
+    app: AppHandle,
+    settings: crate::jobs::JobSettings,
+) -> Result<(), String> {
+    apply_job_settings(&app, &settings)?;
+
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::save(&app_data_dir, crate::jobs::SETTINGS_STEM, &settings)
+        .map_err(|e| format!("Failed to save job settings: {}", e))?;
+
+    log_info_details!(
+        "commands::settings",
+        "job_settings_saved",
+        json!({
+            "limits": settings.limits,
+            "pause_heavy_while_recording": settings.pause_heavy_while_recording
+        })
+    );
+    Ok(())
+}
+
+/// Load background job limits
+#[tauri::command]
+pub async fn load_job_settings(app: AppHandle) -> Result<crate::jobs::JobSettings, String> {
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::load(&app_data_dir, crate::jobs::SETTINGS_STEM)
+        .map_err(|e| format!("Failed to load job settings: {}", e))
+}
+
+// ============================================================================
+// Maintenance Commands
+// ============================================================================
+
+/// Start a background maintenance run over all sessions (no-op if one is active)
+///
+/// Runs as a `maintenance` job (see `jobs`), skipping the session being
+/// recorded and pausing while recording. Progress is emitted as
+/// `maintenance_progress` events. Returns whether a run started.
+pub(crate) fn start_background_maintenance(app: &AppHandle) -> Result<bool, String> {
+    let state = app.state::<AppState>();
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+    if state.maintenance.is_running() {
+        return Ok(false);
+    }
+
+    let jobs =
+        crate::maintenance::plan(storage.recordings_dir(), state.get_session_id().as_deref())
+            .map_err(|e| format!("Failed to scan sessions: {}", e))?;
+    if jobs.is_empty() {
+        return Ok(false);
+    }
+    log_info_details!(
+        "commands::maintenance",
+        "maintenance_started",
+        json!({ "jobs": jobs.len() })
+    );
+
+    if !state.maintenance.try_begin(jobs.len()) {
+        return Ok(false);
+    }
+    let maintenance = Arc::clone(&state.maintenance);
+    let progress_app = app.clone();
+    state.jobs.spawn(
+        crate::jobs::JobCategory::Maintenance,
+        "Rebuild session waveforms/activity",
+        move |ctx| {
+            let total = jobs.len().max(1) as f32;
+            maintenance.run(
+                jobs,
+                || ctx.should_pause(),
+                || ctx.is_cancelled(),
+                |status| {
+                    ctx.set_progress((status.completed + status.failed) as f32 / total);
+                    if status.phase == crate::maintenance::MaintenancePhase::Idle {
+                        log_info_details!(
+                            "commands::maintenance",
+                            "maintenance_finished",
+                            json!({
+                                "completed": status.completed,
+                                "failed": status.failed,
+                                "last_error": status.last_error
+                            })
+                        );
+                    }
+                    let _ = progress_app.emit("maintenance_progress", status);
+                },
+            );
+            Ok(())
+        },
+    );
+    Ok(true)
+}
+
+/// Get progress of the current (or last) maintenance run
+#[tauri::command]
+pub fn get_maintenance_status(state: State<'_, AppState>) -> crate::maintenance::MaintenanceStatus {
+    state.maintenance.status()
+}
+
+/// Manually trigger a maintenance run
+#[tauri::command]
+pub fn run_maintenance(app: AppHandle) -> Result<bool, String> {
+    start_background_maintenance(&app)
+}
+
+// ============================================================================
+// Chapter Commands
+// ============================================================================
+
+/// Write chapter files (`audio.cue`, `chapters.ffmetadata`) for a finished session
+///
+/// Chapters come from agenda item start times, plus session markers when
+/// `include_markers` is set. The session audio itself is left untouched;
+/// M4A files with chapters are muxed with FFmpeg (see `chapters`).
+#[tauri::command]
+pub fn export_session_chapters(
+    state: State<'_, AppState>,
+    session_id: String,
+    include_markers: Option<bool>,
+) -> Result<Vec<crate::chapters::Chapter>, String> {
+    crate::session_id::validate_session_id(&session_id).map_err(|e| e.to_string())?;
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+    let loaded = storage
+        .load_session(&session_id)
+        .map_err(|e| format!("Failed to load session {}: {}", session_id, e))?;
+    let session_dir = storage.get_session_dir(&session_id);
+
+    let agenda = crate::agenda::load_agenda(&session_dir)
+        .map_err(|e| format!("Failed to load agenda: {}", e))?;
+    let markers = crate::markers::load_markers(&session_dir)
+        .map_err(|e| format!("Failed to load markers: {}", e))?;
+
+    let chapters = crate::chapters::build_chapters(
+        &agenda,
+        &markers,
+        include_markers.unwrap_or(false),
+        loaded.metadata.duration_seconds * 1000,
+    );
+    if chapters.is_empty() {
+        return Err("No agenda items or markers to build chapters from".to_string());
+    }
+    crate::chapters::write_chapter_files(&session_dir, &session_id, &chapters)
+        .map_err(|e| format!("Failed to write chapters: {}", e))?;
+
+    log_info_details!(
+        "commands::chapters",
+        "chapters_exported",
+        json!({
+            "session": session_id,
+            "chapter_count": chapters.len()
+        })
+    );
+    Ok(chapters)
+}
+
+// ============================================================================
+// Session Archive Commands
+// ============================================================================
+
+/// Bundle a session into a zip archive for sharing or backup
+///
+/// `output_path` defaults to `archives/<session_id>.zip` in the app data
+/// directory. With `flac_audio`, audio is stored as lossless FLAC.
+#[tauri::command]
+pub async fn archive_session(
+    state: State<'_, AppState>,
+    session_id: String,
+    options: Option<crate::session_archive::ArchiveOptions>,
+    output_path: Option<String>,
+) -> Result<crate::session_archive::WrittenArchive, String> {
+    crate::session_id::validate_session_id(&session_id).map_err(|e| e.to_string())?;
+    if state.get_session(&session_id).is_some() {
+        return Err(format!("Session is still recording: {}", session_id));
+    }
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+
+    let output = output_path
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|| {
+            crate::session_archive::default_archive_path(storage.app_data_dir(), &session_id)
+        });
+    let session_dir = storage.get_session_dir(&session_id);
+    let options = options.unwrap_or_default();
+    let id = session_id.clone();
+    let written = tokio::task::spawn_blocking(move || {
+        crate::session_archive::create_archive(&session_dir, &id, &output, &options)
+    })
+    .await
+    .map_err(|e| format!("Archive task failed: {}", e))?
+    .map_err(|e| format!("Failed to archive session {}: {:#}", session_id, e))?;
+
+    log_info_details!(
+        "commands::archive",
+        "session_archived",
+        json!({
+            "session": session_id,
+            "path": written.path,
+            "files": written.files,
+            "zip_bytes": written.zip_bytes
+        })
+    );
+    Ok(written)
+}
+
+/// Import a session archive into the recordings directory
+///
+/// Fails if a session with the same ID already exists.
+#[tauri::command]
+pub async fn import_archive(
+    state: State<'_, AppState>,
+    archive_path: String,
+) -> Result<crate::session_archive::ImportedArchive, String> {
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+
+    let path = std::path::PathBuf::from(&archive_path);
+    let imported = tokio::task::spawn_blocking(move || {
+        crate::session_archive::import_archive(
+            &path,
+            storage.recordings_dir(),
+            storage.encryption(),
+        )
+    })
+    .await
+    .map_err(|e| format!("Import task failed: {}", e))?
+    .map_err(|e| format!("Failed to import {}: {:#}", archive_path, e))?;
+
+    log_info_details!(
+        "commands::archive",
+        "archive_imported",
+        json!({
+            "session": imported.session_id,
+            "files": imported.files,
+            "decoded_samples": imported.decoded_samples
+        })
+    );
+    Ok(imported)
+}
+
+// ============================================================================
+// Markdown Export Commands
+// ============================================================================
+
+/// Export a session's metadata and final segments as `transcript.md`
+///
+/// Returns the output path.
+#[tauri::command]
+pub fn export_session_markdown(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<String, String> {
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+    let furigana = settings_file::load(storage.app_data_dir(), crate::furigana::SETTINGS_STEM)
+        .and_then(|settings| crate::furigana::Furigana::from_settings(&settings))
+        .map_err(|e| format!("Failed to load furigana dictionary: {:#}", e))?;
+
+    let path = storage
+        .export_session_markdown(&session_id, furigana.as_ref())
+        .map_err(|e| format!("Failed to export session {}: {}", session_id, e))?;
+
+    log_info_details!(
+        "commands::export",
+        "markdown_exported",
+        json!({ "session": session_id, "furigana": furigana.is_some() })
+    );
+    Ok(path.display().to_string())
+}
+
+/// Export the final segments side by side with a translated transcript
+/// version as `transcript.bilingual.md`
+///
+/// `translation_version` names the `transcription.<version>.jsonl` holding
+/// the translation. Furigana (when enabled) only applies to the original.
+/// Returns the output path.
+#[tauri::command]
+pub fn export_session_bilingual_markdown(
+    state: State<'_, AppState>,
+    session_id: String,
+    translation_version: String,
+) -> Result<String, String> {
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+    let furigana = settings_file::load(storage.app_data_dir(), crate::furigana::SETTINGS_STEM)
+        .and_then(|settings| crate::furigana::Furigana::from_settings(&settings))
+        .map_err(|e| format!("Failed to load furigana dictionary: {:#}", e))?;
+
+    let path = storage
+        .export_session_bilingual_markdown(&session_id, &translation_version, furigana.as_ref())
+        .map_err(|e| format!("Failed to export session {}: {}", session_id, e))?;
+
+    log_info_details!(
+        "commands::export",
+        "bilingual_markdown_exported",
+        json!({
+            "session": session_id,
+            "translation_version": translation_version,
+            "furigana": furigana.is_some(),
+        })
+    );
+    Ok(path.display().to_string())
+}
+
+/// Export the bilingual table of `export_session_bilingual_markdown` as
+/// `transcript.bilingual.docx`
+///
+/// Returns the output path.
+#[tauri::command]
+pub fn export_session_bilingual_docx(
+    state: State<'_, AppState>,
+    session_id: String,
+    translation_version: String,
+) -> Result<String, String> {
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+    let furigana = settings_file::load(storage.app_data_dir(), crate::furigana::SETTINGS_STEM)
+        .and_then(|settings| crate::furigana::Furigana::from_settings(&settings))
+        .map_err(|e| format!("Failed to load furigana dictionary: {:#}", e))?;
+
+    let path = storage
+        .export_session_bilingual_docx(&session_id, &translation_version, furigana.as_ref())
+        .map_err(|e| format!("Failed to export session {}: {}", session_id, e))?;
+
+    log_info_details!(
+        "commands::export",
+        "bilingual_docx_exported",
+        json!({
+            "session": session_id,
+            "translation_version": translation_version,
+            "furigana": furigana.is_some(),
+        })
+    );
+    Ok(path.display().to_string())
+}
+
+/// Export the bilingual table of `export_session_bilingual_markdown` as
+/// `transcript.bilingual.html`
+///
+/// Returns the output path.
+#[tauri::command]
+pub fn export_session_bilingual_html(
+    state: State<'_, AppState>,
+    session_id: String,
+    translation_version: String,
+) -> Result<String, String> {
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+    let furigana = settings_file::load(storage.app_data_dir(), crate::furigana::SETTINGS_STEM)
+        .and_then(|settings| crate::furigana::Furigana::from_settings(&settings))
+        .map_err(|e| format!("Failed to load furigana dictionary: {:#}", e))?;
+
+    let path = storage
+        .export_session_bilingual_html(&session_id, &translation_version, furigana.as_ref())
+        .map_err(|e| format!("Failed to export session {}: {}", session_id, e))?;
+
+    log_info_details!(
+        "commands::export",
+        "bilingual_html_exported",
+        json!({
+            "session": session_id,
+            "translation_version": translation_version,
+            "furigana": furigana.is_some(),
+        })
+    );
+    Ok(path.display().to_string())
+}
+
+/// Export a session's metadata and final segments as plain `transcript.txt`
+///
+/// Returns the output path.
+#[tauri::command]
+pub fn export_session_text(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<String, String> {
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+    let furigana = settings_file::load(storage.app_data_dir(), crate::furigana::SETTINGS_STEM)
+        .and_then(|settings| crate::furigana::Furigana::from_settings(&settings))
+        .map_err(|e| format!("Failed to load furigana dictionary: {:#}", e))?;
+
+    let path = storage
+        .export_session_text(&session_id, furigana.as_ref())
+        .map_err(|e| format!("Failed to export session {}: {}", session_id, e))?;
+
+    log_info_details!(
+        "commands::export",
+        "text_exported",
+        json!({ "session": session_id, "furigana": furigana.is_some() })
+    );
+    Ok(path.display().to_string())
+}
+
+/// Export a session's metadata and final segments as `transcript.docx`
+///
+/// Returns the output path.
+#[tauri::command]
+pub fn export_session_docx(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<String, String> {
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+    let furigana = settings_file::load(storage.app_data_dir(), crate::furigana::SETTINGS_STEM)
+        .and_then(|settings| crate::furigana::Furigana::from_settings(&settings))
+        .map_err(|e| format!("Failed to load furigana dictionary: {:#}", e))?;
+
+    let path = storage
+        .export_session_docx(&session_id, furigana.as_ref())
+        .map_err(|e| format!("Failed to export session {}: {}", session_id, e))?;
+
+    log_info_details!(
+        "commands::export",
+        "docx_exported",
+        json!({ "session": session_id, "furigana": furigana.is_some() })
+    );
+    Ok(path.display().to_string())
+}
+
+/// Export a session's metadata and final segments as `transcript.html`
+/// (readings of rare kanji as `<ruby>` when furigana is enabled)
+///
+/// Returns the output path.
+#[tauri::command]
+pub fn export_session_html(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<String, String> {
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+    let furigana = settings_file::load(storage.app_data_dir(), crate::furigana::SETTINGS_STEM)
+        .and_then(|settings| crate::furigana::Furigana::from_settings(&settings))
+        .map_err(|e| format!("Failed to load furigana dictionary: {:#}", e))?;
+
+    let path = storage
+        .export_session_html(&session_id, furigana.as_ref())
+        .map_err(|e| format!("Failed to export session {}: {}", session_id, e))?;
+
+    log_info_details!(
+        "commands::export",
+        "html_exported",
+        json!({ "session": session_id, "furigana": furigana.is_some() })
+    );
+    Ok(path.display().to_string())
+}
+
+/// Export a session with long silent stretches cut out, for sharing
+///
+/// Writes `audio.trimmed.flac` and `transcript.trimmed.md` (timestamps on
+/// the trimmed timeline, `[silence 4:32 omitted]` at every cut) to the
+/// session directory. `options` defaults to cutting silences of 30s or more
+/// down to a 1s pause.
+#[tauri::command]
+pub async fn export_session_trimmed(
+    state: State<'_, AppState>,
+    session_id: String,
+    options: Option<crate::silence_trim::SilenceTrimOptions>,
+) -> Result<crate::silence_trim::TrimmedExport, String> {
+    if state.get_session(&session_id).is_some() {
+        return Err(format!("Session is still recording: {}", session_id));
+    }
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+    let furigana = settings_file::load(storage.app_data_dir(), crate::furigana::SETTINGS_STEM)
+        .and_then(|settings| crate::furigana::Furigana::from_settings(&settings))
+        .map_err(|e| format!("Failed to load furigana dictionary: {:#}", e))?;
+
+    let options = options.unwrap_or_default();
+    let id = session_id.clone();
+    let export = tokio::task::spawn_blocking(move || {
+        crate::silence_trim::export_session(&storage, &id, &options, furigana.as_ref())
+    })
+    .await
+    .map_err(|e| format!("Export task failed: {}", e))?
+    .map_err(|e| format!("Failed to export session {}: {:#}", session_id, e))?;
+
+    log_info_details!(
+        "commands::export",
+        "trimmed_exported",
+        json!({
+            "session": session_id,
+            "omitted": export.omitted.len(),
+            "original_ms": export.original_duration_ms,
+            "trimmed_ms": export.trimmed_duration_ms
+        })
+    );
+    Ok(export)
+}
+
+/// Write a redacted copy of a session for sharing outside the team
+///
+/// `ranges` (session-relative ms) are overwritten in `audio.redacted.flac`
+/// with a bleep or silence, and the transcript segments spoken in them are
+/// flagged or removed in `transcript.redacted.md`. The original audio and
+/// transcript are not modified.
+#[tauri::command]
+pub async fn redact_audio(
+    state: State<'_, AppState>,
+    session_id: String,
+    ranges: Vec<crate::redaction::RedactionRange>,
+    options: Option<crate::redaction::RedactionOptions>,
+) -> Result<crate::redaction::RedactedExport, String> {
+    if state.get_session(&session_id).is_some() {
+        return Err(format!("Session is still recording: {}", session_id));
+    }
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+    let furigana = settings_file::load(storage.app_data_dir(), crate::furigana::SETTINGS_STEM)
+        .and_then(|settings| crate::furigana::Furigana::from_settings(&settings))
+        .map_err(|e| format!("Failed to load furigana dictionary: {:#}", e))?;
+
+    let options = options.unwrap_or_default();
+    let id = session_id.clone();
+    let range_count = ranges.len();
+    let export = tokio::task::spawn_blocking(move || {
+        crate::redaction::redact_session(&storage, &id, &ranges, &options, furigana.as_ref())
+    })
+    .await
+    .map_err(|e| format!("Redaction task failed: {}", e))?
+    .map_err(|e| format!("Failed to redact session {}: {:#}", session_id, e))?;
+
+    log_info_details!(
+        "commands::export",
+        "session_redacted",
+        json!({
+            "session": session_id,
+            "ranges": range_count,
+            "redacted_ms": export.redacted_ms,
+            "redacted_segments": export.redacted_segments
+        })
+    );
+    Ok(export)
+}
+
+/// Result of `batch_export_sessions` and `batch_retranscribe_sessions`
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct BatchStarted {
+    pub job_id: String,
+    pub session_ids: Vec<String>,
+}
+
+/// Export a selection of sessions in one background job
+///
+/// `selection` names sessions by ID and/or filters them by start date and
+/// tag (see `batch_export`). Runs as an `export` job; aggregate progress is
+/// emitted as `batch_export_progress` after each session and the outcome of
+/// every session as `batch_export_finished`. Returns the job ID and the
+/// selected sessions.
+#[tauri::command]
+pub async fn batch_export_sessions(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    selection: crate::batch_export::SessionSelection,
+    format: crate::batch_export::BatchExportFormat,
+) -> Result<BatchStarted, String> {
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+    let furigana = settings_file::load(storage.app_data_dir(), crate::furigana::SETTINGS_STEM)
+        .and_then(|settings| crate::furigana::Furigana::from_settings(&settings))
+        .map_err(|e| format!("Failed to load furigana dictionary: {:#}", e))?;
+
+    let list_storage = storage.clone();
+    let sessions = tokio::task::spawn_blocking(move || list_storage.list_sessions())
+        .await
+        .map_err(|e| format!("Session list task failed: {}", e))?
+        .map_err(|e| format!("Failed to list sessions: {}", e))?;
+    let session_ids =
+        crate::batch_export::select_sessions(&sessions, &selection).map_err(|e| e.to_string())?;
+    if session_ids.is_empty() {
+        return Err("No sessions match the selection".to_string());
+    }
+
+    let job_sessions = session_ids.clone();
+    let job_app = app.clone();
+    let job_id = state.jobs.spawn(
+        crate::jobs::JobCategory::Export,
+        &format!("Export {} sessions ({:?})", session_ids.len(), format),
+        move |ctx| {
+            let report = crate::batch_export::run_batch(
+                &job_sessions,
+                || ctx.checkpoint(),
+                |session_id| {
+                    crate::batch_export::export_session(
+                        &storage,
+                        session_id,
+                        format,
+                        furigana.as_ref(),
+                    )
+                },
+                |progress, item| {
+                    ctx.set_progress(progress.fraction());
+                    let _ = job_app.emit(
+                        "batch_export_progress",
+                        json!({ "job_id": ctx.id(), "progress": progress, "item": item }),
+                    );
+                },
+            )?;
+            log_info_details!(
+                "commands::export",
+                "batch_export_finished",
+                json!({
+                    "job": ctx.id(),
+                    "format": format,
+                    "completed": report.progress.completed,
+                    "failed": report.progress.failed
+                })
+            );
+            let _ = job_app.emit(
+                "batch_export_finished",
+                json!({ "job_id": ctx.id(), "report": report }),
+            );
+            Ok(())
+        },
+    );
+
+    log_info_details!(
+        "commands::export",
+        "batch_export_queued",
+        json!({ "job": job_id, "format": format, "sessions": session_ids.len() })
+    );
+    Ok(BatchStarted {
+        job_id,
+        session_ids,
+    })
+}
+
+/// Re-transcribe a selection of sessions in one background job
+///
+/// Sessions are selected as in `batch_export_sessions`; each one's audio is
+/// transcribed again by a sidecar of the job's own and saved as transcript
+/// `version` (see `retranscription`). Runs as a `transcription` job, paused
+/// while recording; aggregate progress is emitted as
+/// `batch_retranscription_progress` after each session and the outcome of
+/// every session as `batch_retranscription_finished`.
+#[tauri::command]
+pub async fn batch_retranscribe_sessions(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    selection: crate::batch_export::SessionSelection,
+    version: String,
+) -> Result<BatchStarted, String> {
+    crate::retranscription::validate_version(&version).map_err(|e| e.to_string())?;
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+
+    // Same provider as the recording sidecar (local only without an API key)
+    let machine_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    let mut cloud_stt: crate::cloud_stt::CloudSttSettings =
+        settings_file::load(&machine_dir, crate::cloud_stt::SETTINGS_STEM)
+            .map_err(|e| format!("Failed to load cloud STT settings: {}", e))?;
+    let api_key = if cloud_stt.is_enabled() {
+        crate::cloud_stt::load_api_key(cloud_stt.provider).map_err(|e| e.to_string())?
+    } else {
+        None
+    };
+    if api_key.is_none() {
+        cloud_stt = Default::default();
+    }
+
+    let list_storage = storage.clone();
+    let sessions = tokio::task::spawn_blocking(move || list_storage.list_sessions())
+        .await
+        .map_err(|e| format!("Session list task failed: {}", e))?
+        .map_err(|e| format!("Failed to list sessions: {}", e))?;
+    let session_ids =
+        crate::batch_export::select_sessions(&sessions, &selection).map_err(|e| e.to_string())?;
+    if session_ids.is_empty() {
+        return Err("No sessions match the selection".to_string());
+    }
+
+    let job_sessions = session_ids.clone();
+    let job_version = version.clone();
+    let job_app = app.clone();
+    let job_id = state.jobs.spawn(
+        crate::jobs::JobCategory::Transcription,
+        &format!("Re-transcribe {} sessions ({})", session_ids.len(), version),
+        move |ctx| {
+            // Jobs run on threads of their own; the sidecar IO runs on the runtime
+            let (mut sidecar, mut transcriber) = tauri::async_runtime::block_on(
+                crate::retranscription::start_sidecar(cloud_stt, api_key),
+            )?;
+            let report = crate::batch_export::run_batch(
+                &job_sessions,
+                || ctx.checkpoint(),
+                |session_id| {
+                    tauri::async_runtime::block_on(crate::retranscription::retranscribe_session(
+                        &mut transcriber,
+                        &storage,
+                        session_id,
+                        &job_version,
+                        || ctx.checkpoint(),
+                    ))
+                    .map(|path| vec![path])
+                },
+                |progress, item| {
+                    ctx.set_progress(progress.fraction());
+                    let _ = job_app.emit(
+                        "batch_retranscription_progress",
+                        json!({ "job_id": ctx.id(), "progress": progress, "item": item }),
+                    );
+                },
+            );
+            // Closing stdin ends the sidecar
+            drop(transcriber);
+            let _ = tauri::async_runtime::block_on(sidecar.stop());
+            let report = report?;
+            log_info_details!(
+                "commands::retranscription",
+                "batch_retranscription_finished",
+                json!({
+                    "job": ctx.id(),
+                    "version": job_version,
+                    "completed": report.progress.completed,
+                    "failed": report.progress.failed
+                })
+            );
+            let _ = job_app.emit(
+                "batch_retranscription_finished",
+                json!({ "job_id": ctx.id(), "report": report }),
+            );
+            Ok(())
+        },
+    );
+
+    log_info_details!(
+        "commands::retranscription",
+        "batch_retranscription_queued",
+        json!({ "job": job_id, "version": version, "sessions": session_ids.len() })
+    );
+    Ok(BatchStarted {
+        job_id,
+        session_ids,
+    })
+}
+
+/// Save the furigana settings used by exports
+///
+/// The reading dictionary is loaded once to reject unreadable files.
+#[tauri::command]
+pub async fn save_furigana_settings(
+    app: AppHandle,
+    settings: crate::furigana::FuriganaSettings,
+) -> Result<(), String> {
+    settings.validate().map_err(|e| e.to_string())?;
+    let entries = crate::furigana::Furigana::from_settings(&settings)
+        .map_err(|e| format!("Failed to load furigana dictionary: {:#}", e))?
+        .map_or(0, |furigana| furigana.len());
+
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::save(&app_data_dir, crate::furigana::SETTINGS_STEM, &settings)
+        .map_err(|e| format!("Failed to save furigana settings: {}", e))?;
+
+    log_info_details!(
+        "commands::settings",
+        "furigana_settings_saved",
+        json!({ "enabled": settings.enabled, "dictionary_entries": entries })
+    );
+    Ok(())
+}
+
+/// Load the furigana settings from disk
+#[tauri::command]
+pub async fn load_furigana_settings(
+    app: AppHandle,
+) -> Result<crate::furigana::FuriganaSettings, String> {
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::load(&app_data_dir, crate::furigana::SETTINGS_STEM)
+        .map_err(|e| format!("Failed to load furigana settings: {}", e))
+}
+
+/// Search a session's final transcript segments
+///
+/// Returns matching segments with session-relative millisecond offsets so
+/// the UI can jump playback to them. Works during recording as well.
+#[tauri::command]
+pub fn search_in_session(
+    state: State<'_, AppState>,
+    session_id: String,
+    query: String,
+) -> Result<Vec<crate::search::SearchMatch>, String> {
+    crate::session_id::validate_session_id(&session_id).map_err(|e| e.to_string())?;
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+
+    let events = storage
+        .load_transcript(&session_id)
+        .map_err(|e| format!("Failed to load transcript {}: {}", session_id, e))?;
+    Ok(crate::search::search_transcript(&events, &query))
+}
+
+// ============================================================================
+// Summary Commands
+// ============================================================================
+
+static SUMMARY_CLIENT: Lazy<reqwest::Client> = Lazy::new(|| {
+    reqwest::Client::builder()
+        .connect_timeout(std::time::Duration::from_secs(5))
+        .build()
+        .unwrap_or_default()
+});
+
+/// Generate a summary of a session, streaming it as it is produced
+///
+/// Emits `summary_partial` (Tauri event + WebSocket) for each coalesced delta
+/// and `summary_complete` at the end. The finished summary is saved to
+/// `summary.md` in the session directory and returned.
+#[tauri::command]
+pub async fn generate_session_summary(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<String, String> {
+    crate::session_id::validate_session_id(&session_id).map_err(|e| e.to_string())?;
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+    let settings = settings_file::load(storage.app_data_dir(), crate::summary::SETTINGS_STEM)
+        .map_err(|e| format!("Failed to load summary settings: {}", e))?;
+
+    let transcript = storage
+        .load_transcript(&session_id)
+        .map_err(|e| format!("Failed to load transcript: {}", e))?;
+    let transcript = crate::summary::format_transcript(&transcript);
+    if transcript.is_empty() {
+        return Err("Session has no final transcript to summarize".to_string());
+    }
+    let prompt = crate::summary::build_prompt(&settings, &transcript);
+
+    let websocket_server = state.websocket_server.lock().unwrap().clone();
+
+    log_info_details!(
+        "commands::summary",
+        "summary_started",
+        json!({
+            "session": session_id,
+            "model": settings.model,
+            "transcript_chars": transcript.chars().count()
+        })
+    );
+
+    // Deltas are forwarded to a task so WebSocket broadcast never blocks the stream
+    let (delta_tx, mut delta_rx) = tokio::sync::mpsc::unbounded_channel::<String>();
+    let forward_app = app.clone();
+    let forward_session = session_id.clone();
+    let forward_ws = websocket_server.clone();
+    let forwarder = tokio::spawn(async move {
+        let mut seq = 0u64;
+        while let Some(delta) = delta_rx.recv().await {
+            let timestamp = now_epoch_ms();
+            let _ = forward_app.emit(
+                "summary_partial",
+                json!({
+                    "session_id": forward_session,
+                    "seq": seq,
+                    "delta": delta
+                }),
+            );
+            if let Some(ws) = &forward_ws {
+                let _ = ws
+                    .lock()
+                    .await
+                    .broadcast(WebSocketMessage::SummaryPartial {
+                        message_id: crate::message_id::next_id(
+                            crate::message_id::WEBSOCKET,
+                            &forward_session,
+                        ),
+                        session_id: forward_session.clone(),
+                        seq,
+                        delta,
+                        timestamp,
+                    })
+                    .await;
+            }
+            seq += 1;
+        }
+        seq
+    });
+
+    let result = crate::summary::stream_completion(&SUMMARY_CLIENT, &settings, &prompt, |delta| {
+        let _ = delta_tx.send(delta.to_string());
+    })
+    .await;
+    drop(delta_tx);
+    let partial_count = forwarder.await.unwrap_or(0);
+
+    let text = result.map_err(|e| {
+        log_error_details!(
+            "commands::summary",
+            "summary_failed",
+            json!({ "session": session_id, "error": format!("{:#}", e) })
+        );
+        format!("Summary generation failed: {:#}", e)
+    })?;
+
+    if let Err(e) = crate::summary::save_summary(&storage.get_session_dir(&session_id), &text) {
+        log_warn_details!(
+            "commands::summary",
+            "summary_save_failed",
+            json!({ "session": session_id, "error": e.to_string() })
+        );
+    }
+
+    let timestamp = now_epoch_ms();
+    let _ = app.emit(
+        "summary_complete",
+        json!({ "session_id": session_id, "text": text }),
+    );
+    if let Some(ws) = &websocket_server {
+        let _ = ws
+            .lock()
+            .await
+            .broadcast(WebSocketMessage::SummaryComplete {
+                message_id: crate::message_id::next_id(crate::message_id::WEBSOCKET, &session_id),
+                session_id: session_id.clone(),
+                text: text.clone(),
+                timestamp,
+            })
+            .await;
+    }
+
+    log_info_details!(
+        "commands::summary",
+        "summary_completed",
+        json!({
+            "session": session_id,
+            "partial_count": partial_count,
+            "summary_chars": text.chars().count()
+        })
+    );
+    Ok(text)
+}
+
+/// Get the rolling summary of the active (or last) session
+///
+/// Returns None when live summary is disabled. Lets a late joiner catch up.
+#[tauri::command]
+pub fn get_live_summary(state: State<'_, AppState>) -> Option<crate::summary::RollingSummary> {
+    state.live_summary.lock().unwrap().clone()
+}
+
+/// Save summarization backend settings
+#[tauri::command]
+pub async fn save_summary_settings(
+    app: AppHandle,
+    settings: crate::summary::SummarySettings,
+) -> Result<(), String> {
+    settings
+        .validate()
+        .map_err(|e| format!("Invalid summary settings: {}", e))?;
+
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::save(&app_data_dir, crate::summary::SETTINGS_STEM, &settings)
+        .map_err(|e| format!("Failed to save summary settings: {}", e))
+}
+
+/// Load summarization backend settings from disk
+#[tauri::command]
+pub async fn load_summary_settings(
+    app: AppHandle,
+) -> Result<crate::summary::SummarySettings, String> {
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::load(&app_data_dir, crate::summary::SETTINGS_STEM)
+        .map_err(|e| format!("Failed to load summary settings: {}", e))
+}
+
+// ============================================================================
+// Session Timeline Commands
+// ============================================================================
+
+/// Record a UI event (slide changed, screen-share started, ...) at the current session time
+///
+/// Stored in `timeline.jsonl` next to the transcript.
+#[tauri::command]
+pub fn log_session_event(
+    state: State<'_, AppState>,
+    kind: String,
+    data: Option<serde_json::Value>,
+) -> Result<crate::timeline::TimelineEvent, String> {
+    let data = data.unwrap_or(serde_json::Value::Null);
+    crate::timeline::validate_event(&kind, &data).map_err(|e| e.to_string())?;
+
+    let (session_id, at_ms) = match (state.get_session_id(), state.session_elapsed_ms()) {
+        (Some(session_id), Some(at_ms)) => (session_id, at_ms),
+        _ => return Err("Not recording".to_string()),
+    };
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+
+    let event = crate::timeline::TimelineEvent {
+        timestamp_ms: at_ms,
+        kind,
+        data,
+    };
+    crate::timeline::append_event(&storage.get_session_dir(&session_id), &event)
+        .map_err(|e| format!("Failed to record session event: {}", e))?;
+
+    log_debug_details!(
+        "commands::timeline",
+        "event_logged",
+        json!({
+            "session": session_id,
+            "kind": event.kind,
+            "at_ms": at_ms
+        })
+    );
+    Ok(event)
+}
+
+/// Get the UI event timeline of a session
+#[tauri::command]
+pub fn get_session_timeline(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<Vec<crate::timeline::TimelineEvent>, String> {
+    crate::session_id::validate_session_id(&session_id).map_err(|e| e.to_string())?;
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+
+    let session_dir = storage.get_session_dir(&session_id);
+    if !session_dir.exists() {
+        return Err(format!("Session not found: {}", session_id));
+    }
+    crate::timeline::load_timeline(&session_dir)
+        .map_err(|e| format!("Failed to load timeline: {}", e))
+}
+
+// ============================================================================
+// Diagnostic Bundle Commands
+// ============================================================================
+
+/// Plan the diagnostic bundle content (nothing is written yet)
+///
+/// Settings come from every directory of `settings_dirs`: machine-wide
+/// settings under `settings/machine/`, those of a non-default workspace under
+/// `settings/workspace/`.
+fn plan_diagnostic_bundle(
+    state: &AppState,
+    settings_dirs: &[std::path::PathBuf],
+    options: &crate::diagnostics::BundleOptions,
+) -> Result<crate::diagnostics::BundlePlan, String> {
+    use crate::diagnostics::{BundlePlan, EnvironmentSnapshot};
+
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+    let app_data_dir = storage.app_data_dir();
+    let to_string = |e: anyhow::Error| format!("Failed to plan diagnostic bundle: {}", e);
+
+    let mut plan = BundlePlan::new();
+    let mut logs = crate::logger::recent_logs().join("\n");
+    logs.push('\n');
+    plan.add_bytes("logs/recent.jsonl", "Recent log entries", logs.into_bytes());
+    plan.add_json(
+        "environment.json",
+        "OS, CPU, memory, free disk and app version",
+        &EnvironmentSnapshot::collect(app_data_dir),
+    )
+    .map_err(to_string)?;
+    for (dir, prefix) in settings_dirs
+        .iter()
+        .zip(["settings/machine", "settings/workspace"])
+    {
+        plan.add_settings(dir, prefix).map_err(to_string)?;
+    }
+
+    let metrics = json!({
+        "audio_queue": state.audio_queue_snapshot(),
+        "ipc_lanes": state.get_sidecar_stdin().map(|writer| writer.lane_metrics()),
+        "latency": state.latency_snapshot(),
+        "memory": state.memory_snapshot(),
+        "ipc_drift": state.ipc_drift_snapshot(),
+        "ipc_flow": state.ipc_flow_snapshot(),
+        "jobs": state.jobs.jobs(),
+        "maintenance": state.maintenance.status(),
+        "audio_capture": crate::wasapi_capture::last_effective()
+    });
+    plan.add_json(
+        "metrics.json",
+        "Queue, latency, memory, job and audio capture metrics",
+        &metrics,
+    )
+    .map_err(to_string)?;
+
+    let quarantine = state.ipc_quarantine_snapshot();
+    if quarantine.total > 0 {
+        plan.add_json(
+            "ipc/quarantine.json",
+            "Malformed sidecar IPC lines (raw, truncated)",
+            &quarantine,
+        )
+        .map_err(to_string)?;
+    }
+
+    if let Some(interrupted) = state.get_interrupted_recording() {
+        plan.add_json(
+            "crash/interrupted_recording.json",
+            "Last recording interrupted by a crash",
+            &interrupted,
+        )
+        .map_err(to_string)?;
+    }
+
+    if let Some(session_id) = &options.sent_audio_session_id {
+        crate::session_id::validate_session_id(session_id).map_err(|e| e.to_string())?;
+        let dump_path = storage
+            .get_session_dir(session_id)
+            .join("debug")
+            .join("sent_audio.wav");
+        plan.add_file(
+            &format!("audio/{}/sent_audio.wav", session_id),
+            "Audio sent to the sidecar (debug dump)",
+            &dump_path,
+        )
+        .map_err(|e| format!("No sent audio dump for session {}: {}", session_id, e))?;
+    }
+    Ok(plan)
+}
+
+/// Preview the files and size of a diagnostic bundle without writing it
+#[tauri::command]
+pub fn preview_diagnostic_bundle(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    options: Option<crate::diagnostics::BundleOptions>,
+) -> Result<crate::diagnostics::BundlePreview, String> {
+    let plan = plan_diagnostic_bundle(&state, &settings_dirs(&app), &options.unwrap_or_default())?;
+    Ok(plan.preview())
+}
+
+/// Write a diagnostic bundle zip for bug reports
+///
+/// `output_path` defaults to `diagnostics/bundle-<timestamp>.zip` in the app
+/// data directory. Content matches `preview_diagnostic_bundle`.
+#[tauri::command]
+pub async fn generate_diagnostic_bundle(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    options: Option<crate::diagnostics::BundleOptions>,
+    output_path: Option<String>,
+) -> Result<crate::diagnostics::WrittenBundle, String> {
+    let plan = plan_diagnostic_bundle(&state, &settings_dirs(&app), &options.unwrap_or_default())?;
+    let output = match output_path {
+        Some(path) => std::path::PathBuf::from(path),
+        None => {
+            let storage = state
+                .get_storage_service()
+                .ok_or_else(|| "Storage not initialized".to_string())?;
+            storage.app_data_dir().join("diagnostics").join(format!(
+                "bundle-{}.zip",
+                chrono::Local::now().format("%Y%m%d-%H%M%S")
+            ))
+        }
+    };
+
+    let preview = plan.preview();
+    let zip_path = output.clone();
+    let zip_bytes = tokio::task::spawn_blocking(move || plan.write_zip(&zip_path))
+        .await
+        .map_err(|e| format!("Diagnostic bundle task failed: {}", e))?
+        .map_err(|e| format!("Failed to write diagnostic bundle: {}", e))?;
+
+    log_info_details!(
+        "commands::diagnostics",
+        "diagnostic_bundle_written",
+        json!({
+            "path": output.display().to_string(),
+            "entries": preview.entries.len(),
+            "zip_bytes": zip_bytes
+        })
+    );
+    Ok(crate::diagnostics::WrittenBundle {
+        path: output.display().to_string(),
+        zip_bytes,
+        preview,
+    })
+}
+
+// ============================================================================
+// Debug Settings Commands
+// ============================================================================
+
+/// Make debug settings (including the log level) current
+fn apply_debug_settings(
+    app: &AppHandle,
+    settings: &crate::audio_dump::DebugSettings,
+) -> Result<(), String> {
+    app.state::<AppState>().set_debug_settings(settings.clone());
+    Ok(())
+}
+
+/// Save debug settings; the log level applies at once, the sent audio dump
+/// from the next recording
+#[tauri::command]
+pub async fn save_debug_settings(
+    app: AppHandle,
+    settings: crate::audio_dump::DebugSettings,
+) -> Result<(), String> {
+    apply_debug_settings(&app, &settings)?;
+
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::save(&app_data_dir, crate::audio_dump::SETTINGS_STEM, &settings)
+        .map_err(|e| format!("Failed to save debug settings: {}", e))?;
+
+    log_info_details!(
+        "commands::settings",
+        "debug_settings_saved",
+        json!({
+            "dump_sent_audio": settings.dump_sent_audio,
+            "max_dump_mb": settings.max_dump_mb,
+            "log_level": settings.log_level
+        })
+    );
+    Ok(())
+}
+
+/// Load debug settings from disk
+#[tauri::command]
+pub async fn load_debug_settings(
+    app: AppHandle,
+) -> Result<crate::audio_dump::DebugSettings, String> {
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::load(&app_data_dir, crate::audio_dump::SETTINGS_STEM)
+        .map_err(|e| format!("Failed to load debug settings: {}", e))
+}
+
+// ============================================================================
+// IPC Quarantine Commands
+// ============================================================================
+
+/// Get malformed sidecar IPC lines quarantined during the active (or last) session
+#[tauri::command]
+pub fn get_ipc_quarantine(state: State<'_, AppState>) -> crate::ipc_quarantine::QuarantineSnapshot {
+    state.ipc_quarantine_snapshot()
+}
+
+/// Make IPC quarantine settings current
+fn apply_ipc_quarantine_settings(
+    app: &AppHandle,
+    settings: &crate::ipc_quarantine::IpcQuarantineSettings,
+) -> Result<(), String> {
+    app.state::<AppState>()
+        .set_ipc_quarantine_settings(settings.clone());
+    Ok(())
+}
+
+/// Save IPC quarantine settings (consecutive-error threshold); applies immediately
+#[tauri::command]
+pub async fn save_ipc_quarantine_settings(
+    app: AppHandle,
+    settings: crate::ipc_quarantine::IpcQuarantineSettings,
+) -> Result<(), String> {
+    apply_ipc_quarantine_settings(&app, &settings)?;
+
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::save(
+        &app_data_dir,
+        crate::ipc_quarantine::SETTINGS_STEM,
+        &settings,
+    )
+    .map_err(|e| format!("Failed to save IPC quarantine settings: {}", e))?;
+
+    log_info_details!(
+        "commands::settings",
+        "ipc_quarantine_settings_saved",
+        json!({ "max_consecutive_errors": settings.max_consecutive_errors })
+    );
+    Ok(())
+}
+
+/// Load IPC quarantine settings from disk
+#[tauri::command]
+pub async fn load_ipc_quarantine_settings(
+    app: AppHandle,
+) -> Result<crate::ipc_quarantine::IpcQuarantineSettings, String> {
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::load(&app_data_dir, crate::ipc_quarantine::SETTINGS_STEM)
+        .map_err(|e| format!("Failed to load IPC quarantine settings: {}", e))
+}
+
+/// Make WebSocket message size limits current (including on the server)
+async fn apply_websocket_settings(
+    app: &AppHandle,
+    settings: &crate::websocket_limits::WebSocketSettings,
+) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let websocket_server = state.websocket_server.lock().unwrap().clone();
+    if let Some(server) = websocket_server {
+        server.lock().await.set_settings(settings.clone());
+    }
+    state.set_websocket_settings(settings.clone());
+    Ok(())
+}
+
+/// Save WebSocket message size limits; applies to the next broadcast
+#[tauri::command]
+pub async fn save_websocket_settings(
+    app: AppHandle,
+    settings: crate::websocket_limits::WebSocketSettings,
+) -> Result<(), String> {
+    apply_websocket_settings(&app, &settings).await?;
+
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::save(
+        &app_data_dir,
+        crate::websocket_limits::SETTINGS_STEM,
+        &settings,
+    )
+    .map_err(|e| format!("Failed to save WebSocket settings: {}", e))?;
+
+    log_info_details!(
+        "commands::settings",
+        "websocket_settings_saved",
+        json!({
+            "max_message_bytes": settings.max_message_bytes,
+            "max_segment_bytes": settings.max_segment_bytes,
+        })
+    );
+    Ok(())
+}
+
+/// Load WebSocket message size limits from disk
+#[tauri::command]
+pub async fn load_websocket_settings(
+    app: AppHandle,
+) -> Result<crate::websocket_limits::WebSocketSettings, String> {
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::load(&app_data_dir, crate::websocket_limits::SETTINGS_STEM)
+        .map_err(|e| format!("Failed to load WebSocket settings: {}", e))
+}
+
+/// Per-client queue lengths and drop counters of the WebSocket server
+///
+/// Empty before the server has started (and in viewer mode).
+#[tauri::command]
+pub async fn get_websocket_delivery_stats(
+    state: State<'_, AppState>,
+) -> Result<crate::websocket::WebSocketDeliveryStats, String> {
+    let websocket_server = state.websocket_server.lock().unwrap().clone();
+    match websocket_server {
+        Some(server) => Ok(server.lock().await.delivery_stats().await),
+        None => Ok(Default::default()),
+    }
+}
+
+/// Connected WebSocket clients with their subscriptions and delivery
+/// counters, to debug a client that receives nothing
+///
+/// Empty before the server has started (and in viewer mode).
+#[tauri::command]
+pub async fn get_ws_clients(
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::websocket::WsClientInfo>, String> {
+    let websocket_server = state.websocket_server.lock().unwrap().clone();
+    match websocket_server {
+        Some(server) => Ok(server.lock().await.clients().await),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Port this instance's WebSocket server listens on
+///
+/// None until the server has started, in viewer mode, or when every port of
+/// the configured range was taken.
+#[tauri::command]
+pub fn get_websocket_port(
+    state: State<'_, AppState>,
+) -> Option<crate::websocket_port::PortDiscovery> {
+    state.get_websocket_port()
+}
+
+/// Save the WebSocket listening port settings (applied at the next start)
+#[tauri::command]
+pub async fn save_websocket_port_settings(
+    app: AppHandle,
+    settings: crate::websocket_port::WebSocketPortSettings,
+) -> Result<(), String> {
+    settings
+        .validate()
+        .map_err(|e| format!("Invalid WebSocket port settings: {}", e))?;
+
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::save(
+        &app_data_dir,
+        crate::websocket_port::SETTINGS_STEM,
+        &settings,
+    )
+    .map_err(|e| format!("Failed to save WebSocket port settings: {}", e))?;
+
+    log_info_details!(
+        "commands::settings",
+        "websocket_port_settings_saved",
+        json!({
+            "port": settings.port,
+            "fallback_ports": settings.fallback_ports,
+        })
+    );
+    Ok(())
+}
+
+/// Load the WebSocket listening port settings from disk
+#[tauri::command]
+pub async fn load_websocket_port_settings(
+    app: AppHandle,
+) -> Result<crate::websocket_port::WebSocketPortSettings, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::load(&app_data_dir, crate::websocket_port::SETTINGS_STEM)
+        .map_err(|e| format!("Failed to load WebSocket port settings: {}", e))
+}
+
+/// Start or stop the mDNS advertisement to match `settings`
+///
+/// Nothing is advertised until the WebSocket server has a port.
+pub(crate) fn apply_mdns_settings(
+    app: &AppHandle,
+    settings: &crate::websocket_mdns::MdnsSettings,
+) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    if let Some(advertiser) = state.take_mdns() {
+        advertiser.stop();
+        log_info!("commands::mdns", "stopped", "");
+    }
+    if !settings.enabled {
+        return Ok(());
+    }
+    let Some(discovery) = state.get_websocket_port() else {
+        return Ok(());
+    };
+
+    let advertiser = crate::websocket_mdns::MdnsAdvertiser::start(discovery.port)
+        .map_err(|e| format!("Failed to start mDNS advertisement: {:#}", e))?;
+    log_info!(
+        "commands::mdns",
+        "started",
+        format!("port={}", advertiser.port())
+    );
+    state.set_mdns(advertiser);
+    Ok(())
+}
+
+/// Save mDNS settings and start or stop the advertisement to match
+#[tauri::command]
+pub async fn save_mdns_settings(
+    app: AppHandle,
+    settings: crate::websocket_mdns::MdnsSettings,
+) -> Result<(), String> {
+    apply_mdns_settings(&app, &settings)?;
+
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::save(
+        &app_data_dir,
+        crate::websocket_mdns::SETTINGS_STEM,
+        &settings,
+    )
+    .map_err(|e| format!("Failed to save mDNS settings: {}", e))?;
+
+    log_info_details!(
+        "commands::settings",
+        "mdns_settings_saved",
+        json!({ "enabled": settings.enabled })
+    );
+    Ok(())
+}
+
+/// Load mDNS settings from disk
+#[tauri::command]
+pub async fn load_mdns_settings(
+    app: AppHandle,
+) -> Result<crate::websocket_mdns::MdnsSettings, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::load(&app_data_dir, crate::websocket_mdns::SETTINGS_STEM)
+        .map_err(|e| format!("Failed to load mDNS settings: {}", e))
+}
+
+// ============================================================================
+// Remote Control Commands
+// ============================================================================
+
+/// Run a recording command received over WebSocket (see `websocket_control`)
+///
+/// Takes the same path as the `start_recording` / `stop_recording` commands
+/// and tells the desktop window through the `remote_control` event.
+pub(crate) async fn run_remote_control(
+    app: &AppHandle,
+    command: crate::websocket_control::ControlCommand,
+) -> Result<crate::websocket_control::RecordingStatus, String> {
+    use crate::websocket_control::ControlCommand;
+
+    let state = app.state::<AppState>();
+    match &command {
+        ControlCommand::StartRecording { device_id } => {
+            // Next to any recording session (capacity and device checked on start)
+            set_session_audio_format(&state, None)?;
+            state.set_multi_input_enabled(false);
+            start_recording_internal(app, &state, device_id.clone()).await?;
+        }
+        ControlCommand::StopRecording => {
+            let Some(session_id) = state.get_session_id() else {
+                return Err("Not recording".to_string());
+            };
+            stop_recording_internal(&state, &session_id).await?;
+        }
+        ControlCommand::GetStatus => return Ok(remote_control_status(&state)),
+    }
+
+    log_info_details!(
+        "commands::remote_control",
+        "command_applied",
+        json!({ "command": command })
+    );
+    let status = remote_control_status(&state);
+    if let Err(e) = app.emit("remote_control", &status) {
+        log_warn!(
+            "commands::remote_control",
+            "emit_failed",
+            format!("{:?}", e)
+        );
+    }
+    Ok(status)
+}
+
+fn remote_control_status(state: &AppState) -> crate::websocket_control::RecordingStatus {
+    let recording = state.is_recording();
+    crate::websocket_control::RecordingStatus {
+        recording,
+        session_id: state.get_session_id().filter(|_| recording),
+        device_id: state.get_selected_device_id().filter(|_| recording),
+        docs_budget: recording.then(|| state.docs_budget_status()),
+    }
+}
+
+/// Validate remote control settings and apply them to the WebSocket server
+async fn apply_remote_control_settings(
+    app: &AppHandle,
+    settings: &crate::websocket_control::RemoteControlSettings,
+) -> Result<(), String> {
+    settings
+        .validate()
+        .map_err(|e| format!("Invalid remote control settings: {}", e))?;
+    let websocket_server = app
+        .state::<AppState>()
+        .websocket_server
+        .lock()
+        .unwrap()
+        .clone();
+    if let Some(server) = websocket_server {
+        server.lock().await.set_control_settings(settings.clone());
+    }
+    Ok(())
+}
+
+/// Save remote control settings and apply them to the WebSocket server
+///
+/// An empty token is replaced with a generated one; returns the saved
+/// settings so the token can be shown.
+#[tauri::command]
+pub async fn save_remote_control_settings(
+    app: AppHandle,
+    mut settings: crate::websocket_control::RemoteControlSettings,
+) -> Result<crate::websocket_control::RemoteControlSettings, String> {
+    if settings.token.is_empty() {
+        settings.token = crate::http_api::generate_token();
+    }
+    apply_remote_control_settings(&app, &settings).await?;
+
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::save(
+        &app_data_dir,
+        crate::websocket_control::SETTINGS_STEM,
+        &settings,
+    )
+    .map_err(|e| format!("Failed to save remote control settings: {}", e))?;
+
+    log_info_details!(
+        "commands::settings",
+        "remote_control_settings_saved",
+        json!({ "enabled": settings.enabled })
+    );
+    Ok(settings)
+}
+
+/// Load remote control settings from disk
+#[tauri::command]
+pub async fn load_remote_control_settings(
+    app: AppHandle,
+) -> Result<crate::websocket_control::RemoteControlSettings, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::load(&app_data_dir, crate::websocket_control::SETTINGS_STEM)
+        .map_err(|e| format!("Failed to load remote control settings: {}", e))
+}
+
+/// Apply PCM monitoring stream settings to the WebSocket server
+async fn apply_websocket_audio_settings(
+    app: &AppHandle,
+    settings: &crate::websocket_audio::AudioStreamSettings,
+) -> Result<(), String> {
+    let websocket_server = app
+        .state::<AppState>()
+        .websocket_server
+        .lock()
+        .unwrap()
+        .clone();
+    if let Some(server) = websocket_server {
+        server.lock().await.set_audio_settings(settings);
+    }
+    Ok(())
+}
+
+/// Save PCM monitoring stream settings; applies immediately
+#[tauri::command]
+pub async fn save_websocket_audio_settings(
+    app: AppHandle,
+    settings: crate::websocket_audio::AudioStreamSettings,
+) -> Result<(), String> {
+    apply_websocket_audio_settings(&app, &settings).await?;
+
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::save(
+        &app_data_dir,
+        crate::websocket_audio::SETTINGS_STEM,
+        &settings,
+    )
+    .map_err(|e| format!("Failed to save audio stream settings: {}", e))?;
+
+    log_info_details!(
+        "commands::settings",
+        "websocket_audio_settings_saved",
+        json!({ "enabled": settings.enabled })
+    );
+    Ok(())
+}
+
+/// Load PCM monitoring stream settings from disk
+#[tauri::command]
+pub async fn load_websocket_audio_settings(
+    app: AppHandle,
+) -> Result<crate::websocket_audio::AudioStreamSettings, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::load(&app_data_dir, crate::websocket_audio::SETTINGS_STEM)
+        .map_err(|e| format!("Failed to load audio stream settings: {}", e))
+}
+
+// ============================================================================
+// HTTP API Commands
+// ============================================================================
+
+/// Validate HTTP API settings and start or stop the read-only HTTP API to
+/// match
+pub(crate) async fn apply_http_api_settings(
+    app: &AppHandle,
+    settings: &crate::http_api::HttpApiSettings,
+) -> Result<(), String> {
+    settings
+        .validate()
+        .map_err(|e| format!("Invalid HTTP API settings: {}", e))?;
+    let state = app.state::<AppState>();
+    let previous = state.take_http_api();
+    if let Some(server) = previous {
+        server.stop().await;
+        log_info!("commands::http_api", "stopped", "");
+    }
+    if !settings.enabled {
+        return Ok(());
+    }
+
+    // Resolved per request, so the API follows workspace switches
+    let storage_app = app.clone();
+    let storage: crate::http_api::StorageProvider =
+        Arc::new(move || storage_app.state::<AppState>().get_storage_service());
+    // The watchdog's last report, so probes don't touch the disk
+    let health_app = app.clone();
+    let health: crate::http_api::HealthProvider = Arc::new(move || {
+        health_app
+            .state::<AppState>()
+            .get_health_report()
+            .unwrap_or_else(|| crate::app_health::HealthReport::starting(now_epoch_ms()))
+    });
+    let server = crate::http_api::HttpApiServer::start(
+        settings.port,
+        settings.token.clone(),
+        storage,
+        health,
+    )
+    .await
+    .map_err(|e| format!("Failed to start HTTP API: {:#}", e))?;
+    log_info!(
+        "commands::http_api",
+        "started",
+        format!("port={}", server.port())
+    );
+    state.set_http_api(server);
+    Ok(())
+}
+
+/// Port the HTTP API listens on (None while disabled)
+#[tauri::command]
+pub fn get_http_api_port(state: State<'_, AppState>) -> Option<u16> {
+    state.http_api_port()
+}
+
+/// Save HTTP API settings and start or stop the server to match
+///
+/// An empty token is replaced with a generated one; returns the saved
+/// settings so the token can be shown.
+#[tauri::command]
+pub async fn save_http_api_settings(
+    app: AppHandle,
+    mut settings: crate::http_api::HttpApiSettings,
+) -> Result<crate::http_api::HttpApiSettings, String> {
+    if settings.token.is_empty() {
+        settings.token = crate::http_api::generate_token();
+    }
+    apply_http_api_settings(&app, &settings).await?;
+
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::save(&app_data_dir, crate::http_api::SETTINGS_STEM, &settings)
+        .map_err(|e| format!("Failed to save HTTP API settings: {}", e))?;
+
+    log_info_details!(
+        "commands::settings",
+        "http_api_settings_saved",
+        json!({ "enabled": settings.enabled, "port": settings.port })
+    );
+    Ok(settings)
+}
+
+/// Load HTTP API settings from disk
+#[tauri::command]
+pub async fn load_http_api_settings(
+    app: AppHandle,
+) -> Result<crate::http_api::HttpApiSettings, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::load(&app_data_dir, crate::http_api::SETTINGS_STEM)
+        .map_err(|e| format!("Failed to load HTTP API settings: {}", e))
+}
+
+// ============================================================================
+// MQTT Commands
+// ============================================================================
+
+/// Queue an event for the MQTT broker, warning if the queue overflowed
+fn publish_mqtt(state: &AppState, event: crate::mqtt::MqttEvent) {
+    if !state.publish_mqtt(event) {
+        log_warn!(
+            "commands::mqtt",
+            "queue_full",
+            "Broker unreachable for too long; event dropped"
+        );
+    }
+}
+
+/// Validate MQTT settings and start or stop the MQTT publisher to match
+///
+/// The previous publisher (if any) publishes `offline` and disconnects first.
+pub(crate) async fn apply_mqtt_settings(
+    app: &AppHandle,
+    settings: &crate::mqtt::MqttSettings,
+) -> Result<(), String> {
+    settings
+        .validate()
+        .map_err(|e| format!("Invalid MQTT settings: {}", e))?;
+    let state = app.state::<AppState>();
+    let previous = state.take_mqtt();
+    if let Some(publisher) = previous {
+        publisher.stop().await;
+        log_info!("commands::mqtt", "stopped", "");
+    }
+    if !settings.enabled {
+        return Ok(());
+    }
+
+    let publisher = crate::mqtt::MqttPublisher::start(settings.clone());
+    if state.is_recording() {
+        if let Some(session) = state.primary_session() {
+            publisher.publish(crate::mqtt::MqttEvent::SessionStarted {
+                session_id: session.session_id().to_string(),
+            });
+        }
+    }
+    log_info_details!(
+        "commands::mqtt",
+        "started",
+        json!({ "host": settings.host, "port": settings.port, "tls": settings.tls })
+    );
+    state.set_mqtt(publisher);
+    Ok(())
+}
+
+/// Save MQTT settings and reconnect with them
+///
+/// The password is set with `set_mqtt_password`; one in `settings` is ignored.
+#[tauri::command]
+pub async fn save_mqtt_settings(
+    app: AppHandle,
+    mut settings: crate::mqtt::MqttSettings,
+) -> Result<(), String> {
+    settings.password = crate::mqtt::load_password().map_err(|e| e.to_string())?;
+    apply_mqtt_settings(&app, &settings).await?;
+
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::save(&app_data_dir, crate::mqtt::SETTINGS_STEM, &settings)
+        .map_err(|e| format!("Failed to save MQTT settings: {}", e))?;
+
+    log_info_details!(
+        "commands::settings",
+        "mqtt_settings_saved",
+        json!({ "enabled": settings.enabled, "host": settings.host, "qos": settings.qos })
+    );
+    Ok(())
+}
+
+/// Load MQTT settings from disk
+#[tauri::command]
+pub async fn load_mqtt_settings(app: AppHandle) -> Result<crate::mqtt::MqttSettings, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::load(&app_data_dir, crate::mqtt::SETTINGS_STEM)
+        .map_err(|e| format!("Failed to load MQTT settings: {}", e))
+}
+
+/// Store the MQTT broker password in the OS keychain and reconnect with it
+///
+/// `password: None` removes it.
+#[tauri::command]
+pub async fn set_mqtt_password(app: AppHandle, password: Option<String>) -> Result<(), String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    let mut settings = crate::mqtt::load_settings_with_password(&app_data_dir)
+        .map_err(|e| format!("Failed to load MQTT settings: {}", e))?;
+    settings.password = password.filter(|password| !password.is_empty());
+    apply_mqtt_settings(&app, &settings).await?;
+    crate::mqtt::store_password(settings.password.as_deref()).map_err(|e| e.to_string())?;
+
+    log_info_details!(
+        "commands::settings",
+        "mqtt_password_saved",
+        json!({ "removed": settings.password.is_none() })
+    );
+    Ok(())
+}
+
+/// Whether an MQTT broker password is stored (never returns the password)
+#[tauri::command]
+pub fn has_mqtt_password() -> Result<bool, String> {
+    crate::mqtt::load_password()
+        .map(|password| password.is_some())
+        .map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// Outbound Rate Limit Commands
+// ============================================================================
+
+/// Shared outbound rate limiter with the settings in effect, for request tasks
+#[derive(Clone)]
+struct OutboundRateLimit {
+    limiter: Arc<std::sync::Mutex<crate::rate_limit::OutboundRateLimiter>>,
+    settings: crate::rate_limit::RateLimitSettings,
+}
+
+impl OutboundRateLimit {
+    fn from_state(state: &AppState) -> Self {
+        Self {
+            limiter: Arc::clone(&state.outbound_limiter),
+            settings: state.get_rate_limit_settings(),
+        }
+    }
+
+    /// Wait until a request to `url` may go out
+    async fn wait(&self, url: &str) {
+        loop {
+            let wait = self.limiter.lock().unwrap().acquire(
+                url,
+                &self.settings,
+                std::time::Instant::now(),
+            );
+            let Some(wait) = wait else {
+                return;
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Block the target of `url` for the `Retry-After` of a 429/503 response
+    fn note_response(&self, url: &str, response: &reqwest::Response) {
+        if !matches!(response.status().as_u16(), 429 | 503) {
+            return;
+        }
+        let Some(delay) = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| crate::rate_limit::parse_retry_after(value, chrono::Utc::now()))
+        else {
+            return;
+        };
+
+        log_warn_details!(
+            "commands::rate_limit",
+            "retry_after",
+            json!({
+                "target": crate::rate_limit::target_key(url),
+                "status": response.status().as_u16(),
+                "delay_ms": delay.as_millis() as u64
+            })
+        );
+        self.limiter.lock().unwrap().retry_after(
+            url,
+            delay,
+            &self.settings,
+            std::time::Instant::now(),
+        );
+    }
+}
+
+/// Validate outbound rate limit settings and make them current
+fn apply_rate_limit_settings(
+    app: &AppHandle,
+    settings: &crate::rate_limit::RateLimitSettings,
+) -> Result<(), String> {
+    settings
+        .validate()
+        .map_err(|e| format!("Invalid rate limit settings: {}", e))?;
+    app.state::<AppState>()
+        .set_rate_limit_settings(settings.clone());
+    Ok(())
+}
+
+/// Save outbound rate limit settings (takes effect for the next request)
+#[tauri::command]
+pub async fn save_rate_limit_settings(
+    app: AppHandle,
+    settings: crate::rate_limit::RateLimitSettings,
+) -> Result<(), String> {
+    apply_rate_limit_settings(&app, &settings)?;
+
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::save(&app_data_dir, crate::rate_limit::SETTINGS_STEM, &settings)
+        .map_err(|e| format!("Failed to save rate limit settings: {}", e))?;
+
+    log_info_details!(
+        "commands::settings",
+        "rate_limit_settings_saved",
+        json!({
+            "enabled": settings.enabled,
+            "requests_per_minute": settings.requests_per_minute,
+            "burst": settings.burst
+        })
+    );
+    Ok(())
+}
+
+/// Load outbound rate limit settings from disk
+#[tauri::command]
+pub async fn load_rate_limit_settings(
+    app: AppHandle,
+) -> Result<crate::rate_limit::RateLimitSettings, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::load(&app_data_dir, crate::rate_limit::SETTINGS_STEM)
+        .map_err(|e| format!("Failed to load rate limit settings: {}", e))
+}
+
+/// Tokens, `Retry-After` blocks and waits per outbound target
+#[tauri::command]
+pub fn get_rate_limit_metrics(
+    state: State<'_, AppState>,
+) -> Vec<crate::rate_limit::RateLimitTargetSnapshot> {
+    state.rate_limit_snapshot()
+}
+
+// ============================================================================
+// Health Commands
+// ============================================================================
+
+/// Evaluate the process health (probes the recordings directory)
+pub(crate) async fn evaluate_health(app: &AppHandle) -> crate::app_health::HealthReport {
+    use crate::app_health::{HealthInputs, StorageProbe};
+
+    let state = app.state::<AppState>();
+    let storage =
+        match state.get_storage_service() {
+            None => StorageProbe::NotInitialized,
+            Some(storage) => tokio::task::spawn_blocking(move || {
+                match crate::app_health::probe_writable(storage.recordings_dir()) {
+                    Ok(()) => StorageProbe::Writable(storage.check_disk_space().ok()),
+                    Err(e) => StorageProbe::Unwritable(format!("{:#}", e)),
+                }
+            })
+            .await
+            .unwrap_or_else(|e| StorageProbe::Unwritable(e.to_string())),
+        };
+    let inputs = HealthInputs {
+        viewer_mode: state.is_viewer_mode(),
+        sidecar_startup: state.get_sidecar_startup(),
+        sidecar_exited: state.sidecar_exited(),
+        sidecar_slow: state
+            .sidecar_status()
+            .health
+            .as_ref()
+            .is_some_and(crate::sidecar_health::is_slow),
+        storage,
+    };
+    crate::app_health::evaluate(&inputs, now_epoch_ms())
+}
+
+/// Re-evaluate health every `CHECK_INTERVAL` for `/health` and the exit code
+///
+/// Exits the app with `EXIT_UNHEALTHY` when the settings ask for it.
+pub(crate) fn start_health_watchdog(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut watchdog = crate::app_health::Watchdog::default();
+        loop {
+            let report = evaluate_health(&app).await;
+            let state = app.state::<AppState>();
+            let settings = state.get_health_settings();
+            let verdict = watchdog.observe(report.status, std::time::Instant::now(), &settings);
+            if verdict.changed {
+                log_warn_details!(
+                    "commands::health",
+                    "status_changed",
+                    json!({ "status": report.status, "checks": report.checks })
+                );
+            }
+            state.set_health_report(report.clone());
+            if verdict.exit {
+                log_error_details!(
+                    "commands::health",
+                    "watchdog_exit",
+                    json!({
+                        "exit_code": crate::app_health::EXIT_UNHEALTHY,
+                        "grace_secs": settings.unhealthy_grace_secs,
+                        "checks": report.checks
+                    })
+                );
+                app.exit(crate::app_health::EXIT_UNHEALTHY);
+                return;
+            }
+            tokio::time::sleep(crate::app_health::CHECK_INTERVAL).await;
+        }
+    });
+}
+
+/// Current process health (evaluated now)
+#[tauri::command]
+pub async fn get_health(app: AppHandle) -> Result<crate::app_health::HealthReport, String> {
+    Ok(evaluate_health(&app).await)
+}
+
+/// Validate watchdog settings and make them current
+fn apply_health_settings(
+    app: &AppHandle,
+    settings: &crate::app_health::HealthSettings,
+) -> Result<(), String> {
+    settings
+        .validate()
+        .map_err(|e| format!("Invalid health settings: {}", e))?;
+    app.state::<AppState>()
+        .set_health_settings(settings.clone());
+    Ok(())
+}
+
+/// Save watchdog settings (used from the next evaluation)
+#[tauri::command]
+pub async fn save_health_settings(
+    app: AppHandle,
+    settings: crate::app_health::HealthSettings,
+) -> Result<(), String> {
+    apply_health_settings(&app, &settings)?;
+
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::save(&app_data_dir, crate::app_health::SETTINGS_STEM, &settings)
+        .map_err(|e| format!("Failed to save health settings: {}", e))?;
+
+    log_info_details!(
+        "commands::settings",
+        "health_settings_saved",
+        json!({
+            "exit_on_unhealthy": settings.exit_on_unhealthy,
+            "unhealthy_grace_secs": settings.unhealthy_grace_secs
+        })
+    );
+    Ok(())
+}
+
+/// Load watchdog settings from disk
+#[tauri::command]
+pub async fn load_health_settings(
+    app: AppHandle,
+) -> Result<crate::app_health::HealthSettings, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::load(&app_data_dir, crate::app_health::SETTINGS_STEM)
+        .map_err(|e| format!("Failed to load health settings: {}", e))
+}
+
+/// Save transcription worker pool settings; applies from the next app start
+#[tauri::command]
+pub async fn save_transcription_worker_settings(
+    app: AppHandle,
+    settings: crate::transcription_workers::TranscriptionWorkerSettings,
+) -> Result<(), String> {
+    settings
+        .validate()
+        .map_err(|e| format!("Invalid transcription worker settings: {}", e))?;
+
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::save(
+        &app_data_dir,
+        crate::transcription_workers::SETTINGS_STEM,
+        &settings,
+    )
+    .map_err(|e| format!("Failed to save transcription worker settings: {}", e))?;
+
+    log_info_details!(
+        "commands::settings",
+        "transcription_worker_settings_saved",
+        json!({ "workers": settings.workers })
+    );
+    Ok(())
+}
+
+/// Load transcription worker pool settings from disk
+#[tauri::command]
+pub async fn load_transcription_worker_settings(
+    app: AppHandle,
+) -> Result<crate::transcription_workers::TranscriptionWorkerSettings, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::load(&app_data_dir, crate::transcription_workers::SETTINGS_STEM)
+        .map_err(|e| format!("Failed to load transcription worker settings: {}", e))
+}
+
+/// List the engines `estimate_transcription` knows
+#[tauri::command]
+pub fn list_transcription_engines() -> Vec<crate::transcription_estimate::EngineProfile> {
+    crate::transcription_estimate::engines()
+}
+
+/// Estimate duration, CPU/GPU load and cost of transcribing audio with `engine`
+///
+/// Dry run: nothing is queued. The audio length comes from the saved
+/// session `session_id`, or `audio_ms` for a recording not imported yet.
+#[tauri::command]
+pub async fn estimate_transcription(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    engine: String,
+    session_id: Option<String>,
+    audio_ms: Option<u64>,
+) -> Result<crate::transcription_estimate::TranscriptionEstimate, String> {
+    use crate::transcription_estimate::{estimate, find_engine, EstimateInputs};
+
+    let profile =
+        find_engine(&engine).ok_or_else(|| format!("Unknown transcription engine: {}", engine))?;
+
+    let audio_ms = match (session_id, audio_ms) {
+        (Some(session_id), _) => {
+            crate::session_id::validate_session_id(&session_id).map_err(|e| e.to_string())?;
+            let storage = state
+                .get_storage_service()
+                .ok_or_else(|| "Storage not initialized".to_string())?;
+            storage
+                .load_session(&session_id)
+                .map_err(|e| format!("Failed to load session: {}", e))?
+                .metadata
+                .duration_seconds
+                * 1000
+        }
+        (None, Some(audio_ms)) => audio_ms,
+        (None, None) => return Err("Either session_id or audio_ms is required".to_string()),
+    };
+
+    let workers = app
+        .path()
+        .app_data_dir()
+        .ok()
+        .and_then(|dir| {
+            settings_file::load::<crate::transcription_workers::TranscriptionWorkerSettings>(
+                &dir,
+                crate::transcription_workers::SETTINGS_STEM,
+            )
+            .ok()
+        })
+        .unwrap_or_default()
+        .workers;
+
+    // The sidecar's measured speed, if it runs the same model
+    let measured_real_time_factor = state
+        .sidecar_status()
+        .health
+        .filter(|health| health.model.as_deref() == profile.model)
+        .and_then(|health| health.real_time_factor);
+
+    let inputs = EstimateInputs {
+        audio_ms,
+        workers,
+        cpu_cores: num_cpus::get(),
+        measured_real_time_factor,
+        recording: state.is_recording(),
+    };
+    Ok(estimate(&profile, &inputs))
+}
+
+/// Save cloud STT settings; applies from the next app start
+///
+/// The API key is set separately (`set_cloud_stt_api_key`).
+#[tauri::command]
+pub async fn save_cloud_stt_settings(
+    app: AppHandle,
+    settings: crate::cloud_stt::CloudSttSettings,
+) -> Result<(), String> {
+    settings
+        .validate()
+        .map_err(|e| format!("Invalid cloud STT settings: {}", e))?;
+
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::save(&app_data_dir, crate::cloud_stt::SETTINGS_STEM, &settings)
+        .map_err(|e| format!("Failed to save cloud STT settings: {}", e))?;
+
+    log_info_details!(
+        "commands::settings",
+        "cloud_stt_settings_saved",
+        json!({
+            "mode": settings.mode.as_str(),
+            "provider": settings.provider.as_str(),
+        })
+    );
+    Ok(())
+}
+
+/// Load cloud STT settings from disk
+#[tauri::command]
+pub async fn load_cloud_stt_settings(
+    app: AppHandle,
+) -> Result<crate::cloud_stt::CloudSttSettings, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::load(&app_data_dir, crate::cloud_stt::SETTINGS_STEM)
+        .map_err(|e| format!("Failed to load cloud STT settings: {}", e))
+}
+
+/// Store the API key of a cloud STT provider in the OS keychain
+///
+/// `api_key: None` removes it. Applies from the next app start.
+#[tauri::command]
+pub fn set_cloud_stt_api_key(
+    provider: crate::cloud_stt::CloudProvider,
+    api_key: Option<String>,
+) -> Result<(), String> {
+    let api_key = api_key.filter(|key| !key.trim().is_empty());
+    crate::cloud_stt::store_api_key(provider, api_key.as_deref().map(str::trim))
+        .map_err(|e| e.to_string())?;
+
+    log_info_details!(
+        "commands::settings",
+        "cloud_stt_api_key_saved",
+        json!({ "provider": provider.as_str(), "removed": api_key.is_none() })
+    );
+    Ok(())
+}
+
+/// Whether an API key is stored for a cloud STT provider (never returns the key)
+#[tauri::command]
+pub fn has_cloud_stt_api_key(provider: crate::cloud_stt::CloudProvider) -> Result<bool, String> {
+    crate::cloud_stt::load_api_key(provider)
+        .map(|key| key.is_some())
+        .map_err(|e| e.to_string())
+}
+
+/// Re-read the settings files and apply the ones edited since startup (or
+/// the last reload)
+///
+/// Settings read only at startup are reported in `restart_required`; a file
+/// that fails to load or validate keeps the running values.
+#[tauri::command]
+pub async fn reload_settings(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<crate::settings_reload::SettingsReloadReport, String> {
+    let previous = state.get_settings_snapshot();
+    let mut current = crate::settings_reload::SettingsSnapshot::capture(&settings_dirs(&app));
+    let mut report = crate::settings_reload::SettingsReloadReport::default();
+
+    for name in previous.changed(&current) {
+        if !crate::settings_reload::is_live(&name) {
+            report.restart_required.push(name);
+            continue;
+        }
+        match apply_reloaded_setting(&app, &name).await {
+            Ok(()) => report.applied.push(name),
+            Err(e) => {
+                current.restore(&previous, &name);
+                report.failed.push(crate::settings_reload::ReloadFailure {
+                    name,
+                    error: format!("{:#}", e),
+                });
+            }
+        }
+    }
+    state.set_settings_snapshot(current);
+
+    if !report.is_empty() {
+        log_info_details!(
+            "commands::settings",
+            "settings_reloaded",
+            json!({
+                "applied": report.applied,
+                "restart_required": report.restart_required,
+                "failed": report.failed,
+            })
+        );
+    }
+    Ok(report)
+}
+
+/// Apply one changed settings file (a `settings_reload::LIVE` stem)
+///
+/// Uses the same validate-and-apply step as the corresponding `save_*`
+/// command, minus the write.
+async fn apply_reloaded_setting(app: &AppHandle, name: &str) -> anyhow::Result<()> {
+    use crate::settings_file::load;
+
+    let machine_dir = app.path().app_data_dir()?;
+    let workspace_dir = workspace_data_dir(app)?;
+
+    let applied = match name {
+        // Per workspace
+        crate::keyword_alerts::SETTINGS_STEM => {
+            apply_keyword_alert_settings(app, &load(&workspace_dir, name)?)
+        }
+        crate::docs_budget::SETTINGS_STEM => {
+            apply_docs_budget_settings(app, &load(&workspace_dir, name)?)
+        }
+        crate::routing::SETTINGS_STEM => apply_routing_settings(app, &load(&workspace_dir, name)?),
+        crate::transcript_webhooks::SETTINGS_STEM => {
+            apply_transcript_webhook_settings(app, &load(&workspace_dir, name)?)
+        }
+        crate::pipeline::SETTINGS_STEM => {
+            apply_pipeline_settings(app, &load(&workspace_dir, name)?)
+        }
+        crate::jobs::SETTINGS_STEM => apply_job_settings(app, &load(&workspace_dir, name)?),
+        crate::memory_sentinel::SETTINGS_STEM => {
+            apply_memory_sentinel_settings(app, &load(&workspace_dir, name)?)
+        }
+        crate::trash::SETTINGS_STEM => apply_trash_settings(app, &load(&workspace_dir, name)?),
+        crate::audio_format::SETTINGS_STEM => {
+            apply_audio_format_settings(app, &load(&workspace_dir, name)?)
+        }
+        crate::reconnect_policy::SETTINGS_STEM => {
+            apply_reconnect_policy_settings(app, &load(&workspace_dir, name)?)
+        }
+        crate::consent::SETTINGS_STEM => {
+            apply_consent_announcement_settings(app, &load(&workspace_dir, name)?)
+        }
+        crate::device_aliases::SETTINGS_STEM => {
+            apply_device_alias_settings(app, &load(&workspace_dir, name)?)
+        }
+        crate::partial_granularity::SETTINGS_STEM => {
+            apply_partial_granularity_settings(app, &load(&workspace_dir, name)?)
+        }
+        // Includes the log level
+        crate::audio_dump::SETTINGS_STEM => apply_debug_settings(app, &load(&workspace_dir, name)?),
+        crate::ipc_quarantine::SETTINGS_STEM => {
+            apply_ipc_quarantine_settings(app, &load(&workspace_dir, name)?)
+        }
+        crate::retention::SETTINGS_STEM => {
+            apply_retention_settings(app, &load(&workspace_dir, name)?)
+        }
+        crate::websocket_limits::SETTINGS_STEM => {
+            apply_websocket_settings(app, &load(&workspace_dir, name)?).await
+        }
+
+        // Machine-wide
+        crate::websocket_control::SETTINGS_STEM => {
+            apply_remote_control_settings(app, &load(&machine_dir, name)?).await
+        }
+        crate::websocket_audio::SETTINGS_STEM => {
+            apply_websocket_audio_settings(app, &load(&machine_dir, name)?).await
+        }
+        crate::websocket_mdns::SETTINGS_STEM => {
+            apply_mdns_settings(app, &load(&machine_dir, name)?)
+        }
+        crate::http_api::SETTINGS_STEM => {
+            apply_http_api_settings(app, &load(&machine_dir, name)?).await
+        }
+        crate::mqtt::SETTINGS_STEM => {
+            apply_mqtt_settings(
+                app,
+                &crate::mqtt::load_settings_with_password(&machine_dir)?,
+            )
+            .await
+        }
+        crate::ops_webhooks::SETTINGS_STEM => {
+            apply_ops_webhook_settings(app, &load(&machine_dir, name)?)
+        }
+        crate::rate_limit::SETTINGS_STEM => {
+            apply_rate_limit_settings(app, &load(&machine_dir, name)?)
+        }
+        crate::app_health::SETTINGS_STEM => apply_health_settings(app, &load(&machine_dir, name)?),
+        crate::wasapi_capture::SETTINGS_STEM => {
+            apply_wasapi_capture_settings(&load(&machine_dir, name)?)
+        }
+        crate::channel_selection::SETTINGS_STEM => {
+            apply_channel_selection_settings(&load(&machine_dir, name)?)
+        }
+
+        // Read from disk where they are used (input gains at recording start)
+        crate::multi_input_settings::SETTINGS_STEM
+        | crate::summary::SETTINGS_STEM
+        | crate::furigana::SETTINGS_STEM
+        | crate::session_id::SETTINGS_STEM => Ok(()),
+
+        _ => anyhow::bail!("{} is not applied at runtime", name),
+    };
+    applied.map_err(anyhow::Error::msg)
+}
+
+// ============================================================================
+// Transcript Webhook Commands
+// ============================================================================
+
+static TRANSCRIPT_WEBHOOK_CLIENT: Lazy<reqwest::Client> = Lazy::new(|| {
+    reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+        .unwrap_or_default()
+});
+
+/// POST an event to the transcript webhooks subscribed to it
+///
+/// Each delivery runs (and retries) in the background.
+fn send_transcript_webhooks(
+    state: &AppState,
+    body: crate::transcript_webhooks::TranscriptWebhookBody,
+) {
+    let settings = state.get_transcript_webhook_settings();
+    let targets = settings.targets(body.event());
+    if targets.is_empty() {
+        return;
+    }
+
+    let payload = body.into_payload();
+    let rate_limit = OutboundRateLimit::from_state(state);
+    for webhook in targets {
+        tokio::spawn(deliver_transcript_webhook(
+            rate_limit.clone(),
+            webhook.clone(),
+            payload.clone(),
+            settings.max_retries,
+        ));
+    }
+}
+
+async fn deliver_transcript_webhook(
+    rate_limit: OutboundRateLimit,
+    webhook: crate::transcript_webhooks::TranscriptWebhook,
+    payload: crate::transcript_webhooks::TranscriptWebhookPayload,
+    max_retries: u32,
+) {
+    let mut attempt = 0;
+    loop {
+        rate_limit.wait(&webhook.url).await;
+        let mut request = TRANSCRIPT_WEBHOOK_CLIENT.post(&webhook.url).json(&payload);
+        if let Some(token) = &webhook.token {
+            request = request.bearer_auth(token);
+        }
+        let (error, retryable) = match request.send().await {
+            Ok(response) if response.status().is_success() => return,
+            Ok(response) => {
+                rate_limit.note_response(&webhook.url, &response);
+                (
+                    format!("HTTP {}", response.status()),
+                    crate::transcript_webhooks::is_retryable_status(response.status().as_u16()),
+                )
+            }
+            Err(e) => (e.to_string(), true),
+        };
+
+        if !retryable || attempt >= max_retries {
+            log_warn_details!(
+                "commands::transcript_webhooks",
+                "webhook_failed",
+                json!({
+                    "delivery": payload.delivery_id,
+                    "event": payload.body.event(),
+                    "attempts": attempt + 1,
+                    "error": error
+                })
+            );
+            return;
+        }
+        attempt += 1;
+        tokio::time::sleep(crate::transcript_webhooks::retry_delay(attempt)).await;
+    }
+}
+
+/// Validate transcript webhook settings and make them current
+fn apply_transcript_webhook_settings(
+    app: &AppHandle,
+    settings: &crate::transcript_webhooks::TranscriptWebhookSettings,
+) -> Result<(), String> {
+    settings
+        .validate()
+        .map_err(|e| format!("Invalid transcript webhook settings: {}", e))?;
+    app.state::<AppState>()
+        .set_transcript_webhook_settings(settings.clone());
+    Ok(())
+}
+
+/// Save transcript webhook settings (takes effect for the next event)
+#[tauri::command]
+pub async fn save_transcript_webhook_settings(
+    app: AppHandle,
+    settings: crate::transcript_webhooks::TranscriptWebhookSettings,
+) -> Result<(), String> {
+    apply_transcript_webhook_settings(&app, &settings)?;
+
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::save(
+        &app_data_dir,
+        crate::transcript_webhooks::SETTINGS_STEM,
+        &settings,
+    )
+    .map_err(|e| format!("Failed to save transcript webhook settings: {}", e))?;
+
+    log_info_details!(
+        "commands::settings",
+        "transcript_webhook_settings_saved",
+        json!({
+            "enabled": settings.enabled,
+            "webhook_count": settings.webhooks.len()
+        })
+    );
+    Ok(())
+}
+
+/// Load transcript webhook settings from disk
+#[tauri::command]
+pub async fn load_transcript_webhook_settings(
+    app: AppHandle,
+) -> Result<crate::transcript_webhooks::TranscriptWebhookSettings, String> {
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::load(&app_data_dir, crate::transcript_webhooks::SETTINGS_STEM)
+        .map_err(|e| format!("Failed to load transcript webhook settings: {}", e))
+}
+
+// ============================================================================
+// Operational Webhook Commands
+// ============================================================================
+
+static OPS_WEBHOOK_CLIENT: Lazy<reqwest::Client> = Lazy::new(|| {
+    reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+        .unwrap_or_default()
+});
+
+/// Send an operational event to the webhooks subscribed to it
+///
+/// Delivery runs in the background; events within the cooldown of the
+/// previous one of the same kind are dropped.
+pub(crate) fn notify_ops_event(
+    app: &AppHandle,
+    kind: crate::ops_webhooks::OpsEventKind,
+    message: &str,
+    data: serde_json::Value,
+) {
+    let state = app.state::<AppState>();
+    let settings = state.get_ops_webhook_settings();
+    let targets: Vec<crate::ops_webhooks::OpsWebhook> =
+        settings.targets(kind).into_iter().cloned().collect();
+    if targets.is_empty()
+        || !state
+            .ops_throttle
+            .lock()
+            .unwrap()
+            .allow(kind, now_epoch_ms(), settings.cooldown())
+    {
+        return;
+    }
+
+    let event = crate::ops_webhooks::OpsEvent {
+        event: kind,
+        machine: sys_info::hostname().unwrap_or_default(),
+        workspace: state.get_active_workspace(),
+        timestamp: chrono::Local::now().to_rfc3339(),
+        message: message.to_string(),
+        data,
+    };
+    let rate_limit = OutboundRateLimit::from_state(&state);
+    for webhook in targets {
+        let event = event.clone();
+        let rate_limit = rate_limit.clone();
+        tauri::async_runtime::spawn(async move {
+            rate_limit.wait(&webhook.url).await;
+            let mut request = OPS_WEBHOOK_CLIENT.post(&webhook.url).json(&event);
+            if let Some(token) = &webhook.token {
+                request = request.bearer_auth(token);
+            }
+            let result = request
+                .send()
+                .await
+                .inspect(|response| rate_limit.note_response(&webhook.url, response))
+                .and_then(|response| response.error_for_status());
+            if let Err(e) = result {
+                log_warn_details!(
+                    "commands::ops_webhooks",
+                    "webhook_failed",
+                    json!({ "event": event.event, "error": e.to_string() })
+                );
+            }
+        });
+    }
+}
+
+/// Check the free space of the recordings directory periodically and
+/// report warning and critical levels
+pub(crate) fn start_disk_monitor(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            check_disk_for_ops(&app).await;
+            tokio::time::sleep(crate::ops_webhooks::DISK_CHECK_INTERVAL).await;
+        }
+    });
+}
+
+async fn check_disk_for_ops(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    if !state.get_ops_webhook_settings().enabled {
+        return;
+    }
+    let Some(storage) = state.get_storage_service() else {
+        return;
+    };
+    let recordings_dir = storage.recordings_dir().display().to_string();
+    let checked = tokio::task::spawn_blocking(move || storage.check_disk_space()).await;
+    match checked
+        .map_err(anyhow::Error::from)
+        .and_then(|status| status)
     {
-        let is_recording = state.is_recording.lock().unwrap();
-        if !*is_recording {
-            return Err("Not recording".to_string());
+        Ok(status) => {
+            if let Some(kind) = crate::ops_webhooks::disk_event_kind(status) {
+                notify_ops_event(
+                    app,
+                    kind,
+                    &status.to_string(),
+                    json!({ "recordings_dir": recordings_dir }),
+                );
+            }
+        }
+        Err(e) => {
+            log_warn!("commands::ops_webhooks", "disk_check_failed", e.to_string());
+        }
+    }
+}
+
+/// Validate operational webhook settings and make them current
+fn apply_ops_webhook_settings(
+    app: &AppHandle,
+    settings: &crate::ops_webhooks::OpsWebhookSettings,
+) -> Result<(), String> {
+    settings
+        .validate()
+        .map_err(|e| format!("Invalid webhook settings: {}", e))?;
+    app.state::<AppState>()
+        .set_ops_webhook_settings(settings.clone());
+    Ok(())
+}
+
+/// Save operational webhook settings and apply them immediately
+#[tauri::command]
+pub async fn save_ops_webhook_settings(
+    app: AppHandle,
+    settings: crate::ops_webhooks::OpsWebhookSettings,
+) -> Result<(), String> {
+    apply_ops_webhook_settings(&app, &settings)?;
+
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::save(&app_data_dir, crate::ops_webhooks::SETTINGS_STEM, &settings)
+        .map_err(|e| format!("Failed to save webhook settings: {}", e))?;
+
+    log_info_details!(
+        "commands::settings",
+        "ops_webhook_settings_saved",
+        json!({
+            "enabled": settings.enabled,
+            "webhooks": settings.webhooks.len(),
+            "cooldown_minutes": settings.cooldown_minutes,
+        })
+    );
+    Ok(())
+}
+
+/// Load operational webhook settings from disk
+#[tauri::command]
+pub async fn load_ops_webhook_settings(
+    app: AppHandle,
+) -> Result<crate::ops_webhooks::OpsWebhookSettings, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::load(&app_data_dir, crate::ops_webhooks::SETTINGS_STEM)
+        .map_err(|e| format!("Failed to load webhook settings: {}", e))
+}
+
+// ============================================================================
+// Session History Commands
+// ============================================================================
+
+/// List finalized sessions, newest first
+///
+/// A session appears once recording stops and its session.json is written.
+#[tauri::command]
+pub async fn get_session_list(
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::storage::SessionMetadata>, String> {
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+
+    tokio::task::spawn_blocking(move || storage.list_sessions())
+        .await
+        .map_err(|e| format!("Session list task failed: {}", e))?
+        .map_err(|e| format!("Failed to list sessions: {}", e))
+}
+
+/// Load a finalized session: metadata, transcript and audio file path
+///
+/// Only the first `transcript_chunks::SESSION_PAGE_EVENTS` transcript events
+/// are returned; `transcript_total` tells whether to page the rest with
+/// `get_transcript_page`.
+#[tauri::command]
+pub async fn get_session(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<crate::storage::LoadedSession, String> {
+    crate::session_id::validate_session_id(&session_id).map_err(|e| e.to_string())?;
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+
+    let id = session_id.clone();
+    tokio::task::spawn_blocking(move || {
+        storage.load_session_page(&id, crate::transcript_chunks::SESSION_PAGE_EVENTS)
+    })
+    .await
+    .map_err(|e| format!("Session load task failed: {}", e))?
+    .map_err(|e| format!("Failed to load session {}: {}", session_id, e))
+}
+
+/// Load a page of a session's transcript (`limit` capped at
+/// `transcript_chunks::MAX_PAGE_EVENTS`)
+///
+/// Works for sessions still being recorded; hourly parts are merged.
+#[tauri::command]
+pub async fn get_transcript_page(
+    state: State<'_, AppState>,
+    session_id: String,
+    offset: usize,
+    limit: usize,
+) -> Result<crate::transcript_chunks::TranscriptPage, String> {
+    crate::session_id::validate_session_id(&session_id).map_err(|e| e.to_string())?;
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+
+    let id = session_id.clone();
+    let limit = limit.min(crate::transcript_chunks::MAX_PAGE_EVENTS);
+    tokio::task::spawn_blocking(move || storage.load_transcript_page(&id, offset, limit))
+        .await
+        .map_err(|e| format!("Transcript page task failed: {}", e))?
+        .map_err(|e| format!("Failed to load transcript {}: {}", session_id, e))
+}
+
+/// Compute a finalized session's statistics and store them in session.json
+///
+/// Words, words per minute, silence share and segments per speaker are
+/// recomputed; the partial -> final latency recorded at stop is kept.
+#[tauri::command]
+pub async fn compute_session_stats(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<crate::session_stats::SessionStatistics, String> {
+    crate::session_id::validate_session_id(&session_id).map_err(|e| e.to_string())?;
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+
+    let id = session_id.clone();
+    let stats = tokio::task::spawn_blocking(move || {
+        crate::session_stats::update_session(&storage, &id, None)
+    })
+    .await
+    .map_err(|e| format!("Session stats task failed: {}", e))?
+    .map_err(|e| format!("Failed to compute stats of session {}: {}", session_id, e))?;
+
+    log_info_details!(
+        "commands::history",
+        "session_stats_computed",
+        json!({
+            "session": session_id,
+            "total_words": stats.total_words,
+            "silence_percent": stats.silence_percent,
+        })
+    );
+    Ok(stats)
+}
+
+/// Set the user-visible title of a finalized session
+///
+/// `None` or a blank title clears it. Returns the updated metadata.
+#[tauri::command]
+pub fn rename_session(
+    state: State<'_, AppState>,
+    session_id: String,
+    title: Option<String>,
+) -> Result<crate::storage::SessionMetadata, String> {
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+
+    let metadata = storage
+        .rename_session(&session_id, title.as_deref())
+        .map_err(|e| format!("Failed to rename session {}: {}", session_id, e))?;
+
+    log_info_details!(
+        "commands::history",
+        "session_renamed",
+        json!({ "session": session_id, "has_title": metadata.title.is_some() })
+    );
+    Ok(metadata)
+}
+
+/// Replace the tags of a finalized session
+///
+/// Tags are trimmed and deduplicated. Returns the updated metadata.
+#[tauri::command]
+pub fn set_session_tags(
+    state: State<'_, AppState>,
+    session_id: String,
+    tags: Vec<String>,
+) -> Result<crate::storage::SessionMetadata, String> {
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+
+    let metadata = storage
+        .set_session_tags(&session_id, &tags)
+        .map_err(|e| format!("Failed to set tags of session {}: {}", session_id, e))?;
+
+    log_info_details!(
+        "commands::history",
+        "session_tags_set",
+        json!({ "session": session_id, "tags": metadata.tags.len() })
+    );
+    Ok(metadata)
+}
+
+// ============================================================================
+// Session Trash Commands
+// ============================================================================
+
+/// Permanently remove trashed sessions past the retention window
+pub(crate) fn purge_expired_trash(
+    storage: &crate::storage::LocalStorageService,
+    settings: &crate::trash::TrashSettings,
+) {
+    match storage.purge_trash(settings.retention_ms(), now_epoch_ms()) {
+        Ok(purged) if !purged.is_empty() => {
+            log_info_details!(
+                "commands::trash",
+                "trash_purged",
+                json!({ "sessions": purged, "retention_days": settings.retention_days })
+            );
+        }
+        Ok(_) => {}
+        Err(e) => {
+            log_warn_details!(
+                "commands::trash",
+                "trash_purge_failed",
+                json!({ "error": e.to_string() })
+            );
         }
     }
+}
+
+/// Move a session to the trash (restorable until the retention window ends)
+///
+/// The session being recorded cannot be deleted.
+#[tauri::command]
+pub fn delete_session(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<crate::storage::TrashedSession, String> {
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+
+    if state.get_session(&session_id).is_some() {
+        return Err(format!(
+            "Cannot delete the session being recorded: {}",
+            session_id
+        ));
+    }
 
-    stop_recording_internal(&state).await?;
-    Ok("Recording stopped".to_string())
+    let trashed = storage
+        .trash_session(&session_id, now_epoch_ms())
+        .map_err(|e| format!("Failed to delete session: {}", e))?;
+    log_info_details!(
+        "commands::trash",
+        "session_trashed",
+        json!({ "session": session_id })
+    );
+
+    purge_expired_trash(&storage, &state.get_trash_settings());
+    Ok(trashed)
 }
 
-/// Cancel ongoing reconnection attempts
-/// Task 10.4 Phase 2: User-initiated cancellation of auto-reconnect
+/// Upgrade a session's transcript files to the current line schema
+///
+/// Rewritten files keep a `.bak` copy of the original. The session being
+/// recorded cannot be migrated.
 #[tauri::command]
-pub async fn cancel_reconnection(state: State<'_, AppState>) -> Result<String, String> {
-    let mut reconnection_mgr = state.reconnection_manager.lock().await;
+pub fn migrate_session_format(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<crate::storage::SessionFormatMigration, String> {
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
 
-    if !reconnection_mgr.is_reconnecting() {
-        log_info_details!(
-            "commands::reconnection",
-            "cancel_no_job",
-            json!({})
-        );
-        return Ok("No reconnection in progress".to_string());
+    if state.get_session(&session_id).is_some() {
+        return Err(format!(
+            "Cannot migrate the session being recorded: {}",
+            session_id
+        ));
     }
 
-    reconnection_mgr.cancel();
+    let migration = storage
+        .migrate_session_format(&session_id)
+        .map_err(|e| format!("Failed to migrate session format: {}", e))?;
+    log_info_details!(
+        "commands::storage",
+        "session_format_migrated",
+        json!({
+            "session": session_id,
+            "files": migration.migrated_files,
+            "lines": migration.upgraded_lines,
+        })
+    );
+    Ok(migration)
+}
 
+/// Move a trashed session back to the recordings
+#[tauri::command]
+pub fn restore_session(state: State<'_, AppState>, session_id: String) -> Result<(), String> {
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+
+    storage
+        .restore_session(&session_id)
+        .map_err(|e| format!("Failed to restore session: {}", e))?;
     log_info_details!(
-        "commands::reconnection",
-        "cancelled_by_user",
-        json!({})
+        "commands::trash",
+        "session_restored",
+        json!({ "session": session_id })
     );
+    Ok(())
+}
 
-    Ok("Reconnection cancelled".to_string())
+/// List trashed sessions, most recently deleted first
+#[tauri::command]
+pub fn list_trashed_sessions(
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::storage::TrashedSession>, String> {
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+
+    storage
+        .list_trashed_sessions()
+        .map_err(|e| format!("Failed to list trashed sessions: {}", e))
 }
 
-/// Get available Whisper models and system resources
-/// Task 9.2: Whisper model selection UI
-/// Requirement: STT-REQ-006.1, STT-REQ-006.2, STT-REQ-006.4
+/// Make trash settings current
+fn apply_trash_settings(
+    app: &AppHandle,
+    settings: &crate::trash::TrashSettings,
+) -> Result<(), String> {
+    app.state::<AppState>().set_trash_settings(settings.clone());
+    Ok(())
+}
+
+/// Save trash settings and apply them immediately
 #[tauri::command]
-pub async fn get_whisper_models() -> Result<serde_json::Value, String> {
-    log_info!("commands::models", "request_models");
+pub async fn save_trash_settings(
+    app: AppHandle,
+    settings: crate::trash::TrashSettings,
+) -> Result<(), String> {
+    apply_trash_settings(&app, &settings)?;
 
-    // Task 9.2: Available models (STT-REQ-006.2)
-    let models = vec!["tiny", "base", "small", "medium", "large-v3"];
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
 
-    // Task 9.2: Get system resources (MVP0: static values, MVP1: actual detection)
-    let system_resources = serde_json::json!({
-        "cpu_cores": num_cpus::get(),
-        "total_memory_gb": 8,  // MVP0: static, TODO: actual detection
-        "gpu_available": false,  // MVP0: static, TODO: actual detection
-        "gpu_memory_gb": 0,
-    });
+    settings_file::save(&app_data_dir, crate::trash::SETTINGS_STEM, &settings)
+        .map_err(|e| format!("Failed to save trash settings: {}", e))?;
 
-    // Task 9.2: Calculate recommended model based on STT-REQ-006.2
-    let recommended_model = calculate_recommended_model(&system_resources);
+    log_info_details!(
+        "commands::settings",
+        "trash_settings_saved",
+        json!({ "retention_days": settings.retention_days })
+    );
+    Ok(())
+}
 
-    Ok(serde_json::json!({
-        "available_models": models,
-        "system_resources": system_resources,
-        "recommended_model": recommended_model,
-    }))
+/// Load trash settings from disk
+#[tauri::command]
+pub async fn load_trash_settings(app: AppHandle) -> Result<crate::trash::TrashSettings, String> {
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::load(&app_data_dir, crate::trash::SETTINGS_STEM)
+        .map_err(|e| format!("Failed to load trash settings: {}", e))
 }
 
-/// Calculate recommended Whisper model based on system resources
-/// Implements STT-REQ-006.2 model selection rules
-fn calculate_recommended_model(resources: &serde_json::Value) -> String {
-    let memory_gb = resources["total_memory_gb"].as_f64().unwrap_or(4.0);
-    let gpu_available = resources["gpu_available"].as_bool().unwrap_or(false);
-    let gpu_memory_gb = resources["gpu_memory_gb"].as_f64().unwrap_or(0.0);
+/// Validate the default session audio format and make it current
+fn apply_audio_format_settings(
+    app: &AppHandle,
+    settings: &crate::audio_format::AudioFormatSettings,
+) -> Result<(), String> {
+    crate::audio_format::validate_format(settings.format).map_err(|e| e.to_string())?;
+    app.state::<AppState>()
+        .set_audio_format_settings(settings.clone());
+    Ok(())
+}
 
-    if gpu_available && memory_gb >= 8.0 && gpu_memory_gb >= 10.0 {
-        "large-v3".to_string()
-    } else if gpu_available && memory_gb >= 4.0 && gpu_memory_gb >= 5.0 {
-        "medium".to_string()
-    } else if memory_gb >= 4.0 {
-        "small".to_string()
-    } else if memory_gb >= 2.0 {
-        "base".to_string()
-    } else {
-        "tiny".to_string()
+/// Save the default session audio format
+#[tauri::command]
+pub async fn save_audio_format_settings(
+    app: AppHandle,
+    settings: crate::audio_format::AudioFormatSettings,
+) -> Result<(), String> {
+    apply_audio_format_settings(&app, &settings)?;
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::save(&app_data_dir, crate::audio_format::SETTINGS_STEM, &settings)
+        .map_err(|e| format!("Failed to save audio format settings: {}", e))?;
+
+    log_info_details!(
+        "commands::settings",
+        "audio_format_settings_saved",
+        json!({ "format": settings.format })
+    );
+    Ok(())
+}
+
+/// Load the default session audio format from disk
+#[tauri::command]
+pub async fn load_audio_format_settings(
+    app: AppHandle,
+) -> Result<crate::audio_format::AudioFormatSettings, String> {
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::load(&app_data_dir, crate::audio_format::SETTINGS_STEM)
+        .map_err(|e| format!("Failed to load audio format settings: {}", e))
+}
+
+/// Validate the partial text forwarding granularity and make it current
+fn apply_partial_granularity_settings(
+    app: &AppHandle,
+    settings: &crate::partial_granularity::PartialGranularitySettings,
+) -> Result<(), String> {
+    settings.validate().map_err(|e| e.to_string())?;
+    app.state::<AppState>()
+        .set_partial_granularity_settings(settings.clone());
+    Ok(())
+}
+
+/// Save the partial text forwarding granularity
+///
+/// Applies to the running session from its next partial.
+#[tauri::command]
+pub async fn save_partial_granularity_settings(
+    app: AppHandle,
+    settings: crate::partial_granularity::PartialGranularitySettings,
+) -> Result<(), String> {
+    apply_partial_granularity_settings(&app, &settings)?;
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::save(
+        &app_data_dir,
+        crate::partial_granularity::SETTINGS_STEM,
+        &settings,
+    )
+    .map_err(|e| format!("Failed to save partial granularity settings: {}", e))?;
+
+    log_info_details!(
+        "commands::settings",
+        "partial_granularity_settings_saved",
+        json!({
+            "granularity": settings.granularity,
+            "max_pending_chars": settings.max_pending_chars
+        })
+    );
+    Ok(())
+}
+
+/// Load the partial text forwarding granularity from disk
+#[tauri::command]
+pub async fn load_partial_granularity_settings(
+    app: AppHandle,
+) -> Result<crate::partial_granularity::PartialGranularitySettings, String> {
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::load(&app_data_dir, crate::partial_granularity::SETTINGS_STEM)
+        .map_err(|e| format!("Failed to load partial granularity settings: {}", e))
+}
+
+// ============================================================================
+// Encryption Commands
+// ============================================================================
+
+/// Load the session encryption for the storage service
+///
+/// A keychain failure leaves storage without a key: sessions are then
+/// written in plaintext and encrypted sessions cannot be opened.
+pub(crate) fn load_storage_encryption(
+    settings: &crate::encryption::EncryptionSettings,
+    workspace: &str,
+) -> Option<crate::encryption::SessionEncryption> {
+    match crate::encryption::load_session_encryption(settings, workspace) {
+        Ok(encryption) => encryption,
+        Err(e) => {
+            log_error_details!(
+                "commands::encryption",
+                "keychain_unavailable",
+                json!({ "enabled": settings.enabled, "error": format!("{:#}", e) })
+            );
+            None
+        }
     }
 }
 
-/// List available audio input devices
-/// Task 9.1: Audio device selection UI
-/// Requirement: STT-REQ-001.1, STT-REQ-001.2
+/// Save encryption settings and apply them to new writes
 ///
-/// IMPORTANT: Decoupled from recorder instance to allow enumeration before recording starts.
-/// This matches the real device adapter pattern (CoreAudio/WASAPI/ALSA perform static host queries).
+/// Enabling creates the key in the OS keychain on first use; the change is
+/// refused if the keychain is unavailable. Existing files are not rewritten.
 #[tauri::command]
-pub async fn list_audio_devices(
-    _state: State<'_, AppState>,
-) -> Result<Vec<crate::audio_device_adapter::AudioDeviceInfo>, String> {
-    log_info!("commands::audio_devices", "enumerate_requested");
+pub async fn save_encryption_settings(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    settings: crate::encryption::EncryptionSettings,
+) -> Result<(), String> {
+    if state.is_recording() {
+        return Err("Cannot change encryption while recording".to_string());
+    }
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+
+    // Keychain access may block on a user prompt
+    let keychain_settings = settings.clone();
+    let workspace = state.get_active_workspace();
+    let encryption = tokio::task::spawn_blocking(move || {
+        crate::encryption::load_session_encryption(&keychain_settings, &workspace)
+    })
+    .await
+    .map_err(|e| format!("Keychain task failed: {}", e))?
+    .map_err(|e| format!("Failed to access the encryption key: {:#}", e))?;
+
+    settings_file::save(&app_data_dir, crate::encryption::SETTINGS_STEM, &settings)
+        .map_err(|e| format!("Failed to save encryption settings: {}", e))?;
+    state.set_storage_service(storage.with_encryption(encryption));
+
+    log_info_details!(
+        "commands::settings",
+        "encryption_settings_saved",
+        json!({ "enabled": settings.enabled })
+    );
+    Ok(())
+}
+
+/// Load encryption settings from disk
+#[tauri::command]
+pub async fn load_encryption_settings(
+    app: AppHandle,
+) -> Result<crate::encryption::EncryptionSettings, String> {
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::load(&app_data_dir, crate::encryption::SETTINGS_STEM)
+        .map_err(|e| format!("Failed to load encryption settings: {}", e))
+}
+
+// ============================================================================
+// Retention Commands
+// ============================================================================
+
+/// Session excluded from retention: the one being recorded
+fn retention_skip_session(state: &AppState) -> Option<String> {
+    let recording = state.is_recording();
+    if recording {
+        state.get_session_id()
+    } else {
+        None
+    }
+}
 
-    // Task 9.1: Use static enumeration (no dependency on initialized recorder)
-    // MVP1: Real device adapter enumeration
-    match crate::audio_device_adapter::enumerate_devices_static() {
-        Ok(devices) => {
-            log_info_details!(
-                "commands::audio_devices",
-                "enumerate_success",
-                json!({ "count": devices.len() })
-            );
-            for device in &devices {
-                log_debug_details!(
-                    "commands::audio_devices",
-                    "device_entry",
+/// Queue a retention run as a maintenance job (no-op when disabled)
+///
+/// Maintenance jobs don't start while recording, so sessions are never
+/// moved during a meeting. A run that removes anything emits
+/// `retention_applied` with its summary.
+pub(crate) fn start_retention(app: &AppHandle) -> Result<bool, String> {
+    let state = app.state::<AppState>();
+    let settings = state.get_retention_settings();
+    if !settings.enabled {
+        return Ok(false);
+    }
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+
+    let job_app = app.clone();
+    state.jobs.spawn(
+        crate::jobs::JobCategory::Maintenance,
+        "Apply session retention",
+        move |ctx| {
+            ctx.checkpoint()?;
+            let state = job_app.state::<AppState>();
+            let skip_session = retention_skip_session(&state);
+            let summary = crate::retention::apply(
+                &storage,
+                &settings,
+                skip_session.as_deref(),
+                now_epoch_ms(),
+            )?;
+
+            if !summary.removed.is_empty() || !summary.failed.is_empty() {
+                log_info_details!(
+                    "commands::retention",
+                    "retention_applied",
                     json!({
-                        "id": device.id,
-                        "name": device.name,
-                        "sample_rate": device.sample_rate,
-                        "channels": device.channels,
-                        "loopback": device.is_loopback
+                        "action": summary.action,
+                        "removed": summary.removed.iter().map(|c| &c.session_id).collect::<Vec<_>>(),
+                        "failed": summary.failed,
+                        "freed_bytes": summary.freed_bytes,
+                        "remaining_sessions": summary.remaining_sessions
                     })
                 );
             }
-            Ok(devices)
-        }
-        Err(e) => {
-            log_error_details!(
-                "commands::audio_devices",
-                "enumerate_failed",
-                json!({ "error": e.to_string() })
-            );
-            Err(format!("Failed to list audio devices: {}", e))
+            if !summary.removed.is_empty() {
+                let _ = job_app.emit("retention_applied", &summary);
+            }
+            state.set_last_retention(summary);
+            Ok(())
+        },
+    );
+    Ok(true)
+}
+
+/// Re-run retention every `check_interval_hours` (re-read after each run)
+pub(crate) fn start_retention_timer(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let interval = app
+                .state::<AppState>()
+                .get_retention_settings()
+                .check_interval();
+            tokio::time::sleep(interval).await;
+            if let Err(e) = start_retention(&app) {
+                log_warn!("commands::retention", "retention_start_failed", e);
+            }
         }
-    }
+    });
 }
 
-// ============================================================================
-// Multi-Input Settings Commands (Task 7)
-// ============================================================================
+/// Sessions the next retention run would remove, oldest first
+#[tauri::command]
+pub fn preview_retention(
+    state: State<'_, AppState>,
+    settings: Option<crate::retention::RetentionSettings>,
+) -> Result<Vec<crate::retention::RetentionCandidate>, String> {
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+    let settings = settings.unwrap_or_else(|| state.get_retention_settings());
+
+    crate::retention::preview(
+        &storage,
+        &settings,
+        retention_skip_session(&state).as_deref(),
+        now_epoch_ms(),
+    )
+    .map_err(|e| format!("Failed to scan sessions: {}", e))
+}
 
-/// Save multi-input settings to disk
+/// Queue a retention run now; false if retention is disabled
+#[tauri::command]
+pub fn run_retention(app: AppHandle) -> Result<bool, String> {
+    start_retention(&app)
+}
+
+/// Outcome of the last retention run since app start
+#[tauri::command]
+pub fn get_last_retention_summary(
+    state: State<'_, AppState>,
+) -> Option<crate::retention::RetentionSummary> {
+    state.get_last_retention()
+}
+
+/// Validate retention settings and make them current
 ///
-/// Requirement: STTMIX-REQ-001.2, STTMIX-REQ-005.1
+/// The archive directory is validated when archiving is selected.
+fn apply_retention_settings(
+    app: &AppHandle,
+    settings: &crate::retention::RetentionSettings,
+) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    if settings.action == crate::retention::RetentionAction::Archive {
+        let archive_dir = settings
+            .archive_dir
+            .as_deref()
+            .ok_or_else(|| "Archive directory is required for archiving".to_string())?;
+        let recordings_dir = match state.get_storage_service() {
+            Some(storage) => storage.recordings_dir().to_path_buf(),
+            None => crate::storage_root::default_recordings_dir(
+                &workspace_data_dir(app)
+                    .map_err(|e| format!("Failed to get app data directory: {}", e))?,
+            ),
+        };
+        crate::retention::validate_archive_dir(archive_dir, &recordings_dir)
+            .map_err(|e| format!("Invalid archive directory: {}", e))?;
+    }
+    state.set_retention_settings(settings.clone());
+    Ok(())
+}
+
+/// Save retention settings and apply them from the next run
 #[tauri::command]
-pub async fn save_multi_input_settings(
+pub async fn save_retention_settings(
     app: AppHandle,
-    settings: crate::multi_input_settings::MultiInputSettings,
+    settings: crate::retention::RetentionSettings,
 ) -> Result<(), String> {
-    use crate::multi_input_settings::save_settings;
+    apply_retention_settings(&app, &settings)?;
 
-    let app_data_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
 
-    save_settings(&app_data_dir, &settings)
-        .map_err(|e| format!("Failed to save multi-input settings: {}", e))?;
+    settings_file::save(&app_data_dir, crate::retention::SETTINGS_STEM, &settings)
+        .map_err(|e| format!("Failed to save retention settings: {}", e))?;
 
     log_info_details!(
         "commands::settings",
-        "multi_input_settings_saved",
+        "retention_settings_saved",
         json!({
-            "device_count": settings.selected_device_ids.len(),
-            "enabled": settings.multi_input_enabled
+            "enabled": settings.enabled,
+            "max_age_days": settings.max_age_days,
+            "max_sessions": settings.max_sessions,
+            "max_total_gb": settings.max_total_gb,
+            "action": settings.action
         })
     );
-
     Ok(())
 }
 
-/// Load multi-input settings from disk
-///
-/// Requirement: STTMIX-REQ-001.2
+/// Load retention settings from disk
 #[tauri::command]
-pub async fn load_multi_input_settings(
+pub async fn load_retention_settings(
     app: AppHandle,
-) -> Result<crate::multi_input_settings::MultiInputSettings, String> {
-    use crate::multi_input_settings::load_settings;
+) -> Result<crate::retention::RetentionSettings, String> {
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
 
-    let app_data_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    settings_file::load(&app_data_dir, crate::retention::SETTINGS_STEM)
+        .map_err(|e| format!("Failed to load retention settings: {}", e))
+}
 
-    let settings = load_settings(&app_data_dir)
-        .map_err(|e| format!("Failed to load multi-input settings: {}", e))?;
+// ============================================================================
+// Storage Root Commands
+// ============================================================================
+
+/// Build the storage service for the configured recordings root
+///
+/// Falls back to the default root when the configured one is unavailable
+/// (e.g. an external drive that isn't connected).
+pub(crate) fn storage_for_root(
+    app_data_dir: std::path::PathBuf,
+    settings: &crate::storage_root::StorageRootSettings,
+) -> crate::storage::LocalStorageService {
+    if let Some(root) = &settings.recordings_root {
+        if let Err(e) = crate::storage_root::validate_root(root) {
+            log_error_details!(
+                "commands::storage_root",
+                "storage_root_unavailable",
+                json!({
+                    "root": root.display().to_string(),
+                    "error": format!("{:#}", e)
+                })
+            );
+            return crate::storage::LocalStorageService::new(app_data_dir);
+        }
+    }
+    let recordings_dir = settings.recordings_dir(&app_data_dir);
+    crate::storage::LocalStorageService::with_recordings_dir(app_data_dir, recordings_dir)
+}
+
+/// Result of `set_storage_root`
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct StorageRootChange {
+    pub recordings_dir: String,
+    pub migration: Option<crate::storage_root::MigrationReport>,
+}
+
+/// Get the directory recordings are stored in
+#[tauri::command]
+pub fn get_storage_root(state: State<'_, AppState>) -> Result<String, String> {
+    let storage = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+    Ok(storage.recordings_dir().display().to_string())
+}
+
+/// Change the recordings root (None = default `[app_data_dir]/recordings`)
+///
+/// Validates the new root, optionally moves existing recordings there, then
+/// saves the setting and switches storage immediately. Refused while
+/// recording or while background maintenance runs.
+#[tauri::command]
+pub async fn set_storage_root(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    path: Option<String>,
+    migrate: bool,
+) -> Result<StorageRootChange, String> {
+    if state.is_recording() {
+        return Err("Cannot change the storage root while recording".to_string());
+    }
+    if state.maintenance.is_running() {
+        return Err("Cannot change the storage root while maintenance is running".to_string());
+    }
+    let app_data_dir =
+        workspace_data_dir(&app).map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    let current = state
+        .get_storage_service()
+        .ok_or_else(|| "Storage not initialized".to_string())?;
+
+    let settings = crate::storage_root::StorageRootSettings {
+        recordings_root: path.map(std::path::PathBuf::from),
+        ..Default::default()
+    };
+    let new_dir = settings.recordings_dir(&app_data_dir);
+    let old_dir = current.recordings_dir().to_path_buf();
+
+    let (validate_dir, from, to) = (new_dir.clone(), old_dir.clone(), new_dir.clone());
+    let migration = tokio::task::spawn_blocking(move || {
+        crate::storage_root::validate_root(&validate_dir)?;
+        if migrate {
+            crate::storage_root::migrate_recordings(&from, &to).map(Some)
+        } else {
+            Ok(None)
+        }
+    })
+    .await
+    .map_err(|e| format!("Storage root task failed: {}", e))?
+    .map_err(|e| format!("Failed to change storage root: {:#}", e))?;
+
+    settings_file::save(&app_data_dir, crate::storage_root::SETTINGS_STEM, &settings)
+        .map_err(|e| format!("Failed to save storage root settings: {}", e))?;
+    state.set_storage_service(
+        crate::storage::LocalStorageService::with_recordings_dir(app_data_dir, new_dir.clone())
+            .with_encryption(current.encryption().cloned()),
+    );
 
     log_info_details!(
-        "commands::settings",
-        "multi_input_settings_loaded",
+        "commands::storage_root",
+        "storage_root_changed",
         json!({
-            "device_count": settings.selected_device_ids.len(),
-            "enabled": settings.multi_input_enabled
+            "from": old_dir.display().to_string(),
+            "to": new_dir.display().to_string(),
+            "moved": migration.as_ref().map(|m| m.moved.len()),
+            "conflicts": migration.as_ref().map(|m| m.conflicts.clone())
         })
     );
+    Ok(StorageRootChange {
+        recordings_dir: new_dir.display().to_string(),
+        migration,
+    })
+}
 
-    Ok(settings)
+// ============================================================================
+// Workspace Commands
+// ============================================================================
+
+/// Data directory of the active workspace (settings, recordings)
+pub(crate) fn workspace_data_dir(app: &AppHandle) -> tauri::Result<std::path::PathBuf> {
+    let app_data_dir = app.path().app_data_dir()?;
+    let workspace = app.state::<AppState>().get_active_workspace();
+    Ok(crate::workspaces::data_dir(&app_data_dir, &workspace))
 }
 
-/// Get platform information for feature gating
-///
-/// Returns the current OS platform. Multi-input is only supported on macOS.
-///
-/// Requirement: STTMIX-CON-004
-#[tauri::command]
-pub fn get_platform_info() -> PlatformInfo {
-    PlatformInfo {
-        os: std::env::consts::OS.to_string(),
-        multi_input_supported: cfg!(target_os = "macos"),
+/// Directories holding `settings/`: machine-wide, then the active workspace
+/// (the same directory for the default workspace)
+pub(crate) fn settings_dirs(app: &AppHandle) -> Vec<std::path::PathBuf> {
+    let mut dirs: Vec<std::path::PathBuf> = app.path().app_data_dir().into_iter().collect();
+    if let Ok(workspace_dir) = workspace_data_dir(app) {
+        if !dirs.contains(&workspace_dir) {
+            dirs.push(workspace_dir);
+        }
     }
+    dirs
 }
 
-#[derive(serde::Serialize)]
-pub struct PlatformInfo {
-    pub os: String,
-    pub multi_input_supported: bool,
+/// List the workspaces and the active one
+#[tauri::command]
+pub async fn list_workspaces(
+    app: AppHandle,
+) -> Result<crate::workspaces::WorkspaceSettings, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    settings_file::load(&app_data_dir, crate::workspaces::SETTINGS_STEM)
+        .map_err(|e| format!("Failed to load workspace settings: {}", e))
 }
 
-/// Get status of all multi-input channels
-///
-/// Returns buffer occupancy, active status, and metrics for each input.
-/// Used by UI to display input health indicators.
+/// Create an empty named workspace (does not switch to it)
+#[tauri::command]
+pub async fn create_workspace(
+    app: AppHandle,
+    name: String,
+) -> Result<crate::workspaces::WorkspaceSettings, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    let mut settings: crate::workspaces::WorkspaceSettings =
+        settings_file::load(&app_data_dir, crate::workspaces::SETTINGS_STEM)
+            .map_err(|e| format!("Failed to load workspace settings: {}", e))?;
+
+    let created_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64;
+    settings
+        .create(&name, created_at)
+        .map_err(|e| e.to_string())?;
+    let data_dir = crate::workspaces::data_dir(&app_data_dir, &name);
+    std::fs::create_dir_all(&data_dir)
+        .map_err(|e| format!("Failed to create workspace directory: {}", e))?;
+    settings_file::save(&app_data_dir, crate::workspaces::SETTINGS_STEM, &settings)
+        .map_err(|e| format!("Failed to save workspace settings: {}", e))?;
+
+    log_info_details!(
+        "commands::workspace",
+        "workspace_created",
+        json!({ "workspace": name })
+    );
+    Ok(settings)
+}
+
+/// Switch to another workspace
 ///
-/// Requirement: STTMIX-REQ-008.1, Task 8.3
+/// Reloads every setting and the storage (recordings root, encryption key)
+/// from the workspace's data directory, runs crash recovery there and makes
+/// it the workspace opened at the next start. Refused while recording or
+/// while background maintenance runs. Emits `workspace_switched`.
 #[tauri::command]
-pub async fn get_multi_input_status(
+pub async fn switch_workspace(
+    app: AppHandle,
     state: State<'_, AppState>,
-) -> Result<MultiInputStatusResponse, String> {
-    let recorder_opt = state.audio_recorder.lock().unwrap().clone();
-    let recorder_arc = recorder_opt.ok_or("Audio recorder not initialized")?;
-    let recorder = recorder_arc.lock().await;
-
-    let input_statuses = recorder.get_input_status();
-    let mixer_metrics = recorder.get_mixer_metrics();
+    name: String,
+) -> Result<(), String> {
+    if state.is_recording() {
+        return Err("Cannot switch workspaces while recording".to_string());
+    }
+    if state.maintenance.is_running() {
+        return Err("Cannot switch workspaces while maintenance is running".to_string());
+    }
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    let mut settings: crate::workspaces::WorkspaceSettings =
+        settings_file::load(&app_data_dir, crate::workspaces::SETTINGS_STEM)
+            .map_err(|e| format!("Failed to load workspace settings: {}", e))?;
+    if !settings.contains(&name) {
+        return Err(format!("Unknown workspace: {}", name));
+    }
+    let previous = state.get_active_workspace();
+    if previous == name {
+        return Ok(());
+    }
 
-    Ok(MultiInputStatusResponse {
-        inputs: input_statuses,
-        is_recording: recorder.is_recording(),
-        mixer_metrics: mixer_metrics.map(|m| MixerMetricsResponse {
-            drift_correction_count: m.get_drift_correction_count(),
-            clip_count: m.get_clip_count(),
-            silence_insertion_count: m.get_silence_insertion_count(),
-            frames_mixed: m.get_frames_mixed(),
-            max_mix_latency_ms: m.get_max_mix_latency_ms(),
-            avg_mix_latency_ms: m.get_avg_mix_latency_ms(),
-        }),
+    settings.active = name.clone();
+    settings_file::save(&app_data_dir, crate::workspaces::SETTINGS_STEM, &settings)
+        .map_err(|e| format!("Failed to save workspace settings: {}", e))?;
+    state.set_active_workspace(name.clone());
+    // Recovery results of the previous workspace no longer apply
+    *state.interrupted_recording.lock().unwrap() = None;
+    state.set_recovered_sessions(Vec::new());
+
+    // Keychain access may block on a user prompt
+    let load_app = app.clone();
+    let workspace = name.clone();
+    tokio::task::spawn_blocking(move || {
+        let data_dir = crate::workspaces::data_dir(&app_data_dir, &workspace);
+        crate::load_workspace(
+            &load_app,
+            &load_app.state::<AppState>(),
+            &workspace,
+            data_dir,
+        );
     })
-}
+    .await
+    .map_err(|e| format!("Workspace switch task failed: {}", e))?;
 
-#[derive(serde::Serialize)]
-pub struct MultiInputStatusResponse {
-    pub inputs: Vec<InputStatus>,
-    pub is_recording: bool,
-    pub mixer_metrics: Option<MixerMetricsResponse>,
+    log_info_details!(
+        "commands::workspace",
+        "workspace_switched",
+        json!({ "from": previous, "to": name })
+    );
+    let _ = app.emit("workspace_switched", &name);
+    Ok(())
 }
 
-#[derive(serde::Serialize)]
-pub struct MixerMetricsResponse {
-    pub drift_correction_count: u64,
-    pub clip_count: u64,
-    pub silence_insertion_count: u64,
-    pub frames_mixed: u64,
-    // Task 9.1: Latency metrics
-    pub max_mix_latency_ms: f64,
-    pub avg_mix_latency_ms: f64,
+// ============================================================================
+// Viewer Mode Commands
+// ============================================================================
+
+/// Whether this run is in read-only viewer mode
+#[tauri::command]
+pub fn get_viewer_mode(state: State<'_, AppState>) -> bool {
+    state.is_viewer_mode()
 }
 
-/// Validate that selected devices are still available
-///
-/// Returns list of unavailable device IDs
-///
-/// Requirement: STTMIX-REQ-001.2
+/// Save viewer mode settings (applied at the next start)
 #[tauri::command]
-pub async fn validate_multi_input_devices(
+pub async fn save_viewer_mode_settings(
     app: AppHandle,
-    state: State<'_, AppState>,
-) -> Result<Vec<String>, String> {
-    use crate::multi_input_settings::{load_settings, validate_devices};
-
-    // Load current settings
+    settings: crate::viewer_mode::ViewerModeSettings,
+) -> Result<(), String> {
     let app_data_dir = app
         .path()
         .app_data_dir()
         .map_err(|e| format!("Failed to get app data directory: {}", e))?;
 
-    let settings = load_settings(&app_data_dir)
-        .map_err(|e| format!("Failed to load settings: {}", e))?;
-
-    // Get available devices using the recorder's factory
-    let recorder_opt = state.audio_recorder.lock().unwrap().clone();
-    let available_devices: Vec<String> = if let Some(recorder_arc) = recorder_opt {
-        let recorder = recorder_arc.lock().await;
-        recorder
-            .enumerate_devices()
-            .map_err(|e| format!("Failed to enumerate devices: {}", e))?
-            .into_iter()
-            .map(|d| d.id)
-            .collect()
-    } else {
-        Vec::new()
-    };
+    settings_file::save(&app_data_dir, crate::viewer_mode::SETTINGS_STEM, &settings)
+        .map_err(|e| format!("Failed to save viewer mode settings: {}", e))?;
 
-    // Find unavailable devices
-    let unavailable = validate_devices(&settings, &available_devices);
+    log_info_details!(
+        "commands::settings",
+        "viewer_mode_settings_saved",
+        json!({ "enabled": settings.enabled })
+    );
+    Ok(())
+}
 
-    if !unavailable.is_empty() {
-        log_warn_details!(
-            "commands::settings",
-            "unavailable_devices_detected",
-            json!({
-                "unavailable": unavailable,
-                "total_selected": settings.selected_device_ids.len()
-            })
-        );
-    }
+/// Load viewer mode settings from disk
+#[tauri::command]
+pub async fn load_viewer_mode_settings(
+    app: AppHandle,
+) -> Result<crate::viewer_mode::ViewerModeSettings, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
 
-    Ok(unavailable)
+    settings_file::load(&app_data_dir, crate::viewer_mode::SETTINGS_STEM)
+        .map_err(|e| format!("Failed to load viewer mode settings: {}", e))
 }
 
 #[cfg(test)]
@@ -0,0 +1,231 @@
+//! Recording Consent Announcement
+//!
+//! Some jurisdictions require telling participants that a meeting is being
+//! recorded. When enabled, starting a recording:
+//! - plays a short announcement on the selected output device (a WAV file
+//!   chosen by the user, or a built-in chime)
+//! - broadcasts a `notification` of type `recording_consent` to WebSocket
+//!   clients, so the extension can post the message to the meeting chat
+//!
+//! Persisted to `settings/consent_announcement.json` in app data directory.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// `notificationType` of the WebSocket notification
+pub const NOTIFICATION_TYPE: &str = "recording_consent";
+
+/// Longest announcement that is played
+pub const MAX_ANNOUNCEMENT_SECS: u64 = 30;
+
+/// Longest chat message
+pub const MAX_MESSAGE_CHARS: usize = 500;
+
+/// Sample rate of the built-in chime
+const CHIME_SAMPLE_RATE: u32 = 16000;
+
+/// Consent announcement settings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConsentAnnouncementSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Play the audio announcement
+    #[serde(default = "default_true")]
+    pub play_audio: bool,
+    /// Output device name (None: default output device)
+    #[serde(default)]
+    pub output_device: Option<String>,
+    /// 16-bit PCM WAV to play (None: built-in chime)
+    #[serde(default)]
+    pub audio_path: Option<PathBuf>,
+    /// Send the chat notification to WebSocket clients
+    #[serde(default = "default_true")]
+    pub notify_clients: bool,
+    #[serde(default = "default_message")]
+    pub message: String,
+    /// Settings version for future migrations
+    #[serde(default = "default_version")]
+    pub version: u32,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_message() -> String {
+    "This meeting is being transcribed".to_string()
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+impl Default for ConsentAnnouncementSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            play_audio: true,
+            output_device: None,
+            audio_path: None,
+            notify_clients: true,
+            message: default_message(),
+            version: 1,
+        }
+    }
+}
+
+impl ConsentAnnouncementSettings {
+    pub fn validate(&self) -> Result<()> {
+        if self.notify_clients && self.message.trim().is_empty() {
+            anyhow::bail!("The announcement message must not be empty");
+        }
+        if self.message.chars().count() > MAX_MESSAGE_CHARS {
+            anyhow::bail!(
+                "The announcement message must be at most {} characters",
+                MAX_MESSAGE_CHARS
+            );
+        }
+        if let Some(path) = &self.audio_path {
+            read_announcement_wav(path)?;
+        }
+        Ok(())
+    }
+}
+
+/// Mono samples and sample rate of the audio announcement
+pub fn announcement_audio(settings: &ConsentAnnouncementSettings) -> Result<(Vec<i16>, u32)> {
+    match &settings.audio_path {
+        Some(path) => read_announcement_wav(path),
+        None => Ok((chime(), CHIME_SAMPLE_RATE)),
+    }
+}
+
+/// Read a 16-bit PCM WAV, downmixed to mono
+fn read_announcement_wav(path: &Path) -> Result<(Vec<i16>, u32)> {
+    use std::io::Read;
+
+    let mut header = Vec::with_capacity(512);
+    std::fs::File::open(path)
+        .and_then(|file| file.take(512).read_to_end(&mut header))
+        .with_context(|| format!("Failed to read announcement file: {:?}", path))?;
+    let (channels, sample_rate) = wav_format(&header)
+        .with_context(|| format!("Announcement must be a 16-bit PCM WAV: {:?}", path))?;
+
+    let mut interleaved = Vec::new();
+    crate::storage::read_wav_samples(path, |chunk| interleaved.extend_from_slice(chunk))?;
+    let samples: Vec<i16> = interleaved
+        .chunks(channels as usize)
+        .map(|frame| (frame.iter().map(|&s| s as i32).sum::<i32>() / frame.len() as i32) as i16)
+        .collect();
+
+    if samples.len() as u64 > MAX_ANNOUNCEMENT_SECS * sample_rate as u64 {
+        anyhow::bail!(
+            "Announcement is longer than {} seconds: {:?}",
+            MAX_ANNOUNCEMENT_SECS,
+            path
+        );
+    }
+    Ok((samples, sample_rate))
+}
+
+/// Channels and sample rate from the `fmt ` chunk of a 16-bit PCM WAV
+fn wav_format(header: &[u8]) -> Option<(u16, u32)> {
+    if header.get(0..4) != Some(b"RIFF".as_slice()) || header.get(8..12) != Some(b"WAVE".as_slice())
+    {
+        return None;
+    }
+    let mut offset = 12usize;
+    while let Some(chunk) = header.get(offset..offset + 8) {
+        let size = u32::from_le_bytes(chunk[4..8].try_into().ok()?) as usize;
+        if &chunk[0..4] == b"fmt " {
+            let fmt = header.get(offset + 8..offset + 24)?;
+            let u16_at = |i: usize| u16::from_le_bytes([fmt[i], fmt[i + 1]]);
+            let (format, channels, bits) = (u16_at(0), u16_at(2), u16_at(14));
+            let sample_rate = u32::from_le_bytes(fmt[4..8].try_into().ok()?);
+            let pcm16 = format == 1 && bits == 16;
+            return (pcm16 && channels > 0 && sample_rate > 0).then_some((channels, sample_rate));
+        }
+        offset += 8 + size + (size & 1);
+    }
+    None
+}
+
+/// Two-tone chime (880Hz, 660Hz) with short fades
+fn chime() -> Vec<i16> {
+    const TONE_MS: u32 = 250;
+    const FADE_SAMPLES: usize = 160;
+    let tone_samples = (CHIME_SAMPLE_RATE * TONE_MS / 1000) as usize;
+
+    [880.0f32, 660.0]
+        .into_iter()
+        .flat_map(|freq| {
+            (0..tone_samples).map(move |i| {
+                let fade =
+                    i.min(tone_samples - 1 - i).min(FADE_SAMPLES) as f32 / FADE_SAMPLES as f32;
+                let phase = 2.0 * std::f32::consts::PI * freq * i as f32 / CHIME_SAMPLE_RATE as f32;
+                (phase.sin() * fade * 0.3 * i16::MAX as f32) as i16
+            })
+        })
+        .collect()
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+pub const SETTINGS_STEM: &str = "consent_announcement";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_wav(path: &Path, channels: u16, sample_rate: u32, samples: &[i16]) {
+        let data_len = (samples.len() * 2) as u32;
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(b"RIFF");
+        bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+        bytes.extend_from_slice(b"WAVEfmt ");
+        bytes.extend_from_slice(&16u32.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+        bytes.extend_from_slice(&channels.to_le_bytes());
+        bytes.extend_from_slice(&sample_rate.to_le_bytes());
+        bytes.extend_from_slice(&(sample_rate * channels as u32 * 2).to_le_bytes());
+        bytes.extend_from_slice(&(channels * 2).to_le_bytes());
+        bytes.extend_from_slice(&16u16.to_le_bytes());
+        bytes.extend_from_slice(b"data");
+        bytes.extend_from_slice(&data_len.to_le_bytes());
+        bytes.extend(samples.iter().flat_map(|s| s.to_le_bytes()));
+        std::fs::write(path, bytes).unwrap();
+    }
+
+    #[test]
+    fn test_announcement_audio() {
+        let dir = TempDir::new().unwrap();
+        let settings = ConsentAnnouncementSettings::default();
+        let (samples, rate) = announcement_audio(&settings).unwrap();
+        assert_eq!((samples.len(), rate), (8000, 16000));
+
+        // Stereo WAV is downmixed to mono at its own rate
+        let path = dir.path().join("announcement.wav");
+        write_wav(&path, 2, 44100, &[100, 300, -200, -400]);
+        let settings = ConsentAnnouncementSettings {
+            audio_path: Some(path.clone()),
+            ..Default::default()
+        };
+        assert!(settings.validate().is_ok());
+        assert_eq!(
+            announcement_audio(&settings).unwrap(),
+            (vec![200, -300], 44100)
+        );
+
+        std::fs::write(&path, b"not a wav file").unwrap();
+        assert!(settings.validate().is_err());
+        let empty_message = ConsentAnnouncementSettings {
+            message: " ".to_string(),
+            ..Default::default()
+        };
+        assert!(empty_message.validate().is_err());
+    }
+}
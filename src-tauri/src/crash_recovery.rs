@@ -0,0 +1,227 @@
+//! Crash Recovery of Unfinalized Sessions
+//!
+//! session.json is only written when a recording stops, and the audio.wav
+//! header sizes are only patched on a clean close. A session directory with
+//! audio or a transcript but no session.json was therefore cut off by a
+//! crash. The heartbeat recovers the last recording with its exact start
+//! time (see `heartbeat`); this startup scan repairs every other unfinalized
+//! session, e.g. one that crashed before its first heartbeat or whose
+//! heartbeat was lost to a later crash.
+//!
+//! Per session:
+//! - audio.wav header sizes are recomputed from the file length (likewise
+//!   for the per-input tracks)
+//! - session.json is synthesized: the session ends at the last write to its
+//!   files, and lasts as long as its audio or transcript (whichever is longer)
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use crate::storage::{
+    LocalStorageService, SessionMetadata, METADATA_FILENAME, TRANSCRIPT_FILENAME,
+};
+
+const SAMPLE_RATE: u64 = 16000;
+
+/// Warning recorded in the synthesized session.json
+pub const RECOVERY_WARNING: &str =
+    "Recovered after a crash; timing estimated from the session files";
+
+/// Session repaired at startup
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecoveredSession {
+    pub session_id: String,
+    /// Estimated start (ISO 8601)
+    pub start_time: String,
+    pub duration_seconds: u64,
+    /// Audio in the repaired session audio file (seconds)
+    pub recovered_audio_seconds: f64,
+    /// Final segments found in the transcript
+    pub total_segments: u64,
+}
+
+/// Session directories under `recordings_dir` that were never finalized
+///
+/// `skip_session` (a session still being recorded) is left alone.
+pub fn find_unfinalized_sessions(
+    recordings_dir: &Path,
+    skip_session: Option<&str>,
+) -> Result<Vec<String>> {
+    if !recordings_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut sessions: Vec<String> = std::fs::read_dir(recordings_dir)
+        .with_context(|| format!("Failed to read recordings directory: {:?}", recordings_dir))?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir() && is_unfinalized(path))
+        .filter_map(|path| Some(path.file_name()?.to_str()?.to_string()))
+        .filter(|id| Some(id.as_str()) != skip_session)
+        // Hidden directories are not sessions (e.g. the `.trash` area)
+        .filter(|id| !id.starts_with('.'))
+        .collect();
+    sessions.sort();
+    Ok(sessions)
+}
+
+fn is_unfinalized(session_dir: &Path) -> bool {
+    !session_dir.join(METADATA_FILENAME).exists()
+        && (crate::storage::session_audio_path(session_dir).is_some()
+            || session_dir.join(TRANSCRIPT_FILENAME).exists())
+}
+
+/// Repair an unfinalized session and write its session.json
+pub fn repair_session(storage: &LocalStorageService, session_id: &str) -> Result<RecoveredSession> {
+    let session_dir = storage.get_session_dir(session_id);
+    let audio_path = crate::storage::session_audio_path(&session_dir);
+
+    let audio_samples = match &audio_path {
+        Some(path) if path.extension().and_then(|e| e.to_str()) == Some("wav") => {
+            crate::storage::repair_wav_header(path)?
+        }
+        // FLAC/Opus stay readable up to their last complete frame/page
+        Some(_) => count_session_audio_samples(&session_dir),
+        None => 0,
+    };
+    crate::input_tracks::repair_tracks(&session_dir)?;
+
+    let transcript = storage.load_transcript(session_id)?;
+    let transcript_ms = transcript.iter().map(|e| e.timestamp_ms).max().unwrap_or(0);
+    let duration_ms = (audio_samples * 1000 / SAMPLE_RATE).max(transcript_ms);
+
+    let files: Vec<PathBuf> = audio_path
+        .into_iter()
+        .chain(Some(session_dir.join(TRANSCRIPT_FILENAME)))
+        .collect();
+    let end_ms = last_modified_ms(&files)
+        .with_context(|| format!("No readable session files in {:?}", session_dir))?;
+    let start_ms = end_ms.saturating_sub(duration_ms);
+
+    let finals = transcript.iter().filter(|e| e.is_final);
+    let metadata = SessionMetadata {
+        session_id: session_id.to_string(),
+        start_time: crate::storage::format_iso8601_millis(start_ms),
+        end_time: crate::storage::format_iso8601_millis(end_ms),
+        duration_seconds: duration_ms / 1000,
+        audio_device: "unknown".to_string(),
+        model_size: "unknown".to_string(),
+        total_segments: finals.clone().count() as u64,
+        total_characters: finals.map(|e| e.text.chars().count() as u64).sum(),
+        warnings: vec![RECOVERY_WARNING.to_string()],
+        session_uuid: None,
+        title: None,
+        tags: Vec::new(),
+        stats: None,
+        levels: None,
+    };
+    storage.save_session_metadata(&metadata)?;
+
+    Ok(RecoveredSession {
+        session_id: session_id.to_string(),
+        start_time: metadata.start_time,
+        duration_seconds: metadata.duration_seconds,
+        recovered_audio_seconds: audio_samples as f64 / SAMPLE_RATE as f64,
+        total_segments: metadata.total_segments,
+    })
+}
+
+/// Samples decodable from a FLAC/Opus session audio file (0 if unreadable)
+fn count_session_audio_samples(session_dir: &Path) -> u64 {
+    let mut samples = 0u64;
+    // A truncated stream ends in an error after the readable frames
+    let _ = crate::storage::read_session_audio(session_dir, |chunk| samples += chunk.len() as u64);
+    samples
+}
+
+/// Latest modification time (epoch millis) of the existing `files`
+fn last_modified_ms(files: &[PathBuf]) -> Option<u64> {
+    files
+        .iter()
+        .filter_map(|path| std::fs::metadata(path).ok()?.modified().ok())
+        .filter_map(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::TranscriptionEvent;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_repair_unfinalized_sessions() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = LocalStorageService::new(temp_dir.path().to_path_buf());
+
+        // Crashed mid-recording: 2s of audio, one final segment, no session.json
+        storage.create_session("crashed").unwrap();
+        let mut audio = storage.create_audio_writer("crashed").unwrap();
+        audio.write_samples(&vec![100i16; 32000]).unwrap();
+        audio.flush().unwrap();
+        std::mem::forget(audio); // no clean close: header sizes stay unwritten
+        let mut transcript = storage.create_transcript_writer("crashed").unwrap();
+        transcript
+            .append_event(&TranscriptionEvent {
+                timestamp_ms: 1_500,
+                text: "途中まで".to_string(),
+                is_final: true,
+                speaker: None,
+            })
+            .unwrap();
+        transcript.close().unwrap();
+
+        // Finalized, empty and in-progress sessions are left alone
+        storage.create_session("finished").unwrap();
+        storage
+            .create_transcript_writer("finished")
+            .unwrap()
+            .close()
+            .unwrap();
+        storage
+            .save_session_metadata(&SessionMetadata {
+                session_id: "finished".to_string(),
+                start_time: "2025-01-01T00:00:00.000Z".to_string(),
+                end_time: "2025-01-01T00:00:01.000Z".to_string(),
+                duration_seconds: 1,
+                audio_device: "mic".to_string(),
+                model_size: "small".to_string(),
+                total_segments: 0,
+                total_characters: 0,
+                warnings: Vec::new(),
+                session_uuid: None,
+                title: None,
+                tags: Vec::new(),
+                stats: None,
+                levels: None,
+            })
+            .unwrap();
+        storage.create_session("empty").unwrap();
+        storage.create_session("recording").unwrap();
+        storage
+            .create_transcript_writer("recording")
+            .unwrap()
+            .close()
+            .unwrap();
+
+        let recordings_dir = storage.recordings_dir();
+        let unfinalized = find_unfinalized_sessions(recordings_dir, Some("recording")).unwrap();
+        assert_eq!(unfinalized, vec!["crashed".to_string()]);
+
+        let recovered = repair_session(&storage, "crashed").unwrap();
+        assert_eq!(recovered.recovered_audio_seconds, 2.0);
+        assert_eq!(recovered.duration_seconds, 2);
+        assert_eq!(recovered.total_segments, 1);
+
+        let loaded = storage.load_session("crashed").unwrap();
+        assert_eq!(loaded.metadata.total_characters, 4);
+        assert_eq!(loaded.metadata.warnings, vec![RECOVERY_WARNING.to_string()]);
+        assert!(find_unfinalized_sessions(recordings_dir, None)
+            .unwrap()
+            .iter()
+            .all(|id| id != "crashed"));
+    }
+}
@@ -0,0 +1,125 @@
+//! Audio Device Aliases
+//!
+//! Device names such as `alsa_output.pci-0000_00_1f.3.analog-stereo.monitor`
+//! are hard to recognize. Users can give a device a friendly name, which is
+//! shown instead of the device name in:
+//! - `list_audio_devices` results (`name`; `id` stays the real device)
+//! - `InputStatus` (`alias`)
+//! - session metadata (`audio_device`, recorded when the session starts),
+//!   and so in exports
+//!
+//! Persisted to `settings/device_aliases.json` in app data directory.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Longest alias
+pub const MAX_ALIAS_CHARS: usize = 64;
+
+/// Most aliases kept
+pub const MAX_ALIASES: usize = 200;
+
+/// Device alias settings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeviceAliasSettings {
+    /// Alias by device ID
+    #[serde(default)]
+    pub aliases: BTreeMap<String, String>,
+    /// Settings version for future migrations
+    #[serde(default = "default_version")]
+    pub version: u32,
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+impl Default for DeviceAliasSettings {
+    fn default() -> Self {
+        Self {
+            aliases: BTreeMap::new(),
+            version: 1,
+        }
+    }
+}
+
+impl DeviceAliasSettings {
+    pub fn validate(&self) -> Result<()> {
+        if self.aliases.len() > MAX_ALIASES {
+            anyhow::bail!("At most {} device aliases can be set", MAX_ALIASES);
+        }
+        for (device_id, alias) in &self.aliases {
+            if device_id.is_empty() {
+                anyhow::bail!("Device ID must not be empty");
+            }
+            if alias.trim().is_empty() || alias.trim() != alias {
+                anyhow::bail!("Invalid alias for {}: {:?}", device_id, alias);
+            }
+            if alias.chars().count() > MAX_ALIAS_CHARS || alias.chars().any(char::is_control) {
+                anyhow::bail!(
+                    "Alias for {} must be at most {} characters without control characters",
+                    device_id,
+                    MAX_ALIAS_CHARS
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Set the alias of `device_id` (None or blank removes it)
+    pub fn set_alias(&mut self, device_id: &str, alias: Option<&str>) {
+        match alias.map(str::trim).filter(|a| !a.is_empty()) {
+            Some(alias) => {
+                self.aliases
+                    .insert(device_id.to_string(), alias.to_string());
+            }
+            None => {
+                self.aliases.remove(device_id);
+            }
+        }
+    }
+
+    pub fn alias(&self, device_id: &str) -> Option<&str> {
+        self.aliases.get(device_id).map(String::as_str)
+    }
+
+    /// Alias of `device_id`, or `fallback` without one
+    pub fn display_name<'a>(&'a self, device_id: &str, fallback: &'a str) -> &'a str {
+        self.alias(device_id).unwrap_or(fallback)
+    }
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+pub const SETTINGS_STEM: &str = "device_aliases";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_alias() {
+        let monitor = "alsa_output.pci-0000_00_1f.3.analog-stereo.monitor";
+        let mut settings = DeviceAliasSettings::default();
+        settings.set_alias(monitor, Some("  Speakers (loopback) "));
+        assert_eq!(settings.alias(monitor), Some("Speakers (loopback)"));
+        assert_eq!(
+            settings.display_name(monitor, monitor),
+            "Speakers (loopback)"
+        );
+        assert_eq!(settings.display_name("mic-1", "USB Mic"), "USB Mic");
+        assert!(settings.validate().is_ok());
+
+        // Blank alias removes it
+        settings.set_alias(monitor, Some(" "));
+        assert!(settings.aliases.is_empty());
+
+        settings
+            .aliases
+            .insert("mic-1".to_string(), "x".repeat(MAX_ALIAS_CHARS + 1));
+        assert!(settings.validate().is_err());
+    }
+}
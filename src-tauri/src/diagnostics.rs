@@ -0,0 +1,380 @@
+//! Diagnostic Bundle
+//!
+//! Collects what a bug report usually needs into a single zip: recent log
+//! entries, an environment snapshot, settings (secrets stripped), a metrics
+//! snapshot, the last crash report and, on request, a session's sent audio
+//! dump (see `audio_dump`).
+//!
+//! A bundle is planned first so its content and size can be previewed;
+//! nothing is written until the plan is confirmed.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Replacement for stripped values
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Key fragments marking a settings value as secret (lowercase)
+const SECRET_KEY_FRAGMENTS: &[&str] = &[
+    "password",
+    "secret",
+    "token",
+    "api_key",
+    "apikey",
+    "authorization",
+    "credential",
+];
+
+/// What to include beyond the default content
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct BundleOptions {
+    /// Session whose `debug/sent_audio.wav` is attached
+    #[serde(default)]
+    pub sent_audio_session_id: Option<String>,
+}
+
+/// Machine and app details
+#[derive(Debug, Clone, Serialize)]
+pub struct EnvironmentSnapshot {
+    pub app_version: String,
+    pub os: String,
+    pub arch: String,
+    pub os_release: Option<String>,
+    pub cpus: usize,
+    pub total_memory_mb: Option<u64>,
+    pub free_disk_mb: Option<u64>,
+    pub generated_at: String,
+}
+
+impl EnvironmentSnapshot {
+    pub fn collect(app_data_dir: &Path) -> Self {
+        Self {
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            os_release: sys_info::os_release().ok(),
+            cpus: num_cpus::get(),
+            total_memory_mb: sys_info::mem_info().ok().map(|m| m.total / 1024),
+            free_disk_mb: fs2::available_space(app_data_dir)
+                .ok()
+                .map(|b| b / (1024 * 1024)),
+            generated_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum EntrySource {
+    Bytes(Vec<u8>),
+    File(PathBuf),
+}
+
+#[derive(Debug, Clone)]
+struct BundleEntry {
+    path: String,
+    description: String,
+    bytes: u64,
+    source: EntrySource,
+}
+
+/// One file of the bundle as shown in the preview
+#[derive(Debug, Clone, Serialize)]
+pub struct PreviewEntry {
+    pub path: String,
+    pub description: String,
+    pub bytes: u64,
+}
+
+/// Content and (uncompressed) size of a planned bundle
+#[derive(Debug, Clone, Serialize)]
+pub struct BundlePreview {
+    pub entries: Vec<PreviewEntry>,
+    pub total_bytes: u64,
+    /// Settings values replaced with `[REDACTED]`
+    pub redacted_values: usize,
+}
+
+/// Result of writing a bundle
+#[derive(Debug, Clone, Serialize)]
+pub struct WrittenBundle {
+    pub path: String,
+    /// Compressed size
+    pub zip_bytes: u64,
+    pub preview: BundlePreview,
+}
+
+/// Files planned for a bundle
+#[derive(Debug, Clone, Default)]
+pub struct BundlePlan {
+    entries: Vec<BundleEntry>,
+    redacted_values: usize,
+}
+
+impl BundlePlan {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_bytes(&mut self, path: &str, description: &str, data: Vec<u8>) {
+        self.entries.push(BundleEntry {
+            path: path.to_string(),
+            description: description.to_string(),
+            bytes: data.len() as u64,
+            source: EntrySource::Bytes(data),
+        });
+    }
+
+    pub fn add_json<T: Serialize>(
+        &mut self,
+        path: &str,
+        description: &str,
+        value: &T,
+    ) -> Result<()> {
+        let data = serde_json::to_vec_pretty(value)
+            .with_context(|| format!("Failed to serialize {}", path))?;
+        self.add_bytes(path, description, data);
+        Ok(())
+    }
+
+    /// Attach a file by reference (read when the bundle is written)
+    pub fn add_file(&mut self, path: &str, description: &str, source: &Path) -> Result<()> {
+        let bytes = std::fs::metadata(source)
+            .with_context(|| format!("Failed to read {:?}", source))?
+            .len();
+        self.entries.push(BundleEntry {
+            path: path.to_string(),
+            description: description.to_string(),
+            bytes,
+            source: EntrySource::File(source.to_path_buf()),
+        });
+        Ok(())
+    }
+
+    /// Add every `settings/*.json` file of `data_dir` under `prefix`, with
+    /// secrets stripped
+    ///
+    /// Unparsable files are skipped (they may hold anything).
+    pub fn add_settings(&mut self, data_dir: &Path, prefix: &str) -> Result<()> {
+        let settings_dir = data_dir.join("settings");
+        if !settings_dir.exists() {
+            return Ok(());
+        }
+
+        let mut files: Vec<PathBuf> = std::fs::read_dir(&settings_dir)?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect();
+        files.sort();
+
+        for file in files {
+            let Some(name) = file.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            let Ok(json) = std::fs::read_to_string(&file) else {
+                continue;
+            };
+            let Ok(mut value) = serde_json::from_str::<Value>(&json) else {
+                continue;
+            };
+            self.redacted_values += strip_secrets(&mut value);
+            self.add_json(
+                &format!("{}/{}", prefix, name),
+                "Settings (secrets stripped)",
+                &value,
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn preview(&self) -> BundlePreview {
+        BundlePreview {
+            entries: self
+                .entries
+                .iter()
+                .map(|e| PreviewEntry {
+                    path: e.path.clone(),
+                    description: e.description.clone(),
+                    bytes: e.bytes,
+                })
+                .collect(),
+            total_bytes: self.entries.iter().map(|e| e.bytes).sum(),
+            redacted_values: self.redacted_values,
+        }
+    }
+
+    /// Write the bundle as a zip; returns the zip size in bytes
+    pub fn write_zip(&self, output: &Path) -> Result<u64> {
+        if let Some(parent) = output.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = std::fs::File::create(output)
+            .with_context(|| format!("Failed to create {:?}", output))?;
+        let mut zip = zip::ZipWriter::new(file);
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated)
+            .large_file(true);
+
+        for entry in &self.entries {
+            zip.start_file(entry.path.as_str(), options)?;
+            match &entry.source {
+                EntrySource::Bytes(data) => zip.write_all(data)?,
+                EntrySource::File(path) => {
+                    let mut source = std::fs::File::open(path)
+                        .with_context(|| format!("Failed to open {:?}", path))?;
+                    std::io::copy(&mut source, &mut zip)?;
+                }
+            }
+        }
+        zip.finish()?;
+
+        Ok(std::fs::metadata(output)?.len())
+    }
+}
+
+/// Replace secret-looking settings values in place; returns the count
+///
+/// Values under secret-like keys are replaced entirely. URLs keep only
+/// scheme and host: credentials, query strings and (for non-loopback hosts)
+/// paths are stripped, since webhook URLs often embed tokens in the path.
+pub fn strip_secrets(value: &mut Value) -> usize {
+    match value {
+        Value::Object(map) => map
+            .iter_mut()
+            .map(|(key, v)| {
+                let key = key.to_ascii_lowercase();
+                if SECRET_KEY_FRAGMENTS.iter().any(|f| key.contains(f)) && !v.is_null() {
+                    *v = Value::String(REDACTED.to_string());
+                    1
+                } else {
+                    strip_secrets(v)
+                }
+            })
+            .sum(),
+        Value::Array(items) => items.iter_mut().map(strip_secrets).sum(),
+        Value::String(s) => match redact_url(s) {
+            Some(redacted) if redacted != *s => {
+                *s = redacted;
+                1
+            }
+            _ => 0,
+        },
+        _ => 0,
+    }
+}
+
+/// Strip secrets from an http(s) URL (None if `s` is not one)
+fn redact_url(s: &str) -> Option<String> {
+    let (scheme, rest) = s.split_once("://")?;
+    if !scheme.eq_ignore_ascii_case("http") && !scheme.eq_ignore_ascii_case("https") {
+        return None;
+    }
+
+    let authority_end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+    let (authority, tail) = rest.split_at(authority_end);
+    let host_port = match authority.rsplit_once('@') {
+        Some((_, host_port)) => format!("{}@{}", REDACTED, host_port),
+        None => authority.to_string(),
+    };
+    let host = host_port.rsplit('@').next().unwrap_or_default();
+    let loopback = ["localhost", "127.0.0.1", "[::1]"]
+        .iter()
+        .any(|h| host == *h || host.starts_with(&format!("{}:", h)));
+
+    let path_end = tail.find(['?', '#']).unwrap_or(tail.len());
+    let (path, extra) = tail.split_at(path_end);
+    let mut redacted = format!("{}://{}", scheme, host_port);
+    if loopback || path.is_empty() || path == "/" {
+        redacted.push_str(path);
+    } else {
+        redacted.push('/');
+        redacted.push_str(REDACTED);
+    }
+    if !extra.is_empty() {
+        redacted.push('?');
+        redacted.push_str(REDACTED);
+    }
+    Some(redacted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_strip_secrets() {
+        let mut value = json!({
+            "endpoint": "http://localhost:11434/api/generate",
+            "api_key": "sk-123",
+            "watchlists": [{ "keywords": ["budget"] }],
+            "rules": [
+                { "target": { "url": "https://hooks.example.com/services/T0/B0/abc" } },
+                { "target": { "url": "https://user:pw@example.com/?token=x" } }
+            ],
+            "auth": { "bearer_token": null }
+        });
+        assert_eq!(strip_secrets(&mut value), 3);
+        assert_eq!(value["endpoint"], "http://localhost:11434/api/generate");
+        assert_eq!(value["api_key"], REDACTED);
+        assert_eq!(value["watchlists"][0]["keywords"][0], "budget");
+        assert_eq!(
+            value["rules"][0]["target"]["url"],
+            "https://hooks.example.com/[REDACTED]"
+        );
+        assert_eq!(
+            value["rules"][1]["target"]["url"],
+            "https://[REDACTED]@example.com/?[REDACTED]"
+        );
+    }
+
+    #[test]
+    fn test_preview_and_write_zip() {
+        let dir = TempDir::new().unwrap();
+        let settings_dir = dir.path().join("settings");
+        std::fs::create_dir_all(&settings_dir).unwrap();
+        std::fs::write(
+            settings_dir.join("summary.json"),
+            r#"{"endpoint":"https://llm.example.com/v1?key=abc"}"#,
+        )
+        .unwrap();
+        std::fs::write(settings_dir.join("broken.json"), "{").unwrap();
+        let audio = dir.path().join("sent_audio.wav");
+        std::fs::write(&audio, vec![0u8; 1000]).unwrap();
+
+        let mut plan = BundlePlan::new();
+        plan.add_bytes("logs/recent.jsonl", "Recent logs", b"{}\n".to_vec());
+        plan.add_settings(dir.path(), "settings/machine").unwrap();
+        plan.add_file("audio/sent_audio.wav", "Sent audio", &audio)
+            .unwrap();
+
+        let preview = plan.preview();
+        let paths: Vec<&str> = preview.entries.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "logs/recent.jsonl",
+                "settings/machine/summary.json",
+                "audio/sent_audio.wav"
+            ]
+        );
+        assert_eq!(preview.redacted_values, 1);
+        assert!(preview.total_bytes > 1000);
+
+        let output = dir.path().join("out").join("bundle.zip");
+        assert!(plan.write_zip(&output).unwrap() > 0);
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(&output).unwrap()).unwrap();
+        assert_eq!(archive.len(), 3);
+        let mut settings = String::new();
+        std::io::Read::read_to_string(
+            &mut archive.by_name("settings/machine/summary.json").unwrap(),
+            &mut settings,
+        )
+        .unwrap();
+        assert!(settings.contains("https://llm.example.com/[REDACTED]?[REDACTED]"));
+    }
+}
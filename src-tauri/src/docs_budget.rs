@@ -0,0 +1,260 @@
+//! Google Docs Character Budget
+//!
+//! The Chrome extension inserts every final segment into a Google Doc as its
+//! own paragraph, and a document stops accepting text somewhere above a
+//! million characters. The budget counts what has been handed to the
+//! extension — in UTF-16 code units plus the paragraph break, as Docs
+//! counts — both for the whole session and for the current document.
+//!
+//! Crossing `warn_percent` of `limit_characters` in the current document
+//! sends one `docs_budget_warning` notification; reaching the limit sends
+//! `docs_budget_exceeded` so the extension can roll to a new document. When
+//! the extension then reports syncing to a different document (`docsSync`
+//! with another `documentId`), the document count starts over. The counts
+//! are part of the remote control recording status.
+//!
+//! Persisted to `settings/docs_budget.json` in app data directory.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// Notification type sent when the warning threshold is crossed
+pub const NOTIFICATION_WARNING: &str = "docs_budget_warning";
+
+/// Notification type sent when the limit is reached
+pub const NOTIFICATION_EXCEEDED: &str = "docs_budget_exceeded";
+
+/// Character budget configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DocsBudgetSettings {
+    /// Send budget notifications
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Characters a document may take before rolling to a new one
+    #[serde(default = "default_limit_characters")]
+    pub limit_characters: u64,
+    /// Percentage of the limit at which to warn (1-100)
+    #[serde(default = "default_warn_percent")]
+    pub warn_percent: u8,
+    /// Settings version for future migrations
+    #[serde(default = "default_version")]
+    pub version: u32,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Below the Docs maximum of about 1.02 million characters
+fn default_limit_characters() -> u64 {
+    1_000_000
+}
+
+fn default_warn_percent() -> u8 {
+    80
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+impl Default for DocsBudgetSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            limit_characters: default_limit_characters(),
+            warn_percent: default_warn_percent(),
+            version: 1,
+        }
+    }
+}
+
+impl DocsBudgetSettings {
+    pub fn validate(&self) -> Result<()> {
+        if self.limit_characters == 0 {
+            anyhow::bail!("limit_characters must be positive");
+        }
+        if !(1..=100).contains(&self.warn_percent) {
+            anyhow::bail!("warn_percent must be between 1 and 100");
+        }
+        Ok(())
+    }
+
+    fn warn_characters(&self) -> u64 {
+        self.limit_characters * self.warn_percent as u64 / 100
+    }
+}
+
+/// Characters a final segment takes in the document
+pub fn inserted_characters(text: &str) -> u64 {
+    // Docs indexes are UTF-16 code units; each segment ends its paragraph
+    text.encode_utf16().count() as u64 + 1
+}
+
+/// Budget threshold crossed by an insertion
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetAlert {
+    Warning,
+    Exceeded,
+}
+
+impl BudgetAlert {
+    pub fn notification_type(&self) -> &'static str {
+        match self {
+            BudgetAlert::Warning => NOTIFICATION_WARNING,
+            BudgetAlert::Exceeded => NOTIFICATION_EXCEEDED,
+        }
+    }
+
+    pub fn message(&self) -> &'static str {
+        match self {
+            BudgetAlert::Warning => "Google Docsの文字数上限に近づいています",
+            BudgetAlert::Exceeded => {
+                "Google Docsの文字数上限に達しました。新しいドキュメントに切り替えてください"
+            }
+        }
+    }
+}
+
+/// Character counts of the active (or last) session
+#[derive(Debug, Default)]
+pub struct DocsBudget {
+    session_characters: u64,
+    document_characters: u64,
+    document_id: Option<String>,
+    warned: bool,
+    exceeded: bool,
+}
+
+/// Budget state reported in the recording status
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocsBudgetStatus {
+    pub session_characters: u64,
+    pub document_characters: u64,
+    pub limit_characters: u64,
+    pub remaining_characters: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub document_id: Option<String>,
+    pub warning: bool,
+    pub exceeded: bool,
+}
+
+impl DocsBudget {
+    /// Count a final segment handed to the extension; returns a newly
+    /// crossed threshold
+    pub fn record(
+        &mut self,
+        characters: u64,
+        settings: &DocsBudgetSettings,
+    ) -> Option<BudgetAlert> {
+        self.session_characters += characters;
+        self.document_characters += characters;
+        if !settings.enabled {
+            return None;
+        }
+        if !self.exceeded && self.document_characters >= settings.limit_characters {
+            self.exceeded = true;
+            self.warned = true;
+            return Some(BudgetAlert::Exceeded);
+        }
+        if !self.warned && self.document_characters >= settings.warn_characters() {
+            self.warned = true;
+            return Some(BudgetAlert::Warning);
+        }
+        None
+    }
+
+    /// The extension synced to `document_id`; returns true when it rolled
+    /// to a new document (its count starts over)
+    pub fn document_synced(&mut self, document_id: &str) -> bool {
+        let rolled = self
+            .document_id
+            .as_deref()
+            .is_some_and(|current| current != document_id);
+        if rolled {
+            self.document_characters = 0;
+            self.warned = false;
+            self.exceeded = false;
+        }
+        self.document_id = Some(document_id.to_string());
+        rolled
+    }
+
+    pub fn status(&self, settings: &DocsBudgetSettings) -> DocsBudgetStatus {
+        DocsBudgetStatus {
+            session_characters: self.session_characters,
+            document_characters: self.document_characters,
+            limit_characters: settings.limit_characters,
+            remaining_characters: settings
+                .limit_characters
+                .saturating_sub(self.document_characters),
+            document_id: self.document_id.clone(),
+            warning: self.warned,
+            exceeded: self.exceeded,
+        }
+    }
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+pub const SETTINGS_STEM: &str = "docs_budget";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_warns_once_then_exceeds_and_rolls_over() {
+        let settings = DocsBudgetSettings {
+            limit_characters: 100,
+            warn_percent: 80,
+            ..Default::default()
+        };
+        let mut budget = DocsBudget::default();
+        assert!(!budget.document_synced("doc-1"));
+
+        assert_eq!(budget.record(50, &settings), None);
+        assert_eq!(budget.record(30, &settings), Some(BudgetAlert::Warning));
+        assert_eq!(budget.record(10, &settings), None);
+        assert_eq!(budget.record(10, &settings), Some(BudgetAlert::Exceeded));
+        assert_eq!(budget.record(10, &settings), None);
+        assert_eq!(budget.status(&settings).remaining_characters, 0);
+
+        // Same document again: no reset
+        assert!(!budget.document_synced("doc-1"));
+        assert!(budget.document_synced("doc-2"));
+        assert_eq!(budget.record(20, &settings), None);
+        let status = budget.status(&settings);
+        assert_eq!(status.session_characters, 130);
+        assert_eq!(status.document_characters, 20);
+        assert_eq!(status.document_id.as_deref(), Some("doc-2"));
+        assert!(!status.warning);
+
+        assert_eq!(inserted_characters("会議"), 3);
+        assert_eq!(inserted_characters("🎉"), 3);
+    }
+
+    #[test]
+    fn test_settings_validation() {
+        let settings = DocsBudgetSettings {
+            limit_characters: 500_000,
+            warn_percent: 90,
+            ..Default::default()
+        };
+        settings.validate().unwrap();
+
+        let zero_percent = DocsBudgetSettings {
+            warn_percent: 0,
+            ..Default::default()
+        };
+        assert!(zero_percent.validate().is_err());
+        let zero_limit = DocsBudgetSettings {
+            limit_characters: 0,
+            ..Default::default()
+        };
+        assert!(zero_limit.validate().is_err());
+    }
+}
@@ -0,0 +1,280 @@
+//! Minimal DOCX Writer
+//!
+//! Writes WordprocessingML documents made of headings, paragraphs and
+//! simple tables of (optionally bold) text runs, optionally with a ruby
+//! reading (furigana), which is all exported minutes need. The
+//! package holds only the parts Word requires (`[Content_Types].xml`,
+//! `_rels/.rels`, `word/document.xml`) plus `word/styles.xml` so headings
+//! use the built-in "Heading 1/2" styles and show up in the navigation pane.
+//!
+//! Built on the `zip` crate already used for session archives.
+
+use anyhow::{Context, Result};
+use std::io::Write;
+
+const CONTENT_TYPES_XML: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Override PartName="/word/document.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"/><Override PartName="/word/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.styles+xml"/></Types>"#;
+
+const PACKAGE_RELS_XML: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="word/document.xml"/></Relationships>"#;
+
+const DOCUMENT_RELS_XML: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="styles.xml"/></Relationships>"#;
+
+const STYLES_XML: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:styles xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:style w:type="paragraph" w:default="1" w:styleId="Normal"><w:name w:val="Normal"/><w:pPr><w:spacing w:after="80"/></w:pPr></w:style><w:style w:type="paragraph" w:styleId="Heading1"><w:name w:val="heading 1"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:pPr><w:keepNext/><w:spacing w:before="240" w:after="120"/><w:outlineLvl w:val="0"/></w:pPr><w:rPr><w:b/><w:sz w:val="32"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="Heading2"><w:name w:val="heading 2"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:pPr><w:keepNext/><w:spacing w:before="200" w:after="80"/><w:outlineLvl w:val="1"/></w:pPr><w:rPr><w:b/><w:sz w:val="26"/></w:rPr></w:style></w:styles>"#;
+
+/// Ruby text size in half-points (base text is Word's default 10pt)
+const RUBY_HALF_POINTS: u32 = 10;
+const BASE_HALF_POINTS: u32 = 20;
+
+/// Usable page width in twentieths of a point (A4/Letter with default margins)
+const TABLE_WIDTH_TWIPS: u32 = 9000;
+
+const TABLE_BORDERS_XML: &str = r#"<w:tblBorders><w:top w:val="single" w:sz="4" w:space="0" w:color="auto"/><w:left w:val="single" w:sz="4" w:space="0" w:color="auto"/><w:bottom w:val="single" w:sz="4" w:space="0" w:color="auto"/><w:right w:val="single" w:sz="4" w:space="0" w:color="auto"/><w:insideH w:val="single" w:sz="4" w:space="0" w:color="auto"/><w:insideV w:val="single" w:sz="4" w:space="0" w:color="auto"/></w:tblBorders>"#;
+
+/// Table cell: paragraphs of runs
+pub type Cell<'a> = Vec<Vec<Run<'a>>>;
+
+/// Text run of a paragraph
+#[derive(Debug, Clone, Copy)]
+pub struct Run<'a> {
+    pub text: &'a str,
+    pub bold: bool,
+    /// Reading shown above the text (`w:ruby`)
+    pub ruby: Option<&'a str>,
+}
+
+impl<'a> Run<'a> {
+    pub fn plain(text: &'a str) -> Self {
+        Self {
+            text,
+            bold: false,
+            ruby: None,
+        }
+    }
+
+    pub fn bold(text: &'a str) -> Self {
+        Self {
+            text,
+            bold: true,
+            ruby: None,
+        }
+    }
+
+    /// Plain text with `reading` as ruby
+    pub fn ruby(text: &'a str, reading: &'a str) -> Self {
+        Self {
+            text,
+            bold: false,
+            ruby: Some(reading),
+        }
+    }
+}
+
+/// Document body being built
+#[derive(Debug, Default)]
+pub struct DocxDocument {
+    body: String,
+}
+
+impl DocxDocument {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Heading paragraph (`level` 1 or 2)
+    pub fn heading(&mut self, level: u8, text: &str) {
+        self.body.push_str(&format!(
+            r#"<w:p><w:pPr><w:pStyle w:val="Heading{}"/></w:pPr>"#,
+            level.clamp(1, 2)
+        ));
+        push_run(&mut self.body, Run::plain(text));
+        self.body.push_str("</w:p>");
+    }
+
+    /// Body paragraph of `runs`
+    pub fn paragraph(&mut self, runs: &[Run]) {
+        self.body.push_str("<w:p>");
+        for run in runs {
+            push_run(&mut self.body, *run);
+        }
+        self.body.push_str("</w:p>");
+    }
+
+    /// Bordered table with a bold header row repeated on every page
+    ///
+    /// Columns share the page width equally; rows shorter than `header`
+    /// get empty cells.
+    pub fn table(&mut self, header: &[&str], rows: &[Vec<Cell>]) {
+        let columns = header.len().max(1);
+        let column_width = TABLE_WIDTH_TWIPS / columns as u32;
+        self.body.push_str(&format!(
+            r#"<w:tbl><w:tblPr><w:tblW w:w="{}" w:type="dxa"/>{}</w:tblPr><w:tblGrid>"#,
+            column_width * columns as u32,
+            TABLE_BORDERS_XML
+        ));
+        for _ in 0..columns {
+            self.body
+                .push_str(&format!(r#"<w:gridCol w:w="{}"/>"#, column_width));
+        }
+        self.body.push_str("</w:tblGrid>");
+
+        self.body.push_str("<w:tr><w:trPr><w:tblHeader/></w:trPr>");
+        for title in header {
+            self.push_cell(column_width, &[vec![Run::bold(title)]]);
+        }
+        self.body.push_str("</w:tr>");
+        for row in rows {
+            self.body.push_str("<w:tr>");
+            for column in 0..columns {
+                self.push_cell(column_width, row.get(column).map_or(&[], Vec::as_slice));
+            }
+            self.body.push_str("</w:tr>");
+        }
+        self.body.push_str("</w:tbl>");
+    }
+
+    fn push_cell(&mut self, width: u32, paragraphs: &[Vec<Run>]) {
+        self.body.push_str(&format!(
+            r#"<w:tc><w:tcPr><w:tcW w:w="{}" w:type="dxa"/></w:tcPr>"#,
+            width
+        ));
+        // A cell needs at least one paragraph
+        if paragraphs.is_empty() {
+            self.body.push_str("<w:p/>");
+        }
+        for runs in paragraphs {
+            self.paragraph(runs);
+        }
+        self.body.push_str("</w:tc>");
+    }
+
+    /// The .docx package
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let document = format!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>{}<w:sectPr/></w:body></w:document>"#,
+            self.body
+        );
+
+        let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated);
+        for (name, contents) in [
+            ("[Content_Types].xml", CONTENT_TYPES_XML),
+            ("_rels/.rels", PACKAGE_RELS_XML),
+            ("word/_rels/document.xml.rels", DOCUMENT_RELS_XML),
+            ("word/styles.xml", STYLES_XML),
+            ("word/document.xml", document.as_str()),
+        ] {
+            zip.start_file(name, options)
+                .with_context(|| format!("Failed to add {} to document", name))?;
+            zip.write_all(contents.as_bytes())?;
+        }
+        Ok(zip
+            .finish()
+            .context("Failed to finish document")?
+            .into_inner())
+    }
+}
+
+fn push_run(body: &mut String, run: Run) {
+    let Some(reading) = run.ruby else {
+        push_text_run(body, run.text, run.bold);
+        return;
+    };
+    body.push_str(&format!(
+        r#"<w:r><w:ruby><w:rubyPr><w:rubyAlign w:val="distributeSpace"/><w:hps w:val="{}"/><w:hpsRaise w:val="{}"/><w:hpsBaseText w:val="{}"/><w:lid w:val="ja-JP"/></w:rubyPr><w:rt><w:r><w:rPr><w:sz w:val="{}"/></w:rPr><w:t>{}</w:t></w:r></w:rt><w:rubyBase>"#,
+        RUBY_HALF_POINTS,
+        BASE_HALF_POINTS - 2,
+        BASE_HALF_POINTS,
+        RUBY_HALF_POINTS,
+        escape_xml(reading)
+    ));
+    push_text_run(body, run.text, run.bold);
+    body.push_str("</w:rubyBase></w:ruby></w:r>");
+}
+
+fn push_text_run(body: &mut String, text: &str, bold: bool) {
+    body.push_str("<w:r>");
+    if bold {
+        body.push_str("<w:rPr><w:b/></w:rPr>");
+    }
+    body.push_str(r#"<w:t xml:space="preserve">"#);
+    body.push_str(&escape_xml(text));
+    body.push_str("</w:t></w:r>");
+}
+
+/// Escape text for XML; line breaks become spaces and other control
+/// characters (not allowed in XML 1.0) are dropped
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\n' | '\r' | '\t' => escaped.push(' '),
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn test_docx_package() {
+        let mut doc = DocxDocument::new();
+        doc.heading(1, "議事録 <draft>");
+        doc.paragraph(&[Run::bold("[00:01:05] A:"), Run::plain(" R&D\nbudget\u{1}")]);
+        doc.paragraph(&[Run::ruby("議事録", "ぎじろく"), Run::plain("を配布")]);
+        doc.table(
+            &["原文", "訳文"],
+            &[vec![
+                vec![vec![Run::plain("予算")], vec![Run::plain("決定")]],
+                Vec::new(),
+            ]],
+        );
+        let bytes = doc.to_bytes().unwrap();
+
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes)).unwrap();
+        for part in [
+            "[Content_Types].xml",
+            "_rels/.rels",
+            "word/_rels/document.xml.rels",
+            "word/styles.xml",
+        ] {
+            assert!(archive.by_name(part).is_ok(), "missing {}", part);
+        }
+        let mut document = String::new();
+        archive
+            .by_name("word/document.xml")
+            .unwrap()
+            .read_to_string(&mut document)
+            .unwrap();
+        assert!(document.contains(
+            r#"<w:pStyle w:val="Heading1"/></w:pPr><w:r><w:t xml:space="preserve">議事録 &lt;draft&gt;</w:t>"#
+        ));
+        assert!(document.contains(
+            r#"<w:r><w:rPr><w:b/></w:rPr><w:t xml:space="preserve">[00:01:05] A:</w:t></w:r>"#
+        ));
+        assert!(document.contains(r#"<w:t xml:space="preserve"> R&amp;D budget</w:t>"#));
+        assert!(document.contains(
+            r#"<w:rt><w:r><w:rPr><w:sz w:val="10"/></w:rPr><w:t>ぎじろく</w:t></w:r></w:rt><w:rubyBase><w:r><w:t xml:space="preserve">議事録</w:t></w:r></w:rubyBase></w:ruby></w:r><w:r><w:t xml:space="preserve">を配布</w:t>"#
+        ));
+        assert_eq!(document.matches(r#"<w:gridCol w:w="4500"/>"#).count(), 2);
+        assert!(document.contains(
+            r#"<w:tr><w:trPr><w:tblHeader/></w:trPr><w:tc><w:tcPr><w:tcW w:w="4500" w:type="dxa"/></w:tcPr><w:p><w:r><w:rPr><w:b/></w:rPr><w:t xml:space="preserve">原文</w:t>"#
+        ));
+        assert!(document.contains(
+            r#"<w:p><w:r><w:t xml:space="preserve">予算</w:t></w:r></w:p><w:p><w:r><w:t xml:space="preserve">決定</w:t></w:r></w:p></w:tc><w:tc><w:tcPr><w:tcW w:w="4500" w:type="dxa"/></w:tcPr><w:p/></w:tc></w:tr></w:tbl>"#
+        ));
+    }
+}
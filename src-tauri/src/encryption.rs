@@ -0,0 +1,275 @@
+//! At-Rest Encryption of Session Data
+//!
+//! When enabled, `session.json` and `transcription.jsonl` are written
+//! sealed with AES-256-GCM, so meeting content isn't readable as plaintext
+//! on shared machines. The key is generated once and kept in the OS
+//! keychain (macOS Keychain, Windows Credential Manager, Secret Service).
+//!
+//! Sealing is per record: session.json is one sealed line, and every
+//! transcript line is sealed on its own so the transcript stays append-only.
+//! A sealed record is `enc1:` + base64(nonce ‖ ciphertext ‖ tag), with the
+//! file name as associated data (records can't be moved between files).
+//! Readers accept sealed and plaintext records alike, so sessions recorded
+//! before encryption was enabled (or after it was disabled) still load.
+//!
+//! Audio is not encrypted. Every workspace (see `workspaces`) has its own
+//! key.
+//!
+//! Persisted to `settings/encryption.json` in app data directory.
+
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use anyhow::{Context, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Prefix of a sealed record
+pub const SEALED_PREFIX: &str = "enc1:";
+
+const NONCE_LEN: usize = 12;
+const KEY_LEN: usize = 32;
+
+/// Keychain entry holding the key
+const KEYCHAIN_SERVICE: &str = "meeting-minutes-automator";
+const KEYCHAIN_USER: &str = "session-encryption-key";
+
+/// Encryption settings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncryptionSettings {
+    /// Seal session.json and transcription.jsonl of new writes
+    #[serde(default)]
+    pub enabled: bool,
+    /// Settings version for future migrations
+    #[serde(default = "default_version")]
+    pub version: u32,
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+impl Default for EncryptionSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            version: 1,
+        }
+    }
+}
+
+/// AES-256-GCM cipher for session records
+#[derive(Clone)]
+pub struct SessionCipher {
+    cipher: Arc<Aes256Gcm>,
+}
+
+impl SessionCipher {
+    pub fn from_key(key: &[u8; KEY_LEN]) -> Self {
+        Self {
+            cipher: Arc::new(Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key))),
+        }
+    }
+
+    /// Seal `plaintext` (one record of `file_name`)
+    pub fn seal(&self, plaintext: &str, file_name: &str) -> Result<String> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: plaintext.as_bytes(),
+                    aad: file_name.as_bytes(),
+                },
+            )
+            .map_err(|_| anyhow::anyhow!("Encryption failed"))?;
+
+        let mut sealed = nonce.to_vec();
+        sealed.extend_from_slice(&ciphertext);
+        Ok(format!("{}{}", SEALED_PREFIX, BASE64.encode(sealed)))
+    }
+
+    /// Open a sealed record of `file_name`
+    pub fn open(&self, record: &str, file_name: &str) -> Result<String> {
+        let encoded = record
+            .trim()
+            .strip_prefix(SEALED_PREFIX)
+            .context("Record is not sealed")?;
+        let sealed = BASE64
+            .decode(encoded)
+            .context("Sealed record is not valid base64")?;
+        if sealed.len() < NONCE_LEN {
+            anyhow::bail!("Sealed record is truncated");
+        }
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+        let plaintext = self
+            .cipher
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: file_name.as_bytes(),
+                },
+            )
+            .map_err(|_| {
+                anyhow::anyhow!(
+                    "Failed to decrypt {} (wrong key or corrupted data)",
+                    file_name
+                )
+            })?;
+        String::from_utf8(plaintext).context("Decrypted record is not UTF-8")
+    }
+}
+
+/// Encryption configuration of a storage service
+#[derive(Clone)]
+pub struct SessionEncryption {
+    /// Opens sealed records (present whenever a key exists)
+    pub cipher: SessionCipher,
+    /// Seal new writes (the `enabled` setting)
+    pub seal_writes: bool,
+}
+
+impl SessionEncryption {
+    /// Cipher for new writes, None when writes stay plaintext
+    pub fn write_cipher(&self) -> Option<&SessionCipher> {
+        self.seal_writes.then_some(&self.cipher)
+    }
+}
+
+/// Whether `record` is sealed
+pub fn is_sealed(record: &str) -> bool {
+    record.trim_start().starts_with(SEALED_PREFIX)
+}
+
+/// Plaintext of a record that may be sealed
+pub fn open_record(
+    record: &str,
+    file_name: &str,
+    encryption: Option<&SessionEncryption>,
+) -> Result<String> {
+    if !is_sealed(record) {
+        return Ok(record.to_string());
+    }
+    match encryption {
+        Some(encryption) => encryption.cipher.open(record, file_name),
+        None => anyhow::bail!(
+            "{} is encrypted but no encryption key is available",
+            file_name
+        ),
+    }
+}
+
+/// Build the storage encryption of `workspace` from the settings
+///
+/// Enabled: the keychain key is loaded, or created on first use. Disabled:
+/// an existing key is still loaded so earlier sealed sessions stay readable.
+pub fn load_session_encryption(
+    settings: &EncryptionSettings,
+    workspace: &str,
+) -> Result<Option<SessionEncryption>> {
+    let key = if settings.enabled {
+        Some(load_or_create_keychain_key(workspace)?)
+    } else {
+        load_keychain_key(workspace)?
+    };
+    Ok(key.map(|key| SessionEncryption {
+        cipher: SessionCipher::from_key(&key),
+        seal_writes: settings.enabled,
+    }))
+}
+
+/// Keychain user of a workspace's key (the default workspace keeps the
+/// original entry)
+fn keychain_user(workspace: &str) -> String {
+    if workspace == crate::workspaces::DEFAULT_WORKSPACE {
+        KEYCHAIN_USER.to_string()
+    } else {
+        format!("{}:{}", KEYCHAIN_USER, workspace)
+    }
+}
+
+fn keychain_entry(workspace: &str) -> Result<keyring::Entry> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, &keychain_user(workspace))
+        .context("Failed to open the OS keychain")
+}
+
+/// Key stored in the keychain, if any
+fn load_keychain_key(workspace: &str) -> Result<Option<[u8; KEY_LEN]>> {
+    match keychain_entry(workspace)?.get_secret() {
+        Ok(secret) => {
+            let key: [u8; KEY_LEN] = secret
+                .try_into()
+                .map_err(|_| anyhow::anyhow!("Keychain encryption key has the wrong length"))?;
+            Ok(Some(key))
+        }
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(e).context("Failed to read the encryption key from the OS keychain"),
+    }
+}
+
+fn load_or_create_keychain_key(workspace: &str) -> Result<[u8; KEY_LEN]> {
+    if let Some(key) = load_keychain_key(workspace)? {
+        return Ok(key);
+    }
+    let key: [u8; KEY_LEN] = Aes256Gcm::generate_key(&mut OsRng).into();
+    keychain_entry(workspace)?
+        .set_secret(&key)
+        .context("Failed to store the encryption key in the OS keychain")?;
+    Ok(key)
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+pub const SETTINGS_STEM: &str = "encryption";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_and_open() {
+        let cipher = SessionCipher::from_key(&[7u8; KEY_LEN]);
+        let sealed = cipher
+            .seal("{\"text\":\"議事録\"}", "session.json")
+            .unwrap();
+        assert!(is_sealed(&sealed));
+        assert!(!sealed.contains("議事録"));
+        assert_eq!(
+            cipher.open(&sealed, "session.json").unwrap(),
+            "{\"text\":\"議事録\"}"
+        );
+
+        // Bound to the file name and the key
+        assert!(cipher.open(&sealed, "transcription.jsonl").is_err());
+        assert!(SessionCipher::from_key(&[8u8; KEY_LEN])
+            .open(&sealed, "session.json")
+            .is_err());
+        // Fresh nonce per record
+        assert_ne!(
+            cipher.seal("x", "a").unwrap(),
+            cipher.seal("x", "a").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_open_record_passes_plaintext_through() {
+        let encryption = SessionEncryption {
+            cipher: SessionCipher::from_key(&[1u8; KEY_LEN]),
+            seal_writes: false,
+        };
+        assert!(encryption.write_cipher().is_none());
+        assert_eq!(open_record("{}", "session.json", None).unwrap(), "{}");
+
+        let sealed = encryption.cipher.seal("{}", "session.json").unwrap();
+        assert_eq!(
+            open_record(&sealed, "session.json", Some(&encryption)).unwrap(),
+            "{}"
+        );
+        assert!(open_record(&sealed, "session.json", None).is_err());
+    }
+}
@@ -0,0 +1,514 @@
+//! FLAC Encoding of Session Audio
+//!
+//! Minimal lossless encoder for session audio (16kHz, mono, 16-bit), used
+//! to shrink archives: fixed-size blocks, each stored with the best fixed
+//! polynomial predictor (order 0-4) and Rice-coded residuals, or verbatim
+//! when that is smaller. No LPC, so ratios trail the reference encoder a
+//! little, but any FLAC decoder reads the output. Decoding uses `claxon`.
+//!
+//! `FlacFileWriter` records session audio straight to FLAC: frames are
+//! appended as blocks fill and the sample count is patched into STREAMINFO
+//! on close. An interrupted file stays decodable up to its last full frame.
+
+use anyhow::{Context, Result};
+use std::io::{BufWriter, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// Session audio format
+pub const SAMPLE_RATE: u32 = 16_000;
+const BITS_PER_SAMPLE: u32 = 16;
+
+/// Samples per frame (block size code 0b1100)
+pub const BLOCK_SIZE: usize = 4096;
+
+/// Largest Rice parameter with 4-bit parameters (15 is the escape code)
+const MAX_RICE_PARAM: u32 = 14;
+
+/// Encode a session WAV as FLAC into `out`; returns the sample count
+pub fn encode_wav<W: Write>(wav_path: &Path, out: &mut W) -> Result<u64> {
+    // Sample count goes into STREAMINFO, which comes first
+    let mut total_samples = 0u64;
+    crate::storage::read_wav_samples(wav_path, |chunk| total_samples += chunk.len() as u64)
+        .with_context(|| format!("Failed to read {:?}", wav_path))?;
+
+    let mut encoder = FlacEncoder::new(out, total_samples)?;
+    let mut result = Ok(());
+    crate::storage::read_wav_samples(wav_path, |chunk| {
+        if result.is_ok() {
+            result = encoder.write_samples(chunk);
+        }
+    })?;
+    result?;
+    encoder.finish()?;
+    Ok(total_samples)
+}
+
+/// Decode FLAC session audio, passing samples to `on_chunk` per frame
+///
+/// Only 16kHz mono 16-bit streams are accepted (the session format). A
+/// stream cut off mid-frame (interrupted recording) ends at its last full
+/// frame.
+pub fn decode<R: std::io::Read, F>(input: R, mut on_chunk: F) -> Result<u64>
+where
+    F: FnMut(&[i16]) -> Result<()>,
+{
+    let mut reader = claxon::FlacReader::new(input).context("Invalid FLAC stream")?;
+    let info = reader.streaminfo();
+    if info.sample_rate != SAMPLE_RATE || info.channels != 1 || info.bits_per_sample != 16 {
+        anyhow::bail!(
+            "Unsupported FLAC format: {}Hz, {} channel(s), {} bit",
+            info.sample_rate,
+            info.channels,
+            info.bits_per_sample
+        );
+    }
+
+    let mut total = 0u64;
+    let mut blocks = reader.blocks();
+    let mut buffer = Vec::new();
+    let mut samples = Vec::with_capacity(BLOCK_SIZE);
+    loop {
+        let block = match blocks.read_next_or_eof(buffer) {
+            Ok(Some(block)) => block,
+            Ok(None) => break,
+            Err(claxon::Error::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                break
+            }
+            Err(e) => return Err(e.into()),
+        };
+        samples.clear();
+        samples.extend(block.channel(0).iter().map(|&s| s as i16));
+        on_chunk(&samples)?;
+        total += samples.len() as u64;
+        buffer = block.into_buffer();
+    }
+    Ok(total)
+}
+
+/// Streaming encoder; `total_samples` is the declared sample count
+/// (0 = unknown, as in STREAMINFO)
+pub struct FlacEncoder<W: Write> {
+    out: W,
+    pending: Vec<i32>,
+    frame_number: u64,
+    total_samples: u64,
+    written_samples: u64,
+}
+
+impl<W: Write> FlacEncoder<W> {
+    pub fn new(mut out: W, total_samples: u64) -> Result<Self> {
+        out.write_all(b"fLaC")?;
+        out.write_all(&streaminfo_block(total_samples))?;
+        Ok(Self {
+            out,
+            pending: Vec::with_capacity(BLOCK_SIZE),
+            frame_number: 0,
+            total_samples,
+            written_samples: 0,
+        })
+    }
+
+    pub fn write_samples(&mut self, samples: &[i16]) -> Result<()> {
+        for &sample in samples {
+            self.pending.push(sample as i32);
+            if self.pending.len() == BLOCK_SIZE {
+                self.flush_frame()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Samples written so far (including those not yet in a frame)
+    pub fn samples_written(&self) -> u64 {
+        self.written_samples + self.pending.len() as u64
+    }
+
+    /// Output the completed frames are written to
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.out
+    }
+
+    /// Write the last (short) frame; fails if a declared sample count is off
+    pub fn finish(mut self) -> Result<W> {
+        if !self.pending.is_empty() {
+            self.flush_frame()?;
+        }
+        if self.total_samples != 0 && self.written_samples != self.total_samples {
+            anyhow::bail!(
+                "FLAC sample count mismatch: declared {}, written {}",
+                self.total_samples,
+                self.written_samples
+            );
+        }
+        self.out.flush()?;
+        Ok(self.out)
+    }
+
+    fn flush_frame(&mut self) -> Result<()> {
+        let frame = encode_frame(&self.pending, self.frame_number);
+        self.out.write_all(&frame)?;
+        self.written_samples += self.pending.len() as u64;
+        self.frame_number += 1;
+        self.pending.clear();
+        Ok(())
+    }
+}
+
+/// FLAC file recorded incrementally (sample count unknown until close)
+pub struct FlacFileWriter {
+    path: PathBuf,
+    encoder: Option<FlacEncoder<BufWriter<std::fs::File>>>,
+}
+
+impl FlacFileWriter {
+    pub fn create(path: PathBuf) -> Result<Self> {
+        let file = crate::storage::create_file_owner_only(&path)?;
+        let encoder = FlacEncoder::new(BufWriter::new(file), 0)?;
+        Ok(Self {
+            path,
+            encoder: Some(encoder),
+        })
+    }
+
+    pub fn write_samples(&mut self, samples: &[i16]) -> Result<()> {
+        match self.encoder.as_mut() {
+            Some(encoder) => encoder.write_samples(samples),
+            None => anyhow::bail!("FLAC writer already closed: {:?}", self.path),
+        }
+    }
+
+    pub fn samples_written(&self) -> u64 {
+        self.encoder
+            .as_ref()
+            .map(|encoder| encoder.samples_written())
+            .unwrap_or(0)
+    }
+
+    /// Sync the completed frames to disk (the current block stays pending)
+    pub fn flush(&mut self) -> Result<()> {
+        if let Some(encoder) = self.encoder.as_mut() {
+            let out = encoder.get_mut();
+            out.flush()?;
+            out.get_ref().sync_data()?;
+        }
+        Ok(())
+    }
+
+    /// Write the last frame and the final sample count
+    pub fn close(mut self) -> Result<()> {
+        self.finalize()
+    }
+
+    fn finalize(&mut self) -> Result<()> {
+        let Some(encoder) = self.encoder.take() else {
+            return Ok(());
+        };
+        let total_samples = encoder.samples_written();
+        let mut file = encoder.finish()?.into_inner().map_err(|e| e.into_error())?;
+        file.seek(SeekFrom::Start(4))?;
+        file.write_all(&streaminfo_block(total_samples))?;
+        file.sync_all()?;
+        Ok(())
+    }
+}
+
+impl Drop for FlacFileWriter {
+    fn drop(&mut self) {
+        // Same as AudioWriter: finish the file even without close()
+        let _ = self.finalize();
+    }
+}
+
+/// "fLaC" metadata: a single (last) STREAMINFO block
+fn streaminfo_block(total_samples: u64) -> Vec<u8> {
+    let mut bits = BitWriter::default();
+    bits.write(1, 1); // last metadata block
+    bits.write(0, 7); // STREAMINFO
+    bits.write(34, 24);
+    bits.write(BLOCK_SIZE as u64, 16); // min block size (last frame excluded)
+    bits.write(BLOCK_SIZE as u64, 16); // max block size
+    bits.write(0, 24); // min frame size (unknown)
+    bits.write(0, 24); // max frame size (unknown)
+    bits.write(SAMPLE_RATE as u64, 20);
+    bits.write(0, 3); // channels - 1
+    bits.write((BITS_PER_SAMPLE - 1) as u64, 5);
+    bits.write(total_samples & ((1 << 36) - 1), 36);
+    bits.write_bytes(&[0u8; 16]); // MD5 (unset)
+    bits.into_bytes()
+}
+
+fn encode_frame(samples: &[i32], frame_number: u64) -> Vec<u8> {
+    let mut bits = BitWriter::default();
+    bits.write(0b11_1111_1111_1110, 14); // sync
+    bits.write(0, 1);
+    bits.write(0, 1); // fixed block size stream
+    let short = samples.len() != BLOCK_SIZE;
+    bits.write(if short { 0b0111 } else { 0b1100 }, 4);
+    bits.write(0b0101, 4); // 16kHz
+    bits.write(0b0000, 4); // mono
+    bits.write(0b100, 3); // 16 bit
+    bits.write(0, 1);
+    write_utf8_number(&mut bits, frame_number);
+    if short {
+        bits.write(samples.len() as u64 - 1, 16);
+    }
+    let crc = crc8(bits.bytes());
+    bits.write(crc as u64, 8);
+
+    write_subframe(&mut bits, samples);
+    bits.align();
+    let crc = crc16(bits.bytes());
+    bits.write(crc as u64, 16);
+    bits.into_bytes()
+}
+
+fn write_subframe(bits: &mut BitWriter, samples: &[i32]) {
+    if samples.iter().all(|&s| s == samples[0]) {
+        bits.write(0, 1);
+        bits.write(0b000000, 6); // CONSTANT
+        bits.write(0, 1);
+        bits.write_signed(samples[0], BITS_PER_SAMPLE);
+        return;
+    }
+
+    let verbatim_bits = samples.len() as u64 * BITS_PER_SAMPLE as u64;
+    let best = (0..=4usize.min(samples.len() - 1))
+        .map(|order| {
+            let residuals = fixed_residuals(samples, order);
+            let (param, size) = best_rice_param(&residuals);
+            let size = size + order as u64 * BITS_PER_SAMPLE as u64 + 10;
+            (order, residuals, param, size)
+        })
+        .min_by_key(|(_, _, _, size)| *size);
+
+    match best {
+        Some((order, residuals, param, size)) if size < verbatim_bits => {
+            bits.write(0, 1);
+            bits.write(0b001000 | order as u64, 6); // FIXED
+            bits.write(0, 1);
+            for &warmup in &samples[..order] {
+                bits.write_signed(warmup, BITS_PER_SAMPLE);
+            }
+            bits.write(0b00, 2); // Rice, 4-bit parameters
+            bits.write(0, 4); // partition order 0
+            bits.write(param as u64, 4);
+            for &residual in &residuals {
+                bits.write_rice(residual, param);
+            }
+        }
+        _ => {
+            bits.write(0, 1);
+            bits.write(0b000001, 6); // VERBATIM
+            bits.write(0, 1);
+            for &sample in samples {
+                bits.write_signed(sample, BITS_PER_SAMPLE);
+            }
+        }
+    }
+}
+
+/// Residuals of the fixed polynomial predictor of `order`
+fn fixed_residuals(samples: &[i32], order: usize) -> Vec<i32> {
+    (order..samples.len())
+        .map(|n| {
+            let x = |k: usize| samples[n - k];
+            match order {
+                0 => x(0),
+                1 => x(0) - x(1),
+                2 => x(0) - 2 * x(1) + x(2),
+                3 => x(0) - 3 * x(1) + 3 * x(2) - x(3),
+                _ => x(0) - 4 * x(1) + 6 * x(2) - 4 * x(3) + x(4),
+            }
+        })
+        .collect()
+}
+
+fn zigzag(value: i32) -> u64 {
+    ((value << 1) ^ (value >> 31)) as u32 as u64
+}
+
+/// Rice parameter with the fewest bits, and that size
+fn best_rice_param(residuals: &[i32]) -> (u32, u64) {
+    (0..=MAX_RICE_PARAM)
+        .map(|param| {
+            let bits: u64 = residuals
+                .iter()
+                .map(|&r| (zigzag(r) >> param) + 1 + param as u64)
+                .sum();
+            (param, bits)
+        })
+        .min_by_key(|(_, bits)| *bits)
+        .unwrap_or((0, 0))
+}
+
+/// Frame number in FLAC's extended UTF-8 coding
+fn write_utf8_number(bits: &mut BitWriter, value: u64) {
+    if value < 0x80 {
+        bits.write(value, 8);
+        return;
+    }
+    let continuation_bytes = match value {
+        v if v < 0x800 => 1,
+        v if v < 0x1_0000 => 2,
+        v if v < 0x20_0000 => 3,
+        v if v < 0x400_0000 => 4,
+        v if v < 0x8000_0000 => 5,
+        _ => 6,
+    };
+    let lead_marker = (0xFF00u64 >> (continuation_bytes + 1)) & 0xFF;
+    bits.write(lead_marker | (value >> (6 * continuation_bytes)), 8);
+    for i in (0..continuation_bytes).rev() {
+        bits.write(0x80 | ((value >> (6 * i)) & 0x3F), 8);
+    }
+}
+
+fn crc8(data: &[u8]) -> u8 {
+    data.iter().fold(0u8, |crc, &byte| {
+        (0..8).fold(crc ^ byte, |crc, _| {
+            if crc & 0x80 != 0 {
+                (crc << 1) ^ 0x07
+            } else {
+                crc << 1
+            }
+        })
+    })
+}
+
+fn crc16(data: &[u8]) -> u16 {
+    data.iter().fold(0u16, |crc, &byte| {
+        (0..8).fold(crc ^ ((byte as u16) << 8), |crc, _| {
+            if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x8005
+            } else {
+                crc << 1
+            }
+        })
+    })
+}
+
+/// MSB-first bit writer
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    acc: u64,
+    acc_bits: u32,
+}
+
+impl BitWriter {
+    fn write(&mut self, value: u64, bits: u32) {
+        for i in (0..bits).rev() {
+            self.acc = (self.acc << 1) | ((value >> i) & 1);
+            self.acc_bits += 1;
+            if self.acc_bits == 8 {
+                self.bytes.push(self.acc as u8);
+                self.acc = 0;
+                self.acc_bits = 0;
+            }
+        }
+    }
+
+    fn write_signed(&mut self, value: i32, bits: u32) {
+        self.write(value as u32 as u64 & ((1 << bits) - 1), bits);
+    }
+
+    fn write_rice(&mut self, value: i32, param: u32) {
+        let folded = zigzag(value);
+        let mut quotient = folded >> param;
+        while quotient >= 32 {
+            self.write(0, 32);
+            quotient -= 32;
+        }
+        self.write(1, quotient as u32 + 1);
+        self.write(folded & ((1 << param) - 1), param);
+    }
+
+    fn write_bytes(&mut self, data: &[u8]) {
+        for &byte in data {
+            self.write(byte as u64, 8);
+        }
+    }
+
+    /// Pad with zero bits to the next byte boundary
+    fn align(&mut self) {
+        if self.acc_bits > 0 {
+            self.write(0, 8 - self.acc_bits);
+        }
+    }
+
+    /// Completed bytes (call on a byte boundary)
+    fn bytes(&self) -> &[u8] {
+        debug_assert_eq!(self.acc_bits, 0);
+        &self.bytes
+    }
+
+    fn into_bytes(mut self) -> Vec<u8> {
+        self.align();
+        self.bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roundtrip(samples: &[i16]) -> Vec<u8> {
+        let mut encoder = FlacEncoder::new(Vec::new(), samples.len() as u64).unwrap();
+        encoder.write_samples(samples).unwrap();
+        let flac = encoder.finish().unwrap();
+
+        let mut decoded = Vec::new();
+        let total = decode(flac.as_slice(), |chunk| {
+            decoded.extend_from_slice(chunk);
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(total, samples.len() as u64);
+        assert_eq!(decoded, samples);
+        flac
+    }
+
+    #[test]
+    fn test_roundtrip_is_lossless_and_compresses_speechlike_audio() {
+        // Tone with noise and a few clipped samples, over several frames
+        let mut seed = 7u32;
+        let samples: Vec<i16> = (0..BLOCK_SIZE * 3 + 123)
+            .map(|i| {
+                seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                let noise = ((seed >> 16) % 200) as f64 - 100.0;
+                let tone = (i as f64 * 0.05).sin() * 8000.0;
+                if i % 5000 == 0 {
+                    i16::MIN
+                } else {
+                    (tone + noise) as i16
+                }
+            })
+            .collect();
+        let flac = roundtrip(&samples);
+        assert!(flac.len() < samples.len() * 2 * 3 / 4);
+    }
+
+    #[test]
+    fn test_roundtrip_edge_cases() {
+        roundtrip(&[]);
+        roundtrip(&[42]);
+        roundtrip(&[0; BLOCK_SIZE]); // constant subframe
+                                     // Full-scale noise falls back to verbatim
+        let noise: Vec<i16> = (0..1000u32)
+            .map(|i| (i.wrapping_mul(2_654_435_761) >> 16) as u16 as i16)
+            .collect();
+        roundtrip(&noise);
+    }
+
+    #[test]
+    fn test_utf8_frame_numbers() {
+        for (value, expected) in [
+            (0x7Fu64, vec![0x7F]),
+            (0x80, vec![0xC2, 0x80]),
+            (0x1_0000, vec![0xF0, 0x90, 0x80, 0x80]),
+        ] {
+            let mut bits = BitWriter::default();
+            write_utf8_number(&mut bits, value);
+            assert_eq!(bits.into_bytes(), expected);
+        }
+    }
+}
@@ -0,0 +1,290 @@
+//! Furigana (Reading Annotations) for Exports
+//!
+//! Optional post-processing stage for teams reviewing Japanese minutes with
+//! non-native readers: words containing rare kanji get their reading as
+//! ruby annotations (`<ruby>議事録<rt>ぎじろく</rt></ruby>`) in the Markdown
+//! and HTML exports (Markdown viewers render it as inline HTML), as Word
+//! ruby in the DOCX export, and in parentheses (`議事録（ぎじろく）`) in the
+//! plain text export.
+//!
+//! Readings come from a reading dictionary chosen by the user, matched
+//! longest-first. Supported line formats (UTF-8):
+//! - `surface<TAB>reading`
+//! - MeCab IPADIC CSV (`surface,left_id,right_id,cost,pos,...,reading,pronunciation`),
+//!   so a UTF-8 converted mecab-ipadic or user dictionary can be used as is
+//!
+//! A kanji is rare unless it is one of the first- and second-grade education
+//! kanji or listed in `known_kanji`.
+//!
+//! Persisted to `settings/furigana.json` in app data directory.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+/// Kanji never annotated: first- and second-grade education kanji (240)
+pub const COMMON_KANJI: &str = concat!(
+    "一右雨円王音下火花貝学気九休玉金空月犬見五口校左三山子四糸字耳七車手十出女小上森人水正",
+    "生青夕石赤千川先早草足村大男竹中虫町天田土二日入年白八百文木本名目立力林六",
+    "引羽雲園遠何科夏家歌画回会海絵外角楽活間丸岩顔汽記帰弓牛魚京強教近兄形計元言原戸古午後",
+    "語工公広交光考行高黄合谷国黒今才細作算止市矢姉思紙寺自時室社弱首秋週春書少場色食心新親",
+    "図数西声星晴切雪船線前組走多太体台地池知茶昼長鳥朝直通弟店点電刀冬当東答頭同道読内南肉",
+    "馬売買麦半番父風分聞米歩母方北毎妹万明鳴毛門夜野友用曜来里理話",
+);
+
+/// Furigana settings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FuriganaSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Reading dictionary file (required when enabled)
+    #[serde(default)]
+    pub dictionary_path: Option<PathBuf>,
+    /// Additional kanji the readers know (not annotated)
+    #[serde(default)]
+    pub known_kanji: String,
+    /// Settings version for future migrations
+    #[serde(default = "default_version")]
+    pub version: u32,
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+impl Default for FuriganaSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            dictionary_path: None,
+            known_kanji: String::new(),
+            version: 1,
+        }
+    }
+}
+
+impl FuriganaSettings {
+    pub fn validate(&self) -> Result<()> {
+        if self.enabled && self.dictionary_path.is_none() {
+            anyhow::bail!("A reading dictionary is required to add furigana");
+        }
+        Ok(())
+    }
+}
+
+/// Part of annotated text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RubySegment {
+    pub text: String,
+    /// Hiragana reading (rare-kanji words only)
+    pub reading: Option<String>,
+}
+
+/// Reading dictionary plus the kanji that need no annotation
+#[derive(Debug, Default)]
+pub struct Furigana {
+    readings: HashMap<String, String>,
+    /// Longest dictionary surface (chars)
+    max_surface_chars: usize,
+    known_kanji: HashSet<char>,
+}
+
+impl Furigana {
+    /// Build from (surface, reading) pairs
+    ///
+    /// Entries without kanji are skipped; the first reading of a surface wins.
+    pub fn new<I>(entries: I, known_kanji: &str) -> Self
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let mut furigana = Self {
+            known_kanji: COMMON_KANJI.chars().chain(known_kanji.chars()).collect(),
+            ..Default::default()
+        };
+        for (surface, reading) in entries {
+            if !surface.chars().any(is_kanji) || reading.is_empty() {
+                continue;
+            }
+            furigana.max_surface_chars = furigana.max_surface_chars.max(surface.chars().count());
+            furigana
+                .readings
+                .entry(surface)
+                .or_insert_with(|| to_hiragana(&reading));
+        }
+        furigana
+    }
+
+    /// Load the dictionary of enabled `settings` (None when disabled)
+    pub fn from_settings(settings: &FuriganaSettings) -> Result<Option<Self>> {
+        if !settings.enabled {
+            return Ok(None);
+        }
+        let path = settings
+            .dictionary_path
+            .as_deref()
+            .context("A reading dictionary is required to add furigana")?;
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read reading dictionary: {:?}", path))?;
+        Ok(Some(Self::new(
+            parse_dictionary(&text),
+            &settings.known_kanji,
+        )))
+    }
+
+    /// Number of dictionary entries
+    pub fn len(&self) -> usize {
+        self.readings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.readings.is_empty()
+    }
+
+    /// Split `text` into plain runs and rare-kanji words with readings
+    pub fn annotate(&self, text: &str) -> Vec<RubySegment> {
+        let chars: Vec<char> = text.chars().collect();
+        let mut segments: Vec<RubySegment> = Vec::new();
+        let mut plain = String::new();
+        let mut i = 0;
+        while i < chars.len() {
+            match self.longest_match(&chars[i..]) {
+                Some((len, reading)) => {
+                    if !plain.is_empty() {
+                        segments.push(RubySegment {
+                            text: std::mem::take(&mut plain),
+                            reading: None,
+                        });
+                    }
+                    segments.push(RubySegment {
+                        text: chars[i..i + len].iter().collect(),
+                        reading: Some(reading.to_string()),
+                    });
+                    i += len;
+                }
+                None => {
+                    plain.push(chars[i]);
+                    i += 1;
+                }
+            }
+        }
+        if !plain.is_empty() {
+            segments.push(RubySegment {
+                text: plain,
+                reading: None,
+            });
+        }
+        segments
+    }
+
+    /// Longest dictionary word at the start of `chars` that has a rare kanji
+    fn longest_match(&self, chars: &[char]) -> Option<(usize, &str)> {
+        if !chars.first().copied().is_some_and(is_kanji) {
+            return None;
+        }
+        (1..=self.max_surface_chars.min(chars.len()))
+            .rev()
+            .find_map(|len| {
+                let surface: String = chars[..len].iter().collect();
+                let reading = self.readings.get(&surface)?;
+                let rare = surface
+                    .chars()
+                    .any(|c| is_kanji(c) && !self.known_kanji.contains(&c));
+                rare.then_some((len, reading.as_str()))
+            })
+    }
+}
+
+/// Parse dictionary lines into (surface, reading) pairs
+fn parse_dictionary(text: &str) -> Vec<(String, String)> {
+    text.lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                return None;
+            }
+            if let Some((surface, reading)) = line.split_once('\t') {
+                return Some((surface.trim().to_string(), reading.trim().to_string()));
+            }
+            let fields: Vec<&str> = line.split(',').collect();
+            // IPADIC: reading is the 12th field ("*" when unknown)
+            match fields.get(11) {
+                Some(reading) if *reading != "*" => {
+                    Some((fields[0].to_string(), reading.to_string()))
+                }
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Katakana readings (as in IPADIC) to hiragana
+fn to_hiragana(reading: &str) -> String {
+    reading
+        .chars()
+        .map(|c| match c {
+            'ァ'..='ヶ' => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+            _ => c,
+        })
+        .collect()
+}
+
+fn is_kanji(c: char) -> bool {
+    matches!(c, '\u{3400}'..='\u{4DBF}' | '\u{4E00}'..='\u{9FFF}' | '々')
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+pub const SETTINGS_STEM: &str = "furigana";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_annotate_rare_kanji() {
+        let dictionary = "\
+# comment
+議事録\tぎじろく
+議事,1,1,3000,名詞,一般,*,*,*,*,議事,ギジ,ギジ
+会議,1,1,3000,名詞,一般,*,*,*,*,会議,カイギ,カイギ
+今日\tきょう
+謎,1,1,3000,名詞,一般,*,*,*,*,謎,*,*
+";
+        let furigana = Furigana::new(parse_dictionary(dictionary), "");
+        assert_eq!(furigana.len(), 4);
+
+        let segments = furigana.annotate("今日の議事録と会議");
+        assert_eq!(
+            segments,
+            vec![
+                RubySegment {
+                    text: "今日の".to_string(),
+                    reading: None
+                },
+                // Longest match wins over 議事
+                RubySegment {
+                    text: "議事録".to_string(),
+                    reading: Some("ぎじろく".to_string())
+                },
+                RubySegment {
+                    text: "と".to_string(),
+                    reading: None
+                },
+                RubySegment {
+                    text: "会議".to_string(),
+                    reading: Some("かいぎ".to_string())
+                },
+            ]
+        );
+
+        // Kanji the readers know are left alone
+        let furigana = Furigana::new(parse_dictionary(dictionary), "議");
+        assert!(furigana
+            .annotate("会議")
+            .iter()
+            .all(|s| s.reading.is_none()));
+    }
+}
@@ -0,0 +1,246 @@
+//! Recording Heartbeat
+//!
+//! While recording, a small heartbeat file is rewritten every few seconds with
+//! the session id, the number of audio samples persisted, the last IPC event
+//! sequence and a timestamp. The file is removed on a clean stop, so a
+//! heartbeat found at startup means the previous recording was interrupted.
+//!
+//! Path: `[app_data_dir]/recording_heartbeat.json`
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::storage::{LocalStorageService, SessionMetadata};
+
+const HEARTBEAT_FILENAME: &str = "recording_heartbeat.json";
+
+/// Interval between heartbeat writes
+pub const HEARTBEAT_INTERVAL_SECS: u64 = 5;
+
+/// A heartbeat older than this is considered stale (owner is gone)
+pub const HEARTBEAT_STALE_AFTER_MS: u64 = 3 * HEARTBEAT_INTERVAL_SECS * 1000;
+
+/// Recording state snapshot
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Heartbeat {
+    pub session_id: String,
+    /// Session start (epoch millis)
+    pub started_at_ms: u64,
+    /// Last heartbeat write (epoch millis)
+    pub updated_at_ms: u64,
+    /// Samples written to the session audio so far (16kHz mono)
+    pub last_sample_offset: u64,
+    /// Sequence number of the last IPC event received from the sidecar
+    pub last_event_seq: u64,
+    #[serde(default)]
+    pub audio_device: String,
+    /// Session UUID (stable reference, independent of the session ID format)
+    #[serde(default)]
+    pub session_uuid: Option<String>,
+}
+
+impl Heartbeat {
+    pub fn is_stale(&self, now_ms: u64) -> bool {
+        now_ms.saturating_sub(self.updated_at_ms) > HEARTBEAT_STALE_AFTER_MS
+    }
+}
+
+/// Interrupted recording detected at startup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InterruptedRecording {
+    pub session_id: String,
+    /// Last heartbeat time (epoch millis)
+    pub interrupted_at_ms: u64,
+    /// Audio recovered into the session audio file (seconds)
+    pub recovered_audio_seconds: f64,
+    /// User-facing message ("Recording was interrupted at 14:32")
+    pub message: String,
+}
+
+fn heartbeat_path(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join(HEARTBEAT_FILENAME)
+}
+
+/// Write heartbeat (temp file + rename so readers never see a partial file)
+pub fn write_heartbeat(app_data_dir: &Path, heartbeat: &Heartbeat) -> Result<()> {
+    std::fs::create_dir_all(app_data_dir)?;
+    let path = heartbeat_path(app_data_dir);
+    let tmp_path = path.with_extension("json.tmp");
+
+    let json = serde_json::to_string(heartbeat).context("Failed to serialize heartbeat")?;
+    crate::storage::write_file_owner_only(&tmp_path, json.as_bytes())?;
+    std::fs::rename(&tmp_path, &path)
+        .with_context(|| format!("Failed to replace heartbeat file: {:?}", path))?;
+    Ok(())
+}
+
+/// Read heartbeat (None if no recording was in progress)
+pub fn read_heartbeat(app_data_dir: &Path) -> Result<Option<Heartbeat>> {
+    let path = heartbeat_path(app_data_dir);
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let json = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read heartbeat file: {:?}", path))?;
+    let heartbeat = serde_json::from_str(&json).context("Failed to parse heartbeat file")?;
+    Ok(Some(heartbeat))
+}
+
+/// Remove heartbeat on clean stop
+pub fn clear_heartbeat(app_data_dir: &Path) -> Result<()> {
+    let path = heartbeat_path(app_data_dir);
+    if path.exists() {
+        std::fs::remove_file(&path)
+            .with_context(|| format!("Failed to remove heartbeat file: {:?}", path))?;
+    }
+    Ok(())
+}
+
+/// Format "Recording was interrupted at HH:MM" in local time
+pub fn interruption_message(interrupted_at_ms: u64) -> String {
+    let time = chrono::DateTime::from_timestamp_millis(interrupted_at_ms as i64)
+        .unwrap_or_default()
+        .with_timezone(&chrono::Local)
+        .format("%H:%M");
+    format!("Recording was interrupted at {}", time)
+}
+
+/// Crash recovery for the session referenced by a stale heartbeat
+///
+/// - Repairs the audio.wav and per-input track headers (sizes are only
+///   written on clean close); FLAC/Opus files stay readable up to their
+///   last complete frame/page
+/// - Writes session.json from the heartbeat and transcript if missing
+/// - Removes the heartbeat file
+pub fn recover_interrupted_session(
+    app_data_dir: &Path,
+    storage: &LocalStorageService,
+    heartbeat: &Heartbeat,
+) -> Result<InterruptedRecording> {
+    let session_dir = storage.get_session_dir(&heartbeat.session_id);
+
+    let audio_path = session_dir.join("audio.wav");
+    let recovered_samples = if audio_path.exists() {
+        crate::storage::repair_wav_header(&audio_path)?
+    } else if crate::storage::session_audio_path(&session_dir).is_some() {
+        heartbeat.last_sample_offset
+    } else {
+        0
+    };
+    crate::input_tracks::repair_tracks(&session_dir)?;
+
+    if session_dir.exists() && !session_dir.join("session.json").exists() {
+        let transcript = storage.load_transcript(&heartbeat.session_id)?;
+        let finals = transcript.iter().filter(|e| e.is_final);
+        let duration_ms = heartbeat
+            .updated_at_ms
+            .saturating_sub(heartbeat.started_at_ms);
+
+        let metadata = SessionMetadata {
+            session_id: heartbeat.session_id.clone(),
+            start_time: crate::storage::format_iso8601_millis(heartbeat.started_at_ms),
+            end_time: crate::storage::format_iso8601_millis(heartbeat.updated_at_ms),
+            duration_seconds: duration_ms / 1000,
+            audio_device: heartbeat.audio_device.clone(),
+            model_size: "unknown".to_string(),
+            total_segments: finals.clone().count() as u64,
+            total_characters: finals.map(|e| e.text.chars().count() as u64).sum(),
+            warnings: Vec::new(),
+            session_uuid: heartbeat.session_uuid.clone(),
+            title: None,
+            tags: Vec::new(),
+            stats: None,
+            levels: None,
+        };
+        storage.save_session_metadata(&metadata)?;
+    }
+
+    clear_heartbeat(app_data_dir)?;
+
+    Ok(InterruptedRecording {
+        session_id: heartbeat.session_id.clone(),
+        interrupted_at_ms: heartbeat.updated_at_ms,
+        recovered_audio_seconds: recovered_samples as f64 / 16000.0,
+        message: interruption_message(heartbeat.updated_at_ms),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::TranscriptionEvent;
+    use tempfile::TempDir;
+
+    fn heartbeat(session_id: &str, updated_at_ms: u64) -> Heartbeat {
+        Heartbeat {
+            session_id: session_id.to_string(),
+            started_at_ms: 1_000_000,
+            updated_at_ms,
+            last_sample_offset: 16_000,
+            last_event_seq: 7,
+            audio_device: "mic-1".to_string(),
+            session_uuid: Some("1a2b3c4d-0000-4000-8000-000000000000".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_write_read_clear_heartbeat() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(read_heartbeat(temp_dir.path()).unwrap().is_none());
+
+        let hb = heartbeat("s1", 1_005_000);
+        write_heartbeat(temp_dir.path(), &hb).unwrap();
+        assert_eq!(read_heartbeat(temp_dir.path()).unwrap(), Some(hb));
+
+        clear_heartbeat(temp_dir.path()).unwrap();
+        assert!(read_heartbeat(temp_dir.path()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_is_stale() {
+        let hb = heartbeat("s1", 1_000_000);
+        assert!(!hb.is_stale(1_000_000 + HEARTBEAT_STALE_AFTER_MS));
+        assert!(hb.is_stale(1_000_001 + HEARTBEAT_STALE_AFTER_MS));
+    }
+
+    #[test]
+    fn test_recover_interrupted_session_writes_metadata() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = LocalStorageService::new(temp_dir.path().to_path_buf());
+        storage.create_session("s1").unwrap();
+
+        let mut writer = storage.create_transcript_writer("s1").unwrap();
+        writer
+            .append_event(&TranscriptionEvent {
+                timestamp_ms: 1_000,
+                text: "途中まで".to_string(),
+                is_final: true,
+                speaker: None,
+            })
+            .unwrap();
+        writer.close().unwrap();
+
+        let hb = heartbeat("s1", 1_065_000);
+        write_heartbeat(temp_dir.path(), &hb).unwrap();
+
+        let interrupted = recover_interrupted_session(temp_dir.path(), &storage, &hb).unwrap();
+        assert_eq!(interrupted.session_id, "s1");
+        assert!(interrupted
+            .message
+            .starts_with("Recording was interrupted at "));
+
+        let loaded = storage.load_session("s1").unwrap();
+        assert_eq!(loaded.metadata.duration_seconds, 65);
+        assert_eq!(loaded.metadata.total_segments, 1);
+        assert_eq!(loaded.metadata.total_characters, 4);
+        assert_eq!(
+            loaded.metadata.session_uuid.as_deref(),
+            Some("1a2b3c4d-0000-4000-8000-000000000000")
+        );
+
+        // Heartbeat consumed by recovery
+        assert!(read_heartbeat(temp_dir.path()).unwrap().is_none());
+    }
+}
@@ -0,0 +1,654 @@
+//! Local HTTP API (read-only)
+//!
+//! Lets scripts and other apps read recorded sessions without speaking the
+//! WebSocket protocol. A small HTTP/1.1 server on the app's tokio runtime,
+//! bound to 127.0.0.1, one request per connection:
+//!
+//! - `GET /api/sessions` — session list (newest first)
+//! - `GET /api/sessions/<id>` — session metadata
+//! - `GET /api/sessions/<id>/transcript` — transcript events
+//! - `GET /api/sessions/<id>/audio` — the audio file; single `Range:
+//!   bytes=` requests are answered with 206 so players can seek
+//! - `GET /health` — the last watchdog report (`app_health`); 503 while
+//!   unhealthy, so supervisors can probe it without the token
+//!
+//! `HEAD` is accepted wherever `GET` is. Every other request needs
+//! `Authorization: Bearer <token>` (the token is in the settings), and the
+//! `Host` header must name the loopback interface so web pages can't reach
+//! the API through DNS rebinding. Sessions come from the active workspace.
+//!
+//! Disabled by default. Settings are machine-wide; persisted to
+//! `settings/http_api.json` in app data directory.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+use crate::app_health::HealthReport;
+use crate::storage::LocalStorageService;
+
+/// Largest accepted request line plus headers
+const MAX_HEAD_BYTES: usize = 8 * 1024;
+
+/// Time a client gets to send its request
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Shortest accepted token
+const MIN_TOKEN_LEN: usize = 16;
+
+/// HTTP API configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HttpApiSettings {
+    /// Serve the API
+    #[serde(default)]
+    pub enabled: bool,
+    /// Listening port (127.0.0.1)
+    #[serde(default = "default_port")]
+    pub port: u16,
+    /// Bearer token required on every request (generated when empty)
+    #[serde(default)]
+    pub token: String,
+    /// Settings version for future migrations
+    #[serde(default = "default_version")]
+    pub version: u32,
+}
+
+fn default_port() -> u16 {
+    9180
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+impl Default for HttpApiSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: default_port(),
+            token: String::new(),
+            version: 1,
+        }
+    }
+}
+
+impl HttpApiSettings {
+    pub fn validate(&self) -> Result<()> {
+        if self.port < 1024 {
+            anyhow::bail!("Port must be 1024 or higher");
+        }
+        if self.enabled && self.token.len() < MIN_TOKEN_LEN {
+            anyhow::bail!("Token must be at least {} characters", MIN_TOKEN_LEN);
+        }
+        Ok(())
+    }
+}
+
+/// Random token for new settings
+pub fn generate_token() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
+}
+
+// ============================================================================
+// Requests and responses
+// ============================================================================
+
+/// Parsed request head
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub method: String,
+    /// Path without the query string
+    pub path: String,
+    /// Headers with lowercased names
+    pub headers: Vec<(String, String)>,
+}
+
+impl Request {
+    /// Parse the request line and headers
+    pub fn parse(head: &str) -> Result<Self> {
+        let mut lines = head.split("\r\n");
+        let request_line = lines.next().unwrap_or_default();
+        let mut parts = request_line.split(' ');
+        let (Some(method), Some(target), Some(version)) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            anyhow::bail!("Malformed request line");
+        };
+        if !version.starts_with("HTTP/1.") {
+            anyhow::bail!("Unsupported HTTP version: {}", version);
+        }
+        let path = target.split('?').next().unwrap_or_default().to_string();
+
+        let mut headers = Vec::new();
+        for line in lines.take_while(|line| !line.is_empty()) {
+            let (name, value) = line.split_once(':').context("Malformed header")?;
+            headers.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+        }
+        Ok(Self {
+            method: method.to_string(),
+            path,
+            headers,
+        })
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Response body
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Body {
+    Bytes(Vec<u8>),
+    /// `len` bytes of a file from `start`
+    File {
+        path: PathBuf,
+        start: u64,
+        len: u64,
+    },
+}
+
+impl Body {
+    fn len(&self) -> u64 {
+        match self {
+            Body::Bytes(bytes) => bytes.len() as u64,
+            Body::File { len, .. } => *len,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub headers: Vec<(&'static str, String)>,
+    pub body: Body,
+}
+
+impl Response {
+    fn json(status: u16, value: &impl Serialize) -> Self {
+        Self {
+            status,
+            headers: vec![("Content-Type", "application/json".to_string())],
+            body: Body::Bytes(serde_json::to_vec(value).unwrap_or_default()),
+        }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self::json(status, &json!({ "error": message }))
+    }
+}
+
+/// Answer a request
+///
+/// `storage` is None until storage is initialized.
+pub fn respond(request: &Request, token: &str, storage: Option<&LocalStorageService>) -> Response {
+    if !request.header("host").is_some_and(is_loopback_host) {
+        return Response::error(403, "Host must be localhost");
+    }
+    let authorized = request
+        .header("authorization")
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|given| constant_time_eq(given.as_bytes(), token.as_bytes()));
+    if !authorized {
+        return Response::error(401, "Missing or invalid bearer token");
+    }
+    if request.method != "GET" && request.method != "HEAD" {
+        let mut response = Response::error(405, "Only GET and HEAD are supported");
+        response.headers.push(("Allow", "GET, HEAD".to_string()));
+        return response;
+    }
+    let Some(storage) = storage else {
+        return Response::error(503, "Storage not initialized");
+    };
+
+    let segments: Vec<&str> = request
+        .path
+        .trim_matches('/')
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .collect();
+    match segments.as_slice() {
+        ["api", "sessions"] => match storage.list_sessions() {
+            Ok(sessions) => Response::json(200, &sessions),
+            Err(e) => Response::error(500, &format!("{:#}", e)),
+        },
+        ["api", "sessions", session_id, rest @ ..] => {
+            if crate::session_id::validate_session_id(session_id).is_err()
+                || !storage.get_session_dir(session_id).is_dir()
+            {
+                return Response::error(404, "Session not found");
+            }
+            match rest {
+                [] => match storage.load_session(session_id) {
+                    Ok(session) => Response::json(200, &session.metadata),
+                    Err(e) => Response::error(500, &format!("{:#}", e)),
+                },
+                ["transcript"] => match storage.load_transcript(session_id) {
+                    Ok(transcript) => Response::json(200, &transcript),
+                    Err(e) => Response::error(500, &format!("{:#}", e)),
+                },
+                ["audio"] => audio_response(request, &storage.get_session_dir(session_id)),
+                _ => Response::error(404, "Not found"),
+            }
+        }
+        _ => Response::error(404, "Not found"),
+    }
+}
+
+/// Answer `GET /health` (no token needed; the report holds no session data)
+pub fn respond_health(request: &Request, report: &HealthReport) -> Response {
+    if !request.header("host").is_some_and(is_loopback_host) {
+        return Response::error(403, "Host must be localhost");
+    }
+    if request.method != "GET" && request.method != "HEAD" {
+        let mut response = Response::error(405, "Only GET and HEAD are supported");
+        response.headers.push(("Allow", "GET, HEAD".to_string()));
+        return response;
+    }
+    Response::json(report.status.http_status(), report)
+}
+
+/// The session audio, or the requested byte range of it
+fn audio_response(request: &Request, session_dir: &Path) -> Response {
+    let Some(path) = crate::storage::session_audio_path(session_dir) else {
+        return Response::error(404, "Session has no audio");
+    };
+    let size = match std::fs::metadata(&path) {
+        Ok(metadata) => metadata.len(),
+        Err(e) => return Response::error(500, &e.to_string()),
+    };
+    let content_type = match path.extension().and_then(|e| e.to_str()) {
+        Some("flac") => "audio/flac",
+        Some("opus") => "audio/ogg",
+        _ => "audio/wav",
+    };
+    let mut headers = vec![
+        ("Content-Type", content_type.to_string()),
+        ("Accept-Ranges", "bytes".to_string()),
+    ];
+
+    let (status, start, len) = match request.header("range").map(|r| parse_range(r, size)) {
+        None | Some(RangeRequest::Ignored) => (200, 0, size),
+        Some(RangeRequest::Satisfiable { start, end }) => {
+            headers.push(("Content-Range", format!("bytes {}-{}/{}", start, end, size)));
+            (206, start, end - start + 1)
+        }
+        Some(RangeRequest::Unsatisfiable) => {
+            let mut response = Response::error(416, "Range not satisfiable");
+            response
+                .headers
+                .push(("Content-Range", format!("bytes */{}", size)));
+            return response;
+        }
+    };
+    Response {
+        status,
+        headers,
+        body: Body::File { path, start, len },
+    }
+}
+
+/// Outcome of a `Range` header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeRequest {
+    /// Inclusive byte range within the file
+    Satisfiable {
+        start: u64,
+        end: u64,
+    },
+    Unsatisfiable,
+    /// Malformed or multi-range: the whole file is sent (RFC 9110 14.2)
+    Ignored,
+}
+
+/// Parse a single `bytes=` range against a file of `size` bytes
+pub fn parse_range(header: &str, size: u64) -> RangeRequest {
+    let Some(spec) = header.trim().strip_prefix("bytes=") else {
+        return RangeRequest::Ignored;
+    };
+    let Some((first, last)) = spec.trim().split_once('-') else {
+        return RangeRequest::Ignored;
+    };
+    if spec.contains(',') {
+        return RangeRequest::Ignored;
+    }
+    let (first, last) = (first.trim(), last.trim());
+    let range = match (first.parse::<u64>(), last.parse::<u64>()) {
+        // bytes=-N: the last N bytes
+        _ if first.is_empty() => match last.parse::<u64>() {
+            Ok(0) => return RangeRequest::Unsatisfiable,
+            Ok(suffix) => (size.saturating_sub(suffix), size.saturating_sub(1)),
+            Err(_) => return RangeRequest::Ignored,
+        },
+        // bytes=N-
+        (Ok(start), _) if last.is_empty() => (start, size.saturating_sub(1)),
+        (Ok(start), Ok(end)) if start <= end => (start, end.min(size.saturating_sub(1))),
+        _ => return RangeRequest::Ignored,
+    };
+    if size == 0 || range.0 >= size {
+        return RangeRequest::Unsatisfiable;
+    }
+    RangeRequest::Satisfiable {
+        start: range.0,
+        end: range.1,
+    }
+}
+
+/// Whether a `Host` header names the loopback interface
+fn is_loopback_host(host: &str) -> bool {
+    let name = match host.strip_prefix('[') {
+        Some(rest) => rest.split(']').next().unwrap_or_default(),
+        None => host.split(':').next().unwrap_or_default(),
+    };
+    matches!(name, "127.0.0.1" | "localhost" | "::1")
+}
+
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        206 => "Partial Content",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        416 => "Range Not Satisfiable",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    }
+}
+
+// ============================================================================
+// Server
+// ============================================================================
+
+/// Source of the storage to serve (follows workspace switches)
+pub type StorageProvider = Arc<dyn Fn() -> Option<LocalStorageService> + Send + Sync>;
+
+/// Source of the health report served at `/health`
+pub type HealthProvider = Arc<dyn Fn() -> HealthReport + Send + Sync>;
+
+/// Running HTTP API server; stopped on drop
+pub struct HttpApiServer {
+    port: u16,
+    cancel: CancellationToken,
+    accept_handle: Option<JoinHandle<()>>,
+}
+
+impl HttpApiServer {
+    /// Bind 127.0.0.1:`port` and serve until stopped
+    pub async fn start(
+        port: u16,
+        token: String,
+        storage: StorageProvider,
+        health: HealthProvider,
+    ) -> Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", port))
+            .await
+            .with_context(|| format!("Failed to bind HTTP API port {}", port))?;
+        let port = listener.local_addr()?.port();
+        let cancel = CancellationToken::new();
+        let token = Arc::new(token);
+
+        let accept_cancel = cancel.clone();
+        let accept_handle = tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    accepted = listener.accept() => {
+                        let Ok((stream, _)) = accepted else { continue };
+                        let token = Arc::clone(&token);
+                        let storage = Arc::clone(&storage);
+                        let health = Arc::clone(&health);
+                        tokio::spawn(async move {
+                            if let Err(e) =
+                                Self::handle_connection(stream, &token, storage, health).await
+                            {
+                                log_warn!(
+                                    "http_api",
+                                    "connection_error",
+                                    format!("{:#}", e)
+                                );
+                            }
+                        });
+                    }
+                    _ = accept_cancel.cancelled() => break,
+                }
+            }
+        });
+        Ok(Self {
+            port,
+            cancel,
+            accept_handle: Some(accept_handle),
+        })
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Stop accepting connections; returns once the port is released
+    pub async fn stop(mut self) {
+        self.cancel.cancel();
+        if let Some(handle) = self.accept_handle.take() {
+            let _ = handle.await;
+        }
+    }
+
+    async fn handle_connection(
+        mut stream: TcpStream,
+        token: &str,
+        storage: StorageProvider,
+        health: HealthProvider,
+    ) -> Result<()> {
+        let response = match tokio::time::timeout(READ_TIMEOUT, read_head(&mut stream)).await {
+            Ok(Ok(head)) => match Request::parse(&head) {
+                Ok(request) => {
+                    let head_only = request.method == "HEAD";
+                    if request.path == "/health" {
+                        let response = respond_health(&request, &health());
+                        return write_response(&mut stream, response, head_only).await;
+                    }
+                    let token = token.to_string();
+                    let response = tokio::task::spawn_blocking(move || {
+                        respond(&request, &token, storage().as_ref())
+                    })
+                    .await?;
+                    return write_response(&mut stream, response, head_only).await;
+                }
+                Err(e) => Response::error(400, &e.to_string()),
+            },
+            Ok(Err(e)) => Response::error(400, &e.to_string()),
+            Err(_) => return Ok(()),
+        };
+        write_response(&mut stream, response, false).await
+    }
+}
+
+impl Drop for HttpApiServer {
+    fn drop(&mut self) {
+        self.cancel.cancel();
+    }
+}
+
+/// Read up to the blank line ending the request head
+async fn read_head(stream: &mut TcpStream) -> Result<String> {
+    let mut head = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+        if head.len() > MAX_HEAD_BYTES {
+            anyhow::bail!("Request head too large");
+        }
+        let n = stream.read(&mut buf).await?;
+        if n == 0 {
+            anyhow::bail!("Connection closed before the request was complete");
+        }
+        head.extend_from_slice(&buf[..n]);
+    }
+    String::from_utf8(head).context("Request head is not UTF-8")
+}
+
+async fn write_response(stream: &mut TcpStream, response: Response, head_only: bool) -> Result<()> {
+    let mut head = format!(
+        "HTTP/1.1 {} {}\r\n",
+        response.status,
+        reason(response.status)
+    );
+    for (name, value) in &response.headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    head.push_str(&format!(
+        "Content-Length: {}\r\nCache-Control: no-store\r\nX-Content-Type-Options: nosniff\r\nConnection: close\r\n\r\n",
+        response.body.len()
+    ));
+    stream.write_all(head.as_bytes()).await?;
+
+    if !head_only {
+        match response.body {
+            Body::Bytes(bytes) => stream.write_all(&bytes).await?,
+            Body::File { path, start, len } => {
+                let mut file = tokio::fs::File::open(&path).await?;
+                file.seek(std::io::SeekFrom::Start(start)).await?;
+                tokio::io::copy(&mut file.take(len), stream).await?;
+            }
+        }
+    }
+    stream.shutdown().await?;
+    Ok(())
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+pub const SETTINGS_STEM: &str = "http_api";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const TOKEN: &str = "0123456789abcdef";
+
+    fn get(path: &str, extra: &str) -> Request {
+        Request::parse(&format!(
+            "GET {} HTTP/1.1\r\nHost: 127.0.0.1:9180\r\nAuthorization: Bearer {}\r\n{}\r\n",
+            path, TOKEN, extra
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(
+            parse_range("bytes=0-99", 1000),
+            RangeRequest::Satisfiable { start: 0, end: 99 }
+        );
+        assert_eq!(
+            parse_range("bytes=900-", 1000),
+            RangeRequest::Satisfiable {
+                start: 900,
+                end: 999
+            }
+        );
+        assert_eq!(
+            parse_range("bytes=-100", 1000),
+            RangeRequest::Satisfiable {
+                start: 900,
+                end: 999
+            }
+        );
+        assert_eq!(
+            parse_range("bytes=500-5000", 1000),
+            RangeRequest::Satisfiable {
+                start: 500,
+                end: 999
+            }
+        );
+        assert_eq!(
+            parse_range("bytes=1000-", 1000),
+            RangeRequest::Unsatisfiable
+        );
+        assert_eq!(parse_range("bytes=-0", 1000), RangeRequest::Unsatisfiable);
+        assert_eq!(parse_range("bytes=0-1,5-9", 1000), RangeRequest::Ignored);
+        assert_eq!(parse_range("bytes=9-1", 1000), RangeRequest::Ignored);
+        assert_eq!(parse_range("items=0-1", 1000), RangeRequest::Ignored);
+    }
+
+    #[test]
+    fn test_respond_routes_and_guards() {
+        use crate::recording_session::RecordingSession;
+        use crate::storage::AudioFormat;
+
+        let dir = TempDir::new().unwrap();
+        let storage = LocalStorageService::new(dir.path().to_path_buf());
+        let session = RecordingSession::new("s1", "u1", 0);
+        session.start(Some(&storage), AudioFormat::Wav).unwrap();
+        session.stop(Some(&storage), "mic", Vec::new()).unwrap();
+
+        let ok = |request: &Request| respond(request, TOKEN, Some(&storage));
+        assert_eq!(ok(&get("/api/sessions", "")).status, 200);
+        assert_eq!(ok(&get("/api/sessions/s1", "")).status, 200);
+        assert_eq!(ok(&get("/api/sessions/s1/transcript", "")).status, 200);
+        assert_eq!(ok(&get("/api/sessions/missing", "")).status, 404);
+        assert_eq!(ok(&get("/api/sessions/..%2F..", "")).status, 404);
+        assert_eq!(ok(&get("/other", "")).status, 404);
+
+        let audio = ok(&get("/api/sessions/s1/audio", "Range: bytes=0-3\r\n"));
+        assert_eq!(audio.status, 206);
+        assert!(matches!(
+            audio.body,
+            Body::File {
+                start: 0,
+                len: 4,
+                ..
+            }
+        ));
+        assert!(audio
+            .headers
+            .iter()
+            .any(|(name, value)| *name == "Content-Range" && value.starts_with("bytes 0-3/")));
+
+        // Guards
+        let unauthorized = Request::parse("GET /api/sessions HTTP/1.1\r\nHost: localhost\r\n\r\n");
+        assert_eq!(ok(&unauthorized.unwrap()).status, 401);
+        let mut rebound = get("/api/sessions", "");
+        rebound.headers[0].1 = "attacker.example".to_string();
+        assert_eq!(ok(&rebound).status, 403);
+        let mut post = get("/api/sessions", "");
+        post.method = "POST".to_string();
+        assert_eq!(ok(&post).status, 405);
+        assert_eq!(respond(&get("/api/sessions", ""), TOKEN, None).status, 503);
+
+        // Health needs no token but still a loopback Host
+        use crate::app_health::{HealthReport, HealthStatus};
+        let mut report = HealthReport::starting(0);
+        let probe = Request::parse("GET /health HTTP/1.1\r\nHost: localhost:9180\r\n\r\n").unwrap();
+        assert_eq!(respond_health(&probe, &report).status, 200);
+        report.status = HealthStatus::Unhealthy;
+        assert_eq!(respond_health(&probe, &report).status, 503);
+        assert_eq!(respond_health(&rebound, &report).status, 403);
+
+        // Settings
+        let mut settings = HttpApiSettings {
+            enabled: true,
+            ..Default::default()
+        };
+        assert!(settings.validate().is_err());
+        settings.token = generate_token();
+        settings.validate().unwrap();
+    }
+}
@@ -0,0 +1,190 @@
+//! Capture Sample Format Negotiation
+//!
+//! Not every device delivers `f32`: some only offer `i16` or `u16` through
+//! cpal, and a stream built for `f32` then fails to build. Adapters pick the
+//! input config with `select_input_config` — the device's default rate and
+//! channel count in the most convenient sample format it supports (`f32`,
+//! then `i16`, then `u16`) — and build the stream with
+//! `build_f32_input_stream`, which converts other formats so the audio
+//! callback always receives `f32` samples.
+//!
+//! When a selected input lies beyond the default channel count (see
+//! `channel_selection`), the config with the fewest channels that includes
+//! it is used instead.
+
+use anyhow::{anyhow, Result};
+use cpal::traits::DeviceTrait;
+use cpal::{Sample, SampleFormat, SupportedStreamConfig};
+
+/// Sample formats in order of preference (`f32` needs no conversion)
+const FORMAT_PREFERENCE: [SampleFormat; 3] =
+    [SampleFormat::F32, SampleFormat::I16, SampleFormat::U16];
+
+/// Most preferred of the `supported` formats (None when empty)
+pub fn preferred_format(supported: &[SampleFormat]) -> Option<SampleFormat> {
+    FORMAT_PREFERENCE
+        .into_iter()
+        .find(|format| supported.contains(format))
+}
+
+/// Input config for `device`: its default rate and channel count (at least
+/// `min_channels`), in the preferred sample format supported at that rate
+///
+/// Falls back to the default config when the supported configs can't be
+/// listed and the default has enough channels.
+pub fn select_input_config(
+    device: &cpal::Device,
+    min_channels: u16,
+) -> Result<SupportedStreamConfig> {
+    let default = device.default_input_config()?;
+    let ranges: Vec<_> = match device.supported_input_configs() {
+        Ok(configs) => configs
+            .filter(|range| {
+                range.min_sample_rate() <= default.sample_rate()
+                    && default.sample_rate() <= range.max_sample_rate()
+            })
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+
+    let channels = if default.channels() >= min_channels {
+        default.channels()
+    } else {
+        ranges
+            .iter()
+            .map(|range| range.channels())
+            .filter(|&channels| channels >= min_channels)
+            .min()
+            .ok_or_else(|| {
+                anyhow!(
+                    "Device has fewer than {} input channels at {} Hz",
+                    min_channels,
+                    default.sample_rate().0
+                )
+            })?
+    };
+    let candidates: Vec<_> = ranges
+        .into_iter()
+        .filter(|range| range.channels() == channels)
+        .collect();
+
+    let formats: Vec<_> = candidates
+        .iter()
+        .map(|range| range.sample_format())
+        .collect();
+    match preferred_format(&formats) {
+        Some(format) if format != default.sample_format() || channels != default.channels() => {
+            Ok(candidates
+                .into_iter()
+                .find(|range| range.sample_format() == format)
+                .map(|range| range.with_sample_rate(default.sample_rate()))
+                .unwrap_or(default))
+        }
+        _ => Ok(default),
+    }
+}
+
+/// Largest input channel count `device` supports (its default when the
+/// supported configs can't be listed)
+pub fn max_input_channels(device: &cpal::Device) -> Result<u16> {
+    let default_channels = device.default_input_config()?.channels();
+    Ok(device
+        .supported_input_configs()
+        .ok()
+        .and_then(|configs| configs.map(|range| range.channels()).max())
+        .unwrap_or(default_channels)
+        .max(default_channels))
+}
+
+/// Build an input stream on `config` whose data callback receives `f32`
+/// samples, whatever the config's sample format
+pub fn build_f32_input_stream<D, E>(
+    device: &cpal::Device,
+    config: &SupportedStreamConfig,
+    on_data: D,
+    on_error: E,
+) -> Result<cpal::Stream, cpal::BuildStreamError>
+where
+    D: FnMut(&[f32]) + Send + 'static,
+    E: FnMut(cpal::StreamError) + Send + 'static,
+{
+    let stream_config = config.config();
+    match config.sample_format() {
+        SampleFormat::F32 => {
+            let mut on_data = on_data;
+            device.build_input_stream(
+                &stream_config,
+                move |data: &[f32], _: &cpal::InputCallbackInfo| on_data(data),
+                on_error,
+            )
+        }
+        SampleFormat::I16 => {
+            build_converting::<i16, _, _>(device, &stream_config, on_data, on_error)
+        }
+        SampleFormat::U16 => {
+            build_converting::<u16, _, _>(device, &stream_config, on_data, on_error)
+        }
+    }
+}
+
+fn build_converting<T, D, E>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    mut on_data: D,
+    on_error: E,
+) -> Result<cpal::Stream, cpal::BuildStreamError>
+where
+    T: Sample,
+    D: FnMut(&[f32]) + Send + 'static,
+    E: FnMut(cpal::StreamError) + Send + 'static,
+{
+    // Reused across callbacks (no allocation once warmed up)
+    let mut converted = Vec::new();
+    device.build_input_stream(
+        config,
+        move |data: &[T], _: &cpal::InputCallbackInfo| {
+            samples_to_f32(data, &mut converted);
+            on_data(&converted);
+        },
+        on_error,
+    )
+}
+
+/// Convert samples to `f32` in [-1, 1] into `out`
+pub fn samples_to_f32<T: Sample>(data: &[T], out: &mut Vec<f32>) {
+    out.clear();
+    out.extend(data.iter().map(Sample::to_f32));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preferred_format() {
+        assert_eq!(
+            preferred_format(&[SampleFormat::I16, SampleFormat::F32]),
+            Some(SampleFormat::F32)
+        );
+        assert_eq!(
+            preferred_format(&[SampleFormat::U16, SampleFormat::I16]),
+            Some(SampleFormat::I16)
+        );
+        assert_eq!(
+            preferred_format(&[SampleFormat::U16]),
+            Some(SampleFormat::U16)
+        );
+        assert_eq!(preferred_format(&[]), None);
+    }
+
+    #[test]
+    fn test_integer_samples_convert_to_f32() {
+        let mut out = Vec::new();
+        samples_to_f32(&[0i16, i16::MIN, i16::MAX], &mut out);
+        assert_eq!(out, vec![0.0, -1.0, 1.0]);
+
+        // u16 is offset binary: 32768 is silence
+        samples_to_f32(&[32768u16, 0], &mut out);
+        assert_eq!(out, vec![0.0, -1.0]);
+    }
+}
@@ -8,10 +8,12 @@
 // - Per-input gain application
 // - Clipping detection and prevention
 
+use bytes::{Bytes, BytesMut};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Instant;
 
+use crate::input_tracks::InputTrackWriters;
 use crate::multi_input_manager::{InputBuffer, InputConfig};
 
 // ============================================================================
@@ -172,6 +174,10 @@ pub struct InputMixer {
     drift_states: std::collections::HashMap<String, InputDriftState>,
     /// Reference time for drift calculation
     reference_samples: i64,
+    /// Output frame storage, reclaimed once the previous frame is dropped
+    output: BytesMut,
+    /// Per-input WAV tracks, fed with the aligned frames before mixing
+    input_tracks: Option<InputTrackWriters>,
 }
 
 impl InputMixer {
@@ -181,6 +187,8 @@ impl InputMixer {
             metrics: Arc::new(MixerMetrics::new()),
             drift_states: std::collections::HashMap::new(),
             reference_samples: 0,
+            output: BytesMut::with_capacity(BYTES_PER_FRAME),
+            input_tracks: None,
         }
     }
 
@@ -195,6 +203,16 @@ impl InputMixer {
             .insert(device_id.to_string(), InputDriftState::new());
     }
 
+    /// Save each input's aligned frames to its own track
+    pub fn set_input_tracks(&mut self, tracks: InputTrackWriters) {
+        self.input_tracks = Some(tracks);
+    }
+
+    /// Detach the input tracks (to close them)
+    pub fn take_input_tracks(&mut self) -> Option<InputTrackWriters> {
+        self.input_tracks.take()
+    }
+
     /// Remove drift tracking for an input
     pub fn unregister_input(&mut self, device_id: &str) {
         self.drift_states.remove(device_id);
@@ -206,11 +224,11 @@ impl InputMixer {
     /// * `inputs` - List of (config, buffer) pairs for active inputs
     ///
     /// # Returns
-    /// * `Some(Vec<u8>)` - Mixed 10ms frame as i16 PCM bytes (320 bytes)
+    /// * `Some(Bytes)` - Mixed 10ms frame as i16 PCM bytes (320 bytes)
     /// * `None` - If no inputs are available
     ///
     /// Requirement: STTMIX-REQ-004.1, STTMIX-REQ-004.3
-    pub fn mix_frame(&mut self, inputs: &[(InputConfig, Arc<InputBuffer>)]) -> Option<Vec<u8>> {
+    pub fn mix_frame(&mut self, inputs: &[(InputConfig, Arc<InputBuffer>)]) -> Option<Bytes> {
         if inputs.is_empty() {
             return None;
         }
@@ -223,6 +241,9 @@ impl InputMixer {
 
         for (config, buffer) in inputs {
             let frame = self.extract_frame(config, buffer);
+            if let Some(tracks) = self.input_tracks.as_mut() {
+                tracks.write_frame(&config.device_id, &frame);
+            }
             input_frames.push((config.clone(), frame));
         }
 
@@ -338,8 +359,8 @@ impl InputMixer {
     /// Mix multiple input frames into a single output frame
     ///
     /// Requirement: STTMIX-REQ-004.3, STTMIX-REQ-005
-    fn mix_frames(&self, inputs: &[(InputConfig, Vec<i16>)]) -> Vec<u8> {
-        let mut mixed: Vec<f32> = vec![0.0; SAMPLES_PER_FRAME];
+    fn mix_frames(&mut self, inputs: &[(InputConfig, Vec<i16>)]) -> Bytes {
+        let mut mixed = [0.0f32; SAMPLES_PER_FRAME];
         let mut clipped = false;
 
         for (config, samples) in inputs {
@@ -360,25 +381,24 @@ impl InputMixer {
         }
 
         // Convert to i16 PCM with clipping detection
-        let output: Vec<u8> = mixed
-            .iter()
-            .flat_map(|&sample| {
-                // Detect clipping
-                if sample > 1.0 || sample < -1.0 {
-                    clipped = true;
-                }
-                // Clamp and convert
-                let clamped = sample.clamp(-1.0, 1.0);
-                let scaled = (clamped * 32767.0) as i16;
-                scaled.to_le_bytes()
-            })
-            .collect();
+        // reserve() reuses the previous allocation when its frame was dropped
+        self.output.reserve(BYTES_PER_FRAME);
+        for &sample in &mixed {
+            // Detect clipping
+            if !(-1.0..=1.0).contains(&sample) {
+                clipped = true;
+            }
+            // Clamp and convert
+            let clamped = sample.clamp(-1.0, 1.0);
+            let scaled = (clamped * 32767.0) as i16;
+            self.output.extend_from_slice(&scaled.to_le_bytes());
+        }
 
         if clipped {
             self.metrics.increment_clip();
         }
 
-        output
+        self.output.split().freeze()
     }
 
     /// Reset the mixer state (e.g., when starting new recording)
@@ -566,6 +586,31 @@ mod tests {
         assert_eq!(mixer.metrics().get_frames_mixed(), 1);
     }
 
+    // ========================================================================
+    // Test: output frame storage is reused once the frame is dropped
+    // ========================================================================
+
+    #[test]
+    fn test_mix_frame_reuses_output_buffer() {
+        let mut mixer = InputMixer::new();
+        let samples: Vec<i16> = vec![1000; SAMPLES_PER_FRAME * 3];
+        let config = InputConfig::new("mic-1", InputRole::Microphone);
+        let inputs = vec![(config, create_test_buffer(&samples))];
+
+        let first = mixer.mix_frame(&inputs).unwrap();
+        let first_ptr = first.as_ptr();
+        drop(first);
+
+        // Previous frame dropped: same allocation
+        let second = mixer.mix_frame(&inputs).unwrap();
+        assert_eq!(second.as_ptr(), first_ptr);
+
+        // Previous frame still held: new allocation, held frame untouched
+        let third = mixer.mix_frame(&inputs).unwrap();
+        assert_ne!(third.as_ptr(), second.as_ptr());
+        assert_eq!(second.len(), BYTES_PER_FRAME);
+    }
+
     // ========================================================================
     // Test: mix_frame with empty inputs
     // ========================================================================
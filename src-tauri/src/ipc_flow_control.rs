@@ -0,0 +1,233 @@
+//! Audio Send Flow Control (Backpressure from the Python Sidecar)
+//!
+//! The audio sender used to push a batch every 250ms no matter how far
+//! behind the sidecar was, so a slow STT model ended in stdin write
+//! timeouts. The sidecar now acknowledges each `process_audio_stream`
+//! request once processed (`ipc_protocol::AudioAck`), and the controller
+//! here turns the acks into a pacing decision:
+//!
+//! - **Lag** is the audio sent but not yet acknowledged (ms), i.e. the
+//!   queue depth between the stdin writer and the sidecar.
+//! - Above `SLOW_DOWN_LAG_MS` the batch interval doubles (up to
+//!   `MAX_BATCH_INTERVAL`): fewer, larger requests cost the sidecar less
+//!   per-request overhead. Below `CAUGHT_UP_LAG_MS` it halves back.
+//! - Above `HOLD_LAG_MS` nothing is sent; audio waits in the ring buffer
+//!   (which drops the oldest audio once full, as before).
+//!
+//! Until the first ack arrives (an older sidecar never sends one) batches
+//! go out at the base interval. If acks stop for `ACK_STALL_TIMEOUT` the
+//! controller stops holding, so a hung sidecar still surfaces through the
+//! writer's timeouts.
+
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::ipc_protocol::AudioAck;
+
+/// Batch interval while the sidecar keeps up
+pub const BASE_BATCH_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Longest batch interval when slowing down
+pub const MAX_BATCH_INTERVAL: Duration = Duration::from_millis(1000);
+
+/// Lag above which the batch interval grows
+pub const SLOW_DOWN_LAG_MS: u64 = 1500;
+
+/// Lag below which the batch interval shrinks back
+pub const CAUGHT_UP_LAG_MS: u64 = 500;
+
+/// Lag above which sending stops until the sidecar catches up
+/// (below the ring buffer's 5s capacity)
+pub const HOLD_LAG_MS: u64 = 4000;
+
+/// Without an ack for this long, holding stops
+pub const ACK_STALL_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Unacknowledged requests remembered for matching acks
+const MAX_IN_FLIGHT: usize = 256;
+
+/// Flow control state of one recording session
+#[derive(Debug)]
+pub struct FlowController {
+    /// Unacknowledged requests with their end position in the stream (ms)
+    in_flight: VecDeque<(String, u64)>,
+    /// Stream position up to which audio was sent (ms)
+    sent_until_ms: u64,
+    /// Stream position up to which the sidecar acknowledged audio (ms)
+    acked_until_ms: u64,
+    last_ack_at: Option<Instant>,
+    last_ack: Option<AudioAck>,
+    acks: u64,
+    held_ticks: u64,
+    batch_interval: Duration,
+}
+
+impl Default for FlowController {
+    fn default() -> Self {
+        Self {
+            in_flight: VecDeque::new(),
+            sent_until_ms: 0,
+            acked_until_ms: 0,
+            last_ack_at: None,
+            last_ack: None,
+            acks: 0,
+            held_ticks: 0,
+            batch_interval: BASE_BATCH_INTERVAL,
+        }
+    }
+}
+
+/// Flow control metrics of the active (or last) session
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FlowControlSnapshot {
+    /// The sidecar sends acks (flow control in effect)
+    pub active: bool,
+    pub in_flight_requests: usize,
+    pub lag_ms: u64,
+    pub acked_until_ms: u64,
+    /// Speech audio the sidecar holds for transcription (last ack)
+    pub sidecar_buffered_ms: u64,
+    /// Sidecar processing time of the last acknowledged request
+    pub last_processing_ms: u64,
+    pub batch_interval_ms: u64,
+    pub acks: u64,
+    /// Sender ticks skipped while holding
+    pub held_ticks: u64,
+}
+
+impl FlowController {
+    /// A request carrying `audio_ms` of audio was queued for the sidecar
+    pub fn on_sent(&mut self, request_id: &str, audio_ms: u64) {
+        self.sent_until_ms += audio_ms;
+        self.in_flight
+            .push_back((request_id.to_string(), self.sent_until_ms));
+        if self.in_flight.len() > MAX_IN_FLIGHT {
+            self.in_flight.pop_front();
+        }
+    }
+
+    /// Apply an ack and adapt the batch interval
+    ///
+    /// Returns false for acks of unknown (or already acknowledged) requests.
+    pub fn on_ack(&mut self, ack: &AudioAck, now: Instant) -> bool {
+        let Some(position) = self
+            .in_flight
+            .iter()
+            .position(|(request_id, _)| *request_id == ack.request_id)
+        else {
+            return false;
+        };
+        // In-order processing: earlier requests are done too
+        let (_, until_ms) = self.in_flight.drain(..=position).last().unwrap();
+        self.acked_until_ms = until_ms;
+        self.last_ack_at = Some(now);
+        self.last_ack = Some(ack.clone());
+        self.acks += 1;
+
+        let lag_ms = self.lag_ms();
+        if lag_ms > SLOW_DOWN_LAG_MS {
+            self.batch_interval = (self.batch_interval * 2).min(MAX_BATCH_INTERVAL);
+        } else if lag_ms < CAUGHT_UP_LAG_MS {
+            self.batch_interval = (self.batch_interval / 2).max(BASE_BATCH_INTERVAL);
+        }
+        true
+    }
+
+    /// Audio sent but not yet acknowledged (ms)
+    pub fn lag_ms(&self) -> u64 {
+        self.sent_until_ms - self.acked_until_ms
+    }
+
+    /// Interval the sender should batch audio at
+    pub fn batch_interval(&self) -> Duration {
+        self.batch_interval
+    }
+
+    /// Whether the sender should skip this tick (counted as held)
+    pub fn poll_hold(&mut self, now: Instant) -> bool {
+        let Some(last_ack_at) = self.last_ack_at else {
+            return false;
+        };
+        let hold =
+            self.lag_ms() > HOLD_LAG_MS && now.duration_since(last_ack_at) < ACK_STALL_TIMEOUT;
+        if hold {
+            self.held_ticks += 1;
+        }
+        hold
+    }
+
+    pub fn snapshot(&self) -> FlowControlSnapshot {
+        let last_ack = self.last_ack.as_ref();
+        FlowControlSnapshot {
+            active: self.acks > 0,
+            in_flight_requests: self.in_flight.len(),
+            lag_ms: self.lag_ms(),
+            acked_until_ms: self.acked_until_ms,
+            sidecar_buffered_ms: last_ack.map_or(0, |ack| ack.buffered_ms),
+            last_processing_ms: last_ack.map_or(0, |ack| ack.processing_ms),
+            batch_interval_ms: self.batch_interval.as_millis() as u64,
+            acks: self.acks,
+            held_ticks: self.held_ticks,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ack(request_id: &str) -> AudioAck {
+        AudioAck {
+            request_id: request_id.to_string(),
+            processed_ms: 250,
+            buffered_ms: 0,
+            processing_ms: 0,
+        }
+    }
+
+    #[test]
+    fn test_slows_down_and_recovers_with_acks() {
+        let now = Instant::now();
+        let mut flow = FlowController::default();
+        for i in 0..10 {
+            flow.on_sent(&format!("audio-{}", i), 250);
+        }
+        // No ack yet (older sidecar): base pace, never held
+        assert_eq!(flow.batch_interval(), BASE_BATCH_INTERVAL);
+        assert!(!flow.poll_hold(now));
+
+        // Sidecar 2s behind
+        assert!(flow.on_ack(&ack("audio-1"), now));
+        assert_eq!(flow.lag_ms(), 2000);
+        assert_eq!(flow.batch_interval(), BASE_BATCH_INTERVAL * 2);
+        assert!(flow.on_ack(&ack("audio-2"), now));
+        assert!(flow.on_ack(&ack("audio-3"), now));
+        assert_eq!(flow.batch_interval(), MAX_BATCH_INTERVAL);
+
+        // Acks of earlier requests are implied; duplicates are ignored
+        assert!(!flow.on_ack(&ack("audio-2"), now));
+        assert!(flow.on_ack(&ack("audio-8"), now));
+        assert_eq!(flow.lag_ms(), 250);
+        assert_eq!(flow.batch_interval(), MAX_BATCH_INTERVAL / 2);
+        assert_eq!(flow.snapshot().in_flight_requests, 1);
+    }
+
+    #[test]
+    fn test_holds_until_caught_up_or_stalled() {
+        let now = Instant::now();
+        let mut flow = FlowController::default();
+        for i in 0..20 {
+            flow.on_sent(&format!("audio-{}", i), 250);
+        }
+        assert!(flow.on_ack(&ack("audio-0"), now));
+        assert!(flow.poll_hold(now));
+        assert_eq!(flow.snapshot().held_ticks, 1);
+
+        // Hung sidecar: stop holding so write timeouts surface it
+        assert!(!flow.poll_hold(now + ACK_STALL_TIMEOUT));
+
+        assert!(flow.on_ack(&ack("audio-10"), now));
+        assert!(!flow.poll_hold(now));
+    }
+}
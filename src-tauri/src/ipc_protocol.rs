@@ -4,7 +4,9 @@
 //!
 //! ## 設計決定
 //! - **後方互換性**: `#[serde(default = "default_version")]`で旧形式メッセージ受信
-//! - **前方互換性**: `deny_unknown_fields`未使用で未知フィールド無視
+//! - **前方互換性**: `deny_unknown_fields`未使用で未知フィールド無視。
+//!   未知のメッセージタイプは`decode_incoming`で`UnknownType`として返し、
+//!   呼び出し側でカウントしてスキップする（未知のイベントタイプも同様、`ProtocolDrift`）
 //! - **関連ADR**: ADR-003（リソースベースモデル選択とIPC Version Strategy）
 //!
 //! ## 関連要件
@@ -33,6 +35,7 @@
 //! Task 7.1.5: Integration with existing IPC communication (commands.rs, python_sidecar.rs)
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Protocol version constant (STT-REQ-007.4)
 pub const PROTOCOL_VERSION: &str = "1.0";
@@ -183,6 +186,8 @@ pub enum IpcMessage {
         version: String,
         #[serde(rename = "eventType")]
         event_type: String,
+        /// Missing `data` (e.g. future payload-less events) decodes as null
+        #[serde(default)]
         data: serde_json::Value,
     },
 }
@@ -240,15 +245,525 @@ impl IpcMessage {
     }
 }
 
+/// Message types understood by this protocol version (`type` tag values)
+pub const KNOWN_MESSAGE_TYPES: &[&str] = &["request", "response", "error", "event"];
+
+/// Message received from the sidecar
+#[derive(Debug, Clone, PartialEq)]
+pub enum IncomingMessage {
+    Known(IpcMessage),
+    /// Well-formed message of a type added by a newer sidecar; skip it
+    UnknownType {
+        message_type: String,
+        version: String,
+    },
+}
+
+/// Decode a message received from the sidecar (forward-compatible)
+///
+/// Unknown fields are ignored and an unknown `type` tag yields
+/// `IncomingMessage::UnknownType` instead of an error. Errors are left for
+/// messages that are malformed for their (known) type.
+pub fn decode_incoming(value: serde_json::Value) -> serde_json::Result<IncomingMessage> {
+    if let Some(message_type) = value.get("type").and_then(|t| t.as_str()) {
+        if !KNOWN_MESSAGE_TYPES.contains(&message_type) {
+            return Ok(IncomingMessage::UnknownType {
+                message_type: message_type.to_string(),
+                version: value
+                    .get("version")
+                    .and_then(|v| v.as_str())
+                    .map(|v| v.to_string())
+                    .unwrap_or_else(default_version),
+            });
+        }
+    }
+    serde_json::from_value(value).map(IncomingMessage::Known)
+}
+
+/// Counts of unknown message/event types received in a session
+///
+/// A newer sidecar may send types this build doesn't know; they are skipped,
+/// and counted here so version drift shows up in metrics.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ProtocolDrift {
+    pub unknown_message_types: BTreeMap<String, u64>,
+    pub unknown_event_types: BTreeMap<String, u64>,
+}
+
+impl ProtocolDrift {
+    /// Count an unknown message type; returns its count so far
+    pub fn record_unknown_message_type(&mut self, message_type: &str) -> u64 {
+        Self::bump(&mut self.unknown_message_types, message_type)
+    }
+
+    /// Count an unknown event type; returns its count so far
+    pub fn record_unknown_event_type(&mut self, event_type: &str) -> u64 {
+        Self::bump(&mut self.unknown_event_types, event_type)
+    }
+
+    fn bump(counts: &mut BTreeMap<String, u64>, key: &str) -> u64 {
+        let count = counts.entry(key.to_string()).or_insert(0);
+        *count += 1;
+        *count
+    }
+}
+
+/// `process_audio_stream` request borrowing its audio (wire-compatible with `IpcMessage::Request`)
+///
+/// Serializing `IpcMessage::Request` requires copying the batch into a
+/// `serde_json::Value` array (one heap node per byte). This type writes the
+/// same JSON directly from the audio slice.
+#[derive(Serialize)]
+#[serde(tag = "type", rename = "request")]
+struct AudioStreamRequest<'a> {
+    id: &'a str,
+    version: &'a str,
+    method: &'a str,
+    params: AudioStreamParams<'a>,
+}
+
+#[derive(Serialize)]
+struct AudioStreamParams<'a> {
+    audio_data: &'a [u8],
+}
+
+/// Encode a `process_audio_stream` request as a single JSON line (without newline)
+pub fn encode_audio_stream_request(id: &str, audio_data: &[u8]) -> serde_json::Result<String> {
+    // Each byte is at most "255," - presize to avoid regrowth
+    let mut json = Vec::with_capacity(128 + id.len() + audio_data.len() * 4);
+    serde_json::to_writer(
+        &mut json,
+        &AudioStreamRequest {
+            id,
+            version: PROTOCOL_VERSION,
+            method: "process_audio_stream",
+            params: AudioStreamParams { audio_data },
+        },
+    )?;
+    String::from_utf8(json).map_err(serde::ser::Error::custom)
+}
+
+/// Request method offering the sidecar a binary audio channel
+pub const OPEN_AUDIO_CHANNEL_METHOD: &str = "open_audio_channel";
+
+/// Kind of binary audio channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AudioChannelKind {
+    /// Unix domain socket (macOS/Linux)
+    UnixSocket,
+    /// Named pipe (Windows)
+    NamedPipe,
+}
+
+/// Binary audio channel offered to the sidecar (`open_audio_channel` params)
+///
+/// The sidecar connects to `path` as a client, then answers with a response
+/// whose result is `{"accepted": true}`. Any other answer (an older sidecar
+/// replies `UNKNOWN_METHOD`) keeps audio on stdin.
+///
+/// Once accepted, each `process_audio_stream` request on stdin carries
+/// `params.audio_bytes` instead of `params.audio_data`, and exactly that many
+/// bytes of raw PCM (16 kHz mono i16 LE) follow on the channel. The stdin
+/// line is written first and sequences the request among control messages;
+/// the sidecar reads the PCM after reading the line.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AudioChannelOffer {
+    pub kind: AudioChannelKind,
+    pub path: String,
+}
+
+/// `open_audio_channel` request carrying `offer`
+pub fn audio_channel_offer_request(id: &str, offer: &AudioChannelOffer) -> IpcMessage {
+    IpcMessage::Request {
+        id: id.to_string(),
+        version: PROTOCOL_VERSION.to_string(),
+        method: OPEN_AUDIO_CHANNEL_METHOD.to_string(),
+        params: serde_json::json!(offer),
+    }
+}
+
+/// Whether a sidecar message answers offer `id` with an accepted channel
+///
+/// None for messages unrelated to the offer.
+pub fn audio_channel_accepted(id: &str, message: &IpcMessage) -> Option<bool> {
+    match message {
+        IpcMessage::Response {
+            id: reply_id,
+            result,
+            ..
+        } if reply_id == id => Some(result.get("accepted").and_then(|v| v.as_bool()) == Some(true)),
+        IpcMessage::Error { id: reply_id, .. } if reply_id == id => Some(false),
+        _ => None,
+    }
+}
+
+/// `process_audio_stream` request whose PCM follows on the audio channel
+#[derive(Serialize)]
+#[serde(tag = "type", rename = "request")]
+struct AudioChannelRequest<'a> {
+    id: &'a str,
+    version: &'a str,
+    method: &'a str,
+    params: AudioChannelParams,
+}
+
+#[derive(Serialize)]
+struct AudioChannelParams {
+    audio_bytes: usize,
+}
+
+/// Encode a `process_audio_stream` request whose `audio_bytes` of PCM
+/// follow on the audio channel (see `AudioChannelOffer`)
+pub fn encode_audio_channel_request(id: &str, audio_bytes: usize) -> serde_json::Result<String> {
+    serde_json::to_string(&AudioChannelRequest {
+        id,
+        version: PROTOCOL_VERSION,
+        method: "process_audio_stream",
+        params: AudioChannelParams { audio_bytes },
+    })
+}
+
+/// Event the sidecar sends when it has finished a `process_audio_stream`
+/// request (flow control, see `ipc_flow_control`)
+pub const AUDIO_ACK_EVENT: &str = "audio_ack";
+
+/// Acknowledgement of processed audio (`audio_ack` event data)
+///
+/// Requests are processed in order, so acknowledging a request also
+/// acknowledges every earlier one. An older sidecar sends no acks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AudioAck {
+    #[serde(rename = "requestId")]
+    pub request_id: String,
+    /// Audio in the acknowledged request (ms)
+    #[serde(default)]
+    pub processed_ms: u64,
+    /// Queue depth on the sidecar side: speech audio held for transcription (ms)
+    #[serde(default)]
+    pub buffered_ms: u64,
+    /// Time the sidecar spent on the request (ms)
+    #[serde(default)]
+    pub processing_ms: u64,
+}
+
+impl AudioAck {
+    /// Decode `audio_ack` event data (None when malformed)
+    pub fn from_event_data(data: &serde_json::Value) -> Option<Self> {
+        serde_json::from_value(data.clone()).ok()
+    }
+}
+
+/// Answer to a `ping` (not an `IpcMessage`: handled before decoding)
+pub const PONG_MESSAGE_TYPE: &str = "pong";
+
+/// Health metrics a newer sidecar includes in its `pong`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SidecarHealth {
+    /// Whisper model loaded and ready to transcribe
+    #[serde(default)]
+    pub model_loaded: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Processing time per audio time of recent requests (> 1: falling behind)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub real_time_factor: Option<f64>,
+    /// Speech audio waiting for transcription (ms)
+    #[serde(default)]
+    pub queue_depth_ms: u64,
+    /// Resident memory of the sidecar process (MB)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_mb: Option<u64>,
+}
+
+/// `pong` message; `health` is None for an older sidecar
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Pong {
+    pub id: String,
+    #[serde(default)]
+    pub health: Option<SidecarHealth>,
+}
+
+impl Pong {
+    /// Decode a received line if it is a `pong`
+    pub fn from_message(value: &serde_json::Value) -> Option<Self> {
+        if value.get("type").and_then(|t| t.as_str()) != Some(PONG_MESSAGE_TYPE) {
+            return None;
+        }
+        serde_json::from_value(value.clone()).ok()
+    }
+}
+
+/// Protocol versions this build speaks, oldest first
+pub const SUPPORTED_PROTOCOL_VERSIONS: &[&str] = &[PROTOCOL_VERSION];
+
+/// Request method negotiating the protocol version right after `ready`
+pub const HELLO_METHOD: &str = "hello";
+
+/// Error code of a sidecar that shares no version with the offer
+pub const UNSUPPORTED_VERSION_ERROR: &str = "UNSUPPORTED_VERSION";
+
+/// `hello` request offering `SUPPORTED_PROTOCOL_VERSIONS`
+///
+/// The sidecar answers with a response whose result is
+/// `{"version": <chosen>, "versions": [<its own>]}`, choosing the highest
+/// version both sides support, or with an `UNSUPPORTED_VERSION` error. An
+/// older sidecar replies `UNKNOWN_METHOD` and is taken to speak
+/// `PROTOCOL_VERSION`.
+pub fn hello_request(id: &str) -> IpcMessage {
+    IpcMessage::Request {
+        id: id.to_string(),
+        version: PROTOCOL_VERSION.to_string(),
+        method: HELLO_METHOD.to_string(),
+        params: serde_json::json!({ "versions": SUPPORTED_PROTOCOL_VERSIONS }),
+    }
+}
+
+/// `major.minor` of a version string
+fn version_key(version: &str) -> Option<(u32, u32)> {
+    let mut parts = version.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    Some((major, minor))
+}
+
+/// Highest version in both lists (compared as `major.minor`)
+pub fn highest_common_version(ours: &[&str], theirs: &[String]) -> Option<String> {
+    ours.iter()
+        .filter(|version| theirs.iter().any(|their| their == *version))
+        .filter_map(|version| Some((version_key(version)?, *version)))
+        .max()
+        .map(|(_, version)| version.to_string())
+}
+
+/// Protocol version agreed with the sidecar at startup
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NegotiatedProtocol {
+    pub version: String,
+    /// The sidecar answered `hello` (false: older sidecar, version assumed)
+    pub negotiated: bool,
+    /// Versions the sidecar reported supporting
+    pub sidecar_versions: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct HelloResult {
+    version: String,
+    #[serde(default)]
+    versions: Vec<String>,
+}
+
+/// Outcome of `hello` request `id` if `message` answers it
+///
+/// Err when the sidecar shares no version with this build.
+pub fn hello_reply(id: &str, message: &IpcMessage) -> Option<Result<NegotiatedProtocol, String>> {
+    match message {
+        IpcMessage::Response {
+            id: reply_id,
+            result,
+            ..
+        } if reply_id == id => {
+            let reply = match serde_json::from_value::<HelloResult>(result.clone()) {
+                Ok(reply) => reply,
+                Err(e) => return Some(Err(format!("Malformed hello response: {}", e))),
+            };
+            if !SUPPORTED_PROTOCOL_VERSIONS.contains(&reply.version.as_str()) {
+                return Some(Err(format!(
+                    "Sidecar chose protocol version {}, supported: {}",
+                    reply.version,
+                    SUPPORTED_PROTOCOL_VERSIONS.join(", ")
+                )));
+            }
+            Some(Ok(NegotiatedProtocol {
+                version: reply.version,
+                negotiated: true,
+                sidecar_versions: reply.versions,
+            }))
+        }
+        IpcMessage::Error {
+            id: reply_id,
+            error_code,
+            error_message,
+            ..
+        } if reply_id == id => {
+            if error_code == UNSUPPORTED_VERSION_ERROR {
+                return Some(Err(error_message.clone()));
+            }
+            // Older sidecar without the handshake
+            Some(Ok(NegotiatedProtocol {
+                version: PROTOCOL_VERSION.to_string(),
+                negotiated: false,
+                sidecar_versions: Vec::new(),
+            }))
+        }
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_encode_audio_stream_request_matches_ipc_message() {
+        let audio = [0u8, 1, 127, 255];
+        let encoded = encode_audio_stream_request("audio-1", &audio).unwrap();
+
+        let expected = IpcMessage::Request {
+            id: "audio-1".to_string(),
+            version: PROTOCOL_VERSION.to_string(),
+            method: "process_audio_stream".to_string(),
+            params: serde_json::json!({ "audio_data": audio.to_vec() }),
+        };
+        let decoded: IpcMessage = serde_json::from_str(&encoded).unwrap();
+        assert_eq!(decoded, expected);
+    }
+
+    #[test]
+    fn test_hello_negotiates_highest_common_version() {
+        let theirs = vec!["0.9".to_string(), "1.0".to_string(), "1.2".to_string()];
+        assert_eq!(
+            highest_common_version(&["1.0", "1.2", "2.0"], &theirs).as_deref(),
+            Some("1.2")
+        );
+        assert_eq!(highest_common_version(&["2.0"], &theirs), None);
+
+        let response = |version: &str| IpcMessage::Response {
+            id: "hello-1".to_string(),
+            version: PROTOCOL_VERSION.to_string(),
+            result: serde_json::json!({ "version": version, "versions": ["1.0"] }),
+        };
+        let agreed = hello_reply("hello-1", &response(PROTOCOL_VERSION))
+            .unwrap()
+            .unwrap();
+        assert!(agreed.negotiated);
+        assert_eq!(agreed.sidecar_versions, ["1.0"]);
+        assert!(hello_reply("hello-1", &response("9.0")).unwrap().is_err());
+        assert_eq!(hello_reply("other", &response(PROTOCOL_VERSION)), None);
+
+        let error = |code: &str| IpcMessage::Error {
+            id: "hello-1".to_string(),
+            version: PROTOCOL_VERSION.to_string(),
+            error_code: code.to_string(),
+            error_message: "no".to_string(),
+            recoverable: true,
+        };
+        let legacy = hello_reply("hello-1", &error("UNKNOWN_METHOD"))
+            .unwrap()
+            .unwrap();
+        assert!(!legacy.negotiated);
+        assert_eq!(legacy.version, PROTOCOL_VERSION);
+        assert!(hello_reply("hello-1", &error(UNSUPPORTED_VERSION_ERROR))
+            .unwrap()
+            .is_err());
+    }
+
     // ================================================================================
     // Task 7.1: RED - 失敗するテスト（コンパイルエラー含む）
     // Related requirement: STT-REQ-007.2, STT-REQ-007.4
     // ================================================================================
 
+    #[test]
+    fn test_audio_channel_negotiation() {
+        let offer = AudioChannelOffer {
+            kind: AudioChannelKind::UnixSocket,
+            path: "/tmp/mma-audio/audio.sock".to_string(),
+        };
+        let IpcMessage::Request { method, params, .. } =
+            audio_channel_offer_request("ctl-1", &offer)
+        else {
+            panic!("not a request");
+        };
+        assert_eq!(method, OPEN_AUDIO_CHANNEL_METHOD);
+        assert_eq!(params["kind"], "unix_socket");
+        assert_eq!(
+            serde_json::from_value::<AudioChannelOffer>(params).unwrap(),
+            offer
+        );
+
+        let response = |id: &str, result| IpcMessage::Response {
+            id: id.to_string(),
+            version: PROTOCOL_VERSION.to_string(),
+            result,
+        };
+        let accepted = response("ctl-1", serde_json::json!({ "accepted": true }));
+        assert_eq!(audio_channel_accepted("ctl-1", &accepted), Some(true));
+        let declined = response("ctl-1", serde_json::json!({ "accepted": false }));
+        assert_eq!(audio_channel_accepted("ctl-1", &declined), Some(false));
+        let unknown_method = IpcMessage::Error {
+            id: "ctl-1".to_string(),
+            version: PROTOCOL_VERSION.to_string(),
+            error_code: "UNKNOWN_METHOD".to_string(),
+            error_message: "Unknown request method: open_audio_channel".to_string(),
+            recoverable: true,
+        };
+        assert_eq!(
+            audio_channel_accepted("ctl-1", &unknown_method),
+            Some(false)
+        );
+        assert_eq!(audio_channel_accepted("ctl-2", &accepted), None);
+
+        let request: serde_json::Value =
+            serde_json::from_str(&encode_audio_channel_request("req-1", 8000).unwrap()).unwrap();
+        assert_eq!(request["method"], "process_audio_stream");
+        assert_eq!(
+            request["params"],
+            serde_json::json!({ "audio_bytes": 8000 })
+        );
+    }
+
+    #[test]
+    fn test_audio_ack_from_event_data() {
+        let data = serde_json::json!({
+            "requestId": "audio-3",
+            "processed_ms": 250,
+            "buffered_ms": 1200,
+            "processing_ms": 40,
+            "extra": true
+        });
+        assert_eq!(
+            AudioAck::from_event_data(&data),
+            Some(AudioAck {
+                request_id: "audio-3".to_string(),
+                processed_ms: 250,
+                buffered_ms: 1200,
+                processing_ms: 40,
+            })
+        );
+
+        let minimal = AudioAck::from_event_data(&serde_json::json!({ "requestId": "a" })).unwrap();
+        assert_eq!(minimal.processed_ms, 0);
+        assert_eq!(AudioAck::from_event_data(&serde_json::json!({})), None);
+    }
+
+    #[test]
+    fn test_pong_with_and_without_health() {
+        let pong = Pong::from_message(&serde_json::json!({
+            "type": "pong",
+            "id": "ctrl-1",
+            "health": {
+                "model_loaded": true,
+                "model": "small",
+                "real_time_factor": 0.4,
+                "queue_depth_ms": 800,
+                "memory_mb": 1200
+            }
+        }))
+        .unwrap();
+        let health = pong.health.unwrap();
+        assert!(health.model_loaded);
+        assert_eq!(health.real_time_factor, Some(0.4));
+        assert_eq!(health.queue_depth_ms, 800);
+
+        // Older sidecar: bare pong
+        let bare = Pong::from_message(&serde_json::json!({ "type": "pong", "id": "ctrl-2" }));
+        assert_eq!(bare.unwrap().health, None);
+        assert_eq!(
+            Pong::from_message(&serde_json::json!({ "type": "event", "id": "x" })),
+            None
+        );
+    }
+
     #[test]
     fn test_transcription_result_with_all_fields() {
         // Arrange
@@ -581,4 +1096,66 @@ mod tests {
         // Assert: Version defaults to "1.0"
         assert_eq!(msg.version(), "1.0");
     }
+
+    // ================================================================================
+    // Version drift: payloads from a newer sidecar
+    // ================================================================================
+
+    #[test]
+    fn test_decode_incoming_future_payloads() {
+        // Unknown fields on a known type are ignored (newer minor version)
+        let event = serde_json::json!({
+            "type": "event",
+            "version": "1.3",
+            "eventType": "final_text",
+            "data": { "text": "hi", "speaker": "A" },
+            "trace": { "span": 7 }
+        });
+        match decode_incoming(event).unwrap() {
+            IncomingMessage::Known(msg @ IpcMessage::Event { .. }) => {
+                assert!(matches!(
+                    msg.check_version_compatibility(),
+                    VersionCompatibility::MinorMismatch { .. }
+                ));
+            }
+            other => panic!("Expected known event, got {:?}", other),
+        }
+
+        // Unknown event types still decode; the caller skips them
+        let event = serde_json::json!({ "type": "event", "eventType": "diarization_update" });
+        match decode_incoming(event).unwrap() {
+            IncomingMessage::Known(IpcMessage::Event {
+                event_type, data, ..
+            }) => {
+                assert_eq!(event_type, "diarization_update");
+                assert!(data.is_null());
+            }
+            other => panic!("Expected known event, got {:?}", other),
+        }
+
+        // Unknown message types are reported, not rejected
+        let heartbeat = serde_json::json!({ "type": "heartbeat", "version": "1.2", "seq": 1 });
+        assert_eq!(
+            decode_incoming(heartbeat).unwrap(),
+            IncomingMessage::UnknownType {
+                message_type: "heartbeat".to_string(),
+                version: "1.2".to_string()
+            }
+        );
+
+        // A known type missing required fields is still an error
+        let broken = serde_json::json!({ "type": "response", "id": "x" });
+        assert!(decode_incoming(broken).is_err());
+        assert!(decode_incoming(serde_json::json!({ "text": "no tag" })).is_err());
+    }
+
+    #[test]
+    fn test_protocol_drift_counts() {
+        let mut drift = ProtocolDrift::default();
+        assert_eq!(drift.record_unknown_message_type("heartbeat"), 1);
+        assert_eq!(drift.record_unknown_message_type("heartbeat"), 2);
+        assert_eq!(drift.record_unknown_event_type("diarization_update"), 1);
+        assert_eq!(drift.unknown_message_types["heartbeat"], 2);
+        assert_eq!(drift.unknown_event_types.len(), 1);
+    }
 }
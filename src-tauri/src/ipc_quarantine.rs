@@ -0,0 +1,221 @@
+//! Quarantine for malformed sidecar IPC lines
+//!
+//! A line from the sidecar that is not valid JSON (or not a valid protocol
+//! message) is quarantined instead of ending the IPC reader: it is counted
+//! and its raw text kept (truncated) for metrics and diagnostic bundles.
+//! The reader only gives up after `max_consecutive_errors` bad lines in a
+//! row; any valid message resets the run.
+//!
+//! Persisted to `settings/ipc_quarantine.json` in app data directory.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Quarantined lines kept per session
+pub const MAX_QUARANTINED_LINES: usize = 50;
+
+/// Raw line length kept per entry (chars)
+pub const MAX_RAW_LINE_CHARS: usize = 2_000;
+
+/// Quarantine configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IpcQuarantineSettings {
+    /// Consecutive malformed lines before the reader terminates (min 1)
+    #[serde(default = "default_max_consecutive_errors")]
+    pub max_consecutive_errors: u32,
+    /// Settings version for future migrations
+    #[serde(default = "default_version")]
+    pub version: u32,
+}
+
+fn default_max_consecutive_errors() -> u32 {
+    10
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+impl Default for IpcQuarantineSettings {
+    fn default() -> Self {
+        Self {
+            max_consecutive_errors: default_max_consecutive_errors(),
+            version: 1,
+        }
+    }
+}
+
+/// One quarantined line
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct QuarantinedLine {
+    pub at_ms: u64,
+    pub error: String,
+    /// Raw line (truncated to `MAX_RAW_LINE_CHARS`)
+    pub raw: String,
+    /// Length of the original line (bytes)
+    pub raw_bytes: usize,
+}
+
+/// Outcome of quarantining a line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuarantineVerdict {
+    /// Keep reading
+    Continue { consecutive: u32 },
+    /// Consecutive-error threshold reached; the reader should terminate
+    ThresholdExceeded { consecutive: u32, threshold: u32 },
+}
+
+/// Quarantine state of the active (or last) session
+#[derive(Debug, Clone, Serialize)]
+pub struct QuarantineSnapshot {
+    pub total: u64,
+    pub consecutive: u32,
+    pub threshold: u32,
+    /// Most recent quarantined lines, oldest first
+    pub recent: Vec<QuarantinedLine>,
+}
+
+/// Counts and keeps malformed IPC lines
+#[derive(Debug, Default)]
+pub struct IpcQuarantine {
+    settings: IpcQuarantineSettings,
+    consecutive: u32,
+    total: u64,
+    recent: VecDeque<QuarantinedLine>,
+}
+
+impl IpcQuarantine {
+    pub fn new(settings: IpcQuarantineSettings) -> Self {
+        Self {
+            settings,
+            ..Default::default()
+        }
+    }
+
+    pub fn settings(&self) -> &IpcQuarantineSettings {
+        &self.settings
+    }
+
+    pub fn set_settings(&mut self, settings: IpcQuarantineSettings) {
+        self.settings = settings;
+    }
+
+    /// Forget all quarantined lines (new session); settings are kept
+    pub fn reset(&mut self) {
+        self.consecutive = 0;
+        self.total = 0;
+        self.recent.clear();
+    }
+
+    /// Quarantine a malformed line
+    pub fn quarantine(&mut self, raw: &str, error: &str, at_ms: u64) -> QuarantineVerdict {
+        let raw = raw.trim_end_matches(['\r', '\n']);
+        self.consecutive += 1;
+        self.total += 1;
+        self.recent.push_back(QuarantinedLine {
+            at_ms,
+            error: error.to_string(),
+            raw: raw.chars().take(MAX_RAW_LINE_CHARS).collect(),
+            raw_bytes: raw.len(),
+        });
+        while self.recent.len() > MAX_QUARANTINED_LINES {
+            self.recent.pop_front();
+        }
+
+        let threshold = self.settings.max_consecutive_errors.max(1);
+        if self.consecutive >= threshold {
+            QuarantineVerdict::ThresholdExceeded {
+                consecutive: self.consecutive,
+                threshold,
+            }
+        } else {
+            QuarantineVerdict::Continue {
+                consecutive: self.consecutive,
+            }
+        }
+    }
+
+    /// A valid message was read: end the current run of errors
+    pub fn record_valid(&mut self) {
+        self.consecutive = 0;
+    }
+
+    pub fn snapshot(&self) -> QuarantineSnapshot {
+        QuarantineSnapshot {
+            total: self.total,
+            consecutive: self.consecutive,
+            threshold: self.settings.max_consecutive_errors.max(1),
+            recent: self.recent.iter().cloned().collect(),
+        }
+    }
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+pub const SETTINGS_STEM: &str = "ipc_quarantine";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_threshold_counts_consecutive_errors_only() {
+        let mut quarantine = IpcQuarantine::new(IpcQuarantineSettings {
+            max_consecutive_errors: 3,
+            ..Default::default()
+        });
+
+        assert_eq!(
+            quarantine.quarantine("{oops\n", "EOF", 1),
+            QuarantineVerdict::Continue { consecutive: 1 }
+        );
+        quarantine.quarantine("garbage", "expected value", 2);
+        quarantine.record_valid();
+
+        assert_eq!(
+            quarantine.quarantine("x", "e", 3),
+            QuarantineVerdict::Continue { consecutive: 1 }
+        );
+        quarantine.quarantine("x", "e", 4);
+        assert_eq!(
+            quarantine.quarantine("x", "e", 5),
+            QuarantineVerdict::ThresholdExceeded {
+                consecutive: 3,
+                threshold: 3
+            }
+        );
+
+        let snapshot = quarantine.snapshot();
+        assert_eq!(snapshot.total, 5);
+        assert_eq!(snapshot.consecutive, 3);
+        assert_eq!(snapshot.recent[0].raw, "{oops");
+
+        quarantine.reset();
+        assert_eq!(quarantine.snapshot().total, 0);
+        assert_eq!(quarantine.settings().max_consecutive_errors, 3);
+    }
+
+    #[test]
+    fn test_recent_lines_are_bounded_and_truncated() {
+        let mut quarantine = IpcQuarantine::new(IpcQuarantineSettings {
+            max_consecutive_errors: 0,
+            ..Default::default()
+        });
+        // A threshold of 0 behaves like 1
+        assert!(matches!(
+            quarantine.quarantine("x", "e", 0),
+            QuarantineVerdict::ThresholdExceeded { threshold: 1, .. }
+        ));
+
+        let long = "あ".repeat(MAX_RAW_LINE_CHARS + 10);
+        for i in 0..MAX_QUARANTINED_LINES + 5 {
+            quarantine.quarantine(&long, "e", i as u64);
+        }
+        let snapshot = quarantine.snapshot();
+        assert_eq!(snapshot.recent.len(), MAX_QUARANTINED_LINES);
+        assert_eq!(snapshot.recent[0].raw.chars().count(), MAX_RAW_LINE_CHARS);
+        assert_eq!(snapshot.recent[0].raw_bytes, long.len());
+    }
+}
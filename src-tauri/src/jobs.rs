@@ -0,0 +1,524 @@
+//! Background Job Scheduler
+//!
+//! Central place for background work (maintenance, exports, summaries,
+//! re-transcription, backups) so it doesn't compete with live meetings:
+//!
+//! - **Per-category concurrency limits**: a job waits in `queued` until its
+//!   category has a free slot
+//! - **Recording pause**: heavy categories don't start, and running heavy
+//!   jobs block at their next [`JobContext::checkpoint`], while a recording
+//!   is active
+//! - **Cancellation**: queued jobs are dropped, running jobs see
+//!   [`JobContext::is_cancelled`] / a failed checkpoint and stop early
+//!
+//! Limits are persisted to `settings/jobs.json` in app data directory.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use thiserror::Error;
+
+/// Finished jobs kept for `get_jobs`
+const FINISHED_JOB_HISTORY: usize = 50;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum JobError {
+    #[error("Unknown job: {0}")]
+    UnknownJob(String),
+
+    #[error("Job already finished: {0}")]
+    AlreadyFinished(String),
+
+    #[error("Job cancelled")]
+    Cancelled,
+}
+
+// ============================================================================
+// Settings Struct
+// ============================================================================
+
+/// Job category (unit of concurrency limits)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobCategory {
+    /// Derived session files (waveform peaks, activity timeline)
+    Maintenance,
+    Export,
+    Summary,
+    Transcription,
+    Backup,
+}
+
+impl JobCategory {
+    /// Heavy categories are paused while recording (CPU/disk bound)
+    pub fn is_heavy(&self) -> bool {
+        matches!(
+            self,
+            JobCategory::Maintenance | JobCategory::Transcription | JobCategory::Backup
+        )
+    }
+}
+
+/// Scheduler configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JobSettings {
+    /// Max concurrently running jobs per category (missing = 1, 0 = paused)
+    #[serde(default = "default_limits")]
+    pub limits: BTreeMap<JobCategory, usize>,
+    #[serde(default = "default_enabled")]
+    pub pause_heavy_while_recording: bool,
+    /// Settings version for future migrations
+    #[serde(default = "default_version")]
+    pub version: u32,
+}
+
+fn default_limits() -> BTreeMap<JobCategory, usize> {
+    BTreeMap::from([
+        (JobCategory::Maintenance, 1),
+        (JobCategory::Export, 2),
+        (JobCategory::Summary, 1),
+        (JobCategory::Transcription, 1),
+        (JobCategory::Backup, 1),
+    ])
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+impl Default for JobSettings {
+    fn default() -> Self {
+        Self {
+            limits: default_limits(),
+            pause_heavy_while_recording: true,
+            version: 1,
+        }
+    }
+}
+
+impl JobSettings {
+    pub fn limit(&self, category: JobCategory) -> usize {
+        self.limits.get(&category).copied().unwrap_or(1)
+    }
+}
+
+// ============================================================================
+// Jobs
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobState {
+    Queued,
+    Running,
+    /// Running, but held at a checkpoint while recording
+    Paused,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl JobState {
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self,
+            JobState::Completed | JobState::Failed | JobState::Cancelled
+        )
+    }
+
+    /// Occupies a concurrency slot
+    fn is_active(&self) -> bool {
+        matches!(self, JobState::Running | JobState::Paused)
+    }
+}
+
+/// Job as reported to the UI
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobInfo {
+    pub id: String,
+    pub category: JobCategory,
+    pub label: String,
+    pub state: JobState,
+    /// 0.0-1.0 when the job reports progress
+    pub progress: Option<f32>,
+    pub error: Option<String>,
+    pub created_at_ms: u64,
+    pub started_at_ms: Option<u64>,
+    pub finished_at_ms: Option<u64>,
+}
+
+struct JobEntry {
+    info: JobInfo,
+    cancel: Arc<AtomicBool>,
+}
+
+#[derive(Default)]
+struct Inner {
+    jobs: Vec<JobEntry>,
+    settings: JobSettings,
+    recording: bool,
+    next_id: u64,
+}
+
+impl Inner {
+    fn entry_mut(&mut self, id: &str) -> Option<&mut JobEntry> {
+        self.jobs.iter_mut().find(|e| e.info.id == id)
+    }
+
+    fn should_pause(&self, category: JobCategory) -> bool {
+        self.recording && self.settings.pause_heavy_while_recording && category.is_heavy()
+    }
+
+    fn can_start(&self, category: JobCategory) -> bool {
+        let active = self
+            .jobs
+            .iter()
+            .filter(|e| e.info.category == category && e.info.state.is_active())
+            .count();
+        active < self.settings.limit(category) && !self.should_pause(category)
+    }
+
+    fn prune(&mut self) {
+        let finished = self
+            .jobs
+            .iter()
+            .filter(|e| e.info.state.is_finished())
+            .count();
+        let mut excess = finished.saturating_sub(FINISHED_JOB_HISTORY);
+        self.jobs.retain(|e| {
+            if excess > 0 && e.info.state.is_finished() {
+                excess -= 1;
+                false
+            } else {
+                true
+            }
+        });
+    }
+}
+
+fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+/// Handle passed to a running job
+pub struct JobContext {
+    scheduler: Arc<JobScheduler>,
+    id: String,
+    category: JobCategory,
+    cancel: Arc<AtomicBool>,
+}
+
+impl JobContext {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.load(Ordering::SeqCst)
+    }
+
+    /// Whether the job should hold off (heavy job during a recording)
+    pub fn should_pause(&self) -> bool {
+        self.scheduler
+            .inner
+            .lock()
+            .unwrap()
+            .should_pause(self.category)
+    }
+
+    /// Block while paused; fails once the job is cancelled
+    ///
+    /// Call between units of work.
+    pub fn checkpoint(&self) -> Result<(), JobError> {
+        let mut inner = self.scheduler.inner.lock().unwrap();
+        if inner.should_pause(self.category) && !self.is_cancelled() {
+            set_state(&mut inner, &self.id, JobState::Paused);
+            inner = self
+                .scheduler
+                .wake
+                .wait_while(inner, |inner| {
+                    inner.should_pause(self.category) && !self.is_cancelled()
+                })
+                .unwrap();
+            set_state(&mut inner, &self.id, JobState::Running);
+        }
+        if self.is_cancelled() {
+            return Err(JobError::Cancelled);
+        }
+        Ok(())
+    }
+
+    /// Report progress (clamped to 0.0-1.0)
+    pub fn set_progress(&self, progress: f32) {
+        let mut inner = self.scheduler.inner.lock().unwrap();
+        if let Some(entry) = inner.entry_mut(&self.id) {
+            entry.info.progress = Some(progress.clamp(0.0, 1.0));
+        }
+    }
+}
+
+fn set_state(inner: &mut Inner, id: &str, state: JobState) {
+    if let Some(entry) = inner.entry_mut(id) {
+        entry.info.state = state;
+    }
+}
+
+/// Scheduler shared through `AppState`
+#[derive(Default)]
+pub struct JobScheduler {
+    inner: Mutex<Inner>,
+    wake: Condvar,
+}
+
+impl JobScheduler {
+    /// Queue a job on its own thread; returns the job ID
+    ///
+    /// The job starts once its category has a free slot (and, for heavy
+    /// categories, no recording is active).
+    pub fn spawn<F>(self: &Arc<Self>, category: JobCategory, label: &str, job: F) -> String
+    where
+        F: FnOnce(&JobContext) -> Result<()> + Send + 'static,
+    {
+        let cancel = Arc::new(AtomicBool::new(false));
+        let id = {
+            let mut inner = self.inner.lock().unwrap();
+            inner.next_id += 1;
+            let id = format!("job-{}", inner.next_id);
+            inner.jobs.push(JobEntry {
+                info: JobInfo {
+                    id: id.clone(),
+                    category,
+                    label: label.to_string(),
+                    state: JobState::Queued,
+                    progress: None,
+                    error: None,
+                    created_at_ms: now_ms(),
+                    started_at_ms: None,
+                    finished_at_ms: None,
+                },
+                cancel: Arc::clone(&cancel),
+            });
+            id
+        };
+
+        let ctx = JobContext {
+            scheduler: Arc::clone(self),
+            id: id.clone(),
+            category,
+            cancel,
+        };
+        let scheduler = Arc::clone(self);
+        std::thread::spawn(move || scheduler.execute(ctx, job));
+        id
+    }
+
+    fn execute<F>(&self, ctx: JobContext, job: F)
+    where
+        F: FnOnce(&JobContext) -> Result<()>,
+    {
+        {
+            let inner = self.inner.lock().unwrap();
+            let mut inner = self
+                .wake
+                .wait_while(inner, |inner| {
+                    !ctx.is_cancelled() && !inner.can_start(ctx.category)
+                })
+                .unwrap();
+            if ctx.is_cancelled() {
+                self.finish(&mut inner, &ctx.id, JobState::Cancelled, None);
+                return;
+            }
+            if let Some(entry) = inner.entry_mut(&ctx.id) {
+                entry.info.state = JobState::Running;
+                entry.info.started_at_ms = Some(now_ms());
+            }
+        }
+
+        let result = job(&ctx);
+
+        let mut inner = self.inner.lock().unwrap();
+        match result {
+            Ok(()) => self.finish(&mut inner, &ctx.id, JobState::Completed, None),
+            Err(_) if ctx.is_cancelled() => {
+                self.finish(&mut inner, &ctx.id, JobState::Cancelled, None)
+            }
+            Err(e) => self.finish(
+                &mut inner,
+                &ctx.id,
+                JobState::Failed,
+                Some(format!("{:#}", e)),
+            ),
+        }
+    }
+
+    fn finish(&self, inner: &mut Inner, id: &str, state: JobState, error: Option<String>) {
+        if let Some(entry) = inner.entry_mut(id) {
+            entry.info.state = state;
+            entry.info.error = error;
+            entry.info.finished_at_ms = Some(now_ms());
+        }
+        inner.prune();
+        // A slot was freed
+        self.wake.notify_all();
+    }
+
+    /// Current and recently finished jobs, oldest first
+    pub fn jobs(&self) -> Vec<JobInfo> {
+        let inner = self.inner.lock().unwrap();
+        inner.jobs.iter().map(|e| e.info.clone()).collect()
+    }
+
+    /// Request cancellation of a queued or running job
+    pub fn cancel(&self, id: &str) -> Result<(), JobError> {
+        let mut inner = self.inner.lock().unwrap();
+        let entry = inner
+            .entry_mut(id)
+            .ok_or_else(|| JobError::UnknownJob(id.to_string()))?;
+        if entry.info.state.is_finished() {
+            return Err(JobError::AlreadyFinished(id.to_string()));
+        }
+        entry.cancel.store(true, Ordering::SeqCst);
+        self.wake.notify_all();
+        Ok(())
+    }
+
+    /// Recording started/stopped: pauses or resumes heavy jobs
+    pub fn set_recording_active(&self, active: bool) {
+        self.inner.lock().unwrap().recording = active;
+        self.wake.notify_all();
+    }
+
+    pub fn set_settings(&self, settings: JobSettings) {
+        self.inner.lock().unwrap().settings = settings;
+        self.wake.notify_all();
+    }
+
+    pub fn settings(&self) -> JobSettings {
+        self.inner.lock().unwrap().settings.clone()
+    }
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+pub const SETTINGS_STEM: &str = "jobs";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    fn wait_for_state(scheduler: &JobScheduler, id: &str, state: JobState) {
+        let deadline = std::time::Instant::now() + TIMEOUT;
+        while std::time::Instant::now() < deadline {
+            if scheduler
+                .jobs()
+                .iter()
+                .any(|j| j.id == id && j.state == state)
+            {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        panic!("{} never reached {:?}: {:?}", id, state, scheduler.jobs());
+    }
+
+    #[test]
+    fn test_category_limit_queues_jobs() {
+        let scheduler = Arc::new(JobScheduler::default());
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        let release_rx = Arc::new(Mutex::new(release_rx));
+
+        let spawn_blocked = |label: &str| {
+            let rx = Arc::clone(&release_rx);
+            scheduler.spawn(JobCategory::Maintenance, label, move |_| {
+                rx.lock().unwrap().recv_timeout(TIMEOUT)?;
+                Ok(())
+            })
+        };
+        let first = spawn_blocked("first");
+        let second = spawn_blocked("second");
+        wait_for_state(&scheduler, &first, JobState::Running);
+        std::thread::sleep(Duration::from_millis(50));
+        assert_eq!(scheduler.jobs()[1].state, JobState::Queued);
+
+        // Other categories are not blocked
+        let export = scheduler.spawn(JobCategory::Export, "export", |ctx| {
+            ctx.set_progress(2.0);
+            Ok(())
+        });
+        wait_for_state(&scheduler, &export, JobState::Completed);
+        assert_eq!(scheduler.jobs()[2].progress, Some(1.0));
+
+        release_tx.send(()).unwrap();
+        wait_for_state(&scheduler, &first, JobState::Completed);
+        release_tx.send(()).unwrap();
+        wait_for_state(&scheduler, &second, JobState::Completed);
+    }
+
+    #[test]
+    fn test_recording_pauses_heavy_jobs() {
+        let scheduler = Arc::new(JobScheduler::default());
+        scheduler.set_recording_active(true);
+
+        let heavy = scheduler.spawn(JobCategory::Maintenance, "heavy", |_| Ok(()));
+        let light = scheduler.spawn(JobCategory::Summary, "light", |_| Ok(()));
+        wait_for_state(&scheduler, &light, JobState::Completed);
+        assert_eq!(scheduler.jobs()[0].state, JobState::Queued);
+
+        scheduler.set_recording_active(false);
+        wait_for_state(&scheduler, &heavy, JobState::Completed);
+
+        // Running heavy job holds at its checkpoint
+        let (step_tx, step_rx) = mpsc::channel::<()>();
+        let checkpointed = scheduler.spawn(JobCategory::Backup, "backup", move |ctx| {
+            step_rx.recv_timeout(TIMEOUT)?;
+            ctx.checkpoint()?;
+            Ok(())
+        });
+        wait_for_state(&scheduler, &checkpointed, JobState::Running);
+        scheduler.set_recording_active(true);
+        step_tx.send(()).unwrap();
+        wait_for_state(&scheduler, &checkpointed, JobState::Paused);
+        scheduler.set_recording_active(false);
+        wait_for_state(&scheduler, &checkpointed, JobState::Completed);
+    }
+
+    #[test]
+    fn test_cancel_queued_and_running_jobs() {
+        let scheduler = Arc::new(JobScheduler::default());
+        scheduler.set_recording_active(true);
+        let queued = scheduler.spawn(JobCategory::Transcription, "queued", |_| Ok(()));
+        scheduler.cancel(&queued).unwrap();
+        wait_for_state(&scheduler, &queued, JobState::Cancelled);
+        assert_eq!(
+            scheduler.cancel(&queued),
+            Err(JobError::AlreadyFinished(queued.clone()))
+        );
+        assert_eq!(
+            scheduler.cancel("job-999"),
+            Err(JobError::UnknownJob("job-999".to_string()))
+        );
+
+        let running = scheduler.spawn(JobCategory::Export, "loop", |ctx| loop {
+            ctx.checkpoint()?;
+            std::thread::sleep(Duration::from_millis(5));
+        });
+        wait_for_state(&scheduler, &running, JobState::Running);
+        scheduler.cancel(&running).unwrap();
+        wait_for_state(&scheduler, &running, JobState::Cancelled);
+    }
+}
@@ -0,0 +1,238 @@
+//! Live Keyword Alerting
+//!
+//! Configurable keyword watchlists matched against final transcription
+//! segments. A match produces a `keyword-alert` notification with the
+//! matched term and surrounding context.
+//!
+//! Persisted to `settings/keyword_alerts.json` in app data directory.
+
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// Settings Struct
+// ============================================================================
+
+/// Named list of keywords (e.g. "Budget" → ["budget", "予算"])
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeywordWatchlist {
+    pub name: String,
+    #[serde(default)]
+    pub keywords: Vec<String>,
+}
+
+/// Keyword alert configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeywordAlertSettings {
+    /// Alerting enabled
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// Match keywords case-sensitively
+    #[serde(default)]
+    pub case_sensitive: bool,
+
+    /// Number of characters of context on each side of a match
+    #[serde(default = "default_context_chars")]
+    pub context_chars: usize,
+
+    /// Watchlists
+    #[serde(default)]
+    pub watchlists: Vec<KeywordWatchlist>,
+
+    /// Settings version for future migrations
+    #[serde(default = "default_version")]
+    pub version: u32,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_context_chars() -> usize {
+    40
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+impl Default for KeywordAlertSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            case_sensitive: false,
+            context_chars: default_context_chars(),
+            watchlists: Vec::new(),
+            version: 1,
+        }
+    }
+}
+
+// ============================================================================
+// Matching
+// ============================================================================
+
+/// Single keyword hit within a segment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeywordMatch {
+    pub watchlist: String,
+    /// Keyword as configured
+    pub keyword: String,
+    /// Segment excerpt around the match ("…" marks truncation)
+    pub context: String,
+}
+
+impl KeywordAlertSettings {
+    /// Find watchlist keywords in a segment
+    ///
+    /// Each keyword is reported at most once per segment (first occurrence).
+    /// ASCII keywords only match on word boundaries ("budget" does not match
+    /// "budgeting"); other scripts (e.g. Japanese) match as substrings.
+    pub fn find_matches(&self, text: &str) -> Vec<KeywordMatch> {
+        if !self.enabled {
+            return Vec::new();
+        }
+
+        let original: Vec<char> = text.chars().collect();
+        let haystack = self.normalize(text);
+
+        let mut matches = Vec::new();
+        for watchlist in &self.watchlists {
+            for keyword in &watchlist.keywords {
+                let needle = self.normalize(keyword.trim());
+                if needle.is_empty() {
+                    continue;
+                }
+                if let Some(start) = find_word(&haystack, &needle) {
+                    matches.push(KeywordMatch {
+                        watchlist: watchlist.name.clone(),
+                        keyword: keyword.trim().to_string(),
+                        context: excerpt(&original, start, needle.len(), self.context_chars),
+                    });
+                }
+            }
+        }
+        matches
+    }
+
+    /// Lowercase per character, keeping a 1:1 char mapping with the original
+    fn normalize(&self, text: &str) -> Vec<char> {
+        text.chars()
+            .map(|c| {
+                if self.case_sensitive {
+                    return c;
+                }
+                let mut lower = c.to_lowercase();
+                match (lower.next(), lower.next()) {
+                    (Some(l), None) => l,
+                    _ => c,
+                }
+            })
+            .collect()
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+/// Find first occurrence of `needle` in `haystack` respecting ASCII word boundaries
+fn find_word(haystack: &[char], needle: &[char]) -> Option<usize> {
+    if needle.len() > haystack.len() {
+        return None;
+    }
+
+    (0..=haystack.len() - needle.len()).find(|&start| {
+        let end = start + needle.len();
+        if haystack[start..end] != *needle {
+            return false;
+        }
+        let left_ok = !is_word_char(needle[0]) || start == 0 || !is_word_char(haystack[start - 1]);
+        let right_ok = !is_word_char(needle[needle.len() - 1])
+            || end == haystack.len()
+            || !is_word_char(haystack[end]);
+        left_ok && right_ok
+    })
+}
+
+fn excerpt(chars: &[char], start: usize, len: usize, context_chars: usize) -> String {
+    let from = start.saturating_sub(context_chars);
+    let to = (start + len + context_chars).min(chars.len());
+
+    let mut context = String::new();
+    if from > 0 {
+        context.push('…');
+    }
+    context.extend(&chars[from..to]);
+    if to < chars.len() {
+        context.push('…');
+    }
+    context
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+pub const SETTINGS_STEM: &str = "keyword_alerts";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings_with(keywords: &[&str]) -> KeywordAlertSettings {
+        KeywordAlertSettings {
+            watchlists: vec![KeywordWatchlist {
+                name: "topics".to_string(),
+                keywords: keywords.iter().map(|k| k.to_string()).collect(),
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_case_insensitive_word_match() {
+        let settings = settings_with(&["budget"]);
+        let matches = settings.find_matches("Next item is the Budget review");
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].watchlist, "topics");
+        assert_eq!(matches[0].keyword, "budget");
+
+        // Word boundary: no match inside longer words
+        assert!(settings.find_matches("budgeting later").is_empty());
+    }
+
+    #[test]
+    fn test_case_sensitive_match() {
+        let mut settings = settings_with(&["ACME"]);
+        settings.case_sensitive = true;
+        assert!(settings.find_matches("acme corp").is_empty());
+        assert_eq!(settings.find_matches("ACME corp").len(), 1);
+    }
+
+    #[test]
+    fn test_japanese_substring_match() {
+        let settings = settings_with(&["予算"]);
+        let matches = settings.find_matches("来期の予算について");
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].context, "来期の予算について");
+    }
+
+    #[test]
+    fn test_context_is_truncated() {
+        let mut settings = settings_with(&["budget"]);
+        settings.context_chars = 4;
+        let matches = settings.find_matches("we need to talk about budget numbers today");
+        assert_eq!(matches[0].context, "…out budget num…");
+    }
+
+    #[test]
+    fn test_disabled_and_empty_keywords() {
+        let mut settings = settings_with(&["", "  "]);
+        assert!(settings.find_matches("anything").is_empty());
+
+        settings = settings_with(&["budget"]);
+        settings.enabled = false;
+        assert!(settings.find_matches("budget").is_empty());
+    }
+}
@@ -0,0 +1,386 @@
+//! Utterance Latency Breakdown
+//!
+//! Timing checkpoints along the transcription path, aggregated into
+//! per-stage histograms so slowness can be attributed to buffering, the
+//! stdin IPC write, Whisper, or the broadcaster:
+//!
+//! ```text
+//! captured -> batched -> stdin_written -> first partial / final -> persisted
+//!                                                               -> broadcast
+//! ```
+//!
+//! Audio batches are keyed by their IPC request ID (`audio-<ms>`); sidecar
+//! events carry the ID of the batch being processed when they were emitted.
+//! All times are epoch milliseconds.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+/// Batches remembered for matching events (~2 minutes at 250ms batches)
+const MAX_TRACKED_BATCHES: usize = 512;
+
+/// Histogram bucket upper bounds (ms); the last bucket is unbounded
+pub const BUCKET_BOUNDS_MS: [u64; 10] = [10, 25, 50, 100, 250, 500, 1000, 2500, 5000, 10000];
+
+/// Current epoch milliseconds (checkpoint clock)
+pub fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+/// Measured stage
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LatencyStage {
+    /// Audio captured -> batched (ring buffer wait)
+    Buffering,
+    /// Batched -> written to sidecar stdin (writer queue + pipe)
+    IpcWrite,
+    /// Batch written -> first partial of an utterance (Whisper)
+    FirstPartial,
+    /// Batch written -> final text (Whisper)
+    Final,
+    /// First partial -> final text of the same utterance
+    PartialToFinal,
+    /// Final received -> persisted to transcription.jsonl
+    Persist,
+    /// Final received -> broadcast to WebSocket clients
+    Broadcast,
+    /// Audio captured -> final broadcast
+    EndToEnd,
+}
+
+/// Fixed-bucket latency histogram
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LatencyHistogram {
+    /// Counts per bucket (`BUCKET_BOUNDS_MS` + overflow)
+    pub buckets: Vec<u64>,
+    pub count: u64,
+    pub sum_ms: u64,
+    pub max_ms: u64,
+}
+
+impl LatencyHistogram {
+    pub fn record(&mut self, ms: u64) {
+        if self.buckets.is_empty() {
+            self.buckets = vec![0; BUCKET_BOUNDS_MS.len() + 1];
+        }
+        let bucket = BUCKET_BOUNDS_MS
+            .iter()
+            .position(|&bound| ms <= bound)
+            .unwrap_or(BUCKET_BOUNDS_MS.len());
+        self.buckets[bucket] += 1;
+        self.count += 1;
+        self.sum_ms += ms;
+        self.max_ms = self.max_ms.max(ms);
+    }
+
+    /// Add the samples of another histogram
+    pub fn merge(&mut self, other: &LatencyHistogram) {
+        if other.count == 0 {
+            return;
+        }
+        if self.buckets.is_empty() {
+            self.buckets = vec![0; BUCKET_BOUNDS_MS.len() + 1];
+        }
+        for (bucket, count) in self.buckets.iter_mut().zip(&other.buckets) {
+            *bucket += count;
+        }
+        self.count += other.count;
+        self.sum_ms += other.sum_ms;
+        self.max_ms = self.max_ms.max(other.max_ms);
+    }
+
+    pub fn mean_ms(&self) -> Option<u64> {
+        (self.count > 0).then(|| self.sum_ms / self.count)
+    }
+
+    /// Upper bound of the bucket containing quantile `q` (0.0-1.0)
+    ///
+    /// Values in the overflow bucket report the observed maximum.
+    pub fn percentile_ms(&self, q: f64) -> Option<u64> {
+        if self.count == 0 {
+            return None;
+        }
+        let rank = ((self.count as f64) * q.clamp(0.0, 1.0)).ceil().max(1.0) as u64;
+        let mut seen = 0;
+        for (i, &count) in self.buckets.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return Some(BUCKET_BOUNDS_MS.get(i).copied().unwrap_or(self.max_ms));
+            }
+        }
+        Some(self.max_ms)
+    }
+}
+
+/// Histogram with summary statistics, as returned to the UI
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StageLatency {
+    pub histogram: LatencyHistogram,
+    pub mean_ms: Option<u64>,
+    pub p50_ms: Option<u64>,
+    pub p95_ms: Option<u64>,
+}
+
+/// Per-stage latency of the active (or last) session
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LatencySnapshot {
+    pub bucket_bounds_ms: Vec<u64>,
+    pub stages: BTreeMap<LatencyStage, StageLatency>,
+}
+
+impl LatencySnapshot {
+    pub fn from_histograms(histograms: &BTreeMap<LatencyStage, LatencyHistogram>) -> Self {
+        Self {
+            bucket_bounds_ms: BUCKET_BOUNDS_MS.to_vec(),
+            stages: histograms
+                .iter()
+                .map(|(stage, histogram)| {
+                    (
+                        *stage,
+                        StageLatency {
+                            histogram: histogram.clone(),
+                            mean_ms: histogram.mean_ms(),
+                            p50_ms: histogram.percentile_ms(0.5),
+                            p95_ms: histogram.percentile_ms(0.95),
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct BatchTiming {
+    captured_ms: u64,
+    batched_ms: u64,
+    written_ms: Option<u64>,
+}
+
+/// Collects checkpoints and aggregates stage latencies
+#[derive(Debug, Default)]
+pub struct LatencyTracker {
+    batches: HashMap<String, BatchTiming>,
+    order: VecDeque<String>,
+    /// When the first partial since the last final arrived (utterance in
+    /// progress)
+    utterance_started_ms: Option<u64>,
+    histograms: BTreeMap<LatencyStage, LatencyHistogram>,
+}
+
+impl LatencyTracker {
+    fn record(&mut self, stage: LatencyStage, from_ms: u64, to_ms: u64) {
+        self.histograms
+            .entry(stage)
+            .or_default()
+            .record(to_ms.saturating_sub(from_ms));
+    }
+
+    /// Audio batch handed to the stdin writer
+    ///
+    /// `captured_ms` is when its oldest sample was captured.
+    pub fn batch_queued(&mut self, request_id: &str, captured_ms: u64, batched_ms: u64) {
+        self.record(LatencyStage::Buffering, captured_ms, batched_ms);
+        self.batches.insert(
+            request_id.to_string(),
+            BatchTiming {
+                captured_ms,
+                batched_ms,
+                written_ms: None,
+            },
+        );
+        self.order.push_back(request_id.to_string());
+        while self.order.len() > MAX_TRACKED_BATCHES {
+            if let Some(oldest) = self.order.pop_front() {
+                self.batches.remove(&oldest);
+            }
+        }
+    }
+
+    /// Audio batch fully written to sidecar stdin
+    pub fn batch_written(&mut self, request_id: &str, at_ms: u64) {
+        let Some(batch) = self.batches.get_mut(request_id) else {
+            return;
+        };
+        batch.written_ms = Some(at_ms);
+        let batched_ms = batch.batched_ms;
+        self.record(LatencyStage::IpcWrite, batched_ms, at_ms);
+    }
+
+    fn written_ms(&self, request_id: &str) -> Option<u64> {
+        self.batches.get(request_id).and_then(|b| b.written_ms)
+    }
+
+    /// Partial text received; only the first of an utterance is measured
+    pub fn partial_received(&mut self, request_id: &str, at_ms: u64) {
+        if self.utterance_started_ms.is_some() {
+            return;
+        }
+        self.utterance_started_ms = Some(at_ms);
+        if let Some(written_ms) = self.written_ms(request_id) {
+            self.record(LatencyStage::FirstPartial, written_ms, at_ms);
+        }
+    }
+
+    /// Final text handled: received at `final_ms`, then persisted/broadcast
+    pub fn final_completed(
+        &mut self,
+        request_id: &str,
+        final_ms: u64,
+        persisted_ms: Option<u64>,
+        broadcast_ms: Option<u64>,
+    ) {
+        if let Some(started_ms) = self.utterance_started_ms.take() {
+            self.record(LatencyStage::PartialToFinal, started_ms, final_ms);
+        }
+        if let Some(written_ms) = self.written_ms(request_id) {
+            self.record(LatencyStage::Final, written_ms, final_ms);
+        }
+        if let Some(persisted_ms) = persisted_ms {
+            self.record(LatencyStage::Persist, final_ms, persisted_ms);
+        }
+        if let Some(broadcast_ms) = broadcast_ms {
+            self.record(LatencyStage::Broadcast, final_ms, broadcast_ms);
+            if let Some(batch) = self.batches.get(request_id).copied() {
+                self.record(LatencyStage::EndToEnd, batch.captured_ms, broadcast_ms);
+            }
+        }
+    }
+
+    pub fn snapshot(&self) -> LatencySnapshot {
+        LatencySnapshot::from_histograms(&self.histograms)
+    }
+
+    /// Approximate heap usage of the in-flight batch table (bytes)
+    ///
+    /// Request IDs are held twice: by the table and by the eviction order.
+    pub fn approx_bytes(&self) -> usize {
+        self.batches
+            .keys()
+            .map(|id| {
+                std::mem::size_of::<BatchTiming>()
+                    + std::mem::size_of::<String>() * 2
+                    + id.len() * 2
+            })
+            .sum()
+    }
+
+    /// Take the histograms recorded so far, starting a new window
+    ///
+    /// In-flight batches are kept, so utterances spanning windows are still
+    /// measured.
+    pub fn take_histograms(&mut self) -> BTreeMap<LatencyStage, LatencyHistogram> {
+        std::mem::take(&mut self.histograms)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram_percentiles() {
+        let mut histogram = LatencyHistogram::default();
+        assert_eq!(histogram.percentile_ms(0.5), None);
+        for ms in [5, 20, 40, 80, 90, 200, 300, 700, 900, 20_000] {
+            histogram.record(ms);
+        }
+        assert_eq!(histogram.count, 10);
+        assert_eq!(histogram.mean_ms(), Some(2233));
+        assert_eq!(histogram.percentile_ms(0.5), Some(100));
+        assert_eq!(histogram.percentile_ms(0.9), Some(1000));
+        assert_eq!(histogram.percentile_ms(0.95), Some(20_000));
+        assert_eq!(histogram.buckets[BUCKET_BOUNDS_MS.len()], 1);
+
+        let mut merged = LatencyHistogram::default();
+        merged.merge(&LatencyHistogram::default());
+        assert!(merged.buckets.is_empty());
+        merged.merge(&histogram);
+        merged.merge(&histogram);
+        assert_eq!(merged.count, 20);
+        assert_eq!(merged.percentile_ms(0.5), Some(100));
+        assert_eq!(merged.max_ms, 20_000);
+    }
+
+    #[test]
+    fn test_tracker_stage_breakdown() {
+        let mut tracker = LatencyTracker::default();
+        tracker.batch_queued("audio-1", 1_000, 1_300);
+        tracker.batch_written("audio-1", 1_310);
+        tracker.batch_queued("audio-2", 1_250, 1_550);
+        tracker.batch_written("audio-2", 1_560);
+
+        tracker.partial_received("audio-1", 1_700);
+        // Later partials of the same utterance are not "first"
+        tracker.partial_received("audio-2", 1_900);
+        tracker.final_completed("audio-2", 2_560, Some(2_565), Some(2_580));
+
+        let snapshot = tracker.snapshot();
+        let p50 = |stage| snapshot.stages[&stage].p50_ms;
+        let count = |stage: LatencyStage| snapshot.stages[&stage].histogram.count;
+        assert_eq!(count(LatencyStage::Buffering), 2);
+        assert_eq!(
+            snapshot.stages[&LatencyStage::IpcWrite].histogram.sum_ms,
+            20
+        );
+        assert_eq!(count(LatencyStage::FirstPartial), 1);
+        assert_eq!(
+            snapshot.stages[&LatencyStage::FirstPartial]
+                .histogram
+                .max_ms,
+            390
+        );
+        assert_eq!(p50(LatencyStage::Final), Some(1000));
+        assert_eq!(
+            snapshot.stages[&LatencyStage::PartialToFinal]
+                .histogram
+                .max_ms,
+            860
+        );
+        assert_eq!(snapshot.stages[&LatencyStage::Persist].histogram.max_ms, 5);
+        assert_eq!(
+            snapshot.stages[&LatencyStage::Broadcast].histogram.max_ms,
+            20
+        );
+        assert_eq!(
+            snapshot.stages[&LatencyStage::EndToEnd].histogram.max_ms,
+            1_330
+        );
+
+        // Next utterance measures its first partial again; unknown IDs are ignored
+        tracker.partial_received("audio-9", 3_000);
+        tracker.partial_received("audio-2", 3_100);
+        assert_eq!(
+            tracker.snapshot().stages[&LatencyStage::FirstPartial]
+                .histogram
+                .count,
+            1
+        );
+
+        // Taking the histograms starts a new window
+        let window = tracker.take_histograms();
+        assert_eq!(window[&LatencyStage::Buffering].count, 2);
+        assert!(tracker.snapshot().stages.is_empty());
+        tracker.final_completed("audio-2", 3_560, None, None);
+        assert_eq!(
+            tracker.snapshot().stages[&LatencyStage::Final]
+                .histogram
+                .count,
+            1
+        );
+    }
+
+    #[test]
+    fn test_tracked_batches_are_bounded() {
+        let mut tracker = LatencyTracker::default();
+        for i in 0..(MAX_TRACKED_BATCHES + 10) {
+            tracker.batch_queued(&format!("audio-{}", i), 0, 0);
+        }
+        assert_eq!(tracker.batches.len(), MAX_TRACKED_BATCHES);
+        assert!(!tracker.batches.contains_key("audio-0"));
+    }
+}
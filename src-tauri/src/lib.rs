@@ -5,48 +5,593 @@
 
 #[macro_use]
 pub mod logger;
+pub mod activity; // Per-second energy/voice activity timeline
+pub mod agenda;
+pub mod app_health; // Health report, `/health` and the watchdog exit code for supervisors
+pub mod chapters; // Chapter markers (cue sheet / ffmetadata) for exported audio
+pub mod maintenance; // Background rebuild of derived session files
+pub mod memory_sentinel; // Byte accounting and leak warnings for long-lived buffers
+pub mod message_id; // Monotonic ULID-style IDs for WebSocket messages and control requests
+pub mod markers;
 pub mod audio;
 pub mod audio_device_adapter;
+pub mod input_format; // Sample format negotiation and f32 conversion for capture streams
+pub mod channel_selection; // Per-device input selection on multi-channel interfaces
 pub mod audio_device_recorder; // STTMIX Task 1.1 - Facade for single/multi-input
+pub mod audio_dump; // Debug dump of the audio sent to the sidecar
+pub mod audio_format; // Saved session audio format (WAV/FLAC/Opus)
+pub mod batch_export; // Export of many sessions (by ID, date range or tag) in one job
 pub mod multi_input_manager; // STTMIX Task 2.1 - Parallel capture manager
 pub mod multi_input_settings; // STTMIX Task 7.1 - Settings persistence
+pub mod keyword_alerts; // Live keyword alerting
 pub mod input_mixer; // STTMIX Task 4 - Time alignment and mixing
+pub mod input_tracks; // Per-input WAV tracks in multi-input mode
 pub mod resampler; // STTMIX Task 3.1 - Audio resampling and downmix
+pub mod cloud_stt; // Cloud STT provider as fallback for (or instead of) the local model
 pub mod commands;
+pub mod crash_recovery; // Startup repair of sessions left unfinalized by a crash
+pub mod device_aliases; // Friendly names for audio devices
+pub mod diagnostics; // Zip bundle of logs/settings/metrics for bug reports
+pub mod docs_budget; // Google Docs character budget per session/document
+pub mod docx; // Minimal DOCX (WordprocessingML) writer for exports
+pub mod encryption; // AES-GCM at-rest encryption of session.json / transcripts (keychain key)
+pub mod flac; // Lossless FLAC encoding of session audio
+pub mod heartbeat; // Recording heartbeat for crash detection
+pub mod http_api; // Read-only local HTTP API for sessions, transcripts and audio
+pub mod ipc_flow_control; // Adaptive audio batching from sidecar acks (backpressure)
 pub mod ipc_protocol;
+pub mod ipc_quarantine; // Quarantine of malformed sidecar IPC lines
+#[cfg(feature = "opus")]
+pub mod opus; // Ogg Opus session audio (feature `opus`)
+pub mod ops_webhooks; // Webhooks for disk, sidecar and reconnection events (fleet monitoring)
+pub mod jobs; // Background job scheduler with per-category limits
+pub mod latency; // Per-utterance latency breakdown histograms
+pub mod loopback_check; // Chirp test of loopback (virtual device) routing
+pub mod loudness; // Integrated loudness (LUFS) and applied gains per session
+pub mod mqtt; // MQTT publishing of session lifecycle and final segments
+pub mod partial_granularity; // Word- vs sentence-level forwarding of partial text
+pub mod pipeline; // Configurable post-processing stages for final segments
 pub mod python_sidecar;
+pub mod questions; // Question detection and open-question tracking
+pub mod rate_limit; // Token buckets and Retry-After for outbound webhooks
+pub mod reconnect_policy; // Auto / prompt / never policy for device reconnects
 pub mod reconnection_manager; // Task 10.4 Phase 2 - STT-REQ-004.11
+pub mod redaction; // Bleeped or silenced copy of a session for sharing outside the team
+pub mod request_id; // Session-scoped IPC request IDs ({session}-{stream}-{seq})
+pub mod retranscription; // Re-transcription of recorded sessions into new transcript versions
+pub mod retention; // Automatic cleanup of old sessions (age/count/size limits)
+pub mod routing; // Regex-based transcript routing rules
+pub mod search; // Keyword search within a session transcript
+pub mod segmentation; // Sentence splitting of final segments for exports
+pub mod furigana; // Reading annotations for rare kanji in exports
 pub mod ring_buffer; // ADR-013: Phase 2 - Ring Buffer
 pub mod sidecar; // ADR-013: Phase 1 - Facade API
+pub mod session_archive; // Zip archive export/import of a session
+pub mod session_id; // Configurable session ID format
+pub mod recording_session; // Recording session lifecycle (writers, tasks, pause, stats)
+pub mod session_registry; // Recording sessions keyed by session ID (concurrent sessions)
+pub mod session_stats; // Per-session words, pace, silence and latency statistics
+pub mod settings_file; // Settings persistence shared by the settings modules (settings/<stem>.json)
+pub mod settings_reload; // reload_settings: apply edited settings files without a restart
+pub mod silence_trim; // Export with long silent stretches cut out
+pub mod soak; // Hidden --soak mode: long-running leak/latency soak test
 pub mod state;
+pub mod sidecar_audio_channel; // Unix socket / named pipe carrying raw PCM to the sidecar
+pub mod sidecar_health; // Periodic sidecar health checks (model, real-time factor, memory)
+pub mod sidecar_stderr; // Sidecar stderr lines forwarded to the structured logger (python::*)
+pub mod stdin_writer; // Single sidecar stdin writer with control priority
 pub mod storage;
+pub mod storage_root; // Configurable recordings directory (external drive)
+pub mod summary; // Streamed LLM meeting summaries
+pub mod task_supervisor; // Panic isolation for pipeline tasks
+pub mod test_audio; // Hidden --gen-test-audio mode: labeled synthetic audio for fixtures and demos
+pub mod timeline; // UI events recorded into the session timeline
+pub mod trash; // Retention settings for deleted (trashed) sessions
+pub mod transcript_chunks; // Hourly transcript parts with a manifest and paged reads
+pub mod transcript_diff; // Word-level comparison of transcript versions
+pub mod transcript_schema; // Versioned transcript line schema with upgrades of older files
+pub mod transcript_webhooks; // Webhooks for final segments and session ends (no-code integrations)
+pub mod transcription_estimate; // Dry-run duration, CPU/GPU load and cost of a transcription job
+pub mod transcription_workers; // Sidecar worker pool: final transcription in N processes
+pub mod viewer_mode; // Read-only mode for browsing sessions without audio hardware
+pub mod wasapi_capture; // WASAPI exclusive mode and buffer sizing with fallback (Windows)
+pub mod waveform; // audiowaveform-compatible peak files
+pub mod playback; // In-app playback of saved session audio
+pub mod consent; // Recording consent announcement at recording start
 pub mod websocket;
+pub mod websocket_audio; // Opt-in binary PCM monitoring stream for WebSocket clients
+pub mod websocket_control; // Authenticated start/stop/status control messages from WebSocket clients
+pub mod websocket_heartbeat; // Ping/pong liveness checks that disconnect dead clients
+pub mod websocket_limits; // Size limits and chunking of outgoing WebSocket messages
+pub mod websocket_port; // Configurable listening port, fallback range and port discovery
+pub mod websocket_mdns; // mDNS/DNS-SD advertisement of the WebSocket server
+pub mod websocket_queue; // Bounded per-client send queues that drop partials first
+pub mod websocket_replay; // Replay of missed transcriptions to resuming WebSocket clients
+pub mod websocket_retry; // Retries and failure accounting for WebSocket client writes
+pub mod websocket_subscription; // Per-client message type subscriptions
+pub mod workspaces; // Named workspaces with separate settings and recordings (shared machines)
 
 use audio_device_adapter::create_audio_adapter;
-use audio_device_recorder::AudioDeviceRecorder;
 use python_sidecar::PythonSidecarManager;
 use state::AppState;
 use std::sync::Arc;
-use tauri::Manager;
+use storage::LocalStorageService;
+use tauri::{Emitter, Manager};
 use websocket::WebSocketServer;
 
+/// Run each command invoke inside a log span named after the command
+/// (covers the whole call for sync commands, dispatch only for async ones)
+///
+/// In viewer mode, commands outside the viewer allowlist are rejected here.
+fn traced_handler<R, F>(
+    handler: F,
+) -> impl Fn(tauri::ipc::Invoke<R>) -> bool + Send + Sync + 'static
+where
+    R: tauri::Runtime,
+    F: Fn(tauri::ipc::Invoke<R>) -> bool + Send + Sync + 'static,
+{
+    move |invoke| {
+        let command = invoke.message.command().to_string();
+        let _span = logger::LogSpan::enter("ipc::invoke", &command);
+        let in_viewer_mode = invoke
+            .message
+            .webview_ref()
+            .state::<AppState>()
+            .is_viewer_mode();
+        if in_viewer_mode && !viewer_mode::is_allowed(&command) {
+            log_warn!("ipc::invoke", "viewer_mode_rejected", &command);
+            invoke
+                .resolver
+                .reject(format!("{} is not available in viewer mode", command));
+            return true;
+        }
+        handler(invoke)
+    }
+}
+
+/// Load a settings file, or use the defaults if `dir` is unknown or the file
+/// can't be read
+///
+/// `apply` turns the settings into what the app uses (usually `Ok` as is, or
+/// after validating or compiling them); if it fails, the defaults are used as
+/// well. Failures are logged as `<stem>_settings_load_failed`.
+fn load_or_default<T, U>(
+    dir: Option<&std::path::Path>,
+    stem: &str,
+    apply: impl FnOnce(T) -> anyhow::Result<U>,
+) -> U
+where
+    T: serde::de::DeserializeOwned + Default,
+    U: Default,
+{
+    let Some(dir) = dir else {
+        return U::default();
+    };
+    match settings_file::load(dir, stem).and_then(apply) {
+        Ok(value) => value,
+        Err(e) => {
+            log_error!(
+                "bootstrap::settings",
+                &format!("{}_settings_load_failed", stem),
+                format!("{:?}", e)
+            );
+            U::default()
+        }
+    }
+}
+
+/// Cloud STT: the saved setting with its API key, or off
+fn startup_cloud_stt(
+    app_data_dir: Option<&std::path::Path>,
+) -> (cloud_stt::CloudSttSettings, Option<String>) {
+    let settings = load_or_default(
+        app_data_dir,
+        cloud_stt::SETTINGS_STEM,
+        |s: cloud_stt::CloudSttSettings| s.validate().map(|()| s),
+    );
+    if !settings.is_enabled() {
+        return Default::default();
+    }
+    match cloud_stt::load_api_key(settings.provider) {
+        Ok(Some(api_key)) => (settings, Some(api_key)),
+        Ok(None) => {
+            log_warn!(
+                "bootstrap::settings",
+                "cloud_stt_api_key_missing",
+                format!("provider={}", settings.provider.as_str())
+            );
+            Default::default()
+        }
+        Err(e) => {
+            log_error!(
+                "bootstrap::settings",
+                "cloud_stt_api_key_load_failed",
+                format!("{:?}", e)
+            );
+            Default::default()
+        }
+    }
+}
+
+/// Start the local HTTP API if enabled in the saved settings
+async fn start_http_api(app: &tauri::AppHandle) {
+    let Ok(app_data_dir) = app.path().app_data_dir() else {
+        return;
+    };
+    let settings = match settings_file::load(&app_data_dir, http_api::SETTINGS_STEM) {
+        Ok(settings) => settings,
+        Err(e) => {
+            log_error!(
+                "bootstrap::settings",
+                "http_api_settings_load_failed",
+                format!("{:?}", e)
+            );
+            return;
+        }
+    };
+    if let Err(e) = commands::apply_http_api_settings(app, &settings).await {
+        log_error!("bootstrap::http_api", "start_failed", e);
+    }
+}
+
+/// Connect to the MQTT broker if enabled in the saved settings
+async fn start_mqtt(app: &tauri::AppHandle) {
+    let Ok(app_data_dir) = app.path().app_data_dir() else {
+        return;
+    };
+    let settings = match mqtt::load_settings_with_password(&app_data_dir) {
+        Ok(settings) => settings,
+        Err(e) => {
+            log_error!(
+                "bootstrap::settings",
+                "mqtt_settings_load_failed",
+                format!("{:?}", e)
+            );
+            return;
+        }
+    };
+    if let Err(e) = commands::apply_mqtt_settings(app, &settings).await {
+        log_error!("bootstrap::mqtt", "start_failed", e);
+    }
+}
+
+/// Advertise the WebSocket server over mDNS if enabled in the saved settings
+fn start_mdns(app: &tauri::AppHandle) {
+    let Ok(app_data_dir) = app.path().app_data_dir() else {
+        return;
+    };
+    let settings = match settings_file::load(&app_data_dir, websocket_mdns::SETTINGS_STEM) {
+        Ok(settings) => settings,
+        Err(e) => {
+            log_error!(
+                "bootstrap::settings",
+                "mdns_settings_load_failed",
+                format!("{:?}", e)
+            );
+            return;
+        }
+    };
+    if let Err(e) = commands::apply_mdns_settings(app, &settings) {
+        log_error!("bootstrap::mdns", "start_failed", e);
+    }
+}
+
+/// Detect a recording interrupted by a crash (stale heartbeat) and recover its files
+fn recover_interrupted_recording(app_state: &AppState, storage: &LocalStorageService) {
+    let heartbeat = match heartbeat::read_heartbeat(storage.app_data_dir()) {
+        Ok(Some(heartbeat)) => heartbeat,
+        Ok(None) => return,
+        Err(e) => {
+            log_error!("bootstrap::recovery", "heartbeat_read_failed", format!("{:?}", e));
+            return;
+        }
+    };
+
+    let now_ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64;
+    if !heartbeat.is_stale(now_ms) {
+        // Possibly owned by another running instance
+        log_warn!(
+            "bootstrap::recovery",
+            "heartbeat_not_stale",
+            format!("session={}", heartbeat.session_id)
+        );
+        return;
+    }
+
+    match heartbeat::recover_interrupted_session(storage.app_data_dir(), storage, &heartbeat) {
+        Ok(interrupted) => {
+            log_warn_details!(
+                "bootstrap::recovery",
+                "interrupted_recording_recovered",
+                serde_json::json!({
+                    "session": interrupted.session_id,
+                    "interrupted_at_ms": interrupted.interrupted_at_ms,
+                    "last_sample_offset": heartbeat.last_sample_offset,
+                    "last_event_seq": heartbeat.last_event_seq
+                })
+            );
+            app_state.set_interrupted_recording(interrupted);
+        }
+        Err(e) => {
+            log_error!("bootstrap::recovery", "recovery_failed", format!("{:?}", e));
+        }
+    }
+}
+
+/// Repair sessions left without session.json by a crash and emit `recovered_sessions`
+///
+/// Runs after the heartbeat recovery; a heartbeat still present at this point
+/// belongs to a live recording, whose session is skipped.
+fn recover_unfinalized_sessions(
+    app: &tauri::AppHandle,
+    app_state: &AppState,
+    storage: &LocalStorageService,
+) {
+    let active_session = heartbeat::read_heartbeat(storage.app_data_dir())
+        .ok()
+        .flatten()
+        .map(|heartbeat| heartbeat.session_id);
+    let session_ids = match crash_recovery::find_unfinalized_sessions(
+        storage.recordings_dir(),
+        active_session.as_deref(),
+    ) {
+        Ok(session_ids) => session_ids,
+        Err(e) => {
+            log_error!("bootstrap::recovery", "unfinalized_scan_failed", format!("{:?}", e));
+            return;
+        }
+    };
+
+    let mut recovered = Vec::new();
+    for session_id in session_ids {
+        match crash_recovery::repair_session(storage, &session_id) {
+            Ok(session) => recovered.push(session),
+            Err(e) => {
+                log_error_details!(
+                    "bootstrap::recovery",
+                    "session_repair_failed",
+                    serde_json::json!({ "session": session_id, "error": format!("{:#}", e) })
+                );
+            }
+        }
+    }
+    if recovered.is_empty() {
+        return;
+    }
+
+    log_warn_details!(
+        "bootstrap::recovery",
+        "unfinalized_sessions_recovered",
+        serde_json::json!({
+            "sessions": recovered.iter().map(|s| &s.session_id).collect::<Vec<_>>()
+        })
+    );
+    let _ = app.emit("recovered_sessions", &recovered);
+    app_state.set_recovered_sessions(recovered);
+}
+
+/// Workspace to open at startup: `--workspace <name>` if it exists, else the
+/// last active one
+fn startup_workspace(app_data_dir: &std::path::Path) -> String {
+    let settings: workspaces::WorkspaceSettings =
+        match settings_file::load(app_data_dir, workspaces::SETTINGS_STEM) {
+            Ok(settings) => settings,
+            Err(e) => {
+                log_error!(
+                    "bootstrap::settings",
+                    "workspace_settings_load_failed",
+                    format!("{:?}", e)
+                );
+                return workspaces::DEFAULT_WORKSPACE.to_string();
+            }
+        };
+    if let Some(requested) = workspaces::requested_workspace(std::env::args()) {
+        if settings.contains(&requested) {
+            return requested;
+        }
+        log_warn!("bootstrap::workspace", "unknown_workspace", requested);
+    }
+    if settings.contains(&settings.active) {
+        settings.active
+    } else {
+        workspaces::DEFAULT_WORKSPACE.to_string()
+    }
+}
+
+/// Load the settings and storage of a workspace into the app state
+///
+/// Runs at startup and on `switch_workspace`; `app_data_dir` is the data
+/// directory of `workspace`. Settings that fail to load fall back to their
+/// defaults, so nothing carries over from the previous workspace.
+pub(crate) fn load_workspace(
+    app: &tauri::AppHandle,
+    app_state: &AppState,
+    workspace: &str,
+    app_data_dir: std::path::PathBuf,
+) {
+    let dir = Some(app_data_dir.as_path());
+    app_state.set_keyword_alert_settings(load_or_default(dir, keyword_alerts::SETTINGS_STEM, Ok));
+    app_state.set_docs_budget_settings(load_or_default(dir, docs_budget::SETTINGS_STEM, Ok));
+    app_state.set_routing_engine(load_or_default(dir, routing::SETTINGS_STEM, |s| {
+        routing::RoutingEngine::compile(&s)
+    }));
+    app_state.set_transcript_webhook_settings(load_or_default(
+        dir,
+        transcript_webhooks::SETTINGS_STEM,
+        Ok,
+    ));
+    app_state.set_pipeline(load_or_default(dir, pipeline::SETTINGS_STEM, |s| {
+        pipeline::Pipeline::compile(&s)
+    }));
+    app_state
+        .jobs
+        .set_settings(load_or_default(dir, jobs::SETTINGS_STEM, Ok));
+    app_state.set_memory_sentinel_settings(load_or_default(
+        dir,
+        memory_sentinel::SETTINGS_STEM,
+        Ok,
+    ));
+    app_state.set_trash_settings(load_or_default(dir, trash::SETTINGS_STEM, Ok));
+    app_state.set_audio_format_settings(load_or_default(dir, audio_format::SETTINGS_STEM, Ok));
+    app_state.set_reconnect_policy_settings(load_or_default(
+        dir,
+        reconnect_policy::SETTINGS_STEM,
+        Ok,
+    ));
+    app_state.set_consent_announcement_settings(load_or_default(dir, consent::SETTINGS_STEM, Ok));
+    app_state.set_device_alias_settings(load_or_default(dir, device_aliases::SETTINGS_STEM, Ok));
+    app_state.set_partial_granularity_settings(load_or_default(
+        dir,
+        partial_granularity::SETTINGS_STEM,
+        Ok,
+    ));
+    app_state.set_debug_settings(load_or_default(dir, audio_dump::SETTINGS_STEM, Ok));
+    app_state.set_ipc_quarantine_settings(load_or_default(dir, ipc_quarantine::SETTINGS_STEM, Ok));
+    app_state.set_retention_settings(load_or_default(dir, retention::SETTINGS_STEM, Ok));
+    app_state.set_websocket_settings(load_or_default(dir, websocket_limits::SETTINGS_STEM, Ok));
+    // Without readable settings, an existing key still opens sealed sessions
+    let encryption = commands::load_storage_encryption(
+        &load_or_default(dir, encryption::SETTINGS_STEM, Ok),
+        workspace,
+    );
+    let root_settings = load_or_default(dir, storage_root::SETTINGS_STEM, Ok);
+    let storage =
+        commands::storage_for_root(app_data_dir, &root_settings).with_encryption(encryption);
+    recover_interrupted_recording(app_state, &storage);
+    recover_unfinalized_sessions(app, app_state, &storage);
+    commands::purge_expired_trash(&storage, &app_state.get_trash_settings());
+    app_state.set_storage_service(storage);
+    app_state.set_settings_snapshot(settings_reload::SettingsSnapshot::capture(
+        &commands::settings_dirs(app),
+    ));
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
         .manage(AppState::new())
         .setup(|app| {
             // Get AppHandle for use in async task
             let app_handle = app.handle().clone();
 
+            // Machine-wide settings (`--viewer` overrides the saved viewer mode)
+            let app_data_dir = app.path().app_data_dir().ok();
+            let dir = app_data_dir.as_deref();
+            let viewer_mode = viewer_mode::requested(std::env::args())
+                || load_or_default(
+                    dir,
+                    viewer_mode::SETTINGS_STEM,
+                    |s: viewer_mode::ViewerModeSettings| Ok(s.enabled),
+                );
+            app.state::<AppState>().set_viewer_mode(viewer_mode);
+            app.state::<AppState>()
+                .set_ops_webhook_settings(load_or_default(dir, ops_webhooks::SETTINGS_STEM, Ok));
+            app.state::<AppState>()
+                .set_rate_limit_settings(load_or_default(dir, rate_limit::SETTINGS_STEM, Ok));
+            app.state::<AppState>().set_health_settings(load_or_default(
+                dir,
+                app_health::SETTINGS_STEM,
+                |s: app_health::HealthSettings| s.validate().map(|()| s),
+            ));
+            wasapi_capture::set_settings(load_or_default(
+                dir,
+                wasapi_capture::SETTINGS_STEM,
+                |s: wasapi_capture::WasapiCaptureSettings| s.validate().map(|()| s),
+            ));
+            channel_selection::set_settings(load_or_default(
+                dir,
+                channel_selection::SETTINGS_STEM,
+                |s: channel_selection::ChannelSelectionSettings| s.validate().map(|()| s),
+            ));
+
+            // 0. Initialize local storage (STT-REQ-005.1) and persisted settings
+            match app.path().app_data_dir() {
+                Ok(app_data_dir) => {
+                    let app_state = app.state::<AppState>();
+                    let workspace = startup_workspace(&app_data_dir);
+                    log_info!("bootstrap::workspace", "workspace_opened", &workspace);
+                    app_state.set_active_workspace(workspace.clone());
+                    load_workspace(
+                        app.handle(),
+                        &app_state,
+                        &workspace,
+                        workspaces::data_dir(&app_data_dir, &workspace),
+                    );
+                    if let Err(e) = commands::start_background_maintenance(app.handle()) {
+                        log_warn!("bootstrap::maintenance", "maintenance_start_failed", e);
+                    }
+                    if let Err(e) = commands::start_retention(app.handle()) {
+                        log_warn!("bootstrap::retention", "retention_start_failed", e);
+                    }
+                    commands::start_retention_timer(app.handle().clone());
+                    commands::start_disk_monitor(app.handle().clone());
+                }
+                Err(e) => {
+                    log_error!(
+                        "bootstrap::storage",
+                        "app_data_dir_unavailable",
+                        format!("{:?}", e)
+                    );
+                }
+            }
+
             // Initialize all components asynchronously
             // AC-003.2: Start Python sidecar process
             // AC-006.1: Start WebSocket server
             tauri::async_runtime::spawn(async move {
                 let app_state = app_handle.state::<AppState>();
 
+                // 0.5. Start the local HTTP API (read-only, so also in viewer mode)
+                start_http_api(&app_handle).await;
+
+                // 0.55. Watch health for `/health` and supervisor restarts
+                commands::start_health_watchdog(app_handle.clone());
+
+                // Viewer mode: sessions only, no sidecar, audio or WebSocket server
+                if viewer_mode {
+                    log_info!("bootstrap::viewer", "viewer_mode_enabled", "");
+                    return;
+                }
+
+                // 0.6. Connect to the MQTT broker (publishes lifecycle and segments)
+                start_mqtt(&app_handle).await;
+
                 // 1. Start Python sidecar
                 let mut sidecar = PythonSidecarManager::new();
+                let workers = load_or_default(
+                    app_handle.path().app_data_dir().ok().as_deref(),
+                    transcription_workers::SETTINGS_STEM,
+                    |s: transcription_workers::TranscriptionWorkerSettings| {
+                        s.validate().map(|()| s)
+                    },
+                );
+                if workers.is_pool() {
+                    log_info!(
+                        "bootstrap::python",
+                        "worker_pool_enabled",
+                        format!("workers={}", workers.workers)
+                    );
+                }
+                sidecar.set_transcription_workers(workers);
+                let (cloud_stt, api_key) =
+                    startup_cloud_stt(app_handle.path().app_data_dir().ok().as_deref());
+                if cloud_stt.is_enabled() {
+                    log_info!(
+                        "bootstrap::python",
+                        "cloud_stt_enabled",
+                        format!(
+                            "mode={}, provider={}",
+                            cloud_stt.mode.as_str(),
+                            cloud_stt.provider.as_str()
+                        )
+                    );
+                }
+                sidecar.set_cloud_stt(cloud_stt, api_key);
                 match sidecar.start().await {
                     Ok(_) => {
                         log_info!("bootstrap::python", "sidecar_started", "");
@@ -55,8 +600,32 @@ pub fn run() {
                         match sidecar.wait_for_ready().await {
                             Ok(_) => {
                                 log_info!("bootstrap::python", "sidecar_ready", "");
+                                if let Some(protocol) = sidecar.protocol() {
+                                    log_info_details!(
+                                        "bootstrap::python",
+                                        "protocol_negotiated",
+                                        serde_json::json!(protocol)
+                                    );
+                                    app_state.set_sidecar_protocol(protocol.clone());
+                                }
+                                match sidecar.negotiate_audio_channel().await {
+                                    Ok(true) => {
+                                        log_info!("bootstrap::python", "audio_channel_open", "")
+                                    }
+                                    Ok(false) => log_info!(
+                                        "bootstrap::python",
+                                        "audio_channel_declined",
+                                        "audio stays on stdin"
+                                    ),
+                                    Err(e) => log_warn!(
+                                        "bootstrap::python",
+                                        "audio_channel_failed",
+                                        format!("{:?}", e)
+                                    ),
+                                }
                                 let sidecar_arc = Arc::new(tokio::sync::Mutex::new(sidecar));
                                 app_state.set_python_sidecar(sidecar_arc);
+                                app_state.set_sidecar_startup(app_health::SidecarStartup::Ready);
                             }
                             Err(e) => {
                                 log_error!(
@@ -64,6 +633,15 @@ pub fn run() {
                                     "sidecar_ready_timeout",
                                     format!("{:?}", e)
                                 );
+                                app_state.set_sidecar_startup(app_health::SidecarStartup::Failed(
+                                    format!("did not become ready: {:#}", e),
+                                ));
+                                commands::notify_ops_event(
+                                    &app_handle,
+                                    ops_webhooks::OpsEventKind::SidecarFailed,
+                                    &format!("Sidecar did not become ready: {:#}", e),
+                                    serde_json::json!({ "stage": "ready" }),
+                                );
                             }
                         }
                     }
@@ -73,6 +651,14 @@ pub fn run() {
                             "sidecar_start_failed",
                             format!("{:?}", e)
                         );
+                        let reason = format!("failed to start: {:#}", e);
+                        app_state.set_sidecar_startup(app_health::SidecarStartup::Failed(reason));
+                        commands::notify_ops_event(
+                            &app_handle,
+                            ops_webhooks::OpsEventKind::SidecarFailed,
+                            &format!("Sidecar failed to start: {:#}", e),
+                            serde_json::json!({ "stage": "start" }),
+                        );
                     }
                 }
 
@@ -92,11 +678,8 @@ pub fn run() {
                     }
                 }
 
-                // 2.2. Initialize audio device recorder (single/multi-input facade)
-                let recorder_factory = Arc::new(|| create_audio_adapter());
-                let recorder = AudioDeviceRecorder::new(recorder_factory);
-                let recorder_arc = Arc::new(tokio::sync::Mutex::new(recorder));
-                app_state.set_audio_recorder(recorder_arc);
+                // 2.2. Audio adapters of the per-session recorders (single/multi-input facade)
+                app_state.set_audio_recorder_factory(Arc::new(|| create_audio_adapter()));
 
                 // 2.5. Initialize audio event channel (MVP1 - STT-REQ-004.9/10/11)
                 let (audio_event_tx, audio_event_rx) = std::sync::mpsc::channel();
@@ -108,17 +691,61 @@ pub fn run() {
                     commands::monitor_audio_events(app_clone).await;
                 });
 
-                // 3. Start WebSocket server
+                // 3. Start WebSocket server on the configured port (or a fallback)
+                let app_data_dir = app_handle.path().app_data_dir().ok();
+                let dir = app_data_dir.as_deref();
+                let ports = load_or_default(
+                    dir,
+                    websocket_port::SETTINGS_STEM,
+                    |s: websocket_port::WebSocketPortSettings| s.validate().map(|()| s),
+                );
                 let mut ws_server = WebSocketServer::new_with_app_handle(app_handle.clone());
-                match ws_server.start().await {
+                ws_server.set_settings(app_state.get_websocket_settings());
+                ws_server.set_control_settings(load_or_default(
+                    dir,
+                    websocket_control::SETTINGS_STEM,
+                    |s: websocket_control::RemoteControlSettings| s.validate().map(|()| s),
+                ));
+                ws_server.set_audio_settings(&load_or_default(
+                    dir,
+                    websocket_audio::SETTINGS_STEM,
+                    Ok,
+                ));
+                match ws_server.start_in(ports.candidates()).await {
                     Ok(port) => {
                         log_info!(
                             "bootstrap::websocket",
                             "server_started",
                             format!("port={}", port)
                         );
+                        if port != ports.port {
+                            log_warn!(
+                                "bootstrap::websocket",
+                                "port_fallback",
+                                format!("preferred={} port={}", ports.port, port)
+                            );
+                        }
+                        let discovery = websocket_port::PortDiscovery {
+                            port,
+                            pid: std::process::id(),
+                            workspace: app_state.get_active_workspace(),
+                            started_at: latency::now_ms(),
+                        };
+                        if let Some(app_data_dir) = &app_data_dir {
+                            if let Err(e) =
+                                websocket_port::write_discovery(app_data_dir, &discovery)
+                            {
+                                log_warn!(
+                                    "bootstrap::websocket",
+                                    "discovery_write_failed",
+                                    format!("{:?}", e)
+                                );
+                            }
+                        }
+                        app_state.set_websocket_port(discovery);
                         let server_arc = Arc::new(tokio::sync::Mutex::new(ws_server));
                         app_state.set_websocket_server(server_arc);
+                        start_mdns(&app_handle);
                     }
                     Err(e) => {
                         log_error!(
@@ -126,28 +753,208 @@ pub fn run() {
                             "server_start_failed",
                             format!("{:?}", e)
                         );
+                        let _ = app_handle.emit("websocket_unavailable", format!("{:#}", e));
                     }
                 }
             });
 
             Ok(())
         })
-        .invoke_handler(tauri::generate_handler![
+        .invoke_handler(traced_handler(tauri::generate_handler![
             commands::start_recording,
             commands::start_recording_multi, // STTMIX Task 1.3: Multi-input support
             commands::stop_recording,
             commands::list_audio_devices,
+            commands::set_device_alias,
+            commands::load_device_alias_settings,
             commands::get_whisper_models,
             commands::cancel_reconnection,
+            commands::confirm_reconnection,
+            commands::save_reconnect_policy_settings,
+            commands::load_reconnect_policy_settings,
+            // Recording consent announcement
+            commands::save_consent_announcement_settings,
+            commands::load_consent_announcement_settings,
+            commands::list_output_devices,
+            commands::validate_loopback_routing,
             // STTMIX Task 7: Settings persistence
             commands::save_multi_input_settings,
             commands::load_multi_input_settings,
             commands::validate_multi_input_devices,
+            commands::save_wasapi_capture_settings,
+            commands::load_wasapi_capture_settings,
+            commands::save_channel_selection_settings,
+            commands::load_channel_selection_settings,
             // STTMIX Task 8: Platform info for feature gating
             commands::get_platform_info,
             // STTMIX Task 8.3: Multi-input status for UI display
             commands::get_multi_input_status,
-        ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+            // Keyword alerting
+            commands::save_keyword_alert_settings,
+            commands::load_keyword_alert_settings,
+            commands::save_docs_budget_settings,
+            commands::load_docs_budget_settings,
+            commands::get_docs_budget_status,
+            // Transcript routing
+            commands::save_routing_settings,
+            commands::load_routing_settings,
+            commands::save_transcript_webhook_settings,
+            commands::load_transcript_webhook_settings,
+            commands::save_pipeline_settings,
+            commands::load_pipeline_settings,
+            commands::save_session_id_settings,
+            commands::load_session_id_settings,
+            // Crash recovery
+            commands::get_interrupted_recording,
+            commands::get_recovered_sessions,
+            commands::get_audio_queue_metrics,
+            commands::get_ipc_lane_metrics,
+            commands::get_ipc_flow_metrics,
+            commands::get_sidecar_status,
+            commands::get_latency_metrics,
+            commands::get_memory_metrics,
+            commands::save_memory_sentinel_settings,
+            commands::load_memory_sentinel_settings,
+            commands::send_sidecar_control,
+            // Agenda tracking
+            commands::set_agenda,
+            commands::get_agenda,
+            commands::mark_agenda_item_started,
+            commands::mark_agenda_item_finished,
+            commands::get_agenda_report,
+            commands::get_question_report,
+            commands::diff_transcripts,
+            commands::export_session_chapters,
+            commands::export_session_markdown,
+            commands::export_session_bilingual_markdown,
+            commands::export_session_bilingual_docx,
+            commands::export_session_bilingual_html,
+            commands::export_session_text,
+            commands::export_session_docx,
+            commands::export_session_html,
+            commands::export_session_trimmed,
+            commands::redact_audio,
+            commands::batch_export_sessions,
+            commands::batch_retranscribe_sessions,
+            commands::save_furigana_settings,
+            commands::load_furigana_settings,
+            commands::archive_session,
+            commands::import_archive,
+            commands::search_in_session,
+            commands::get_session_activity,
+            commands::get_waveform,
+            commands::get_waveform_envelope,
+            commands::play_session_audio,
+            commands::stop_playback,
+            commands::get_maintenance_status,
+            commands::run_maintenance,
+            commands::get_jobs,
+            commands::cancel_job,
+            commands::save_job_settings,
+            commands::load_job_settings,
+            commands::log_session_event,
+            commands::get_session_timeline,
+            commands::generate_session_summary,
+            commands::get_live_summary,
+            commands::save_summary_settings,
+            commands::load_summary_settings,
+            // Session history
+            commands::get_session_list,
+            commands::get_session,
+            commands::get_transcript_page,
+            commands::compute_session_stats,
+            commands::rename_session,
+            commands::set_session_tags,
+            commands::migrate_session_format,
+            // Session trash
+            commands::delete_session,
+            commands::restore_session,
+            commands::list_trashed_sessions,
+            commands::save_trash_settings,
+            commands::load_trash_settings,
+            // Session audio format
+            commands::save_audio_format_settings,
+            commands::load_audio_format_settings,
+            // Partial text granularity
+            commands::save_partial_granularity_settings,
+            commands::load_partial_granularity_settings,
+            // Encryption
+            commands::save_encryption_settings,
+            commands::load_encryption_settings,
+            // Retention
+            commands::preview_retention,
+            commands::run_retention,
+            commands::get_last_retention_summary,
+            commands::save_retention_settings,
+            commands::load_retention_settings,
+            // Storage root
+            commands::get_storage_root,
+            commands::set_storage_root,
+            // Workspaces
+            commands::list_workspaces,
+            commands::create_workspace,
+            commands::switch_workspace,
+            // Debugging
+            commands::save_debug_settings,
+            commands::load_debug_settings,
+            commands::get_ipc_quarantine,
+            commands::save_ipc_quarantine_settings,
+            commands::load_ipc_quarantine_settings,
+            commands::save_websocket_settings,
+            commands::load_websocket_settings,
+            commands::get_websocket_delivery_stats,
+            commands::get_ws_clients,
+            commands::get_websocket_port,
+            commands::save_websocket_port_settings,
+            commands::load_websocket_port_settings,
+            commands::save_mdns_settings,
+            commands::load_mdns_settings,
+            commands::save_remote_control_settings,
+            commands::load_remote_control_settings,
+            commands::save_websocket_audio_settings,
+            commands::load_websocket_audio_settings,
+            commands::get_http_api_port,
+            commands::save_http_api_settings,
+            commands::load_http_api_settings,
+            commands::save_mqtt_settings,
+            commands::load_mqtt_settings,
+            commands::set_mqtt_password,
+            commands::has_mqtt_password,
+            commands::save_ops_webhook_settings,
+            commands::load_ops_webhook_settings,
+            commands::save_rate_limit_settings,
+            commands::load_rate_limit_settings,
+            commands::get_rate_limit_metrics,
+            commands::get_health,
+            commands::save_health_settings,
+            commands::load_health_settings,
+            commands::save_transcription_worker_settings,
+            commands::load_transcription_worker_settings,
+            commands::list_transcription_engines,
+            commands::estimate_transcription,
+            commands::save_cloud_stt_settings,
+            commands::load_cloud_stt_settings,
+            commands::set_cloud_stt_api_key,
+            commands::has_cloud_stt_api_key,
+            commands::reload_settings,
+            commands::preview_diagnostic_bundle,
+            commands::generate_diagnostic_bundle,
+            // Viewer mode
+            commands::get_viewer_mode,
+            commands::save_viewer_mode_settings,
+            commands::load_viewer_mode_settings,
+        ]))
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            if let tauri::RunEvent::Exit = event {
+                // Other instances and tools must not find a port nobody listens on
+                if let Ok(app_data_dir) = app.path().app_data_dir() {
+                    let _ = websocket_port::remove_discovery(&app_data_dir, std::process::id());
+                }
+                if let Some(advertiser) = app.state::<AppState>().take_mdns() {
+                    advertiser.stop();
+                }
+            }
+        });
 }
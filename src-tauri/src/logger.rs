@@ -1,12 +1,68 @@
 // Structured Logging Module
 // Walking Skeleton (MVP0) - JSON log output
+//
+// Correlation fields (top-level, filled automatically):
+// - session_id: active recording session (set_session_context) or details.session
+// - correlation_id: utterance/request ID from details.request (audio batch ->
+//   IPC events -> storage -> broadcast share one ID)
+// - span_id: innermost LogSpan on the current thread (one per command invoke)
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use serde_json;
-use std::time::SystemTime;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+use std::sync::{Mutex, RwLock};
+use std::time::{Instant, SystemTime};
+
+/// Session attached to every log entry while recording
+static SESSION_CONTEXT: RwLock<Option<String>> = RwLock::new(None);
+
+static NEXT_SPAN_ID: AtomicU64 = AtomicU64::new(1);
+/// Spans entered but not yet ended (all threads)
+static OPEN_SPANS: AtomicU64 = AtomicU64::new(0);
+
+/// Serialized entries kept in memory for diagnostic bundles
+const RECENT_LOG_CAPACITY: usize = 5000;
+static RECENT_LOGS: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+thread_local! {
+    /// Open spans on this thread (innermost last)
+    static SPAN_STACK: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Set (or clear) the session ID attached to all subsequent log entries
+pub fn set_session_context(session_id: Option<&str>) {
+    *SESSION_CONTEXT.write().unwrap() = session_id.map(str::to_string);
+}
+
+/// Number of spans entered but not yet ended, across all threads
+///
+/// Used by the soak test to detect spans that never end.
+pub fn open_span_count() -> u64 {
+    OPEN_SPANS.load(Ordering::SeqCst)
+}
+
+/// Most recent log entries (JSON lines, oldest first)
+pub fn recent_logs() -> Vec<String> {
+    RECENT_LOGS.lock().unwrap().iter().cloned().collect()
+}
+
+fn remember_log(json: &str) {
+    let mut recent = RECENT_LOGS.lock().unwrap();
+    if recent.len() == RECENT_LOG_CAPACITY {
+        recent.pop_front();
+    }
+    recent.push_back(json.to_string());
+}
+
+fn current_span_id() -> Option<String> {
+    SPAN_STACK.with(|stack| stack.borrow().last().cloned())
+}
 
 /// Log levels
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum LogLevel {
     Debug,
@@ -15,6 +71,18 @@ pub enum LogLevel {
     Error,
 }
 
+/// Lowest level written (debug settings); entries below it are dropped
+static MIN_LEVEL: AtomicU8 = AtomicU8::new(LogLevel::Debug as u8);
+
+pub fn set_min_level(level: LogLevel) {
+    MIN_LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+/// Whether entries at `level` are written
+pub fn is_enabled(level: LogLevel) -> bool {
+    level as u8 >= MIN_LEVEL.load(Ordering::Relaxed)
+}
+
 /// Structured log entry
 #[derive(Debug, Serialize)]
 pub struct LogEntry {
@@ -24,6 +92,12 @@ pub struct LogEntry {
     message: Option<String>,
     timestamp: u64,
     #[serde(skip_serializing_if = "Option::is_none")]
+    session_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    correlation_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    span_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     details: Option<serde_json::Value>,
 }
 
@@ -38,6 +112,9 @@ impl LogEntry {
                 .duration_since(SystemTime::UNIX_EPOCH)
                 .unwrap()
                 .as_millis() as u64,
+            session_id: SESSION_CONTEXT.read().unwrap().clone(),
+            correlation_id: None,
+            span_id: current_span_id(),
             details: None,
         }
     }
@@ -47,19 +124,91 @@ impl LogEntry {
         self
     }
 
+    /// Attach details; `session`/`request` keys are mirrored to the
+    /// top-level correlation fields
     pub fn with_details(mut self, details: serde_json::Value) -> Self {
+        if let Some(session) = details.get("session").and_then(|v| v.as_str()) {
+            self.session_id = Some(session.to_string());
+        }
+        if let Some(request) = details.get("request").and_then(|v| v.as_str()) {
+            self.correlation_id = Some(request.to_string());
+        }
         self.details = Some(details);
         self
     }
 
+    pub fn with_correlation(mut self, correlation_id: impl Into<String>) -> Self {
+        self.correlation_id = Some(correlation_id.into());
+        self
+    }
+
     pub fn log(self) {
+        if !is_enabled(self.level) {
+            return;
+        }
         match serde_json::to_string(&self) {
-            Ok(json) => println!("{}", json),
+            Ok(json) => {
+                println!("{}", json);
+                remember_log(&json);
+            }
             Err(_) => eprintln!("Failed to serialize log entry"),
         }
     }
 }
 
+/// Timed span: logs `span_start`/`span_end` (with `duration_ms`) and tags
+/// entries logged on this thread in between with its `span_id`
+///
+/// Not `Send`: a span must not be held across `.await` (the entries of other
+/// tasks on the same thread would be attributed to it).
+pub struct LogSpan {
+    id: String,
+    component: String,
+    name: String,
+    started: Instant,
+    _not_send: PhantomData<*const ()>,
+}
+
+impl LogSpan {
+    pub fn enter(component: &str, name: &str) -> Self {
+        let id = format!("span-{}", NEXT_SPAN_ID.fetch_add(1, Ordering::Relaxed));
+        SPAN_STACK.with(|stack| stack.borrow_mut().push(id.clone()));
+        OPEN_SPANS.fetch_add(1, Ordering::SeqCst);
+        LogEntry::new(LogLevel::Debug, component, "span_start")
+            .with_details(serde_json::json!({ "span": name }))
+            .log();
+        Self {
+            id,
+            component: component.to_string(),
+            name: name.to_string(),
+            started: Instant::now(),
+            _not_send: PhantomData,
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+impl Drop for LogSpan {
+    fn drop(&mut self) {
+        LogEntry::new(LogLevel::Debug, &self.component, "span_end")
+            .with_details(serde_json::json!({
+                "span": self.name,
+                "duration_ms": self.started.elapsed().as_millis() as u64
+            }))
+            .log();
+        SPAN_STACK.with(|stack| {
+            let mut stack = stack.borrow_mut();
+            if let Some(pos) = stack.iter().rposition(|id| *id == self.id) {
+                stack.remove(pos);
+            }
+        });
+        OPEN_SPANS.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
 /// Convenience macros for structured logging
 #[macro_export]
 macro_rules! log_info {
@@ -191,4 +340,50 @@ mod tests {
         assert!(json.contains("\"key\":\"value\""));
         assert!(json.contains("\"count\":42"));
     }
+
+    #[test]
+    fn test_recent_logs_keep_latest_entries() {
+        LogEntry::new(LogLevel::Info, "test", "remembered_event").log();
+        let recent = recent_logs();
+        assert!(recent.len() <= RECENT_LOG_CAPACITY);
+        assert!(recent.iter().any(|line| line.contains("remembered_event")));
+    }
+
+    #[test]
+    fn test_correlation_fields_from_details() {
+        let entry = LogEntry::new(LogLevel::Info, "test", "final_text")
+            .with_details(serde_json::json!({ "session": "session-1", "request": "audio-42" }));
+        assert_eq!(entry.session_id.as_deref(), Some("session-1"));
+        assert_eq!(entry.correlation_id.as_deref(), Some("audio-42"));
+
+        let json = serde_json::to_string(&LogEntry::new(LogLevel::Info, "test", "plain")).unwrap();
+        assert!(!json.contains("correlation_id"));
+    }
+
+    #[test]
+    fn test_span_tags_entries_on_thread() {
+        assert!(LogEntry::new(LogLevel::Info, "test", "before")
+            .span_id
+            .is_none());
+        {
+            let outer = LogSpan::enter("test", "outer");
+            assert_eq!(
+                LogEntry::new(LogLevel::Info, "test", "in_outer")
+                    .span_id
+                    .as_deref(),
+                Some(outer.id())
+            );
+            {
+                let inner = LogSpan::enter("test", "inner");
+                assert_eq!(current_span_id().as_deref(), Some(inner.id()));
+                assert!(open_span_count() >= 2);
+            }
+            assert_eq!(current_span_id().as_deref(), Some(outer.id()));
+
+            // Other threads are unaffected
+            let other = std::thread::spawn(current_span_id).join().unwrap();
+            assert!(other.is_none());
+        }
+        assert!(current_span_id().is_none());
+    }
 }
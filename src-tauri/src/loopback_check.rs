@@ -0,0 +1,187 @@
+//! Loopback Routing Check
+//!
+//! Before a meeting, verifies that audio sent to an output reaches the
+//! loopback input (BlackHole, a virtual cable, a PulseAudio monitor): a
+//! known chirp is played while the loopback input is captured, and the
+//! capture is searched for the chirp by normalized cross-correlation.
+//!
+//! The capture is the adapter's 16kHz mono feed, so the chirp is generated
+//! at 16kHz and sweeps 1-3kHz, well inside the band that survives
+//! resampling on either side.
+
+use serde::Serialize;
+use std::time::Duration;
+
+/// Sample rate of the chirp and of the captured feed
+pub const SAMPLE_RATE: u32 = 16000;
+
+/// Capture before the chirp starts (lets the input stream settle)
+pub const LEAD_IN: Duration = Duration::from_millis(300);
+
+/// Capture after the chirp ends (covers output and loopback latency)
+pub const TAIL: Duration = Duration::from_millis(500);
+
+/// Minimum normalized correlation for the chirp to count as detected
+pub const DETECTION_THRESHOLD: f32 = 0.5;
+
+/// Peak level below which the capture counts as silent
+pub const SILENCE_DBFS: f32 = -60.0;
+
+const CHIRP_MS: u32 = 300;
+const CHIRP_START_HZ: f32 = 1000.0;
+const CHIRP_END_HZ: f32 = 3000.0;
+const CHIRP_FADE_MS: u32 = 10;
+
+/// Level reported for an empty or all-zero capture
+const FLOOR_DBFS: f32 = -96.0;
+
+/// Outcome of the routing check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LoopbackStatus {
+    /// The chirp reached the loopback input: routing is correct
+    Detected,
+    /// The loopback input carried audio, but not the chirp (the output is
+    /// routed somewhere else)
+    NotDetected,
+    /// The loopback input carried only silence
+    Silent,
+}
+
+/// Result of a routing check
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LoopbackCheckResult {
+    pub status: LoopbackStatus,
+    /// Best normalized correlation with the chirp (0.0-1.0)
+    pub correlation: f32,
+    /// Delay from starting playback to the chirp in the capture (ms)
+    pub latency_ms: Option<u64>,
+    /// Peak level of the capture (dBFS)
+    pub peak_dbfs: f32,
+}
+
+/// Linear 1-3kHz sweep at [`SAMPLE_RATE`] with short fades
+pub fn chirp() -> Vec<i16> {
+    let len = (SAMPLE_RATE * CHIRP_MS / 1000) as usize;
+    let fade = (SAMPLE_RATE * CHIRP_FADE_MS / 1000) as usize;
+    let duration = len as f32 / SAMPLE_RATE as f32;
+    let sweep_rate = (CHIRP_END_HZ - CHIRP_START_HZ) / duration;
+
+    (0..len)
+        .map(|i| {
+            let t = i as f32 / SAMPLE_RATE as f32;
+            let phase =
+                2.0 * std::f32::consts::PI * (CHIRP_START_HZ * t + sweep_rate * t * t / 2.0);
+            let gain = i.min(len - 1 - i).min(fade) as f32 / fade as f32;
+            (phase.sin() * gain * 0.5 * i16::MAX as f32) as i16
+        })
+        .collect()
+}
+
+/// Look for the chirp in `captured` (16kHz mono)
+///
+/// `playback_start` is the number of captured samples when playback began.
+pub fn analyze(captured: &[i16], playback_start: usize) -> LoopbackCheckResult {
+    let peak = captured.iter().map(|s| s.unsigned_abs()).max().unwrap_or(0);
+    let peak_dbfs = if peak == 0 {
+        FLOOR_DBFS
+    } else {
+        (20.0 * (peak as f32 / 32768.0).log10()).max(FLOOR_DBFS)
+    };
+
+    let (correlation, offset) = best_match(captured, &chirp());
+    let status = if peak_dbfs < SILENCE_DBFS {
+        LoopbackStatus::Silent
+    } else if correlation >= DETECTION_THRESHOLD {
+        LoopbackStatus::Detected
+    } else {
+        LoopbackStatus::NotDetected
+    };
+    let latency_ms = (status == LoopbackStatus::Detected)
+        .then(|| offset.saturating_sub(playback_start) as u64 * 1000 / SAMPLE_RATE as u64);
+
+    LoopbackCheckResult {
+        status,
+        correlation,
+        latency_ms,
+        peak_dbfs,
+    }
+}
+
+/// Best normalized cross-correlation of `reference` within `signal`
+///
+/// Returns the absolute correlation (a polarity flip still matches) and the
+/// offset of the best match.
+fn best_match(signal: &[i16], reference: &[i16]) -> (f32, usize) {
+    let n = reference.len();
+    if n == 0 || signal.len() < n {
+        return (0.0, 0);
+    }
+    let reference: Vec<f64> = reference.iter().map(|&s| s as f64).collect();
+    let signal: Vec<f64> = signal.iter().map(|&s| s as f64).collect();
+    let reference_norm = reference.iter().map(|r| r * r).sum::<f64>().sqrt();
+
+    // Energy of the current window, updated as it slides
+    let mut window_energy: f64 = signal[..n].iter().map(|x| x * x).sum();
+    let mut best = (0.0f64, 0usize);
+    for offset in 0..=signal.len() - n {
+        if offset > 0 {
+            let (out, into) = (signal[offset - 1], signal[offset + n - 1]);
+            window_energy = (window_energy - out * out + into * into).max(0.0);
+        }
+        let denominator = reference_norm * window_energy.sqrt();
+        if denominator < 1.0 {
+            continue;
+        }
+        let dot: f64 = signal[offset..offset + n]
+            .iter()
+            .zip(&reference)
+            .map(|(x, r)| x * r)
+            .sum();
+        let correlation = (dot / denominator).abs();
+        if correlation > best.0 {
+            best = (correlation, offset);
+        }
+    }
+    (best.0.min(1.0) as f32, best.1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic noise in [-amplitude, amplitude]
+    fn noise(len: usize, amplitude: i16) -> Vec<i16> {
+        let mut state: u32 = 12345;
+        (0..len)
+            .map(|_| {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                ((state >> 16) as i32 % (amplitude as i32 * 2 + 1) - amplitude as i32) as i16
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_detects_chirp_in_capture() {
+        // Chirp at half level, 100ms after playback started, over noise
+        let mut captured = noise(16000, 500);
+        let playback_start = 3200;
+        for (i, s) in chirp().iter().enumerate() {
+            let at = playback_start + 1600 + i;
+            captured[at] = captured[at].saturating_add(s / 2);
+        }
+        let result = analyze(&captured, playback_start);
+        assert_eq!(result.status, LoopbackStatus::Detected);
+        assert!(result.correlation > 0.9);
+        assert_eq!(result.latency_ms, Some(100));
+
+        // Audio without the chirp: routed elsewhere
+        let result = analyze(&noise(16000, 5000), 0);
+        assert_eq!(result.status, LoopbackStatus::NotDetected);
+        assert_eq!(result.latency_ms, None);
+
+        // Nothing captured at all
+        assert_eq!(analyze(&vec![0; 16000], 0).status, LoopbackStatus::Silent);
+        assert_eq!(analyze(&[], 0).peak_dbfs, FLOOR_DBFS);
+    }
+}
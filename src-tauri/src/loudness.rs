@@ -0,0 +1,282 @@
+//! Session Loudness
+//!
+//! Input level metadata stored in session.json, so later analysis or
+//! re-transcription can compensate for level differences and users can see
+//! why one meeting is much quieter than another:
+//! - the gain applied to each input (multi-input mixer gain; single-input
+//!   capture is unscaled, 0 dB)
+//! - integrated loudness (ITU-R BS.1770 / EBU R128, LUFS) of the saved audio
+//! - a per-minute loudness trajectory
+//!
+//! There is no automatic gain control: the applied gains stay fixed for the
+//! whole session, so the trajectory is the measured loudness of each minute.
+//!
+//! The meter runs on the 16kHz mono session feed. K-weighting coefficients
+//! are derived for that rate from the BS.1770 analog prototypes.
+
+use serde::{Deserialize, Serialize};
+
+/// Sample rate of the session feed
+pub const SAMPLE_RATE: u32 = 16000;
+
+/// Length of one trajectory point
+pub const TRAJECTORY_INTERVAL_SECONDS: u64 = 60;
+
+/// Absolute gate (LUFS)
+const ABSOLUTE_GATE_LUFS: f64 = -70.0;
+
+/// Relative gate below the absolute-gated loudness (LU)
+const RELATIVE_GATE_LU: f64 = -10.0;
+
+/// Gating blocks are 400ms long, starting every 100ms
+const STEP_SAMPLES: usize = (SAMPLE_RATE / 10) as usize;
+const STEPS_PER_BLOCK: usize = 4;
+const STEPS_PER_INTERVAL: usize = TRAJECTORY_INTERVAL_SECONDS as usize * 10;
+
+/// Gain applied to one input while recording
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AppliedGain {
+    pub device_id: String,
+    pub gain_db: f32,
+}
+
+/// Input levels of one session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionLevels {
+    #[serde(default)]
+    pub applied_gains: Vec<AppliedGain>,
+    /// Integrated loudness (LUFS); None when the audio is silent
+    #[serde(default)]
+    pub integrated_lufs: Option<f64>,
+    /// Loudness per [`TRAJECTORY_INTERVAL_SECONDS`] (LUFS); None for silent
+    /// intervals
+    #[serde(default)]
+    pub loudness_trajectory: Vec<Option<f64>>,
+}
+
+/// Second-order IIR section (direct form I)
+#[derive(Debug, Clone, Copy)]
+struct Biquad {
+    b: [f64; 3],
+    a: [f64; 2],
+    x: [f64; 2],
+    y: [f64; 2],
+}
+
+impl Biquad {
+    fn new(b: [f64; 3], a: [f64; 2]) -> Self {
+        Self {
+            b,
+            a,
+            x: [0.0; 2],
+            y: [0.0; 2],
+        }
+    }
+
+    fn process(&mut self, input: f64) -> f64 {
+        let output = self.b[0] * input + self.b[1] * self.x[0] + self.b[2] * self.x[1]
+            - self.a[0] * self.y[0]
+            - self.a[1] * self.y[1];
+        self.x = [input, self.x[0]];
+        self.y = [output, self.y[0]];
+        output
+    }
+}
+
+/// K-weighting: high shelf (head effects) followed by the RLB high-pass
+fn k_weighting(sample_rate: f64) -> [Biquad; 2] {
+    let shelf = {
+        let (f0, gain_db, q) = (1681.974450955533, 3.999843853973347, 0.7071752369554196);
+        let k = (std::f64::consts::PI * f0 / sample_rate).tan();
+        let vh = 10f64.powf(gain_db / 20.0);
+        let vb = vh.powf(0.4996667741545416);
+        let a0 = 1.0 + k / q + k * k;
+        Biquad::new(
+            [
+                (vh + vb * k / q + k * k) / a0,
+                2.0 * (k * k - vh) / a0,
+                (vh - vb * k / q + k * k) / a0,
+            ],
+            [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+        )
+    };
+    let high_pass = {
+        let (f0, q) = (38.13547087602444, 0.5003270373238773);
+        let k = (std::f64::consts::PI * f0 / sample_rate).tan();
+        let a0 = 1.0 + k / q + k * k;
+        Biquad::new(
+            [1.0, -2.0, 1.0],
+            [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+        )
+    };
+    [shelf, high_pass]
+}
+
+/// Measures the loudness of the session feed while it is recorded
+#[derive(Debug)]
+pub struct LoudnessMeter {
+    filters: [Biquad; 2],
+    step_sum_squares: f64,
+    step_samples: usize,
+    /// Mean square of every completed 100ms step (K-weighted)
+    steps: Vec<f64>,
+    pending_byte: Option<u8>,
+}
+
+impl Default for LoudnessMeter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LoudnessMeter {
+    pub fn new() -> Self {
+        Self {
+            filters: k_weighting(SAMPLE_RATE as f64),
+            step_sum_squares: 0.0,
+            step_samples: 0,
+            steps: Vec::new(),
+            pending_byte: None,
+        }
+    }
+
+    /// Feed 16-bit LE PCM bytes (same batches as the session audio writer)
+    pub fn write_pcm_bytes(&mut self, bytes: &[u8]) {
+        let mut rest = bytes;
+        if let Some(low) = self.pending_byte.take() {
+            match rest.split_first() {
+                Some((&high, tail)) => {
+                    self.push(&[i16::from_le_bytes([low, high])]);
+                    rest = tail;
+                }
+                None => self.pending_byte = Some(low),
+            }
+        }
+        let mut chunks = rest.chunks_exact(2);
+        for chunk in chunks.by_ref() {
+            self.push(&[i16::from_le_bytes([chunk[0], chunk[1]])]);
+        }
+        if let [odd] = chunks.remainder() {
+            self.pending_byte = Some(*odd);
+        }
+    }
+
+    /// Feed samples
+    pub fn push(&mut self, samples: &[i16]) {
+        for &sample in samples {
+            let mut value = sample as f64 / 32768.0;
+            for filter in &mut self.filters {
+                value = filter.process(value);
+            }
+            self.step_sum_squares += value * value;
+            self.step_samples += 1;
+            if self.step_samples == STEP_SAMPLES {
+                self.steps.push(self.step_sum_squares / STEP_SAMPLES as f64);
+                self.step_sum_squares = 0.0;
+                self.step_samples = 0;
+            }
+        }
+    }
+
+    /// Integrated loudness of everything fed so far (LUFS)
+    pub fn integrated_lufs(&self) -> Option<f64> {
+        gated_loudness(&block_powers(&self.steps))
+    }
+
+    /// Loudness of each trajectory interval (a trailing partial interval
+    /// included)
+    pub fn trajectory(&self) -> Vec<Option<f64>> {
+        let blocks = block_powers(&self.steps);
+        // Block i starts at step i
+        blocks
+            .chunks(STEPS_PER_INTERVAL)
+            .map(gated_loudness)
+            .collect()
+    }
+
+    /// Session levels with the measured loudness
+    pub fn levels(&self, applied_gains: Vec<AppliedGain>) -> SessionLevels {
+        SessionLevels {
+            applied_gains,
+            integrated_lufs: self.integrated_lufs(),
+            loudness_trajectory: self.trajectory(),
+        }
+    }
+}
+
+/// Mean square of every 400ms gating block (75% overlap)
+fn block_powers(steps: &[f64]) -> Vec<f64> {
+    steps
+        .windows(STEPS_PER_BLOCK)
+        .map(|window| window.iter().sum::<f64>() / STEPS_PER_BLOCK as f64)
+        .collect()
+}
+
+fn loudness(power: f64) -> f64 {
+    -0.691 + 10.0 * power.log10()
+}
+
+/// Gated loudness of `blocks` (BS.1770-4 absolute and relative gates)
+fn gated_loudness(blocks: &[f64]) -> Option<f64> {
+    let mean_above = |threshold: f64| {
+        let above: Vec<f64> = blocks
+            .iter()
+            .copied()
+            .filter(|&power| power > 0.0 && loudness(power) > threshold)
+            .collect();
+        (!above.is_empty()).then(|| above.iter().sum::<f64>() / above.len() as f64)
+    };
+    let relative_gate = loudness(mean_above(ABSOLUTE_GATE_LUFS)?) + RELATIVE_GATE_LU;
+    mean_above(relative_gate.max(ABSOLUTE_GATE_LUFS)).map(|power| round1(loudness(power)))
+}
+
+fn round1(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(seconds: usize, amplitude: f64) -> Vec<i16> {
+        (0..seconds * SAMPLE_RATE as usize)
+            .map(|i| {
+                let t = i as f64 / SAMPLE_RATE as f64;
+                ((2.0 * std::f64::consts::PI * 1000.0 * t).sin() * amplitude * 32767.0) as i16
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_integrated_loudness_and_trajectory() {
+        // 1kHz sine at -20dBFS peak reads -23 LUFS
+        let mut meter = LoudnessMeter::new();
+        let pcm: Vec<u8> = sine(10, 0.1).iter().flat_map(|s| s.to_le_bytes()).collect();
+        // Odd-sized batches must not shift the samples
+        for batch in pcm.chunks(333) {
+            meter.write_pcm_bytes(batch);
+        }
+        let integrated = meter.integrated_lufs().unwrap();
+        assert!((integrated + 23.0).abs() <= 0.3, "{}", integrated);
+
+        // Silence is gated out of the integrated loudness but shows in the
+        // trajectory
+        let mut meter = LoudnessMeter::new();
+        meter.push(&sine(60, 0.1));
+        meter.push(&vec![0; 70 * SAMPLE_RATE as usize]);
+        meter.push(&sine(5, 0.01));
+        let levels = meter.levels(vec![AppliedGain {
+            device_id: "mic".to_string(),
+            gain_db: -6.0,
+        }]);
+        assert!((levels.integrated_lufs.unwrap() - integrated).abs() <= 0.2);
+        let trajectory = &levels.loudness_trajectory;
+        assert_eq!(trajectory.len(), 3);
+        assert!((trajectory[0].unwrap() - integrated).abs() <= 0.2);
+        // Only the click of the sine stopping is left above the gate
+        assert!(trajectory[1].is_none_or(|lufs| lufs < -60.0));
+        assert!((trajectory[2].unwrap() - (integrated - 20.0)).abs() <= 0.3);
+
+        assert_eq!(LoudnessMeter::new().integrated_lufs(), None);
+    }
+}
@@ -2,5 +2,17 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
+    // Hidden soak test mode (see soak.rs)
+    if std::env::args().nth(1).as_deref() == Some("--soak") {
+        std::process::exit(meeting_minutes_automator_lib::soak::run_from_args(
+            std::env::args().skip(2),
+        ));
+    }
+    // Hidden test audio generator (see test_audio.rs)
+    if std::env::args().nth(1).as_deref() == Some("--gen-test-audio") {
+        std::process::exit(meeting_minutes_automator_lib::test_audio::run_from_args(
+            std::env::args().skip(2),
+        ));
+    }
     meeting_minutes_automator_lib::run()
 }
@@ -0,0 +1,304 @@
+//! Background Maintenance
+//!
+//! Builds derived files that legacy sessions are missing (waveform peaks,
+//! activity timeline) once, as a low-priority background job. Work pauses
+//! while a recording is active and resumes afterwards; progress is reported
+//! through a callback and `MaintenanceService::status`.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Pause between jobs so maintenance never competes with the UI
+const JOB_INTERVAL: Duration = Duration::from_millis(100);
+/// Poll interval while paused
+const PAUSE_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Derived file that can be (re)built for a session
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MaintenanceJob {
+    /// `waveform.dat` (see `waveform`)
+    Waveform,
+    /// `activity.bin` (see `activity`)
+    Activity,
+}
+
+impl MaintenanceJob {
+    pub const ALL: [MaintenanceJob; 2] = [MaintenanceJob::Waveform, MaintenanceJob::Activity];
+
+    /// Whether the session is missing this job's output
+    pub fn is_needed(&self, session_dir: &Path) -> bool {
+        if crate::storage::session_audio_path(session_dir).is_none() {
+            return false;
+        }
+        match self {
+            MaintenanceJob::Waveform => !session_dir
+                .join(crate::waveform::WAVEFORM_FILENAME)
+                .exists(),
+            MaintenanceJob::Activity => !session_dir
+                .join(crate::activity::ACTIVITY_FILENAME)
+                .exists(),
+        }
+    }
+
+    pub fn run(&self, session_dir: &Path) -> Result<()> {
+        match self {
+            MaintenanceJob::Waveform => crate::waveform::generate(session_dir).map(|_| ()),
+            MaintenanceJob::Activity => {
+                let points = crate::activity::compute_from_session_audio(session_dir)?;
+                crate::activity::write_activity(session_dir, &points)
+            }
+        }
+    }
+}
+
+/// Pending unit of work
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedJob {
+    pub session_id: String,
+    pub session_dir: PathBuf,
+    pub job: MaintenanceJob,
+}
+
+/// List jobs needed by the sessions under `recordings_dir`
+///
+/// `skip_session` (the session being recorded) is left alone.
+pub fn plan(recordings_dir: &Path, skip_session: Option<&str>) -> Result<Vec<PlannedJob>> {
+    if !recordings_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut sessions: Vec<(String, PathBuf)> = std::fs::read_dir(recordings_dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .filter_map(|path| {
+            let id = path.file_name()?.to_str()?.to_string();
+            Some((id, path))
+        })
+        .filter(|(id, _)| Some(id.as_str()) != skip_session)
+        // Hidden directories are not sessions (e.g. the `.trash` area)
+        .filter(|(id, _)| !id.starts_with('.'))
+        .collect();
+    sessions.sort();
+
+    Ok(sessions
+        .into_iter()
+        .flat_map(|(session_id, session_dir)| {
+            MaintenanceJob::ALL
+                .into_iter()
+                .filter(|job| job.is_needed(&session_dir))
+                .map(|job| PlannedJob {
+                    session_id: session_id.clone(),
+                    session_dir: session_dir.clone(),
+                    job,
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect())
+}
+
+// ============================================================================
+// Service
+// ============================================================================
+
+/// Service state
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MaintenancePhase {
+    #[default]
+    Idle,
+    Running,
+    /// Waiting for the recording to end
+    Paused,
+}
+
+/// Progress of the current (or last) maintenance run
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MaintenanceStatus {
+    pub phase: MaintenancePhase,
+    pub total: usize,
+    pub completed: usize,
+    pub failed: usize,
+    pub current_session: Option<String>,
+    pub current_job: Option<MaintenanceJob>,
+    pub last_error: Option<String>,
+}
+
+/// Tracks maintenance runs, one at a time
+#[derive(Debug, Default)]
+pub struct MaintenanceService {
+    status: Mutex<MaintenanceStatus>,
+    running: AtomicBool,
+}
+
+impl MaintenanceService {
+    pub fn status(&self) -> MaintenanceStatus {
+        self.status.lock().unwrap().clone()
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    /// Claim the service for a run of `total` jobs; false if one is active
+    pub fn try_begin(&self, total: usize) -> bool {
+        if self.running.swap(true, Ordering::SeqCst) {
+            return false;
+        }
+        *self.status.lock().unwrap() = MaintenanceStatus {
+            phase: MaintenancePhase::Running,
+            total,
+            ..Default::default()
+        };
+        true
+    }
+
+    /// Work through `jobs` on the current thread (after `try_begin`)
+    ///
+    /// `is_busy` is checked before each job (e.g. "recording in progress")
+    /// and pauses the run while true; `is_cancelled` ends it early.
+    /// `on_progress` is called after every status change.
+    pub fn run<B, C, P>(&self, jobs: Vec<PlannedJob>, is_busy: B, is_cancelled: C, on_progress: P)
+    where
+        B: Fn() -> bool,
+        C: Fn() -> bool,
+        P: Fn(&MaintenanceStatus),
+    {
+        on_progress(&self.status());
+        for planned in jobs {
+            if is_busy() {
+                on_progress(&self.update(|s| s.phase = MaintenancePhase::Paused));
+                while is_busy() && !is_cancelled() {
+                    std::thread::sleep(PAUSE_POLL_INTERVAL);
+                }
+            }
+            if is_cancelled() {
+                break;
+            }
+            on_progress(&self.update(|s| {
+                s.phase = MaintenancePhase::Running;
+                s.current_session = Some(planned.session_id.clone());
+                s.current_job = Some(planned.job);
+            }));
+
+            // Re-check: the session may have been processed on demand meanwhile
+            let result = if planned.job.is_needed(&planned.session_dir) {
+                planned.job.run(&planned.session_dir)
+            } else {
+                Ok(())
+            };
+            on_progress(&self.update(|s| match &result {
+                Ok(()) => s.completed += 1,
+                Err(e) => {
+                    s.failed += 1;
+                    s.last_error = Some(format!(
+                        "{} ({:?}): {:#}",
+                        planned.session_id, planned.job, e
+                    ));
+                }
+            }));
+            std::thread::sleep(JOB_INTERVAL);
+        }
+        let status = self.update(|s| {
+            s.phase = MaintenancePhase::Idle;
+            s.current_session = None;
+            s.current_job = None;
+        });
+        self.running.store(false, Ordering::SeqCst);
+        on_progress(&status);
+    }
+
+    fn update(&self, apply: impl FnOnce(&mut MaintenanceStatus)) -> MaintenanceStatus {
+        let mut status = self.status.lock().unwrap();
+        apply(&mut status);
+        status.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_session(recordings: &Path, id: &str, with_waveform: bool) {
+        let dir = recordings.join(id);
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut wav = vec![0u8; 44];
+        wav.extend((0..20_000i16).flat_map(|i| (i % 1000).to_le_bytes()));
+        std::fs::write(dir.join("audio.wav"), wav).unwrap();
+        if with_waveform {
+            crate::waveform::generate(&dir).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_plan_lists_missing_outputs() {
+        let temp_dir = TempDir::new().unwrap();
+        let recordings = temp_dir.path().join("recordings");
+        write_session(&recordings, "a", true);
+        write_session(&recordings, "b", false);
+        write_session(&recordings, "live", false);
+        std::fs::create_dir_all(recordings.join("empty")).unwrap();
+
+        let jobs: Vec<(String, MaintenanceJob)> = plan(&recordings, Some("live"))
+            .unwrap()
+            .into_iter()
+            .map(|j| (j.session_id, j.job))
+            .collect();
+        assert_eq!(
+            jobs,
+            vec![
+                ("a".to_string(), MaintenanceJob::Activity),
+                ("b".to_string(), MaintenanceJob::Waveform),
+                ("b".to_string(), MaintenanceJob::Activity),
+            ]
+        );
+        assert!(plan(&temp_dir.path().join("missing"), None)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_service_runs_jobs_and_reports_progress() {
+        let temp_dir = TempDir::new().unwrap();
+        let recordings = temp_dir.path().join("recordings");
+        write_session(&recordings, "a", false);
+        let jobs = plan(&recordings, None).unwrap();
+
+        let service = MaintenanceService::default();
+        assert!(service.try_begin(jobs.len()));
+        assert!(!service.try_begin(0));
+        let (tx, rx) = std::sync::mpsc::channel();
+        service.run(
+            jobs,
+            || false,
+            || false,
+            move |status| {
+                let _ = tx.send(status.clone());
+            },
+        );
+        assert!(!service.is_running());
+
+        let updates: Vec<MaintenanceStatus> = rx.iter().collect();
+        let last = updates.last().unwrap();
+        assert_eq!(last.phase, MaintenancePhase::Idle);
+        assert_eq!((last.total, last.completed, last.failed), (2, 2, 0));
+        assert!(updates
+            .iter()
+            .any(|s| s.current_job == Some(MaintenanceJob::Waveform)));
+        assert!(plan(&recordings, None).unwrap().is_empty());
+
+        // Cancelled runs stop before the next job
+        write_session(&recordings, "b", false);
+        let jobs = plan(&recordings, None).unwrap();
+        assert!(service.try_begin(jobs.len()));
+        service.run(jobs, || false, || true, |_| {});
+        assert_eq!(service.status().completed, 0);
+        assert_eq!(plan(&recordings, None).unwrap().len(), 2);
+    }
+}
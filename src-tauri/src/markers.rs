@@ -0,0 +1,84 @@
+//! Session Markers
+//!
+//! Time-coded markers within a recording session (created by routing rules
+//! or by the user). Timestamps are milliseconds relative to session start.
+//!
+//! Persisted as JSON Lines to `recordings/<session_id>/markers.jsonl`.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::Path;
+
+const MARKERS_FILENAME: &str = "markers.jsonl";
+
+/// Single session marker
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionMarker {
+    /// Session-relative timestamp (ms)
+    pub timestamp_ms: u64,
+    /// Marker label shown in exports
+    pub label: String,
+    /// Origin of the marker (e.g. "routing:<rule name>", "user")
+    pub source: String,
+    /// Segment text that produced the marker, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+/// Append marker to `<session_dir>/markers.jsonl`
+pub fn append_marker(session_dir: &Path, marker: &SessionMarker) -> Result<()> {
+    let path = session_dir.join(MARKERS_FILENAME);
+    let mut line = serde_json::to_string(marker).context("Failed to serialize marker")?;
+    line.push('\n');
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open markers file: {:?}", path))?;
+    file.write_all(line.as_bytes())?;
+    file.flush()?;
+    Ok(())
+}
+
+/// Load all markers of a session (empty if none were recorded)
+pub fn load_markers(session_dir: &Path) -> Result<Vec<SessionMarker>> {
+    let path = session_dir.join(MARKERS_FILENAME);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read markers file: {:?}", path))?;
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| serde_json::from_str(line).context("Failed to parse marker"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_append_and_load_markers() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(load_markers(temp_dir.path()).unwrap().is_empty());
+
+        let marker = SessionMarker {
+            timestamp_ms: 1_500,
+            label: "Action item".to_string(),
+            source: "routing:todo".to_string(),
+            text: Some("TODO: send slides".to_string()),
+        };
+        append_marker(temp_dir.path(), &marker).unwrap();
+        append_marker(temp_dir.path(), &marker).unwrap();
+
+        let loaded = load_markers(temp_dir.path()).unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[0], marker);
+    }
+}
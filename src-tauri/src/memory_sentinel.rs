@@ -0,0 +1,308 @@
+//! Memory-leak sentinels for long-lived buffers
+//!
+//! While recording, the large owned buffers (audio queue, WebSocket client
+//! queues, transcript state, mixer input buffers) are sampled periodically.
+//! A structure that keeps growing over `growth_samples` consecutive samples
+//! and ends above its configured bound raises one warning; the warning is
+//! re-armed once the structure shrinks back under the bound.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+
+/// Structure names reported in samples and metrics
+pub const AUDIO_QUEUE: &str = "audio_queue";
+pub const WEBSOCKET_QUEUES: &str = "websocket_queues";
+pub const MIXER_BUFFERS: &str = "mixer_buffers";
+pub const QUESTIONS: &str = "questions";
+pub const LIVE_SUMMARY: &str = "live_summary";
+pub const AGENDA: &str = "agenda";
+pub const LATENCY_TRACKER: &str = "latency_tracker";
+
+/// Sentinel configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MemorySentinelSettings {
+    pub enabled: bool,
+    /// Seconds between samples during recording
+    pub sample_interval_secs: u64,
+    /// Consecutive growing samples before a structure counts as leaking
+    pub growth_samples: usize,
+    /// Bound for structures without an entry in `bounds` (bytes)
+    pub default_bound_bytes: u64,
+    /// Per-structure bounds (bytes), keyed by structure name
+    pub bounds: BTreeMap<String, u64>,
+}
+
+impl Default for MemorySentinelSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            sample_interval_secs: 30,
+            growth_samples: 10,
+            default_bound_bytes: 4 * 1024 * 1024,
+            bounds: BTreeMap::new(),
+        }
+    }
+}
+
+impl MemorySentinelSettings {
+    pub fn bound_for(&self, structure: &str) -> u64 {
+        self.bounds
+            .get(structure)
+            .copied()
+            .unwrap_or(self.default_bound_bytes)
+    }
+}
+
+/// Raised when a structure grew monotonically beyond its bound
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MemoryWarning {
+    pub structure: String,
+    pub bytes: u64,
+    pub bound_bytes: u64,
+    /// Growth over the observed samples (bytes)
+    pub growth_bytes: u64,
+    pub samples: usize,
+    pub at_ms: u64,
+}
+
+/// Per-structure accounting in `MemorySnapshot`
+#[derive(Debug, Clone, Serialize)]
+pub struct StructureUsage {
+    pub name: String,
+    pub bytes: u64,
+    pub peak_bytes: u64,
+    pub bound_bytes: u64,
+    /// Samples in a row without shrinking (0 = shrank or unchanged at last sample)
+    pub growing_samples: usize,
+}
+
+/// Buffer accounting of the active (or last) session
+#[derive(Debug, Clone, Serialize)]
+pub struct MemorySnapshot {
+    pub sampled_at_ms: Option<u64>,
+    pub sample_count: u64,
+    pub total_bytes: u64,
+    pub structures: Vec<StructureUsage>,
+    pub warnings: Vec<MemoryWarning>,
+}
+
+#[derive(Debug, Default)]
+struct StructureTrack {
+    history: VecDeque<u64>,
+    peak_bytes: u64,
+    warned: bool,
+}
+
+impl StructureTrack {
+    fn bytes(&self) -> u64 {
+        self.history.back().copied().unwrap_or(0)
+    }
+
+    /// Length of the trailing non-decreasing run with net growth
+    fn growing_samples(&self) -> usize {
+        let mut run = 0;
+        for (prev, next) in self
+            .history
+            .iter()
+            .rev()
+            .skip(1)
+            .zip(self.history.iter().rev())
+        {
+            if next < prev {
+                break;
+            }
+            run += 1;
+        }
+        let first = self.history[self.history.len() - 1 - run];
+        if self.bytes() > first {
+            run
+        } else {
+            0
+        }
+    }
+}
+
+/// Samples buffer sizes and detects monotonic growth
+#[derive(Debug, Default)]
+pub struct MemorySentinel {
+    settings: MemorySentinelSettings,
+    structures: BTreeMap<String, StructureTrack>,
+    sample_count: u64,
+    sampled_at_ms: Option<u64>,
+    warnings: Vec<MemoryWarning>,
+}
+
+impl MemorySentinel {
+    pub fn new(settings: MemorySentinelSettings) -> Self {
+        Self {
+            settings,
+            ..Default::default()
+        }
+    }
+
+    pub fn settings(&self) -> &MemorySentinelSettings {
+        &self.settings
+    }
+
+    pub fn set_settings(&mut self, settings: MemorySentinelSettings) {
+        self.settings = settings;
+    }
+
+    /// Forget all samples (new session); settings are kept
+    pub fn reset(&mut self) {
+        self.structures.clear();
+        self.sample_count = 0;
+        self.sampled_at_ms = None;
+        self.warnings.clear();
+    }
+
+    /// Record one sample of per-structure byte counts
+    ///
+    /// Returns the warnings raised by this sample. Structures missing from
+    /// a sample keep their previous history.
+    pub fn observe(&mut self, sample: &[(&str, u64)], at_ms: u64) -> Vec<MemoryWarning> {
+        let window = self.settings.growth_samples.max(1);
+        let mut raised = Vec::new();
+
+        for &(name, bytes) in sample {
+            let bound = self.settings.bound_for(name);
+            let track = self.structures.entry(name.to_string()).or_default();
+            track.history.push_back(bytes);
+            while track.history.len() > window + 1 {
+                track.history.pop_front();
+            }
+            track.peak_bytes = track.peak_bytes.max(bytes);
+
+            if bytes <= bound {
+                track.warned = false;
+                continue;
+            }
+            let run = track.growing_samples();
+            if self.settings.enabled && !track.warned && run >= window {
+                track.warned = true;
+                raised.push(MemoryWarning {
+                    structure: name.to_string(),
+                    bytes,
+                    bound_bytes: bound,
+                    growth_bytes: bytes - track.history[track.history.len() - 1 - run],
+                    samples: run,
+                    at_ms,
+                });
+            }
+        }
+
+        self.sample_count += 1;
+        self.sampled_at_ms = Some(at_ms);
+        self.warnings.extend(raised.iter().cloned());
+        raised
+    }
+
+    pub fn snapshot(&self) -> MemorySnapshot {
+        let structures: Vec<StructureUsage> = self
+            .structures
+            .iter()
+            .map(|(name, track)| StructureUsage {
+                name: name.clone(),
+                bytes: track.bytes(),
+                peak_bytes: track.peak_bytes,
+                bound_bytes: self.settings.bound_for(name),
+                growing_samples: track.growing_samples(),
+            })
+            .collect();
+        MemorySnapshot {
+            sampled_at_ms: self.sampled_at_ms,
+            sample_count: self.sample_count,
+            total_bytes: structures.iter().map(|s| s.bytes).sum(),
+            structures,
+            warnings: self.warnings.clone(),
+        }
+    }
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+pub const SETTINGS_STEM: &str = "memory_sentinel";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sentinel(bound: u64, growth_samples: usize) -> MemorySentinel {
+        MemorySentinel::new(MemorySentinelSettings {
+            growth_samples,
+            default_bound_bytes: bound,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_warns_once_on_monotonic_growth_beyond_bound() {
+        let mut sentinel = sentinel(1_000, 3);
+
+        // Growing but still under the bound
+        for (i, bytes) in [200, 400, 600, 800].into_iter().enumerate() {
+            assert!(sentinel.observe(&[(QUESTIONS, bytes)], i as u64).is_empty());
+        }
+        // Crosses the bound after 3+ growing samples
+        let warnings = sentinel.observe(&[(QUESTIONS, 1_200)], 4);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].structure, QUESTIONS);
+        assert_eq!(warnings[0].growth_bytes, 1_200 - 400);
+        assert_eq!(warnings[0].samples, 3);
+
+        // No repeat while it stays above the bound
+        assert!(sentinel.observe(&[(QUESTIONS, 1_500)], 5).is_empty());
+
+        // Re-armed after dropping back under the bound
+        sentinel.observe(&[(QUESTIONS, 100)], 6);
+        for (i, bytes) in [1_100, 1_200, 1_300].into_iter().enumerate() {
+            let warnings = sentinel.observe(&[(QUESTIONS, bytes)], 7 + i as u64);
+            assert_eq!(warnings.len(), usize::from(i == 2));
+        }
+
+        let snapshot = sentinel.snapshot();
+        assert_eq!(snapshot.warnings.len(), 2);
+        assert_eq!(snapshot.structures[0].peak_bytes, 1_500);
+    }
+
+    #[test]
+    fn test_no_warning_for_fluctuating_or_flat_usage() {
+        let mut sentinel = sentinel(1_000, 3);
+        for (i, bytes) in [2_000, 2_500, 2_100, 2_600, 2_200, 2_200, 2_200, 2_200]
+            .into_iter()
+            .enumerate()
+        {
+            assert!(sentinel
+                .observe(&[(AUDIO_QUEUE, bytes)], i as u64)
+                .is_empty());
+        }
+        assert_eq!(sentinel.snapshot().structures[0].growing_samples, 0);
+    }
+
+    #[test]
+    fn test_per_structure_bounds_and_reset() {
+        let mut settings = MemorySentinelSettings {
+            growth_samples: 1,
+            ..Default::default()
+        };
+        settings.bounds.insert(MIXER_BUFFERS.to_string(), 10);
+        let mut sentinel = MemorySentinel::new(settings);
+
+        sentinel.observe(&[(MIXER_BUFFERS, 5), (AGENDA, 5)], 0);
+        let warnings = sentinel.observe(&[(MIXER_BUFFERS, 50), (AGENDA, 50)], 1);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].structure, MIXER_BUFFERS);
+
+        let snapshot = sentinel.snapshot();
+        assert_eq!(snapshot.total_bytes, 100);
+        assert_eq!(snapshot.sample_count, 2);
+
+        sentinel.reset();
+        let snapshot = sentinel.snapshot();
+        assert!(snapshot.structures.is_empty() && snapshot.warnings.is_empty());
+        assert_eq!(sentinel.settings().bound_for(MIXER_BUFFERS), 10);
+    }
+}
@@ -0,0 +1,127 @@
+//! Message IDs
+//!
+//! IDs of outgoing messages (WebSocket messages and notifications, sidecar
+//! control requests) have the form `{kind}-{scope}-{time}{seq}`, e.g.
+//! `ws-20250101-093000-01JGFM9WT00003`:
+//!
+//! - `kind`: message family (`ws`, `ctl`)
+//! - `scope`: session ID, or a fixed scope outside sessions (`sidecar`)
+//! - `time`: epoch millis, 10 Crockford base32 digits (as in ULID)
+//! - `seq`: 4 base32 digits, counting up within the same millisecond
+//!
+//! IDs from one generator are unique and sort in issue order: the clock is
+//! never allowed to run backwards, and a millisecond whose sequence space
+//! is exhausted borrows the next one. Audio request IDs to the sidecar have
+//! their own session-scoped format (see `request_id`).
+
+use once_cell::sync::Lazy;
+use std::sync::Mutex;
+
+/// WebSocket messages (transcriptions, alerts, notifications, ...)
+pub const WEBSOCKET: &str = "ws";
+
+/// Sidecar control requests
+pub const CONTROL: &str = "ctl";
+
+const CROCKFORD: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const TIME_DIGITS: usize = 10;
+const SEQ_DIGITS: usize = 4;
+const MAX_SEQ: u32 = (1 << (5 * SEQ_DIGITS)) - 1;
+
+/// Monotonic ID generator
+#[derive(Default)]
+pub struct MessageIdGenerator {
+    /// Time and sequence of the last issued ID
+    last: Mutex<(u64, u32)>,
+}
+
+impl MessageIdGenerator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Next ID at the current time
+    pub fn next(&self, kind: &str, scope: &str) -> String {
+        let now_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+        self.next_at(now_ms, kind, scope)
+    }
+
+    /// Next ID for a clock reading of `now_ms`
+    pub fn next_at(&self, now_ms: u64, kind: &str, scope: &str) -> String {
+        let (time_ms, seq) = {
+            let mut last = self.last.lock().unwrap();
+            *last = match *last {
+                (time, seq) if now_ms <= time && seq < MAX_SEQ => (time, seq + 1),
+                (time, _) if now_ms <= time => (time + 1, 0),
+                _ => (now_ms, 0),
+            };
+            *last
+        };
+
+        let mut id = String::with_capacity(kind.len() + scope.len() + 16);
+        id.push_str(kind);
+        id.push('-');
+        id.push_str(scope);
+        id.push('-');
+        push_base32(&mut id, time_ms, TIME_DIGITS);
+        push_base32(&mut id, seq as u64, SEQ_DIGITS);
+        id
+    }
+}
+
+/// Process-wide generator
+static GENERATOR: Lazy<MessageIdGenerator> = Lazy::new(MessageIdGenerator::new);
+
+/// Next ID from the process-wide generator
+pub fn next_id(kind: &str, scope: &str) -> String {
+    GENERATOR.next(kind, scope)
+}
+
+/// Append `value` as `digits` Crockford base32 digits (most significant first)
+fn push_base32(out: &mut String, value: u64, digits: usize) {
+    for i in (0..digits).rev() {
+        let digit = (value >> (5 * i)) & 0x1F;
+        out.push(CROCKFORD[digit as usize] as char);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ids_are_unique_and_ordered_within_a_millisecond() {
+        let generator = MessageIdGenerator::new();
+        let first = generator.next_at(1_700_000_000_000, WEBSOCKET, "s1");
+        let second = generator.next_at(1_700_000_000_000, WEBSOCKET, "s1");
+        assert_eq!(first, "ws-s1-01HF7YAT000000");
+        assert_eq!(second, "ws-s1-01HF7YAT000001");
+
+        // A clock step backwards keeps counting from the last time
+        let third = generator.next_at(1_699_999_999_000, WEBSOCKET, "s1");
+        assert_eq!(third, "ws-s1-01HF7YAT000002");
+        let later = generator.next_at(1_700_000_000_001, WEBSOCKET, "s1");
+        assert_eq!(later, "ws-s1-01HF7YAT010000");
+
+        let mut ids = vec![later.clone(), first.clone(), third.clone(), second.clone()];
+        ids.sort();
+        assert_eq!(ids, vec![first, second, third, later]);
+    }
+
+    #[test]
+    fn test_exhausted_sequence_borrows_next_millisecond() {
+        let generator = MessageIdGenerator::new();
+        *generator.last.lock().unwrap() = (5, MAX_SEQ);
+        assert_eq!(
+            generator.next_at(5, CONTROL, "sidecar"),
+            "ctl-sidecar-00000000060000"
+        );
+        assert_eq!(
+            generator.next_at(6, CONTROL, "sidecar"),
+            "ctl-sidecar-00000000060001"
+        );
+    }
+}
@@ -0,0 +1,724 @@
+//! MQTT Publishing
+//!
+//! Publishes session lifecycle events and final segments to an MQTT broker
+//! for home/office automation (e.g. a "recording" sign at the meeting room
+//! door) or a company knowledge pipeline. Topics, under `topic_prefix`:
+//!
+//! - `<prefix>/status` — `recording`, `idle` or `offline` (retained; the
+//!   broker publishes `offline` as last will when the app disappears)
+//! - `<prefix>/session/started`, `<prefix>/session/stopped` — JSON
+//! - `<prefix>/transcript` — JSON per final segment (only through the
+//!   pipeline's `mqtt` stage, so it sees redacted text)
+//!
+//! A minimal MQTT 3.1.1 client: plain TCP or TLS (system-independent
+//! Mozilla roots), optional username/password, QoS 0 or 1. With QoS 1 every
+//! message waits for its PUBACK and is sent again after a reconnect, so
+//! delivery is at-least-once. Messages queue while the broker is away
+//! (up to `QUEUE_CAPACITY`); the client reconnects with backoff.
+//!
+//! The password is kept in the OS keychain (`set_mqtt_password`), not in
+//! the settings file.
+//!
+//! Settings are machine-wide (not per workspace); persisted to
+//! `settings/mqtt.json` in app data directory.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+use crate::settings_file;
+
+/// Keychain service of the broker password
+const KEYCHAIN_SERVICE: &str = "meeting-minutes-automator";
+
+/// Events waiting for the broker
+pub const QUEUE_CAPACITY: usize = 1000;
+
+/// Keep alive announced in CONNECT (a ping is sent at half of it)
+const KEEP_ALIVE: Duration = Duration::from_secs(60);
+
+/// Time for the connection, CONNACK and every PUBACK
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(10);
+
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Largest packet accepted from the broker (only CONNACK, PUBACK and
+/// PINGRESP are expected)
+const MAX_PACKET_LEN: usize = 64 * 1024;
+
+/// MQTT configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MqttSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Broker host name
+    #[serde(default)]
+    pub host: String,
+    #[serde(default = "default_port")]
+    pub port: u16,
+    /// Connect with TLS (usually port 8883)
+    #[serde(default)]
+    pub tls: bool,
+    /// Client identifier (empty: generated per connection)
+    #[serde(default)]
+    pub client_id: String,
+    #[serde(default)]
+    pub username: Option<String>,
+    /// From the keychain (`load_settings_with_password`); never serialized.
+    /// Still read from older files so it can be moved to the keychain.
+    #[serde(default, skip_serializing)]
+    pub password: Option<String>,
+    /// Prefix of every topic
+    #[serde(default = "default_topic_prefix")]
+    pub topic_prefix: String,
+    /// QoS of every message (0 or 1)
+    #[serde(default)]
+    pub qos: u8,
+    /// Settings version for future migrations
+    #[serde(default = "default_version")]
+    pub version: u32,
+}
+
+fn default_port() -> u16 {
+    1883
+}
+
+fn default_topic_prefix() -> String {
+    "meeting-minutes".to_string()
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+impl Default for MqttSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: String::new(),
+            port: default_port(),
+            tls: false,
+            client_id: String::new(),
+            username: None,
+            password: None,
+            topic_prefix: default_topic_prefix(),
+            qos: 0,
+            version: 1,
+        }
+    }
+}
+
+impl MqttSettings {
+    pub fn validate(&self) -> Result<()> {
+        if self.enabled && self.host.trim().is_empty() {
+            anyhow::bail!("Broker host is required");
+        }
+        if self.qos > 1 {
+            anyhow::bail!("QoS must be 0 or 1");
+        }
+        if self.password.is_some() && self.username.is_none() {
+            anyhow::bail!("A password needs a username");
+        }
+        let prefix = &self.topic_prefix;
+        if prefix.is_empty()
+            || prefix.starts_with('/')
+            || prefix.ends_with('/')
+            || prefix.contains(['+', '#', '\0'])
+        {
+            anyhow::bail!(
+                "Topic prefix must be non-empty, without wildcards or leading/trailing '/'"
+            );
+        }
+        Ok(())
+    }
+
+    pub fn topic(&self, suffix: &str) -> String {
+        format!("{}/{}", self.topic_prefix, suffix)
+    }
+}
+
+/// Event to publish
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MqttEvent {
+    SessionStarted {
+        session_id: String,
+    },
+    SessionStopped {
+        session_id: String,
+        warnings: Vec<String>,
+    },
+    FinalSegment {
+        session_id: String,
+        text: String,
+        timestamp_ms: u64,
+    },
+}
+
+/// Message on the wire
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MqttMessage {
+    pub topic: String,
+    pub payload: Vec<u8>,
+    pub retain: bool,
+}
+
+impl MqttEvent {
+    /// Messages for this event, sent at `now_ms`
+    pub fn messages(&self, settings: &MqttSettings, now_ms: u64) -> Vec<MqttMessage> {
+        let message = |suffix: &str, payload: serde_json::Value| MqttMessage {
+            topic: settings.topic(suffix),
+            payload: payload.to_string().into_bytes(),
+            retain: false,
+        };
+        match self {
+            MqttEvent::SessionStarted { session_id } => vec![
+                status_message(settings, "recording"),
+                message(
+                    "session/started",
+                    json!({ "session_id": session_id, "timestamp": now_ms }),
+                ),
+            ],
+            MqttEvent::SessionStopped {
+                session_id,
+                warnings,
+            } => vec![
+                status_message(settings, "idle"),
+                message(
+                    "session/stopped",
+                    json!({ "session_id": session_id, "timestamp": now_ms, "warnings": warnings }),
+                ),
+            ],
+            MqttEvent::FinalSegment {
+                session_id,
+                text,
+                timestamp_ms,
+            } => vec![message(
+                "transcript",
+                json!({ "session_id": session_id, "text": text, "timestamp_ms": timestamp_ms }),
+            )],
+        }
+    }
+}
+
+fn status_message(settings: &MqttSettings, status: &str) -> MqttMessage {
+    MqttMessage {
+        topic: settings.topic("status"),
+        payload: status.as_bytes().to_vec(),
+        retain: true,
+    }
+}
+
+// ============================================================================
+// Packets (MQTT 3.1.1)
+// ============================================================================
+
+const CONNACK: u8 = 0x20;
+const PUBACK: u8 = 0x40;
+const PINGREQ: [u8; 2] = [0xC0, 0x00];
+const DISCONNECT: [u8; 2] = [0xE0, 0x00];
+
+fn put_str(buf: &mut Vec<u8>, s: &[u8]) {
+    buf.extend_from_slice(&(s.len() as u16).to_be_bytes());
+    buf.extend_from_slice(s);
+}
+
+/// Fixed header + variable header and payload
+fn packet(header: u8, body: &[u8]) -> Vec<u8> {
+    let mut out = vec![header];
+    let mut len = body.len();
+    loop {
+        let mut byte = (len % 128) as u8;
+        len /= 128;
+        if len > 0 {
+            byte |= 0x80;
+        }
+        out.push(byte);
+        if len == 0 {
+            break;
+        }
+    }
+    out.extend_from_slice(body);
+    out
+}
+
+/// CONNECT with a clean session and the retained `offline` status as will
+pub fn encode_connect(settings: &MqttSettings, client_id: &str) -> Vec<u8> {
+    let mut flags = 0x02 | 0x04 | 0x20 | (settings.qos << 3);
+    if settings.username.is_some() {
+        flags |= 0x80;
+    }
+    if settings.password.is_some() {
+        flags |= 0x40;
+    }
+    let mut body = Vec::new();
+    put_str(&mut body, b"MQTT");
+    body.push(4); // protocol level 3.1.1
+    body.push(flags);
+    body.extend_from_slice(&(KEEP_ALIVE.as_secs() as u16).to_be_bytes());
+    put_str(&mut body, client_id.as_bytes());
+    put_str(&mut body, settings.topic("status").as_bytes());
+    put_str(&mut body, b"offline");
+    if let Some(username) = &settings.username {
+        put_str(&mut body, username.as_bytes());
+    }
+    if let Some(password) = &settings.password {
+        put_str(&mut body, password.as_bytes());
+    }
+    packet(0x10, &body)
+}
+
+/// PUBLISH; `packet_id` and `dup` only apply to QoS 1
+pub fn encode_publish(message: &MqttMessage, qos: u8, packet_id: u16, dup: bool) -> Vec<u8> {
+    let mut header = 0x30 | (qos << 1) | u8::from(message.retain);
+    if dup && qos > 0 {
+        header |= 0x08;
+    }
+    let mut body = Vec::new();
+    put_str(&mut body, message.topic.as_bytes());
+    if qos > 0 {
+        body.extend_from_slice(&packet_id.to_be_bytes());
+    }
+    body.extend_from_slice(&message.payload);
+    packet(header, &body)
+}
+
+/// Read one packet: (packet type and flags, body)
+async fn read_packet<R: AsyncRead + Unpin>(reader: &mut R) -> Result<(u8, Vec<u8>)> {
+    let header = reader.read_u8().await?;
+    let mut len = 0usize;
+    for shift in 0..4 {
+        let byte = reader.read_u8().await?;
+        len |= ((byte & 0x7F) as usize) << (7 * shift);
+        if byte & 0x80 == 0 {
+            if len > MAX_PACKET_LEN {
+                anyhow::bail!("Packet too large: {} bytes", len);
+            }
+            let mut body = vec![0; len];
+            reader.read_exact(&mut body).await?;
+            return Ok((header, body));
+        }
+    }
+    anyhow::bail!("Malformed packet length")
+}
+
+// ============================================================================
+// Client
+// ============================================================================
+
+trait Transport: AsyncRead + AsyncWrite + Unpin + Send {}
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> Transport for T {}
+
+/// Background MQTT client; stopped on drop
+pub struct MqttPublisher {
+    tx: mpsc::Sender<MqttEvent>,
+    cancel: CancellationToken,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MqttPublisher {
+    /// Start connecting to the broker in the background
+    pub fn start(settings: MqttSettings) -> Self {
+        let (tx, rx) = mpsc::channel(QUEUE_CAPACITY);
+        let cancel = CancellationToken::new();
+        let handle = tokio::spawn(run(Arc::new(settings), rx, cancel.clone()));
+        Self {
+            tx,
+            cancel,
+            handle: Some(handle),
+        }
+    }
+
+    /// Queue an event; false if the queue is full (the event is dropped)
+    pub fn publish(&self, event: MqttEvent) -> bool {
+        self.tx.try_send(event).is_ok()
+    }
+
+    /// Publish the `offline` status, disconnect and wait for the client
+    pub async fn stop(mut self) {
+        self.cancel.cancel();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.await;
+        }
+    }
+}
+
+impl Drop for MqttPublisher {
+    fn drop(&mut self) {
+        self.cancel.cancel();
+    }
+}
+
+/// State kept across reconnects
+struct ClientState {
+    recording: bool,
+    /// Messages of an event not yet acknowledged (QoS 1) or written
+    pending: Vec<MqttMessage>,
+    packet_id: u16,
+}
+
+async fn run(
+    settings: Arc<MqttSettings>,
+    mut rx: mpsc::Receiver<MqttEvent>,
+    cancel: CancellationToken,
+) {
+    let mut client = ClientState {
+        recording: false,
+        pending: Vec::new(),
+        packet_id: 0,
+    };
+    let mut backoff = Duration::from_secs(1);
+    while !cancel.is_cancelled() {
+        match connect(&settings).await {
+            Ok(stream) => {
+                backoff = Duration::from_secs(1);
+                match session(stream, &settings, &mut rx, &cancel, &mut client).await {
+                    Ok(()) => return,
+                    Err(e) => {
+                        log_warn!("mqtt", "connection_lost", format!("{:#}", e));
+                    }
+                }
+            }
+            Err(e) => {
+                log_warn!("mqtt", "connect_failed", format!("{:#}", e));
+            }
+        }
+        tokio::select! {
+            _ = tokio::time::sleep(backoff) => {}
+            _ = cancel.cancelled() => return,
+        }
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
+}
+
+async fn connect(settings: &MqttSettings) -> Result<Box<dyn Transport>> {
+    let address = (settings.host.as_str(), settings.port);
+    let tcp = tokio::time::timeout(RESPONSE_TIMEOUT, TcpStream::connect(address))
+        .await
+        .context("Connection timed out")??;
+    let mut stream: Box<dyn Transport> = if settings.tls {
+        let mut roots = tokio_rustls::rustls::RootCertStore::empty();
+        roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+        let config = tokio_rustls::rustls::ClientConfig::builder()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        let server_name =
+            tokio_rustls::rustls::pki_types::ServerName::try_from(settings.host.clone())
+                .context("Invalid broker host name")?;
+        let connector = tokio_rustls::TlsConnector::from(Arc::new(config));
+        Box::new(connector.connect(server_name, tcp).await?)
+    } else {
+        Box::new(tcp)
+    };
+
+    let client_id = if settings.client_id.is_empty() {
+        format!("meeting-minutes-{}", uuid::Uuid::new_v4().simple())
+    } else {
+        settings.client_id.clone()
+    };
+    stream
+        .write_all(&encode_connect(settings, &client_id))
+        .await?;
+    let (header, body) = tokio::time::timeout(RESPONSE_TIMEOUT, read_packet(&mut stream))
+        .await
+        .context("No CONNACK from broker")??;
+    if header != CONNACK || body.len() != 2 {
+        anyhow::bail!("Unexpected packet instead of CONNACK: {:#x}", header);
+    }
+    match body[1] {
+        0 => Ok(stream),
+        4 => anyhow::bail!("Broker rejected the username or password"),
+        5 => anyhow::bail!("Not authorized by the broker"),
+        code => anyhow::bail!("Broker refused the connection (code {})", code),
+    }
+}
+
+/// Publish until the queue closes or the client is stopped
+async fn session(
+    stream: Box<dyn Transport>,
+    settings: &MqttSettings,
+    rx: &mut mpsc::Receiver<MqttEvent>,
+    cancel: &CancellationToken,
+    client: &mut ClientState,
+) -> Result<()> {
+    let (mut reader, mut writer) = tokio::io::split(stream);
+    // Packets from the broker, read by a task of their own (reads aren't
+    // cancel-safe inside select!)
+    let (packets_tx, mut packets) = mpsc::channel(16);
+    let reader_task = tokio::spawn(async move {
+        while let Ok(packet) = read_packet(&mut reader).await {
+            if packets_tx.send(packet).await.is_err() {
+                break;
+            }
+        }
+    });
+    let result = async {
+        let status = if client.recording {
+            "recording"
+        } else {
+            "idle"
+        };
+        let mut resend = vec![status_message(settings, status)];
+        resend.append(&mut client.pending);
+        send_all(&mut writer, &mut packets, settings, client, resend, true).await?;
+
+        let mut ping = tokio::time::interval(KEEP_ALIVE / 2);
+        ping.tick().await;
+        loop {
+            tokio::select! {
+                event = rx.recv() => {
+                    let Some(event) = event else { return Ok(()) };
+                    match &event {
+                        MqttEvent::SessionStarted { .. } => client.recording = true,
+                        MqttEvent::SessionStopped { .. } => client.recording = false,
+                        MqttEvent::FinalSegment { .. } => {}
+                    }
+                    let messages = event.messages(settings, crate::latency::now_ms());
+                    send_all(&mut writer, &mut packets, settings, client, messages, false).await?;
+                }
+                _ = ping.tick() => writer.write_all(&PINGREQ).await?,
+                packet = packets.recv() => {
+                    if packet.is_none() {
+                        anyhow::bail!("Connection closed by the broker");
+                    }
+                }
+                _ = cancel.cancelled() => {
+                    let offline = status_message(settings, "offline");
+                    writer.write_all(&encode_publish(&offline, 0, 0, false)).await?;
+                    writer.write_all(&DISCONNECT).await?;
+                    writer.flush().await?;
+                    return Ok(());
+                }
+            }
+        }
+    }
+    .await;
+    reader_task.abort();
+    result
+}
+
+/// Send messages in order; with QoS 1 each waits for its PUBACK
+///
+/// Unsent (or unacknowledged) messages stay in `client.pending` for the
+/// next connection.
+async fn send_all(
+    writer: &mut (impl AsyncWrite + Unpin),
+    packets: &mut mpsc::Receiver<(u8, Vec<u8>)>,
+    settings: &MqttSettings,
+    client: &mut ClientState,
+    messages: Vec<MqttMessage>,
+    dup: bool,
+) -> Result<()> {
+    client.pending = messages;
+    while let Some(message) = client.pending.first() {
+        client.packet_id = client.packet_id.checked_add(1).unwrap_or(1);
+        let packet_id = client.packet_id;
+        writer
+            .write_all(&encode_publish(message, settings.qos, packet_id, dup))
+            .await?;
+        if settings.qos > 0 {
+            tokio::time::timeout(RESPONSE_TIMEOUT, async {
+                loop {
+                    match packets.recv().await {
+                        Some((PUBACK, body)) if body == packet_id.to_be_bytes() => return Ok(()),
+                        Some(_) => continue,
+                        None => anyhow::bail!("Connection closed by the broker"),
+                    }
+                }
+            })
+            .await
+            .context("No PUBACK from broker")??;
+        }
+        client.pending.remove(0);
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Password
+// ============================================================================
+
+fn keychain_entry() -> Result<keyring::Entry> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, "mqtt-password").context("Failed to open the OS keychain")
+}
+
+/// Broker password, if one is stored
+pub fn load_password() -> Result<Option<String>> {
+    match keychain_entry()?.get_password() {
+        Ok(password) => Ok(Some(password)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(e).context("Failed to read the MQTT password from the OS keychain"),
+    }
+}
+
+/// Store the broker password (None removes it)
+pub fn store_password(password: Option<&str>) -> Result<()> {
+    let entry = keychain_entry()?;
+    match password {
+        Some(password) => entry
+            .set_password(password)
+            .context("Failed to store the MQTT password in the OS keychain"),
+        None => match entry.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(e).context("Failed to remove the MQTT password"),
+        },
+    }
+}
+
+/// Saved settings with the password from the keychain, ready to connect
+///
+/// A password still in an older `mqtt.json` is moved to the keychain and
+/// the file rewritten without it.
+pub fn load_settings_with_password(app_data_dir: &Path) -> Result<MqttSettings> {
+    let mut settings: MqttSettings = settings_file::load(app_data_dir, SETTINGS_STEM)?;
+    match settings.password.as_deref() {
+        Some(password) => {
+            store_password(Some(password))?;
+            settings_file::save(app_data_dir, SETTINGS_STEM, &settings)?;
+        }
+        None => settings.password = load_password()?,
+    }
+    Ok(settings)
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+pub const SETTINGS_STEM: &str = "mqtt";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_packet_encoding_and_settings() {
+        let mut settings = MqttSettings {
+            enabled: true,
+            host: "broker.local".to_string(),
+            topic_prefix: "room1".to_string(),
+            ..Default::default()
+        };
+        settings.validate().unwrap();
+
+        let connect = encode_connect(&settings, "c");
+        assert_eq!(connect[0], 0x10);
+        assert_eq!(connect[1] as usize, connect.len() - 2);
+        assert_eq!(&connect[2..9], b"\x00\x04MQTT\x04");
+        assert_eq!(connect[9], 0x26); // clean session, will, will retain
+
+        let status = status_message(&settings, "idle");
+        assert_eq!(
+            encode_publish(&status, 0, 7, false),
+            b"\x31\x12\x00\x0croom1/statusidle"
+        );
+        let qos1 = encode_publish(&status, 1, 7, true);
+        assert_eq!(qos1[0], 0x3B);
+        assert_eq!(&qos1[16..18], &[0, 7]);
+
+        // Multi-byte remaining length
+        let large = MqttMessage {
+            topic: "t".to_string(),
+            payload: vec![0; 200],
+            retain: false,
+        };
+        assert_eq!(&encode_publish(&large, 0, 0, false)[1..3], &[0xCB, 0x01]);
+
+        let started = MqttEvent::SessionStarted {
+            session_id: "s1".to_string(),
+        }
+        .messages(&settings, 5);
+        assert_eq!(started[0], status_message(&settings, "recording"));
+        assert_eq!(started[1].topic, "room1/session/started");
+
+        settings.topic_prefix = "room1/#".to_string();
+        assert!(settings.validate().is_err());
+        settings.topic_prefix = "room1".to_string();
+        settings.qos = 2;
+        assert!(settings.validate().is_err());
+    }
+
+    #[test]
+    fn test_password_is_not_written() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let settings = MqttSettings {
+            username: Some("door".to_string()),
+            password: Some("secret".to_string()),
+            ..Default::default()
+        };
+        settings_file::save(dir.path(), SETTINGS_STEM, &settings).unwrap();
+        let json = std::fs::read_to_string(dir.path().join("settings/mqtt.json")).unwrap();
+        assert!(!json.contains("password"));
+        assert!(!json.contains("secret"));
+        let loaded: MqttSettings = settings_file::load(dir.path(), SETTINGS_STEM).unwrap();
+        assert_eq!(loaded.password, None);
+
+        // Older files: still read, so the password can move to the keychain
+        let legacy: MqttSettings =
+            serde_json::from_str(r#"{"username": "door", "password": "secret"}"#).unwrap();
+        assert_eq!(legacy.password.as_deref(), Some("secret"));
+    }
+
+    #[tokio::test]
+    async fn test_read_packet_rejects_oversized_length() {
+        // PUBLISH announcing ~2 MB; rejected before reading (or allocating) the body
+        let mut input: &[u8] = &[0x30, 0x80, 0x80, 0x80, 0x01];
+        let err = read_packet(&mut input).await.unwrap_err();
+        assert!(err.to_string().contains("too large"));
+
+        let mut input: &[u8] = &[0xD0, 0x00];
+        assert_eq!(read_packet(&mut input).await.unwrap(), (0xD0, Vec::new()));
+    }
+
+    #[tokio::test]
+    async fn test_publisher_delivers_with_qos1() {
+        let broker = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let settings = MqttSettings {
+            enabled: true,
+            host: "127.0.0.1".to_string(),
+            port: broker.local_addr().unwrap().port(),
+            qos: 1,
+            ..Default::default()
+        };
+        let publisher = MqttPublisher::start(settings);
+        assert!(publisher.publish(MqttEvent::FinalSegment {
+            session_id: "s1".to_string(),
+            text: "こんにちは".to_string(),
+            timestamp_ms: 42,
+        }));
+
+        let (mut stream, _) = broker.accept().await.unwrap();
+        let (header, _) = read_packet(&mut stream).await.unwrap();
+        assert_eq!(header, 0x10);
+        stream.write_all(&[CONNACK, 2, 0, 0]).await.unwrap();
+
+        let mut topics = Vec::new();
+        while topics.len() < 2 {
+            let (header, body) = read_packet(&mut stream).await.unwrap();
+            assert_eq!(header & 0xF0, 0x30);
+            let topic_len = u16::from_be_bytes([body[0], body[1]]) as usize;
+            topics.push(String::from_utf8(body[2..2 + topic_len].to_vec()).unwrap());
+            let packet_id = &body[2 + topic_len..4 + topic_len];
+            stream
+                .write_all(&[PUBACK, 2, packet_id[0], packet_id[1]])
+                .await
+                .unwrap();
+        }
+        assert_eq!(
+            topics,
+            ["meeting-minutes/status", "meeting-minutes/transcript"]
+        );
+
+        publisher.stop().await;
+        let (_, body) = read_packet(&mut stream).await.unwrap();
+        assert!(body.ends_with(b"offline"));
+        assert_eq!(read_packet(&mut stream).await.unwrap().0, DISCONNECT[0]);
+    }
+}
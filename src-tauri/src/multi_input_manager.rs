@@ -94,6 +94,9 @@ pub struct InputStatus {
     pub is_muted: bool,
     /// Frames dropped due to lock contention
     pub lock_contention_drops: u64,
+    /// User-given device name (see `device_aliases`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
 }
 
 /// Configuration for a single input
@@ -437,7 +440,7 @@ impl MultiInputManager {
                     }
 
                     // Create callback that writes to this input's buffer (real-time safe)
-                    let callback: AudioChunkCallback = Box::new(move |data: Vec<u8>| {
+                    let callback: AudioChunkCallback = Box::new(move |data: bytes::Bytes| {
                         // push() uses try_lock() - if lock contention, frame is dropped
                         // This is intentional for real-time safety
                         let _ = buffer_clone.push(&data);
@@ -587,6 +590,7 @@ impl MultiInputManager {
                     gain_db: state.config.gain_db,
                     is_muted: state.config.muted,
                     lock_contention_drops: state.buffer.lock_contention_drops(),
+                    alias: None,
                 }
             })
             .collect()
@@ -5,10 +5,8 @@
 //! Requirements: STTMIX-REQ-001.2, STTMIX-REQ-005.1
 //! Design: meeting-minutes-stt-multi-input/design.md
 
-use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
 
 use crate::multi_input_manager::InputRole;
 
@@ -64,6 +62,10 @@ pub struct MultiInputSettings {
     #[serde(default)]
     pub degradation_policy: DegradationPolicy,
 
+    /// Save each input to its own WAV in the session directory
+    #[serde(default)]
+    pub save_input_tracks: bool,
+
     /// Settings version for future migrations
     #[serde(default = "default_version")]
     pub version: u32,
@@ -82,6 +84,7 @@ impl Default for MultiInputSettings {
             mute_states: HashMap::new(),
             multi_input_enabled: false,
             degradation_policy: DegradationPolicy::default(),
+            save_input_tracks: false,
             version: 1,
         }
     }
@@ -142,66 +145,7 @@ impl MultiInputSettings {
 // Persistence
 // ============================================================================
 
-const SETTINGS_FILENAME: &str = "multi_input.json";
-const SETTINGS_SUBDIR: &str = "settings";
-
-/// Get the settings file path
-fn get_settings_path(app_data_dir: &PathBuf) -> PathBuf {
-    app_data_dir.join(SETTINGS_SUBDIR).join(SETTINGS_FILENAME)
-}
-
-/// Save multi-input settings to disk
-///
-/// Creates the settings directory if it doesn't exist.
-///
-/// Requirement: STTMIX-REQ-001.2
-pub fn save_settings(app_data_dir: &PathBuf, settings: &MultiInputSettings) -> Result<()> {
-    let settings_dir = app_data_dir.join(SETTINGS_SUBDIR);
-
-    // Create settings directory if needed
-    if !settings_dir.exists() {
-        std::fs::create_dir_all(&settings_dir)
-            .with_context(|| format!("Failed to create settings directory: {:?}", settings_dir))?;
-    }
-
-    let settings_path = get_settings_path(app_data_dir);
-    let json = serde_json::to_string_pretty(settings)
-        .context("Failed to serialize multi-input settings")?;
-
-    std::fs::write(&settings_path, json)
-        .with_context(|| format!("Failed to write settings file: {:?}", settings_path))?;
-
-    eprintln!("💾 Saved multi-input settings to {:?}", settings_path);
-    Ok(())
-}
-
-/// Load multi-input settings from disk
-///
-/// Returns default settings if file doesn't exist.
-///
-/// Requirement: STTMIX-REQ-001.2
-pub fn load_settings(app_data_dir: &PathBuf) -> Result<MultiInputSettings> {
-    let settings_path = get_settings_path(app_data_dir);
-
-    if !settings_path.exists() {
-        eprintln!("📁 No multi-input settings found, using defaults");
-        return Ok(MultiInputSettings::default());
-    }
-
-    let json = std::fs::read_to_string(&settings_path)
-        .with_context(|| format!("Failed to read settings file: {:?}", settings_path))?;
-
-    let settings: MultiInputSettings =
-        serde_json::from_str(&json).context("Failed to parse multi-input settings")?;
-
-    eprintln!(
-        "📁 Loaded multi-input settings: {} devices, enabled={}",
-        settings.selected_device_ids.len(),
-        settings.multi_input_enabled
-    );
-
-    Ok(settings)
-}
+pub const SETTINGS_STEM: &str = "multi_input";
 
 /// Check if selected devices are still available
 ///
@@ -294,12 +238,14 @@ mod tests {
         settings.set_mute("loopback-1", true);
         settings.multi_input_enabled = true;
         settings.degradation_policy = DegradationPolicy::StopOnAnyFailure;
+        settings.save_input_tracks = true;
 
         // Save
-        save_settings(&app_data_dir, &settings).unwrap();
+        crate::settings_file::save(&app_data_dir, SETTINGS_STEM, &settings).unwrap();
 
         // Load
-        let loaded = load_settings(&app_data_dir).unwrap();
+        let loaded: MultiInputSettings =
+            crate::settings_file::load(&app_data_dir, SETTINGS_STEM).unwrap();
         assert_eq!(loaded.selected_device_ids.len(), 2);
         assert!(loaded.selected_device_ids.contains(&"mic-1".to_string()));
         assert!(loaded.selected_device_ids.contains(&"loopback-1".to_string()));
@@ -307,6 +253,7 @@ mod tests {
         assert!(loaded.is_muted("loopback-1"));
         assert!(loaded.multi_input_enabled);
         assert_eq!(loaded.degradation_policy, DegradationPolicy::StopOnAnyFailure);
+        assert!(loaded.save_input_tracks);
     }
 
     #[test]
@@ -314,7 +261,8 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         let app_data_dir = temp_dir.path().to_path_buf();
 
-        let settings = load_settings(&app_data_dir).unwrap();
+        let settings: MultiInputSettings =
+            crate::settings_file::load(&app_data_dir, SETTINGS_STEM).unwrap();
         assert!(settings.selected_device_ids.is_empty());
     }
 
@@ -0,0 +1,212 @@
+//! Operational Event Webhooks
+//!
+//! For a fleet of recorder machines monitored centrally: operational events
+//! are POSTed as JSON to the configured webhooks, on top of the usual UI
+//! notifications. Events:
+//!
+//! - `disk_warning` / `disk_critical` — free space of the recordings
+//!   directory below 1GB / 500MB (checked every `DISK_CHECK_INTERVAL`)
+//! - `sidecar_failed` — the Python sidecar didn't start or get ready
+//! - `pipeline_task_failed` — a supervised recording task panicked
+//!   (restarted or the session stopped)
+//! - `reconnection_exhausted` — an audio device didn't come back within
+//!   the reconnect policy
+//!
+//! Each webhook receives every event, or only those listed in `events`, and
+//! may send a bearer token. An event kind is sent at most once per
+//! `cooldown_minutes`, so a full disk doesn't page anyone every few minutes.
+//!
+//! Settings are machine-wide (not per workspace); persisted to
+//! `settings/ops_webhooks.json` in app data directory.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Interval of the disk space check
+pub const DISK_CHECK_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Most webhooks
+pub const MAX_WEBHOOKS: usize = 16;
+
+/// Operational event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OpsEventKind {
+    DiskWarning,
+    DiskCritical,
+    SidecarFailed,
+    PipelineTaskFailed,
+    ReconnectionExhausted,
+}
+
+/// One webhook endpoint
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpsWebhook {
+    /// http(s) URL receiving the POST
+    pub url: String,
+    /// Events to send (empty: every event)
+    #[serde(default)]
+    pub events: Vec<OpsEventKind>,
+    /// Sent as `Authorization: Bearer <token>`
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+impl OpsWebhook {
+    pub fn accepts(&self, kind: OpsEventKind) -> bool {
+        self.events.is_empty() || self.events.contains(&kind)
+    }
+}
+
+/// Webhook configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpsWebhookSettings {
+    /// Send events
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub webhooks: Vec<OpsWebhook>,
+    /// Minimum time between two events of the same kind
+    #[serde(default = "default_cooldown_minutes")]
+    pub cooldown_minutes: u32,
+    /// Settings version for future migrations
+    #[serde(default = "default_version")]
+    pub version: u32,
+}
+
+fn default_cooldown_minutes() -> u32 {
+    30
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+impl Default for OpsWebhookSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            webhooks: Vec::new(),
+            cooldown_minutes: default_cooldown_minutes(),
+            version: 1,
+        }
+    }
+}
+
+impl OpsWebhookSettings {
+    pub fn validate(&self) -> Result<()> {
+        if self.webhooks.len() > MAX_WEBHOOKS {
+            anyhow::bail!("At most {} webhooks are supported", MAX_WEBHOOKS);
+        }
+        for webhook in &self.webhooks {
+            if !(webhook.url.starts_with("https://") || webhook.url.starts_with("http://")) {
+                anyhow::bail!("Webhook URL must start with http:// or https://");
+            }
+        }
+        Ok(())
+    }
+
+    /// Webhooks receiving `kind` (none while disabled)
+    pub fn targets(&self, kind: OpsEventKind) -> Vec<&OpsWebhook> {
+        if !self.enabled {
+            return Vec::new();
+        }
+        self.webhooks.iter().filter(|w| w.accepts(kind)).collect()
+    }
+
+    pub fn cooldown(&self) -> Duration {
+        Duration::from_secs(self.cooldown_minutes as u64 * 60)
+    }
+}
+
+/// Webhook payload
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OpsEvent {
+    pub event: OpsEventKind,
+    /// Host name of the machine
+    pub machine: String,
+    pub workspace: String,
+    /// Time of the event (RFC 3339)
+    pub timestamp: String,
+    pub message: String,
+    /// Event-specific details
+    pub data: Value,
+}
+
+/// Per-kind cooldown of sent events
+#[derive(Debug, Default)]
+pub struct OpsThrottle {
+    /// Time each kind was last sent (Unix ms)
+    last_sent: HashMap<OpsEventKind, u64>,
+}
+
+impl OpsThrottle {
+    /// Whether `kind` may be sent at `now_ms`; records the send if so
+    pub fn allow(&mut self, kind: OpsEventKind, now_ms: u64, cooldown: Duration) -> bool {
+        let cooldown_ms = cooldown.as_millis() as u64;
+        match self.last_sent.get(&kind) {
+            Some(&last) if now_ms.saturating_sub(last) < cooldown_ms => false,
+            _ => {
+                self.last_sent.insert(kind, now_ms);
+                true
+            }
+        }
+    }
+}
+
+/// Event for a disk status, if it is worth reporting
+pub fn disk_event_kind(status: crate::storage::DiskSpaceStatus) -> Option<OpsEventKind> {
+    match status {
+        crate::storage::DiskSpaceStatus::Sufficient => None,
+        crate::storage::DiskSpaceStatus::Warning => Some(OpsEventKind::DiskWarning),
+        crate::storage::DiskSpaceStatus::Critical => Some(OpsEventKind::DiskCritical),
+    }
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+pub const SETTINGS_STEM: &str = "ops_webhooks";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_targets_throttle_and_settings() {
+        let mut settings = OpsWebhookSettings {
+            webhooks: vec![
+                OpsWebhook {
+                    url: "https://monitor.example/all".to_string(),
+                    events: Vec::new(),
+                    token: None,
+                },
+                OpsWebhook {
+                    url: "https://monitor.example/disk".to_string(),
+                    events: vec![OpsEventKind::DiskWarning, OpsEventKind::DiskCritical],
+                    token: Some("secret".to_string()),
+                },
+            ],
+            ..Default::default()
+        };
+        assert!(settings.targets(OpsEventKind::DiskCritical).is_empty());
+        settings.enabled = true;
+        assert_eq!(settings.targets(OpsEventKind::DiskCritical).len(), 2);
+        assert_eq!(settings.targets(OpsEventKind::SidecarFailed).len(), 1);
+        settings.validate().unwrap();
+
+        let mut throttle = OpsThrottle::default();
+        let cooldown = settings.cooldown();
+        assert!(throttle.allow(OpsEventKind::DiskWarning, 0, cooldown));
+        assert!(!throttle.allow(OpsEventKind::DiskWarning, 29 * 60 * 1000, cooldown));
+        assert!(throttle.allow(OpsEventKind::DiskCritical, 1, cooldown));
+        assert!(throttle.allow(OpsEventKind::DiskWarning, 30 * 60 * 1000, cooldown));
+
+        settings.webhooks[0].url = "ftp://monitor.example".to_string();
+        assert!(settings.validate().is_err());
+    }
+}
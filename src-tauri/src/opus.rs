@@ -0,0 +1,245 @@
+//! Ogg Opus Session Audio (feature `opus`)
+//!
+//! Lossy speech encoding for long recordings: 20ms Opus frames at 24kbps
+//! (VoIP mode), about 1/20 of the 16kHz PCM size. Packets go into an Ogg
+//! stream (RFC 7845) with `OpusHead`/`OpusTags` headers; a page is closed
+//! every second so an interrupted recording loses at most that much.
+//!
+//! Needs libopus (built by `audiopus_sys`), hence the feature flag.
+
+use anyhow::{Context, Result};
+use audiopus::coder::{Decoder, Encoder};
+use audiopus::{Application, Bitrate, Channels, SampleRate, Signal};
+use ogg::writing::PacketWriteEndInfo;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// Samples per Opus frame (20ms at 16kHz)
+const FRAME_SAMPLES: usize = 320;
+
+/// Encoder bitrate (bits/second)
+pub const BITRATE_BPS: i32 = 24_000;
+
+/// Frames per Ogg page (1 second)
+const FRAMES_PER_PAGE: u64 = 50;
+
+/// Ogg granule positions count 48kHz samples
+const GRANULE_SCALE: u64 = 3;
+
+/// Largest Opus packet
+const MAX_PACKET_BYTES: usize = 4000;
+
+/// Largest decoded frame (120ms at 16kHz)
+const MAX_FRAME_SAMPLES: usize = 1920;
+
+const STREAM_SERIAL: u32 = 0x4D4D_4131;
+
+/// Ogg Opus file recorded incrementally
+pub struct OpusFileWriter {
+    path: PathBuf,
+    packets: Option<ogg::PacketWriter<BufWriter<std::fs::File>>>,
+    encoder: Encoder,
+    pending: Vec<i16>,
+    /// Encoder delay in 48kHz samples (OpusHead pre-skip)
+    pre_skip: u64,
+    frames_written: u64,
+    samples_written: u64,
+}
+
+impl OpusFileWriter {
+    pub fn create(path: PathBuf) -> Result<Self> {
+        let mut encoder = Encoder::new(SampleRate::Hz16000, Channels::Mono, Application::Voip)
+            .context("Failed to create Opus encoder")?;
+        encoder.set_bitrate(Bitrate::BitsPerSecond(BITRATE_BPS))?;
+        encoder.set_signal(Signal::Voice)?;
+        let pre_skip = encoder.lookahead()? as u64 * GRANULE_SCALE;
+
+        let file = crate::storage::create_file_owner_only(&path)?;
+        let mut packets = ogg::PacketWriter::new(BufWriter::new(file));
+        // Each header packet gets its own page (RFC 7845 section 3)
+        packets.write_packet(
+            opus_head(pre_skip as u16).into_boxed_slice(),
+            STREAM_SERIAL,
+            PacketWriteEndInfo::EndPage,
+            0,
+        )?;
+        packets.write_packet(
+            opus_tags().into_boxed_slice(),
+            STREAM_SERIAL,
+            PacketWriteEndInfo::EndPage,
+            0,
+        )?;
+
+        Ok(Self {
+            path,
+            packets: Some(packets),
+            encoder,
+            pending: Vec::with_capacity(FRAME_SAMPLES),
+            pre_skip,
+            frames_written: 0,
+            samples_written: 0,
+        })
+    }
+
+    pub fn write_samples(&mut self, samples: &[i16]) -> Result<()> {
+        for &sample in samples {
+            self.pending.push(sample);
+            if self.pending.len() == FRAME_SAMPLES {
+                self.write_frame(false)?;
+            }
+        }
+        self.samples_written += samples.len() as u64;
+        Ok(())
+    }
+
+    pub fn samples_written(&self) -> u64 {
+        self.samples_written
+    }
+
+    /// Sync the written pages to disk (the open page stays buffered)
+    pub fn flush(&mut self) -> Result<()> {
+        if let Some(packets) = self.packets.as_mut() {
+            let out = packets.inner_mut();
+            out.flush()?;
+            out.get_ref().sync_data()?;
+        }
+        Ok(())
+    }
+
+    /// Encode the last (zero-padded) frame and end the stream
+    pub fn close(mut self) -> Result<()> {
+        self.finalize()
+    }
+
+    fn finalize(&mut self) -> Result<()> {
+        if self.packets.is_none() {
+            return Ok(());
+        }
+        self.pending.resize(FRAME_SAMPLES, 0);
+        self.write_frame(true)?;
+        if let Some(packets) = self.packets.take() {
+            let file = packets
+                .into_inner()
+                .into_inner()
+                .map_err(|e| e.into_error())?;
+            file.sync_all()?;
+        }
+        Ok(())
+    }
+
+    fn write_frame(&mut self, last: bool) -> Result<()> {
+        let Some(packets) = self.packets.as_mut() else {
+            anyhow::bail!("Opus writer already closed: {:?}", self.path);
+        };
+        let mut packet = vec![0u8; MAX_PACKET_BYTES];
+        let len = self
+            .encoder
+            .encode(&self.pending, &mut packet)
+            .context("Opus encoding failed")?;
+        packet.truncate(len);
+        self.pending.clear();
+        self.frames_written += 1;
+
+        // The last granule position trims the padding of the final frame
+        let encoded = if last {
+            self.samples_written
+        } else {
+            self.frames_written * FRAME_SAMPLES as u64
+        };
+        let granule = self.pre_skip + encoded * GRANULE_SCALE;
+        let end = if last {
+            PacketWriteEndInfo::EndStream
+        } else if self.frames_written % FRAMES_PER_PAGE == 0 {
+            PacketWriteEndInfo::EndPage
+        } else {
+            PacketWriteEndInfo::NormalPacket
+        };
+        packets.write_packet(packet.into_boxed_slice(), STREAM_SERIAL, end, granule)?;
+        Ok(())
+    }
+}
+
+impl Drop for OpusFileWriter {
+    fn drop(&mut self) {
+        let _ = self.finalize();
+    }
+}
+
+/// Decode an Ogg Opus session file, passing samples to `on_chunk` per packet
+///
+/// Pre-skip and end padding are trimmed; a stream cut off mid-page ends at
+/// its last complete page.
+pub fn decode<F>(path: &Path, mut on_chunk: F) -> Result<u64>
+where
+    F: FnMut(&[i16]),
+{
+    let file = std::io::BufReader::new(
+        std::fs::File::open(path).with_context(|| format!("Failed to open {:?}", path))?,
+    );
+    let mut reader = ogg::PacketReader::new(file);
+
+    let head = reader.read_packet()?.context("Empty Ogg stream")?;
+    if !head.data.starts_with(b"OpusHead") || head.data.len() < 19 {
+        anyhow::bail!("Not an Ogg Opus stream: {:?}", path);
+    }
+    let pre_skip = u16::from_le_bytes([head.data[10], head.data[11]]) as u64 / GRANULE_SCALE;
+    let _tags = reader.read_packet()?;
+
+    let mut decoder = Decoder::new(SampleRate::Hz16000, Channels::Mono)?;
+    let mut output = vec![0i16; MAX_FRAME_SAMPLES];
+    let mut skip = pre_skip;
+    let mut total = 0u64;
+    loop {
+        let packet = match reader.read_packet() {
+            Ok(Some(packet)) => packet,
+            Ok(None) => break,
+            // Interrupted recording: unfinished last page
+            Err(_) if total > 0 => break,
+            Err(e) => return Err(e.into()),
+        };
+        let decoded = decoder.decode(
+            Some(packet.data.as_slice().try_into()?),
+            output.as_mut_slice().try_into()?,
+            false,
+        )?;
+        let mut samples = &output[..decoded];
+
+        let drop_front = skip.min(samples.len() as u64) as usize;
+        samples = &samples[drop_front..];
+        skip -= drop_front as u64;
+
+        if packet.last_in_stream() {
+            // Granule position of the last page marks the real end
+            let end = (packet.absgp_page() / GRANULE_SCALE).saturating_sub(pre_skip);
+            let keep = end.saturating_sub(total).min(samples.len() as u64) as usize;
+            samples = &samples[..keep];
+        }
+        if !samples.is_empty() {
+            on_chunk(samples);
+            total += samples.len() as u64;
+        }
+    }
+    Ok(total)
+}
+
+/// `OpusHead` identification header (mono, 16kHz input, family 0)
+fn opus_head(pre_skip: u16) -> Vec<u8> {
+    let mut head = b"OpusHead".to_vec();
+    head.push(1); // version
+    head.push(1); // channels
+    head.extend_from_slice(&pre_skip.to_le_bytes());
+    head.extend_from_slice(&crate::flac::SAMPLE_RATE.to_le_bytes());
+    head.extend_from_slice(&0i16.to_le_bytes()); // output gain
+    head.push(0); // channel mapping family
+    head
+}
+
+/// `OpusTags` comment header
+fn opus_tags() -> Vec<u8> {
+    let vendor = concat!("meeting-minutes-automator ", env!("CARGO_PKG_VERSION"));
+    let mut tags = b"OpusTags".to_vec();
+    tags.extend_from_slice(&(vendor.len() as u32).to_le_bytes());
+    tags.extend_from_slice(vendor.as_bytes());
+    tags.extend_from_slice(&0u32.to_le_bytes()); // no user comments
+    tags
+}
@@ -0,0 +1,182 @@
+//! Partial Text Granularity
+//!
+//! The sidecar sends a partial hypothesis for nearly every new word. Caption
+//! overlays want all of them; clients that insert text into a document
+//! (Google Docs) prefer fewer, larger updates. With `sentence` granularity a
+//! partial is only forwarded to WebSocket clients once it completes another
+//! sentence (ends on `.`, `?`, `!`, `。`, ...) or has grown by
+//! `max_pending_chars` since the last forwarded partial, so unpunctuated
+//! speech still shows up.
+//!
+//! Final text is always forwarded and resets the gate. The app's own live
+//! view receives every partial regardless of the setting.
+//!
+//! Persisted to `settings/partial_granularity.json` in app data directory.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// When partial text is forwarded
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PartialGranularity {
+    /// Every partial update (word-level)
+    #[default]
+    Word,
+    /// Only on sentence boundaries, or after `max_pending_chars`
+    Sentence,
+}
+
+/// Partial text granularity settings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartialGranularitySettings {
+    #[serde(default)]
+    pub granularity: PartialGranularity,
+    /// Sentence mode: forward anyway once the text grew by this many chars
+    #[serde(default = "default_max_pending_chars")]
+    pub max_pending_chars: usize,
+    /// Settings version for future migrations
+    #[serde(default = "default_version")]
+    pub version: u32,
+}
+
+fn default_max_pending_chars() -> usize {
+    80
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+impl Default for PartialGranularitySettings {
+    fn default() -> Self {
+        Self {
+            granularity: PartialGranularity::Word,
+            max_pending_chars: 80,
+            version: 1,
+        }
+    }
+}
+
+impl PartialGranularitySettings {
+    pub fn validate(&self) -> Result<()> {
+        if self.max_pending_chars == 0 {
+            anyhow::bail!("max_pending_chars must be at least 1");
+        }
+        Ok(())
+    }
+}
+
+/// Per-session gate deciding which partials are forwarded
+#[derive(Debug, Default)]
+pub struct PartialGate {
+    /// Sentences completed in the last forwarded partial
+    forwarded_sentences: usize,
+    /// Length (chars) of the last forwarded partial
+    forwarded_chars: usize,
+}
+
+impl PartialGate {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the partial `text` should be forwarded (and record it if so)
+    pub fn admit(&mut self, text: &str, settings: &PartialGranularitySettings) -> bool {
+        let chars = text.chars().count();
+        let sentences = count_sentences(text);
+
+        let admitted = match settings.granularity {
+            PartialGranularity::Word => true,
+            PartialGranularity::Sentence => {
+                sentences > self.forwarded_sentences
+                    || chars >= self.forwarded_chars + settings.max_pending_chars
+            }
+        };
+        if admitted {
+            self.forwarded_sentences = sentences;
+            self.forwarded_chars = chars;
+        }
+        admitted
+    }
+
+    /// Start over for the next utterance (after its final text)
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Number of completed sentences in `text`
+///
+/// ASCII terminators only count before whitespace or at the end, so
+/// decimals ("3.5") and abbreviations inside words don't split sentences.
+/// Runs of terminators ("?!", "...") count once.
+fn count_sentences(text: &str) -> usize {
+    let mut count = 0;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        let terminal = match c {
+            '。' | '？' | '！' | '…' => true,
+            '.' | '?' | '!' => chars.peek().is_none_or(|next| next.is_whitespace()),
+            _ => false,
+        };
+        if terminal && !chars.peek().is_some_and(|next| is_terminator(*next)) {
+            count += 1;
+        }
+    }
+    count
+}
+
+fn is_terminator(c: char) -> bool {
+    matches!(c, '.' | '?' | '!' | '。' | '？' | '！' | '…')
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+pub const SETTINGS_STEM: &str = "partial_granularity";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sentence_settings(max_pending_chars: usize) -> PartialGranularitySettings {
+        PartialGranularitySettings {
+            granularity: PartialGranularity::Sentence,
+            max_pending_chars,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_count_sentences() {
+        assert_eq!(count_sentences("hello world"), 0);
+        assert_eq!(count_sentences("It costs 3.5 dollars"), 0);
+        assert_eq!(count_sentences("Really?! Yes."), 2);
+        assert_eq!(count_sentences("Wait... what"), 1);
+        assert_eq!(count_sentences("今日は晴れ。明日は"), 1);
+    }
+
+    #[test]
+    fn test_sentence_gate() {
+        let settings = sentence_settings(20);
+        let mut gate = PartialGate::new();
+
+        assert!(!gate.admit("We should", &settings));
+        assert!(!gate.admit("We should ship", &settings));
+        assert!(gate.admit("We should ship it.", &settings));
+        assert!(!gate.admit("We should ship it. Next", &settings));
+        // Long unpunctuated text is forwarded after max_pending_chars
+        assert!(gate.admit("We should ship it. Next week maybe on Friday", &settings));
+
+        gate.reset();
+        assert!(!gate.admit("Then", &settings));
+        assert!(gate.admit("Then again?", &settings));
+
+        let word = PartialGranularitySettings::default();
+        let mut gate = PartialGate::new();
+        assert!(gate.admit("We", &word));
+        assert!(gate.admit("We should", &word));
+    }
+}
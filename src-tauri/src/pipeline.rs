@@ -0,0 +1,346 @@
+//! Post-processing Pipeline
+//!
+//! Declarative, ordered list of stages applied to each final segment after it
+//! has been persisted and broadcast. Stages can be reordered or disabled in
+//! settings without code changes.
+//!
+//! - **Transform stages** (`redaction`, `punctuation`) rewrite the text seen
+//!   by every later stage (e.g. redact before routing webhooks or the LLM)
+//! - **Dispatch stages** (`keyword_alerts`, `routing`, `live_summary`,
+//!   `question_tracking`, `mqtt`, `webhooks`) hand the current text to the
+//!   existing handlers
+//!
+//! Persisted to `settings/pipeline.json` in app data directory.
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+// ============================================================================
+// Settings Struct
+// ============================================================================
+
+/// Stage type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StageKind {
+    /// Replace regex matches (options: `patterns`, `replacement`)
+    Redaction,
+    /// Normalize whitespace and terminate sentences (options: `terminator`)
+    Punctuation,
+    KeywordAlerts,
+    Routing,
+    LiveSummary,
+    QuestionTracking,
+    /// Publish to the MQTT broker (if configured)
+    Mqtt,
+    /// POST to the transcript webhooks (if configured)
+    Webhooks,
+}
+
+/// Single stage entry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StageConfig {
+    pub stage: StageKind,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Stage-specific options
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+    pub options: serde_json::Value,
+}
+
+impl StageConfig {
+    pub fn new(stage: StageKind) -> Self {
+        Self {
+            stage,
+            enabled: true,
+            options: serde_json::Value::Null,
+        }
+    }
+}
+
+/// Pipeline configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PipelineSettings {
+    #[serde(default = "default_stages")]
+    pub stages: Vec<StageConfig>,
+    /// Settings version for future migrations
+    #[serde(default = "default_version")]
+    pub version: u32,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+/// Built-in post-broadcast stages, in their historical order
+fn default_stages() -> Vec<StageConfig> {
+    vec![
+        StageConfig::new(StageKind::KeywordAlerts),
+        StageConfig::new(StageKind::Routing),
+        StageConfig::new(StageKind::LiveSummary),
+        StageConfig::new(StageKind::QuestionTracking),
+        StageConfig::new(StageKind::Mqtt),
+        StageConfig::new(StageKind::Webhooks),
+    ]
+}
+
+impl Default for PipelineSettings {
+    fn default() -> Self {
+        Self {
+            stages: default_stages(),
+            version: 1,
+        }
+    }
+}
+
+// ============================================================================
+// Stage Options
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+struct RedactionOptions {
+    patterns: Vec<String>,
+    #[serde(default = "default_replacement")]
+    replacement: String,
+}
+
+fn default_replacement() -> String {
+    "[REDACTED]".to_string()
+}
+
+#[derive(Debug, Deserialize)]
+struct PunctuationOptions {
+    /// Appended when the segment has no sentence-ending punctuation
+    #[serde(default = "default_terminator")]
+    terminator: String,
+}
+
+fn default_terminator() -> String {
+    "。".to_string()
+}
+
+fn parse_options<T: serde::de::DeserializeOwned>(
+    options: &serde_json::Value,
+    stage: StageKind,
+) -> Result<T> {
+    let options = if options.is_null() {
+        serde_json::json!({})
+    } else {
+        options.clone()
+    };
+    serde_json::from_value(options).with_context(|| format!("Invalid options for {:?}", stage))
+}
+
+// ============================================================================
+// Engine
+// ============================================================================
+
+#[derive(Debug)]
+enum CompiledStage {
+    Redaction {
+        patterns: Vec<Regex>,
+        replacement: String,
+    },
+    Punctuation {
+        terminator: String,
+    },
+    Dispatch(StageKind),
+}
+
+impl CompiledStage {
+    fn compile(config: &StageConfig) -> Result<Self> {
+        Ok(match config.stage {
+            StageKind::Redaction => {
+                let options: RedactionOptions = parse_options(&config.options, config.stage)?;
+                if options.patterns.is_empty() {
+                    anyhow::bail!("Redaction stage needs at least one pattern");
+                }
+                let patterns = options
+                    .patterns
+                    .iter()
+                    .map(|p| {
+                        Regex::new(p).with_context(|| format!("Invalid redaction pattern: {}", p))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                CompiledStage::Redaction {
+                    patterns,
+                    replacement: options.replacement,
+                }
+            }
+            StageKind::Punctuation => {
+                let options: PunctuationOptions = parse_options(&config.options, config.stage)?;
+                CompiledStage::Punctuation {
+                    terminator: options.terminator,
+                }
+            }
+            kind => CompiledStage::Dispatch(kind),
+        })
+    }
+
+    fn transform(&self, text: &str) -> String {
+        match self {
+            CompiledStage::Redaction {
+                patterns,
+                replacement,
+            } => patterns.iter().fold(text.to_string(), |acc, pattern| {
+                pattern.replace_all(&acc, replacement.as_str()).into_owned()
+            }),
+            CompiledStage::Punctuation { terminator } => {
+                let normalized = text.split_whitespace().collect::<Vec<_>>().join(" ");
+                let terminated = normalized.ends_with(['。', '.', '?', '？', '!', '！']);
+                if normalized.is_empty() || terminated {
+                    normalized
+                } else {
+                    normalized + terminator
+                }
+            }
+            CompiledStage::Dispatch(_) => text.to_string(),
+        }
+    }
+}
+
+/// Dispatch stage to run with the text at that point of the pipeline
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StageAction {
+    pub stage: StageKind,
+    pub text: String,
+}
+
+/// Compiled pipeline
+#[derive(Debug)]
+pub struct Pipeline {
+    stages: Vec<CompiledStage>,
+}
+
+impl Default for Pipeline {
+    fn default() -> Self {
+        Self::compile(&PipelineSettings::default()).expect("default pipeline compiles")
+    }
+}
+
+impl Pipeline {
+    /// Compile settings; fails on duplicate stages or invalid options
+    pub fn compile(settings: &PipelineSettings) -> Result<Self> {
+        let mut seen = HashSet::new();
+        let mut stages = Vec::new();
+        for config in &settings.stages {
+            if !seen.insert(config.stage) {
+                anyhow::bail!("Stage {:?} is listed more than once", config.stage);
+            }
+            let compiled = CompiledStage::compile(config)?;
+            if config.enabled {
+                stages.push(compiled);
+            }
+        }
+        Ok(Self { stages })
+    }
+
+    /// Run transform stages and list the dispatch stages to execute, in order
+    pub fn plan(&self, text: &str) -> Vec<StageAction> {
+        let mut current = text.to_string();
+        let mut actions = Vec::new();
+        for stage in &self.stages {
+            match stage {
+                CompiledStage::Dispatch(kind) => actions.push(StageAction {
+                    stage: *kind,
+                    text: current.clone(),
+                }),
+                transform => current = transform.transform(&current),
+            }
+        }
+        actions
+    }
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+pub const SETTINGS_STEM: &str = "pipeline";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn stages(pipeline: &Pipeline, text: &str) -> Vec<StageKind> {
+        pipeline.plan(text).into_iter().map(|a| a.stage).collect()
+    }
+
+    #[test]
+    fn test_default_pipeline_keeps_builtin_order() {
+        let pipeline = Pipeline::default();
+        assert_eq!(
+            stages(&pipeline, "hello"),
+            vec![
+                StageKind::KeywordAlerts,
+                StageKind::Routing,
+                StageKind::LiveSummary,
+                StageKind::QuestionTracking,
+                StageKind::Mqtt,
+                StageKind::Webhooks
+            ]
+        );
+    }
+
+    #[test]
+    fn test_transforms_apply_to_later_stages_only() {
+        let settings = PipelineSettings {
+            stages: vec![
+                StageConfig::new(StageKind::KeywordAlerts),
+                StageConfig {
+                    stage: StageKind::Redaction,
+                    enabled: true,
+                    options: json!({ "patterns": [r"\d{3}-\d{4}-\d{4}"] }),
+                },
+                StageConfig::new(StageKind::Punctuation),
+                StageConfig::new(StageKind::Routing),
+                StageConfig {
+                    enabled: false,
+                    ..StageConfig::new(StageKind::LiveSummary)
+                },
+            ],
+            ..Default::default()
+        };
+        let pipeline = Pipeline::compile(&settings).unwrap();
+
+        let actions = pipeline.plan("電話は  090-1234-5678 です");
+        assert_eq!(actions.len(), 2);
+        assert_eq!(actions[0].stage, StageKind::KeywordAlerts);
+        assert_eq!(actions[0].text, "電話は  090-1234-5678 です");
+        assert_eq!(actions[1].stage, StageKind::Routing);
+        assert_eq!(actions[1].text, "電話は [REDACTED] です。");
+    }
+
+    #[test]
+    fn test_compile_rejects_invalid_pipelines() {
+        let duplicate = PipelineSettings {
+            stages: vec![
+                StageConfig::new(StageKind::Routing),
+                StageConfig::new(StageKind::Routing),
+            ],
+            ..Default::default()
+        };
+        assert!(Pipeline::compile(&duplicate).is_err());
+
+        let bad_pattern = PipelineSettings {
+            stages: vec![StageConfig {
+                stage: StageKind::Redaction,
+                enabled: true,
+                options: json!({ "patterns": ["(unclosed"] }),
+            }],
+            ..Default::default()
+        };
+        assert!(Pipeline::compile(&bad_pattern).is_err());
+
+        // Unknown stage names are rejected when parsing settings
+        let unknown = r#"{"stages":[{"stage":"translation"}]}"#;
+        assert!(serde_json::from_str::<PipelineSettings>(unknown).is_err());
+    }
+}
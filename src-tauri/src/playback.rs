@@ -0,0 +1,302 @@
+//! Session Audio Playback
+//!
+//! Plays a saved session through the default output device for in-app
+//! review. The 16kHz mono session audio (WAV, FLAC or Opus) is decoded into
+//! memory and resampled (linear interpolation) to the device's rate, with the
+//! mono signal copied to every output channel.
+//!
+//! One playback at a time: the caller stops the previous player before
+//! starting the next. `on_finished` runs when the audio runs out, not when
+//! playback is stopped.
+//!
+//! [`AudioPlayer::play`] plays arbitrary mono samples on a chosen output
+//! device (used for the recording consent announcement).
+
+use anyhow::{anyhow, Context, Result};
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use std::path::Path;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// Session audio sample rate (see `storage::AudioWriter`)
+pub const SAMPLE_RATE: u32 = 16000;
+
+/// How often the player thread checks for the end of the audio
+const END_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Read position in decoded mono audio
+pub struct PlaybackCursor {
+    samples: Vec<i16>,
+    source_rate: u32,
+    /// Position in source samples (fractional while resampling)
+    position: f64,
+    /// Source samples per output frame
+    step: f64,
+}
+
+impl PlaybackCursor {
+    pub fn new(samples: Vec<i16>, source_rate: u32, start_ms: u64, output_rate: u32) -> Self {
+        let source_rate = source_rate.max(1);
+        Self {
+            samples,
+            source_rate,
+            position: (start_ms * source_rate as u64 / 1000) as f64,
+            step: source_rate as f64 / output_rate.max(1) as f64,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.position >= self.samples.len() as f64
+    }
+
+    /// Current position (ms)
+    pub fn position_ms(&self) -> u64 {
+        (self.position * 1000.0 / self.source_rate as f64) as u64
+    }
+
+    /// Fill interleaved frames of `channels` channels (silence past the end)
+    pub fn fill(&mut self, out: &mut [f32], channels: usize) {
+        for frame in out.chunks_mut(channels.max(1)) {
+            let value = self.next_sample();
+            frame.fill(value);
+        }
+    }
+
+    fn next_sample(&mut self) -> f32 {
+        let index = self.position as usize;
+        let Some(&current) = self.samples.get(index) else {
+            return 0.0;
+        };
+        let next = self.samples.get(index + 1).copied().unwrap_or(current);
+        let frac = (self.position - index as f64) as f32;
+        self.position += self.step;
+        (current as f32 + (next as f32 - current as f32) * frac) / 32768.0
+    }
+}
+
+/// Playback of mono audio on an output device
+pub struct AudioPlayer {
+    id: String,
+    cursor: Arc<Mutex<PlaybackCursor>>,
+    shutdown_tx: mpsc::Sender<()>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl AudioPlayer {
+    /// Decode the session audio and start playing it from `start_ms`
+    pub fn start<F>(
+        session_id: &str,
+        session_dir: &Path,
+        start_ms: u64,
+        on_finished: F,
+    ) -> Result<Self>
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let mut samples = Vec::new();
+        crate::storage::read_session_audio(session_dir, |chunk| samples.extend_from_slice(chunk))
+            .with_context(|| format!("Failed to read session audio: {:?}", session_dir))?;
+        Self::play(
+            session_id,
+            samples,
+            SAMPLE_RATE,
+            start_ms,
+            None,
+            on_finished,
+        )
+    }
+
+    /// Play mono `samples` from `start_ms` on `output_device` (by name;
+    /// None plays on the default output device)
+    ///
+    /// `id` identifies what is playing (e.g. the session ID).
+    pub fn play<F>(
+        id: &str,
+        samples: Vec<i16>,
+        sample_rate: u32,
+        start_ms: u64,
+        output_device: Option<&str>,
+        on_finished: F,
+    ) -> Result<Self>
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let duration_ms = samples.len() as u64 * 1000 / sample_rate.max(1) as u64;
+        if start_ms >= duration_ms {
+            anyhow::bail!(
+                "start_ms {} is past the end of the audio ({} ms)",
+                start_ms,
+                duration_ms
+            );
+        }
+
+        let device = find_output_device(output_device)?;
+        let supported = device.default_output_config()?;
+        let sample_format = supported.sample_format();
+        let config: cpal::StreamConfig = supported.into();
+
+        let cursor = Arc::new(Mutex::new(PlaybackCursor::new(
+            samples,
+            sample_rate,
+            start_ms,
+            config.sample_rate.0,
+        )));
+        let (shutdown_tx, shutdown_rx) = mpsc::channel();
+        let (ready_tx, ready_rx) = mpsc::channel();
+
+        // The stream is built and dropped on its own thread (cpal streams
+        // are not Send on every platform)
+        let stream_cursor = Arc::clone(&cursor);
+        let thread = std::thread::spawn(move || {
+            let stream = match sample_format {
+                cpal::SampleFormat::F32 => {
+                    build_stream::<f32>(&device, &config, Arc::clone(&stream_cursor))
+                }
+                cpal::SampleFormat::I16 => {
+                    build_stream::<i16>(&device, &config, Arc::clone(&stream_cursor))
+                }
+                cpal::SampleFormat::U16 => {
+                    build_stream::<u16>(&device, &config, Arc::clone(&stream_cursor))
+                }
+            };
+            let stream = match stream {
+                Ok(stream) => {
+                    ready_tx.send(Ok(())).ok();
+                    stream
+                }
+                Err(e) => {
+                    ready_tx.send(Err(e)).ok();
+                    return;
+                }
+            };
+
+            loop {
+                match shutdown_rx.recv_timeout(END_POLL_INTERVAL) {
+                    Err(RecvTimeoutError::Timeout) => {
+                        if stream_cursor.lock().unwrap().is_finished() {
+                            break;
+                        }
+                    }
+                    // Stopped
+                    _ => return,
+                }
+            }
+            drop(stream);
+            on_finished();
+        });
+
+        ready_rx
+            .recv()
+            .map_err(|_| anyhow!("Playback thread exited before starting"))??;
+
+        Ok(Self {
+            id: id.to_string(),
+            cursor,
+            shutdown_tx,
+            thread: Some(thread),
+        })
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Current position (ms)
+    pub fn position_ms(&self) -> u64 {
+        self.cursor.lock().unwrap().position_ms()
+    }
+
+    /// Stop playback and release the output device
+    ///
+    /// Returns the position playback stopped at (ms).
+    pub fn stop(mut self) -> u64 {
+        self.shutdown();
+        self.position_ms()
+    }
+
+    fn shutdown(&mut self) {
+        self.shutdown_tx.send(()).ok();
+        if let Some(thread) = self.thread.take() {
+            thread.join().ok();
+        }
+    }
+}
+
+impl Drop for AudioPlayer {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+/// Names of the available output devices
+pub fn output_device_names() -> Result<Vec<String>> {
+    let host = cpal::default_host();
+    Ok(host
+        .output_devices()?
+        .filter_map(|d| d.name().ok())
+        .collect())
+}
+
+/// Output device by name, or the default output device
+fn find_output_device(name: Option<&str>) -> Result<cpal::Device> {
+    let host = cpal::default_host();
+    match name {
+        Some(name) => host
+            .output_devices()?
+            .find(|d| d.name().ok().as_deref() == Some(name))
+            .ok_or_else(|| anyhow!("Output device not found: {}", name)),
+        None => host
+            .default_output_device()
+            .ok_or_else(|| anyhow!("No audio output device available")),
+    }
+}
+
+/// Output stream pulling samples of type `T` from `cursor`
+fn build_stream<T: cpal::Sample>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    cursor: Arc<Mutex<PlaybackCursor>>,
+) -> Result<cpal::Stream> {
+    let channels = config.channels as usize;
+    let mut scratch: Vec<f32> = Vec::new();
+    let stream = device.build_output_stream(
+        config,
+        move |data: &mut [T], _: &cpal::OutputCallbackInfo| {
+            scratch.resize(data.len(), 0.0);
+            cursor.lock().unwrap().fill(&mut scratch, channels);
+            for (out, value) in data.iter_mut().zip(&scratch) {
+                *out = T::from(value);
+            }
+        },
+        |err| {
+            log_warn!("playback", "stream_error", format!("{:?}", err));
+        },
+    )?;
+    stream.play()?;
+    Ok(stream)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_resamples_to_output() {
+        // 16kHz -> 32kHz stereo: every source sample spans two frames
+        let mut cursor = PlaybackCursor::new(vec![0, 16384, 0], SAMPLE_RATE, 0, 32000);
+        let mut out = vec![1.0f32; 16];
+        cursor.fill(&mut out, 2);
+        assert_eq!(&out[..6], &[0.0, 0.0, 0.25, 0.25, 0.5, 0.5]);
+        assert_eq!(&out[8..12], &[0.0, 0.0, 0.0, 0.0]);
+        // Silence once the audio ran out
+        assert!(cursor.is_finished());
+        assert_eq!(&out[12..], &[0.0; 4]);
+
+        // start_ms skips into the audio
+        let cursor = PlaybackCursor::new(vec![0; 32000], SAMPLE_RATE, 1500, 48000);
+        assert_eq!(cursor.position_ms(), 1500);
+        assert!(!cursor.is_finished());
+    }
+}
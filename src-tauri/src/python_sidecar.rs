@@ -39,6 +39,9 @@ pub enum PythonSidecarError {
     #[error("Python process not running")]
     ProcessNotRunning,
 
+    #[error("No protocol version in common with the sidecar: {0}")]
+    IncompatibleProtocol(String),
+
     #[error("Python detection failed: {0}")]
     DetectionFailed(#[from] PythonDetectionError),
 }
@@ -51,22 +54,72 @@ pub struct PythonSidecarManager {
     process: Option<tokio::process::Child>,
     stdin: Option<tokio::process::ChildStdin>,
     stdout: Option<tokio::io::BufReader<tokio::process::ChildStdout>>,
+    /// Binary audio channel, when the sidecar accepted one
+    audio_channel: Option<crate::sidecar_audio_channel::AudioChannel>,
+    /// Protocol version agreed in `wait_for_ready`
+    protocol: Option<crate::ipc_protocol::NegotiatedProtocol>,
+    /// Messages that arrived while waiting for the `hello` answer
+    pending_messages: std::collections::VecDeque<serde_json::Value>,
+    /// Transcription worker pool passed to the sidecar at start
+    transcription_workers: crate::transcription_workers::TranscriptionWorkerSettings,
+    /// Cloud STT passed to the sidecar at start, with its API key
+    cloud_stt: crate::cloud_stt::CloudSttSettings,
+    cloud_stt_api_key: Option<String>,
 }
 
+/// Time the sidecar has to answer `hello`
+const HELLO_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
 impl PythonSidecarManager {
     pub fn new() -> Self {
         Self {
             process: None,
             stdin: None,
             stdout: None,
+            audio_channel: None,
+            protocol: None,
+            pending_messages: std::collections::VecDeque::new(),
+            transcription_workers: Default::default(),
+            cloud_stt: Default::default(),
+            cloud_stt_api_key: None,
         }
     }
 
+    /// Run final transcription in a worker pool (`transcription_workers`)
+    ///
+    /// Takes effect at the next `start`.
+    pub fn set_transcription_workers(
+        &mut self,
+        settings: crate::transcription_workers::TranscriptionWorkerSettings,
+    ) {
+        self.transcription_workers = settings;
+    }
+
+    /// Transcribe with a cloud provider as fallback or instead of the local
+    /// model (`cloud_stt`)
+    ///
+    /// Takes effect at the next `start`.
+    pub fn set_cloud_stt(
+        &mut self,
+        settings: crate::cloud_stt::CloudSttSettings,
+        api_key: Option<String>,
+    ) {
+        self.cloud_stt = settings;
+        self.cloud_stt_api_key = api_key;
+    }
+
     /// Check if the Python process is currently running
     pub fn is_running(&self) -> bool {
         self.process.is_some()
     }
 
+    /// Check if the started Python process has exited since
+    pub fn has_exited(&mut self) -> bool {
+        self.process
+            .as_mut()
+            .is_some_and(|process| !matches!(process.try_wait(), Ok(None)))
+    }
+
     /// Detect Python executable following the 6-step algorithm (design.md compliant)
     ///
     /// Priority order:
@@ -293,11 +346,18 @@ impl PythonSidecarManager {
         let mut child = Command::new(&python_path)
             .arg("-u") // Unbuffered stdout/stderr (critical for IPC handshake)
             .arg(&script_path)
+            .args(self.transcription_workers.sidecar_args())
+            .args(self.cloud_stt.sidecar_args())
             .stdin(std::process::Stdio::piped())
             .stdout(std::process::Stdio::piped())
             .stderr(std::process::Stdio::piped())
             .env_clear() // Clear inherited env
             .envs(std::env::vars()) // Re-add all current env vars (includes TEST_FIXTURE_MODE)
+            .envs(
+                self.cloud_stt_api_key
+                    .iter()
+                    .map(|key| (crate::cloud_stt::API_KEY_ENV, key)),
+            ) // Not on the command line, where other processes can read it
             .spawn()
             .map_err(|e| PythonSidecarError::StartupFailed(e.to_string()))?;
 
@@ -311,6 +371,11 @@ impl PythonSidecarManager {
             .take()
             .ok_or_else(|| PythonSidecarError::StartupFailed("Failed to get stdout".to_string()))?;
 
+        // Forward stderr (Python logging, tracebacks) to the structured logger
+        if let Some(stderr) = child.stderr.take() {
+            crate::sidecar_stderr::spawn_reader(stderr);
+        }
+
         // Store process and streams
         self.stdin = Some(stdin);
         self.stdout = Some(BufReader::new(stdout));
@@ -352,6 +417,7 @@ impl PythonSidecarManager {
 
             // Check if this is the 'ready' message
             if msg.get("type").and_then(|v| v.as_str()) == Some("ready") {
+                self.protocol = Some(self.negotiate_protocol().await?);
                 return Ok(());
             }
 
@@ -360,6 +426,92 @@ impl PythonSidecarManager {
         }
     }
 
+    /// Agree on the protocol version with a `hello` exchange
+    ///
+    /// Messages arriving before the answer (e.g. events) are kept for
+    /// `receive_message`. An older sidecar answers `UNKNOWN_METHOD` and is
+    /// taken to speak `PROTOCOL_VERSION`.
+    async fn negotiate_protocol(
+        &mut self,
+    ) -> Result<crate::ipc_protocol::NegotiatedProtocol, PythonSidecarError> {
+        use crate::ipc_protocol::{hello_reply, hello_request, IpcMessage};
+
+        let id = crate::message_id::next_id(crate::message_id::CONTROL, "sidecar");
+        let request = serde_json::to_value(hello_request(&id))
+            .map_err(|e| PythonSidecarError::CommunicationFailed(e.to_string()))?;
+        self.send_message(request).await?;
+
+        let mut skipped = Vec::new();
+        let reply = tokio::time::timeout(HELLO_TIMEOUT, async {
+            loop {
+                let message = self.read_message().await?;
+                if let Ok(decoded) = serde_json::from_value::<IpcMessage>(message.clone()) {
+                    if let Some(reply) = hello_reply(&id, &decoded) {
+                        return Ok::<_, PythonSidecarError>(reply);
+                    }
+                }
+                skipped.push(message);
+            }
+        })
+        .await
+        .map_err(|_| PythonSidecarError::CommunicationFailed("No answer to hello".to_string()))??;
+        self.pending_messages.extend(skipped);
+        reply.map_err(PythonSidecarError::IncompatibleProtocol)
+    }
+
+    /// Protocol version agreed with the sidecar (None before `wait_for_ready`)
+    pub fn protocol(&self) -> Option<&crate::ipc_protocol::NegotiatedProtocol> {
+        self.protocol.as_ref()
+    }
+
+    /// Offer the sidecar a binary audio channel (`sidecar_audio_channel`)
+    ///
+    /// Call after `wait_for_ready`, before stdin/stdout are taken. Returns
+    /// whether the sidecar accepted; otherwise audio stays on stdin. Other
+    /// messages arriving meanwhile (e.g. events) are skipped.
+    pub async fn negotiate_audio_channel(&mut self) -> Result<bool, PythonSidecarError> {
+        use crate::ipc_protocol::{
+            audio_channel_accepted, audio_channel_offer_request, IpcMessage,
+        };
+        use crate::sidecar_audio_channel::{AudioChannelListener, CONNECT_TIMEOUT};
+
+        let listener = AudioChannelListener::bind()
+            .map_err(|e| PythonSidecarError::CommunicationFailed(format!("{:#}", e)))?;
+        let id = crate::message_id::next_id(crate::message_id::CONTROL, "sidecar");
+        let request = audio_channel_offer_request(&id, &listener.offer());
+        let request = serde_json::to_value(&request)
+            .map_err(|e| PythonSidecarError::CommunicationFailed(e.to_string()))?;
+        self.send_message(request).await?;
+
+        let answer = tokio::time::timeout(CONNECT_TIMEOUT, async {
+            loop {
+                let message = self.receive_message().await?;
+                let Ok(message) = serde_json::from_value::<IpcMessage>(message) else {
+                    continue;
+                };
+                if let Some(accepted) = audio_channel_accepted(&id, &message) {
+                    return Ok::<_, PythonSidecarError>(accepted);
+                }
+            }
+        })
+        .await
+        .map_err(|_| {
+            PythonSidecarError::CommunicationFailed(
+                "No answer to the audio channel offer".to_string(),
+            )
+        })??;
+        if !answer {
+            return Ok(false);
+        }
+
+        let channel = listener
+            .accept()
+            .await
+            .map_err(|e| PythonSidecarError::CommunicationFailed(format!("{:#}", e)))?;
+        self.audio_channel = Some(channel);
+        Ok(true)
+    }
+
     /// Send a JSON message to Python sidecar via stdin
     pub async fn send_message(
         &mut self,
@@ -395,6 +547,14 @@ impl PythonSidecarManager {
 
     /// Receive a message from Python sidecar
     pub async fn receive_message(&mut self) -> Result<serde_json::Value, PythonSidecarError> {
+        if let Some(message) = self.pending_messages.pop_front() {
+            return Ok(message);
+        }
+        self.read_message().await
+    }
+
+    /// Read the next message from stdout
+    async fn read_message(&mut self) -> Result<serde_json::Value, PythonSidecarError> {
         use tokio::io::AsyncBufReadExt;
 
         let stdout = self
@@ -488,6 +648,12 @@ impl PythonSidecarManager {
         self.stdout.take()
     }
 
+    /// Take ownership of the binary audio channel for the stdin writer task
+    /// None when the sidecar declined it (audio then goes over stdin)
+    pub fn take_audio_channel(&mut self) -> Option<crate::sidecar_audio_channel::AudioChannel> {
+        self.audio_channel.take()
+    }
+
     /// Get process ID (for testing)
     pub fn get_process_id(&self) -> Option<u32> {
         self.process.as_ref().and_then(|p| p.id())
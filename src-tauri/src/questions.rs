@@ -0,0 +1,295 @@
+//! Question Tracking
+//!
+//! Detects question-form final segments and tracks whether they get an
+//! apparent answer within a window. A later non-question segment counts as an
+//! answer when it starts with an answer cue ("はい", "yes", ...) or shares a
+//! content word with the question. Questions without an answer when the
+//! window closes are reported as open.
+//!
+//! Heuristic only: it lists candidates for the facilitator to follow up on.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use crate::storage::TranscriptionEvent;
+
+/// Default answer window (ms)
+pub const DEFAULT_ANSWER_WINDOW_MS: u64 = 2 * 60 * 1000;
+
+/// Sentence endings that mark a Japanese question without "？"
+const JA_QUESTION_ENDINGS: &[&str] = &[
+    "ですか",
+    "ますか",
+    "でしょうか",
+    "ませんか",
+    "のか",
+    "かな",
+    "だろうか",
+    "どう",
+];
+
+/// Leading words of an English question
+const EN_QUESTION_STARTS: &[&str] = &[
+    "what", "why", "how", "when", "where", "who", "which", "can", "could", "would", "should", "do",
+    "does", "did", "is", "are", "will", "shall",
+];
+
+/// Leading words that mark a reply
+const ANSWER_CUES: &[&str] = &[
+    "はい",
+    "いいえ",
+    "いえ",
+    "ええ",
+    "そうですね",
+    "それは",
+    "yes",
+    "no",
+    "yeah",
+    "sure",
+    "i think",
+    "we will",
+    "it is",
+];
+
+/// Check whether a final segment is phrased as a question
+pub fn is_question(text: &str) -> bool {
+    let trimmed = text.trim();
+    if trimmed.ends_with('?') || trimmed.ends_with('？') {
+        return true;
+    }
+
+    let body = trimmed.trim_end_matches(['。', '.', '!', '！', '、', ' ']);
+    if JA_QUESTION_ENDINGS
+        .iter()
+        .any(|ending| body.ends_with(ending))
+    {
+        return true;
+    }
+
+    let lower = body.to_lowercase();
+    lower
+        .split_whitespace()
+        .next()
+        .map(|first| EN_QUESTION_STARTS.contains(&first) && lower.split_whitespace().count() >= 4)
+        .unwrap_or(false)
+}
+
+fn starts_with_answer_cue(text: &str) -> bool {
+    let lower = text.trim().to_lowercase();
+    ANSWER_CUES.iter().any(|cue| {
+        lower.starts_with(cue)
+            && !lower[cue.len()..]
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_ascii_alphanumeric())
+    })
+}
+
+/// Content tokens: latin words of 4+ letters and kanji/katakana bigrams
+fn content_tokens(text: &str) -> HashSet<String> {
+    let lower = text.to_lowercase();
+    let mut tokens: HashSet<String> = lower
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| word.len() >= 4)
+        .map(str::to_string)
+        .collect();
+
+    let is_content_char = |c: char| matches!(c, '\u{4E00}'..='\u{9FFF}' | '\u{30A0}'..='\u{30FF}');
+    let chars: Vec<char> = lower.chars().collect();
+    for pair in chars.windows(2) {
+        if is_content_char(pair[0]) && is_content_char(pair[1]) {
+            tokens.insert(pair.iter().collect());
+        }
+    }
+    tokens
+}
+
+/// Question and its apparent answer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrackedQuestion {
+    /// Session-relative timestamp (ms)
+    pub timestamp_ms: u64,
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub answered_at_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub answer: Option<String>,
+}
+
+impl TrackedQuestion {
+    pub fn is_answered(&self) -> bool {
+        self.answered_at_ms.is_some()
+    }
+}
+
+/// Change produced by a final segment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuestionUpdate {
+    Detected(TrackedQuestion),
+    Answered(TrackedQuestion),
+}
+
+/// Questions of a session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuestionReport {
+    pub questions: Vec<TrackedQuestion>,
+    /// Questions without an apparent answer
+    pub open: Vec<TrackedQuestion>,
+}
+
+/// Tracks questions over the final segments of a session
+#[derive(Debug, Clone)]
+pub struct QuestionTracker {
+    window_ms: u64,
+    questions: Vec<TrackedQuestion>,
+}
+
+impl Default for QuestionTracker {
+    fn default() -> Self {
+        Self::new(DEFAULT_ANSWER_WINDOW_MS)
+    }
+}
+
+impl QuestionTracker {
+    pub fn new(window_ms: u64) -> Self {
+        Self {
+            window_ms,
+            questions: Vec::new(),
+        }
+    }
+
+    /// Observe a final segment
+    pub fn observe(&mut self, timestamp_ms: u64, text: &str) -> Vec<QuestionUpdate> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Vec::new();
+        }
+
+        if is_question(text) {
+            let question = TrackedQuestion {
+                timestamp_ms,
+                text: text.to_string(),
+                answered_at_ms: None,
+                answer: None,
+            };
+            self.questions.push(question.clone());
+            return vec![QuestionUpdate::Detected(question)];
+        }
+
+        let cue = starts_with_answer_cue(text);
+        let tokens = content_tokens(text);
+        let window_ms = self.window_ms;
+        let mut updates = Vec::new();
+        // Latest open question in the window first: a reply usually answers it
+        for question in self.questions.iter_mut().rev() {
+            if question.is_answered()
+                || timestamp_ms < question.timestamp_ms
+                || timestamp_ms - question.timestamp_ms > window_ms
+            {
+                continue;
+            }
+            let overlaps = !tokens.is_disjoint(&content_tokens(&question.text));
+            if overlaps || (cue && updates.is_empty()) {
+                question.answered_at_ms = Some(timestamp_ms);
+                question.answer = Some(text.to_string());
+                updates.push(QuestionUpdate::Answered(question.clone()));
+            }
+        }
+        updates
+    }
+
+    /// All questions with their current state
+    pub fn report(&self) -> QuestionReport {
+        QuestionReport {
+            questions: self.questions.clone(),
+            open: self
+                .questions
+                .iter()
+                .filter(|q| !q.is_answered())
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// Approximate heap usage (bytes), for the memory sentinel
+    pub fn approx_bytes(&self) -> usize {
+        self.questions
+            .iter()
+            .map(|q| {
+                std::mem::size_of::<TrackedQuestion>()
+                    + q.text.capacity()
+                    + q.answer.as_ref().map_or(0, String::capacity)
+            })
+            .sum()
+    }
+
+    /// Replay the final segments of a saved transcript
+    pub fn from_transcript(events: &[TranscriptionEvent], window_ms: u64) -> Self {
+        let mut tracker = Self::new(window_ms);
+        for event in events.iter().filter(|e| e.is_final) {
+            tracker.observe(event.timestamp_ms, &event.text);
+        }
+        tracker
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_question() {
+        assert!(is_question("予算はいくらですか"));
+        assert!(is_question("来週までに終わりますか。"));
+        assert!(is_question("Is the draft ready?"));
+        assert!(is_question("What is the deadline for this"));
+        assert!(is_question("締め切りは？"));
+
+        assert!(!is_question("予算は承認されました。"));
+        assert!(!is_question("What a day"));
+        assert!(!is_question("This is done."));
+    }
+
+    #[test]
+    fn test_answer_by_cue_or_overlap() {
+        let mut tracker = QuestionTracker::new(60_000);
+        let updates = tracker.observe(1_000, "予算の上限はいくらですか");
+        assert!(matches!(updates[0], QuestionUpdate::Detected(_)));
+        tracker.observe(2_000, "Is the launch date fixed?");
+
+        // Shares "予算" with the first question
+        let updates = tracker.observe(5_000, "予算は500万円です");
+        assert_eq!(updates.len(), 1);
+        match &updates[0] {
+            QuestionUpdate::Answered(q) => assert_eq!(q.timestamp_ms, 1_000),
+            other => panic!("Expected answer, got {:?}", other),
+        }
+
+        // Cue answers the latest open question
+        tracker.observe(6_000, "Yes, next Monday.");
+        let report = tracker.report();
+        assert_eq!(report.questions.len(), 2);
+        assert!(report.open.is_empty());
+    }
+
+    #[test]
+    fn test_unanswered_outside_window_stays_open() {
+        let events = vec![
+            TranscriptionEvent {
+                timestamp_ms: 0,
+                text: "誰が議事録を担当しますか".to_string(),
+                is_final: true,
+                speaker: None,
+            },
+            TranscriptionEvent {
+                timestamp_ms: 120_000,
+                text: "はい、では次の議題です".to_string(),
+                is_final: true,
+                speaker: None,
+            },
+        ];
+        let report = QuestionTracker::from_transcript(&events, 60_000).report();
+        assert_eq!(report.open.len(), 1);
+        assert_eq!(report.open[0].text, "誰が議事録を担当しますか");
+    }
+}
@@ -0,0 +1,350 @@
+//! Outbound Rate Limiting
+//!
+//! Transcript webhooks, routing rule webhooks and operational webhooks each
+//! fire per event, so a burst of final segments used to become a burst of
+//! POSTs to the same service — enough to get a Zapier/n8n token throttled
+//! or banned. Every outbound request now takes a token from a shared
+//! limiter first:
+//!
+//! - One **token bucket per target** (scheme + host + port of the URL), so
+//!   webhooks of the same service share a budget and other services are
+//!   unaffected. `burst` tokens, refilled at `requests_per_minute`.
+//! - A 429 or 503 response with `Retry-After` (seconds or HTTP date) blocks
+//!   the target until then, capped at `max_retry_after_secs`. Requests wait
+//!   instead of failing; retries of transcript webhooks wait as well.
+//!
+//! The Google Docs client in the Chrome extension has its own limiter and
+//! honors `Retry-After` in its backoff.
+//!
+//! Settings are machine-wide (not per workspace); persisted to
+//! `settings/rate_limits.json` in app data directory.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Most targets tracked; idle full buckets are dropped beyond this
+const MAX_TARGETS: usize = 256;
+
+/// Rate limit configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimitSettings {
+    /// Apply rate limits (off: requests go out immediately)
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Sustained requests per minute to one target
+    #[serde(default = "default_requests_per_minute")]
+    pub requests_per_minute: u32,
+    /// Requests a target may receive back to back
+    #[serde(default = "default_burst")]
+    pub burst: u32,
+    /// Longest `Retry-After` honored
+    #[serde(default = "default_max_retry_after_secs")]
+    pub max_retry_after_secs: u64,
+    /// Settings version for future migrations
+    #[serde(default = "default_version")]
+    pub version: u32,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_requests_per_minute() -> u32 {
+    60
+}
+
+fn default_burst() -> u32 {
+    10
+}
+
+fn default_max_retry_after_secs() -> u64 {
+    300
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+impl Default for RateLimitSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            requests_per_minute: default_requests_per_minute(),
+            burst: default_burst(),
+            max_retry_after_secs: default_max_retry_after_secs(),
+            version: 1,
+        }
+    }
+}
+
+impl RateLimitSettings {
+    pub fn validate(&self) -> Result<()> {
+        if !(1..=6000).contains(&self.requests_per_minute) {
+            anyhow::bail!("requests_per_minute must be between 1 and 6000");
+        }
+        if !(1..=1000).contains(&self.burst) {
+            anyhow::bail!("burst must be between 1 and 1000");
+        }
+        if self.max_retry_after_secs > 3600 {
+            anyhow::bail!("max_retry_after_secs must be at most 3600");
+        }
+        Ok(())
+    }
+
+    fn refill_interval(&self) -> Duration {
+        Duration::from_secs(60) / self.requests_per_minute.max(1)
+    }
+}
+
+/// Target of a URL: `scheme://host[:port]`, lowercased
+pub fn target_key(url: &str) -> String {
+    let (scheme, rest) = url.split_once("://").unwrap_or(("", url));
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host = authority.rsplit('@').next().unwrap_or_default();
+    format!("{}://{}", scheme, host).to_ascii_lowercase()
+}
+
+/// Parse a `Retry-After` header: delay seconds or an HTTP date
+pub fn parse_retry_after(value: &str, now: chrono::DateTime<chrono::Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let at = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (at.with_timezone(&chrono::Utc) - now)
+            .to_std()
+            .unwrap_or_default(),
+    )
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: u32,
+    refilled_at: Instant,
+    blocked_until: Option<Instant>,
+    waits: u64,
+}
+
+impl Bucket {
+    fn refill(&mut self, settings: &RateLimitSettings, now: Instant) {
+        let interval = settings.refill_interval();
+        let elapsed = now.saturating_duration_since(self.refilled_at);
+        let earned = (elapsed.as_nanos() / interval.as_nanos()) as u64;
+        if earned == 0 {
+            return;
+        }
+        let tokens = (self.tokens as u64 + earned).min(settings.burst as u64) as u32;
+        self.tokens = tokens;
+        self.refilled_at = if tokens == settings.burst {
+            now
+        } else {
+            self.refilled_at + interval * earned as u32
+        };
+    }
+
+    fn is_idle(&self, settings: &RateLimitSettings, now: Instant) -> bool {
+        self.tokens >= settings.burst && self.blocked_until.is_none_or(|until| until <= now)
+    }
+}
+
+/// Limiter state of one target
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RateLimitTargetSnapshot {
+    pub target: String,
+    pub available_tokens: u32,
+    /// Remaining `Retry-After` block
+    pub blocked_ms: u64,
+    /// Requests that had to wait
+    pub waits: u64,
+}
+
+/// Token buckets of all outbound targets
+#[derive(Debug, Default)]
+pub struct OutboundRateLimiter {
+    buckets: HashMap<String, Bucket>,
+}
+
+impl OutboundRateLimiter {
+    /// Take a token for a request to `url`
+    ///
+    /// Returns None when the request may go out now, or how long to wait
+    /// before asking again.
+    pub fn acquire(
+        &mut self,
+        url: &str,
+        settings: &RateLimitSettings,
+        now: Instant,
+    ) -> Option<Duration> {
+        if !settings.enabled {
+            return None;
+        }
+        let bucket = self.bucket(url, settings, now);
+        if let Some(until) = bucket.blocked_until.filter(|&until| until > now) {
+            bucket.waits += 1;
+            return Some(until - now);
+        }
+        bucket.blocked_until = None;
+        bucket.refill(settings, now);
+        if bucket.tokens > 0 {
+            bucket.tokens -= 1;
+            return None;
+        }
+        bucket.waits += 1;
+        Some((bucket.refilled_at + settings.refill_interval()).saturating_duration_since(now))
+    }
+
+    /// The target of `url` asked to wait `delay` (`Retry-After`)
+    pub fn retry_after(
+        &mut self,
+        url: &str,
+        delay: Duration,
+        settings: &RateLimitSettings,
+        now: Instant,
+    ) {
+        let until = now + delay.min(Duration::from_secs(settings.max_retry_after_secs));
+        let bucket = self.bucket(url, settings, now);
+        if bucket.blocked_until.is_none_or(|current| current < until) {
+            bucket.blocked_until = Some(until);
+        }
+    }
+
+    pub fn snapshot(&self, now: Instant) -> Vec<RateLimitTargetSnapshot> {
+        let mut targets: Vec<RateLimitTargetSnapshot> = self
+            .buckets
+            .iter()
+            .map(|(target, bucket)| RateLimitTargetSnapshot {
+                target: target.clone(),
+                available_tokens: bucket.tokens,
+                blocked_ms: bucket.blocked_until.map_or(0, |until| {
+                    until.saturating_duration_since(now).as_millis() as u64
+                }),
+                waits: bucket.waits,
+            })
+            .collect();
+        targets.sort_by(|a, b| a.target.cmp(&b.target));
+        targets
+    }
+
+    fn bucket(&mut self, url: &str, settings: &RateLimitSettings, now: Instant) -> &mut Bucket {
+        let key = target_key(url);
+        if !self.buckets.contains_key(&key) && self.buckets.len() >= MAX_TARGETS {
+            self.buckets
+                .retain(|_, bucket| !bucket.is_idle(settings, now));
+        }
+        self.buckets.entry(key).or_insert_with(|| Bucket {
+            tokens: settings.burst,
+            refilled_at: now,
+            blocked_until: None,
+            waits: 0,
+        })
+    }
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+pub const SETTINGS_STEM: &str = "rate_limits";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_bucket_per_target() {
+        let settings = RateLimitSettings {
+            requests_per_minute: 60,
+            burst: 2,
+            ..Default::default()
+        };
+        let now = Instant::now();
+        let mut limiter = OutboundRateLimiter::default();
+
+        assert_eq!(
+            limiter.acquire("https://hooks.example.com/a", &settings, now),
+            None
+        );
+        assert_eq!(
+            limiter.acquire("https://HOOKS.example.com/b?x=1", &settings, now),
+            None
+        );
+        // Same host shares the bucket; another host has its own
+        assert_eq!(
+            limiter.acquire("https://hooks.example.com/a", &settings, now),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(
+            limiter.acquire("https://other.example.com/", &settings, now),
+            None
+        );
+
+        let later = now + Duration::from_millis(1500);
+        assert_eq!(
+            limiter.acquire("https://hooks.example.com/a", &settings, later),
+            None
+        );
+        assert_eq!(
+            limiter.acquire("https://hooks.example.com/a", &settings, later),
+            Some(Duration::from_millis(500))
+        );
+        assert_eq!(limiter.snapshot(later)[0].waits, 2);
+
+        let disabled = RateLimitSettings {
+            enabled: false,
+            ..settings
+        };
+        assert_eq!(
+            limiter.acquire("https://hooks.example.com/a", &disabled, later),
+            None
+        );
+
+        assert_eq!(
+            target_key("https://user:pw@Example.com:8443/path#frag"),
+            "https://example.com:8443"
+        );
+    }
+
+    #[test]
+    fn test_retry_after_blocks_target() {
+        let settings = RateLimitSettings {
+            max_retry_after_secs: 60,
+            ..Default::default()
+        };
+        let now = Instant::now();
+        let mut limiter = OutboundRateLimiter::default();
+        let url = "https://hooks.example.com/a";
+
+        limiter.retry_after(url, Duration::from_secs(30), &settings, now);
+        assert_eq!(
+            limiter.acquire(url, &settings, now),
+            Some(Duration::from_secs(30))
+        );
+        // Capped at max_retry_after_secs; a shorter block doesn't shorten it
+        limiter.retry_after(url, Duration::from_secs(3600), &settings, now);
+        limiter.retry_after(url, Duration::from_secs(5), &settings, now);
+        assert_eq!(
+            limiter.acquire(url, &settings, now),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            limiter.acquire(url, &settings, now + Duration::from_secs(60)),
+            None
+        );
+
+        let date_now = chrono::DateTime::parse_from_rfc3339("2015-10-21T07:27:30Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        assert_eq!(
+            parse_retry_after("120", date_now),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT", date_now),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(parse_retry_after("soon", date_now), None);
+    }
+}
@@ -0,0 +1,115 @@
+//! Reconnect Policy Settings
+//!
+//! When the recording device disconnects, auto-reconnect restarts recording
+//! into a new session. Participants consented to the original recording, so
+//! the user decides whether that may happen unattended:
+//!
+//! - `auto`: reconnect after a countdown of `grace_period_secs` (cancelable)
+//! - `prompt`: reconnect only once the user confirms (`confirm_reconnection`)
+//!   within `grace_period_secs`
+//! - `never`: don't reconnect; the user restarts recording manually
+//!
+//! During the countdown `device_reconnect_countdown` is emitted every second.
+//!
+//! Persisted to `settings/reconnect_policy.json` in app data directory.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// Longest allowed grace period
+pub const MAX_GRACE_PERIOD_SECS: u32 = 300;
+
+/// What happens after the recording device disconnects
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReconnectPolicy {
+    #[default]
+    Auto,
+    Prompt,
+    Never,
+}
+
+/// Reconnect policy settings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReconnectPolicySettings {
+    #[serde(default)]
+    pub policy: ReconnectPolicy,
+    /// Countdown before reconnecting (`auto`) or to confirm (`prompt`)
+    #[serde(default = "default_grace_period_secs")]
+    pub grace_period_secs: u32,
+    /// Settings version for future migrations
+    #[serde(default = "default_version")]
+    pub version: u32,
+}
+
+fn default_grace_period_secs() -> u32 {
+    10
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+impl Default for ReconnectPolicySettings {
+    fn default() -> Self {
+        Self {
+            policy: ReconnectPolicy::Auto,
+            grace_period_secs: 10,
+            version: 1,
+        }
+    }
+}
+
+impl ReconnectPolicySettings {
+    pub fn validate(&self) -> Result<()> {
+        if self.grace_period_secs > MAX_GRACE_PERIOD_SECS {
+            anyhow::bail!(
+                "grace_period_secs must be at most {} (got {})",
+                MAX_GRACE_PERIOD_SECS,
+                self.grace_period_secs
+            );
+        }
+        if self.policy == ReconnectPolicy::Prompt && self.grace_period_secs == 0 {
+            anyhow::bail!("The prompt policy needs a grace period to answer the prompt");
+        }
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+pub const SETTINGS_STEM: &str = "reconnect_policy";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation() {
+        let settings = ReconnectPolicySettings {
+            policy: ReconnectPolicy::Prompt,
+            grace_period_secs: 30,
+            ..Default::default()
+        };
+        assert!(settings.validate().is_ok());
+
+        let no_time_to_answer = ReconnectPolicySettings {
+            grace_period_secs: 0,
+            ..settings.clone()
+        };
+        assert!(no_time_to_answer.validate().is_err());
+        let immediate = ReconnectPolicySettings {
+            policy: ReconnectPolicy::Auto,
+            grace_period_secs: 0,
+            ..Default::default()
+        };
+        assert!(immediate.validate().is_ok());
+        let too_long = ReconnectPolicySettings {
+            grace_period_secs: MAX_GRACE_PERIOD_SECS + 1,
+            ..Default::default()
+        };
+        assert!(too_long.validate().is_err());
+    }
+}
@@ -8,6 +8,8 @@
 //! - Max 3 attempts
 //! - 5 second intervals
 //! - Job-based architecture with Supervisor pattern
+//! - Reconnect policy (auto / prompt / never) with a grace-period countdown
+//!   before the first attempt (see `reconnect_policy`)
 //!
 //! ## Design Principles (Critical Review #4 Compliance)
 //! 1. ✅ JobState pattern: Minimal state management (no HashMap, no generation counter)
@@ -22,14 +24,20 @@ use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tauri::{AppHandle, Emitter, Manager};
 
+use crate::reconnect_policy::{ReconnectPolicy, ReconnectPolicySettings};
+
 
 /// Cancellation reason with priority control
 ///
-/// Priority: NewJob(3) > UserRequest(2) > UserManualResume(1)
+/// Priority: NewJob(3) > UserRequest(2) > UserManualResume(1) > NotConfirmed(0)
 /// Higher priority reasons cannot be overwritten by lower priority ones.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum CancelReason {
-    /// User manually resumed recording (is_recording=true)
+    /// Prompt policy: the grace period ended without confirm_reconnection
+    /// Priority: Lowest
+    NotConfirmed = 0,
+
+    /// User manually resumed recording on the device
     /// Priority: Low (can be overwritten by others)
     UserManualResume = 1,
 
@@ -44,7 +52,7 @@ pub enum CancelReason {
 
 /// Set cancel reason with priority control
 ///
-/// Priority: NewJob(3) > UserRequest(2) > UserManualResume(1)
+/// Priority: NewJob(3) > UserRequest(2) > UserManualResume(1) > NotConfirmed(0)
 /// Higher priority reasons cannot be overwritten by lower priority ones.
 fn set_cancel_reason_priority(
     cancel_reason: &Arc<Mutex<Option<CancelReason>>>,
@@ -93,6 +101,7 @@ pub enum ReconnectionResult {
 /// - Cancellation reason with priority control
 /// - AbortHandle for immediate task cancellation
 /// - Current attempt counter for UI progress tracking
+/// - Confirmation flag set by confirm_reconnection
 /// - Device ID for UI notifications
 ///
 /// Note: JoinHandle is NOT stored here because the supervisor consumes it.
@@ -114,6 +123,9 @@ struct JobState {
     #[allow(dead_code)]
     current_attempt: Arc<AtomicU32>,
 
+    /// User confirmed the reconnection (ends the grace period early)
+    confirmed: Arc<AtomicBool>,
+
     /// Device ID being reconnected
     device_id: String,
 }
@@ -149,8 +161,14 @@ impl ReconnectionManager {
     ///
     /// # Arguments
     /// * `device_id` - Disconnected device identifier
+    /// * `policy` - Reconnect policy (must not be `Never`) and grace period
     /// * `app` - Tauri AppHandle for state access and event emission
-    pub fn start_job(&mut self, device_id: String, app: AppHandle) {
+    pub fn start_job(
+        &mut self,
+        device_id: String,
+        policy: ReconnectPolicySettings,
+        app: AppHandle,
+    ) {
         // Cancel existing job if any
         if let Some(old_job) = self.current_job.take() {
             // Set cancel reason with priority control
@@ -172,10 +190,12 @@ impl ReconnectionManager {
         let cancel_flag = Arc::new(AtomicBool::new(false));
         let cancel_reason = Arc::new(Mutex::new(None));
         let current_attempt = Arc::new(AtomicU32::new(0));
+        let confirmed = Arc::new(AtomicBool::new(false));
 
         let cancel_clone = cancel_flag.clone();
         let cancel_reason_clone = cancel_reason.clone();
         let current_attempt_clone = current_attempt.clone();
+        let confirmed_clone = confirmed.clone();
         let device_id_task = device_id.clone();
         let app_task = app.clone();
 
@@ -184,9 +204,11 @@ impl ReconnectionManager {
             reconnect_task(
                 app_task,
                 device_id_task,
+                policy,
                 cancel_clone,
                 cancel_reason_clone,
                 current_attempt_clone,
+                confirmed_clone,
             )
             .await
         });
@@ -303,6 +325,12 @@ impl ReconnectionManager {
                             "error": last_error
                         }),
                     );
+                    crate::commands::notify_ops_event(
+                        &app_supervisor,
+                        crate::ops_webhooks::OpsEventKind::ReconnectionExhausted,
+                        &format!("Device did not reconnect: {}", last_error),
+                        json!({ "device_id": device_id, "attempts": attempts }),
+                    );
                 }
                 ReconnectionResult::Cancelled { device_id, attempt, reason } => {
                     // Task returned Cancelled
@@ -310,6 +338,7 @@ impl ReconnectionManager {
                         Some(CancelReason::UserRequest) => "user_cancel",
                         Some(CancelReason::UserManualResume) => "user_manual_resume",
                         Some(CancelReason::NewJob) => "new_disconnect_event",
+                        Some(CancelReason::NotConfirmed) => "not_confirmed",
                         None => "unknown",
                     };
                     log_info_details!(
@@ -341,6 +370,7 @@ impl ReconnectionManager {
             cancel_reason,
             abort_handle,
             current_attempt,
+            confirmed,
             device_id: device_id.clone(),
         });
 
@@ -371,6 +401,25 @@ impl ReconnectionManager {
         }
     }
 
+    /// Confirm the current reconnection job
+    ///
+    /// Ends the grace period: the job starts reconnecting right away.
+    /// Returns false if no job is running.
+    pub fn confirm(&self) -> bool {
+        match &self.current_job {
+            Some(job) => {
+                job.confirmed.store(true, Ordering::Relaxed);
+                log_info_details!(
+                    "reconnection::job",
+                    "confirmed_by_user",
+                    json!({ "job_id": job.id, "device_id": job.device_id })
+                );
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Check if a reconnection job is currently running
     pub fn is_reconnecting(&self) -> bool {
         self.current_job.is_some()
@@ -394,18 +443,22 @@ impl Default for ReconnectionManager {
 /// # Arguments
 /// * `app` - Tauri AppHandle for state access
 /// * `device_id` - Disconnected device identifier
+/// * `policy` - Reconnect policy and grace period
 /// * `cancel_flag` - Atomic flag for lock-free cancellation
 /// * `cancel_reason` - Shared cancellation reason with priority control
 /// * `current_attempt` - Shared current attempt counter
+/// * `confirmed` - Set by confirm_reconnection
 ///
 /// # Returns
 /// * `ReconnectionResult` - Success, Failed, or Cancelled (with reason)
 async fn reconnect_task(
     app: AppHandle,
     device_id: String,
+    policy: ReconnectPolicySettings,
     cancel_flag: Arc<AtomicBool>,
     cancel_reason: Arc<Mutex<Option<CancelReason>>>,
     current_attempt: Arc<AtomicU32>,
+    confirmed: Arc<AtomicBool>,
 ) -> ReconnectionResult {
     use crate::state::AppState;
 
@@ -417,10 +470,30 @@ async fn reconnect_task(
         "started",
         json!({
             "device_id": device_id,
-            "max_retries": MAX_RETRIES
+            "max_retries": MAX_RETRIES,
+            "policy": policy.policy,
+            "grace_period_secs": policy.grace_period_secs
         })
     );
 
+    // Grace period: countdown (auto) or wait for confirmation (prompt)
+    if let Err(reason) = grace_period(
+        &app,
+        &device_id,
+        &policy,
+        &cancel_flag,
+        &cancel_reason,
+        &confirmed,
+    )
+    .await
+    {
+        return ReconnectionResult::Cancelled {
+            device_id,
+            attempt: 0,
+            reason,
+        };
+    }
+
     for attempt in 1..=MAX_RETRIES {
         // Update current attempt counter
         current_attempt.store(attempt, Ordering::Relaxed);
@@ -453,11 +526,10 @@ async fn reconnect_task(
             };
         }
 
-        // Step 2: Check if user manually resumed recording
+        // Step 2: Check if user manually resumed recording on the device
         {
             let state = app.state::<AppState>();
-            let is_recording = state.is_recording.lock().unwrap();
-            if *is_recording {
+            if state.session_using_device(&device_id).is_some() {
                 // Set cancel reason with priority control
                 set_cancel_reason_priority(&cancel_reason, CancelReason::UserManualResume);
                 log_info_details!(
@@ -491,8 +563,7 @@ async fn reconnect_task(
 
                     // Check user resumed
                     let state = app.state::<AppState>();
-                    let is_recording = state.is_recording.lock().unwrap();
-                    if *is_recording {
+                    if state.session_using_device(&device_id).is_some() {
                         break;
                     }
                 }
@@ -595,10 +666,9 @@ async fn reconnect_task(
         }
 
         // Step 5: Attempt to start recording
-        // Note: start_recording_internal is permissive (already recording = success)
         let state = app.state::<AppState>();
         match crate::commands::start_recording_internal(&app, &state, device_id.clone()).await {
-            Ok(()) => {
+            Ok(_) => {
                 log_info_details!(
                     "reconnection::task",
                     "success",
@@ -645,3 +715,85 @@ async fn reconnect_task(
         last_error: error_msg,
     }
 }
+
+/// Countdown before the first reconnection attempt
+///
+/// Emits `device_reconnect_countdown` every second. Auto policy: proceeds
+/// when the countdown ends or the user confirms early. Prompt policy:
+/// proceeds only on confirmation.
+///
+/// # Returns
+/// * `Ok(())` - go ahead and reconnect
+/// * `Err(reason)` - cancelled (user cancel, manual resume, not confirmed)
+async fn grace_period(
+    app: &AppHandle,
+    device_id: &str,
+    policy: &ReconnectPolicySettings,
+    cancel_flag: &AtomicBool,
+    cancel_reason: &Arc<Mutex<Option<CancelReason>>>,
+    confirmed: &AtomicBool,
+) -> Result<(), Option<CancelReason>> {
+    use crate::state::AppState;
+
+    const POLL_INTERVAL: Duration = Duration::from_millis(100);
+    let awaiting_confirmation = policy.policy == ReconnectPolicy::Prompt;
+    let deadline =
+        tokio::time::Instant::now() + Duration::from_secs(policy.grace_period_secs as u64);
+    let mut last_announced = None;
+
+    loop {
+        if confirmed.load(Ordering::Relaxed) {
+            log_info_details!(
+                "reconnection::task",
+                "grace_period_confirmed",
+                json!({ "device_id": device_id })
+            );
+            return Ok(());
+        }
+        if cancel_flag.load(Ordering::Relaxed) {
+            return Err(cancel_reason.lock().unwrap().take());
+        }
+        if app
+            .state::<AppState>()
+            .session_using_device(device_id)
+            .is_some()
+        {
+            set_cancel_reason_priority(cancel_reason, CancelReason::UserManualResume);
+            return Err(cancel_reason.lock().unwrap().take());
+        }
+
+        let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+        if remaining.is_zero() {
+            if !awaiting_confirmation {
+                return Ok(());
+            }
+            set_cancel_reason_priority(cancel_reason, CancelReason::NotConfirmed);
+            log_info_details!(
+                "reconnection::task",
+                "not_confirmed",
+                json!({
+                    "device_id": device_id,
+                    "grace_period_secs": policy.grace_period_secs
+                })
+            );
+            return Err(cancel_reason.lock().unwrap().take());
+        }
+
+        // Announce each remaining second once (rounded up)
+        let seconds_remaining = remaining.as_millis().div_ceil(1000) as u64;
+        if last_announced != Some(seconds_remaining) {
+            last_announced = Some(seconds_remaining);
+            let _ = app.emit(
+                "device_reconnect_countdown",
+                json!({
+                    "device_id": device_id,
+                    "policy": policy.policy,
+                    "seconds_remaining": seconds_remaining,
+                    "awaiting_confirmation": awaiting_confirmation
+                }),
+            );
+        }
+
+        tokio::time::sleep(POLL_INTERVAL.min(remaining)).await;
+    }
+}
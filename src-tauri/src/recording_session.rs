@@ -0,0 +1,646 @@
+//! Recording Session
+//!
+//! One recording session owns everything that lives for exactly as long as
+//! the recording: its audio recorder, writers (audio, transcript, activity),
+//! the cancel token and join handles of its pipeline tasks, request IDs, the
+//! send queue metrics and flow control of its audio, the channel its sidecar
+//! events are routed to, the IPC event sequence, running stats and the
+//! loudness meter. Sessions recorded at the same time share nothing but the
+//! sidecar (see `session_registry`).
+//!
+//! Lifecycle: `start` (open the session directory and writers) →
+//! `pause`/`resume` any number of times → `stop` (cancel tasks, close the
+//! writers, write session.json). `flush` syncs the writers while recording.
+//! While paused, audio is neither saved nor sent to the sidecar.
+
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+use crate::activity::ActivityRecorder;
+use crate::audio_device_recorder::AudioDeviceRecorder;
+use crate::ipc_flow_control::FlowController;
+use crate::loudness::{AppliedGain, LoudnessMeter};
+use crate::partial_granularity::{PartialGate, PartialGranularitySettings};
+use crate::request_id::RequestIdNamespace;
+use crate::ring_buffer::AudioQueueMetrics;
+use crate::storage::{
+    AudioFormat, LocalStorageService, SessionAudioWriter, SessionMetadata, TranscriptWriter,
+    TranscriptionEvent,
+};
+
+/// Lifecycle phase of a recording session
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionPhase {
+    /// Registered, writers not opened yet
+    Created,
+    Recording,
+    Paused,
+    /// Finalized; the session no longer accepts audio
+    Stopped,
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum SessionLifecycleError {
+    #[error("Cannot {action} session {session_id}: it is {phase:?}")]
+    InvalidTransition {
+        session_id: String,
+        action: &'static str,
+        phase: SessionPhase,
+    },
+}
+
+/// Running totals of a session
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SessionStats {
+    /// Audio batches accepted (saved and sent to the sidecar)
+    pub audio_batches: u64,
+    pub audio_bytes: u64,
+    /// Audio discarded while paused
+    pub paused_bytes: u64,
+    /// Transcription events appended to transcription.jsonl
+    pub transcript_events: u64,
+    /// Failed audio or transcript writes
+    pub write_errors: u64,
+}
+
+/// Sidecar event routed to a session
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionIpcEvent {
+    pub event_type: String,
+    pub data: Value,
+}
+
+#[derive(Default)]
+struct StatCounters {
+    audio_batches: AtomicU64,
+    audio_bytes: AtomicU64,
+    paused_bytes: AtomicU64,
+    transcript_events: AtomicU64,
+    write_errors: AtomicU64,
+}
+
+/// State of one recording session
+pub struct RecordingSession {
+    session_id: String,
+    session_uuid: String,
+    /// Wall-clock start (epoch millis); transcript timestamps are relative to it
+    started_at_ms: u64,
+    phase: Mutex<SessionPhase>,
+    /// Cancels the session's pipeline tasks (IPC reader, audio sender, ...)
+    cancel_token: CancellationToken,
+    /// Join handles of the pipeline tasks, for forced teardown
+    tasks: Mutex<Vec<tokio::task::JoinHandle<()>>>,
+    /// Captures the session's input device(s)
+    recorder: Mutex<Option<Arc<tokio::sync::Mutex<AudioDeviceRecorder>>>>,
+    /// Send queue accounting of the session's audio
+    queue_metrics: Arc<AudioQueueMetrics>,
+    /// Audio send pacing, from the sidecar's acks of this session's requests
+    ipc_flow: Arc<Mutex<FlowController>>,
+    /// Sidecar events of this session (see `deliver_ipc_event`)
+    ipc_events: Mutex<Option<mpsc::UnboundedSender<SessionIpcEvent>>>,
+    transcript_writer: Mutex<Option<TranscriptWriter>>,
+    audio_writer: Mutex<Option<SessionAudioWriter>>,
+    activity_recorder: Mutex<Option<ActivityRecorder>>,
+    /// Measures the loudness of the saved audio (for session.json)
+    loudness_meter: Mutex<Option<LoudnessMeter>>,
+    /// Gains the inputs are recorded with
+    applied_gains: Mutex<Vec<AppliedGain>>,
+    /// Sequence number of the last IPC event (reported in the heartbeat)
+    ipc_event_seq: AtomicU64,
+    request_ids: Arc<RequestIdNamespace>,
+    /// Decides which partial transcriptions are forwarded to clients
+    partial_gate: Mutex<PartialGate>,
+    stats: StatCounters,
+}
+
+impl RecordingSession {
+    pub fn new(session_id: &str, session_uuid: &str, started_at_ms: u64) -> Self {
+        Self {
+            session_id: session_id.to_string(),
+            session_uuid: session_uuid.to_string(),
+            started_at_ms,
+            phase: Mutex::new(SessionPhase::Created),
+            cancel_token: CancellationToken::new(),
+            tasks: Mutex::new(Vec::new()),
+            recorder: Mutex::new(None),
+            queue_metrics: Arc::new(AudioQueueMetrics::new()),
+            ipc_flow: Arc::new(Mutex::new(FlowController::default())),
+            ipc_events: Mutex::new(None),
+            transcript_writer: Mutex::new(None),
+            audio_writer: Mutex::new(None),
+            activity_recorder: Mutex::new(None),
+            loudness_meter: Mutex::new(None),
+            applied_gains: Mutex::new(Vec::new()),
+            ipc_event_seq: AtomicU64::new(0),
+            request_ids: Arc::new(RequestIdNamespace::new(session_id)),
+            partial_gate: Mutex::new(PartialGate::new()),
+            stats: StatCounters::default(),
+        }
+    }
+
+    pub fn session_id(&self) -> &str {
+        &self.session_id
+    }
+
+    pub fn session_uuid(&self) -> &str {
+        &self.session_uuid
+    }
+
+    pub fn started_at_ms(&self) -> u64 {
+        self.started_at_ms
+    }
+
+    /// Milliseconds elapsed since the session started
+    pub fn elapsed_ms(&self) -> u64 {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+        now.saturating_sub(self.started_at_ms)
+    }
+
+    pub fn phase(&self) -> SessionPhase {
+        *self.phase.lock().unwrap()
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.phase() == SessionPhase::Paused
+    }
+
+    pub fn stats(&self) -> SessionStats {
+        let load = |counter: &AtomicU64| counter.load(Ordering::Relaxed);
+        SessionStats {
+            audio_batches: load(&self.stats.audio_batches),
+            audio_bytes: load(&self.stats.audio_bytes),
+            paused_bytes: load(&self.stats.paused_bytes),
+            transcript_events: load(&self.stats.transcript_events),
+            write_errors: load(&self.stats.write_errors),
+        }
+    }
+
+    /// Move from `from` to `to`, or fail with the current phase
+    fn transition(
+        &self,
+        action: &'static str,
+        from: &[SessionPhase],
+        to: SessionPhase,
+    ) -> Result<(), SessionLifecycleError> {
+        let mut phase = self.phase.lock().unwrap();
+        if !from.contains(&phase) {
+            return Err(SessionLifecycleError::InvalidTransition {
+                session_id: self.session_id.clone(),
+                action,
+                phase: *phase,
+            });
+        }
+        *phase = to;
+        Ok(())
+    }
+
+    /// Start recording, opening the session directory and writers
+    ///
+    /// Persistence is best-effort: without a storage service, or when the
+    /// writers cannot be opened (the error is returned), the session still
+    /// records but nothing is saved. The activity timeline is optional and
+    /// only logged when unavailable.
+    pub fn start(&self, storage: Option<&LocalStorageService>, format: AudioFormat) -> Result<()> {
+        self.transition("start", &[SessionPhase::Created], SessionPhase::Recording)?;
+        let Some(storage) = storage else {
+            return Ok(());
+        };
+
+        let session_dir = storage.create_session(&self.session_id)?;
+        let audio_writer = storage.create_session_audio_writer(&self.session_id, format)?;
+        let transcript_writer = storage.create_transcript_writer(&self.session_id)?;
+        *self.audio_writer.lock().unwrap() = Some(audio_writer);
+        *self.transcript_writer.lock().unwrap() = Some(transcript_writer);
+        *self.loudness_meter.lock().unwrap() = Some(LoudnessMeter::new());
+
+        match ActivityRecorder::create(&session_dir) {
+            Ok(recorder) => *self.activity_recorder.lock().unwrap() = Some(recorder),
+            Err(e) => {
+                log_warn_details!(
+                    "recording_session",
+                    "activity_recorder_unavailable",
+                    json!({ "session": self.session_id, "error": e.to_string() })
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Pause: audio is dropped until `resume`
+    pub fn pause(&self) -> Result<(), SessionLifecycleError> {
+        self.transition("pause", &[SessionPhase::Recording], SessionPhase::Paused)
+    }
+
+    pub fn resume(&self) -> Result<(), SessionLifecycleError> {
+        self.transition("resume", &[SessionPhase::Paused], SessionPhase::Recording)
+    }
+
+    /// Sync the audio written so far to disk (limits loss on a crash)
+    pub fn flush(&self) -> Result<()> {
+        if let Some(writer) = self.audio_writer.lock().unwrap().as_mut() {
+            writer.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Stop the session: cancel its tasks, close the writers and write
+    /// session.json
+    ///
+    /// Returns the saved metadata, or None when the session was not
+    /// persisted (or already stopped). Failures to close individual writers
+    /// are logged; only a failed session.json write is returned.
+    pub fn stop(
+        &self,
+        storage: Option<&LocalStorageService>,
+        audio_device: &str,
+        warnings: Vec<String>,
+    ) -> Result<Option<SessionMetadata>> {
+        if self
+            .transition(
+                "stop",
+                &[
+                    SessionPhase::Created,
+                    SessionPhase::Recording,
+                    SessionPhase::Paused,
+                ],
+                SessionPhase::Stopped,
+            )
+            .is_err()
+        {
+            return Ok(None);
+        }
+        self.cancel();
+        self.ipc_events.lock().unwrap().take();
+        let elapsed_ms = self.elapsed_ms();
+
+        let audio_writer = self.audio_writer.lock().unwrap().take();
+        let activity_recorder = self.activity_recorder.lock().unwrap().take();
+        let transcript_writer = self.transcript_writer.lock().unwrap().take();
+        let levels = self
+            .loudness_meter
+            .lock()
+            .unwrap()
+            .take()
+            .map(|meter| meter.levels(self.applied_gains.lock().unwrap().clone()));
+
+        if let Some(audio_writer) = audio_writer {
+            if let Err(e) = audio_writer.close() {
+                self.log_close_failed("audio_close_failed", &e);
+            }
+        }
+        if let Some(recorder) = activity_recorder {
+            if let Err(e) = recorder.finish() {
+                self.log_close_failed("activity_close_failed", &e);
+            }
+        }
+        let (Some(writer), Some(storage)) = (transcript_writer, storage) else {
+            return Ok(None);
+        };
+
+        let total_segments = writer.final_segments();
+        let total_characters = writer.final_characters();
+        if let Err(e) = writer.close() {
+            self.log_close_failed("transcript_close_failed", &e);
+        }
+
+        let metadata = SessionMetadata {
+            session_id: self.session_id.clone(),
+            start_time: crate::storage::format_iso8601_millis(self.started_at_ms),
+            end_time: crate::storage::format_iso8601_millis(self.started_at_ms + elapsed_ms),
+            duration_seconds: elapsed_ms / 1000,
+            audio_device: audio_device.to_string(),
+            model_size: "unknown".to_string(),
+            total_segments,
+            total_characters,
+            warnings,
+            session_uuid: Some(self.session_uuid.clone()),
+            title: None,
+            tags: Vec::new(),
+            stats: None,
+            levels,
+        };
+        storage.save_session_metadata(&metadata)?;
+        Ok(Some(metadata))
+    }
+
+    fn log_close_failed(&self, event: &str, error: &anyhow::Error) {
+        log_warn_details!(
+            "recording_session",
+            event,
+            json!({ "session": self.session_id, "error": error.to_string() })
+        );
+    }
+
+    pub fn cancel_token(&self) -> CancellationToken {
+        self.cancel_token.clone()
+    }
+
+    /// Cancel the session's pipeline tasks
+    pub fn cancel(&self) {
+        self.cancel_token.cancel();
+    }
+
+    pub fn request_ids(&self) -> Arc<RequestIdNamespace> {
+        Arc::clone(&self.request_ids)
+    }
+
+    /// Register a pipeline task for forced teardown
+    pub fn register_task(&self, handle: tokio::task::JoinHandle<()>) {
+        let mut tasks = self.tasks.lock().unwrap();
+        tasks.retain(|task| !task.is_finished());
+        tasks.push(handle);
+    }
+
+    /// Number of registered tasks that are still running
+    pub fn running_tasks(&self) -> usize {
+        self.tasks
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|task| !task.is_finished())
+            .count()
+    }
+
+    /// Abort all registered tasks
+    /// Returns the number of tasks that were still running
+    pub fn abort_tasks(&self) -> usize {
+        let tasks: Vec<_> = self.tasks.lock().unwrap().drain(..).collect();
+        tasks
+            .iter()
+            .filter(|task| !task.is_finished())
+            .inspect(|task| task.abort())
+            .count()
+    }
+
+    /// Record the gains the inputs are captured with (stored in session.json)
+    pub fn set_applied_gains(&self, gains: Vec<AppliedGain>) {
+        *self.applied_gains.lock().unwrap() = gains;
+    }
+
+    /// Input devices of the session (from the applied gains)
+    pub fn device_ids(&self) -> Vec<String> {
+        self.applied_gains
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|gain| gain.device_id.clone())
+            .collect()
+    }
+
+    pub fn set_recorder(&self, recorder: Arc<tokio::sync::Mutex<AudioDeviceRecorder>>) {
+        *self.recorder.lock().unwrap() = Some(recorder);
+    }
+
+    /// Audio recorder of the session (None before capture was set up)
+    pub fn recorder(&self) -> Option<Arc<tokio::sync::Mutex<AudioDeviceRecorder>>> {
+        self.recorder.lock().unwrap().clone()
+    }
+
+    /// Detach the recorder (a wedged one is left to its blocking thread)
+    pub fn take_recorder(&self) -> Option<Arc<tokio::sync::Mutex<AudioDeviceRecorder>>> {
+        self.recorder.lock().unwrap().take()
+    }
+
+    pub fn queue_metrics(&self) -> Arc<AudioQueueMetrics> {
+        Arc::clone(&self.queue_metrics)
+    }
+
+    pub fn ipc_flow(&self) -> Arc<Mutex<FlowController>> {
+        Arc::clone(&self.ipc_flow)
+    }
+
+    /// Open the channel sidecar events of this session are delivered to
+    ///
+    /// Replaces a previously opened channel.
+    pub fn open_ipc_channel(&self) -> mpsc::UnboundedReceiver<SessionIpcEvent> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        *self.ipc_events.lock().unwrap() = Some(tx);
+        rx
+    }
+
+    /// Hand a sidecar event to the session's event task
+    ///
+    /// Returns false when nobody receives it any more (no channel opened,
+    /// the event task ended or the session was stopped).
+    pub fn deliver_ipc_event(&self, event_type: &str, data: Value) -> bool {
+        let guard = self.ipc_events.lock().unwrap();
+        let Some(tx) = guard.as_ref() else {
+            return false;
+        };
+        tx.send(SessionIpcEvent {
+            event_type: event_type.to_string(),
+            data,
+        })
+        .is_ok()
+    }
+
+    /// Append a transcription event; no-op without a transcript writer
+    pub fn append_transcript_event(&self, event: &TranscriptionEvent) -> Result<()> {
+        let mut guard = self.transcript_writer.lock().unwrap();
+        let Some(writer) = guard.as_mut() else {
+            return Ok(());
+        };
+        match writer.append_event(event) {
+            Ok(()) => {
+                self.stats.transcript_events.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            }
+            Err(e) => {
+                self.stats.write_errors.fetch_add(1, Ordering::Relaxed);
+                Err(e)
+            }
+        }
+    }
+
+    /// Accept a batch of 16-bit PCM bytes for the session
+    ///
+    /// Returns false while the session is not recording (paused or stopped):
+    /// the batch is dropped and must not be transcribed either. Otherwise it
+    /// is appended to the session audio and activity (when persisted).
+    pub fn append_audio(&self, pcm_bytes: &[u8]) -> Result<bool> {
+        match self.phase() {
+            SessionPhase::Recording => {}
+            SessionPhase::Paused => {
+                self.stats
+                    .paused_bytes
+                    .fetch_add(pcm_bytes.len() as u64, Ordering::Relaxed);
+                return Ok(false);
+            }
+            SessionPhase::Created | SessionPhase::Stopped => return Ok(false),
+        }
+        self.stats.audio_batches.fetch_add(1, Ordering::Relaxed);
+        self.stats
+            .audio_bytes
+            .fetch_add(pcm_bytes.len() as u64, Ordering::Relaxed);
+
+        let written = self.write_audio(pcm_bytes);
+        if written.is_err() {
+            self.stats.write_errors.fetch_add(1, Ordering::Relaxed);
+        }
+        written.map(|()| true)
+    }
+
+    fn write_audio(&self, pcm_bytes: &[u8]) -> Result<()> {
+        let mut guard = self.audio_writer.lock().unwrap();
+        let Some(writer) = guard.as_mut() else {
+            return Ok(());
+        };
+        writer.write_pcm_bytes(pcm_bytes)?;
+        drop(guard);
+
+        if let Some(recorder) = self.activity_recorder.lock().unwrap().as_mut() {
+            recorder.write_pcm_bytes(pcm_bytes)?;
+        }
+        if let Some(meter) = self.loudness_meter.lock().unwrap().as_mut() {
+            meter.write_pcm_bytes(pcm_bytes);
+        }
+        Ok(())
+    }
+
+    /// Samples written to the session audio
+    pub fn audio_samples(&self) -> u64 {
+        self.audio_writer
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|writer| writer.samples_written())
+            .unwrap_or(0)
+    }
+
+    /// Record an IPC event, returning its sequence number (1-based)
+    pub fn record_ipc_event(&self) -> u64 {
+        self.ipc_event_seq.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    /// Sequence number of the last IPC event
+    pub fn last_ipc_event_seq(&self) -> u64 {
+        self.ipc_event_seq.load(Ordering::Relaxed)
+    }
+
+    /// Whether a partial transcription should be forwarded to clients
+    pub fn admit_partial(&self, text: &str, settings: &PartialGranularitySettings) -> bool {
+        self.partial_gate.lock().unwrap().admit(text, settings)
+    }
+
+    /// Reset the partial gate after a final transcription
+    pub fn reset_partials(&self) {
+        self.partial_gate.lock().unwrap().reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn pcm(samples: usize) -> Vec<u8> {
+        (0..samples)
+            .flat_map(|i| (((i as f64 * 0.02).sin() * 2000.0) as i16).to_le_bytes())
+            .collect()
+    }
+
+    #[test]
+    fn test_lifecycle_persists_session() {
+        let dir = TempDir::new().unwrap();
+        let storage = LocalStorageService::new(dir.path().to_path_buf());
+        let session = RecordingSession::new("s1", "u1", 0);
+        assert!(!session.append_audio(&pcm(100)).unwrap());
+
+        session.set_applied_gains(vec![AppliedGain {
+            device_id: "mic".to_string(),
+            gain_db: 0.0,
+        }]);
+        session.start(Some(&storage), AudioFormat::Wav).unwrap();
+        assert_eq!(session.phase(), SessionPhase::Recording);
+        assert!(session.append_audio(&pcm(16_000)).unwrap());
+        session.flush().unwrap();
+
+        session.pause().unwrap();
+        assert!(!session.append_audio(&pcm(8_000)).unwrap());
+        assert!(session.pause().is_err());
+        session.resume().unwrap();
+        assert!(session.append_audio(&pcm(16_000)).unwrap());
+
+        session
+            .append_transcript_event(&TranscriptionEvent {
+                timestamp_ms: 10,
+                text: "hello".to_string(),
+                is_final: true,
+                speaker: None,
+            })
+            .unwrap();
+        assert_eq!(
+            session.stats(),
+            SessionStats {
+                audio_batches: 2,
+                audio_bytes: 64_000,
+                paused_bytes: 16_000,
+                transcript_events: 1,
+                write_errors: 0,
+            }
+        );
+        assert_eq!(session.audio_samples(), 32_000);
+
+        let metadata = session
+            .stop(Some(&storage), "mic", vec!["warn".to_string()])
+            .unwrap()
+            .unwrap();
+        assert_eq!(metadata.total_segments, 1);
+        assert_eq!(metadata.warnings, vec!["warn"]);
+        let levels = metadata.levels.as_ref().unwrap();
+        assert_eq!(levels.applied_gains[0].device_id, "mic");
+        assert!(levels.integrated_lufs.is_some());
+        assert_eq!(levels.loudness_trajectory.len(), 1);
+        assert!(session.cancel_token().is_cancelled());
+        assert_eq!(session.phase(), SessionPhase::Stopped);
+        assert_eq!(storage.load_session("s1").unwrap().metadata, metadata);
+
+        // Stopped sessions reject audio and a second stop is a no-op
+        assert!(!session.append_audio(&pcm(100)).unwrap());
+        assert!(session
+            .stop(Some(&storage), "mic", Vec::new())
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_start_without_storage_records_but_saves_nothing() {
+        let session = RecordingSession::new("s1", "u1", 0);
+        session.start(None, AudioFormat::Wav).unwrap();
+        assert!(session.append_audio(&pcm(100)).unwrap());
+        assert_eq!(session.audio_samples(), 0);
+        assert_eq!(
+            session
+                .start(None, AudioFormat::Wav)
+                .unwrap_err()
+                .to_string(),
+            "Cannot start session s1: it is Recording"
+        );
+        assert!(session.stop(None, "mic", Vec::new()).unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_abort_tasks_counts_running_tasks() {
+        let session = RecordingSession::new("s", "u", 0);
+        let token = session.cancel_token();
+        session.register_task(tokio::spawn(async move { token.cancelled().await }));
+        session.register_task(tokio::spawn(std::future::pending::<()>()));
+        assert_eq!(session.running_tasks(), 2);
+
+        session.cancel();
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        assert_eq!(session.running_tasks(), 1);
+        assert_eq!(session.abort_tasks(), 1);
+        assert_eq!(session.running_tasks(), 0);
+    }
+}
@@ -0,0 +1,312 @@
+//! Audio Redaction
+//!
+//! Produces a `redacted` copy of a session that is safe to share outside the
+//! team: the given time ranges are overwritten in a copy of the audio
+//! (silence or a 1kHz bleep) and the transcript segments spoken in them are
+//! removed or flagged. The original audio and transcript are left as is.
+//!
+//! Written to the session directory:
+//! - `audio.redacted.flac`
+//! - `transcript.redacted.md` (the Markdown export of the redacted segments)
+//!
+//! Segments only carry the time their final result arrived, so a segment
+//! is taken to cover the audio since the previous final one; it is redacted
+//! when that stretch overlaps a range.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::furigana::Furigana;
+use crate::storage::{AudioFormat, LocalStorageService, SessionAudioWriter, TranscriptionEvent};
+
+/// Redacted audio file name (in the session directory)
+pub const REDACTED_AUDIO_FILENAME: &str = "audio.redacted.flac";
+
+/// Redacted transcript file name (in the session directory)
+pub const REDACTED_TRANSCRIPT_FILENAME: &str = "transcript.redacted.md";
+
+/// Text standing in for a flagged segment
+pub const REDACTED_TEXT: &str = "[redacted]";
+
+/// Most ranges per request
+pub const MAX_RANGES: usize = 500;
+
+const SAMPLE_RATE: u64 = 16000;
+const SAMPLES_PER_MS: u64 = SAMPLE_RATE / 1000;
+const TONE_HZ: f64 = 1000.0;
+/// Bleep level (-20dBFS)
+const TONE_AMPLITUDE: f64 = 0.1 * i16::MAX as f64;
+
+/// Time range to redact (session-relative)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RedactionRange {
+    pub start_ms: u64,
+    pub end_ms: u64,
+}
+
+impl RedactionRange {
+    fn overlaps(&self, start_ms: u64, end_ms: u64) -> bool {
+        self.start_ms < end_ms && start_ms < self.end_ms
+    }
+}
+
+/// What replaces the redacted audio
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AudioRedaction {
+    Silence,
+    /// 1kHz bleep
+    #[default]
+    Tone,
+}
+
+/// What happens to redacted transcript segments
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TranscriptRedaction {
+    /// Left out of the transcript
+    Remove,
+    /// Kept with the text replaced by [`REDACTED_TEXT`]
+    #[default]
+    Flag,
+}
+
+/// Redaction options
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RedactionOptions {
+    #[serde(default)]
+    pub audio: AudioRedaction,
+    #[serde(default)]
+    pub transcript: TranscriptRedaction,
+}
+
+/// Result of a redaction
+#[derive(Debug, Clone, Serialize)]
+pub struct RedactedExport {
+    pub audio_path: PathBuf,
+    pub transcript_path: PathBuf,
+    /// Audio overwritten (overlapping ranges counted once)
+    pub redacted_ms: u64,
+    /// Final segments removed or flagged
+    pub redacted_segments: usize,
+}
+
+pub fn validate_ranges(ranges: &[RedactionRange]) -> Result<()> {
+    if ranges.is_empty() {
+        anyhow::bail!("At least one range must be given");
+    }
+    if ranges.len() > MAX_RANGES {
+        anyhow::bail!("At most {} ranges can be redacted at once", MAX_RANGES);
+    }
+    if let Some(range) = ranges.iter().find(|r| r.start_ms >= r.end_ms) {
+        anyhow::bail!(
+            "Invalid range: {}ms-{}ms (start must be before end)",
+            range.start_ms,
+            range.end_ms
+        );
+    }
+    Ok(())
+}
+
+/// Redact the final segments overlapping `ranges`
+///
+/// Partials are dropped. Returns the events and the number of redacted
+/// segments.
+pub fn redact_transcript(
+    events: &[TranscriptionEvent],
+    ranges: &[RedactionRange],
+    mode: TranscriptRedaction,
+) -> (Vec<TranscriptionEvent>, usize) {
+    let mut redacted = Vec::new();
+    let mut count = 0;
+    let mut previous_final_ms = 0;
+    for event in events.iter().filter(|e| e.is_final) {
+        let spoken_from = previous_final_ms.min(event.timestamp_ms);
+        previous_final_ms = event.timestamp_ms;
+        // Inclusive end: the final itself may arrive inside a range
+        if !ranges
+            .iter()
+            .any(|range| range.overlaps(spoken_from, event.timestamp_ms + 1))
+        {
+            redacted.push(event.clone());
+            continue;
+        }
+        count += 1;
+        if mode == TranscriptRedaction::Flag {
+            redacted.push(TranscriptionEvent {
+                text: REDACTED_TEXT.to_string(),
+                ..event.clone()
+            });
+        }
+    }
+    (redacted, count)
+}
+
+/// Sort `ranges` and merge overlapping or touching ones
+pub fn merge_ranges(ranges: &[RedactionRange]) -> Vec<RedactionRange> {
+    let mut sorted = ranges.to_vec();
+    sorted.sort_by_key(|range| range.start_ms);
+    let mut merged: Vec<RedactionRange> = Vec::with_capacity(sorted.len());
+    for range in sorted {
+        match merged.last_mut() {
+            Some(last) if range.start_ms <= last.end_ms => {
+                last.end_ms = last.end_ms.max(range.end_ms);
+            }
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+/// Overwrite the samples of a chunk starting at sample `position` that fall
+/// in `ranges` (merged, see [`merge_ranges`]); returns the number of samples
+/// overwritten
+pub fn redact_samples(
+    samples: &mut [i16],
+    position: u64,
+    ranges: &[RedactionRange],
+    mode: AudioRedaction,
+) -> u64 {
+    let end = position + samples.len() as u64;
+    let mut overwritten = 0;
+    for range in ranges {
+        let from = (range.start_ms * SAMPLES_PER_MS).max(position);
+        let to = (range.end_ms * SAMPLES_PER_MS).min(end);
+        for n in from..to {
+            samples[(n - position) as usize] = match mode {
+                AudioRedaction::Silence => 0,
+                // Phase follows the absolute sample position: no clicks at
+                // chunk boundaries
+                AudioRedaction::Tone => {
+                    let t = n as f64 / SAMPLE_RATE as f64;
+                    ((2.0 * std::f64::consts::PI * TONE_HZ * t).sin() * TONE_AMPLITUDE) as i16
+                }
+            };
+        }
+        overwritten += to.saturating_sub(from);
+    }
+    overwritten
+}
+
+/// Write the redacted audio and transcript of a finalized session
+///
+/// `furigana` annotates the transcript as in the Markdown export.
+pub fn redact_session(
+    storage: &LocalStorageService,
+    session_id: &str,
+    ranges: &[RedactionRange],
+    options: &RedactionOptions,
+    furigana: Option<&Furigana>,
+) -> Result<RedactedExport> {
+    validate_ranges(ranges)?;
+    crate::session_id::validate_session_id(session_id)?;
+    let ranges = merge_ranges(ranges);
+    let loaded = storage.load_session(session_id)?;
+    let session_dir = storage.get_session_dir(session_id);
+
+    let audio_path = session_dir.join(REDACTED_AUDIO_FILENAME);
+    let mut writer = SessionAudioWriter::create(audio_path.clone(), AudioFormat::Flac)?;
+    let mut position = 0u64;
+    let mut overwritten = 0u64;
+    let mut written: Result<()> = Ok(());
+    crate::storage::read_session_audio(&session_dir, |samples| {
+        let mut samples = samples.to_vec();
+        overwritten += redact_samples(&mut samples, position, &ranges, options.audio);
+        position += samples.len() as u64;
+        if written.is_ok() {
+            written = writer.write_samples(&samples);
+        }
+    })?;
+    written?;
+    writer
+        .close()
+        .with_context(|| format!("Failed to write redacted audio: {:?}", audio_path))?;
+
+    let (events, redacted_segments) =
+        redact_transcript(&loaded.transcripts, &ranges, options.transcript);
+    let markdown = crate::storage::render_session_markdown(&loaded.metadata, &events, furigana);
+    let transcript_path = session_dir.join(REDACTED_TRANSCRIPT_FILENAME);
+    crate::storage::write_file_owner_only(&transcript_path, markdown.as_bytes())?;
+
+    Ok(RedactedExport {
+        audio_path,
+        transcript_path,
+        redacted_ms: overwritten / SAMPLES_PER_MS,
+        redacted_segments,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(timestamp_ms: u64, text: &str, is_final: bool) -> TranscriptionEvent {
+        TranscriptionEvent {
+            timestamp_ms,
+            text: text.to_string(),
+            is_final,
+            speaker: None,
+        }
+    }
+
+    #[test]
+    fn test_redact_samples_and_transcript() {
+        let ranges = [
+            RedactionRange {
+                start_ms: 10,
+                end_ms: 20,
+            },
+            RedactionRange {
+                start_ms: 15,
+                end_ms: 25,
+            },
+        ];
+        assert!(validate_ranges(&ranges).is_ok());
+        assert!(validate_ranges(&[]).is_err());
+        assert!(validate_ranges(&[RedactionRange {
+            start_ms: 5,
+            end_ms: 5
+        }])
+        .is_err());
+
+        let ranges = merge_ranges(&ranges);
+        assert_eq!(
+            ranges,
+            vec![RedactionRange {
+                start_ms: 10,
+                end_ms: 25
+            }]
+        );
+
+        // 0-40ms chunk
+        let mut samples = vec![1000i16; 640];
+        let overwritten = redact_samples(&mut samples, 0, &ranges, AudioRedaction::Silence);
+        assert_eq!(overwritten, 15 * 16);
+        assert!(samples[..160].iter().all(|&s| s == 1000));
+        assert!(samples[160..400].iter().all(|&s| s == 0));
+        assert!(samples[400..].iter().all(|&s| s == 1000));
+
+        // 20-40ms chunk
+        let mut samples = vec![1000i16; 320];
+        redact_samples(&mut samples, 320, &ranges, AudioRedaction::Tone);
+        assert!(samples[..80].iter().all(|&s| s != 1000 && s.abs() <= 3277));
+        assert!(samples[80..].iter().all(|&s| s == 1000));
+
+        let events = [
+            event(5, "before", true),
+            event(8, "partial", false),
+            event(12, "inside", true),
+            event(30, "spoken into the range", true),
+            event(40, "after", true),
+        ];
+        let (flagged, count) = redact_transcript(&events, &ranges, TranscriptRedaction::Flag);
+        assert_eq!(count, 2);
+        let texts: Vec<&str> = flagged.iter().map(|e| e.text.as_str()).collect();
+        assert_eq!(texts, ["before", REDACTED_TEXT, REDACTED_TEXT, "after"]);
+
+        let (removed, _) = redact_transcript(&events, &ranges, TranscriptRedaction::Remove);
+        let texts: Vec<&str> = removed.iter().map(|e| e.text.as_str()).collect();
+        assert_eq!(texts, ["before", "after"]);
+    }
+}
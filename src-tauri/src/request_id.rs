@@ -0,0 +1,232 @@
+//! Session-scoped IPC request IDs
+//!
+//! Audio requests sent to the sidecar carry IDs of the form
+//! `{session}-{stream}-{seq}` (e.g. `20250101-093000-audio-42`). The sidecar
+//! echoes the ID as `requestId` in the events it emits for that request, so
+//! events can be attributed to a session and stream. Events whose ID was not
+//! issued by the current session (a previous session's sidecar output, a
+//! malformed ID) are rejected, and a final text repeated for the same request
+//! is detected as a duplicate.
+//!
+//! Session IDs may contain '-', so IDs are parsed from the right: the stream
+//! name never contains '-' and the sequence number is decimal.
+
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::fmt;
+use std::sync::Mutex;
+use thiserror::Error;
+
+/// Stream of the (mixed) recording audio
+pub const AUDIO_STREAM: &str = "audio";
+
+/// Final texts remembered for duplicate detection
+const RECENT_FINALS: usize = 256;
+
+/// Parsed request ID
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RequestId {
+    pub session: String,
+    pub stream: String,
+    pub seq: u64,
+}
+
+impl fmt::Display for RequestId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}-{}", self.session, self.stream, self.seq)
+    }
+}
+
+impl RequestId {
+    pub fn parse(raw: &str) -> Result<Self, RequestIdError> {
+        let malformed = || RequestIdError::Malformed(raw.to_string());
+        let mut parts = raw.rsplitn(3, '-');
+        let seq = parts
+            .next()
+            .filter(|s| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()))
+            .and_then(|s| s.parse::<u64>().ok())
+            .ok_or_else(malformed)?;
+        let stream = parts
+            .next()
+            .filter(|s| is_valid_stream(s))
+            .ok_or_else(malformed)?;
+        let session = parts
+            .next()
+            .filter(|s| !s.is_empty())
+            .ok_or_else(malformed)?;
+        Ok(Self {
+            session: session.to_string(),
+            stream: stream.to_string(),
+            seq,
+        })
+    }
+}
+
+/// Stream names: lowercase ASCII alphanumerics and '_'
+pub fn is_valid_stream(stream: &str) -> bool {
+    !stream.is_empty()
+        && stream
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_')
+}
+
+/// Why an incoming request ID was rejected
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum RequestIdError {
+    #[error("Malformed request ID: {0}")]
+    Malformed(String),
+    #[error("Request ID {id} belongs to session {session}, not the current one")]
+    ForeignSession { id: String, session: String },
+    #[error("Request ID {0} was never issued")]
+    NotIssued(String),
+}
+
+#[derive(Debug, Default)]
+struct NamespaceState {
+    /// Next sequence number per stream
+    next_seq: BTreeMap<String, u64>,
+    recent_finals: VecDeque<(RequestId, String)>,
+    recent_final_keys: HashSet<(RequestId, String)>,
+}
+
+/// Issues and validates the request IDs of one session
+#[derive(Debug)]
+pub struct RequestIdNamespace {
+    session: String,
+    state: Mutex<NamespaceState>,
+}
+
+impl RequestIdNamespace {
+    pub fn new(session: &str) -> Self {
+        Self {
+            session: session.to_string(),
+            state: Mutex::new(NamespaceState::default()),
+        }
+    }
+
+    pub fn session(&self) -> &str {
+        &self.session
+    }
+
+    /// Issue the next ID of `stream` (sequence numbers start at 1)
+    pub fn next_id(&self, stream: &str) -> RequestId {
+        debug_assert!(is_valid_stream(stream), "invalid stream name {stream:?}");
+        let mut state = self.state.lock().unwrap();
+        let next = state.next_seq.entry(stream.to_string()).or_insert(1);
+        let seq = *next;
+        *next += 1;
+        RequestId {
+            session: self.session.clone(),
+            stream: stream.to_string(),
+            seq,
+        }
+    }
+
+    /// Validate an ID received from the sidecar
+    pub fn validate(&self, raw: &str) -> Result<RequestId, RequestIdError> {
+        let id = RequestId::parse(raw)?;
+        if id.session != self.session {
+            return Err(RequestIdError::ForeignSession {
+                id: raw.to_string(),
+                session: id.session,
+            });
+        }
+        let issued = self
+            .state
+            .lock()
+            .unwrap()
+            .next_seq
+            .get(&id.stream)
+            .is_some_and(|next| id.seq < *next);
+        if !issued {
+            return Err(RequestIdError::NotIssued(raw.to_string()));
+        }
+        Ok(id)
+    }
+
+    /// Record a final text; returns true if the same text was already
+    /// finalized for this request
+    pub fn is_duplicate_final(&self, id: &RequestId, text: &str) -> bool {
+        let key = (id.clone(), text.to_string());
+        let mut state = self.state.lock().unwrap();
+        if state.recent_final_keys.contains(&key) {
+            return true;
+        }
+        state.recent_final_keys.insert(key.clone());
+        state.recent_finals.push_back(key);
+        while state.recent_finals.len() > RECENT_FINALS {
+            if let Some(old) = state.recent_finals.pop_front() {
+                state.recent_final_keys.remove(&old);
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_and_parse_roundtrip() {
+        let namespace = RequestIdNamespace::new("20250101-093000-ab12");
+        let first = namespace.next_id(AUDIO_STREAM);
+        let second = namespace.next_id(AUDIO_STREAM);
+        assert_eq!(first.to_string(), "20250101-093000-ab12-audio-1");
+        assert_eq!(second.seq, 2);
+        assert_eq!(namespace.next_id("mic_1").seq, 1);
+
+        let parsed = RequestId::parse(&second.to_string()).unwrap();
+        assert_eq!(parsed, second);
+
+        for raw in [
+            "audio-1700000000000",
+            "s-audio-",
+            "s-Audio-1",
+            "-audio-1",
+            "x",
+        ] {
+            assert!(
+                matches!(RequestId::parse(raw), Err(RequestIdError::Malformed(_))),
+                "{raw} should be malformed"
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_rejects_foreign_and_unissued_ids() {
+        let namespace = RequestIdNamespace::new("s-2");
+        let issued = namespace.next_id(AUDIO_STREAM);
+        assert_eq!(namespace.validate(&issued.to_string()).unwrap(), issued);
+
+        assert!(matches!(
+            namespace.validate("s-1-audio-1"),
+            Err(RequestIdError::ForeignSession { session, .. }) if session == "s-1"
+        ));
+        assert!(matches!(
+            namespace.validate("s-2-audio-2"),
+            Err(RequestIdError::NotIssued(_))
+        ));
+        assert!(matches!(
+            namespace.validate("s-2-mic-1"),
+            Err(RequestIdError::NotIssued(_))
+        ));
+    }
+
+    #[test]
+    fn test_duplicate_final_detection() {
+        let namespace = RequestIdNamespace::new("s");
+        let a = namespace.next_id(AUDIO_STREAM);
+        let b = namespace.next_id(AUDIO_STREAM);
+
+        assert!(!namespace.is_duplicate_final(&a, "hello"));
+        assert!(namespace.is_duplicate_final(&a, "hello"));
+        assert!(!namespace.is_duplicate_final(&a, "world"));
+        assert!(!namespace.is_duplicate_final(&b, "hello"));
+
+        // Only the most recent finals are remembered
+        for i in 0..RECENT_FINALS {
+            namespace.is_duplicate_final(&b, &i.to_string());
+        }
+        assert!(!namespace.is_duplicate_final(&a, "hello"));
+    }
+}
@@ -0,0 +1,441 @@
+//! Session Retention
+//!
+//! Optional automatic cleanup of old recordings. When enabled, sessions past
+//! any configured limit (age, count, total size) are removed oldest first:
+//! moved to the trash (purged once the trash retention window ends) or moved
+//! to an archive directory. The session being recorded and sessions whose
+//! start time can't be read are never removed.
+//!
+//! Runs at startup and every `check_interval_hours` as a maintenance job;
+//! a run that removes anything emits `retention_applied` with its summary.
+//!
+//! Persisted to `settings/retention.json` in app data directory.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::storage::LocalStorageService;
+
+const MS_PER_DAY: u64 = 24 * 60 * 60 * 1000;
+const BYTES_PER_GB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+/// What happens to sessions past the limits
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetentionAction {
+    /// Move to the trash (restorable until the trash retention ends)
+    #[default]
+    Trash,
+    /// Move to `archive_dir`
+    Archive,
+}
+
+/// Retention configuration (every limit is optional)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RetentionSettings {
+    /// Automatic cleanup is off unless enabled
+    #[serde(default)]
+    pub enabled: bool,
+    /// Remove sessions started more than this many days ago
+    #[serde(default)]
+    pub max_age_days: Option<u32>,
+    /// Keep at most this many sessions
+    #[serde(default)]
+    pub max_sessions: Option<u32>,
+    /// Keep the sessions' total size under this many GB
+    #[serde(default)]
+    pub max_total_gb: Option<f64>,
+    #[serde(default)]
+    pub action: RetentionAction,
+    /// Destination of `RetentionAction::Archive`
+    #[serde(default)]
+    pub archive_dir: Option<PathBuf>,
+    /// Hours between periodic runs (min 1)
+    #[serde(default = "default_check_interval_hours")]
+    pub check_interval_hours: u32,
+    /// Settings version for future migrations
+    #[serde(default = "default_version")]
+    pub version: u32,
+}
+
+fn default_check_interval_hours() -> u32 {
+    24
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+impl Default for RetentionSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_age_days: None,
+            max_sessions: None,
+            max_total_gb: None,
+            action: RetentionAction::Trash,
+            archive_dir: None,
+            check_interval_hours: default_check_interval_hours(),
+            version: 1,
+        }
+    }
+}
+
+impl RetentionSettings {
+    pub fn check_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.check_interval_hours.max(1) as u64 * 3600)
+    }
+}
+
+/// Which limit a session was removed for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetentionReason {
+    MaxAge,
+    MaxSessions,
+    MaxTotalSize,
+}
+
+/// A session considered by retention
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionUsage {
+    pub session_id: String,
+    /// Recording start (None = unreadable; never removed)
+    pub started_at_ms: Option<u64>,
+    pub size_bytes: u64,
+}
+
+/// A session selected for removal
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RetentionCandidate {
+    pub session_id: String,
+    pub started_at_ms: u64,
+    pub size_bytes: u64,
+    pub reason: RetentionReason,
+}
+
+/// Sessions past the limits, oldest first
+///
+/// Limits apply in order: age, then count, then total size, each on the
+/// sessions the previous ones kept. Sessions without a start time still
+/// count toward the count and size limits.
+pub fn select_for_removal(
+    sessions: &[SessionUsage],
+    settings: &RetentionSettings,
+    now_ms: u64,
+) -> Vec<RetentionCandidate> {
+    let mut removable: Vec<(&SessionUsage, u64)> = sessions
+        .iter()
+        .filter_map(|s| s.started_at_ms.map(|started| (s, started)))
+        .collect();
+    removable.sort_by(|a, b| {
+        a.1.cmp(&b.1)
+            .then_with(|| a.0.session_id.cmp(&b.0.session_id))
+    });
+
+    let mut kept_count = sessions.len() as u64;
+    let mut kept_bytes: u64 = sessions.iter().map(|s| s.size_bytes).sum();
+    let max_age_ms = settings.max_age_days.map(|days| days as u64 * MS_PER_DAY);
+    let max_bytes = settings
+        .max_total_gb
+        .map(|gb| (gb.max(0.0) * BYTES_PER_GB) as u64);
+
+    let mut selected = Vec::new();
+    for (session, started_at_ms) in removable {
+        let reason = if max_age_ms.is_some_and(|max| now_ms.saturating_sub(started_at_ms) > max) {
+            RetentionReason::MaxAge
+        } else if settings
+            .max_sessions
+            .is_some_and(|max| kept_count > max as u64)
+        {
+            RetentionReason::MaxSessions
+        } else if max_bytes.is_some_and(|max| kept_bytes > max) {
+            RetentionReason::MaxTotalSize
+        } else {
+            continue;
+        };
+        kept_count -= 1;
+        kept_bytes -= session.size_bytes;
+        selected.push(RetentionCandidate {
+            session_id: session.session_id.clone(),
+            started_at_ms,
+            size_bytes: session.size_bytes,
+            reason,
+        });
+    }
+    selected
+}
+
+/// A session retention failed to remove
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RetentionFailure {
+    pub session_id: String,
+    pub error: String,
+}
+
+/// Outcome of one retention run
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RetentionSummary {
+    pub ran_at_ms: u64,
+    pub action: RetentionAction,
+    pub removed: Vec<RetentionCandidate>,
+    pub failed: Vec<RetentionFailure>,
+    pub freed_bytes: u64,
+    pub remaining_sessions: usize,
+    pub remaining_bytes: u64,
+}
+
+/// Sessions in the recordings directory, except `skip_session`
+pub fn scan_sessions(
+    storage: &LocalStorageService,
+    skip_session: Option<&str>,
+) -> Result<Vec<SessionUsage>> {
+    Ok(storage
+        .list_sessions()?
+        .into_iter()
+        .filter(|metadata| Some(metadata.session_id.as_str()) != skip_session)
+        .map(|metadata| SessionUsage {
+            size_bytes: dir_size(&storage.get_session_dir(&metadata.session_id)),
+            started_at_ms: parse_start_ms(&metadata.start_time),
+            session_id: metadata.session_id,
+        })
+        .collect())
+}
+
+/// Sessions the next run would remove (no changes made)
+pub fn preview(
+    storage: &LocalStorageService,
+    settings: &RetentionSettings,
+    skip_session: Option<&str>,
+    now_ms: u64,
+) -> Result<Vec<RetentionCandidate>> {
+    let sessions = scan_sessions(storage, skip_session)?;
+    Ok(select_for_removal(&sessions, settings, now_ms))
+}
+
+/// Remove the sessions past the limits
+///
+/// A session that fails to move is reported and the run continues.
+pub fn apply(
+    storage: &LocalStorageService,
+    settings: &RetentionSettings,
+    skip_session: Option<&str>,
+    now_ms: u64,
+) -> Result<RetentionSummary> {
+    let archive_dir = match settings.action {
+        RetentionAction::Trash => None,
+        RetentionAction::Archive => Some(
+            settings
+                .archive_dir
+                .as_deref()
+                .context("Retention action is archive but no archive directory is set")?,
+        ),
+    };
+
+    let sessions = scan_sessions(storage, skip_session)?;
+    let mut summary = RetentionSummary {
+        ran_at_ms: now_ms,
+        action: settings.action,
+        removed: Vec::new(),
+        failed: Vec::new(),
+        freed_bytes: 0,
+        remaining_sessions: sessions.len(),
+        remaining_bytes: sessions.iter().map(|s| s.size_bytes).sum(),
+    };
+
+    for candidate in select_for_removal(&sessions, settings, now_ms) {
+        let result = match archive_dir {
+            None => storage
+                .trash_session(&candidate.session_id, now_ms)
+                .map(|_| ()),
+            Some(dir) => archive_session(storage, &candidate.session_id, dir),
+        };
+        match result {
+            Ok(()) => {
+                summary.freed_bytes += candidate.size_bytes;
+                summary.remaining_sessions -= 1;
+                summary.remaining_bytes -= candidate.size_bytes;
+                summary.removed.push(candidate);
+            }
+            Err(e) => summary.failed.push(RetentionFailure {
+                session_id: candidate.session_id,
+                error: format!("{:#}", e),
+            }),
+        }
+    }
+    Ok(summary)
+}
+
+/// Move a session directory into the archive directory
+fn archive_session(
+    storage: &LocalStorageService,
+    session_id: &str,
+    archive_dir: &Path,
+) -> Result<()> {
+    let destination = archive_dir.join(session_id);
+    if destination.exists() {
+        anyhow::bail!("Already archived: {:?}", destination);
+    }
+    std::fs::create_dir_all(archive_dir)
+        .with_context(|| format!("Failed to create archive directory: {:?}", archive_dir))?;
+    crate::storage_root::move_entry(&storage.get_session_dir(session_id), &destination)
+}
+
+/// Check an archive directory against the recordings directory
+pub fn validate_archive_dir(archive_dir: &Path, recordings_dir: &Path) -> Result<()> {
+    crate::storage_root::validate_root(archive_dir)?;
+    if archive_dir.starts_with(recordings_dir) || recordings_dir.starts_with(archive_dir) {
+        anyhow::bail!(
+            "Archive directory must be outside the recordings directory: {:?}",
+            archive_dir
+        );
+    }
+    Ok(())
+}
+
+/// `start_time` of session.json as epoch ms
+fn parse_start_ms(start_time: &str) -> Option<u64> {
+    chrono::DateTime::parse_from_rfc3339(start_time)
+        .ok()
+        .and_then(|time| u64::try_from(time.timestamp_millis()).ok())
+}
+
+/// Total size of the files under `path` (unreadable entries count as 0)
+pub fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| match entry.file_type() {
+            Ok(kind) if kind.is_dir() => dir_size(&entry.path()),
+            Ok(_) => entry.metadata().map(|m| m.len()).unwrap_or(0),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+pub const SETTINGS_STEM: &str = "retention";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::SessionMetadata;
+    use tempfile::TempDir;
+
+    fn usage(session_id: &str, started_at_ms: Option<u64>, size_bytes: u64) -> SessionUsage {
+        SessionUsage {
+            session_id: session_id.to_string(),
+            started_at_ms,
+            size_bytes,
+        }
+    }
+
+    #[test]
+    fn test_select_applies_limits_oldest_first() {
+        let now = 100 * MS_PER_DAY;
+        let sessions = vec![
+            usage("d", Some(99 * MS_PER_DAY), 400),
+            usage("a", Some(10 * MS_PER_DAY), 100),
+            usage("unknown", None, 100),
+            usage("c", Some(95 * MS_PER_DAY), 300),
+            usage("b", Some(90 * MS_PER_DAY), 200),
+        ];
+
+        let settings = RetentionSettings {
+            enabled: true,
+            max_age_days: Some(30),
+            max_sessions: Some(3),
+            max_total_gb: Some(550.0 / BYTES_PER_GB),
+            ..Default::default()
+        };
+        let selected = select_for_removal(&sessions, &settings, now);
+        let picked: Vec<(&str, RetentionReason)> = selected
+            .iter()
+            .map(|c| (c.session_id.as_str(), c.reason))
+            .collect();
+        // a: too old; b: 4 sessions left > 3; c: 800 bytes left > 550
+        assert_eq!(
+            picked,
+            vec![
+                ("a", RetentionReason::MaxAge),
+                ("b", RetentionReason::MaxSessions),
+                ("c", RetentionReason::MaxTotalSize),
+            ]
+        );
+
+        // No limits: nothing selected
+        assert!(select_for_removal(&sessions, &RetentionSettings::default(), now).is_empty());
+    }
+
+    fn write_session(storage: &LocalStorageService, session_id: &str, start_time: &str) {
+        let dir = storage.create_session(session_id).unwrap();
+        std::fs::write(dir.join("audio.wav"), vec![0u8; 1000]).unwrap();
+        storage
+            .save_session_metadata(&SessionMetadata {
+                session_id: session_id.to_string(),
+                start_time: start_time.to_string(),
+                end_time: start_time.to_string(),
+                duration_seconds: 1,
+                audio_device: "mic".to_string(),
+                model_size: "small".to_string(),
+                total_segments: 0,
+                total_characters: 0,
+                warnings: Vec::new(),
+                session_uuid: None,
+                title: None,
+                tags: Vec::new(),
+                stats: None,
+                levels: None,
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn test_apply_trashes_or_archives_oldest_sessions() {
+        let dir = TempDir::new().unwrap();
+        let storage = LocalStorageService::new(dir.path().to_path_buf());
+        write_session(&storage, "s1", "2025-01-01T09:00:00Z");
+        write_session(&storage, "s2", "2025-01-02T09:00:00Z");
+        write_session(&storage, "s3", "2025-01-03T09:00:00Z");
+        write_session(&storage, "s4", "2025-01-04T09:00:00Z");
+        let now = parse_start_ms("2025-01-05T09:00:00Z").unwrap();
+
+        let mut settings = RetentionSettings {
+            enabled: true,
+            max_sessions: Some(2),
+            ..Default::default()
+        };
+        // The active session is never removed
+        let summary = apply(&storage, &settings, Some("s1"), now).unwrap();
+        assert_eq!(summary.removed.len(), 1);
+        assert_eq!(summary.removed[0].session_id, "s2");
+        assert!(summary.freed_bytes >= 1000);
+        assert_eq!(summary.remaining_sessions, 2);
+        assert_eq!(storage.list_trashed_sessions().unwrap()[0].session_id, "s2");
+
+        let archive = dir.path().join("archive");
+        settings.action = RetentionAction::Archive;
+        settings.archive_dir = Some(archive.clone());
+        settings.max_sessions = Some(1);
+        let summary = apply(&storage, &settings, None, now).unwrap();
+        let removed: Vec<&str> = summary
+            .removed
+            .iter()
+            .map(|c| c.session_id.as_str())
+            .collect();
+        assert_eq!(removed, vec!["s1", "s3"]);
+        assert!(archive.join("s1").join("session.json").exists());
+        assert!(!storage.get_session_dir("s3").exists());
+        assert_eq!(storage.list_sessions().unwrap().len(), 1);
+
+        // Archive without a directory is refused before touching anything
+        settings.archive_dir = None;
+        assert!(apply(&storage, &settings, None, now).is_err());
+    }
+}
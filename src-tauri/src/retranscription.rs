@@ -0,0 +1,352 @@
+//! Re-transcription
+//!
+//! Runs the recorded audio of a finished session through the sidecar again
+//! (e.g. after switching to a larger model) and saves the result as another
+//! transcript version, `transcription.<version>.jsonl` (see `storage`). The
+//! recording-time transcript (`v1`) is never overwritten; compare versions
+//! with `diff_transcripts`.
+//!
+//! Jobs get a sidecar of their own, so live meetings keep theirs. The audio
+//! goes to it as `process_audio_stream` requests of one second each, the
+//! next one after the `audio_ack` of the previous one. A final text is
+//! stamped with the end of the batch it arrived in, as the live pipeline
+//! stamps the session time at which a final arrives. Silence is appended
+//! so speech running to the end of the recording is finalized too.
+
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::process::{ChildStdin, ChildStdout};
+
+use crate::jobs::JobError;
+use crate::python_sidecar::PythonSidecarManager;
+use crate::request_id::RequestId;
+use crate::ring_buffer::SAMPLE_RATE;
+use crate::storage::{LocalStorageService, TranscriptionEvent};
+
+/// Stream name in the request IDs (see `request_id`)
+const STREAM: &str = "retranscribe";
+
+/// Audio per request
+const BATCH_SAMPLES: usize = SAMPLE_RATE;
+
+/// Silence after the recording, longer than the VAD's speech end
+const TRAILING_SILENCE_MS: usize = 2000;
+
+/// Time for the sidecar to acknowledge one batch (a final may be
+/// transcribed within it)
+const BATCH_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Finals below this confidence are Whisper hallucinations on silence
+/// (same threshold as live transcription)
+const MIN_CONFIDENCE: f64 = 0.50;
+
+/// Check a version name for new transcripts (`v1` is the recording's)
+pub fn validate_version(version: &str) -> Result<()> {
+    if version == "v1" {
+        anyhow::bail!("v1 is the transcript of the recording; choose another version name");
+    }
+    crate::storage::transcript_file_name(version)?;
+    Ok(())
+}
+
+/// Sidecar connection transcribing batch after batch
+pub struct SidecarTranscriber<W, R> {
+    writer: W,
+    reader: R,
+    seq: u64,
+}
+
+impl<W: AsyncWrite + Unpin, R: AsyncBufRead + Unpin> SidecarTranscriber<W, R> {
+    pub fn new(writer: W, reader: R) -> Self {
+        Self {
+            writer,
+            reader,
+            seq: 0,
+        }
+    }
+
+    /// Send one batch and wait for its `audio_ack`; returns the final texts
+    /// that arrived in between
+    async fn transcribe_batch(&mut self, session_id: &str, pcm: &[u8]) -> Result<Vec<String>> {
+        self.seq += 1;
+        let id = RequestId {
+            session: session_id.to_string(),
+            stream: STREAM.to_string(),
+            seq: self.seq,
+        }
+        .to_string();
+        let mut line = crate::ipc_protocol::encode_audio_stream_request(&id, pcm)?;
+        line.push('\n');
+        self.writer.write_all(line.as_bytes()).await?;
+        self.writer.flush().await?;
+
+        let mut finals = Vec::new();
+        let reader = &mut self.reader;
+        tokio::time::timeout(BATCH_TIMEOUT, async {
+            let mut line = String::new();
+            loop {
+                line.clear();
+                if reader.read_line(&mut line).await? == 0 {
+                    anyhow::bail!("Sidecar exited");
+                }
+                // Not every line is a message (e.g. stray prints)
+                let Ok(message) = serde_json::from_str::<serde_json::Value>(line.trim()) else {
+                    continue;
+                };
+                match message["type"].as_str() {
+                    Some("error") if message["id"] == id.as_str() => anyhow::bail!(
+                        "Sidecar error: {}",
+                        message["errorMessage"].as_str().unwrap_or("unknown")
+                    ),
+                    Some("event") => {
+                        let data = &message["data"];
+                        match message["eventType"].as_str() {
+                            Some("final_text") => {
+                                let text = data["text"].as_str().unwrap_or("").trim();
+                                let confident = data["confidence"]
+                                    .as_f64()
+                                    .is_none_or(|confidence| confidence >= MIN_CONFIDENCE);
+                                if !text.is_empty() && confident {
+                                    finals.push(text.to_string());
+                                }
+                            }
+                            Some("audio_ack") if data["requestId"] == id.as_str() => {
+                                return Ok(());
+                            }
+                            _ => {}
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        })
+        .await
+        .context("Sidecar did not acknowledge the audio")??;
+        Ok(finals)
+    }
+}
+
+/// Start a sidecar for a re-transcription job
+///
+/// Uses the machine's cloud STT settings, like the recording sidecar. The
+/// manager owns the process: keep it until the job is done.
+pub async fn start_sidecar(
+    cloud_stt: crate::cloud_stt::CloudSttSettings,
+    api_key: Option<String>,
+) -> Result<(
+    PythonSidecarManager,
+    SidecarTranscriber<ChildStdin, BufReader<ChildStdout>>,
+)> {
+    let mut sidecar = PythonSidecarManager::new();
+    sidecar.set_cloud_stt(cloud_stt, api_key);
+    sidecar
+        .start()
+        .await
+        .context("Failed to start Python sidecar")?;
+    sidecar
+        .wait_for_ready()
+        .await
+        .context("Python sidecar did not become ready")?;
+    let stdin = sidecar.take_stdin().context("Sidecar stdin unavailable")?;
+    let stdout = sidecar
+        .take_stdout()
+        .context("Sidecar stdout unavailable")?;
+    Ok((sidecar, SidecarTranscriber::new(stdin, stdout)))
+}
+
+/// Re-transcribe one session and save the result as `version`
+///
+/// `checkpoint` runs before each batch (see `JobContext::checkpoint`).
+/// Nothing is written unless the whole recording was transcribed. Returns
+/// the transcript file.
+pub async fn retranscribe_session<W, R>(
+    transcriber: &mut SidecarTranscriber<W, R>,
+    storage: &LocalStorageService,
+    session_id: &str,
+    version: &str,
+    checkpoint: impl Fn() -> std::result::Result<(), JobError>,
+) -> Result<PathBuf>
+where
+    W: AsyncWrite + Unpin,
+    R: AsyncBufRead + Unpin,
+{
+    crate::session_id::validate_session_id(session_id)?;
+    validate_version(version)?;
+
+    // Decoded on a thread of its own, one batch at a time
+    let session_dir = storage.get_session_dir(session_id);
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<Vec<i16>>(4);
+    let decoder = std::thread::spawn(move || {
+        let mut batch = Vec::with_capacity(BATCH_SAMPLES);
+        crate::storage::read_session_audio(&session_dir, |chunk| {
+            for &sample in chunk {
+                batch.push(sample);
+                if batch.len() == BATCH_SAMPLES {
+                    let _ = tx.blocking_send(std::mem::take(&mut batch));
+                }
+            }
+        })?;
+        if !batch.is_empty() {
+            let _ = tx.blocking_send(batch);
+        }
+        anyhow::Ok(())
+    });
+
+    let mut events = Vec::new();
+    let mut sent_samples = 0usize;
+    let mut push_finals = |finals: Vec<String>, sent_samples: usize| {
+        let timestamp_ms = (sent_samples * 1000 / SAMPLE_RATE) as u64;
+        events.extend(finals.into_iter().map(|text| TranscriptionEvent {
+            timestamp_ms,
+            text,
+            is_final: true,
+            speaker: None,
+        }));
+    };
+    while let Some(samples) = rx.recv().await {
+        checkpoint()?;
+        sent_samples += samples.len();
+        let pcm: Vec<u8> = samples.iter().flat_map(|s| s.to_le_bytes()).collect();
+        push_finals(
+            transcriber.transcribe_batch(session_id, &pcm).await?,
+            sent_samples,
+        );
+    }
+    decoder
+        .join()
+        .map_err(|_| anyhow::anyhow!("Audio decoder panicked"))??;
+
+    let silence = vec![0u8; TRAILING_SILENCE_MS * SAMPLE_RATE / 1000 * 2];
+    push_finals(
+        transcriber.transcribe_batch(session_id, &silence).await?,
+        sent_samples,
+    );
+
+    storage.save_transcript_version(session_id, version, &events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording_session::RecordingSession;
+    use crate::storage::AudioFormat;
+    use serde_json::json;
+
+    /// Answer every request with an ack; finals for the given request numbers
+    fn fake_sidecar(
+        stream: tokio::io::DuplexStream,
+        finals: Vec<(u64, &'static str, f64)>,
+        fail_at: Option<u64>,
+    ) -> tokio::task::JoinHandle<u64> {
+        tokio::spawn(async move {
+            let (reader, mut writer) = tokio::io::split(stream);
+            let mut lines = BufReader::new(reader).lines();
+            let mut requests = 0;
+            while let Ok(Some(line)) = lines.next_line().await {
+                requests += 1;
+                let request: serde_json::Value = serde_json::from_str(&line).unwrap();
+                let id = request["id"].as_str().unwrap().to_string();
+                let mut replies = vec![json!("log line").to_string()];
+                if fail_at == Some(requests) {
+                    replies.push(
+                        json!({ "type": "error", "id": id, "errorMessage": "model crashed" })
+                            .to_string(),
+                    );
+                }
+                for (at, text, confidence) in &finals {
+                    if *at == requests {
+                        replies.push(
+                            json!({
+                                "type": "event",
+                                "eventType": "final_text",
+                                "data": { "requestId": id, "text": text, "confidence": confidence }
+                            })
+                            .to_string(),
+                        );
+                    }
+                }
+                replies.push(
+                    json!({ "type": "event", "eventType": "audio_ack", "data": { "requestId": id } })
+                        .to_string(),
+                );
+                for reply in replies {
+                    writer
+                        .write_all(format!("{}\n", reply).as_bytes())
+                        .await
+                        .unwrap();
+                }
+            }
+            requests
+        })
+    }
+
+    fn recorded_session(storage: &LocalStorageService, session_id: &str, samples: usize) {
+        let session = RecordingSession::new(session_id, "u1", 0);
+        session.start(Some(storage), AudioFormat::Wav).unwrap();
+        session.append_audio(&vec![0u8; samples * 2]).unwrap();
+        session.stop(Some(storage), "mic", Vec::new()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_retranscribe_session_saves_version() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = LocalStorageService::new(dir.path().to_path_buf());
+        recorded_session(&storage, "s1", SAMPLE_RATE * 5 / 2);
+
+        let (client, server) = tokio::io::duplex(1 << 20);
+        let sidecar = fake_sidecar(
+            server,
+            vec![(2, "こんにちは", 0.9), (3, "…", 0.1), (4, "最後", 0.8)],
+            None,
+        );
+        let (reader, writer) = tokio::io::split(client);
+        let mut transcriber = SidecarTranscriber::new(writer, BufReader::new(reader));
+
+        let path = retranscribe_session(&mut transcriber, &storage, "s1", "v2", || Ok(()))
+            .await
+            .unwrap();
+        assert!(path.ends_with("transcription.v2.jsonl"));
+        let saved = storage.load_transcript_version("s1", "v2").unwrap();
+        let saved: Vec<_> = saved
+            .iter()
+            .map(|e| (e.timestamp_ms, e.text.as_str()))
+            .collect();
+        // 2.5 s in three batches, then the trailing silence
+        assert_eq!(saved, [(2000, "こんにちは"), (2500, "最後")]);
+
+        drop(transcriber);
+        assert_eq!(sidecar.await.unwrap(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_retranscribe_session_failures() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = LocalStorageService::new(dir.path().to_path_buf());
+        recorded_session(&storage, "s1", SAMPLE_RATE * 2);
+
+        let (client, server) = tokio::io::duplex(1 << 20);
+        let _sidecar = fake_sidecar(server, Vec::new(), Some(2));
+        let (reader, writer) = tokio::io::split(client);
+        let mut transcriber = SidecarTranscriber::new(writer, BufReader::new(reader));
+
+        assert!(
+            retranscribe_session(&mut transcriber, &storage, "s1", "v1", || Ok(()))
+                .await
+                .is_err()
+        );
+        let cancelled = retranscribe_session(&mut transcriber, &storage, "s1", "v2", || {
+            Err(JobError::Cancelled)
+        })
+        .await
+        .unwrap_err();
+        assert!(cancelled.to_string().contains("cancelled"));
+        let failed = retranscribe_session(&mut transcriber, &storage, "s1", "v2", || Ok(()))
+            .await
+            .unwrap_err();
+        assert!(failed.to_string().contains("model crashed"));
+        // Nothing written for failed runs
+        assert!(storage.load_transcript_version("s1", "v2").is_err());
+    }
+}
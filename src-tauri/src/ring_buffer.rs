@@ -4,6 +4,8 @@
 // Updated: Drop-oldest strategy for real-time priority
 
 use ringbuf::{traits::*, HeapRb};
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 /// Buffer capacity constants (ADR-013)
@@ -12,8 +14,21 @@ pub const CHANNELS: usize = 1; // mono
 pub const BYTES_PER_SAMPLE: usize = 2; // 16-bit PCM
 pub const BUFFER_SECS: usize = 5; // 5 seconds
 
+/// Audio send queue bound, expressed in milliseconds of audio
+pub const BUFFER_MS: usize = BUFFER_SECS * 1000;
+
 /// Total buffer capacity: 160,000 bytes = 156 KB
-pub const BUFFER_CAPACITY: usize = SAMPLE_RATE * CHANNELS * BYTES_PER_SAMPLE * BUFFER_SECS;
+pub const BUFFER_CAPACITY: usize = bytes_for_ms(BUFFER_MS);
+
+/// Bytes of 16kHz mono 16-bit PCM for a duration in milliseconds
+pub const fn bytes_for_ms(ms: usize) -> usize {
+    SAMPLE_RATE * CHANNELS * BYTES_PER_SAMPLE * ms / 1000
+}
+
+/// Duration in milliseconds of 16kHz mono 16-bit PCM bytes
+pub const fn ms_for_bytes(bytes: usize) -> u64 {
+    (bytes * 1000 / (SAMPLE_RATE * CHANNELS * BYTES_PER_SAMPLE)) as u64
+}
 
 /// Buffer occupancy level
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -98,6 +113,101 @@ pub fn occupancy(rb: &HeapRb<u8>) -> f32 {
     rb.occupied_len() as f32 / BUFFER_CAPACITY as f32
 }
 
+/// Send queue accounting (shared by audio callback and sender task)
+///
+/// Overflow behavior:
+/// 1. Occupancy above `BufferLevel::Normal` wakes the sender task early
+///    (the real-time callback never blocks, so the consumer drains instead)
+/// 2. If the queue is still full, the oldest audio is dropped and counted;
+///    the sender task reports new drops as an `audio_queue_overflow` event
+#[derive(Debug, Default)]
+pub struct AudioQueueMetrics {
+    /// Bytes currently queued
+    pub queued_bytes: AtomicU64,
+    /// Highest queued bytes observed in this session
+    pub peak_queued_bytes: AtomicU64,
+    /// Total bytes accepted into the queue
+    pub enqueued_bytes: AtomicU64,
+    /// Total bytes taken by the sender task
+    pub dequeued_bytes: AtomicU64,
+    /// Total oldest bytes discarded on overflow
+    pub dropped_bytes: AtomicU64,
+    /// Number of pushes that had to drop data
+    pub overflow_count: AtomicU64,
+    /// Frames skipped because the queue lock was contended
+    pub skipped_frames: AtomicU64,
+}
+
+/// Point-in-time copy of `AudioQueueMetrics`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct AudioQueueSnapshot {
+    pub capacity_bytes: u64,
+    pub capacity_ms: u64,
+    pub queued_bytes: u64,
+    pub queued_ms: u64,
+    pub peak_queued_bytes: u64,
+    pub enqueued_bytes: u64,
+    pub dequeued_bytes: u64,
+    pub dropped_bytes: u64,
+    pub dropped_ms: u64,
+    pub overflow_count: u64,
+    pub skipped_frames: u64,
+}
+
+impl AudioQueueMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a push result and the resulting occupancy
+    pub fn record_push(&self, pushed: usize, dropped: usize, occupied: usize) {
+        self.enqueued_bytes
+            .fetch_add(pushed as u64, Ordering::Relaxed);
+        if dropped > 0 {
+            self.dropped_bytes
+                .fetch_add(dropped as u64, Ordering::Relaxed);
+            self.overflow_count.fetch_add(1, Ordering::Relaxed);
+        }
+        self.queued_bytes.store(occupied as u64, Ordering::Relaxed);
+        self.peak_queued_bytes
+            .fetch_max(occupied as u64, Ordering::Relaxed);
+    }
+
+    /// Record a pop by the sender task and the resulting occupancy
+    pub fn record_pop(&self, popped: usize, occupied: usize) {
+        self.dequeued_bytes
+            .fetch_add(popped as u64, Ordering::Relaxed);
+        self.queued_bytes.store(occupied as u64, Ordering::Relaxed);
+    }
+
+    /// Record a frame lost to lock contention
+    pub fn record_skipped_frame(&self) {
+        self.skipped_frames.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn get_dropped_bytes(&self) -> u64 {
+        self.dropped_bytes.load(Ordering::Relaxed)
+    }
+
+    pub fn snapshot(&self) -> AudioQueueSnapshot {
+        let queued_bytes = self.queued_bytes.load(Ordering::Relaxed);
+        let dropped_bytes = self.get_dropped_bytes();
+        AudioQueueSnapshot {
+            capacity_bytes: BUFFER_CAPACITY as u64,
+            capacity_ms: BUFFER_MS as u64,
+            queued_bytes,
+            queued_ms: ms_for_bytes(queued_bytes as usize),
+            peak_queued_bytes: self.peak_queued_bytes.load(Ordering::Relaxed),
+            enqueued_bytes: self.enqueued_bytes.load(Ordering::Relaxed),
+            dequeued_bytes: self.dequeued_bytes.load(Ordering::Relaxed),
+            dropped_bytes,
+            dropped_ms: ms_for_bytes(dropped_bytes as usize),
+            overflow_count: self.overflow_count.load(Ordering::Relaxed),
+            skipped_frames: self.skipped_frames.load(Ordering::Relaxed),
+        }
+    }
+}
+
 /// Convert f32 PCM samples to 16-bit PCM bytes
 ///
 /// Input: f32 samples in range [-1.0, 1.0]
@@ -134,6 +244,44 @@ mod tests {
         assert_eq!(BUFFER_CAPACITY, 160_000);
     }
 
+    #[test]
+    fn test_ms_byte_conversion() {
+        // 20ms frame = 320 samples = 640 bytes
+        assert_eq!(bytes_for_ms(20), 640);
+        assert_eq!(ms_for_bytes(640), 20);
+        assert_eq!(ms_for_bytes(BUFFER_CAPACITY), BUFFER_MS as u64);
+    }
+
+    #[test]
+    fn test_queue_metrics_accounting() {
+        let rb = new_shared_ring_buffer();
+        let mut guard = rb.lock().unwrap();
+        let metrics = AudioQueueMetrics::new();
+
+        // Overfill by 1000 bytes
+        let chunk = vec![0u8; 32000];
+        for _ in 0..5 {
+            let (pushed, dropped, _) = push_audio_drop_oldest(&mut guard, &chunk);
+            metrics.record_push(pushed, dropped, guard.occupied_len());
+        }
+        let (pushed, dropped, _) = push_audio_drop_oldest(&mut guard, &[0u8; 1000]);
+        metrics.record_push(pushed, dropped, guard.occupied_len());
+
+        let mut buf = vec![0u8; 64000];
+        let popped = pop_audio(&mut guard, &mut buf);
+        metrics.record_pop(popped, guard.occupied_len());
+        metrics.record_skipped_frame();
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.enqueued_bytes, 161_000);
+        assert_eq!(snapshot.dropped_bytes, 1000);
+        assert_eq!(snapshot.overflow_count, 1);
+        assert_eq!(snapshot.peak_queued_bytes, BUFFER_CAPACITY as u64);
+        assert_eq!(snapshot.queued_bytes, 96_000);
+        assert_eq!(snapshot.queued_ms, 3000);
+        assert_eq!(snapshot.skipped_frames, 1);
+    }
+
     #[test]
     fn test_shared_ring_buffer_creation() {
         let rb = new_shared_ring_buffer();
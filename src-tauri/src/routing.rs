@@ -0,0 +1,289 @@
+//! Transcript Routing Rules
+//!
+//! Regex rules matched against final segments in the post-broadcast stage.
+//! Each matching rule dispatches the segment to its targets:
+//! webhook, clipboard, a separate JSONL file, or a session marker.
+//!
+//! Persisted to `settings/routing_rules.json` in app data directory.
+
+use anyhow::{Context, Result};
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::Path;
+
+// ============================================================================
+// Settings Struct
+// ============================================================================
+
+/// Dispatch target for a matched segment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RoutingTarget {
+    /// POST segment as JSON to a URL
+    Webhook { url: String },
+    /// Copy segment text to the system clipboard
+    Clipboard,
+    /// Append segment to a JSONL file in the session directory
+    Jsonl { file_name: String },
+    /// Create a session marker (label defaults to the rule name)
+    Marker {
+        #[serde(default)]
+        label: Option<String>,
+    },
+}
+
+/// Single routing rule
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoutingRule {
+    pub name: String,
+    /// Regular expression (Rust `regex` syntax)
+    pub pattern: String,
+    #[serde(default)]
+    pub case_insensitive: bool,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub targets: Vec<RoutingTarget>,
+}
+
+/// Routing configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoutingSettings {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub rules: Vec<RoutingRule>,
+    /// Settings version for future migrations
+    #[serde(default = "default_version")]
+    pub version: u32,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+impl Default for RoutingSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            rules: Vec::new(),
+            version: 1,
+        }
+    }
+}
+
+// ============================================================================
+// Engine
+// ============================================================================
+
+/// Rule that matched a segment
+#[derive(Debug, Clone)]
+pub struct RouteMatch {
+    pub rule_name: String,
+    /// Matched portion of the segment
+    pub matched: String,
+    pub targets: Vec<RoutingTarget>,
+}
+
+/// Routing rules with compiled regexes
+#[derive(Debug, Default)]
+pub struct RoutingEngine {
+    enabled: bool,
+    rules: Vec<(RoutingRule, Regex)>,
+}
+
+impl RoutingEngine {
+    /// Compile settings; fails on invalid regex or target configuration
+    pub fn compile(settings: &RoutingSettings) -> Result<Self> {
+        let mut rules = Vec::with_capacity(settings.rules.len());
+        for rule in &settings.rules {
+            for target in &rule.targets {
+                validate_target(target)
+                    .with_context(|| format!("Invalid target in rule '{}'", rule.name))?;
+            }
+            let regex = RegexBuilder::new(&rule.pattern)
+                .case_insensitive(rule.case_insensitive)
+                .build()
+                .with_context(|| format!("Invalid pattern in rule '{}'", rule.name))?;
+            rules.push((rule.clone(), regex));
+        }
+
+        Ok(Self {
+            enabled: settings.enabled,
+            rules,
+        })
+    }
+
+    /// Evaluate all enabled rules against a segment
+    pub fn route(&self, text: &str) -> Vec<RouteMatch> {
+        if !self.enabled {
+            return Vec::new();
+        }
+
+        self.rules
+            .iter()
+            .filter(|(rule, _)| rule.enabled && !rule.targets.is_empty())
+            .filter_map(|(rule, regex)| {
+                regex.find(text).map(|m| RouteMatch {
+                    rule_name: rule.name.clone(),
+                    matched: m.as_str().to_string(),
+                    targets: rule.targets.clone(),
+                })
+            })
+            .collect()
+    }
+}
+
+fn validate_target(target: &RoutingTarget) -> Result<()> {
+    match target {
+        RoutingTarget::Webhook { url } => {
+            if !(url.starts_with("http://") || url.starts_with("https://")) {
+                anyhow::bail!("Webhook URL must start with http:// or https://: {}", url);
+            }
+        }
+        RoutingTarget::Jsonl { file_name } => {
+            let plain = Path::new(file_name)
+                .file_name()
+                .map(|name| name == file_name.as_str())
+                .unwrap_or(false);
+            if !plain || !file_name.ends_with(".jsonl") {
+                anyhow::bail!(
+                    "JSONL target must be a plain *.jsonl file name: {}",
+                    file_name
+                );
+            }
+        }
+        RoutingTarget::Clipboard | RoutingTarget::Marker { .. } => {}
+    }
+    Ok(())
+}
+
+/// Record written by the JSONL target (also the webhook payload)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoutedSegment {
+    pub session_id: String,
+    pub rule: String,
+    pub matched: String,
+    pub text: String,
+    /// Session-relative timestamp (ms)
+    pub timestamp_ms: u64,
+}
+
+/// Append routed segment to `<session_dir>/<file_name>`
+pub fn append_jsonl(session_dir: &Path, file_name: &str, segment: &RoutedSegment) -> Result<()> {
+    let path = session_dir.join(file_name);
+    let mut line = serde_json::to_string(segment)?;
+    line.push('\n');
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open routing output: {:?}", path))?;
+    file.write_all(line.as_bytes())?;
+    Ok(())
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+pub const SETTINGS_STEM: &str = "routing_rules";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn rule(name: &str, pattern: &str, targets: Vec<RoutingTarget>) -> RoutingRule {
+        RoutingRule {
+            name: name.to_string(),
+            pattern: pattern.to_string(),
+            case_insensitive: true,
+            enabled: true,
+            targets,
+        }
+    }
+
+    #[test]
+    fn test_route_matches_enabled_rules() {
+        let mut disabled = rule("disabled", "todo", vec![RoutingTarget::Clipboard]);
+        disabled.enabled = false;
+        let settings = RoutingSettings {
+            rules: vec![
+                rule(
+                    "action-items",
+                    r"todo:\s*\w+",
+                    vec![RoutingTarget::Marker { label: None }],
+                ),
+                rule("decisions", "決定", vec![RoutingTarget::Clipboard]),
+                disabled,
+            ],
+            ..Default::default()
+        };
+        let engine = RoutingEngine::compile(&settings).unwrap();
+
+        let matches = engine.route("TODO: review the draft");
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].rule_name, "action-items");
+        assert_eq!(matches[0].matched, "TODO: review");
+
+        assert_eq!(engine.route("これで決定です")[0].rule_name, "decisions");
+        assert!(engine.route("nothing here").is_empty());
+    }
+
+    #[test]
+    fn test_compile_rejects_invalid_rules() {
+        let bad_regex = RoutingSettings {
+            rules: vec![rule("bad", "(unclosed", vec![RoutingTarget::Clipboard])],
+            ..Default::default()
+        };
+        assert!(RoutingEngine::compile(&bad_regex).is_err());
+
+        let bad_jsonl = RoutingSettings {
+            rules: vec![rule(
+                "escape",
+                "x",
+                vec![RoutingTarget::Jsonl {
+                    file_name: "../outside.jsonl".to_string(),
+                }],
+            )],
+            ..Default::default()
+        };
+        assert!(RoutingEngine::compile(&bad_jsonl).is_err());
+
+        let bad_url = RoutingSettings {
+            rules: vec![rule(
+                "hook",
+                "x",
+                vec![RoutingTarget::Webhook {
+                    url: "ftp://example.com".to_string(),
+                }],
+            )],
+            ..Default::default()
+        };
+        assert!(RoutingEngine::compile(&bad_url).is_err());
+    }
+
+    #[test]
+    fn test_append_jsonl() {
+        let temp_dir = TempDir::new().unwrap();
+        let segment = RoutedSegment {
+            session_id: "s1".to_string(),
+            rule: "r".to_string(),
+            matched: "budget".to_string(),
+            text: "budget talk".to_string(),
+            timestamp_ms: 42,
+        };
+        append_jsonl(temp_dir.path(), "budget.jsonl", &segment).unwrap();
+        append_jsonl(temp_dir.path(), "budget.jsonl", &segment).unwrap();
+
+        let content = std::fs::read_to_string(temp_dir.path().join("budget.jsonl")).unwrap();
+        assert_eq!(content.lines().count(), 2);
+    }
+}
@@ -0,0 +1,130 @@
+//! Transcript Search
+//!
+//! Finds final segments of a session containing a query, with the offsets
+//! the UI needs to jump playback to them. Matching is case-insensitive and
+//! ignores surrounding whitespace of the query.
+//!
+//! Final segments are stamped when the final text arrives, i.e. around the
+//! end of the utterance. A match therefore spans from the previous final
+//! segment (`start_ms`) to its own timestamp (`end_ms`).
+
+use serde::Serialize;
+
+use crate::storage::TranscriptionEvent;
+
+/// One matching final segment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SearchMatch {
+    /// Index among the session's final segments
+    pub segment_index: usize,
+    /// Session-relative start of the segment (ms)
+    pub start_ms: u64,
+    /// Session-relative timestamp of the segment (ms)
+    pub end_ms: u64,
+    pub text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speaker: Option<String>,
+    /// Matched ranges in `text` as [start, end) character offsets
+    pub ranges: Vec<(usize, usize)>,
+}
+
+/// Search the final segments of a transcript
+///
+/// Returns matches in transcript order; an empty query matches nothing.
+pub fn search_transcript(events: &[TranscriptionEvent], query: &str) -> Vec<SearchMatch> {
+    let needle = fold(query.trim());
+    if needle.is_empty() {
+        return Vec::new();
+    }
+
+    let mut matches = Vec::new();
+    let mut previous_ms = 0;
+    for (segment_index, event) in events.iter().filter(|e| e.is_final).enumerate() {
+        let ranges = find_ranges(&fold(&event.text), &needle);
+        if !ranges.is_empty() {
+            matches.push(SearchMatch {
+                segment_index,
+                start_ms: previous_ms.min(event.timestamp_ms),
+                end_ms: event.timestamp_ms,
+                text: event.text.clone(),
+                speaker: event.speaker.clone(),
+                ranges,
+            });
+        }
+        previous_ms = event.timestamp_ms;
+    }
+    matches
+}
+
+/// Lowercase per character, keeping one char per input char so offsets
+/// into the folded text are offsets into the original
+fn fold(text: &str) -> Vec<char> {
+    text.chars()
+        .map(|c| {
+            let mut lower = c.to_lowercase();
+            match (lower.next(), lower.next()) {
+                (Some(l), None) => l,
+                _ => c,
+            }
+        })
+        .collect()
+}
+
+/// Non-overlapping occurrences of `needle` in `haystack`
+fn find_ranges(haystack: &[char], needle: &[char]) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+    let mut i = 0;
+    while i + needle.len() <= haystack.len() {
+        if haystack[i..i + needle.len()] == *needle {
+            ranges.push((i, i + needle.len()));
+            i += needle.len();
+        } else {
+            i += 1;
+        }
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(timestamp_ms: u64, text: &str, is_final: bool) -> TranscriptionEvent {
+        TranscriptionEvent {
+            timestamp_ms,
+            text: text.to_string(),
+            is_final,
+            speaker: None,
+        }
+    }
+
+    #[test]
+    fn test_search_returns_offsets_and_ranges() {
+        let events = vec![
+            event(1_000, "予算の話", false),
+            event(4_000, "今期の予算について", true),
+            event(9_000, "Budget review", true),
+            event(15_000, "予算は予算として", true),
+        ];
+
+        let matches = search_transcript(&events, " 予算 ");
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].segment_index, 0);
+        assert_eq!((matches[0].start_ms, matches[0].end_ms), (0, 4_000));
+        assert_eq!(matches[0].ranges, vec![(3, 5)]);
+        assert_eq!((matches[1].start_ms, matches[1].end_ms), (9_000, 15_000));
+        assert_eq!(matches[1].ranges, vec![(0, 2), (3, 5)]);
+
+        let matches = search_transcript(&events, "BUDGET");
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].segment_index, 1);
+        assert_eq!(matches[0].ranges, vec![(0, 6)]);
+    }
+
+    #[test]
+    fn test_empty_query_matches_nothing() {
+        let events = vec![event(1_000, "hello", true)];
+        assert!(search_transcript(&events, "  ").is_empty());
+        assert!(search_transcript(&events, "world").is_empty());
+    }
+}
@@ -0,0 +1,220 @@
+//! Sentence Segmentation for Exports
+//!
+//! Final segments, Japanese ones especially, often arrive as long runs with
+//! little or no punctuation. Exports split each segment into sentences with
+//! rules for its script (detected per segment, since the transcript does not
+//! record the language):
+//!
+//! - CJK: break after `。`, `！`, `？` (keeping closing brackets and quotes
+//!   with the sentence); unpunctuated runs are wrapped at
+//!   [`CJK_MAX_SENTENCE_CHARS`], preferring the last `、` or space
+//! - Latin: break after `.`, `!`, `?` followed by whitespace, except after
+//!   common abbreviations and initials; runs are wrapped at
+//!   [`LATIN_MAX_SENTENCE_CHARS`] on a space
+//!
+//! Segmentation only splits: the sentences contain all of the segment's
+//! text, in order (whitespace at the breaks is trimmed).
+
+/// Wrap width for unpunctuated CJK text (characters)
+pub const CJK_MAX_SENTENCE_CHARS: usize = 60;
+
+/// Wrap width for unpunctuated Latin text (characters)
+pub const LATIN_MAX_SENTENCE_CHARS: usize = 200;
+
+/// Lowercased abbreviations that don't end a sentence (without the final dot)
+const ABBREVIATIONS: &[&str] = &[
+    "mr", "mrs", "ms", "dr", "prof", "sr", "jr", "st", "vs", "e.g", "i.e", "a.m", "p.m", "approx",
+    "no", "fig",
+];
+
+/// Writing system rules used for a segment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Script {
+    Cjk,
+    Latin,
+}
+
+impl Script {
+    /// CJK unless Latin letters outnumber CJK characters more than 2:1
+    /// (a CJK character carries about as much as a short word)
+    pub fn detect(text: &str) -> Self {
+        let (cjk, latin) = text.chars().fold((0usize, 0usize), |(cjk, latin), c| {
+            if is_cjk(c) {
+                (cjk + 1, latin)
+            } else if c.is_alphabetic() {
+                (cjk, latin + 1)
+            } else {
+                (cjk, latin)
+            }
+        });
+        if cjk > 0 && cjk * 2 >= latin {
+            Script::Cjk
+        } else {
+            Script::Latin
+        }
+    }
+
+    fn max_sentence_chars(self) -> usize {
+        match self {
+            Script::Cjk => CJK_MAX_SENTENCE_CHARS,
+            Script::Latin => LATIN_MAX_SENTENCE_CHARS,
+        }
+    }
+}
+
+/// Split `text` into sentences using the rules of its detected script
+pub fn split_sentences(text: &str) -> Vec<String> {
+    let script = Script::detect(text);
+    let max_chars = script.max_sentence_chars();
+    let chars: Vec<char> = text.trim().chars().collect();
+
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut i = 0;
+    while i < chars.len() {
+        let mut end = None;
+        if is_terminator(chars[i]) {
+            // Keep runs of terminators and closing brackets with the sentence
+            let mut j = i + 1;
+            while j < chars.len() && (is_terminator(chars[j]) || is_closer(chars[j])) {
+                j += 1;
+            }
+            if ends_sentence(script, &chars[start..i], chars[i], chars.get(j)) {
+                end = Some(j);
+            } else {
+                i = j - 1;
+            }
+        } else if i + 1 - start >= max_chars {
+            end = Some(wrap_point(script, &chars[start..=i]).map_or(i + 1, |p| start + p));
+        }
+
+        match end {
+            Some(end) => {
+                push_sentence(&mut sentences, &chars[start..end]);
+                start = end;
+                i = end;
+            }
+            None => i += 1,
+        }
+    }
+    push_sentence(&mut sentences, &chars[start..]);
+    sentences
+}
+
+/// Whether the terminator `c` after `before` ends a sentence, given the
+/// character following it (and any closers)
+fn ends_sentence(script: Script, before: &[char], c: char, next: Option<&char>) -> bool {
+    if matches!(c, '。' | '！' | '？') {
+        return true;
+    }
+    match script {
+        // Full-width text may still use ASCII `!`/`?`; `.` is mostly decimals
+        Script::Cjk => c != '.' && next.is_none_or(|n| n.is_whitespace() || is_cjk(*n)),
+        Script::Latin => {
+            next.is_none_or(|n| n.is_whitespace()) && !(c == '.' && is_abbreviation(before))
+        }
+    }
+}
+
+/// Whether the word before a `.` is an abbreviation or an initial ("J.")
+fn is_abbreviation(before: &[char]) -> bool {
+    let start = before
+        .iter()
+        .rposition(|c| c.is_whitespace())
+        .map_or(0, |p| p + 1);
+    let word: String = before[start..].iter().collect();
+    let word = word.trim_start_matches(['(', '"', '\'']);
+    let is_initial = word.chars().count() == 1 && word.chars().all(char::is_uppercase);
+    is_initial || ABBREVIATIONS.contains(&word.to_lowercase().as_str())
+}
+
+/// Break offset (exclusive) in an over-long run: after its last `、`, `，`
+/// or space in the second half; None to break at the end of the run
+fn wrap_point(script: Script, run: &[char]) -> Option<usize> {
+    let min = run.len() / 2;
+    run.iter()
+        .rposition(|&c| match script {
+            Script::Cjk => matches!(c, '、' | '，' | ',') || c.is_whitespace(),
+            Script::Latin => c.is_whitespace(),
+        })
+        .filter(|&p| p >= min)
+        .map(|p| p + 1)
+}
+
+fn push_sentence(sentences: &mut Vec<String>, chars: &[char]) {
+    let sentence: String = chars.iter().collect();
+    let sentence = sentence.trim();
+    if !sentence.is_empty() {
+        sentences.push(sentence.to_string());
+    }
+}
+
+fn is_terminator(c: char) -> bool {
+    matches!(c, '.' | '!' | '?' | '。' | '！' | '？')
+}
+
+fn is_closer(c: char) -> bool {
+    matches!(
+        c,
+        '」' | '』' | '）' | '】' | '〕' | '"' | '\'' | ')' | '”' | '’'
+    )
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30FF}' // Hiragana, Katakana
+        | '\u{3400}'..='\u{4DBF}' // CJK Extension A
+        | '\u{4E00}'..='\u{9FFF}' // CJK Unified Ideographs
+        | '\u{AC00}'..='\u{D7AF}' // Hangul
+        | '\u{FF66}'..='\u{FF9F}' // Half-width Katakana
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_japanese() {
+        assert_eq!(
+            split_sentences("予算を確認しました。来週までに「見積もり」を出します！よろしく"),
+            vec![
+                "予算を確認しました。",
+                "来週までに「見積もり」を出します！",
+                "よろしく"
+            ]
+        );
+        // Closing brackets stay with their sentence
+        assert_eq!(
+            split_sentences("「了解です。」次の議題へ"),
+            vec!["「了解です。」", "次の議題へ"]
+        );
+
+        // Unpunctuated runs wrap at the last comma past half the width
+        let run = format!("{}、{}", "あ".repeat(40), "い".repeat(40));
+        let sentences = split_sentences(&run);
+        assert_eq!(sentences[0], format!("{}、", "あ".repeat(40)));
+        assert_eq!(sentences.concat(), run);
+        assert!(sentences
+            .iter()
+            .all(|s| s.chars().count() <= CJK_MAX_SENTENCE_CHARS));
+        assert_eq!(Script::detect("Zoomで会議"), Script::Cjk);
+    }
+
+    #[test]
+    fn test_split_latin() {
+        assert_eq!(
+            split_sentences("We met Dr. Smith at 3.30 p.m. today. Is that right?! Yes."),
+            vec![
+                "We met Dr. Smith at 3.30 p.m. today.",
+                "Is that right?!",
+                "Yes."
+            ]
+        );
+        assert_eq!(
+            split_sentences("Ask J. Doe (e.g. via mail)."),
+            vec!["Ask J. Doe (e.g. via mail)."]
+        );
+        assert_eq!(split_sentences("  "), Vec::<String>::new());
+    }
+}
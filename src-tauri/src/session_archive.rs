@@ -0,0 +1,394 @@
+//! Session Archives
+//!
+//! Bundles a session (session.json, transcription.jsonl and its hourly
+//! parts, audio) into a single zip for sharing or backup, and imports such
+//! archives back into the recordings directory. Audio is stored as
+//! `audio.wav`, or as `audio.flac` (lossless, see `flac`) when compression
+//! is requested; the import restores `audio.wav` either way. Sessions recorded as FLAC or Opus
+//! are archived with their audio file as-is (Opus stays Opus on import).
+//!
+//! Files are archived as stored: an encrypted session (see `encryption`)
+//! stays encrypted and only imports where its key is available.
+//!
+//! Archives carry a `manifest.json` (format version, session ID, files).
+//! Only the known file names are extracted, so archive paths can never
+//! escape the session directory.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Archive layout version
+pub const ARCHIVE_FORMAT_VERSION: u32 = 1;
+
+pub const MANIFEST_FILE: &str = "manifest.json";
+const METADATA_FILE: &str = "session.json";
+const TRANSCRIPT_FILE: &str = "transcription.jsonl";
+const WAV_FILE: &str = "audio.wav";
+const FLAC_FILE: &str = "audio.flac";
+const OPUS_FILE: &str = "audio.opus";
+
+/// Archive options
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ArchiveOptions {
+    /// Store audio as FLAC instead of WAV
+    #[serde(default)]
+    pub flac_audio: bool,
+}
+
+/// `manifest.json` of an archive
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchiveManifest {
+    pub format_version: u32,
+    pub session_id: String,
+    /// Archived file names (besides the manifest)
+    pub files: Vec<String>,
+    pub app_version: String,
+}
+
+/// Written archive
+#[derive(Debug, Clone, Serialize)]
+pub struct WrittenArchive {
+    pub path: String,
+    pub session_id: String,
+    pub files: Vec<String>,
+    pub zip_bytes: u64,
+}
+
+/// Imported session
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportedArchive {
+    pub session_id: String,
+    pub files: Vec<String>,
+    /// Samples restored from FLAC (None when the archive held a WAV)
+    pub decoded_samples: Option<u64>,
+}
+
+/// Write `session_dir` as a zip archive at `output`
+///
+/// session.json is required; the transcript and audio are added when present.
+pub fn create_archive(
+    session_dir: &Path,
+    session_id: &str,
+    output: &Path,
+    options: &ArchiveOptions,
+) -> Result<WrittenArchive> {
+    if !session_dir.join(METADATA_FILE).is_file() {
+        anyhow::bail!("Session has no {}: {}", METADATA_FILE, session_id);
+    }
+    if let Some(parent) = output.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let mut files = vec![METADATA_FILE.to_string()];
+    if session_dir.join(TRANSCRIPT_FILE).is_file() {
+        files.push(TRANSCRIPT_FILE.to_string());
+    }
+    // Later hourly parts of long sessions (see transcript_chunks)
+    for (index, _) in crate::transcript_chunks::list_parts(session_dir)? {
+        if index > 1 {
+            files.push(crate::transcript_chunks::part_file_name(index));
+        }
+    }
+    if session_dir
+        .join(crate::transcript_chunks::MANIFEST_FILENAME)
+        .is_file()
+    {
+        files.push(crate::transcript_chunks::MANIFEST_FILENAME.to_string());
+    }
+    let wav_path = session_dir.join(WAV_FILE);
+    if wav_path.is_file() {
+        files.push(
+            if options.flac_audio {
+                FLAC_FILE
+            } else {
+                WAV_FILE
+            }
+            .to_string(),
+        );
+    } else if let Some(name) = [FLAC_FILE, OPUS_FILE]
+        .into_iter()
+        .find(|name| session_dir.join(name).is_file())
+    {
+        files.push(name.to_string());
+    }
+
+    let file =
+        std::fs::File::create(output).with_context(|| format!("Failed to create {:?}", output))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let deflated = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .large_file(true);
+    // FLAC does not deflate further
+    let stored = deflated.compression_method(zip::CompressionMethod::Stored);
+
+    let manifest = ArchiveManifest {
+        format_version: ARCHIVE_FORMAT_VERSION,
+        session_id: session_id.to_string(),
+        files: files.clone(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+    };
+    zip.start_file(MANIFEST_FILE, deflated)?;
+    zip.write_all(&serde_json::to_vec_pretty(&manifest)?)?;
+
+    for name in &files {
+        if name == FLAC_FILE && wav_path.is_file() {
+            zip.start_file(FLAC_FILE, stored)?;
+            crate::flac::encode_wav(&wav_path, &mut zip)?;
+            continue;
+        }
+        let path = session_dir.join(name);
+        let compression = if name == FLAC_FILE || name == OPUS_FILE {
+            stored
+        } else {
+            deflated
+        };
+        zip.start_file(name.as_str(), compression)?;
+        let mut source =
+            std::fs::File::open(&path).with_context(|| format!("Failed to open {:?}", path))?;
+        std::io::copy(&mut source, &mut zip)?;
+    }
+    zip.finish()?;
+
+    Ok(WrittenArchive {
+        path: output.display().to_string(),
+        session_id: session_id.to_string(),
+        files,
+        zip_bytes: std::fs::metadata(output)?.len(),
+    })
+}
+
+/// Read the manifest of an archive
+pub fn read_manifest(archive_path: &Path) -> Result<ArchiveManifest> {
+    let mut archive = open_archive(archive_path)?;
+    read_manifest_from(&mut archive)
+}
+
+/// Import an archive into `recordings_dir`
+///
+/// Fails if a session with the same ID already exists. The session is
+/// unpacked into a staging directory first, so a failed import leaves
+/// nothing behind. `encryption` opens an encrypted session.json.
+pub fn import_archive(
+    archive_path: &Path,
+    recordings_dir: &Path,
+    encryption: Option<&crate::encryption::SessionEncryption>,
+) -> Result<ImportedArchive> {
+    let mut archive = open_archive(archive_path)?;
+    let manifest = read_manifest_from(&mut archive)?;
+    let session_id = manifest.session_id.clone();
+    crate::session_id::validate_session_id(&session_id)?;
+    if !manifest.files.iter().any(|f| f == METADATA_FILE) {
+        anyhow::bail!("Archive has no {}", METADATA_FILE);
+    }
+
+    let destination = recordings_dir.join(&session_id);
+    if destination.exists() {
+        anyhow::bail!("Session already exists: {}", session_id);
+    }
+    let staging = recordings_dir.join(format!(".import-{}", session_id));
+    if staging.exists() {
+        std::fs::remove_dir_all(&staging)?;
+    }
+    std::fs::create_dir_all(&staging)?;
+
+    let result = unpack(&mut archive, &manifest, &staging, encryption).and_then(|imported| {
+        std::fs::rename(&staging, &destination)
+            .with_context(|| format!("Failed to move session into {:?}", destination))?;
+        Ok(imported)
+    });
+    if result.is_err() {
+        let _ = std::fs::remove_dir_all(&staging);
+    }
+    result
+}
+
+fn unpack(
+    archive: &mut zip::ZipArchive<std::fs::File>,
+    manifest: &ArchiveManifest,
+    staging: &Path,
+    encryption: Option<&crate::encryption::SessionEncryption>,
+) -> Result<ImportedArchive> {
+    let mut files = Vec::new();
+    let mut decoded_samples = None;
+
+    for name in &manifest.files {
+        let mut entry = archive
+            .by_name(name)
+            .with_context(|| format!("Archive is missing {}", name))?;
+        match name.as_str() {
+            // Hourly transcript parts have validated names (no path components)
+            file if matches!(file, METADATA_FILE | TRANSCRIPT_FILE | WAV_FILE | OPUS_FILE)
+                || crate::transcript_chunks::part_index(file).is_some()
+                || file == crate::transcript_chunks::MANIFEST_FILENAME =>
+            {
+                let mut out = std::fs::File::create(staging.join(name))?;
+                std::io::copy(&mut entry, &mut out)?;
+                files.push(name.clone());
+            }
+            FLAC_FILE => {
+                let mut writer = crate::storage::AudioWriter::new(staging.join(WAV_FILE))?;
+                let samples = crate::flac::decode(&mut entry, |chunk| writer.write_samples(chunk))?;
+                writer.close()?;
+                decoded_samples = Some(samples);
+                files.push(WAV_FILE.to_string());
+            }
+            other => anyhow::bail!("Unexpected file in archive: {}", other),
+        }
+    }
+
+    // The session ID in session.json must match the manifest
+    let metadata = crate::storage::read_metadata_file(&staging.join(METADATA_FILE), encryption)
+        .context("Invalid session.json in archive")?;
+    if metadata.session_id != manifest.session_id {
+        anyhow::bail!(
+            "Archive session mismatch: manifest {}, session.json {}",
+            manifest.session_id,
+            metadata.session_id
+        );
+    }
+
+    Ok(ImportedArchive {
+        session_id: manifest.session_id.clone(),
+        files,
+        decoded_samples,
+    })
+}
+
+fn open_archive(archive_path: &Path) -> Result<zip::ZipArchive<std::fs::File>> {
+    let file = std::fs::File::open(archive_path)
+        .with_context(|| format!("Failed to open {:?}", archive_path))?;
+    zip::ZipArchive::new(file).with_context(|| format!("Not a zip archive: {:?}", archive_path))
+}
+
+fn read_manifest_from(archive: &mut zip::ZipArchive<std::fs::File>) -> Result<ArchiveManifest> {
+    let mut json = String::new();
+    archive
+        .by_name(MANIFEST_FILE)
+        .context("Not a session archive (manifest.json missing)")?
+        .read_to_string(&mut json)?;
+    let manifest: ArchiveManifest =
+        serde_json::from_str(&json).context("Invalid archive manifest")?;
+    if manifest.format_version > ARCHIVE_FORMAT_VERSION {
+        anyhow::bail!(
+            "Archive format {} is newer than supported ({})",
+            manifest.format_version,
+            ARCHIVE_FORMAT_VERSION
+        );
+    }
+    Ok(manifest)
+}
+
+/// Default archive path: `archives/<session_id>.zip` in the app data directory
+pub fn default_archive_path(app_data_dir: &Path, session_id: &str) -> PathBuf {
+    app_data_dir
+        .join("archives")
+        .join(format!("{}.zip", session_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{LocalStorageService, SessionMetadata};
+    use tempfile::TempDir;
+
+    fn recorded_session(storage: &LocalStorageService, session_id: &str) -> Vec<i16> {
+        storage.create_session(session_id).unwrap();
+        let samples: Vec<i16> = (0..10_000)
+            .map(|i| ((i as f64 * 0.03).sin() * 3000.0) as i16)
+            .collect();
+        let mut audio = storage.create_audio_writer(session_id).unwrap();
+        audio.write_samples(&samples).unwrap();
+        audio.close().unwrap();
+        std::fs::write(
+            storage.get_session_dir(session_id).join(TRANSCRIPT_FILE),
+            "{\"timestamp_ms\":0,\"text\":\"hello\",\"is_final\":true}\n",
+        )
+        .unwrap();
+        storage
+            .save_session_metadata(&SessionMetadata {
+                session_id: session_id.to_string(),
+                start_time: "2025-01-01T00:00:00.000Z".to_string(),
+                end_time: "2025-01-01T00:00:01.000Z".to_string(),
+                duration_seconds: 1,
+                audio_device: "mic".to_string(),
+                model_size: "small".to_string(),
+                total_segments: 1,
+                total_characters: 5,
+                warnings: Vec::new(),
+                session_uuid: None,
+                title: None,
+                tags: Vec::new(),
+                stats: None,
+                levels: None,
+            })
+            .unwrap();
+        samples
+    }
+
+    #[test]
+    fn test_archive_and_import_with_flac_audio() {
+        let source = TempDir::new().unwrap();
+        let storage = LocalStorageService::new(source.path().to_path_buf());
+        let samples = recorded_session(&storage, "s1");
+        let wav = std::fs::read(storage.get_session_dir("s1").join(WAV_FILE)).unwrap();
+
+        let output = default_archive_path(source.path(), "s1");
+        let written = create_archive(
+            &storage.get_session_dir("s1"),
+            "s1",
+            &output,
+            &ArchiveOptions { flac_audio: true },
+        )
+        .unwrap();
+        assert_eq!(
+            written.files,
+            vec![METADATA_FILE, TRANSCRIPT_FILE, FLAC_FILE]
+        );
+        assert_eq!(read_manifest(&output).unwrap().session_id, "s1");
+
+        let target = TempDir::new().unwrap();
+        let imported = import_archive(&output, target.path(), None).unwrap();
+        assert_eq!(imported.session_id, "s1");
+        assert_eq!(imported.decoded_samples, Some(samples.len() as u64));
+        assert_eq!(
+            std::fs::read(target.path().join("s1").join(WAV_FILE)).unwrap(),
+            wav
+        );
+        assert!(target.path().join("s1").join(TRANSCRIPT_FILE).is_file());
+
+        // A second import conflicts and leaves no staging directory
+        assert!(import_archive(&output, target.path(), None).is_err());
+        assert!(!target.path().join(".import-s1").exists());
+    }
+
+    #[test]
+    fn test_import_rejects_foreign_zip_and_unsafe_ids() {
+        let dir = TempDir::new().unwrap();
+        let write_zip = |name: &str, manifest: Option<&str>| {
+            let path = dir.path().join(name);
+            let mut zip = zip::ZipWriter::new(std::fs::File::create(&path).unwrap());
+            let options = zip::write::SimpleFileOptions::default();
+            if let Some(manifest) = manifest {
+                zip.start_file(MANIFEST_FILE, options).unwrap();
+                zip.write_all(manifest.as_bytes()).unwrap();
+            }
+            zip.start_file("other.txt", options).unwrap();
+            zip.finish().unwrap();
+            path
+        };
+
+        let foreign = write_zip("foreign.zip", None);
+        assert!(import_archive(&foreign, dir.path(), None).is_err());
+
+        let traversal = write_zip(
+            "traversal.zip",
+            Some(
+                r#"{"format_version":1,"session_id":"../evil","files":["session.json"],"app_version":"0"}"#,
+            ),
+        );
+        assert!(import_archive(&traversal, dir.path(), None).is_err());
+        assert!(!dir.path().join("evil").exists());
+    }
+}
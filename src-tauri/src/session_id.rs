@@ -0,0 +1,196 @@
+//! Session ID Format
+//!
+//! Session IDs name the session directory under `recordings/`, so a readable,
+//! sortable format is preferred over a bare UUID. The format is a template
+//! rendered at session start:
+//!
+//! - `{uuid}`: full UUID v4
+//! - `{short_uuid}`: first 8 hex digits of the UUID
+//! - `strftime` specifiers (e.g. `%Y-%m-%dT%H-%M`) in local time
+//!
+//! The full UUID is always stored in session metadata as the stable reference.
+//!
+//! Persisted to `settings/session_id.json` in app data directory.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use uuid::Uuid;
+
+/// Default format: `2025-06-05T14-30_1a2b3c4d`
+pub const DEFAULT_TEMPLATE: &str = "%Y-%m-%dT%H-%M_{short_uuid}";
+
+/// Maximum rendered length (keeps paths well below OS limits)
+pub const MAX_SESSION_ID_LEN: usize = 100;
+
+// ============================================================================
+// Settings Struct
+// ============================================================================
+
+/// Session ID format configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionIdSettings {
+    #[serde(default = "default_template")]
+    pub template: String,
+    /// Settings version for future migrations
+    #[serde(default = "default_version")]
+    pub version: u32,
+}
+
+fn default_template() -> String {
+    DEFAULT_TEMPLATE.to_string()
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+impl Default for SessionIdSettings {
+    fn default() -> Self {
+        Self {
+            template: default_template(),
+            version: 1,
+        }
+    }
+}
+
+// ============================================================================
+// Generation
+// ============================================================================
+
+/// Generated session identity
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionIdentity {
+    /// Rendered session ID (directory name)
+    pub session_id: String,
+    /// Full UUID v4 (stable reference stored in metadata)
+    pub uuid: String,
+}
+
+/// Check that a session ID is safe to use as a single directory name
+///
+/// Allowed: ASCII letters, digits, `-`, `_`, `.` (not leading). Rejects path
+/// separators, Windows-reserved characters and `.`/`..`.
+pub fn validate_session_id(session_id: &str) -> Result<()> {
+    if session_id.is_empty() {
+        anyhow::bail!("Session ID is empty");
+    }
+    if session_id.len() > MAX_SESSION_ID_LEN {
+        anyhow::bail!(
+            "Session ID is longer than {} characters: {}",
+            MAX_SESSION_ID_LEN,
+            session_id
+        );
+    }
+    if session_id.starts_with('.') {
+        anyhow::bail!("Session ID must not start with '.': {}", session_id);
+    }
+    if let Some(c) = session_id
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+    {
+        anyhow::bail!(
+            "Session ID contains path-unsafe character {:?}: {}",
+            c,
+            session_id
+        );
+    }
+    Ok(())
+}
+
+/// Render a template for the given time and UUID
+pub fn render<Tz>(template: &str, now: &DateTime<Tz>, uuid: &Uuid) -> Result<String>
+where
+    Tz: TimeZone,
+    Tz::Offset: std::fmt::Display,
+{
+    let uuid_str = uuid.to_string();
+    let expanded = template
+        .replace("{uuid}", &uuid_str)
+        .replace("{short_uuid}", &uuid_str[..8]);
+
+    // Invalid strftime specifiers surface as fmt::Error instead of panicking
+    let mut session_id = String::new();
+    write!(session_id, "{}", now.format(&expanded))
+        .map_err(|_| anyhow::anyhow!("Invalid date format in template: {}", template))?;
+
+    validate_session_id(&session_id)
+        .with_context(|| format!("Invalid session ID template: {}", template))?;
+    Ok(session_id)
+}
+
+/// Validate a template by rendering it with a sample time and UUID
+///
+/// Templates without `{uuid}`/`{short_uuid}` are rejected: two sessions
+/// started in the same minute would share a directory.
+pub fn validate_template(template: &str) -> Result<()> {
+    if !template.contains("{uuid}") && !template.contains("{short_uuid}") {
+        anyhow::bail!(
+            "Session ID template must contain {{uuid}} or {{short_uuid}}: {}",
+            template
+        );
+    }
+    render(template, &Local::now(), &Uuid::new_v4()).map(|_| ())
+}
+
+/// Generate a session identity from settings (local time)
+pub fn generate(settings: &SessionIdSettings) -> Result<SessionIdentity> {
+    let uuid = Uuid::new_v4();
+    let session_id = render(&settings.template, &Local::now(), &uuid)?;
+    Ok(SessionIdentity {
+        session_id,
+        uuid: uuid.to_string(),
+    })
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+pub const SETTINGS_STEM: &str = "session_id";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn sample_uuid() -> Uuid {
+        Uuid::parse_str("1a2b3c4d-0000-4000-8000-000000000000").unwrap()
+    }
+
+    #[test]
+    fn test_render_default_template() {
+        let now = Utc.with_ymd_and_hms(2025, 6, 5, 14, 30, 12).unwrap();
+        let session_id = render(DEFAULT_TEMPLATE, &now, &sample_uuid()).unwrap();
+        assert_eq!(session_id, "2025-06-05T14-30_1a2b3c4d");
+
+        let legacy = render("{uuid}", &now, &sample_uuid()).unwrap();
+        assert_eq!(legacy, "1a2b3c4d-0000-4000-8000-000000000000");
+    }
+
+    #[test]
+    fn test_rejects_path_unsafe_ids() {
+        let now = Utc.with_ymd_and_hms(2025, 6, 5, 14, 30, 0).unwrap();
+        // ':' is invalid on Windows, '/' would create nested directories
+        assert!(render("%H:%M_{short_uuid}", &now, &sample_uuid()).is_err());
+        assert!(render("%Y/%m_{short_uuid}", &now, &sample_uuid()).is_err());
+        assert!(render("..{short_uuid}", &now, &sample_uuid()).is_err());
+        assert!(render("meeting {short_uuid}", &now, &sample_uuid()).is_err());
+        // Invalid strftime specifier
+        assert!(render("%Q_{short_uuid}", &now, &sample_uuid()).is_err());
+    }
+
+    #[test]
+    fn test_validate_template_requires_uuid() {
+        assert!(validate_template(DEFAULT_TEMPLATE).is_ok());
+        assert!(validate_template("%Y-%m-%dT%H-%M").is_err());
+    }
+
+    #[test]
+    fn test_generate_keeps_uuid() {
+        let identity = generate(&SessionIdSettings::default()).unwrap();
+        assert!(Uuid::parse_str(&identity.uuid).is_ok());
+        assert!(identity.session_id.ends_with(&identity.uuid[..8]));
+    }
+}
@@ -0,0 +1,287 @@
+//! Recording Session Registry
+//!
+//! Recording sessions (see `recording_session`) keyed by session ID, so the
+//! core can run more than one recording at once (e.g. two meeting rooms
+//! from one machine). The UI records one session at a time: it works
+//! with the primary session, which is the most recently opened one still
+//! registered.
+//!
+//! A session is opened when recording starts and closed once it has been
+//! finalized; closing cancels its tasks. All sessions share the one sidecar:
+//! its events are routed back to their session by the session part of the
+//! request ID they answer (see `event_session`).
+
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+
+use crate::recording_session::RecordingSession;
+use crate::request_id::RequestId;
+
+/// Sessions recorded at the same time (bounded by CPU and the sidecar)
+pub const MAX_CONCURRENT_SESSIONS: usize = 4;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum SessionRegistryError {
+    #[error("Session already registered: {0}")]
+    AlreadyRegistered(String),
+
+    #[error("Too many concurrent sessions (max {0})")]
+    TooManySessions(usize),
+}
+
+#[derive(Default)]
+struct RegistryInner {
+    sessions: BTreeMap<String, Arc<RecordingSession>>,
+    /// Session IDs in the order they were opened
+    opened: Vec<String>,
+}
+
+/// Recording sessions keyed by session ID
+#[derive(Default)]
+pub struct SessionRegistry {
+    inner: Mutex<RegistryInner>,
+}
+
+impl SessionRegistry {
+    /// Register a new session; it becomes the primary session
+    pub fn open(
+        &self,
+        session_id: &str,
+        session_uuid: &str,
+        started_at_ms: u64,
+    ) -> Result<Arc<RecordingSession>, SessionRegistryError> {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.sessions.contains_key(session_id) {
+            return Err(SessionRegistryError::AlreadyRegistered(
+                session_id.to_string(),
+            ));
+        }
+        if inner.sessions.len() >= MAX_CONCURRENT_SESSIONS {
+            return Err(SessionRegistryError::TooManySessions(
+                MAX_CONCURRENT_SESSIONS,
+            ));
+        }
+        let session = Arc::new(RecordingSession::new(
+            session_id,
+            session_uuid,
+            started_at_ms,
+        ));
+        inner
+            .sessions
+            .insert(session_id.to_string(), Arc::clone(&session));
+        inner.opened.push(session_id.to_string());
+        Ok(session)
+    }
+
+    pub fn get(&self, session_id: &str) -> Option<Arc<RecordingSession>> {
+        self.inner.lock().unwrap().sessions.get(session_id).cloned()
+    }
+
+    /// Most recently opened session still registered
+    pub fn primary(&self) -> Option<Arc<RecordingSession>> {
+        let inner = self.inner.lock().unwrap();
+        inner
+            .opened
+            .last()
+            .and_then(|id| inner.sessions.get(id))
+            .cloned()
+    }
+
+    /// Unregister a session and cancel its tasks
+    pub fn close(&self, session_id: &str) -> Option<Arc<RecordingSession>> {
+        let mut inner = self.inner.lock().unwrap();
+        inner.opened.retain(|id| id != session_id);
+        let session = inner.sessions.remove(session_id)?;
+        session.cancel();
+        Some(session)
+    }
+
+    /// Registered sessions in the order they were opened
+    pub fn sessions(&self) -> Vec<Arc<RecordingSession>> {
+        let inner = self.inner.lock().unwrap();
+        inner
+            .opened
+            .iter()
+            .filter_map(|id| inner.sessions.get(id))
+            .cloned()
+            .collect()
+    }
+
+    /// Session a sidecar event belongs to
+    ///
+    /// Events answering a request carry its ID (`requestId`), whose session
+    /// part picks the session. Events without one (or with a malformed one,
+    /// which the session's validation then reports) go to the primary
+    /// session. None if the request's session is no longer registered.
+    pub fn event_session(&self, data: &Value) -> Option<Arc<RecordingSession>> {
+        let request_id = data.get("requestId").and_then(|v| v.as_str());
+        match request_id.map(RequestId::parse) {
+            Some(Ok(id)) => self.get(&id.session),
+            _ => self.primary(),
+        }
+    }
+
+    /// Registered session IDs in the order they were opened
+    pub fn session_ids(&self) -> Vec<String> {
+        self.inner.lock().unwrap().opened.clone()
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap().sessions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording_session::SessionIpcEvent;
+    use crate::storage::{AudioFormat, LocalStorageService};
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_sessions_are_independent() {
+        let registry = SessionRegistry::default();
+        let room_a = registry.open("room-a", "uuid-a", 1_000).unwrap();
+        let room_b = registry.open("room-b", "uuid-b", 2_000).unwrap();
+
+        assert_eq!(room_a.record_ipc_event(), 1);
+        assert_eq!(room_a.record_ipc_event(), 2);
+        assert_eq!(room_b.last_ipc_event_seq(), 0);
+        assert_eq!(
+            room_b.request_ids().next_id("audio").to_string(),
+            "room-b-audio-1"
+        );
+        assert!(room_a.request_ids().validate("room-b-audio-1").is_err());
+
+        // Closing one session cancels only its own tasks
+        registry.close("room-a");
+        assert!(room_a.cancel_token().is_cancelled());
+        assert!(!room_b.cancel_token().is_cancelled());
+        assert!(registry.get("room-a").is_none());
+        assert_eq!(registry.session_ids(), vec!["room-b"]);
+    }
+
+    #[test]
+    fn test_primary_is_latest_open_session() {
+        let registry = SessionRegistry::default();
+        assert!(registry.primary().is_none());
+
+        registry.open("a", "1", 0).unwrap();
+        registry.open("b", "2", 0).unwrap();
+        assert_eq!(registry.primary().unwrap().session_id(), "b");
+        assert_eq!(
+            registry.open("a", "3", 0).err(),
+            Some(SessionRegistryError::AlreadyRegistered("a".to_string()))
+        );
+
+        registry.close("b");
+        assert_eq!(registry.primary().unwrap().session_id(), "a");
+        registry.close("a");
+        assert!(registry.is_empty());
+
+        for i in 0..MAX_CONCURRENT_SESSIONS {
+            registry.open(&i.to_string(), "u", 0).unwrap();
+        }
+        assert_eq!(
+            registry.open("extra", "u", 0).err(),
+            Some(SessionRegistryError::TooManySessions(
+                MAX_CONCURRENT_SESSIONS
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_two_sessions_record_at_once() {
+        let dir = TempDir::new().unwrap();
+        let storage = Arc::new(LocalStorageService::new(dir.path().to_path_buf()));
+        let registry = Arc::new(SessionRegistry::default());
+
+        let room_a = registry.open("room-a", "uuid-a", 0).unwrap();
+        let room_b = registry.open("room-b", "uuid-b", 0).unwrap();
+        let mut events_a = room_a.open_ipc_channel();
+        let mut events_b = room_b.open_ipc_channel();
+        for session in [&room_a, &room_b] {
+            session.start(Some(&storage), AudioFormat::Wav).unwrap();
+        }
+
+        // Both capture at once, each into its own session
+        let pcm: Vec<u8> = (0..3_200u32)
+            .flat_map(|i| (i as i16).to_le_bytes())
+            .collect();
+        let writers: Vec<_> = [(Arc::clone(&room_a), 3), (Arc::clone(&room_b), 5)]
+            .into_iter()
+            .map(|(session, batches)| {
+                let pcm = pcm.clone();
+                tokio::spawn(async move {
+                    for _ in 0..batches {
+                        let id = session.request_ids().next_id("audio");
+                        session.queue_metrics().record_push(pcm.len(), 0, pcm.len());
+                        session
+                            .ipc_flow()
+                            .lock()
+                            .unwrap()
+                            .on_sent(&id.to_string(), 100);
+                        assert!(session.append_audio(&pcm).unwrap());
+                        tokio::task::yield_now().await;
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.await.unwrap();
+        }
+        assert_eq!(room_a.audio_samples(), 3 * 3_200);
+        assert_eq!(room_b.audio_samples(), 5 * 3_200);
+        assert_eq!(room_a.queue_metrics().snapshot().enqueued_bytes, 3 * 6_400);
+        assert_eq!(room_b.queue_metrics().snapshot().enqueued_bytes, 5 * 6_400);
+        let in_flight =
+            |s: &RecordingSession| s.ipc_flow().lock().unwrap().snapshot().in_flight_requests;
+        assert_eq!((in_flight(&room_a), in_flight(&room_b)), (3, 5));
+
+        // Sidecar events reach the session whose request they answer
+        let answer = |request_id: &str| json!({ "requestId": request_id, "text": "x" });
+        for data in [answer("room-a-audio-2"), answer("room-b-audio-4")] {
+            let session = registry.event_session(&data).unwrap();
+            assert!(session.deliver_ipc_event("transcription", data));
+        }
+        let to_primary = registry.event_session(&json!({ "status": "ok" })).unwrap();
+        assert_eq!(to_primary.session_id(), "room-b");
+        assert_eq!(
+            events_a.recv().await.unwrap(),
+            SessionIpcEvent {
+                event_type: "transcription".to_string(),
+                data: answer("room-a-audio-2"),
+            }
+        );
+        assert_eq!(
+            events_b.recv().await.unwrap().data,
+            answer("room-b-audio-4")
+        );
+        assert!(events_a.try_recv().is_err());
+
+        // Stopping one leaves the other recording
+        room_a.stop(Some(&storage), "mic-a", Vec::new()).unwrap();
+        registry.close("room-a");
+        assert!(events_a.recv().await.is_none());
+        assert!(registry.event_session(&answer("room-a-audio-3")).is_none());
+        assert!(!room_a.deliver_ipc_event("transcription", json!({})));
+        assert!(room_b.append_audio(&pcm).unwrap());
+        assert!(room_b.deliver_ipc_event("transcription", json!({})));
+        assert_eq!(registry.session_ids(), vec!["room-b"]);
+
+        room_b.stop(Some(&storage), "mic-b", Vec::new()).unwrap();
+        registry.close("room-b");
+        let saved_a = storage.load_session("room-a").unwrap().metadata;
+        let saved_b = storage.load_session("room-b").unwrap().metadata;
+        assert_eq!(saved_a.audio_device, "mic-a");
+        assert_eq!(saved_b.audio_device, "mic-b");
+        assert!(registry.is_empty());
+    }
+}
@@ -0,0 +1,191 @@
+//! Session Statistics
+//!
+//! Per-session figures for the summary panel, persisted in session.json:
+//! - total words and words per minute of the final segments (tokens as in
+//!   `transcript_diff::tokenize`: ASCII words, one per CJK character)
+//! - share of silence, from the per-second voice activity (`activity.bin`)
+//! - final segments per speaker label
+//! - first partial -> final latency distribution
+//!
+//! Partials are not persisted, so the latency distribution is only known
+//! while the session is recorded: it is captured when recording stops and
+//! kept when the statistics are recomputed later.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::activity::ActivityPoint;
+use crate::latency::StageLatency;
+use crate::storage::{LocalStorageService, SessionMetadata, TranscriptionEvent};
+
+/// Statistics of one session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionStatistics {
+    pub total_words: u64,
+    /// Words per minute of recording (0.0 for sessions under a second)
+    pub words_per_minute: f64,
+    /// Share of silent audio (%); None when no activity is available
+    #[serde(default)]
+    pub silence_percent: Option<f64>,
+    /// Final segments per speaker label
+    #[serde(default)]
+    pub segments_per_speaker: BTreeMap<String, u64>,
+    /// Final segments without a speaker label
+    #[serde(default)]
+    pub unlabeled_segments: u64,
+    /// First partial -> final latency; None when not recorded
+    #[serde(default)]
+    pub partial_to_final: Option<StageLatency>,
+}
+
+/// Compute the statistics of a session
+///
+/// `activity` holds one point per second of audio.
+pub fn compute(
+    metadata: &SessionMetadata,
+    events: &[TranscriptionEvent],
+    activity: Option<&[ActivityPoint]>,
+    partial_to_final: Option<StageLatency>,
+) -> SessionStatistics {
+    let mut total_words = 0u64;
+    let mut segments_per_speaker: BTreeMap<String, u64> = BTreeMap::new();
+    let mut unlabeled_segments = 0u64;
+    for event in events
+        .iter()
+        .filter(|e| e.is_final && !e.text.trim().is_empty())
+    {
+        total_words += crate::transcript_diff::tokenize(&event.text).len() as u64;
+        match event
+            .speaker
+            .as_deref()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+        {
+            Some(speaker) => *segments_per_speaker.entry(speaker.to_string()).or_default() += 1,
+            None => unlabeled_segments += 1,
+        }
+    }
+
+    let minutes = metadata.duration_seconds as f64 / 60.0;
+    let words_per_minute = if metadata.duration_seconds > 0 {
+        round1(total_words as f64 / minutes)
+    } else {
+        0.0
+    };
+    let silence_percent = activity.filter(|points| !points.is_empty()).map(|points| {
+        let voiced: u64 = points.iter().map(|p| p.voiced as u64).sum();
+        round1(100.0 - voiced as f64 / points.len() as f64)
+    });
+
+    SessionStatistics {
+        total_words,
+        words_per_minute,
+        silence_percent,
+        segments_per_speaker,
+        unlabeled_segments,
+        partial_to_final,
+    }
+}
+
+/// Compute a finalized session's statistics and store them in session.json
+///
+/// Without `partial_to_final`, the previously stored latency is kept.
+pub fn update_session(
+    storage: &LocalStorageService,
+    session_id: &str,
+    partial_to_final: Option<StageLatency>,
+) -> Result<SessionStatistics> {
+    let loaded = storage.load_session(session_id)?;
+    // Sessions without audio (or activity) have no silence figure
+    let activity = crate::activity::load_activity(&storage.get_session_dir(session_id)).ok();
+    let partial_to_final = partial_to_final.or_else(|| {
+        loaded
+            .metadata
+            .stats
+            .as_ref()
+            .and_then(|stats| stats.partial_to_final.clone())
+    });
+
+    let stats = compute(
+        &loaded.metadata,
+        &loaded.transcripts,
+        activity.as_deref(),
+        partial_to_final,
+    );
+    storage.set_session_stats(session_id, stats.clone())?;
+    Ok(stats)
+}
+
+fn round1(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(text: &str, is_final: bool, speaker: Option<&str>) -> TranscriptionEvent {
+        TranscriptionEvent {
+            timestamp_ms: 0,
+            text: text.to_string(),
+            is_final,
+            speaker: speaker.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_compute_statistics() {
+        let metadata = SessionMetadata {
+            session_id: "2025-10-13T10-00_abcd1234".to_string(),
+            start_time: "2025-10-13T10:00:00Z".to_string(),
+            end_time: "2025-10-13T10:02:00Z".to_string(),
+            duration_seconds: 120,
+            audio_device: "default".to_string(),
+            model_size: "small".to_string(),
+            total_segments: 3,
+            total_characters: 20,
+            warnings: Vec::new(),
+            session_uuid: None,
+            title: None,
+            tags: Vec::new(),
+            stats: None,
+            levels: None,
+        };
+        let events = [
+            event("Let's review the budget.", true, Some("A")),
+            event("ignored partial text", false, None),
+            event("予算を確認", true, Some(" A ")),
+            event("OK", true, None),
+            event("  ", true, Some("B")),
+        ];
+        let activity = [
+            ActivityPoint {
+                level: 0,
+                voiced: 0,
+            },
+            ActivityPoint {
+                level: 200,
+                voiced: 100,
+            },
+            ActivityPoint {
+                level: 150,
+                voiced: 50,
+            },
+        ];
+
+        let stats = compute(&metadata, &events, Some(&activity), None);
+        // 4 words + 5 CJK characters + 1 word
+        assert_eq!(stats.total_words, 10);
+        assert_eq!(stats.words_per_minute, 5.0);
+        assert_eq!(stats.silence_percent, Some(50.0));
+        assert_eq!(
+            stats.segments_per_speaker,
+            BTreeMap::from([("A".to_string(), 2)])
+        );
+        assert_eq!(stats.unlabeled_segments, 1);
+
+        let stats = compute(&metadata, &[], Some(&[]), None);
+        assert_eq!((stats.total_words, stats.silence_percent), (0, None));
+    }
+}
@@ -0,0 +1,91 @@
+//! Settings Files
+//!
+//! Every setting is one JSON file, `settings/<stem>.json`, in the app data
+//! directory (machine-wide settings) or in the data directory of the
+//! active workspace. Settings modules keep only their type (with `Default`)
+//! and its validation; reading and writing the file happens here.
+
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+pub const SETTINGS_SUBDIR: &str = "settings";
+
+/// Path of the settings file `stem` under `dir`
+pub fn path(dir: &Path, stem: &str) -> PathBuf {
+    dir.join(SETTINGS_SUBDIR).join(format!("{}.json", stem))
+}
+
+/// Save settings as pretty-printed JSON
+///
+/// Creates the settings directory if it doesn't exist.
+pub fn save<T: Serialize>(dir: &Path, stem: &str, settings: &T) -> Result<()> {
+    let settings_dir = dir.join(SETTINGS_SUBDIR);
+
+    if !settings_dir.exists() {
+        std::fs::create_dir_all(&settings_dir)
+            .with_context(|| format!("Failed to create settings directory: {:?}", settings_dir))?;
+    }
+
+    let settings_path = path(dir, stem);
+    let json = serde_json::to_string_pretty(settings)
+        .with_context(|| format!("Failed to serialize {} settings", stem))?;
+
+    std::fs::write(&settings_path, json)
+        .with_context(|| format!("Failed to write settings file: {:?}", settings_path))?;
+
+    Ok(())
+}
+
+/// Load settings
+///
+/// Returns default settings if the file doesn't exist.
+pub fn load<T: DeserializeOwned + Default>(dir: &Path, stem: &str) -> Result<T> {
+    let settings_path = path(dir, stem);
+
+    if !settings_path.exists() {
+        return Ok(T::default());
+    }
+
+    let json = std::fs::read_to_string(&settings_path)
+        .with_context(|| format!("Failed to read settings file: {:?}", settings_path))?;
+
+    serde_json::from_str(&json).with_context(|| format!("Failed to parse {} settings", stem))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use tempfile::TempDir;
+
+    #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+    struct Sample {
+        #[serde(default)]
+        name: String,
+        #[serde(default)]
+        count: u32,
+    }
+
+    #[test]
+    fn test_roundtrip_and_defaults() {
+        let dir = TempDir::new().unwrap();
+        assert_eq!(
+            load::<Sample>(dir.path(), "sample").unwrap(),
+            Sample::default()
+        );
+
+        let sample = Sample {
+            name: "a".to_string(),
+            count: 3,
+        };
+        save(dir.path(), "sample", &sample).unwrap();
+        assert!(dir.path().join("settings/sample.json").is_file());
+        assert_eq!(load::<Sample>(dir.path(), "sample").unwrap(), sample);
+
+        std::fs::write(path(dir.path(), "sample"), "{").unwrap();
+        let err = load::<Sample>(dir.path(), "sample").unwrap_err();
+        assert!(err.to_string().contains("sample settings"));
+    }
+}
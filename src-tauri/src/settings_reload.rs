@@ -0,0 +1,202 @@
+//! Settings Reload Without Restart
+//!
+//! Settings files can be edited by hand (or deployed by a config tool) while
+//! the app runs. `reload_settings` re-reads the `settings/` directories of
+//! the app data directory and the active workspace, compares them with what
+//! was read at startup (or the last reload) and applies the changed files:
+//!
+//! - Most settings take effect at once, e.g. the log level (`debug.json`),
+//!   redaction and other pipeline rules (`pipeline.json`), WebSocket
+//!   remote control auth (`websocket_control.json`), partial coalescing
+//!   (`partial_granularity.json`), webhooks and the HTTP API. Input gains
+//!   (`multi_input.json`) are read at every recording start anyway.
+//! - Files in `RESTART_REQUIRED` (and unknown files) are only reported:
+//!   they are read once at startup, such as the WebSocket port, the
+//!   storage root, the sidecar's transcription workers or cloud STT.
+//!
+//! A changed file that fails to load or validate keeps the running values
+//! and is reported as failed; it is compared again on the next reload.
+
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+
+/// Settings read only at startup (file stems)
+pub const RESTART_REQUIRED: &[&str] = &[
+    "cloud_stt",
+    "encryption",
+    "storage_root",
+    "transcription_workers",
+    "viewer_mode",
+    "websocket_port",
+    "workspaces",
+];
+
+/// Settings applied by `reload_settings` (file stems)
+pub const LIVE: &[&str] = &[
+    "audio_format",
+    "channel_selection",
+    "consent_announcement",
+    "debug",
+    "device_aliases",
+    "docs_budget",
+    "furigana",
+    "health",
+    "http_api",
+    "ipc_quarantine",
+    "jobs",
+    "keyword_alerts",
+    "memory_sentinel",
+    "mqtt",
+    "multi_input",
+    "ops_webhooks",
+    "partial_granularity",
+    "pipeline",
+    "rate_limits",
+    "reconnect_policy",
+    "retention",
+    "routing_rules",
+    "session_id",
+    "summary",
+    "transcript_webhooks",
+    "trash",
+    "wasapi_capture",
+    "websocket",
+    "websocket_audio",
+    "websocket_control",
+    "websocket_mdns",
+];
+
+/// Whether a changed settings file can be applied without a restart
+pub fn is_live(name: &str) -> bool {
+    LIVE.contains(&name)
+}
+
+/// Contents of the settings files, by file stem
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SettingsSnapshot {
+    files: BTreeMap<String, String>,
+}
+
+impl SettingsSnapshot {
+    /// Read the `*.json` files of each directory's `settings/` subdirectory
+    ///
+    /// Missing directories and unreadable files are left out (a file that
+    /// disappears counts as changed: its settings fall back to defaults).
+    pub fn capture(app_data_dirs: &[PathBuf]) -> Self {
+        let mut files = BTreeMap::new();
+        for dir in app_data_dirs {
+            let Ok(entries) = std::fs::read_dir(dir.join("settings")) else {
+                continue;
+            };
+            for entry in entries.filter_map(|entry| entry.ok()) {
+                let path = entry.path();
+                if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                    continue;
+                }
+                let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                    continue;
+                };
+                if let Ok(contents) = std::fs::read_to_string(&path) {
+                    files.insert(name.to_string(), contents);
+                }
+            }
+        }
+        Self { files }
+    }
+
+    /// Names of the files added, removed or modified in `newer`
+    pub fn changed(&self, newer: &SettingsSnapshot) -> Vec<String> {
+        let names: BTreeSet<&String> = self.files.keys().chain(newer.files.keys()).collect();
+        names
+            .into_iter()
+            .filter(|name| self.files.get(*name) != newer.files.get(*name))
+            .cloned()
+            .collect()
+    }
+
+    /// Keep the previous contents of `name` (it failed to apply)
+    pub fn restore(&mut self, previous: &SettingsSnapshot, name: &str) {
+        match previous.files.get(name) {
+            Some(contents) => self.files.insert(name.to_string(), contents.clone()),
+            None => self.files.remove(name),
+        };
+    }
+}
+
+/// A changed file that couldn't be applied
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReloadFailure {
+    pub name: String,
+    pub error: String,
+}
+
+/// Result of `reload_settings`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SettingsReloadReport {
+    /// Changed and in effect now
+    pub applied: Vec<String>,
+    /// Changed, but read only at startup
+    pub restart_required: Vec<String>,
+    pub failed: Vec<ReloadFailure>,
+}
+
+impl SettingsReloadReport {
+    pub fn is_empty(&self) -> bool {
+        self.applied.is_empty() && self.restart_required.is_empty() && self.failed.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_snapshot_detects_changed_files() {
+        let machine = TempDir::new().unwrap();
+        let workspace = TempDir::new().unwrap();
+        let write = |dir: &TempDir, name: &str, contents: &str| {
+            std::fs::create_dir_all(dir.path().join("settings")).unwrap();
+            std::fs::write(dir.path().join("settings").join(name), contents).unwrap();
+        };
+        write(&machine, "websocket_control.json", r#"{"enabled":false}"#);
+        write(&machine, "websocket_port.json", r#"{"port":9001}"#);
+        write(&workspace, "debug.json", r#"{"log_level":"debug"}"#);
+        write(&workspace, "notes.txt", "ignored");
+        let dirs = [machine.path().to_path_buf(), workspace.path().to_path_buf()];
+
+        let before = SettingsSnapshot::capture(&dirs);
+        assert!(before.changed(&SettingsSnapshot::capture(&dirs)).is_empty());
+
+        write(&machine, "websocket_control.json", r#"{"enabled":true}"#);
+        write(&machine, "websocket_port.json", r#"{"port":9002}"#);
+        std::fs::remove_file(workspace.path().join("settings/debug.json")).unwrap();
+        write(&workspace, "pipeline.json", "{}");
+        let mut after = SettingsSnapshot::capture(&dirs);
+        assert_eq!(
+            before.changed(&after),
+            ["debug", "pipeline", "websocket_control", "websocket_port"]
+        );
+
+        // A file that failed to apply is compared again next time
+        after.restore(&before, "websocket_control");
+        after.restore(&before, "pipeline");
+        assert_eq!(
+            after.changed(&SettingsSnapshot::capture(&dirs)),
+            ["pipeline", "websocket_control"]
+        );
+    }
+
+    #[test]
+    fn test_live_and_restart_required_are_disjoint() {
+        assert!(is_live("websocket_control"));
+        assert!(is_live("partial_granularity"));
+        assert!(!is_live("websocket_port"));
+        assert!(!is_live("unknown_future_settings"));
+        for name in RESTART_REQUIRED {
+            assert!(!is_live(name), "{}", name);
+        }
+        assert!(SettingsReloadReport::default().is_empty());
+    }
+}
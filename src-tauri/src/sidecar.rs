@@ -250,6 +250,11 @@ impl Sidecar {
             .take()
             .ok_or_else(|| SidecarError::SpawnFailed("Failed to get stdout".to_string()))?;
 
+        // stderr goes to the structured logger (python::*)
+        if let Some(stderr) = child.stderr.take() {
+            crate::sidecar_stderr::spawn_reader(stderr);
+        }
+
         let child_pid = child.id();
 
         // Spawn internal tasks
@@ -0,0 +1,243 @@
+//! Binary Audio Channel to the Python Sidecar
+//!
+//! Sending PCM as JSON arrays over stdin costs ~4 bytes of text per sample
+//! byte plus a Python list of ints per batch. When the sidecar supports it,
+//! raw PCM goes over a dedicated channel instead — a Unix domain socket on
+//! macOS/Linux, a named pipe on Windows — while stdin keeps carrying every
+//! JSON message (see `ipc_protocol::AudioChannelOffer` for the negotiation
+//! and framing).
+//!
+//! The socket lives in a fresh owner-only directory and is unlinked as soon
+//! as the sidecar has connected; the pipe accepts a single instance and its
+//! DACL admits the current user only (other accounts can't open it). Any
+//! failure — an older sidecar, a refused connection, a broken channel later
+//! on — falls back to JSON on stdin.
+
+use anyhow::{Context, Result};
+use std::time::Duration;
+use tokio::io::AsyncWrite;
+
+use crate::ipc_protocol::{AudioChannelKind, AudioChannelOffer};
+
+/// Time allowed for the sidecar to connect after accepting the offer
+pub const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Connected write end of the audio channel
+pub type AudioChannel = Box<dyn AsyncWrite + Unpin + Send>;
+
+/// Channel endpoint waiting for the sidecar to connect
+pub struct AudioChannelListener {
+    #[cfg(unix)]
+    listener: tokio::net::UnixListener,
+    #[cfg(unix)]
+    dir: std::path::PathBuf,
+    #[cfg(windows)]
+    server: tokio::net::windows::named_pipe::NamedPipeServer,
+    path: String,
+}
+
+impl AudioChannelListener {
+    /// Create the endpoint (fails on platforms without either transport)
+    #[cfg(unix)]
+    pub fn bind() -> Result<Self> {
+        use std::os::unix::fs::DirBuilderExt;
+
+        // Short name: socket paths are limited to ~104 bytes on macOS
+        let token = uuid::Uuid::new_v4().simple().to_string();
+        let dir = std::env::temp_dir().join(format!("mma-audio-{}", &token[..12]));
+        std::fs::DirBuilder::new()
+            .mode(0o700)
+            .create(&dir)
+            .with_context(|| format!("Failed to create audio channel directory: {:?}", dir))?;
+        let socket_path = dir.join("audio.sock");
+        let listener = match tokio::net::UnixListener::bind(&socket_path) {
+            Ok(listener) => listener,
+            Err(e) => {
+                let _ = std::fs::remove_dir_all(&dir);
+                return Err(e).context("Failed to bind audio channel socket");
+            }
+        };
+        Ok(Self {
+            listener,
+            path: socket_path.to_string_lossy().into_owned(),
+            dir,
+        })
+    }
+
+    /// Create the endpoint (fails on platforms without either transport)
+    #[cfg(windows)]
+    pub fn bind() -> Result<Self> {
+        let token = uuid::Uuid::new_v4().simple().to_string();
+        let path = format!(r"\\.\pipe\mma-audio-{}", token);
+        let descriptor = UserOnlyDescriptor::new()?;
+        let mut attributes = winapi::um::minwinbase::SECURITY_ATTRIBUTES {
+            nLength: std::mem::size_of::<winapi::um::minwinbase::SECURITY_ATTRIBUTES>() as u32,
+            lpSecurityDescriptor: descriptor.0,
+            bInheritHandle: 0,
+        };
+        // SAFETY: `attributes` and the descriptor it points to outlive the call
+        let server = unsafe {
+            tokio::net::windows::named_pipe::ServerOptions::new()
+                .first_pipe_instance(true)
+                .max_instances(1)
+                .access_inbound(false)
+                .create_with_security_attributes_raw(
+                    &path,
+                    std::ptr::addr_of_mut!(attributes).cast(),
+                )
+        }
+        .context("Failed to create audio channel pipe")?;
+        Ok(Self { server, path })
+    }
+
+    /// Create the endpoint (fails on platforms without either transport)
+    #[cfg(not(any(unix, windows)))]
+    pub fn bind() -> Result<Self> {
+        anyhow::bail!("No binary audio channel on this platform")
+    }
+
+    /// Offer sent to the sidecar
+    pub fn offer(&self) -> AudioChannelOffer {
+        AudioChannelOffer {
+            kind: if cfg!(windows) {
+                AudioChannelKind::NamedPipe
+            } else {
+                AudioChannelKind::UnixSocket
+            },
+            path: self.path.clone(),
+        }
+    }
+
+    /// Wait for the sidecar's connection (within `CONNECT_TIMEOUT`)
+    #[cfg(unix)]
+    pub async fn accept(self) -> Result<AudioChannel> {
+        let accepted = tokio::time::timeout(CONNECT_TIMEOUT, self.listener.accept()).await;
+        // The connected stream no longer needs the socket file
+        let _ = std::fs::remove_dir_all(&self.dir);
+        let (stream, _) = accepted
+            .context("Sidecar did not connect to the audio channel")?
+            .context("Failed to accept audio channel connection")?;
+        // Only the write half is used; the sidecar never writes back
+        let (_, writer) = stream.into_split();
+        Ok(Box::new(writer))
+    }
+
+    /// Wait for the sidecar's connection (within `CONNECT_TIMEOUT`)
+    #[cfg(windows)]
+    pub async fn accept(self) -> Result<AudioChannel> {
+        tokio::time::timeout(CONNECT_TIMEOUT, self.server.connect())
+            .await
+            .context("Sidecar did not connect to the audio channel")?
+            .context("Failed to accept audio channel connection")?;
+        Ok(Box::new(self.server))
+    }
+
+    /// Wait for the sidecar's connection (within `CONNECT_TIMEOUT`)
+    #[cfg(not(any(unix, windows)))]
+    pub async fn accept(self) -> Result<AudioChannel> {
+        anyhow::bail!("No binary audio channel on this platform")
+    }
+}
+
+/// Security descriptor whose protected DACL grants the current user only
+#[cfg(windows)]
+struct UserOnlyDescriptor(winapi::um::winnt::PSECURITY_DESCRIPTOR);
+
+#[cfg(windows)]
+impl UserOnlyDescriptor {
+    fn new() -> Result<Self> {
+        use std::ptr;
+        use winapi::shared::sddl::{
+            ConvertSidToStringSidW, ConvertStringSecurityDescriptorToSecurityDescriptorW,
+            SDDL_REVISION_1,
+        };
+        use winapi::um::handleapi::CloseHandle;
+        use winapi::um::processthreadsapi::{GetCurrentProcess, OpenProcessToken};
+        use winapi::um::securitybaseapi::GetTokenInformation;
+        use winapi::um::winbase::LocalFree;
+        use winapi::um::winnt::{TokenUser, HANDLE, TOKEN_QUERY, TOKEN_USER};
+
+        unsafe {
+            let mut token: HANDLE = ptr::null_mut();
+            if OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &mut token) == 0 {
+                return Err(std::io::Error::last_os_error())
+                    .context("Failed to open the process token");
+            }
+            let mut len = 0u32;
+            GetTokenInformation(token, TokenUser, ptr::null_mut(), 0, &mut len);
+            // u64 elements keep TOKEN_USER aligned
+            let mut buffer = vec![0u64; (len as usize).div_ceil(8)];
+            let read =
+                GetTokenInformation(token, TokenUser, buffer.as_mut_ptr().cast(), len, &mut len);
+            let read_error = std::io::Error::last_os_error();
+            CloseHandle(token);
+            if read == 0 {
+                return Err(read_error).context("Failed to read the current user");
+            }
+            let user = &*(buffer.as_ptr() as *const TOKEN_USER);
+
+            let mut sid_ptr = ptr::null_mut();
+            if ConvertSidToStringSidW(user.User.Sid, &mut sid_ptr) == 0 {
+                return Err(std::io::Error::last_os_error())
+                    .context("Failed to format the current user SID");
+            }
+            let sid_len = (0..).take_while(|&i| *sid_ptr.add(i) != 0).count();
+            let sid = String::from_utf16_lossy(std::slice::from_raw_parts(sid_ptr, sid_len));
+            LocalFree(sid_ptr.cast());
+
+            // Protected (no inherited entries): generic all for the user only
+            let sddl: Vec<u16> = format!("D:P(A;;GA;;;{})", sid)
+                .encode_utf16()
+                .chain(std::iter::once(0))
+                .collect();
+            let mut descriptor = ptr::null_mut();
+            if ConvertStringSecurityDescriptorToSecurityDescriptorW(
+                sddl.as_ptr(),
+                SDDL_REVISION_1.into(),
+                &mut descriptor,
+                ptr::null_mut(),
+            ) == 0
+            {
+                return Err(std::io::Error::last_os_error())
+                    .context("Failed to build the audio channel security descriptor");
+            }
+            Ok(Self(descriptor))
+        }
+    }
+}
+
+#[cfg(windows)]
+impl Drop for UserOnlyDescriptor {
+    fn drop(&mut self) {
+        unsafe {
+            winapi::um::winbase::LocalFree(self.0);
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[tokio::test]
+    async fn test_socket_channel_delivers_pcm() {
+        let listener = AudioChannelListener::bind().unwrap();
+        let offer = listener.offer();
+        assert_eq!(offer.kind, AudioChannelKind::UnixSocket);
+        let socket_dir = std::path::Path::new(&offer.path)
+            .parent()
+            .unwrap()
+            .to_path_buf();
+
+        let mut sidecar = tokio::net::UnixStream::connect(&offer.path).await.unwrap();
+        let mut channel = listener.accept().await.unwrap();
+        assert!(!socket_dir.exists());
+
+        channel.write_all(&[1, 0, 2, 0]).await.unwrap();
+        channel.flush().await.unwrap();
+        let mut pcm = [0u8; 4];
+        sidecar.read_exact(&mut pcm).await.unwrap();
+        assert_eq!(pcm, [1, 0, 2, 0]);
+    }
+}
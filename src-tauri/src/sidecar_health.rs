@@ -0,0 +1,153 @@
+//! Sidecar Health Checks
+//!
+//! "Transcription is slow" reports used to come with nothing to go on. While
+//! recording, a `ping` goes to the sidecar on the control lane every
+//! `HEALTH_CHECK_INTERVAL`; a newer sidecar answers with its health
+//! (`ipc_protocol::SidecarHealth`): model loaded, real-time factor of recent
+//! requests, speech audio waiting for transcription and memory use. The
+//! time until the `pong` arrives is measured too — the sidecar handles
+//! messages in order, so it shows how long a request waits behind audio.
+//!
+//! Every report is logged (a warning when the real-time factor exceeds
+//! `SLOW_REAL_TIME_FACTOR`) and the last one is returned by
+//! `get_sidecar_status`. An older sidecar answers with a bare `pong`, which
+//! still counts as a reply.
+
+use serde::Serialize;
+use std::time::{Duration, Instant};
+
+use crate::ipc_protocol::{NegotiatedProtocol, Pong, SidecarHealth};
+
+/// Interval between health checks while recording
+pub const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Real-time factor above which the sidecar is falling behind
+pub const SLOW_REAL_TIME_FACTOR: f64 = 1.0;
+
+/// Health check state of the active (or last) session
+#[derive(Debug, Default)]
+pub struct HealthMonitor {
+    /// Outstanding ping and when it was sent
+    pending: Option<(String, Instant)>,
+    last_health: Option<SidecarHealth>,
+    /// Unix ms of the last reply
+    last_reply_ms: Option<u64>,
+    round_trip_ms: Option<u64>,
+    checks: u64,
+    replies: u64,
+    /// Pings superseded by the next check without a reply
+    missed: u64,
+}
+
+/// Health of the sidecar as returned by `get_sidecar_status`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SidecarStatus {
+    /// The sidecar process is running
+    pub running: bool,
+    /// Protocol version agreed at startup
+    pub protocol: Option<NegotiatedProtocol>,
+    /// Last reported health (None before a reply, or from an older sidecar)
+    pub health: Option<SidecarHealth>,
+    /// Unix ms of the last reply
+    pub last_reply_ms: Option<u64>,
+    /// Time from ping to pong of the last reply
+    pub round_trip_ms: Option<u64>,
+    pub checks: u64,
+    pub replies: u64,
+    pub missed: u64,
+}
+
+impl HealthMonitor {
+    /// A ping with `id` was queued for the sidecar
+    pub fn on_ping_sent(&mut self, id: String, now: Instant) {
+        if self.pending.is_some() {
+            self.missed += 1;
+        }
+        self.pending = Some((id, now));
+        self.checks += 1;
+    }
+
+    /// Apply a pong; returns false when it answers no outstanding ping
+    /// (e.g. a manual `send_sidecar_control` ping)
+    pub fn on_pong(&mut self, pong: &Pong, now: Instant, now_ms: u64) -> bool {
+        let Some((_, sent_at)) = self.pending.take_if(|(id, _)| *id == pong.id) else {
+            return false;
+        };
+        self.round_trip_ms = Some(now.duration_since(sent_at).as_millis() as u64);
+        self.last_reply_ms = Some(now_ms);
+        self.replies += 1;
+        if pong.health.is_some() {
+            self.last_health = pong.health.clone();
+        }
+        true
+    }
+
+    pub fn status(&self, running: bool) -> SidecarStatus {
+        SidecarStatus {
+            running,
+            protocol: None,
+            health: self.last_health.clone(),
+            last_reply_ms: self.last_reply_ms,
+            round_trip_ms: self.round_trip_ms,
+            checks: self.checks,
+            replies: self.replies,
+            missed: self.missed,
+        }
+    }
+}
+
+/// Whether reported health means the sidecar is falling behind
+pub fn is_slow(health: &SidecarHealth) -> bool {
+    !health.model_loaded
+        || health
+            .real_time_factor
+            .is_some_and(|rtf| rtf > SLOW_REAL_TIME_FACTOR)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pong(id: &str, real_time_factor: Option<f64>) -> Pong {
+        Pong {
+            id: id.to_string(),
+            health: real_time_factor.map(|rtf| SidecarHealth {
+                model_loaded: true,
+                model: Some("small".to_string()),
+                real_time_factor: Some(rtf),
+                queue_depth_ms: 0,
+                memory_mb: Some(900),
+            }),
+        }
+    }
+
+    #[test]
+    fn test_matches_pongs_to_pings() {
+        let now = Instant::now();
+        let mut monitor = HealthMonitor::default();
+
+        monitor.on_ping_sent("ctrl-1".to_string(), now);
+        // Unanswered ping superseded by the next check
+        monitor.on_ping_sent("ctrl-2".to_string(), now);
+        assert!(!monitor.on_pong(&pong("ctrl-1", Some(0.5)), now, 0));
+        assert!(monitor.on_pong(
+            &pong("ctrl-2", Some(1.5)),
+            now + Duration::from_millis(120),
+            1_000
+        ));
+        assert!(!monitor.on_pong(&pong("ctrl-2", Some(0.5)), now, 0));
+
+        let status = monitor.status(true);
+        assert_eq!(status.round_trip_ms, Some(120));
+        assert_eq!(status.last_reply_ms, Some(1_000));
+        assert_eq!((status.checks, status.replies, status.missed), (2, 1, 1));
+        assert!(is_slow(status.health.as_ref().unwrap()));
+
+        // A bare pong keeps the last reported health
+        monitor.on_ping_sent("ctrl-3".to_string(), now);
+        assert!(monitor.on_pong(&pong("ctrl-3", None), now, 2_000));
+        let status = monitor.status(true);
+        assert_eq!(status.health.unwrap().real_time_factor, Some(1.5));
+        assert_eq!(status.last_reply_ms, Some(2_000));
+    }
+}
@@ -0,0 +1,252 @@
+//! Structured Capture of Sidecar stderr
+//!
+//! The Python sidecar logs to stderr (stdout carries IPC), which used to be
+//! piped and never read: tracebacks vanished, and a chatty sidecar could
+//! fill the pipe and block. A reader task now forwards every record into
+//! the structured logger under a `python::<logger>` component:
+//!
+//! - Lines in the sidecar's log format
+//!   (`%(asctime)s - %(name)s - %(levelname)s - %(message)s`) keep their
+//!   logger name and level.
+//! - Other lines continue the previous record, so a traceback arrives as
+//!   one entry. A line with no record to continue (a bare `print`, an
+//!   uncaught exception) starts a `python::stderr` record at warn level.
+//! - A record is emitted when the next one starts, or after
+//!   `IDLE_FLUSH` without output, so the last traceback isn't held back.
+
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+
+use crate::logger::{LogEntry, LogLevel};
+
+/// Quiet time after which a pending record is emitted
+pub const IDLE_FLUSH: Duration = Duration::from_millis(200);
+
+/// Lines kept per record (the rest of a huge traceback is counted only)
+const MAX_RECORD_LINES: usize = 200;
+
+/// Logger name of lines outside the log format
+const RAW_LOGGER: &str = "stderr";
+
+/// One log record of the sidecar
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PythonLogRecord {
+    /// Python logger name (`stderr` for raw output)
+    pub logger: String,
+    /// Python level name (`INFO`, `WARNING`, ...)
+    pub level: String,
+    /// Message including continuation lines
+    pub message: String,
+    /// Continuation lines dropped beyond `MAX_RECORD_LINES`
+    pub truncated_lines: usize,
+}
+
+impl PythonLogRecord {
+    fn raw(line: &str) -> Self {
+        Self {
+            logger: RAW_LOGGER.to_string(),
+            level: "WARNING".to_string(),
+            message: line.to_string(),
+            truncated_lines: 0,
+        }
+    }
+
+    fn append(&mut self, line: &str) {
+        if self.message.lines().count() >= MAX_RECORD_LINES {
+            self.truncated_lines += 1;
+        } else {
+            self.message.push('\n');
+            self.message.push_str(line);
+        }
+    }
+
+    /// Structured logger level of the Python level
+    pub fn log_level(&self) -> LogLevel {
+        match self.level.as_str() {
+            "DEBUG" => LogLevel::Debug,
+            "INFO" => LogLevel::Info,
+            "WARNING" => LogLevel::Warn,
+            _ => LogLevel::Error, // ERROR, CRITICAL
+        }
+    }
+
+    /// Forward to the structured logger
+    pub fn log(&self) {
+        let component = format!("python::{}", self.logger);
+        let mut entry =
+            LogEntry::new(self.log_level(), &component, "sidecar_log").with_message(&self.message);
+        if self.truncated_lines > 0 {
+            entry = entry.with_details(serde_json::json!({
+                "truncated_lines": self.truncated_lines
+            }));
+        }
+        entry.log();
+    }
+}
+
+/// Parse a line in the sidecar's log format
+pub fn parse_log_line(line: &str) -> Option<PythonLogRecord> {
+    let mut fields = line.splitn(4, " - ");
+    let (timestamp, logger, level, message) = (
+        fields.next()?,
+        fields.next()?,
+        fields.next()?,
+        fields.next()?,
+    );
+    // asctime: "2025-01-01 12:00:00,123"
+    let looks_like_time = timestamp.len() == 23
+        && timestamp.as_bytes()[4] == b'-'
+        && timestamp.as_bytes()[19] == b',';
+    let known_level = matches!(level, "DEBUG" | "INFO" | "WARNING" | "ERROR" | "CRITICAL");
+    if !looks_like_time || !known_level || logger.is_empty() {
+        return None;
+    }
+    Some(PythonLogRecord {
+        logger: logger.to_string(),
+        level: level.to_string(),
+        message: message.to_string(),
+        truncated_lines: 0,
+    })
+}
+
+/// Groups stderr lines into records
+#[derive(Debug, Default)]
+pub struct StderrCollector {
+    pending: Option<PythonLogRecord>,
+}
+
+impl StderrCollector {
+    /// Add a line; returns the record it completes, if any
+    pub fn push(&mut self, line: &str) -> Option<PythonLogRecord> {
+        if line.trim().is_empty() && self.pending.is_none() {
+            return None;
+        }
+        if let Some(record) = parse_log_line(line) {
+            return self.pending.replace(record);
+        }
+        match self.pending.as_mut() {
+            Some(record) => {
+                record.append(line);
+                None
+            }
+            None => {
+                self.pending = Some(PythonLogRecord::raw(line));
+                None
+            }
+        }
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Take the pending record (idle or end of stream)
+    pub fn flush(&mut self) -> Option<PythonLogRecord> {
+        self.pending.take()
+    }
+}
+
+/// Read the sidecar's stderr until EOF, forwarding records to the logger
+pub fn spawn_reader<R>(stderr: R) -> tokio::task::JoinHandle<()>
+where
+    R: AsyncRead + Unpin + Send + 'static,
+{
+    tokio::spawn(async move {
+        let mut lines = BufReader::new(stderr).lines();
+        let mut collector = StderrCollector::default();
+        loop {
+            // Wait for more lines only briefly while a record is pending
+            let next = if collector.is_pending() {
+                match tokio::time::timeout(IDLE_FLUSH, lines.next_line()).await {
+                    Ok(next) => next,
+                    Err(_) => {
+                        if let Some(record) = collector.flush() {
+                            record.log();
+                        }
+                        continue;
+                    }
+                }
+            } else {
+                lines.next_line().await
+            };
+            match next {
+                Ok(Some(line)) => {
+                    if let Some(record) = collector.push(line.trim_end()) {
+                        record.log();
+                    }
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    // Invalid UTF-8 and the like: keep what was collected
+                    log_warn!(
+                        "python_sidecar::stderr",
+                        "stderr_read_failed",
+                        format!("{:?}", e)
+                    );
+                    break;
+                }
+            }
+        }
+        if let Some(record) = collector.flush() {
+            record.log();
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_log_line() {
+        let record = parse_log_line(
+            "2025-01-01 12:00:00,123 - stt_engine.audio_pipeline - WARNING - slow - really",
+        )
+        .unwrap();
+        assert_eq!(record.logger, "stt_engine.audio_pipeline");
+        assert_eq!(record.level, "WARNING");
+        assert_eq!(record.message, "slow - really");
+        assert!(matches!(record.log_level(), LogLevel::Warn));
+
+        assert_eq!(parse_log_line("plain output"), None);
+        assert_eq!(parse_log_line("a - b - c - d"), None);
+        assert_eq!(
+            parse_log_line("2025-01-01 12:00:00,123 - x - TRACE - m"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_collector_groups_tracebacks() {
+        let mut collector = StderrCollector::default();
+        assert_eq!(
+            collector
+                .push("2025-01-01 12:00:00,123 - __main__ - ERROR - Error handling message: boom"),
+            None
+        );
+        assert_eq!(collector.push("Traceback (most recent call last):"), None);
+        assert_eq!(
+            collector.push("  File \"main.py\", line 1, in <module>"),
+            None
+        );
+        assert_eq!(collector.push("ValueError: boom"), None);
+
+        let record = collector
+            .push("2025-01-01 12:00:01,000 - __main__ - INFO - next")
+            .unwrap();
+        assert_eq!(record.logger, "__main__");
+        assert!(matches!(record.log_level(), LogLevel::Error));
+        assert_eq!(record.message.lines().count(), 4);
+        assert!(record.message.ends_with("ValueError: boom"));
+
+        assert_eq!(collector.flush().unwrap().message, "next");
+        assert_eq!(collector.flush(), None);
+
+        // Output outside the log format without a record to continue
+        assert_eq!(collector.push(""), None);
+        assert_eq!(collector.push("Traceback (most recent call last):"), None);
+        let raw = collector.flush().unwrap();
+        assert_eq!(raw.logger, "stderr");
+        assert!(matches!(raw.log_level(), LogLevel::Warn));
+    }
+}
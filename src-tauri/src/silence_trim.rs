@@ -0,0 +1,348 @@
+//! Silence Trimming on Export
+//!
+//! Sparse meetings (long waits, breaks, a recorder left running) produce
+//! audio that is mostly silence. This export drops every silent stretch of
+//! at least `min_silence_seconds`, keeping a `keep_gap_ms` pause in its
+//! place, and writes a share-able copy next to the session:
+//! - `audio.trimmed.flac`: the session audio without the omitted stretches
+//! - `transcript.trimmed.md`: the Markdown transcript with timestamps on the
+//!   trimmed timeline and a `[silence 4:32 omitted]` marker at every cut
+//!
+//! Silence is read from the per-second voice activity (`activity.bin`): a
+//! second counts as silent when none of its frames was voiced.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::activity::ActivityPoint;
+use crate::furigana::Furigana;
+use crate::storage::{AudioFormat, LocalStorageService, SessionAudioWriter};
+
+/// Trimmed audio file name (in the session directory)
+pub const TRIMMED_AUDIO_FILENAME: &str = "audio.trimmed.flac";
+
+/// Trimmed transcript file name (in the session directory)
+pub const TRIMMED_TRANSCRIPT_FILENAME: &str = "transcript.trimmed.md";
+
+/// Longest gap kept in place of an omitted stretch
+pub const MAX_KEEP_GAP_MS: u64 = 10_000;
+
+const SAMPLES_PER_MS: u64 = 16;
+
+/// Silence trimming options
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SilenceTrimOptions {
+    /// Shortest silent stretch that is trimmed
+    #[serde(default = "default_min_silence_seconds")]
+    pub min_silence_seconds: u64,
+    /// Pause kept in place of a trimmed stretch (split around the cut)
+    #[serde(default = "default_keep_gap_ms")]
+    pub keep_gap_ms: u64,
+}
+
+fn default_min_silence_seconds() -> u64 {
+    30
+}
+
+fn default_keep_gap_ms() -> u64 {
+    1000
+}
+
+impl Default for SilenceTrimOptions {
+    fn default() -> Self {
+        Self {
+            min_silence_seconds: 30,
+            keep_gap_ms: 1000,
+        }
+    }
+}
+
+impl SilenceTrimOptions {
+    pub fn validate(&self) -> Result<()> {
+        if self.min_silence_seconds < 2 {
+            anyhow::bail!("min_silence_seconds must be at least 2");
+        }
+        if self.keep_gap_ms > MAX_KEEP_GAP_MS || self.keep_gap_ms >= self.min_silence_seconds * 1000
+        {
+            anyhow::bail!(
+                "keep_gap_ms must be at most {} and shorter than min_silence_seconds",
+                MAX_KEEP_GAP_MS
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Stretch of the original audio left out of the export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct OmittedSpan {
+    pub start_ms: u64,
+    pub end_ms: u64,
+}
+
+impl OmittedSpan {
+    pub fn duration_ms(&self) -> u64 {
+        self.end_ms - self.start_ms
+    }
+}
+
+/// Result of a trimmed export
+#[derive(Debug, Clone, Serialize)]
+pub struct TrimmedExport {
+    pub audio_path: PathBuf,
+    pub transcript_path: PathBuf,
+    pub omitted: Vec<OmittedSpan>,
+    pub original_duration_ms: u64,
+    pub trimmed_duration_ms: u64,
+}
+
+/// Stretches to omit: silent runs of at least `min_silence_seconds`, less
+/// half the kept gap at each end
+///
+/// `points` holds one activity point per second.
+pub fn find_silences(points: &[ActivityPoint], options: &SilenceTrimOptions) -> Vec<OmittedSpan> {
+    let half_gap = options.keep_gap_ms / 2;
+    let mut spans = Vec::new();
+    let mut run_start = None;
+    // A trailing sentinel closes a run reaching the end of the audio
+    for (second, voiced) in points
+        .iter()
+        .map(|p| p.voiced > 0)
+        .chain(std::iter::once(true))
+        .enumerate()
+    {
+        match (voiced, run_start) {
+            (false, None) => run_start = Some(second as u64),
+            (true, Some(start)) => {
+                if second as u64 - start >= options.min_silence_seconds {
+                    spans.push(OmittedSpan {
+                        start_ms: start * 1000 + half_gap,
+                        end_ms: second as u64 * 1000 - (options.keep_gap_ms - half_gap),
+                    });
+                }
+                run_start = None;
+            }
+            _ => {}
+        }
+    }
+    spans
+}
+
+/// Position of `original_ms` on the trimmed timeline (times inside an
+/// omitted stretch map to the cut)
+pub fn trimmed_ms(spans: &[OmittedSpan], original_ms: u64) -> u64 {
+    let removed: u64 = spans
+        .iter()
+        .filter(|span| span.start_ms < original_ms)
+        .map(|span| span.end_ms.min(original_ms) - span.start_ms)
+        .sum();
+    original_ms - removed
+}
+
+/// Marker standing in for an omitted stretch, e.g. `[silence 4:32 omitted]`
+pub fn omitted_marker(span: &OmittedSpan) -> String {
+    let seconds = span.duration_ms() / 1000;
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("[silence {}:{:02}:{:02} omitted]", hours, minutes, seconds)
+    } else {
+        format!("[silence {}:{:02} omitted]", minutes, seconds)
+    }
+}
+
+/// Write the trimmed audio and transcript of a finalized session
+///
+/// `furigana` annotates the transcript as in the Markdown export.
+pub fn export_session(
+    storage: &LocalStorageService,
+    session_id: &str,
+    options: &SilenceTrimOptions,
+    furigana: Option<&Furigana>,
+) -> Result<TrimmedExport> {
+    options.validate()?;
+    crate::session_id::validate_session_id(session_id)?;
+    let loaded = storage.load_session(session_id)?;
+    let session_dir = storage.get_session_dir(session_id);
+    let activity = crate::activity::load_activity(&session_dir)?;
+    let omitted = find_silences(&activity, options);
+
+    let audio_path = session_dir.join(TRIMMED_AUDIO_FILENAME);
+    let mut writer = SessionAudioWriter::create(audio_path.clone(), AudioFormat::Flac)?;
+    let mut position = 0u64;
+    let mut written: Result<()> = Ok(());
+    crate::storage::read_session_audio(&session_dir, |samples| {
+        if written.is_ok() {
+            written = write_kept(&mut writer, samples, position, &omitted);
+        }
+        position += samples.len() as u64;
+    })?;
+    written?;
+    let trimmed_samples = writer.samples_written();
+    writer
+        .close()
+        .with_context(|| format!("Failed to write trimmed audio: {:?}", audio_path))?;
+
+    let markdown = crate::storage::render_trimmed_markdown(
+        &loaded.metadata,
+        &loaded.transcripts,
+        &omitted,
+        furigana,
+    );
+    let transcript_path = session_dir.join(TRIMMED_TRANSCRIPT_FILENAME);
+    crate::storage::write_file_owner_only(&transcript_path, markdown.as_bytes())?;
+
+    Ok(TrimmedExport {
+        audio_path,
+        transcript_path,
+        omitted,
+        original_duration_ms: position / SAMPLES_PER_MS,
+        trimmed_duration_ms: trimmed_samples / SAMPLES_PER_MS,
+    })
+}
+
+/// Write the samples of a chunk starting at sample `position` that fall
+/// outside the omitted stretches
+fn write_kept(
+    writer: &mut SessionAudioWriter,
+    samples: &[i16],
+    position: u64,
+    omitted: &[OmittedSpan],
+) -> Result<()> {
+    let end = position + samples.len() as u64;
+    let mut cursor = position;
+    for span in omitted {
+        let (cut_start, cut_end) = (span.start_ms * SAMPLES_PER_MS, span.end_ms * SAMPLES_PER_MS);
+        if cut_end <= cursor || cut_start >= end {
+            continue;
+        }
+        if cut_start > cursor {
+            writer.write_samples(
+                &samples[(cursor - position) as usize..(cut_start - position) as usize],
+            )?;
+        }
+        cursor = cut_end.min(end);
+    }
+    if cursor < end {
+        writer.write_samples(&samples[(cursor - position) as usize..])?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn activity(pattern: &str) -> Vec<ActivityPoint> {
+        pattern
+            .chars()
+            .map(|c| ActivityPoint {
+                level: 0,
+                voiced: if c == 'v' { 80 } else { 0 },
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_find_silences_and_map_times() {
+        let options = SilenceTrimOptions {
+            min_silence_seconds: 3,
+            keep_gap_ms: 1000,
+        };
+        assert!(options.validate().is_ok());
+        // Silent 2-6s (trimmed), 7-9s (too short), 10-14s (trailing, trimmed)
+        let spans = find_silences(&activity("vv....v..v...."), &options);
+        assert_eq!(
+            spans,
+            vec![
+                OmittedSpan {
+                    start_ms: 2500,
+                    end_ms: 5500
+                },
+                OmittedSpan {
+                    start_ms: 10_500,
+                    end_ms: 13_500
+                },
+            ]
+        );
+
+        assert_eq!(trimmed_ms(&spans, 2000), 2000);
+        assert_eq!(trimmed_ms(&spans, 4000), 2500);
+        assert_eq!(trimmed_ms(&spans, 6000), 3000);
+        assert_eq!(trimmed_ms(&spans, 14_000), 8000);
+
+        assert_eq!(
+            omitted_marker(&OmittedSpan {
+                start_ms: 0,
+                end_ms: 272_400
+            }),
+            "[silence 4:32 omitted]"
+        );
+        assert_eq!(
+            omitted_marker(&OmittedSpan {
+                start_ms: 0,
+                end_ms: 3_723_000
+            }),
+            "[silence 1:02:03 omitted]"
+        );
+
+        let invalid = SilenceTrimOptions {
+            min_silence_seconds: 3,
+            keep_gap_ms: 3000,
+        };
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_export_trimmed_session() {
+        use crate::recording_session::RecordingSession;
+        use crate::storage::TranscriptionEvent;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = LocalStorageService::new(dir.path().to_path_buf());
+        let session = RecordingSession::new("s1", "u1", 0);
+        session.start(Some(&storage), AudioFormat::Wav).unwrap();
+        // 2s speech, 6s silence, 2s speech
+        let tone = |seconds: usize| -> Vec<u8> {
+            (0..seconds * 16_000)
+                .flat_map(|i| (((i as f64 * 0.3).sin() * 8000.0) as i16).to_le_bytes())
+                .collect()
+        };
+        session.append_audio(&tone(2)).unwrap();
+        session.append_audio(&vec![0; 6 * 32_000]).unwrap();
+        session.append_audio(&tone(2)).unwrap();
+        for (timestamp_ms, text) in [(1_500, "first"), (9_500, "second")] {
+            session
+                .append_transcript_event(&TranscriptionEvent {
+                    timestamp_ms,
+                    text: text.to_string(),
+                    is_final: true,
+                    speaker: None,
+                })
+                .unwrap();
+        }
+        session.stop(Some(&storage), "mic", Vec::new()).unwrap();
+
+        let options = SilenceTrimOptions {
+            min_silence_seconds: 3,
+            keep_gap_ms: 1000,
+        };
+        let export = export_session(&storage, "s1", &options, None).unwrap();
+        assert_eq!(
+            export.omitted,
+            vec![OmittedSpan {
+                start_ms: 2500,
+                end_ms: 7500
+            }]
+        );
+        assert_eq!(export.original_duration_ms, 10_000);
+        assert_eq!(export.trimmed_duration_ms, 5000);
+        assert!(export.audio_path.ends_with(TRIMMED_AUDIO_FILENAME));
+
+        let markdown = std::fs::read_to_string(&export.transcript_path).unwrap();
+        assert!(markdown.contains("- 無音カット: 1箇所（計00:00:05）\n"));
+        assert!(markdown.contains(
+            "\n**[00:00:01]** first\n\n[silence 0:05 omitted]\n\n**[00:00:04]** second\n"
+        ));
+    }
+}
@@ -0,0 +1,779 @@
+//! Soak Test Mode
+//!
+//! Hidden `--soak` mode of the main binary, for reproducing leaks on any
+//! machine. Fixture audio is looped through a simulated capture adapter
+//! (10ms frames into the ring buffer, 250ms batches through the stdin writer
+//! to the real Python sidecar) for N hours. Process memory/CPU and
+//! per-utterance latency are sampled every window.
+//!
+//! Invariants checked at the end:
+//! - every log span has ended (no `span_start` without `span_end`)
+//! - no audio batch was rejected by the stdin writer
+//! - resident memory growth (app and sidecar) stays within `--max-rss-growth-mb`
+//! - the final-text p95 latency of the last window stays within
+//!   `--p95-tolerance` times that of the first window
+//!
+//! ```bash
+//! meeting-minutes-automator --soak --hours 8 --fixture meeting.wav --report soak.json
+//! ```
+//!
+//! Exit code: 0 passed, 1 invariant violated, 2 soak could not run.
+
+use crate::ipc_protocol::{encode_audio_stream_request, IpcMessage};
+use crate::latency::{now_ms, LatencyHistogram, LatencySnapshot, LatencyStage, LatencyTracker};
+use crate::logger::{open_span_count, LogSpan};
+use crate::python_sidecar::PythonSidecarManager;
+use crate::ring_buffer::{
+    ms_for_bytes, new_shared_ring_buffer, pop_audio, push_audio_drop_oldest, SharedRingBuffer,
+    BUFFER_CAPACITY, BYTES_PER_SAMPLE, SAMPLE_RATE,
+};
+use crate::stdin_writer::{ControlMessage, Lane, StdinWriter, StdinWriterError};
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Serialize;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::f32::consts::TAU;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::io::AsyncBufReadExt;
+use tokio::sync::Notify;
+
+/// Capture frame of the simulated adapter
+const FRAME_MS: u64 = 10;
+/// Sender batch interval (same as the recording pipeline)
+const BATCH_INTERVAL: Duration = Duration::from_millis(250);
+/// Time allowed for trailing results after capture stops
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(5);
+/// Windows with fewer measured finals are left out of the p95 comparison
+const MIN_FINALS_PER_WINDOW: u64 = 5;
+/// Linux clock ticks per second (`_SC_CLK_TCK`, 100 on all supported targets)
+#[cfg(any(target_os = "linux", test))]
+const CLOCK_TICKS_PER_SEC: u64 = 100;
+
+const USAGE: &str = r#"--soak - Long-running soak test
+
+OPTIONS:
+    --hours <n>                  Test duration, fractions allowed (default: 2)
+    --fixture <wav>              16kHz mono 16-bit WAV to loop (default: synthetic tone)
+    --window-secs <n>            Sampling window (default: 60)
+    --report <path>              JSON report (default: soak-report.json)
+    --max-rss-growth-mb <n>      Allowed memory growth per process (default: 100)
+    --p95-tolerance <x>          Allowed final-text p95 growth factor (default: 3.0)
+"#;
+
+/// Soak run options (arguments following `--soak`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct SoakConfig {
+    pub duration: Duration,
+    /// Audio looped by the simulated adapter (synthetic tone if None)
+    pub fixture: Option<PathBuf>,
+    pub window: Duration,
+    pub report: PathBuf,
+    pub max_rss_growth_mb: u64,
+    pub p95_tolerance: f64,
+}
+
+impl Default for SoakConfig {
+    fn default() -> Self {
+        Self {
+            duration: Duration::from_secs(2 * 3600),
+            fixture: None,
+            window: Duration::from_secs(60),
+            report: PathBuf::from("soak-report.json"),
+            max_rss_growth_mb: 100,
+            p95_tolerance: 3.0,
+        }
+    }
+}
+
+impl SoakConfig {
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Self> {
+        let mut config = Self::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .ok_or_else(|| anyhow!("{} requires a value", name))
+            };
+            match arg.as_str() {
+                "--hours" => {
+                    let hours: f64 = value("--hours")?
+                        .parse()
+                        .context("Failed to parse --hours")?;
+                    if !hours.is_finite() || hours <= 0.0 {
+                        bail!("--hours must be greater than zero");
+                    }
+                    config.duration = Duration::from_secs_f64(hours * 3600.0);
+                }
+                "--fixture" => config.fixture = Some(PathBuf::from(value("--fixture")?)),
+                "--window-secs" => {
+                    let secs: u64 = value("--window-secs")?
+                        .parse()
+                        .context("Failed to parse --window-secs")?;
+                    if secs == 0 {
+                        bail!("--window-secs must be greater than zero");
+                    }
+                    config.window = Duration::from_secs(secs);
+                }
+                "--report" => config.report = PathBuf::from(value("--report")?),
+                "--max-rss-growth-mb" => {
+                    config.max_rss_growth_mb = value("--max-rss-growth-mb")?
+                        .parse()
+                        .context("Failed to parse --max-rss-growth-mb")?;
+                }
+                "--p95-tolerance" => {
+                    let tolerance: f64 = value("--p95-tolerance")?
+                        .parse()
+                        .context("Failed to parse --p95-tolerance")?;
+                    if tolerance.is_nan() || tolerance < 1.0 {
+                        bail!("--p95-tolerance must be at least 1.0");
+                    }
+                    config.p95_tolerance = tolerance;
+                }
+                other => bail!("Unknown soak argument: {}", other),
+            }
+        }
+        Ok(config)
+    }
+}
+
+// ============================================================================
+// Simulated Capture
+// ============================================================================
+
+/// Looping audio source of the simulated capture adapter
+pub struct FixtureSource {
+    samples: Vec<i16>,
+    position: usize,
+}
+
+impl FixtureSource {
+    /// Load a 16kHz mono 16-bit WAV
+    pub fn from_wav(path: &Path) -> Result<Self> {
+        let mut samples = Vec::new();
+        crate::storage::read_wav_samples(path, |chunk| samples.extend_from_slice(chunk))
+            .with_context(|| format!("Failed to read fixture: {:?}", path))?;
+        if samples.is_empty() {
+            bail!("Fixture contains no audio: {:?}", path);
+        }
+        Ok(Self {
+            samples,
+            position: 0,
+        })
+    }
+
+    /// 1s 440Hz tone followed by 1s of silence (as in `stt_burn_in`)
+    pub fn synthetic() -> Self {
+        let samples = (0..SAMPLE_RATE * 2)
+            .map(|i| {
+                if i < SAMPLE_RATE {
+                    let t = i as f32 / SAMPLE_RATE as f32;
+                    (0.5 * (TAU * 440.0 * t).sin() * i16::MAX as f32) as i16
+                } else {
+                    0
+                }
+            })
+            .collect();
+        Self {
+            samples,
+            position: 0,
+        }
+    }
+
+    /// Next `samples` samples as 16-bit LE PCM, wrapping around at the end
+    pub fn next_frame(&mut self, samples: usize) -> Vec<u8> {
+        let mut frame = Vec::with_capacity(samples * BYTES_PER_SAMPLE);
+        for _ in 0..samples {
+            frame.extend_from_slice(&self.samples[self.position].to_le_bytes());
+            self.position = (self.position + 1) % self.samples.len();
+        }
+        frame
+    }
+}
+
+// ============================================================================
+// Process Usage
+// ============================================================================
+
+/// Resident memory and cumulative CPU time of a process
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProcessUsage {
+    pub rss_bytes: u64,
+    pub cpu_time: Duration,
+}
+
+#[cfg(target_os = "linux")]
+pub fn process_usage(pid: u32) -> Option<ProcessUsage> {
+    let status = std::fs::read_to_string(format!("/proc/{}/status", pid)).ok()?;
+    let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    Some(ProcessUsage {
+        rss_bytes: parse_vm_rss(&status)?,
+        cpu_time: parse_proc_stat_cpu_time(&stat)?,
+    })
+}
+
+#[cfg(target_os = "macos")]
+pub fn process_usage(pid: u32) -> Option<ProcessUsage> {
+    let output = std::process::Command::new("ps")
+        .args(["-o", "rss=,time=", "-p", &pid.to_string()])
+        .output()
+        .ok()?;
+    parse_ps_usage(&String::from_utf8_lossy(&output.stdout))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub fn process_usage(_pid: u32) -> Option<ProcessUsage> {
+    None
+}
+
+/// `VmRSS` of `/proc/<pid>/status`, in bytes
+#[cfg(any(target_os = "linux", test))]
+fn parse_vm_rss(status: &str) -> Option<u64> {
+    let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+    let kb: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kb * 1024)
+}
+
+/// `utime + stime` of `/proc/<pid>/stat`
+#[cfg(any(target_os = "linux", test))]
+fn parse_proc_stat_cpu_time(stat: &str) -> Option<Duration> {
+    // The command name may contain spaces: fields are counted after its ')'
+    let fields: Vec<&str> = stat.rsplit_once(')')?.1.split_whitespace().collect();
+    let utime: u64 = fields.get(11)?.parse().ok()?;
+    let stime: u64 = fields.get(12)?.parse().ok()?;
+    Some(Duration::from_millis(
+        (utime + stime) * 1000 / CLOCK_TICKS_PER_SEC,
+    ))
+}
+
+/// `ps -o rss=,time=` output: RSS in KB and `[[hh:]mm:]ss.ss` CPU time
+#[cfg(any(target_os = "macos", test))]
+fn parse_ps_usage(output: &str) -> Option<ProcessUsage> {
+    let mut fields = output.split_whitespace();
+    let kb: u64 = fields.next()?.parse().ok()?;
+    let mut secs = 0.0;
+    for part in fields.next()?.split(':') {
+        secs = secs * 60.0 + part.parse::<f64>().ok()?;
+    }
+    Some(ProcessUsage {
+        rss_bytes: kb * 1024,
+        cpu_time: Duration::from_secs_f64(secs),
+    })
+}
+
+/// Samples a process, deriving CPU% from the previous sample
+struct UsageSampler {
+    pid: u32,
+    last: Option<(Instant, ProcessUsage)>,
+}
+
+impl UsageSampler {
+    fn new(pid: u32) -> Self {
+        let mut sampler = Self { pid, last: None };
+        sampler.sample();
+        sampler
+    }
+
+    /// (RSS bytes, CPU % since the previous sample)
+    fn sample(&mut self) -> (Option<u64>, Option<f64>) {
+        let now = Instant::now();
+        let Some(usage) = process_usage(self.pid) else {
+            return (None, None);
+        };
+        let cpu_percent = self.last.map(|(at, previous)| {
+            let wall = now.duration_since(at).as_secs_f64().max(0.001);
+            usage
+                .cpu_time
+                .saturating_sub(previous.cpu_time)
+                .as_secs_f64()
+                / wall
+                * 100.0
+        });
+        self.last = Some((now, usage));
+        (Some(usage.rss_bytes), cpu_percent)
+    }
+}
+
+// ============================================================================
+// Report
+// ============================================================================
+
+/// One sampling window
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SoakWindow {
+    pub elapsed_secs: u64,
+    pub rss_bytes: Option<u64>,
+    pub cpu_percent: Option<f64>,
+    pub sidecar_rss_bytes: Option<u64>,
+    pub sidecar_cpu_percent: Option<f64>,
+    /// Cumulative
+    pub batches_sent: u64,
+    /// Cumulative
+    pub batches_rejected: u64,
+    /// Finals matched to a batch in this window
+    pub finals: u64,
+    pub final_p95_ms: Option<u64>,
+    pub open_spans: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SoakReport {
+    pub duration_secs: u64,
+    pub fixture: Option<PathBuf>,
+    pub windows: Vec<SoakWindow>,
+    /// Whole-run latency
+    pub latency: LatencySnapshot,
+    pub open_spans_at_end: u64,
+    pub violations: Vec<String>,
+    pub passed: bool,
+}
+
+/// Check the soak invariants, describing each violation
+pub fn check_invariants(
+    config: &SoakConfig,
+    windows: &[SoakWindow],
+    open_spans_at_end: u64,
+) -> Vec<String> {
+    let mut violations = Vec::new();
+
+    if open_spans_at_end > 0 {
+        violations.push(format!("{} log span(s) never ended", open_spans_at_end));
+    }
+
+    let rejected = windows.last().map(|w| w.batches_rejected).unwrap_or(0);
+    if rejected > 0 {
+        violations.push(format!(
+            "{} audio batch(es) rejected (sidecar not keeping up)",
+            rejected
+        ));
+    }
+
+    let limit_bytes = config.max_rss_growth_mb * 1024 * 1024;
+    let app_rss: Vec<u64> = windows.iter().filter_map(|w| w.rss_bytes).collect();
+    let sidecar_rss: Vec<u64> = windows.iter().filter_map(|w| w.sidecar_rss_bytes).collect();
+    for (name, samples) in [("App", app_rss), ("Sidecar", sidecar_rss)] {
+        if let (Some(first), Some(last)) = (samples.first(), samples.last()) {
+            let growth = last.saturating_sub(*first);
+            if growth > limit_bytes {
+                violations.push(format!(
+                    "{} memory grew by {:.1} MB (limit {} MB)",
+                    name,
+                    growth as f64 / (1024.0 * 1024.0),
+                    config.max_rss_growth_mb
+                ));
+            }
+        }
+    }
+
+    let p95s: Vec<u64> = windows
+        .iter()
+        .filter(|w| w.finals >= MIN_FINALS_PER_WINDOW)
+        .filter_map(|w| w.final_p95_ms)
+        .collect();
+    if let [first, .., last] = p95s.as_slice() {
+        if *last as f64 > *first as f64 * config.p95_tolerance {
+            violations.push(format!(
+                "Final-text p95 latency rose from {} ms to {} ms",
+                first, last
+            ));
+        }
+    }
+
+    violations
+}
+
+// ============================================================================
+// Run
+// ============================================================================
+
+/// Entry point of `--soak` (`args` follow the flag); returns the exit code
+pub fn run_from_args<I: IntoIterator<Item = String>>(args: I) -> i32 {
+    let args: Vec<String> = args.into_iter().collect();
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{}", USAGE);
+        return 0;
+    }
+    let config = match SoakConfig::parse(args) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{:#}\n\n{}", e, USAGE);
+            return 2;
+        }
+    };
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("Failed to start async runtime: {}", e);
+            return 2;
+        }
+    };
+
+    match runtime.block_on(run(&config)) {
+        Ok(report) => {
+            log_info_details!(
+                "soak",
+                "finished",
+                json!({
+                    "passed": report.passed,
+                    "violations": report.violations,
+                    "report": config.report
+                })
+            );
+            if report.passed {
+                0
+            } else {
+                1
+            }
+        }
+        Err(e) => {
+            log_error_details!("soak", "failed", json!({ "error": format!("{:#}", e) }));
+            2
+        }
+    }
+}
+
+/// Run the soak test and write its report
+pub async fn run(config: &SoakConfig) -> Result<SoakReport> {
+    let mut source = match &config.fixture {
+        Some(path) => FixtureSource::from_wav(path)?,
+        None => FixtureSource::synthetic(),
+    };
+
+    let mut sidecar = PythonSidecarManager::new();
+    sidecar
+        .start()
+        .await
+        .context("Failed to start Python sidecar")?;
+    sidecar
+        .wait_for_ready()
+        .await
+        .context("Python sidecar did not become ready")?;
+    let sidecar_pid = sidecar.get_process_id();
+    let stdin = sidecar.take_stdin().context("Sidecar stdin unavailable")?;
+    let mut stdout = sidecar
+        .take_stdout()
+        .context("Sidecar stdout unavailable")?;
+    let writer = StdinWriter::spawn(stdin);
+    let latency = Arc::new(Mutex::new(LatencyTracker::default()));
+
+    log_info_details!(
+        "soak",
+        "started",
+        json!({
+            "duration_secs": config.duration.as_secs(),
+            "fixture": config.fixture,
+            "window_secs": config.window.as_secs(),
+            "sidecar_pid": sidecar_pid
+        })
+    );
+
+    // Sidecar events -> latency checkpoints
+    let flushed = Arc::new(Notify::new());
+    let reader = {
+        let latency = Arc::clone(&latency);
+        let flushed = Arc::clone(&flushed);
+        tokio::spawn(async move {
+            let mut line = String::new();
+            loop {
+                line.clear();
+                match stdout.read_line(&mut line).await {
+                    Ok(0) | Err(_) => break,
+                    Ok(_) => handle_sidecar_line(line.trim(), &latency, &flushed),
+                }
+            }
+        })
+    };
+
+    // Simulated capture adapter: real-time 10ms frames into the ring buffer
+    let ring_buffer = new_shared_ring_buffer();
+    let capture = {
+        let ring_buffer = Arc::clone(&ring_buffer);
+        let samples_per_frame = SAMPLE_RATE * FRAME_MS as usize / 1000;
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_millis(FRAME_MS));
+            loop {
+                interval.tick().await;
+                let frame = source.next_frame(samples_per_frame);
+                push_audio_drop_oldest(&mut ring_buffer.lock().unwrap(), &frame);
+            }
+        })
+    };
+
+    let started = Instant::now();
+    let mut app_usage = UsageSampler::new(std::process::id());
+    let mut sidecar_usage = sidecar_pid.map(UsageSampler::new);
+    let mut totals: BTreeMap<LatencyStage, LatencyHistogram> = BTreeMap::new();
+    let mut windows = Vec::new();
+    let mut batch_buffer = vec![0u8; BUFFER_CAPACITY];
+    let mut batches_sent = 0u64;
+
+    let mut sample_window = |batches_sent: u64| {
+        let histograms = latency.lock().unwrap().take_histograms();
+        for (stage, histogram) in &histograms {
+            totals.entry(*stage).or_default().merge(histogram);
+        }
+        let finals = histograms.get(&LatencyStage::Final);
+        let (rss_bytes, cpu_percent) = app_usage.sample();
+        let (sidecar_rss_bytes, sidecar_cpu_percent) = sidecar_usage
+            .as_mut()
+            .map(|sampler| sampler.sample())
+            .unwrap_or((None, None));
+        let window = SoakWindow {
+            elapsed_secs: started.elapsed().as_secs(),
+            rss_bytes,
+            cpu_percent,
+            sidecar_rss_bytes,
+            sidecar_cpu_percent,
+            batches_sent,
+            batches_rejected: writer.audio_rejected(),
+            finals: finals.map(|h| h.count).unwrap_or(0),
+            final_p95_ms: finals.and_then(|h| h.percentile_ms(0.95)),
+            open_spans: open_span_count(),
+        };
+        log_info_details!(
+            "soak",
+            "window",
+            serde_json::to_value(&window).unwrap_or_default()
+        );
+        windows.push(window);
+    };
+
+    let mut batch_interval = tokio::time::interval(BATCH_INTERVAL);
+    let mut window_interval =
+        tokio::time::interval_at(tokio::time::Instant::now() + config.window, config.window);
+    let deadline = tokio::time::sleep(config.duration);
+    tokio::pin!(deadline);
+    loop {
+        tokio::select! {
+            _ = &mut deadline => break,
+            _ = window_interval.tick() => sample_window(batches_sent),
+            _ = batch_interval.tick() => {
+                if send_batch(&ring_buffer, &mut batch_buffer, &writer, &latency, batches_sent) {
+                    batches_sent += 1;
+                }
+            }
+        }
+    }
+
+    // Stop capture and wait for the trailing results: queued audio is written
+    // first (control messages skip the audio queue), then the sidecar answers
+    // the flush once the speech in progress is transcribed
+    capture.abort();
+    let drained = tokio::time::timeout(DRAIN_TIMEOUT, async {
+        while writer.depth(Lane::Audio) > 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        writer.send_control_message(ControlMessage::Flush).await?;
+        flushed.notified().await;
+        Ok::<_, StdinWriterError>(())
+    })
+    .await;
+    match drained {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => {
+            log_warn_details!("soak", "flush_failed", json!({ "error": e.to_string() }));
+        }
+        Err(_) => {
+            log_warn_details!(
+                "soak",
+                "flush_timeout",
+                json!({ "timeout_secs": DRAIN_TIMEOUT.as_secs() })
+            );
+        }
+    }
+    sample_window(batches_sent);
+
+    drop(writer);
+    if let Err(e) = sidecar.shutdown().await {
+        log_warn_details!(
+            "soak",
+            "sidecar_shutdown_failed",
+            json!({ "error": e.to_string() })
+        );
+    }
+    reader.abort();
+
+    let open_spans_at_end = open_span_count();
+    let violations = check_invariants(config, &windows, open_spans_at_end);
+    let report = SoakReport {
+        duration_secs: started.elapsed().as_secs(),
+        fixture: config.fixture.clone(),
+        windows,
+        latency: LatencySnapshot::from_histograms(&totals),
+        open_spans_at_end,
+        passed: violations.is_empty(),
+        violations,
+    };
+
+    let json = serde_json::to_string_pretty(&report)?;
+    std::fs::write(&config.report, json)
+        .with_context(|| format!("Failed to write soak report: {:?}", config.report))?;
+    Ok(report)
+}
+
+/// Queue the buffered audio as one batch; false if nothing was sent
+fn send_batch(
+    ring_buffer: &SharedRingBuffer,
+    batch_buffer: &mut [u8],
+    writer: &StdinWriter,
+    latency: &Arc<Mutex<LatencyTracker>>,
+    seq: u64,
+) -> bool {
+    let _span = LogSpan::enter("soak", "audio_batch");
+    let bytes_read = pop_audio(&mut ring_buffer.lock().unwrap(), batch_buffer);
+    if bytes_read == 0 {
+        return false;
+    }
+
+    let request_id = format!("soak-{}", seq);
+    let Ok(line) = encode_audio_stream_request(&request_id, &batch_buffer[..bytes_read]) else {
+        return false;
+    };
+    let batched_ms = now_ms();
+    let captured_ms = batched_ms.saturating_sub(ms_for_bytes(bytes_read));
+    latency
+        .lock()
+        .unwrap()
+        .batch_queued(&request_id, captured_ms, batched_ms);
+
+    let hook_latency = Arc::clone(latency);
+    writer
+        .try_send_audio_with_hook(line, move || {
+            hook_latency
+                .lock()
+                .unwrap()
+                .batch_written(&request_id, now_ms())
+        })
+        .is_ok()
+}
+
+fn handle_sidecar_line(line: &str, latency: &Mutex<LatencyTracker>, flushed: &Notify) {
+    let _span = LogSpan::enter("soak", "sidecar_event");
+    let (event_type, data) = match serde_json::from_str::<IpcMessage>(line) {
+        Ok(IpcMessage::Event {
+            event_type, data, ..
+        }) => (event_type, data),
+        Ok(IpcMessage::Response { result, .. }) => {
+            if result["status"] == "flushed" {
+                flushed.notify_one();
+            }
+            return;
+        }
+        _ => return,
+    };
+    let Some(request_id) = data.get("requestId").and_then(|v| v.as_str()) else {
+        return;
+    };
+    match event_type.as_str() {
+        "partial_text" => latency
+            .lock()
+            .unwrap()
+            .partial_received(request_id, now_ms()),
+        "final_text" => latency
+            .lock()
+            .unwrap()
+            .final_completed(request_id, now_ms(), None, None),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_config() {
+        let config = SoakConfig::parse(args(&[
+            "--hours",
+            "0.5",
+            "--fixture",
+            "a.wav",
+            "--window-secs",
+            "10",
+            "--p95-tolerance",
+            "2",
+        ]))
+        .unwrap();
+        assert_eq!(config.duration, Duration::from_secs(1800));
+        assert_eq!(config.fixture, Some(PathBuf::from("a.wav")));
+        assert_eq!(config.window, Duration::from_secs(10));
+        assert_eq!(config.p95_tolerance, 2.0);
+        assert_eq!(config.max_rss_growth_mb, 100);
+
+        assert!(SoakConfig::parse(args(&["--hours", "0"])).is_err());
+        assert!(SoakConfig::parse(args(&["--hours"])).is_err());
+        assert!(SoakConfig::parse(args(&["--p95-tolerance", "0.5"])).is_err());
+        assert!(SoakConfig::parse(args(&["--verbose"])).is_err());
+    }
+
+    #[test]
+    fn test_fixture_source_loops() {
+        let mut source = FixtureSource {
+            samples: vec![1, 2, 3],
+            position: 0,
+        };
+        let frame = source.next_frame(4);
+        let samples: Vec<i16> = frame
+            .chunks_exact(2)
+            .map(|c| i16::from_le_bytes([c[0], c[1]]))
+            .collect();
+        assert_eq!(samples, vec![1, 2, 3, 1]);
+        assert_eq!(source.next_frame(1), 2i16.to_le_bytes().to_vec());
+
+        let synthetic = FixtureSource::synthetic();
+        assert_eq!(synthetic.samples.len(), SAMPLE_RATE * 2);
+        assert!(synthetic.samples[SAMPLE_RATE..].iter().all(|&s| s == 0));
+    }
+
+    #[test]
+    fn test_parse_process_usage() {
+        let status = "Name:\tmeeting\nVmPeak:\t  9000 kB\nVmRSS:\t  2048 kB\n";
+        assert_eq!(parse_vm_rss(status), Some(2048 * 1024));
+        assert_eq!(parse_vm_rss("Name:\tx\n"), None);
+
+        let stat = "1234 (tokio worker) S 1 1234 1234 0 -1 4194560 100 0 0 0 250 50 0 0 20 0";
+        assert_eq!(parse_proc_stat_cpu_time(stat), Some(Duration::from_secs(3)));
+
+        let usage = parse_ps_usage("  51200   1:02.50\n").unwrap();
+        assert_eq!(usage.rss_bytes, 51200 * 1024);
+        assert_eq!(usage.cpu_time, Duration::from_millis(62_500));
+        assert!(parse_ps_usage("").is_none());
+    }
+
+    #[test]
+    fn test_check_invariants() {
+        let config = SoakConfig::default();
+        let mb = 1024 * 1024;
+        let window = |rss_mb: u64, finals: u64, p95: u64| SoakWindow {
+            rss_bytes: Some(rss_mb * mb),
+            finals,
+            final_p95_ms: Some(p95),
+            ..Default::default()
+        };
+
+        let healthy = vec![
+            window(200, 10, 1000),
+            window(250, 2, 10_000),
+            window(290, 10, 2500),
+        ];
+        assert!(check_invariants(&config, &healthy, 0).is_empty());
+
+        let leaking = vec![window(200, 10, 1000), window(400, 10, 5000)];
+        let violations = check_invariants(&config, &leaking, 1);
+        assert_eq!(violations.len(), 3);
+        assert!(violations[0].contains("span"));
+        assert!(violations[1].starts_with("App memory grew by 200.0 MB"));
+        assert!(violations[2].contains("1000 ms to 5000 ms"));
+
+        let rejected = vec![SoakWindow {
+            batches_rejected: 3,
+            ..Default::default()
+        }];
+        assert_eq!(check_invariants(&config, &rejected, 0).len(), 1);
+    }
+}
@@ -3,26 +3,67 @@
 // MVP1 - Audio Device Event Management
 // Task 10.4 Phase 2 - Device Reconnection Management
 
+use crate::agenda::AgendaTracker;
+use crate::app_health::{HealthReport, HealthSettings, SidecarStartup};
 use crate::audio_device_adapter::{AudioDeviceAdapter, AudioEventReceiver, AudioEventSender};
-use crate::audio_device_recorder::AudioDeviceRecorder;
+use crate::audio_device_recorder::AdapterFactory;
+use crate::audio_dump::DebugSettings;
+use crate::audio_format::AudioFormatSettings;
+use crate::consent::ConsentAnnouncementSettings;
+use crate::crash_recovery::RecoveredSession;
+use crate::device_aliases::DeviceAliasSettings;
+use crate::docs_budget::{BudgetAlert, DocsBudget, DocsBudgetSettings, DocsBudgetStatus};
+use crate::heartbeat::InterruptedRecording;
+use crate::http_api::HttpApiServer;
+use crate::ipc_flow_control::{FlowControlSnapshot, FlowController};
+use crate::ipc_protocol::{NegotiatedProtocol, ProtocolDrift};
+use crate::ipc_quarantine::{
+    IpcQuarantine, IpcQuarantineSettings, QuarantineSnapshot, QuarantineVerdict,
+};
+use crate::jobs::JobScheduler;
+use crate::keyword_alerts::KeywordAlertSettings;
+use crate::latency::{LatencySnapshot, LatencyTracker};
+use crate::maintenance::MaintenanceService;
+use crate::memory_sentinel::{
+    MemorySentinel, MemorySentinelSettings, MemorySnapshot, MemoryWarning,
+};
+use crate::mqtt::{MqttEvent, MqttPublisher};
+use crate::ops_webhooks::{OpsThrottle, OpsWebhookSettings};
+use crate::partial_granularity::PartialGranularitySettings;
+use crate::pipeline::Pipeline;
+use crate::playback::AudioPlayer;
 use crate::python_sidecar::PythonSidecarManager;
+use crate::questions::QuestionTracker;
+use crate::rate_limit::{OutboundRateLimiter, RateLimitSettings, RateLimitTargetSnapshot};
+use crate::reconnect_policy::ReconnectPolicySettings;
 use crate::reconnection_manager::ReconnectionManager;
+use crate::recording_session::RecordingSession;
+use crate::retention::{RetentionSettings, RetentionSummary};
+use crate::ring_buffer::AudioQueueSnapshot;
+use crate::routing::RoutingEngine;
+use crate::session_registry::{SessionRegistry, SessionRegistryError};
+use crate::settings_reload::SettingsSnapshot;
+use crate::sidecar_health::{HealthMonitor, SidecarStatus};
+use crate::stdin_writer::StdinWriter;
+use crate::storage::{AudioFormat, LocalStorageService};
+use crate::summary::RollingSummary;
+use crate::transcript_webhooks::TranscriptWebhookSettings;
+use crate::trash::TrashSettings;
 use crate::websocket::WebSocketServer;
+use crate::websocket_limits::WebSocketSettings;
+use crate::websocket_mdns::MdnsAdvertiser;
+use crate::websocket_port::PortDiscovery;
 use std::sync::{Arc, Mutex};
 use tokio::sync::broadcast;
-use tokio_util::sync::CancellationToken;
 
-/// Type alias for Python sidecar stdin handle
-pub type SidecarStdin = Arc<tokio::sync::Mutex<tokio::process::ChildStdin>>;
+/// Type alias for Python sidecar stdin handle (writer task owns ChildStdin)
+pub type SidecarStdin = Arc<StdinWriter>;
 /// Type alias for Python sidecar stdout handle
 pub type SidecarStdout =
     Arc<tokio::sync::Mutex<tokio::io::BufReader<tokio::process::ChildStdout>>>;
 
 /// Application state shared across Tauri commands
 pub struct AppState {
-    /// Recording state
-    pub is_recording: Mutex<bool>,
-
     /// Selected audio device ID (single device mode - backward compatible)
     /// Task 9.1 - STT-REQ-001.2 (user device selection)
     pub selected_device_id: Mutex<Option<String>>,
@@ -39,6 +80,22 @@ pub struct AppState {
     /// Initialized during Tauri setup, None before initialization
     pub websocket_server: Mutex<Option<Arc<tokio::sync::Mutex<WebSocketServer>>>>,
 
+    /// Port the WebSocket server listens on (see websocket_port)
+    /// Set once the server has started, None before (or in viewer mode)
+    pub websocket_port: Mutex<Option<PortDiscovery>>,
+
+    /// mDNS advertisement of the WebSocket server (see websocket_mdns)
+    /// Running while enabled in the settings, None otherwise
+    pub mdns: Mutex<Option<MdnsAdvertiser>>,
+
+    /// Read-only local HTTP API (see http_api)
+    /// Running while enabled in the settings, None otherwise
+    pub http_api: Mutex<Option<HttpApiServer>>,
+
+    /// MQTT publisher (see mqtt)
+    /// Running while enabled in the settings, None otherwise
+    pub mqtt: Mutex<Option<MqttPublisher>>,
+
     /// Python sidecar process manager
     /// Initialized during Tauri setup, None before initialization
     pub python_sidecar: Mutex<Option<Arc<tokio::sync::Mutex<PythonSidecarManager>>>>,
@@ -47,9 +104,9 @@ pub struct AppState {
     /// Initialized during Tauri setup, None before initialization
     pub audio_device: Mutex<Option<Arc<tokio::sync::Mutex<Box<dyn AudioDeviceAdapter>>>>>,
 
-    /// Audio device recorder facade (single/multi-input)
+    /// Creates the audio adapters of each session's recorder (single/multi-input)
     /// Initialized during Tauri setup, None before initialization
-    pub audio_recorder: Mutex<Option<Arc<tokio::sync::Mutex<AudioDeviceRecorder>>>>,
+    pub audio_recorder_factory: Mutex<Option<AdapterFactory>>,
 
     /// Audio device event sender for monitoring device health
     /// MVP1 - STT-REQ-004.9/10/11
@@ -64,61 +121,253 @@ pub struct AppState {
     /// Related: STT-REQ-007 (Event Stream Protocol)
     pub ipc_event_tx: Mutex<Option<broadcast::Sender<serde_json::Value>>>,
 
-    /// Current recording session identifier (UUID v4)
-    pub session_id: Mutex<Option<String>>,
+    /// Recording sessions keyed by session ID (tasks, writers, IPC state)
+    /// The UI works with the primary (most recently started) session
+    pub sessions: SessionRegistry,
+
+    /// Last closed session (metrics shown until the next session starts)
+    pub last_session: Mutex<Option<Arc<RecordingSession>>>,
 
     /// Reconnection manager for audio device recovery
     /// Task 10.4 Phase 2 - STT-REQ-004.11
     /// Using tokio::sync::Mutex to allow .await across lock (Send requirement)
     pub reconnection_manager: tokio::sync::Mutex<ReconnectionManager>,
 
-    /// Python sidecar stdin handle (extracted for concurrent access)
-    /// Allows audio sender task to write without blocking IPC reader
+    /// What happens after the recording device disconnects
+    /// Loaded from settings during Tauri setup
+    pub reconnect_policy_settings: Mutex<ReconnectPolicySettings>,
+
+    /// Python sidecar stdin writer (extracted for concurrent access)
+    /// Single writer task: control messages are written ahead of audio
     pub sidecar_stdin: Mutex<Option<SidecarStdin>>,
 
     /// Python sidecar stdout handle (extracted for concurrent access)
     /// Allows IPC reader task to read without blocking audio sender
     pub sidecar_stdout: Mutex<Option<SidecarStdout>>,
 
-    /// Cancellation token for recording tasks (IPC reader, audio sender)
-    /// Used to gracefully stop tasks when recording ends
-    pub recording_cancel_token: Mutex<Option<CancellationToken>>,
+    /// Sidecar stdout reader routing events to their sessions
+    /// Started with the first recording, None before (or once it ended)
+    pub ipc_reader: Mutex<Option<tokio::task::JoinHandle<()>>>,
+
+    /// Local storage service for session persistence
+    /// Initialized during Tauri setup (app_data_dir), None before initialization
+    /// Related requirement: STT-REQ-005.1
+    pub storage_service: Mutex<Option<LocalStorageService>>,
+
+    /// Workspace whose data directory storage and settings use
+    /// Selected during Tauri setup, changed by switch_workspace
+    pub active_workspace: Mutex<String>,
+
+    /// Read-only viewer mode (no sidecar or audio; see viewer_mode)
+    /// Decided during Tauri setup, fixed for the run
+    pub viewer_mode: Mutex<bool>,
+
+    /// Interrupted recording detected at startup from a stale heartbeat
+    pub interrupted_recording: Mutex<Option<InterruptedRecording>>,
+
+    /// Unfinalized sessions repaired by the startup scan
+    pub recovered_sessions: Mutex<Vec<RecoveredSession>>,
+
+    /// Agenda and its time-coded progress for the current meeting
+    pub agenda: Mutex<AgendaTracker>,
+
+    /// Questions detected in the active session and their apparent answers
+    pub question_tracker: Mutex<QuestionTracker>,
+
+    /// Rolling summary of the active session (None when live summary is disabled)
+    pub live_summary: Mutex<Option<RollingSummary>>,
+
+    /// Playback of a saved session (None when nothing is playing)
+    pub playback: Mutex<Option<AudioPlayer>>,
+
+    /// Recording consent announcement played / posted at recording start
+    /// Loaded from settings during Tauri setup
+    pub consent_announcement_settings: Mutex<ConsentAnnouncementSettings>,
+
+    /// Consent announcement being played (kept alive until the next one)
+    pub consent_announcement: Mutex<Option<AudioPlayer>>,
+
+    /// Friendly names of audio devices
+    /// Loaded from settings during Tauri setup
+    pub device_alias_settings: Mutex<DeviceAliasSettings>,
+
+    /// Keyword watchlists matched against final segments
+    /// Loaded from settings during Tauri setup
+    pub keyword_alert_settings: Mutex<KeywordAlertSettings>,
+
+    /// Google Docs character budget configuration
+    /// Loaded from settings during Tauri setup
+    pub docs_budget_settings: Mutex<DocsBudgetSettings>,
+
+    /// Characters handed to the extension in the current session
+    pub docs_budget: Mutex<DocsBudget>,
+
+    /// Compiled transcript routing rules (post-broadcast stage)
+    /// Loaded from settings during Tauri setup
+    pub routing_engine: Mutex<Arc<RoutingEngine>>,
+
+    /// Compiled post-processing pipeline (stage order for final segments)
+    /// Loaded from settings during Tauri setup
+    pub pipeline: Mutex<Arc<Pipeline>>,
+
+    /// Per-utterance latency checkpoints of the active (or last) session
+    /// Shared with the stdin writer's written hooks
+    pub latency: Arc<Mutex<LatencyTracker>>,
+
+    /// Byte accounting of long-lived buffers (active or last session)
+    /// Settings loaded during Tauri setup
+    pub memory_sentinel: Mutex<MemorySentinel>,
+
+    /// Retention of deleted sessions
+    /// Loaded from settings during Tauri setup
+    pub trash_settings: Mutex<TrashSettings>,
+
+    /// Automatic cleanup of old sessions
+    /// Loaded from settings during Tauri setup
+    pub retention_settings: Mutex<RetentionSettings>,
+
+    /// Outcome of the last retention run (since app start)
+    pub last_retention: Mutex<Option<RetentionSummary>>,
+
+    /// Debugging aids (sent audio dump)
+    /// Loaded from settings during Tauri setup
+    pub debug_settings: Mutex<DebugSettings>,
+
+    /// Malformed sidecar IPC lines of the active (or last) session
+    /// Settings loaded during Tauri setup
+    pub ipc_quarantine: Mutex<IpcQuarantine>,
+
+    /// Unknown IPC message/event types of the active (or last) session
+    pub ipc_drift: Mutex<ProtocolDrift>,
+
+    /// Sidecar health checks of the active (or last) session
+    pub sidecar_health: Mutex<HealthMonitor>,
+
+    /// How far the bootstrap got starting the sidecar
+    pub sidecar_startup: Mutex<SidecarStartup>,
+
+    /// Protocol version agreed with the sidecar at startup
+    pub sidecar_protocol: Mutex<Option<NegotiatedProtocol>>,
+
+    /// Watchdog settings (machine-wide)
+    /// Loaded from settings during Tauri setup
+    pub health_settings: Mutex<HealthSettings>,
+
+    /// Last watchdog report (None before the first evaluation)
+    pub health_report: Mutex<Option<HealthReport>>,
+
+    /// Settings files as last read (compared by `reload_settings`)
+    pub settings_snapshot: Mutex<SettingsSnapshot>,
+
+    /// Outgoing WebSocket message size limits
+    /// Loaded from settings during Tauri setup
+    pub websocket_settings: Mutex<WebSocketSettings>,
+
+    /// Webhooks for operational events (machine-wide)
+    /// Loaded from settings during Tauri setup
+    pub ops_webhook_settings: Mutex<OpsWebhookSettings>,
+
+    /// Cooldown of sent operational events
+    pub ops_throttle: Mutex<OpsThrottle>,
+
+    /// Outbound request rate limits (machine-wide)
+    /// Loaded from settings during Tauri setup
+    pub rate_limit_settings: Mutex<RateLimitSettings>,
+
+    /// Token buckets of outbound integration targets
+    /// Shared with the request tasks
+    pub outbound_limiter: Arc<Mutex<OutboundRateLimiter>>,
+
+    /// Webhooks for final segments and session ends (per workspace)
+    /// Loaded from settings with the workspace
+    pub transcript_webhook_settings: Mutex<TranscriptWebhookSettings>,
+
+    /// Default session audio format
+    /// Loaded from settings during Tauri setup
+    pub audio_format_settings: Mutex<AudioFormatSettings>,
+
+    /// Audio format requested for the next session (overrides the settings)
+    /// Set by start_recording; kept for sessions restarted after a reconnect
+    pub audio_format_override: Mutex<Option<AudioFormat>>,
+
+    /// Forwarding granularity of partial transcriptions
+    /// Loaded from settings during Tauri setup
+    pub partial_granularity_settings: Mutex<PartialGranularitySettings>,
+
+    /// Background builder of derived files for legacy sessions
+    pub maintenance: Arc<MaintenanceService>,
+
+    /// Scheduler for background jobs (concurrency limits, recording pause)
+    /// Limits loaded from settings during Tauri setup
+    pub jobs: Arc<JobScheduler>,
 }
 
 impl AppState {
     pub fn new() -> Self {
         Self {
-            is_recording: Mutex::new(false),
             selected_device_id: Mutex::new(None),
             selected_device_ids: Mutex::new(Vec::new()),
             multi_input_enabled: Mutex::new(false),
             websocket_server: Mutex::new(None),
+            websocket_port: Mutex::new(None),
+            mdns: Mutex::new(None),
+            http_api: Mutex::new(None),
+            mqtt: Mutex::new(None),
             python_sidecar: Mutex::new(None),
             audio_device: Mutex::new(None),
-            audio_recorder: Mutex::new(None),
+            audio_recorder_factory: Mutex::new(None),
             audio_event_tx: Mutex::new(None),
             audio_event_rx: Mutex::new(None),
             ipc_event_tx: Mutex::new(None),
-            session_id: Mutex::new(None),
+            sessions: SessionRegistry::default(),
+            last_session: Mutex::new(None),
             reconnection_manager: tokio::sync::Mutex::new(ReconnectionManager::new()),
+            reconnect_policy_settings: Mutex::new(ReconnectPolicySettings::default()),
             sidecar_stdin: Mutex::new(None),
             sidecar_stdout: Mutex::new(None),
-            recording_cancel_token: Mutex::new(None),
-        }
-    }
-
-    /// Create and store a new cancellation token for recording tasks
-    /// Returns a clone of the token for use by tasks
-    pub fn create_recording_cancel_token(&self) -> CancellationToken {
-        let token = CancellationToken::new();
-        *self.recording_cancel_token.lock().unwrap() = Some(token.clone());
-        token
-    }
-
-    /// Cancel the current recording tasks
-    pub fn cancel_recording_tasks(&self) {
-        if let Some(token) = self.recording_cancel_token.lock().unwrap().take() {
-            token.cancel();
+            ipc_reader: Mutex::new(None),
+            storage_service: Mutex::new(None),
+            active_workspace: Mutex::new(crate::workspaces::DEFAULT_WORKSPACE.to_string()),
+            viewer_mode: Mutex::new(false),
+            interrupted_recording: Mutex::new(None),
+            recovered_sessions: Mutex::new(Vec::new()),
+            agenda: Mutex::new(AgendaTracker::default()),
+            question_tracker: Mutex::new(QuestionTracker::default()),
+            live_summary: Mutex::new(None),
+            playback: Mutex::new(None),
+            consent_announcement_settings: Mutex::new(ConsentAnnouncementSettings::default()),
+            consent_announcement: Mutex::new(None),
+            device_alias_settings: Mutex::new(DeviceAliasSettings::default()),
+            keyword_alert_settings: Mutex::new(KeywordAlertSettings::default()),
+            docs_budget_settings: Mutex::new(DocsBudgetSettings::default()),
+            docs_budget: Mutex::new(DocsBudget::default()),
+            routing_engine: Mutex::new(Arc::new(RoutingEngine::default())),
+            pipeline: Mutex::new(Arc::new(Pipeline::default())),
+            latency: Arc::new(Mutex::new(LatencyTracker::default())),
+            memory_sentinel: Mutex::new(MemorySentinel::default()),
+            trash_settings: Mutex::new(TrashSettings::default()),
+            retention_settings: Mutex::new(RetentionSettings::default()),
+            last_retention: Mutex::new(None),
+            debug_settings: Mutex::new(DebugSettings::default()),
+            ipc_quarantine: Mutex::new(IpcQuarantine::default()),
+            ipc_drift: Mutex::new(ProtocolDrift::default()),
+            sidecar_health: Mutex::new(HealthMonitor::default()),
+            sidecar_startup: Mutex::new(SidecarStartup::default()),
+            sidecar_protocol: Mutex::new(None),
+            health_settings: Mutex::new(HealthSettings::default()),
+            health_report: Mutex::new(None),
+            settings_snapshot: Mutex::new(SettingsSnapshot::default()),
+            websocket_settings: Mutex::new(WebSocketSettings::default()),
+            ops_webhook_settings: Mutex::new(OpsWebhookSettings::default()),
+            ops_throttle: Mutex::new(OpsThrottle::default()),
+            rate_limit_settings: Mutex::new(RateLimitSettings::default()),
+            outbound_limiter: Arc::new(Mutex::new(OutboundRateLimiter::default())),
+            transcript_webhook_settings: Mutex::new(TranscriptWebhookSettings::default()),
+            audio_format_settings: Mutex::new(AudioFormatSettings::default()),
+            audio_format_override: Mutex::new(None),
+            partial_granularity_settings: Mutex::new(PartialGranularitySettings::default()),
+            maintenance: Arc::new(MaintenanceService::default()),
+            jobs: Arc::new(JobScheduler::default()),
         }
     }
 
@@ -138,12 +387,82 @@ impl AppState {
         self.sidecar_stdout.lock().unwrap().clone()
     }
 
+    /// Start the sidecar stdout reader unless it is running
+    /// Returns true if it was started
+    pub fn ensure_ipc_reader(&self, start: impl FnOnce() -> tokio::task::JoinHandle<()>) -> bool {
+        let mut reader = self.ipc_reader.lock().unwrap();
+        if reader.as_ref().is_some_and(|reader| !reader.is_finished()) {
+            return false;
+        }
+        *reader = Some(start());
+        true
+    }
+
+    /// Store the (restarted) sidecar stdout reader
+    pub fn set_ipc_reader(&self, reader: tokio::task::JoinHandle<()>) {
+        *self.ipc_reader.lock().unwrap() = Some(reader);
+    }
+
     /// Set WebSocket server after initialization
     pub fn set_websocket_server(&self, server: Arc<tokio::sync::Mutex<WebSocketServer>>) {
         let mut ws = self.websocket_server.lock().unwrap();
         *ws = Some(server);
     }
 
+    pub fn set_websocket_port(&self, discovery: PortDiscovery) {
+        *self.websocket_port.lock().unwrap() = Some(discovery);
+    }
+
+    pub fn get_websocket_port(&self) -> Option<PortDiscovery> {
+        self.websocket_port.lock().unwrap().clone()
+    }
+
+    pub fn set_mdns(&self, advertiser: MdnsAdvertiser) {
+        *self.mdns.lock().unwrap() = Some(advertiser);
+    }
+
+    /// Take the running mDNS advertisement (to withdraw it)
+    pub fn take_mdns(&self) -> Option<MdnsAdvertiser> {
+        self.mdns.lock().unwrap().take()
+    }
+
+    pub fn set_http_api(&self, server: HttpApiServer) {
+        *self.http_api.lock().unwrap() = Some(server);
+    }
+
+    /// Take the running HTTP API server (to stop it)
+    pub fn take_http_api(&self) -> Option<HttpApiServer> {
+        self.http_api.lock().unwrap().take()
+    }
+
+    /// Port of the running HTTP API server
+    pub fn http_api_port(&self) -> Option<u16> {
+        self.http_api
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|server| server.port())
+    }
+
+    pub fn set_mqtt(&self, publisher: MqttPublisher) {
+        *self.mqtt.lock().unwrap() = Some(publisher);
+    }
+
+    /// Take the running MQTT publisher (to stop it)
+    pub fn take_mqtt(&self) -> Option<MqttPublisher> {
+        self.mqtt.lock().unwrap().take()
+    }
+
+    /// Queue an event for the MQTT broker (no-op while disabled)
+    ///
+    /// Returns false if the publisher's queue is full.
+    pub fn publish_mqtt(&self, event: MqttEvent) -> bool {
+        match self.mqtt.lock().unwrap().as_ref() {
+            Some(publisher) => publisher.publish(event),
+            None => true,
+        }
+    }
+
     /// Set Python sidecar manager after initialization
     pub fn set_python_sidecar(&self, sidecar: Arc<tokio::sync::Mutex<PythonSidecarManager>>) {
         let mut py = self.python_sidecar.lock().unwrap();
@@ -156,10 +475,14 @@ impl AppState {
         *audio = Some(device);
     }
 
-    /// Set audio recorder after initialization
-    pub fn set_audio_recorder(&self, recorder: Arc<tokio::sync::Mutex<AudioDeviceRecorder>>) {
-        let mut audio = self.audio_recorder.lock().unwrap();
-        *audio = Some(recorder);
+    /// Set the audio adapter factory of session recorders after initialization
+    pub fn set_audio_recorder_factory(&self, factory: AdapterFactory) {
+        *self.audio_recorder_factory.lock().unwrap() = Some(factory);
+    }
+
+    /// Audio adapter factory for a new recorder
+    pub fn get_audio_recorder_factory(&self) -> Option<AdapterFactory> {
+        self.audio_recorder_factory.lock().unwrap().clone()
     }
 
     /// Set audio event channel after initialization
@@ -263,22 +586,544 @@ impl AppState {
         }
     }
 
-    /// Set active recording session identifier
-    pub fn set_session_id(&self, session: String) {
-        let mut guard = self.session_id.lock().unwrap();
-        *guard = Some(session);
+    /// Register a new recording session (becomes the primary session)
+    pub fn open_session(
+        &self,
+        session_id: &str,
+        session_uuid: &str,
+        started_at_ms: u64,
+    ) -> Result<Arc<RecordingSession>, SessionRegistryError> {
+        self.sessions.open(session_id, session_uuid, started_at_ms)
+    }
+
+    /// Get a registered recording session
+    pub fn get_session(&self, session_id: &str) -> Option<Arc<RecordingSession>> {
+        self.sessions.get(session_id)
+    }
+
+    /// Get the primary recording session
+    pub fn primary_session(&self) -> Option<Arc<RecordingSession>> {
+        self.sessions.primary()
+    }
+
+    /// Unregister a finalized session (cancels its remaining tasks)
+    ///
+    /// Its metrics stay readable until the next session starts.
+    pub fn close_session(&self, session_id: &str) {
+        if let Some(session) = self.sessions.close(session_id) {
+            *self.last_session.lock().unwrap() = Some(session);
+        }
+    }
+
+    /// Whether any session is recording
+    pub fn is_recording(&self) -> bool {
+        !self.sessions.is_empty()
     }
 
-    /// Get current session identifier
+    /// Registered session IDs in the order they were opened
+    pub fn session_ids(&self) -> Vec<String> {
+        self.sessions.session_ids()
+    }
+
+    /// Session recording from an input device
+    pub fn session_using_device(&self, device_id: &str) -> Option<Arc<RecordingSession>> {
+        self.sessions
+            .sessions()
+            .into_iter()
+            .find(|session| session.device_ids().iter().any(|id| id == device_id))
+    }
+
+    /// Session a sidecar event belongs to (see `SessionRegistry::event_session`)
+    pub fn event_session(&self, data: &serde_json::Value) -> Option<Arc<RecordingSession>> {
+        self.sessions.event_session(data)
+    }
+
+    /// Primary session, or the last closed one when nothing is recording
+    fn primary_or_last_session(&self) -> Option<Arc<RecordingSession>> {
+        self.primary_session()
+            .or_else(|| self.last_session.lock().unwrap().clone())
+    }
+
+    /// Get the primary session identifier
     pub fn get_session_id(&self) -> Option<String> {
-        let guard = self.session_id.lock().unwrap();
-        guard.clone()
+        self.primary_session()
+            .map(|session| session.session_id().to_string())
+    }
+
+    /// Get UUID of the primary session
+    pub fn get_session_uuid(&self) -> Option<String> {
+        self.primary_session()
+            .map(|session| session.session_uuid().to_string())
+    }
+
+    // ========================================================================
+    // Session Persistence (STT-REQ-005)
+    // ========================================================================
+
+    /// Set storage service after initialization
+    pub fn set_storage_service(&self, service: LocalStorageService) {
+        let mut storage = self.storage_service.lock().unwrap();
+        *storage = Some(service);
+    }
+
+    /// Get storage service
+    pub fn get_storage_service(&self) -> Option<LocalStorageService> {
+        self.storage_service.lock().unwrap().clone()
+    }
+
+    pub fn set_active_workspace(&self, workspace: String) {
+        *self.active_workspace.lock().unwrap() = workspace;
+    }
+
+    pub fn get_active_workspace(&self) -> String {
+        self.active_workspace.lock().unwrap().clone()
+    }
+
+    pub fn set_viewer_mode(&self, enabled: bool) {
+        *self.viewer_mode.lock().unwrap() = enabled;
+    }
+
+    pub fn is_viewer_mode(&self) -> bool {
+        *self.viewer_mode.lock().unwrap()
+    }
+
+    /// Milliseconds elapsed since the primary session started
+    /// Returns None when no session is active
+    pub fn session_elapsed_ms(&self) -> Option<u64> {
+        self.primary_session().map(|session| session.elapsed_ms())
+    }
+
+    /// Store interrupted recording detected at startup
+    pub fn set_interrupted_recording(&self, interrupted: InterruptedRecording) {
+        *self.interrupted_recording.lock().unwrap() = Some(interrupted);
+    }
+
+    /// Get interrupted recording detected at startup
+    pub fn get_interrupted_recording(&self) -> Option<InterruptedRecording> {
+        self.interrupted_recording.lock().unwrap().clone()
+    }
+
+    pub fn set_recovered_sessions(&self, sessions: Vec<RecoveredSession>) {
+        *self.recovered_sessions.lock().unwrap() = sessions;
+    }
+
+    pub fn get_recovered_sessions(&self) -> Vec<RecoveredSession> {
+        self.recovered_sessions.lock().unwrap().clone()
+    }
+
+    /// Replace keyword alert settings (after load/save)
+    pub fn set_keyword_alert_settings(&self, settings: KeywordAlertSettings) {
+        *self.keyword_alert_settings.lock().unwrap() = settings;
+    }
+
+    /// Get keyword alert settings
+    pub fn get_keyword_alert_settings(&self) -> KeywordAlertSettings {
+        self.keyword_alert_settings.lock().unwrap().clone()
+    }
+
+    /// Replace compiled routing rules
+    pub fn set_routing_engine(&self, engine: RoutingEngine) {
+        *self.routing_engine.lock().unwrap() = Arc::new(engine);
+    }
+
+    /// Get compiled routing rules
+    pub fn get_routing_engine(&self) -> Arc<RoutingEngine> {
+        self.routing_engine.lock().unwrap().clone()
+    }
+
+    /// Replace compiled post-processing pipeline
+    pub fn set_pipeline(&self, pipeline: Pipeline) {
+        *self.pipeline.lock().unwrap() = Arc::new(pipeline);
+    }
+
+    /// Get compiled post-processing pipeline
+    pub fn get_pipeline(&self) -> Arc<Pipeline> {
+        self.pipeline.lock().unwrap().clone()
+    }
+
+    /// Snapshot of the primary (or last) session's audio send queue metrics
+    /// (None before the first session)
+    pub fn audio_queue_snapshot(&self) -> Option<AudioQueueSnapshot> {
+        self.primary_or_last_session()
+            .map(|session| session.queue_metrics().snapshot())
+    }
+
+    /// Start latency tracking afresh (on recording start)
+    pub fn reset_latency(&self) {
+        *self.latency.lock().unwrap() = LatencyTracker::default();
+    }
+
+    /// Record latency checkpoints
+    pub fn record_latency(&self, record: impl FnOnce(&mut LatencyTracker)) {
+        record(&mut self.latency.lock().unwrap());
+    }
+
+    /// Per-stage latency histograms of the active (or last) session
+    pub fn latency_snapshot(&self) -> LatencySnapshot {
+        self.latency.lock().unwrap().snapshot()
     }
 
-    /// Clear current session identifier
-    pub fn clear_session_id(&self) {
-        let mut guard = self.session_id.lock().unwrap();
-        *guard = None;
+    pub fn set_memory_sentinel_settings(&self, settings: MemorySentinelSettings) {
+        self.memory_sentinel.lock().unwrap().set_settings(settings);
+    }
+
+    pub fn get_memory_sentinel_settings(&self) -> MemorySentinelSettings {
+        self.memory_sentinel.lock().unwrap().settings().clone()
+    }
+
+    /// Forget buffer samples of the previous session (on recording start)
+    pub fn reset_memory_sentinel(&self) {
+        self.memory_sentinel.lock().unwrap().reset();
+    }
+
+    /// Byte counts of the buffers owned directly by the state
+    ///
+    /// WebSocket client queues and mixer buffers sit behind async locks and
+    /// are sampled by the caller.
+    pub fn owned_buffer_sizes(&self) -> Vec<(&'static str, u64)> {
+        use crate::memory_sentinel::{
+            AGENDA, AUDIO_QUEUE, LATENCY_TRACKER, LIVE_SUMMARY, QUESTIONS,
+        };
+
+        let mut sizes = Vec::new();
+        if let Some(queue) = self.audio_queue_snapshot() {
+            sizes.push((AUDIO_QUEUE, queue.queued_bytes));
+        }
+        sizes.push((
+            QUESTIONS,
+            self.question_tracker.lock().unwrap().approx_bytes() as u64,
+        ));
+        if let Some(summary) = self.live_summary.lock().unwrap().as_ref() {
+            sizes.push((LIVE_SUMMARY, summary.approx_bytes() as u64));
+        }
+        sizes.push((AGENDA, self.agenda.lock().unwrap().approx_bytes() as u64));
+        sizes.push((
+            LATENCY_TRACKER,
+            self.latency.lock().unwrap().approx_bytes() as u64,
+        ));
+        sizes
+    }
+
+    /// Record a buffer sample; returns newly raised growth warnings
+    pub fn observe_memory(&self, sample: &[(&str, u64)], at_ms: u64) -> Vec<MemoryWarning> {
+        self.memory_sentinel.lock().unwrap().observe(sample, at_ms)
+    }
+
+    /// Per-structure buffer accounting of the active (or last) session
+    pub fn memory_snapshot(&self) -> MemorySnapshot {
+        self.memory_sentinel.lock().unwrap().snapshot()
+    }
+
+    pub fn set_trash_settings(&self, settings: TrashSettings) {
+        *self.trash_settings.lock().unwrap() = settings;
+    }
+
+    pub fn get_trash_settings(&self) -> TrashSettings {
+        self.trash_settings.lock().unwrap().clone()
+    }
+
+    pub fn set_retention_settings(&self, settings: RetentionSettings) {
+        *self.retention_settings.lock().unwrap() = settings;
+    }
+
+    pub fn get_retention_settings(&self) -> RetentionSettings {
+        self.retention_settings.lock().unwrap().clone()
+    }
+
+    pub fn set_last_retention(&self, summary: RetentionSummary) {
+        *self.last_retention.lock().unwrap() = Some(summary);
+    }
+
+    pub fn get_last_retention(&self) -> Option<RetentionSummary> {
+        self.last_retention.lock().unwrap().clone()
+    }
+
+    pub fn set_debug_settings(&self, settings: DebugSettings) {
+        crate::logger::set_min_level(settings.log_level);
+        *self.debug_settings.lock().unwrap() = settings;
+    }
+
+    pub fn get_debug_settings(&self) -> DebugSettings {
+        self.debug_settings.lock().unwrap().clone()
+    }
+
+    pub fn set_websocket_settings(&self, settings: WebSocketSettings) {
+        *self.websocket_settings.lock().unwrap() = settings;
+    }
+
+    pub fn get_websocket_settings(&self) -> WebSocketSettings {
+        self.websocket_settings.lock().unwrap().clone()
+    }
+
+    pub fn set_ops_webhook_settings(&self, settings: OpsWebhookSettings) {
+        *self.ops_webhook_settings.lock().unwrap() = settings;
+    }
+
+    pub fn get_ops_webhook_settings(&self) -> OpsWebhookSettings {
+        self.ops_webhook_settings.lock().unwrap().clone()
+    }
+
+    pub fn set_rate_limit_settings(&self, settings: RateLimitSettings) {
+        *self.rate_limit_settings.lock().unwrap() = settings;
+    }
+
+    pub fn get_rate_limit_settings(&self) -> RateLimitSettings {
+        self.rate_limit_settings.lock().unwrap().clone()
+    }
+
+    pub fn rate_limit_snapshot(&self) -> Vec<RateLimitTargetSnapshot> {
+        self.outbound_limiter
+            .lock()
+            .unwrap()
+            .snapshot(std::time::Instant::now())
+    }
+
+    pub fn set_transcript_webhook_settings(&self, settings: TranscriptWebhookSettings) {
+        *self.transcript_webhook_settings.lock().unwrap() = settings;
+    }
+
+    pub fn get_transcript_webhook_settings(&self) -> TranscriptWebhookSettings {
+        self.transcript_webhook_settings.lock().unwrap().clone()
+    }
+
+    pub fn set_audio_format_settings(&self, settings: AudioFormatSettings) {
+        *self.audio_format_settings.lock().unwrap() = settings;
+    }
+
+    pub fn get_audio_format_settings(&self) -> AudioFormatSettings {
+        self.audio_format_settings.lock().unwrap().clone()
+    }
+
+    pub fn set_audio_format_override(&self, format: Option<AudioFormat>) {
+        *self.audio_format_override.lock().unwrap() = format;
+    }
+
+    /// Audio format for a new session (per-session override, else settings)
+    pub fn session_audio_format(&self) -> AudioFormat {
+        self.audio_format_override
+            .lock()
+            .unwrap()
+            .unwrap_or_else(|| self.get_audio_format_settings().format)
+    }
+
+    pub fn set_partial_granularity_settings(&self, settings: PartialGranularitySettings) {
+        *self.partial_granularity_settings.lock().unwrap() = settings;
+    }
+
+    pub fn get_partial_granularity_settings(&self) -> PartialGranularitySettings {
+        self.partial_granularity_settings.lock().unwrap().clone()
+    }
+
+    pub fn set_reconnect_policy_settings(&self, settings: ReconnectPolicySettings) {
+        *self.reconnect_policy_settings.lock().unwrap() = settings;
+    }
+
+    pub fn get_reconnect_policy_settings(&self) -> ReconnectPolicySettings {
+        self.reconnect_policy_settings.lock().unwrap().clone()
+    }
+
+    pub fn set_consent_announcement_settings(&self, settings: ConsentAnnouncementSettings) {
+        *self.consent_announcement_settings.lock().unwrap() = settings;
+    }
+
+    pub fn get_consent_announcement_settings(&self) -> ConsentAnnouncementSettings {
+        self.consent_announcement_settings.lock().unwrap().clone()
+    }
+
+    pub fn set_device_alias_settings(&self, settings: DeviceAliasSettings) {
+        *self.device_alias_settings.lock().unwrap() = settings;
+    }
+
+    pub fn get_device_alias_settings(&self) -> DeviceAliasSettings {
+        self.device_alias_settings.lock().unwrap().clone()
+    }
+
+    /// Alias of an audio device, or its ID without one
+    pub fn device_display_name(&self, device_id: &str) -> String {
+        self.device_alias_settings
+            .lock()
+            .unwrap()
+            .display_name(device_id, device_id)
+            .to_string()
+    }
+
+    pub fn set_ipc_quarantine_settings(&self, settings: IpcQuarantineSettings) {
+        self.ipc_quarantine.lock().unwrap().set_settings(settings);
+    }
+
+    pub fn get_ipc_quarantine_settings(&self) -> IpcQuarantineSettings {
+        self.ipc_quarantine.lock().unwrap().settings().clone()
+    }
+
+    /// Forget quarantined IPC lines (on session start)
+    pub fn reset_ipc_quarantine(&self) {
+        self.ipc_quarantine.lock().unwrap().reset();
+    }
+
+    /// Quarantine a malformed IPC line
+    pub fn quarantine_ipc_line(&self, raw: &str, error: &str, at_ms: u64) -> QuarantineVerdict {
+        self.ipc_quarantine
+            .lock()
+            .unwrap()
+            .quarantine(raw, error, at_ms)
+    }
+
+    /// A valid IPC message was read
+    pub fn record_valid_ipc_line(&self) {
+        self.ipc_quarantine.lock().unwrap().record_valid();
+    }
+
+    pub fn ipc_quarantine_snapshot(&self) -> QuarantineSnapshot {
+        self.ipc_quarantine.lock().unwrap().snapshot()
+    }
+
+    /// Forget unknown IPC type counts (on session start)
+    pub fn reset_ipc_drift(&self) {
+        *self.ipc_drift.lock().unwrap() = ProtocolDrift::default();
+    }
+
+    /// Count a skipped unknown IPC message type; returns its count so far
+    pub fn record_unknown_ipc_message_type(&self, message_type: &str) -> u64 {
+        self.ipc_drift
+            .lock()
+            .unwrap()
+            .record_unknown_message_type(message_type)
+    }
+
+    /// Count a skipped unknown IPC event type; returns its count so far
+    pub fn record_unknown_ipc_event_type(&self, event_type: &str) -> u64 {
+        self.ipc_drift
+            .lock()
+            .unwrap()
+            .record_unknown_event_type(event_type)
+    }
+
+    pub fn ipc_drift_snapshot(&self) -> ProtocolDrift {
+        self.ipc_drift.lock().unwrap().clone()
+    }
+
+    /// Audio flow control of the primary (or last) session
+    pub fn ipc_flow_snapshot(&self) -> FlowControlSnapshot {
+        match self.primary_or_last_session() {
+            Some(session) => session.ipc_flow().lock().unwrap().snapshot(),
+            None => FlowController::default().snapshot(),
+        }
+    }
+
+    /// Start sidecar health checks afresh (on recording start)
+    pub fn reset_sidecar_health(&self) {
+        *self.sidecar_health.lock().unwrap() = HealthMonitor::default();
+    }
+
+    /// A health check ping with `id` was queued for the sidecar
+    pub fn record_sidecar_ping(&self, id: String) {
+        self.sidecar_health
+            .lock()
+            .unwrap()
+            .on_ping_sent(id, std::time::Instant::now());
+    }
+
+    /// Apply a sidecar `pong`; false when it answers no health check
+    pub fn record_sidecar_pong(&self, pong: &crate::ipc_protocol::Pong, now_ms: u64) -> bool {
+        self.sidecar_health
+            .lock()
+            .unwrap()
+            .on_pong(pong, std::time::Instant::now(), now_ms)
+    }
+
+    pub fn sidecar_status(&self) -> SidecarStatus {
+        let running = self.get_sidecar_stdin().is_some();
+        let mut status = self.sidecar_health.lock().unwrap().status(running);
+        status.protocol = self.get_sidecar_protocol();
+        status
+    }
+
+    pub fn set_sidecar_protocol(&self, protocol: NegotiatedProtocol) {
+        *self.sidecar_protocol.lock().unwrap() = Some(protocol);
+    }
+
+    pub fn get_sidecar_protocol(&self) -> Option<NegotiatedProtocol> {
+        self.sidecar_protocol.lock().unwrap().clone()
+    }
+
+    pub fn set_sidecar_startup(&self, startup: SidecarStartup) {
+        *self.sidecar_startup.lock().unwrap() = startup;
+    }
+
+    pub fn get_sidecar_startup(&self) -> SidecarStartup {
+        self.sidecar_startup.lock().unwrap().clone()
+    }
+
+    /// The started sidecar process has exited (false while its lock is busy)
+    pub fn sidecar_exited(&self) -> bool {
+        let Some(sidecar) = self.python_sidecar.lock().unwrap().clone() else {
+            return false;
+        };
+        sidecar
+            .try_lock()
+            .map(|mut sidecar| sidecar.has_exited())
+            .unwrap_or(false)
+    }
+
+    pub fn set_health_settings(&self, settings: HealthSettings) {
+        *self.health_settings.lock().unwrap() = settings;
+    }
+
+    pub fn get_health_settings(&self) -> HealthSettings {
+        self.health_settings.lock().unwrap().clone()
+    }
+
+    pub fn set_health_report(&self, report: HealthReport) {
+        *self.health_report.lock().unwrap() = Some(report);
+    }
+
+    pub fn get_health_report(&self) -> Option<HealthReport> {
+        self.health_report.lock().unwrap().clone()
+    }
+
+    pub fn set_settings_snapshot(&self, snapshot: SettingsSnapshot) {
+        *self.settings_snapshot.lock().unwrap() = snapshot;
+    }
+
+    pub fn get_settings_snapshot(&self) -> SettingsSnapshot {
+        self.settings_snapshot.lock().unwrap().clone()
+    }
+
+    /// Replace docs budget settings (after load/save)
+    pub fn set_docs_budget_settings(&self, settings: DocsBudgetSettings) {
+        *self.docs_budget_settings.lock().unwrap() = settings;
+    }
+
+    /// Get docs budget settings
+    pub fn get_docs_budget_settings(&self) -> DocsBudgetSettings {
+        self.docs_budget_settings.lock().unwrap().clone()
+    }
+
+    /// Start the character budget afresh (on recording start)
+    pub fn reset_docs_budget(&self) {
+        *self.docs_budget.lock().unwrap() = DocsBudget::default();
+    }
+
+    /// Count a final segment handed to the extension
+    pub fn record_docs_inserted(&self, text: &str) -> Option<BudgetAlert> {
+        let settings = self.get_docs_budget_settings();
+        self.docs_budget
+            .lock()
+            .unwrap()
+            .record(crate::docs_budget::inserted_characters(text), &settings)
+    }
+
+    /// The extension synced to `document_id`; true when it rolled over
+    pub fn docs_document_synced(&self, document_id: &str) -> bool {
+        self.docs_budget
+            .lock()
+            .unwrap()
+            .document_synced(document_id)
+    }
+
+    pub fn docs_budget_status(&self) -> DocsBudgetStatus {
+        let settings = self.get_docs_budget_settings();
+        self.docs_budget.lock().unwrap().status(&settings)
     }
 }
 
@@ -371,6 +1216,38 @@ mod tests {
         assert_eq!(ids[1], "loopback-1");
     }
 
+    #[test]
+    fn test_session_elapsed_requires_active_session() {
+        let state = AppState::new();
+        assert!(state.session_elapsed_ms().is_none());
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+        state.open_session("s1", "u1", now - 1_000).unwrap();
+        assert!(state.session_elapsed_ms().unwrap() >= 1_000);
+
+        state.close_session("s1");
+        assert!(state.session_elapsed_ms().is_none());
+    }
+
+    #[test]
+    fn test_ipc_event_sequence_is_per_session() {
+        let state = AppState::new();
+        let first = state.open_session("s1", "u1", 0).unwrap();
+        assert_eq!(first.last_ipc_event_seq(), 0);
+        assert_eq!(first.record_ipc_event(), 1);
+        assert_eq!(first.record_ipc_event(), 2);
+        assert_eq!(first.last_ipc_event_seq(), 2);
+
+        // A new session starts its own sequence and becomes primary
+        let second = state.open_session("s2", "u2", 0).unwrap();
+        assert_eq!(second.last_ipc_event_seq(), 0);
+        assert_eq!(state.get_session_id().as_deref(), Some("s2"));
+        assert_eq!(state.get_session("s1").unwrap().last_ipc_event_seq(), 2);
+    }
+
     #[test]
     fn test_backward_compatibility_single_device() {
         let state = AppState::new();
@@ -0,0 +1,707 @@
+//! Sidecar stdin writer
+//!
+//! A single task owns the Python sidecar's stdin and writes Line-Delimited
+//! JSON from two priority lanes:
+//!
+//! - **control**: `change_model`, `flush`, `cancel`, `ping` — always written
+//!   before the next audio batch, never stuck behind an audio backlog
+//! - **audio**: `process_audio_stream` batches — bounded, rejected when full
+//!
+//! With a binary audio channel (`sidecar_audio_channel`), an audio batch is
+//! a short JSON line on stdin followed by its raw PCM on the channel. If the
+//! channel breaks it is closed, and senders go back to JSON batches
+//! (`has_audio_channel`).
+//!
+//! A line that fails or times out may be half written, and anything after
+//! it would be read as part of it. So a failed stdin write ends the writer:
+//! stdin is closed (the sidecar exits on EOF) and `failed` resolves, for the
+//! owner to stop the session and restart the sidecar.
+//!
+//! No caller ever holds a lock across a slow or wedged write.
+
+use crate::ipc_protocol::{IpcMessage, PROTOCOL_VERSION};
+use crate::sidecar_audio_channel::AudioChannel;
+use serde::Serialize;
+use serde_json::json;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+/// Maximum time for a single line write before it is abandoned
+pub const WRITE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Queued audio batches (250ms each) before new batches are rejected
+pub const AUDIO_QUEUE_CAPACITY: usize = 8;
+
+/// Queued control messages before `send_control` waits
+pub const CONTROL_QUEUE_CAPACITY: usize = 32;
+
+/// Error returned when a line cannot be queued
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum StdinWriterError {
+    #[error("Audio queue is full (sidecar is not keeping up)")]
+    AudioQueueFull,
+
+    #[error("Stdin writer has stopped")]
+    Closed,
+
+    #[error("Failed to encode control message: {0}")]
+    Encode(String),
+
+    #[error("No binary audio channel to the sidecar")]
+    NoAudioChannel,
+}
+
+/// Writer lane
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lane {
+    Control,
+    Audio,
+}
+
+impl Lane {
+    fn as_str(self) -> &'static str {
+        match self {
+            Lane::Control => "control",
+            Lane::Audio => "audio",
+        }
+    }
+}
+
+/// Control message sent to the sidecar on the priority lane
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ControlMessage {
+    /// Switch the Whisper model (`approve_upgrade` request)
+    ChangeModel { target_model: String },
+    /// Flush buffered audio and emit pending results
+    Flush,
+    /// Cancel in-flight processing
+    Cancel,
+    /// Health check (answered with `pong`)
+    Ping,
+}
+
+impl ControlMessage {
+    /// Request method (or message type for `ping`)
+    pub fn method(&self) -> &'static str {
+        match self {
+            ControlMessage::ChangeModel { .. } => "approve_upgrade",
+            ControlMessage::Flush => "flush",
+            ControlMessage::Cancel => "cancel",
+            ControlMessage::Ping => "ping",
+        }
+    }
+
+    /// Encode as a single JSON line (without newline)
+    pub fn encode(&self, id: &str) -> serde_json::Result<String> {
+        match self {
+            // Handled as a top-level message type by the sidecar
+            ControlMessage::Ping => serde_json::to_string(&json!({
+                "type": "ping",
+                "id": id,
+                "version": PROTOCOL_VERSION
+            })),
+            _ => {
+                let params = match self {
+                    ControlMessage::ChangeModel { target_model } => {
+                        json!({ "target_model": target_model })
+                    }
+                    _ => json!({}),
+                };
+                serde_json::to_string(&IpcMessage::Request {
+                    id: id.to_string(),
+                    version: PROTOCOL_VERSION.to_string(),
+                    method: self.method().to_string(),
+                    params,
+                })
+            }
+        }
+    }
+}
+
+/// Counters of one lane
+#[derive(Debug, Default)]
+pub struct LaneCounters {
+    /// Lines written to stdin
+    pub written: AtomicU64,
+    /// Lines rejected because the lane was full
+    pub rejected: AtomicU64,
+    /// Highest queue depth observed
+    pub peak_depth: AtomicU64,
+}
+
+/// Writer counters (for logs and diagnostics)
+#[derive(Debug, Default)]
+pub struct StdinWriterStats {
+    pub control: LaneCounters,
+    pub audio: LaneCounters,
+    pub write_failures: AtomicU64,
+    pub write_timeouts: AtomicU64,
+}
+
+impl StdinWriterStats {
+    fn lane(&self, lane: Lane) -> &LaneCounters {
+        match lane {
+            Lane::Control => &self.control,
+            Lane::Audio => &self.audio,
+        }
+    }
+}
+
+/// Queue depth metrics of one lane
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct LaneMetrics {
+    /// Lines currently queued
+    pub depth: u64,
+    pub peak_depth: u64,
+    pub capacity: u64,
+    pub written: u64,
+    pub rejected: u64,
+}
+
+/// Queue depth metrics of both lanes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct IpcLaneMetrics {
+    pub control: LaneMetrics,
+    pub audio: LaneMetrics,
+    pub write_failures: u64,
+    pub write_timeouts: u64,
+}
+
+/// Callback run once an audio line has been fully written
+pub type WrittenHook = Box<dyn FnOnce() + Send>;
+
+/// Queued audio line with its optional completion hook
+struct AudioLine {
+    line: String,
+    /// PCM following the line on the audio channel
+    pcm: Option<Vec<u8>>,
+    on_written: Option<WrittenHook>,
+}
+
+/// Handle to the stdin writer task
+pub struct StdinWriter {
+    control_tx: mpsc::Sender<String>,
+    audio_tx: mpsc::Sender<AudioLine>,
+    /// Binary audio channel still usable
+    audio_channel_open: Arc<AtomicBool>,
+    /// Cancelled when a stdin write fails (the writer has stopped)
+    failed: CancellationToken,
+    stats: Arc<StdinWriterStats>,
+    handle: JoinHandle<()>,
+}
+
+impl StdinWriter {
+    /// Spawn the writer task; it owns `stdin` until every handle is dropped
+    pub fn spawn<W>(stdin: W) -> Self
+    where
+        W: AsyncWrite + Unpin + Send + 'static,
+    {
+        Self::spawn_with_timeout(stdin, None, WRITE_TIMEOUT)
+    }
+
+    /// Spawn the writer task with the sidecar's binary audio channel, if any
+    pub fn spawn_with_audio_channel<W>(stdin: W, audio_channel: Option<AudioChannel>) -> Self
+    where
+        W: AsyncWrite + Unpin + Send + 'static,
+    {
+        Self::spawn_with_timeout(stdin, audio_channel, WRITE_TIMEOUT)
+    }
+
+    fn spawn_with_timeout<W>(
+        mut stdin: W,
+        mut audio_channel: Option<AudioChannel>,
+        write_timeout: Duration,
+    ) -> Self
+    where
+        W: AsyncWrite + Unpin + Send + 'static,
+    {
+        let (control_tx, mut control_rx) = mpsc::channel::<String>(CONTROL_QUEUE_CAPACITY);
+        let (audio_tx, mut audio_rx) = mpsc::channel::<AudioLine>(AUDIO_QUEUE_CAPACITY);
+        let stats = Arc::new(StdinWriterStats::default());
+        let task_stats = Arc::clone(&stats);
+        let audio_channel_open = Arc::new(AtomicBool::new(audio_channel.is_some()));
+        let task_channel_open = Arc::clone(&audio_channel_open);
+        let failed = CancellationToken::new();
+        let task_failed = failed.clone();
+
+        let handle = tokio::spawn(async move {
+            loop {
+                // biased: drain the control lane before the next audio batch
+                let (line, lane, pcm, on_written) = tokio::select! {
+                    biased;
+                    Some(line) = control_rx.recv() => (line, Lane::Control, None, None),
+                    Some(audio) = audio_rx.recv() => {
+                        (audio.line, Lane::Audio, audio.pcm, audio.on_written)
+                    }
+                    else => break,
+                };
+
+                // The line goes first: the sidecar reads the PCM after it
+                let channel = audio_channel.as_mut().filter(|_| pcm.is_some());
+                let mut line_written = false;
+                let write = async {
+                    stdin.write_all(line.as_bytes()).await?;
+                    stdin.write_all(b"\n").await?;
+                    stdin.flush().await?;
+                    line_written = true;
+                    if let (Some(channel), Some(pcm)) = (channel, &pcm) {
+                        channel.write_all(pcm).await?;
+                        channel.flush().await?;
+                    }
+                    Ok::<_, std::io::Error>(())
+                };
+
+                let result = tokio::time::timeout(write_timeout, write).await;
+                let stdin_broken = !line_written && !matches!(result, Ok(Ok(())));
+                if pcm.is_some() && !matches!(result, Ok(Ok(()))) && audio_channel.is_some() {
+                    // The stream may be mid-batch: close it so the sidecar
+                    // sees EOF, and send JSON batches from now on
+                    audio_channel = None;
+                    task_channel_open.store(false, Ordering::Relaxed);
+                    log_warn!(
+                        "sidecar::stdin_writer",
+                        "audio_channel_closed",
+                        "falling back to JSON audio on stdin"
+                    );
+                }
+                match result {
+                    Ok(Ok(())) => {
+                        task_stats
+                            .lane(lane)
+                            .written
+                            .fetch_add(1, Ordering::Relaxed);
+                        if let Some(on_written) = on_written {
+                            on_written();
+                        }
+                    }
+                    Ok(Err(e)) => {
+                        task_stats.write_failures.fetch_add(1, Ordering::Relaxed);
+                        log_error_details!(
+                            "sidecar::stdin_writer",
+                            "write_failed",
+                            json!({
+                                "lane": lane.as_str(),
+                                "bytes": line.len(),
+                                "error": format!("{:?}", e)
+                            })
+                        );
+                    }
+                    Err(_timeout) => {
+                        task_stats.write_timeouts.fetch_add(1, Ordering::Relaxed);
+                        log_error_details!(
+                            "sidecar::stdin_writer",
+                            "write_timeout",
+                            json!({
+                                "lane": lane.as_str(),
+                                "bytes": line.len(),
+                                "timeout_secs": write_timeout.as_secs()
+                            })
+                        );
+                    }
+                }
+                if stdin_broken {
+                    // Possibly half a line on stdin: nothing after it can be
+                    // framed, so stop and close stdin
+                    log_error_details!(
+                        "sidecar::stdin_writer",
+                        "stdin_broken",
+                        json!({ "lane": lane.as_str() })
+                    );
+                    task_failed.cancel();
+                    break;
+                }
+            }
+            log_info!("sidecar::stdin_writer", "writer_task_ended");
+        });
+
+        Self {
+            control_tx,
+            audio_tx,
+            audio_channel_open,
+            failed,
+            stats,
+            handle,
+        }
+    }
+
+    /// Queue a raw control line (written ahead of any queued audio)
+    pub async fn send_control(&self, line: String) -> Result<(), StdinWriterError> {
+        self.control_tx
+            .send(line)
+            .await
+            .map_err(|_| StdinWriterError::Closed)?;
+        self.record_depth(Lane::Control);
+        Ok(())
+    }
+
+    /// Queue a control message on the priority lane
+    ///
+    /// Returns the generated message id (`ctl-sidecar-...`, see `message_id`)
+    /// for matching the response.
+    pub async fn send_control_message(
+        &self,
+        message: ControlMessage,
+    ) -> Result<String, StdinWriterError> {
+        let id = crate::message_id::next_id(crate::message_id::CONTROL, "sidecar");
+        let line = message
+            .encode(&id)
+            .map_err(|e| StdinWriterError::Encode(e.to_string()))?;
+        self.send_control(line).await?;
+        log_info_details!(
+            "sidecar::stdin_writer",
+            "control_queued",
+            json!({ "id": id, "method": message.method() })
+        );
+        Ok(id)
+    }
+
+    /// Queue an audio batch without waiting
+    ///
+    /// Rejects the batch when the sidecar is not keeping up, so the audio
+    /// sender never stalls behind a wedged write.
+    pub fn try_send_audio(&self, line: String) -> Result<(), StdinWriterError> {
+        self.queue_audio(AudioLine {
+            line,
+            pcm: None,
+            on_written: None,
+        })
+    }
+
+    /// Queue an audio batch, running `on_written` once it reaches stdin
+    ///
+    /// The hook is dropped without running if the write fails or times out.
+    pub fn try_send_audio_with_hook<F>(
+        &self,
+        line: String,
+        on_written: F,
+    ) -> Result<(), StdinWriterError>
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.queue_audio(AudioLine {
+            line,
+            pcm: None,
+            on_written: Some(Box::new(on_written)),
+        })
+    }
+
+    /// Whether audio batches can go over the binary audio channel
+    pub fn has_audio_channel(&self) -> bool {
+        self.audio_channel_open.load(Ordering::Relaxed)
+    }
+
+    /// Queue an audio batch as `line` (see
+    /// `ipc_protocol::encode_audio_channel_request`) plus `pcm` on the audio
+    /// channel, running `on_written` once both are written
+    pub fn try_send_audio_frame<F>(
+        &self,
+        line: String,
+        pcm: Vec<u8>,
+        on_written: F,
+    ) -> Result<(), StdinWriterError>
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        if !self.has_audio_channel() {
+            return Err(StdinWriterError::NoAudioChannel);
+        }
+        self.queue_audio(AudioLine {
+            line,
+            pcm: Some(pcm),
+            on_written: Some(Box::new(on_written)),
+        })
+    }
+
+    fn queue_audio(&self, audio: AudioLine) -> Result<(), StdinWriterError> {
+        self.audio_tx.try_send(audio).map_err(|e| match e {
+            mpsc::error::TrySendError::Full(_) => {
+                self.stats.audio.rejected.fetch_add(1, Ordering::Relaxed);
+                StdinWriterError::AudioQueueFull
+            }
+            mpsc::error::TrySendError::Closed(_) => StdinWriterError::Closed,
+        })?;
+        self.record_depth(Lane::Audio);
+        Ok(())
+    }
+
+    fn queue_usage(&self, lane: Lane) -> (usize, usize) {
+        match lane {
+            Lane::Control => (self.control_tx.max_capacity(), self.control_tx.capacity()),
+            Lane::Audio => (self.audio_tx.max_capacity(), self.audio_tx.capacity()),
+        }
+    }
+
+    /// Lines currently queued in a lane
+    pub fn depth(&self, lane: Lane) -> u64 {
+        let (max_capacity, capacity) = self.queue_usage(lane);
+        (max_capacity - capacity) as u64
+    }
+
+    fn record_depth(&self, lane: Lane) {
+        self.stats
+            .lane(lane)
+            .peak_depth
+            .fetch_max(self.depth(lane), Ordering::Relaxed);
+    }
+
+    pub fn stats(&self) -> &StdinWriterStats {
+        &self.stats
+    }
+
+    /// Audio batches rejected because the queue was full
+    pub fn audio_rejected(&self) -> u64 {
+        self.stats.audio.rejected.load(Ordering::Relaxed)
+    }
+
+    /// Per-lane queue depth metrics
+    pub fn lane_metrics(&self) -> IpcLaneMetrics {
+        let lane_metrics = |lane: Lane| {
+            let counters = self.stats.lane(lane);
+            LaneMetrics {
+                depth: self.depth(lane),
+                peak_depth: counters.peak_depth.load(Ordering::Relaxed),
+                capacity: self.queue_usage(lane).0 as u64,
+                written: counters.written.load(Ordering::Relaxed),
+                rejected: counters.rejected.load(Ordering::Relaxed),
+            }
+        };
+        IpcLaneMetrics {
+            control: lane_metrics(Lane::Control),
+            audio: lane_metrics(Lane::Audio),
+            write_failures: self.stats.write_failures.load(Ordering::Relaxed),
+            write_timeouts: self.stats.write_timeouts.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Whether the writer task is still running
+    pub fn is_running(&self) -> bool {
+        !self.handle.is_finished()
+    }
+
+    /// Resolves once a stdin write has failed and the writer stopped
+    pub async fn failed(&self) {
+        self.failed.cancelled().await
+    }
+
+    /// Whether a stdin write has failed (see `failed`)
+    pub fn has_failed(&self) -> bool {
+        self.failed.is_cancelled()
+    }
+}
+
+impl Drop for StdinWriter {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncBufReadExt, BufReader};
+
+    #[tokio::test]
+    async fn test_lines_are_written_in_order() {
+        let (client, server) = tokio::io::duplex(1024);
+        let writer = StdinWriter::spawn(client);
+
+        let (written_tx, written_rx) = tokio::sync::oneshot::channel();
+        writer
+            .try_send_audio_with_hook("a1".to_string(), move || {
+                let _ = written_tx.send(());
+            })
+            .unwrap();
+        writer.send_control("c1".to_string()).await.unwrap();
+
+        let mut lines = BufReader::new(server).lines();
+        let mut received = vec![
+            lines.next_line().await.unwrap().unwrap(),
+            lines.next_line().await.unwrap().unwrap(),
+        ];
+        received.sort();
+        assert_eq!(received, vec!["a1", "c1"]);
+        written_rx.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_audio_frames_use_channel_until_it_breaks() {
+        use tokio::io::AsyncReadExt;
+
+        let (stdin, stdin_reader) = tokio::io::duplex(1024);
+        let (channel, mut channel_reader) = tokio::io::duplex(1024);
+        let writer = StdinWriter::spawn_with_audio_channel(stdin, Some(Box::new(channel)));
+        assert!(writer.has_audio_channel());
+        let mut lines = BufReader::new(stdin_reader).lines();
+
+        let (written_tx, written_rx) = tokio::sync::oneshot::channel();
+        writer
+            .try_send_audio_frame("f1".to_string(), vec![1, 0, 2, 0], move || {
+                let _ = written_tx.send(());
+            })
+            .unwrap();
+        assert_eq!(lines.next_line().await.unwrap().unwrap(), "f1");
+        let mut pcm = [0u8; 4];
+        channel_reader.read_exact(&mut pcm).await.unwrap();
+        assert_eq!(pcm, [1, 0, 2, 0]);
+        written_rx.await.unwrap();
+
+        // Sidecar side gone: the channel is closed and JSON batches resume
+        drop(channel_reader);
+        writer
+            .try_send_audio_frame("f2".to_string(), vec![3, 0], || {})
+            .unwrap();
+        assert_eq!(lines.next_line().await.unwrap().unwrap(), "f2");
+        tokio::time::timeout(Duration::from_secs(1), async {
+            while writer.has_audio_channel() {
+                tokio::task::yield_now().await;
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(
+            writer.try_send_audio_frame("f3".to_string(), vec![], || {}),
+            Err(StdinWriterError::NoAudioChannel)
+        );
+        writer.try_send_audio("j1".to_string()).unwrap();
+        assert_eq!(lines.next_line().await.unwrap().unwrap(), "j1");
+    }
+
+    #[tokio::test]
+    async fn test_control_messages_skip_queued_audio() {
+        // Tiny pipe: the first audio write blocks until the reader drains it
+        let (client, server) = tokio::io::duplex(8);
+        let writer = StdinWriter::spawn(client);
+
+        writer
+            .try_send_audio("audio-0-long-line".to_string())
+            .unwrap();
+        tokio::task::yield_now().await;
+        writer.try_send_audio("audio-1".to_string()).unwrap();
+        writer.try_send_audio("audio-2".to_string()).unwrap();
+        writer.send_control("control".to_string()).await.unwrap();
+
+        let metrics = writer.lane_metrics();
+        assert_eq!(metrics.audio.depth, 2);
+        assert_eq!(metrics.control.depth, 1);
+        assert_eq!(metrics.audio.capacity, AUDIO_QUEUE_CAPACITY as u64);
+
+        let mut lines = BufReader::new(server).lines();
+        let mut received = Vec::new();
+        for _ in 0..4 {
+            received.push(lines.next_line().await.unwrap().unwrap());
+        }
+        assert_eq!(
+            received,
+            vec!["audio-0-long-line", "control", "audio-1", "audio-2"]
+        );
+
+        let metrics = writer.lane_metrics();
+        assert_eq!(metrics.audio.depth, 0);
+        assert_eq!(metrics.audio.peak_depth, 2);
+        assert_eq!(metrics.control.written, 1);
+    }
+
+    #[tokio::test]
+    async fn test_audio_rejected_when_queue_full() {
+        // Reader never drains: the writer wedges on the first line
+        let (client, _server) = tokio::io::duplex(1);
+        let writer = StdinWriter::spawn(client);
+
+        let mut rejected = 0;
+        for i in 0..AUDIO_QUEUE_CAPACITY + 4 {
+            tokio::task::yield_now().await;
+            if writer.try_send_audio(format!("audio-{}", i))
+                == Err(StdinWriterError::AudioQueueFull)
+            {
+                rejected += 1;
+            }
+        }
+        assert!(rejected > 0);
+        assert_eq!(writer.audio_rejected(), rejected);
+        assert_eq!(
+            writer.lane_metrics().audio.depth,
+            AUDIO_QUEUE_CAPACITY as u64
+        );
+    }
+
+    #[tokio::test]
+    async fn test_wedged_write_times_out() {
+        let (client, _server) = tokio::io::duplex(1);
+        let writer = StdinWriter::spawn_with_timeout(client, None, Duration::from_millis(20));
+
+        let (written_tx, mut written_rx) = tokio::sync::oneshot::channel();
+        writer
+            .try_send_audio_with_hook("stuck".to_string(), move || {
+                let _ = written_tx.send(());
+            })
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(writer.lane_metrics().write_timeouts, 1);
+        // The hook is dropped unrun
+        assert!(written_rx.try_recv().is_err());
+        // Stdin may hold half a line: the writer stops instead of writing on
+        assert!(writer.has_failed());
+        assert!(!writer.is_running());
+        assert_eq!(
+            writer.try_send_audio("next".to_string()),
+            Err(StdinWriterError::Closed)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_partial_control_write_stops_writer() {
+        use tokio::io::AsyncReadExt;
+
+        let (client, mut server) = tokio::io::duplex(4);
+        let writer = StdinWriter::spawn_with_timeout(client, None, Duration::from_millis(20));
+
+        writer
+            .send_control_message(ControlMessage::Flush)
+            .await
+            .unwrap();
+        tokio::time::timeout(Duration::from_secs(1), writer.failed())
+            .await
+            .unwrap();
+
+        // Only the first bytes of the line got through, then stdin closed
+        let mut received = Vec::new();
+        server.read_to_end(&mut received).await.unwrap();
+        assert_eq!(received.len(), 4);
+        assert_eq!(
+            writer.send_control("ping".to_string()).await,
+            Err(StdinWriterError::Closed)
+        );
+    }
+
+    #[test]
+    fn test_control_message_encoding() {
+        let change = ControlMessage::ChangeModel {
+            target_model: "small".to_string(),
+        }
+        .encode("ctl-0")
+        .unwrap();
+        let parsed: IpcMessage = serde_json::from_str(&change).unwrap();
+        match parsed {
+            IpcMessage::Request { method, params, .. } => {
+                assert_eq!(method, "approve_upgrade");
+                assert_eq!(params["target_model"], "small");
+            }
+            other => panic!("Expected request, got {:?}", other),
+        }
+
+        let ping: serde_json::Value =
+            serde_json::from_str(&ControlMessage::Ping.encode("ctl-1").unwrap()).unwrap();
+        assert_eq!(ping["type"], "ping");
+        assert_eq!(ping["id"], "ctl-1");
+
+        let flush: serde_json::Value =
+            serde_json::from_str(&ControlMessage::Flush.encode("ctl-2").unwrap()).unwrap();
+        assert_eq!(flush["method"], "flush");
+    }
+}
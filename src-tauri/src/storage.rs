@@ -8,9 +8,22 @@ use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use uuid::Uuid;
 
+use crate::encryption::{SessionCipher, SessionEncryption};
+use crate::furigana::{Furigana, RubySegment};
+use crate::loudness::SessionLevels;
+use crate::session_stats::SessionStatistics;
+
+/// session.json / transcription.jsonl のファイル名（暗号化時の関連データにも使用）
+pub(crate) const METADATA_FILENAME: &str = "session.json";
+pub(crate) const TRANSCRIPT_FILENAME: &str = "transcription.jsonl";
+
 #[derive(Clone)]
 pub struct LocalStorageService {
     app_data_dir: PathBuf,
+    /// 録音ルート（既定: [app_data_dir]/recordings、storage_root設定で変更可能）
+    recordings_dir: PathBuf,
+    /// 保存時暗号化（encryption設定、鍵はOSキーチェーン）
+    encryption: Option<SessionEncryption>,
 }
 
 /// セッションハンドル（RAII）
@@ -63,7 +76,51 @@ impl SessionHandle {
 
 impl LocalStorageService {
     pub fn new(app_data_dir: PathBuf) -> Self {
-        Self { app_data_dir }
+        let recordings_dir = app_data_dir.join("recordings");
+        Self {
+            app_data_dir,
+            recordings_dir,
+            encryption: None,
+        }
+    }
+
+    /// 録音ルートを指定して作成（外付けドライブ等）
+    /// 設定ファイル等は引き続きapp_data_dirに保存される
+    pub fn with_recordings_dir(app_data_dir: PathBuf, recordings_dir: PathBuf) -> Self {
+        Self {
+            app_data_dir,
+            recordings_dir,
+            encryption: None,
+        }
+    }
+
+    /// 保存時暗号化を設定（Noneで平文のみ読み書き）
+    pub fn with_encryption(mut self, encryption: Option<SessionEncryption>) -> Self {
+        self.encryption = encryption;
+        self
+    }
+
+    /// 保存時暗号化の設定
+    pub fn encryption(&self) -> Option<&SessionEncryption> {
+        self.encryption.as_ref()
+    }
+
+    /// 新規書き込み用の暗号（暗号化無効時はNone）
+    fn write_cipher(&self) -> Option<SessionCipher> {
+        self.encryption
+            .as_ref()
+            .and_then(|e| e.write_cipher())
+            .cloned()
+    }
+
+    /// アプリデータディレクトリ
+    pub fn app_data_dir(&self) -> &std::path::Path {
+        &self.app_data_dir
+    }
+
+    /// 録音ルートディレクトリ（セッションディレクトリの親）
+    pub fn recordings_dir(&self) -> &std::path::Path {
+        &self.recordings_dir
     }
 
     /// セッション開始（原子的操作）
@@ -110,7 +167,7 @@ impl LocalStorageService {
     }
 
     /// セッションディレクトリ作成
-    /// Path: [app_data_dir]/recordings/[session_id]/
+    /// Path: [recordings_dir]/[session_id]/
     /// Related requirement: STT-REQ-005.1, STT-REQ-005.8
     ///
     /// **重要**: ディスク容量チェックを実施
@@ -121,7 +178,7 @@ impl LocalStorageService {
         if disk_status == DiskSpaceStatus::Critical {
             anyhow::bail!(
                 "ディスク容量が不足しているため録音できません（残り500MB未満）: {}",
-                self.app_data_dir.display()
+                self.recordings_dir.display()
             );
         }
 
@@ -132,7 +189,7 @@ impl LocalStorageService {
 
     /// セッションディレクトリパス取得
     pub fn get_session_dir(&self, session_id: &str) -> PathBuf {
-        self.app_data_dir.join("recordings").join(session_id)
+        self.recordings_dir.join(session_id)
     }
 
     /// WAVファイルライター作成
@@ -147,7 +204,7 @@ impl LocalStorageService {
         if disk_status == DiskSpaceStatus::Critical {
             anyhow::bail!(
                 "ディスク容量が不足しているため録音できません（残り500MB未満）: {}",
-                self.app_data_dir.display()
+                self.recordings_dir.display()
             );
         }
 
@@ -156,6 +213,39 @@ impl LocalStorageService {
         AudioWriter::new(audio_path)
     }
 
+    /// 指定形式のセッション音声ライター作成
+    /// WAV以外はaudio.flac / audio.opusに書き込む（STT用PCMパスは変わらない）
+    pub fn create_session_audio_writer(
+        &self,
+        session_id: &str,
+        format: AudioFormat,
+    ) -> Result<SessionAudioWriter> {
+        let disk_status = self.check_disk_space()?;
+        if disk_status == DiskSpaceStatus::Critical {
+            anyhow::bail!(
+                "ディスク容量が不足しているため録音できません（残り500MB未満）: {}",
+                self.recordings_dir.display()
+            );
+        }
+
+        let audio_path = self.get_session_dir(session_id).join(format.file_name());
+        SessionAudioWriter::create(audio_path, format)
+    }
+
+    /// 送信音声ダンプ用WAVライター作成（デバッグ用）
+    /// Path: [recordings_dir]/[session_id]/debug/sent_audio.wav
+    /// サイドカーへ送信したバイト列をそのまま書き込む（audio_dump参照）
+    /// セッションディレクトリが未作成の場合はエラー
+    pub fn create_sent_audio_dump(&self, session_id: &str) -> Result<AudioWriter> {
+        let session_dir = self.get_session_dir(session_id);
+        if !session_dir.is_dir() {
+            anyhow::bail!("セッションが見つかりません: {}", session_id);
+        }
+        let debug_dir = session_dir.join("debug");
+        std::fs::create_dir_all(&debug_dir)?;
+        AudioWriter::new(debug_dir.join("sent_audio.wav"))
+    }
+
     /// 文字起こし結果ライター作成
     /// JSON Lines形式で追記書き込み
     /// Related requirement: STT-REQ-005.3, STT-REQ-005.8
@@ -168,34 +258,81 @@ impl LocalStorageService {
         if disk_status == DiskSpaceStatus::Critical {
             anyhow::bail!(
                 "ディスク容量が不足しているため録音できません（残り500MB未満）: {}",
-                self.app_data_dir.display()
+                self.recordings_dir.display()
             );
         }
 
         let session_dir = self.get_session_dir(session_id);
-        let transcript_path = session_dir.join("transcription.jsonl");
-        TranscriptWriter::new(transcript_path)
+        TranscriptWriter::new(session_dir, self.write_cipher())
     }
 
     /// セッションメタデータ保存
-    /// session.jsonファイルに保存
+    /// session.jsonファイルに保存（暗号化有効時は暗号化して保存）
     /// Related requirement: STT-REQ-005.4
     pub fn save_session_metadata(&self, metadata: &SessionMetadata) -> Result<()> {
         let session_dir = self.get_session_dir(&metadata.session_id);
-        let metadata_path = session_dir.join("session.json");
+        let metadata_path = session_dir.join(METADATA_FILENAME);
 
-        let json = serde_json::to_string_pretty(metadata)?;
+        let mut json = serde_json::to_string_pretty(metadata)?;
+        if let Some(cipher) = self.write_cipher() {
+            json = cipher.seal(&json, METADATA_FILENAME)?;
+        }
         write_file_owner_only(&metadata_path, json.as_bytes())?;
 
         Ok(())
     }
 
+    /// セッションのタイトルを変更（Noneまたは空文字でタイトルを消去）
+    /// session.jsonを書き換え、更新後のメタデータを返す
+    pub fn rename_session(&self, session_id: &str, title: Option<&str>) -> Result<SessionMetadata> {
+        let title = normalize_session_title(title)?;
+        self.update_session_metadata(session_id, |metadata| metadata.title = title)
+    }
+
+    /// セッションのタグを置き換え
+    /// session.jsonを書き換え、更新後のメタデータを返す
+    pub fn set_session_tags(&self, session_id: &str, tags: &[String]) -> Result<SessionMetadata> {
+        let tags = normalize_session_tags(tags)?;
+        self.update_session_metadata(session_id, |metadata| metadata.tags = tags)
+    }
+
+    /// セッション統計を保存
+    /// session.jsonを書き換え、更新後のメタデータを返す
+    pub fn set_session_stats(
+        &self,
+        session_id: &str,
+        stats: SessionStatistics,
+    ) -> Result<SessionMetadata> {
+        self.update_session_metadata(session_id, |metadata| metadata.stats = Some(stats))
+    }
+
+    /// session.jsonを読み込み、`update`を適用して保存
+    /// 録音中（session.json未作成）のセッションはエラー
+    fn update_session_metadata(
+        &self,
+        session_id: &str,
+        update: impl FnOnce(&mut SessionMetadata),
+    ) -> Result<SessionMetadata> {
+        crate::session_id::validate_session_id(session_id)?;
+        let metadata_path = self.get_session_dir(session_id).join(METADATA_FILENAME);
+        if !metadata_path.exists() {
+            anyhow::bail!(
+                "セッションが見つかりません（録音中は変更できません）: {}",
+                session_id
+            );
+        }
+        let mut metadata = read_metadata_file(&metadata_path, self.encryption())?;
+        update(&mut metadata);
+        self.save_session_metadata(&metadata)?;
+        Ok(metadata)
+    }
+
     /// セッション一覧取得
     /// recordings/ディレクトリ内の全セッションメタデータを読み込み、
     /// 日時降順でソートしたリストを返す
     /// Related requirement: STT-REQ-005.5
     pub fn list_sessions(&self) -> Result<Vec<SessionMetadata>> {
-        let recordings_dir = self.app_data_dir.join("recordings");
+        let recordings_dir = &self.recordings_dir;
 
         // recordingsディレクトリが存在しない場合は空リストを返す
         if !recordings_dir.exists() {
@@ -205,7 +342,7 @@ impl LocalStorageService {
         let mut sessions = Vec::new();
 
         // recordingsディレクトリ内の各セッションディレクトリを走査
-        for entry in std::fs::read_dir(&recordings_dir)? {
+        for entry in std::fs::read_dir(recordings_dir)? {
             let entry = entry?;
             let path = entry.path();
 
@@ -213,15 +350,13 @@ impl LocalStorageService {
                 continue;
             }
 
-            let metadata_path = path.join("session.json");
+            let metadata_path = path.join(METADATA_FILENAME);
             if !metadata_path.exists() {
                 continue;
             }
 
-            // session.json読み込み
-            let json = std::fs::read_to_string(&metadata_path)?;
-            let metadata: SessionMetadata = serde_json::from_str(&json)?;
-            sessions.push(metadata);
+            // session.json読み込み（暗号化されていれば復号）
+            sessions.push(read_metadata_file(&metadata_path, self.encryption())?);
         }
 
         // 日時降順ソート（start_timeの降順）
@@ -236,49 +371,515 @@ impl LocalStorageService {
     pub fn load_session(&self, session_id: &str) -> Result<LoadedSession> {
         let session_dir = self.get_session_dir(session_id);
 
-        // session.json読み込み
-        let metadata_path = session_dir.join("session.json");
-        let json = std::fs::read_to_string(&metadata_path)?;
-        let metadata: SessionMetadata = serde_json::from_str(&json)?;
+        // session.json読み込み（暗号化されていれば復号）
+        let metadata = read_metadata_file(&session_dir.join(METADATA_FILENAME), self.encryption())?;
+
+        // transcription.jsonl読み込み（分割パートは結合）
+        let transcripts = self.load_transcript(session_id)?;
+
+        // 音声ファイルパス（圧縮形式のセッションはaudio.flac / audio.opus）
+        let audio_path =
+            session_audio_path(&session_dir).unwrap_or_else(|| session_dir.join("audio.wav"));
+
+        Ok(LoadedSession {
+            metadata,
+            transcript_total: transcripts.len(),
+            transcripts,
+            audio_path,
+        })
+    }
+
+    /// セッション読み込み（文字起こしは先頭`limit`件のみ）
+    /// 長時間セッションでもメモリ上の文字起こしを抑え、続きは`load_transcript_page`で取得する
+    pub fn load_session_page(&self, session_id: &str, limit: usize) -> Result<LoadedSession> {
+        let session_dir = self.get_session_dir(session_id);
+        let metadata = read_metadata_file(&session_dir.join(METADATA_FILENAME), self.encryption())?;
+        let page = self.load_transcript_page(session_id, 0, limit)?;
+        let audio_path =
+            session_audio_path(&session_dir).unwrap_or_else(|| session_dir.join("audio.wav"));
+
+        Ok(LoadedSession {
+            metadata,
+            transcripts: page.events,
+            transcript_total: page.total,
+            audio_path,
+        })
+    }
+
+    /// セッションをMarkdownにエクスポート
+    /// session.jsonのメタデータと確定セグメントからtranscript.mdを生成する
+    /// `furigana`指定時は難読語に読み（<ruby>）を付ける
+    /// Returns: 出力ファイルパス
+    pub fn export_session_markdown(
+        &self,
+        session_id: &str,
+        furigana: Option<&Furigana>,
+    ) -> Result<PathBuf> {
+        crate::session_id::validate_session_id(session_id)?;
+        let loaded = self.load_session(session_id)?;
+        let markdown = render_session_markdown(&loaded.metadata, &loaded.transcripts, furigana);
+
+        let output_path = self
+            .get_session_dir(session_id)
+            .join(MARKDOWN_EXPORT_FILENAME);
+        write_file_owner_only(&output_path, markdown.as_bytes())?;
+        Ok(output_path)
+    }
+
+    /// セッションをプレーンテキストにエクスポート（transcript.txt）
+    /// `furigana`指定時は難読語の後に読みを括弧書きで付ける
+    /// Returns: 出力ファイルパス
+    pub fn export_session_text(
+        &self,
+        session_id: &str,
+        furigana: Option<&Furigana>,
+    ) -> Result<PathBuf> {
+        crate::session_id::validate_session_id(session_id)?;
+        let loaded = self.load_session(session_id)?;
+        let text = render_session_text(&loaded.metadata, &loaded.transcripts, furigana);
+
+        let output_path = self.get_session_dir(session_id).join(TEXT_EXPORT_FILENAME);
+        write_file_owner_only(&output_path, text.as_bytes())?;
+        Ok(output_path)
+    }
+
+    /// セッションをDOCXにエクスポート（transcript.docx）
+    /// `furigana`指定時は難読語にルビを付ける
+    /// Returns: 出力ファイルパス
+    pub fn export_session_docx(
+        &self,
+        session_id: &str,
+        furigana: Option<&Furigana>,
+    ) -> Result<PathBuf> {
+        crate::session_id::validate_session_id(session_id)?;
+        let loaded = self.load_session(session_id)?;
+        let docx = render_session_docx(&loaded.metadata, &loaded.transcripts, furigana)?;
+
+        let output_path = self.get_session_dir(session_id).join(DOCX_EXPORT_FILENAME);
+        write_file_owner_only(&output_path, &docx)?;
+        Ok(output_path)
+    }
 
-        // transcription.jsonl読み込み
-        let transcript_path = session_dir.join("transcription.jsonl");
+    /// セッションをHTMLにエクスポート（transcript.html）
+    /// `furigana`指定時は難読語に読み（<ruby>）を付ける
+    /// Returns: 出力ファイルパス
+    pub fn export_session_html(
+        &self,
+        session_id: &str,
+        furigana: Option<&Furigana>,
+    ) -> Result<PathBuf> {
+        crate::session_id::validate_session_id(session_id)?;
+        let loaded = self.load_session(session_id)?;
+        let html = render_session_html(&loaded.metadata, &loaded.transcripts, furigana);
+
+        let output_path = self.get_session_dir(session_id).join(HTML_EXPORT_FILENAME);
+        write_file_owner_only(&output_path, html.as_bytes())?;
+        Ok(output_path)
+    }
+
+    /// 原文と訳文を対訳表でMarkdownにエクスポート
+    /// 訳文は`translation_version`の文字起こし（transcription.<version>.jsonl）から取得し、
+    /// transcript.bilingual.mdを生成する
+    /// Returns: 出力ファイルパス
+    pub fn export_session_bilingual_markdown(
+        &self,
+        session_id: &str,
+        translation_version: &str,
+        furigana: Option<&Furigana>,
+    ) -> Result<PathBuf> {
+        let (loaded, translations) = self.load_bilingual(session_id, translation_version)?;
+        let markdown = render_bilingual_markdown(
+            &loaded.metadata,
+            &loaded.transcripts,
+            &translations,
+            furigana,
+        );
+
+        let output_path = self
+            .get_session_dir(session_id)
+            .join(BILINGUAL_MARKDOWN_EXPORT_FILENAME);
+        write_file_owner_only(&output_path, markdown.as_bytes())?;
+        Ok(output_path)
+    }
+
+    /// 原文と訳文を対訳表でDOCXにエクスポート（transcript.bilingual.docx）
+    /// 表の構成は`export_session_bilingual_markdown`と同じ
+    /// Returns: 出力ファイルパス
+    pub fn export_session_bilingual_docx(
+        &self,
+        session_id: &str,
+        translation_version: &str,
+        furigana: Option<&Furigana>,
+    ) -> Result<PathBuf> {
+        let (loaded, translations) = self.load_bilingual(session_id, translation_version)?;
+        let docx = render_bilingual_docx(
+            &loaded.metadata,
+            &loaded.transcripts,
+            &translations,
+            furigana,
+        )?;
+
+        let output_path = self
+            .get_session_dir(session_id)
+            .join(BILINGUAL_DOCX_EXPORT_FILENAME);
+        write_file_owner_only(&output_path, &docx)?;
+        Ok(output_path)
+    }
+
+    /// 原文と訳文を対訳表でHTMLにエクスポート（transcript.bilingual.html）
+    /// 表の構成は`export_session_bilingual_markdown`と同じ
+    /// Returns: 出力ファイルパス
+    pub fn export_session_bilingual_html(
+        &self,
+        session_id: &str,
+        translation_version: &str,
+        furigana: Option<&Furigana>,
+    ) -> Result<PathBuf> {
+        let (loaded, translations) = self.load_bilingual(session_id, translation_version)?;
+        let html = render_bilingual_html(
+            &loaded.metadata,
+            &loaded.transcripts,
+            &translations,
+            furigana,
+        );
+
+        let output_path = self
+            .get_session_dir(session_id)
+            .join(BILINGUAL_HTML_EXPORT_FILENAME);
+        write_file_owner_only(&output_path, html.as_bytes())?;
+        Ok(output_path)
+    }
+
+    /// 対訳エクスポート用にセッションと訳文（`translation_version`の文字起こし）を読み込み
+    fn load_bilingual(
+        &self,
+        session_id: &str,
+        translation_version: &str,
+    ) -> Result<(LoadedSession, Vec<TranscriptionEvent>)> {
+        crate::session_id::validate_session_id(session_id)?;
+        if translation_version == "v1" {
+            anyhow::bail!("訳文には録音時以外の文字起こしバージョンを指定してください");
+        }
+        let loaded = self.load_session(session_id)?;
+        let translations = self.load_transcript_version(session_id, translation_version)?;
+        Ok((loaded, translations))
+    }
+
+    /// 文字起こし結果のみ読み込み
+    /// session.jsonが未作成（録音中）のセッションでも読み込み可能
+    /// 時間分割されたパート（transcript_chunks参照）は順に結合する
+    pub fn load_transcript(&self, session_id: &str) -> Result<Vec<TranscriptionEvent>> {
+        let parts = crate::transcript_chunks::list_parts(&self.get_session_dir(session_id))?;
         let mut transcripts = Vec::new();
+        for (_, path) in parts {
+            transcripts.extend(read_transcript_file(&path, self.encryption())?);
+        }
+        Ok(transcripts)
+    }
+
+    /// 文字起こしのページ読み込み（`offset`件目から最大`limit`件）
+    /// マニフェストで件数が分かる確定済みパートは、ページ範囲外なら読まずに件数だけ数える
+    /// （最後のパートは録音中・異常終了の可能性があるため常に読む）
+    pub fn load_transcript_page(
+        &self,
+        session_id: &str,
+        offset: usize,
+        limit: usize,
+    ) -> Result<crate::transcript_chunks::TranscriptPage> {
+        let session_dir = self.get_session_dir(session_id);
+        let parts = crate::transcript_chunks::list_parts(&session_dir)?;
+        // 壊れたマニフェストは無視（全パートを読む）
+        let manifest = crate::transcript_chunks::load_manifest(&session_dir)
+            .ok()
+            .flatten();
+
+        let mut total = 0;
+        let mut events = Vec::new();
+        for (i, (index, path)) in parts.iter().enumerate() {
+            let is_last = i + 1 == parts.len();
+            let known = manifest
+                .as_ref()
+                .filter(|_| !is_last)
+                .and_then(|manifest| manifest.events_in(*index));
+            if let Some(count) = known {
+                let count = count as usize;
+                if total + count <= offset || events.len() >= limit {
+                    total += count;
+                    continue;
+                }
+            }
+
+            let part_events = read_transcript_file(path, self.encryption())?;
+            let start = offset.saturating_sub(total).min(part_events.len());
+            let take = (limit - events.len()).min(part_events.len() - start);
+            total += part_events.len();
+            events.extend(part_events.into_iter().skip(start).take(take));
+        }
+
+        Ok(crate::transcript_chunks::TranscriptPage {
+            offset,
+            total,
+            events,
+        })
+    }
+
+    /// 指定バージョンの文字起こし読み込み
+    /// "v1" = transcription.jsonl（録音時）、それ以外 = transcription.<version>.jsonl（再文字起こし）
+    pub fn load_transcript_version(
+        &self,
+        session_id: &str,
+        version: &str,
+    ) -> Result<Vec<TranscriptionEvent>> {
+        crate::session_id::validate_session_id(session_id)?;
+        let transcript_path = self
+            .get_session_dir(session_id)
+            .join(transcript_file_name(version)?);
+
+        if version == "v1" && transcript_path.exists() {
+            return self.load_transcript(session_id);
+        }
+        if !transcript_path.exists() {
+            anyhow::bail!(
+                "文字起こしバージョンが見つかりません: {} ({})",
+                version,
+                transcript_path.display()
+            );
+        }
+        read_transcript_file(&transcript_path, self.encryption())
+    }
+
+    /// 再文字起こし結果を新しいバージョンとして保存（transcription.<version>.jsonl）
+    /// 録音時の"v1"は上書き不可。同名バージョンは一時ファイル経由で置き換える
+    /// 暗号化有効時は行ごとに暗号化する
+    pub fn save_transcript_version(
+        &self,
+        session_id: &str,
+        version: &str,
+        events: &[TranscriptionEvent],
+    ) -> Result<PathBuf> {
+        crate::session_id::validate_session_id(session_id)?;
+        if version == "v1" {
+            anyhow::bail!("録音時の文字起こし（v1）は上書きできません");
+        }
+        let file_name = transcript_file_name(version)?;
+        let session_dir = self.get_session_dir(session_id);
+        if !session_dir.is_dir() {
+            anyhow::bail!("セッションが見つかりません: {}", session_id);
+        }
+
+        let cipher = self.write_cipher();
+        let mut content = String::new();
+        for event in events {
+            let mut json_line = crate::transcript_schema::encode_line(event)?;
+            if let Some(cipher) = &cipher {
+                json_line = cipher.seal(&json_line, TRANSCRIPT_FILENAME)?;
+            }
+            content.push_str(&json_line);
+            content.push('\n');
+        }
+        let path = session_dir.join(&file_name);
+        let temp_path = session_dir.join(format!("{}.tmp", file_name));
+        write_file_owner_only(&temp_path, content.as_bytes())?;
+        std::fs::rename(&temp_path, &path)?;
+        Ok(path)
+    }
+
+    /// 文字起こしファイルを現行スキーマに移行（transcript_schema参照）
+    /// 全バージョン（transcription.jsonl / transcription.<version>.jsonl）が対象。
+    /// 旧スキーマの行を含むファイルのみ、`<ファイル名>.bak`へ退避してから置き換える。
+    /// 暗号化された行は暗号化したまま書き戻す
+    pub fn migrate_session_format(&self, session_id: &str) -> Result<SessionFormatMigration> {
+        crate::session_id::validate_session_id(session_id)?;
+        let session_dir = self.get_session_dir(session_id);
+        if !session_dir.is_dir() {
+            anyhow::bail!("セッションが見つかりません: {}", session_id);
+        }
 
-        if transcript_path.exists() {
-            let content = std::fs::read_to_string(&transcript_path)?;
+        let mut file_names: Vec<String> = std::fs::read_dir(&session_dir)?
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .filter(|name| {
+                name == TRANSCRIPT_FILENAME
+                    || (name.starts_with("transcription.") && name.ends_with(".jsonl"))
+            })
+            .collect();
+        file_names.sort();
+
+        let mut migration = SessionFormatMigration::default();
+        for file_name in file_names {
+            let path = session_dir.join(&file_name);
+            let content = std::fs::read_to_string(&path)?;
+            let mut upgraded_lines = 0;
+            let mut lines = Vec::new();
             for line in content.lines() {
                 if line.trim().is_empty() {
                     continue;
                 }
-                let event: TranscriptionEvent = serde_json::from_str(line)?;
-                transcripts.push(event);
+                let plain =
+                    crate::encryption::open_record(line, TRANSCRIPT_FILENAME, self.encryption())?;
+                let (event, version) = crate::transcript_schema::decode_line(&plain)
+                    .map_err(|e| anyhow::anyhow!("{}: {}", file_name, e))?;
+                if version == crate::transcript_schema::CURRENT_VERSION {
+                    lines.push(line.to_string());
+                    continue;
+                }
+                upgraded_lines += 1;
+                let json_line = crate::transcript_schema::encode_line(&event)?;
+                match self.encryption() {
+                    Some(encryption) if crate::encryption::is_sealed(line) => {
+                        lines.push(encryption.cipher.seal(&json_line, TRANSCRIPT_FILENAME)?)
+                    }
+                    _ => lines.push(json_line),
+                }
             }
+            if upgraded_lines == 0 {
+                continue;
+            }
+
+            let backup_name = format!("{}.bak", file_name);
+            std::fs::copy(&path, session_dir.join(&backup_name))?;
+            // 一時ファイル経由で置き換え（途中終了でも元ファイルが壊れない）
+            let temp_path = session_dir.join(format!("{}.tmp", file_name));
+            let mut migrated = lines.join("\n");
+            migrated.push('\n');
+            write_file_owner_only(&temp_path, migrated.as_bytes())?;
+            std::fs::rename(&temp_path, &path)?;
+
+            migration.migrated_files.push(file_name);
+            migration.backup_files.push(backup_name);
+            migration.upgraded_lines += upgraded_lines;
         }
+        Ok(migration)
+    }
 
-        // audio.wavパス
-        let audio_path = session_dir.join("audio.wav");
+    /// ゴミ箱ディレクトリパス取得
+    /// Path: [recordings_dir]/.trash/
+    /// セッションIDは'.'で始まらないため、セッションディレクトリと衝突しない
+    pub fn get_trash_dir(&self) -> PathBuf {
+        self.recordings_dir.join(TRASH_DIR_NAME)
+    }
 
-        Ok(LoadedSession {
-            metadata,
-            transcripts,
-            audio_path,
-        })
+    /// セッションをゴミ箱へ移動（削除の取り消しが可能）
+    /// セッションディレクトリごと移動し、削除日時をtrash.jsonに記録する
+    pub fn trash_session(&self, session_id: &str, deleted_at_ms: u64) -> Result<TrashedSession> {
+        crate::session_id::validate_session_id(session_id)?;
+
+        let session_dir = self.get_session_dir(session_id);
+        if !session_dir.is_dir() {
+            anyhow::bail!("セッションが見つかりません: {}", session_id);
+        }
+        let trashed_dir = self.get_trash_dir().join(session_id);
+        if trashed_dir.exists() {
+            anyhow::bail!("同じIDのセッションが既にゴミ箱にあります: {}", session_id);
+        }
+
+        std::fs::create_dir_all(self.get_trash_dir())?;
+        std::fs::rename(&session_dir, &trashed_dir)?;
+
+        let trashed = TrashedSession {
+            session_id: session_id.to_string(),
+            deleted_at_ms,
+            metadata: read_session_metadata(&trashed_dir, self.encryption()),
+        };
+        let entry = serde_json::json!({
+            "session_id": session_id,
+            "deleted_at_ms": deleted_at_ms
+        });
+        write_file_owner_only(
+            &trashed_dir.join(TRASH_ENTRY_FILE),
+            serde_json::to_string_pretty(&entry)?.as_bytes(),
+        )?;
+        Ok(trashed)
+    }
+
+    /// ゴミ箱からセッションを復元
+    /// 同じIDのセッションが既に存在する場合はエラー
+    pub fn restore_session(&self, session_id: &str) -> Result<()> {
+        crate::session_id::validate_session_id(session_id)?;
+
+        let trashed_dir = self.get_trash_dir().join(session_id);
+        if !trashed_dir.is_dir() {
+            anyhow::bail!("ゴミ箱にセッションが見つかりません: {}", session_id);
+        }
+        let session_dir = self.get_session_dir(session_id);
+        if session_dir.exists() {
+            anyhow::bail!("同じIDのセッションが既に存在します: {}", session_id);
+        }
+
+        std::fs::rename(&trashed_dir, &session_dir)?;
+        let entry_path = session_dir.join(TRASH_ENTRY_FILE);
+        if entry_path.exists() {
+            std::fs::remove_file(entry_path)?;
+        }
+        Ok(())
+    }
+
+    /// ゴミ箱内のセッション一覧取得（削除日時降順）
+    /// trash.jsonが読めないエントリは削除日時0として扱う（次回の期限切れ削除で消える）
+    pub fn list_trashed_sessions(&self) -> Result<Vec<TrashedSession>> {
+        let trash_dir = self.get_trash_dir();
+        if !trash_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut trashed = Vec::new();
+        for entry in std::fs::read_dir(&trash_dir)? {
+            let path = entry?.path();
+            if !path.is_dir() {
+                continue;
+            }
+            let Some(session_id) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            let deleted_at_ms = std::fs::read_to_string(path.join(TRASH_ENTRY_FILE))
+                .ok()
+                .and_then(|json| serde_json::from_str::<serde_json::Value>(&json).ok())
+                .and_then(|entry| entry["deleted_at_ms"].as_u64())
+                .unwrap_or(0);
+            trashed.push(TrashedSession {
+                session_id: session_id.to_string(),
+                deleted_at_ms,
+                metadata: read_session_metadata(&path, self.encryption()),
+            });
+        }
+
+        trashed.sort_by_key(|t| std::cmp::Reverse(t.deleted_at_ms));
+        Ok(trashed)
+    }
+
+    /// 保持期間を過ぎたゴミ箱内セッションを完全削除
+    /// Returns: 削除したセッションID
+    pub fn purge_trash(&self, retention_ms: u64, now_ms: u64) -> Result<Vec<String>> {
+        let mut purged = Vec::new();
+        for trashed in self.list_trashed_sessions()? {
+            if trashed.deleted_at_ms.saturating_add(retention_ms) > now_ms {
+                continue;
+            }
+            std::fs::remove_dir_all(self.get_trash_dir().join(&trashed.session_id))?;
+            purged.push(trashed.session_id);
+        }
+        Ok(purged)
     }
 
     /// ディスク容量チェック
     /// Related requirement: STT-REQ-005.7, STT-REQ-005.8
     ///
-    /// app_data_dirが配置されているファイルシステムの空き容量を確認
+    /// recordings_dirが配置されているファイルシステムの空き容量を確認
+    /// （未作成の場合は存在する最も近い親ディレクトリで判定）
     /// - 1GB以上: DiskSpaceStatus::Sufficient
     /// - 500MB以上1GB未満: DiskSpaceStatus::Warning（警告ログ・通知）
     /// - 500MB未満: DiskSpaceStatus::Critical（録音開始拒否）
     pub fn check_disk_space(&self) -> Result<DiskSpaceStatus> {
         use fs2::available_space;
 
-        // app_data_dirが配置されているファイルシステムの空き容量取得
+        // recordings_dirが配置されているファイルシステムの空き容量取得
         // 外付けHDDや別パーティションでも正確に取得可能
-        let free_bytes = available_space(&self.app_data_dir)?;
+        let probe = self
+            .recordings_dir
+            .ancestors()
+            .find(|dir| dir.exists())
+            .unwrap_or(&self.app_data_dir);
+        let free_bytes = available_space(probe)?;
 
         const ONE_GB: u64 = 1024 * 1024 * 1024;
         const FIVE_HUNDRED_MB: u64 = 500 * 1024 * 1024;
@@ -290,7 +891,7 @@ impl LocalStorageService {
             eprintln!(
                 "⚠️ ディスク容量警告: 残り容量 {} MB ({})",
                 free_bytes / (1024 * 1024),
-                self.app_data_dir.display()
+                self.recordings_dir.display()
             );
             DiskSpaceStatus::Warning
         } else {
@@ -298,7 +899,7 @@ impl LocalStorageService {
             eprintln!(
                 "❌ ディスク容量クリティカル: 残り容量 {} MB ({})",
                 free_bytes / (1024 * 1024),
-                self.app_data_dir.display()
+                self.recordings_dir.display()
             );
             DiskSpaceStatus::Critical
         };
@@ -307,11 +908,22 @@ impl LocalStorageService {
     }
 }
 
+/// WAVヘッダー長（RIFF 12 + JUNK 36 + fmt 24 + dataヘッダー 8バイト）
+/// JUNKチャンクはRF64昇格時にds64チャンクへ置き換える予約領域（EBU Tech 3306）
+pub const WAV_HEADER_LEN: u64 = 80;
+/// JUNKチャンク導入前の標準ヘッダー長
+const LEGACY_WAV_HEADER_LEN: u64 = 44;
+/// ds64チャンク本体のサイズ（RIFF/data/サンプル数 各u64 + テーブル長u32）
+const DS64_CHUNK_SIZE: u32 = 28;
+/// dataチャンクサイズ欄のオフセット
+const DATA_SIZE_OFFSET: u64 = WAV_HEADER_LEN - 4;
+
 /// WAVファイルへのストリーミング書き込み
 /// 16kHz, モノラル, 16bit PCM形式
+/// データが4GB（u32サイズ上限）を超えた場合はクローズ時にRF64へ自動昇格する
 pub struct AudioWriter {
     file: std::fs::File,
-    samples_written: u32,
+    samples_written: u64,
 }
 
 /// Create a file with owner-only permissions (Unix: 0o600, Windows: default ACLs)
@@ -320,7 +932,7 @@ pub struct AudioWriter {
 /// - Unix/Linux/macOS: Uses mode(0o600) for owner-only read/write
 /// - Windows: Uses default ACLs (TODO: implement SetNamedSecurityInfoW)
 #[cfg(unix)]
-fn create_file_owner_only(path: &std::path::Path) -> Result<std::fs::File> {
+pub(crate) fn create_file_owner_only(path: &std::path::Path) -> Result<std::fs::File> {
     use std::fs::OpenOptions;
     use std::os::unix::fs::OpenOptionsExt;
 
@@ -333,7 +945,7 @@ fn create_file_owner_only(path: &std::path::Path) -> Result<std::fs::File> {
 }
 
 #[cfg(not(unix))]
-fn create_file_owner_only(path: &std::path::Path) -> Result<std::fs::File> {
+pub(crate) fn create_file_owner_only(path: &std::path::Path) -> Result<std::fs::File> {
     // TODO(SEC-003): Windows file permissions
     // Current implementation uses default Windows ACLs (typically 644-equivalent).
     // For production, implement using winapi::um::aclapi::SetNamedSecurityInfoW
@@ -364,7 +976,7 @@ fn open_file_append_owner_only(path: &std::path::Path) -> Result<std::fs::File>
 
 /// Write data to a file with owner-only permissions
 #[cfg(unix)]
-fn write_file_owner_only(path: &std::path::Path, contents: &[u8]) -> Result<()> {
+pub(crate) fn write_file_owner_only(path: &std::path::Path, contents: &[u8]) -> Result<()> {
     use std::io::Write;
     let mut file = create_file_owner_only(path)?;
     file.write_all(contents)?;
@@ -372,7 +984,7 @@ fn write_file_owner_only(path: &std::path::Path, contents: &[u8]) -> Result<()>
 }
 
 #[cfg(not(unix))]
-fn write_file_owner_only(path: &std::path::Path, contents: &[u8]) -> Result<()> {
+pub(crate) fn write_file_owner_only(path: &std::path::Path, contents: &[u8]) -> Result<()> {
     // TODO(SEC-003): Windows file permissions - see create_file_owner_only()
     std::fs::write(path, contents)?;
     Ok(())
@@ -380,7 +992,7 @@ fn write_file_owner_only(path: &std::path::Path, contents: &[u8]) -> Result<()>
 
 impl AudioWriter {
     /// 新規WAVファイルライター作成
-    fn new(wav_path: PathBuf) -> Result<Self> {
+    pub(crate) fn new(wav_path: PathBuf) -> Result<Self> {
         let file = create_file_owner_only(&wav_path)?;
         let mut writer = Self {
             file,
@@ -390,7 +1002,7 @@ impl AudioWriter {
         Ok(writer)
     }
 
-    /// WAVヘッダー書き込み（44バイト）
+    /// WAVヘッダー書き込み（80バイト）
     /// 16kHz, モノラル, 16bit PCM
     fn write_wav_header(&mut self) -> Result<()> {
         use std::io::Write;
@@ -400,6 +1012,11 @@ impl AudioWriter {
         self.file.write_all(&0u32.to_le_bytes())?; // ファイルサイズ（後で更新）
         self.file.write_all(b"WAVE")?;
 
+        // JUNKチャンク（RF64昇格時のds64予約領域）
+        self.file.write_all(b"JUNK")?;
+        self.file.write_all(&DS64_CHUNK_SIZE.to_le_bytes())?;
+        self.file.write_all(&[0u8; DS64_CHUNK_SIZE as usize])?;
+
         // fmtチャンク
         self.file.write_all(b"fmt ")?;
         self.file.write_all(&16u32.to_le_bytes())?; // fmtチャンクサイズ
@@ -414,55 +1031,1028 @@ impl AudioWriter {
         self.file.write_all(b"data")?;
         self.file.write_all(&0u32.to_le_bytes())?; // データサイズ（後で更新）
 
-        Ok(())
+        Ok(())
+    }
+
+    /// 音声データ書き込み（i16サンプル配列）
+    /// Related requirement: STT-REQ-005.2
+    pub fn write_samples(&mut self, samples: &[i16]) -> Result<()> {
+        use std::io::Write;
+
+        for &sample in samples {
+            self.file.write_all(&sample.to_le_bytes())?;
+        }
+        self.samples_written += samples.len() as u64;
+        Ok(())
+    }
+
+    /// 音声データ書き込み（16bit LE PCMバイト列）
+    /// IPC送信バッチをそのまま書き込むための一括書き込み版
+    pub fn write_pcm_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        use std::io::Write;
+
+        // 奇数バイトは不完全サンプルのため切り捨て
+        let usable = bytes.len() & !1;
+        self.file.write_all(&bytes[..usable])?;
+        self.samples_written += (usable / 2) as u64;
+        Ok(())
+    }
+
+    /// 書き込み済みサンプル数
+    pub fn samples_written(&self) -> u64 {
+        self.samples_written
+    }
+
+    /// 書き込み済みデータをディスクに同期（ヘッダーはclose時に更新）
+    pub fn flush(&mut self) -> Result<()> {
+        self.file.sync_data()?;
+        Ok(())
+    }
+
+    /// WAVファイルを閉じる（ヘッダー更新）
+    /// Related requirement: STT-REQ-005.2
+    pub fn close(mut self) -> Result<()> {
+        self.finalize()
+    }
+
+    /// ヘッダー更新の内部実装
+    fn finalize(&mut self) -> Result<()> {
+        // 16bit = 2 bytes
+        write_wav_sizes(&mut self.file, self.samples_written * 2)?;
+        self.file.sync_all()?;
+        Ok(())
+    }
+}
+
+/// 80バイトヘッダーのサイズ欄を更新
+/// RIFFサイズがu32に収まらない場合はRF64（ds64チャンク）に昇格する
+fn write_wav_sizes(file: &mut std::fs::File, data_size: u64) -> Result<()> {
+    use std::io::{Seek, SeekFrom, Write};
+
+    let riff_size = data_size + WAV_HEADER_LEN - 8;
+    if riff_size <= u32::MAX as u64 {
+        file.seek(SeekFrom::Start(0))?;
+        file.write_all(b"RIFF")?;
+        file.write_all(&(riff_size as u32).to_le_bytes())?;
+        file.seek(SeekFrom::Start(12))?;
+        file.write_all(b"JUNK")?;
+        file.seek(SeekFrom::Start(DATA_SIZE_OFFSET))?;
+        file.write_all(&(data_size as u32).to_le_bytes())?;
+    } else {
+        // RF64: 32bitサイズ欄は0xFFFFFFFF、実サイズはds64チャンクに格納
+        file.seek(SeekFrom::Start(0))?;
+        file.write_all(b"RF64")?;
+        file.write_all(&u32::MAX.to_le_bytes())?;
+        file.seek(SeekFrom::Start(12))?;
+        file.write_all(b"ds64")?;
+        file.write_all(&DS64_CHUNK_SIZE.to_le_bytes())?;
+        file.write_all(&riff_size.to_le_bytes())?;
+        file.write_all(&data_size.to_le_bytes())?;
+        file.write_all(&(data_size / 2).to_le_bytes())?; // サンプル数（モノラル）
+        file.write_all(&0u32.to_le_bytes())?; // テーブル長
+        file.seek(SeekFrom::Start(DATA_SIZE_OFFSET))?;
+        file.write_all(&u32::MAX.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+/// WAV/RF64ヘッダーからdataチャンク本体のオフセットを求める
+/// 解析できないヘッダーは旧形式（44バイト）とみなす
+fn wav_data_offset(header: &[u8]) -> u64 {
+    let valid_magic = matches!(header.get(0..4), Some(b"RIFF") | Some(b"RF64"))
+        && header.get(8..12) == Some(b"WAVE".as_slice());
+    if !valid_magic {
+        return LEGACY_WAV_HEADER_LEN;
+    }
+
+    let mut offset = 12usize;
+    while let Some(chunk) = header.get(offset..offset + 8) {
+        if &chunk[0..4] == b"data" {
+            return offset as u64 + 8;
+        }
+        let size = u32::from_le_bytes(chunk[4..8].try_into().unwrap()) as usize;
+        // チャンクは偶数バイト境界に揃う
+        offset += 8 + size + (size & 1);
+    }
+    LEGACY_WAV_HEADER_LEN
+}
+
+/// ファイル先頭を読み、dataチャンク本体のオフセットを返す
+fn read_wav_data_offset(file: &mut std::fs::File) -> Result<u64> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let mut header = Vec::with_capacity(512);
+    file.seek(SeekFrom::Start(0))?;
+    (&mut *file).take(512).read_to_end(&mut header)?;
+    Ok(wav_data_offset(&header))
+}
+
+/// Drop実装: close()忘れ時の自動ヘッダー更新
+/// Related requirement: STT-REQ-005.2（異常終了時のデータ保護）
+impl Drop for AudioWriter {
+    fn drop(&mut self) {
+        // close()されていない場合でもヘッダー更新を試みる
+        let _ = self.finalize();
+    }
+}
+
+/// セッション音声の保存形式
+/// STTへ送るPCMはこの設定に関係なく16kHz/16bitのまま（保存ファイルのみ変わる）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AudioFormat {
+    /// 非圧縮WAV（audio.wav）
+    #[default]
+    Wav,
+    /// 可逆圧縮FLAC（audio.flac、概ね50〜60%に縮小）
+    Flac,
+    /// 非可逆Opus（audio.opus、Ogg Opus 24kbps）。`opus` feature有効時のみ
+    Opus,
+}
+
+impl AudioFormat {
+    pub const ALL: [AudioFormat; 3] = [AudioFormat::Wav, AudioFormat::Flac, AudioFormat::Opus];
+
+    /// セッションディレクトリ内の音声ファイル名
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            AudioFormat::Wav => "audio.wav",
+            AudioFormat::Flac => "audio.flac",
+            AudioFormat::Opus => "audio.opus",
+        }
+    }
+
+    /// このビルドで録音できるか（Opusはlibopusが必要）
+    pub fn is_supported(&self) -> bool {
+        match self {
+            AudioFormat::Wav | AudioFormat::Flac => true,
+            AudioFormat::Opus => cfg!(feature = "opus"),
+        }
+    }
+}
+
+/// セッション音声のライター（保存形式ごと）
+pub enum SessionAudioWriter {
+    Wav(AudioWriter),
+    Flac(crate::flac::FlacFileWriter),
+    #[cfg(feature = "opus")]
+    Opus(crate::opus::OpusFileWriter),
+}
+
+impl SessionAudioWriter {
+    /// 指定形式のライターを作成
+    pub fn create(path: PathBuf, format: AudioFormat) -> Result<Self> {
+        match format {
+            AudioFormat::Wav => Ok(Self::Wav(AudioWriter::new(path)?)),
+            AudioFormat::Flac => Ok(Self::Flac(crate::flac::FlacFileWriter::create(path)?)),
+            #[cfg(feature = "opus")]
+            AudioFormat::Opus => Ok(Self::Opus(crate::opus::OpusFileWriter::create(path)?)),
+            #[cfg(not(feature = "opus"))]
+            AudioFormat::Opus => anyhow::bail!("このビルドはOpus録音に対応していません"),
+        }
+    }
+
+    pub fn format(&self) -> AudioFormat {
+        match self {
+            Self::Wav(_) => AudioFormat::Wav,
+            Self::Flac(_) => AudioFormat::Flac,
+            #[cfg(feature = "opus")]
+            Self::Opus(_) => AudioFormat::Opus,
+        }
+    }
+
+    /// 音声データ書き込み（16bit LE PCMバイト列）
+    pub fn write_pcm_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        if let Self::Wav(writer) = self {
+            return writer.write_pcm_bytes(bytes);
+        }
+        // 奇数バイトは不完全サンプルのため切り捨て
+        let samples: Vec<i16> = bytes
+            .chunks_exact(2)
+            .map(|c| i16::from_le_bytes([c[0], c[1]]))
+            .collect();
+        self.write_samples(&samples)
+    }
+
+    /// 音声データ書き込み（i16サンプル配列）
+    pub fn write_samples(&mut self, samples: &[i16]) -> Result<()> {
+        match self {
+            Self::Wav(writer) => writer.write_samples(samples),
+            Self::Flac(writer) => writer.write_samples(samples),
+            #[cfg(feature = "opus")]
+            Self::Opus(writer) => writer.write_samples(samples),
+        }
+    }
+
+    /// 書き込み済みサンプル数
+    pub fn samples_written(&self) -> u64 {
+        match self {
+            Self::Wav(writer) => writer.samples_written(),
+            Self::Flac(writer) => writer.samples_written(),
+            #[cfg(feature = "opus")]
+            Self::Opus(writer) => writer.samples_written(),
+        }
+    }
+
+    /// 書き込み済みデータをディスクに同期（録音中の定期保存用）
+    pub fn flush(&mut self) -> Result<()> {
+        match self {
+            Self::Wav(writer) => writer.flush(),
+            Self::Flac(writer) => writer.flush(),
+            #[cfg(feature = "opus")]
+            Self::Opus(writer) => writer.flush(),
+        }
+    }
+
+    /// ファイルを閉じる（ヘッダー・総サンプル数を確定）
+    pub fn close(self) -> Result<()> {
+        match self {
+            Self::Wav(writer) => writer.close(),
+            Self::Flac(writer) => writer.close(),
+            #[cfg(feature = "opus")]
+            Self::Opus(writer) => writer.close(),
+        }
+    }
+}
+
+/// セッションの音声ファイル（audio.wav / audio.flac / audio.opus の順に探す）
+pub fn session_audio_path(session_dir: &std::path::Path) -> Option<PathBuf> {
+    AudioFormat::ALL
+        .iter()
+        .map(|format| session_dir.join(format.file_name()))
+        .find(|path| path.is_file())
+}
+
+/// セッション音声のサンプルを形式によらず順に読み出す（16kHz, モノラル）
+/// WAVは1秒分ずつ、FLAC/Opusはフレーム単位で`on_chunk`に渡す
+pub fn read_session_audio<F>(session_dir: &std::path::Path, mut on_chunk: F) -> Result<()>
+where
+    F: FnMut(&[i16]),
+{
+    let Some(path) = session_audio_path(session_dir) else {
+        anyhow::bail!("音声ファイルがありません: {}", session_dir.display());
+    };
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("flac") => {
+            let file = std::io::BufReader::new(std::fs::File::open(&path)?);
+            crate::flac::decode(file, |chunk| {
+                on_chunk(chunk);
+                Ok(())
+            })?;
+            Ok(())
+        }
+        #[cfg(feature = "opus")]
+        Some("opus") => {
+            crate::opus::decode(&path, |chunk| on_chunk(chunk))?;
+            Ok(())
+        }
+        #[cfg(not(feature = "opus"))]
+        Some("opus") => anyhow::bail!("このビルドはOpus音声の読み込みに対応していません"),
+        _ => read_wav_samples(&path, on_chunk),
+    }
+}
+
+/// WAVヘッダー修復（異常終了でヘッダーが未更新のファイル向け）
+/// ファイル長からRIFF/dataチャンクサイズを再計算して書き戻す
+///
+/// # Returns
+/// 修復後のサンプル数
+pub fn repair_wav_header(wav_path: &std::path::Path) -> Result<u64> {
+    use std::io::{Seek, SeekFrom, Write};
+
+    let mut file = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(wav_path)?;
+    let file_len = file.metadata()?.len();
+    let data_offset = read_wav_data_offset(&mut file)?;
+    if file_len < data_offset {
+        anyhow::bail!("WAVヘッダーが不完全です: {}", wav_path.display());
+    }
+
+    if data_offset == WAV_HEADER_LEN {
+        // 不完全な末尾サンプルは除外（4GB超はRF64に昇格）
+        let data_size = (file_len - WAV_HEADER_LEN) & !1;
+        write_wav_sizes(&mut file, data_size)?;
+        file.sync_all()?;
+        return Ok(data_size / 2);
+    }
+
+    // 旧形式（44バイトヘッダー）はRF64の予約領域がないためu32上限で打ち切る
+    let data_size = ((file_len - LEGACY_WAV_HEADER_LEN) & !1).min(u32::MAX as u64 - 36) as u32;
+    file.seek(SeekFrom::Start(4))?;
+    file.write_all(&(data_size + 36).to_le_bytes())?;
+    file.seek(SeekFrom::Start(40))?;
+    file.write_all(&data_size.to_le_bytes())?;
+    file.sync_all()?;
+
+    Ok(data_size as u64 / 2)
+}
+
+/// セッションWAV（16kHz, モノラル, 16bit PCM）のサンプルを順に読み出す
+/// 1秒分（16000サンプル）ずつ`on_chunk`に渡す。不完全な末尾サンプルは無視
+/// RIFF/RF64どちらのヘッダーにも対応（dataチャンクはファイル末尾まで読む）
+pub fn read_wav_samples<F>(wav_path: &std::path::Path, mut on_chunk: F) -> Result<()>
+where
+    F: FnMut(&[i16]),
+{
+    use std::io::{Read, Seek, SeekFrom};
+
+    let mut file = std::fs::File::open(wav_path)?;
+    let data_offset = read_wav_data_offset(&mut file)?;
+    if file.metadata()?.len() < data_offset {
+        anyhow::bail!("WAVヘッダーが不完全です: {}", wav_path.display());
+    }
+    file.seek(SeekFrom::Start(data_offset))?;
+
+    let mut buffer = Vec::with_capacity(32000);
+    loop {
+        buffer.clear();
+        // takeで読み取り位置をサンプル境界に揃える
+        let read = (&mut file).take(32000).read_to_end(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        let samples: Vec<i16> = buffer
+            .chunks_exact(2)
+            .map(|c| i16::from_le_bytes([c[0], c[1]]))
+            .collect();
+        on_chunk(&samples);
+    }
+    Ok(())
+}
+
+/// エポックミリ秒をISO 8601形式（UTC, ミリ秒精度）に変換
+/// 例: 2025-10-13T15:30:45.123Z
+pub fn format_iso8601_millis(epoch_ms: u64) -> String {
+    chrono::DateTime::from_timestamp_millis(epoch_ms as i64)
+        .unwrap_or_default()
+        .to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+}
+
+/// 文字起こしバージョンのファイル名
+/// バージョン名は英小文字・数字・`_`・`-`のみ（パス操作を防止）
+pub fn transcript_file_name(version: &str) -> Result<String> {
+    if version == "v1" {
+        return Ok("transcription.jsonl".to_string());
+    }
+    // transcription.partN.jsonlは録音時の文字起こしの分割パート
+    if crate::transcript_chunks::is_reserved_version(version) {
+        anyhow::bail!("予約された文字起こしバージョン名です: {:?}", version);
+    }
+    let valid = !version.is_empty()
+        && version.len() <= 32
+        && version
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-');
+    if !valid {
+        anyhow::bail!("不正な文字起こしバージョン名: {:?}", version);
+    }
+    Ok(format!("transcription.{}.jsonl", version))
+}
+
+/// Markdownエクスポートのファイル名（セッションディレクトリ内）
+pub const MARKDOWN_EXPORT_FILENAME: &str = "transcript.md";
+
+/// セッションのMarkdown文書を生成
+/// 見出し・メタデータ一覧・確定セグメント（[HH:MM:SS]、話者ラベルがあれば併記）
+/// 複数の文からなるセグメントは文ごとの箇条書きにする（`segmentation`参照）
+/// `furigana`指定時は本文の難読語に読みを付ける
+pub fn render_session_markdown(
+    metadata: &SessionMetadata,
+    events: &[TranscriptionEvent],
+    furigana: Option<&Furigana>,
+) -> String {
+    let mut md = String::new();
+    write_markdown_header(&mut md, metadata);
+
+    md.push_str("\n## 文字起こし\n");
+    let finals = final_segments(events);
+    if finals.is_empty() {
+        md.push_str("\n（確定した文字起こしはありません）\n");
+    }
+    for event in finals {
+        write_markdown_segment(&mut md, event, event.timestamp_ms, furigana);
+    }
+    md
+}
+
+/// 確定セグメント1件をMarkdownに追記（時刻は`timestamp_ms`で表示）
+fn write_markdown_segment(
+    md: &mut String,
+    event: &TranscriptionEvent,
+    timestamp_ms: u64,
+    furigana: Option<&Furigana>,
+) {
+    use std::fmt::Write as _;
+
+    let timestamp = format_hms(timestamp_ms);
+    let label = match event
+        .speaker
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+    {
+        Some(speaker) => format!("**[{}] {}:**", timestamp, escape_markdown(speaker)),
+        None => format!("**[{}]**", timestamp),
+    };
+    let sentences = crate::segmentation::split_sentences(&event.text);
+    if sentences.len() > 1 {
+        let _ = writeln!(md, "\n{}\n", label);
+        for sentence in sentences {
+            let _ = writeln!(md, "- {}", render_text(&sentence, furigana));
+        }
+    } else {
+        let _ = writeln!(
+            md,
+            "\n{} {}",
+            label,
+            render_text(event.text.trim(), furigana)
+        );
+    }
+}
+
+/// 無音カット済み音声に合わせたMarkdown文書を生成（`silence_trim`参照）
+/// 構成は`render_session_markdown`と同じで、時刻はカット後の時間軸に換算し、
+/// カット位置に`[silence 4:32 omitted]`を挿入する
+pub fn render_trimmed_markdown(
+    metadata: &SessionMetadata,
+    events: &[TranscriptionEvent],
+    omitted: &[crate::silence_trim::OmittedSpan],
+    furigana: Option<&Furigana>,
+) -> String {
+    use crate::silence_trim::{omitted_marker, trimmed_ms};
+    use std::fmt::Write as _;
+
+    let mut md = String::new();
+    write_markdown_header(&mut md, metadata);
+    if !omitted.is_empty() {
+        let total_ms: u64 = omitted.iter().map(|span| span.duration_ms()).sum();
+        let _ = writeln!(
+            md,
+            "- 無音カット: {}箇所（計{}）",
+            omitted.len(),
+            format_hms(total_ms)
+        );
+    }
+
+    md.push_str("\n## 文字起こし\n");
+    let finals = final_segments(events);
+    if finals.is_empty() {
+        md.push_str("\n（確定した文字起こしはありません）\n");
+    }
+    // カット区間内に確定したセグメントはマーカーの前（カット位置の時刻）に置く
+    let mut markers = omitted.iter().peekable();
+    for event in finals {
+        while let Some(span) = markers.next_if(|span| span.end_ms <= event.timestamp_ms) {
+            let _ = writeln!(md, "\n{}", omitted_marker(span));
+        }
+        write_markdown_segment(
+            &mut md,
+            event,
+            trimmed_ms(omitted, event.timestamp_ms),
+            furigana,
+        );
+    }
+    for span in markers {
+        let _ = writeln!(md, "\n{}", omitted_marker(span));
+    }
+    md
+}
+
+/// 対訳Markdownエクスポートのファイル名（セッションディレクトリ内）
+pub const BILINGUAL_MARKDOWN_EXPORT_FILENAME: &str = "transcript.bilingual.md";
+
+/// 対訳DOCXエクスポートのファイル名（セッションディレクトリ内）
+pub const BILINGUAL_DOCX_EXPORT_FILENAME: &str = "transcript.bilingual.docx";
+
+/// 対訳HTMLエクスポートのファイル名（セッションディレクトリ内）
+pub const BILINGUAL_HTML_EXPORT_FILENAME: &str = "transcript.bilingual.html";
+
+/// 対訳表の列見出し
+const BILINGUAL_COLUMNS: [&str; 3] = ["時刻", "原文", "訳文"];
+
+/// 対訳表の1行：原文の確定セグメントと対応する訳文
+struct BilingualRow<'a> {
+    event: &'a TranscriptionEvent,
+    translation: String,
+}
+
+/// 原文の確定セグメントごとに訳文を対応付ける（対訳エクスポート共通）
+/// 訳文セグメントは開始時刻が原文セグメントの区間
+/// （そのセグメントの開始から次のセグメントの開始まで）に入るものを対応付ける
+fn bilingual_rows<'a>(
+    events: &'a [TranscriptionEvent],
+    translations: &[TranscriptionEvent],
+) -> Vec<BilingualRow<'a>> {
+    let finals = final_segments(events);
+    let mut translated = final_segments(translations).into_iter().peekable();
+    // 最初のセグメントより前の訳文は最初の行に含める
+    finals
+        .iter()
+        .enumerate()
+        .map(|(i, event)| {
+            let next_start = finals.get(i + 1).map_or(u64::MAX, |e| e.timestamp_ms);
+            let mut texts = Vec::new();
+            while let Some(translation) = translated.next_if(|e| e.timestamp_ms < next_start) {
+                texts.push(translation.text.trim());
+            }
+            BilingualRow {
+                event,
+                translation: texts.join(" "),
+            }
+        })
+        .collect()
+}
+
+/// 対訳表の時刻列（[HH:MM:SS]、話者ラベルがあれば併記、話者は`escape`でエスケープ）
+fn bilingual_label(event: &TranscriptionEvent, escape: fn(&str) -> String) -> String {
+    let timestamp = format_hms(event.timestamp_ms);
+    match event
+        .speaker
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+    {
+        Some(speaker) => format!("[{}] {}", timestamp, escape(speaker)),
+        None => format!("[{}]", timestamp),
+    }
+}
+
+/// 原文と訳文を並べたMarkdown文書を生成
+/// メタデータは`render_session_markdown`と同じ。本文は確定セグメントごとの行を持つ表
+/// （時刻・話者 | 原文 | 訳文、`bilingual_rows`参照）で、複数の文は<br>で改行する
+pub fn render_bilingual_markdown(
+    metadata: &SessionMetadata,
+    events: &[TranscriptionEvent],
+    translations: &[TranscriptionEvent],
+    furigana: Option<&Furigana>,
+) -> String {
+    use std::fmt::Write as _;
+
+    let mut md = String::new();
+    write_markdown_header(&mut md, metadata);
+
+    md.push_str("\n## 文字起こし（対訳）\n");
+    let rows = bilingual_rows(events, translations);
+    if rows.is_empty() {
+        md.push_str("\n（確定した文字起こしはありません）\n");
+        return md;
+    }
+
+    let _ = writeln!(
+        md,
+        "\n| {} |\n| --- | --- | --- |",
+        BILINGUAL_COLUMNS.join(" | ")
+    );
+    for row in rows {
+        let _ = writeln!(
+            md,
+            "| {} | {} | {} |",
+            bilingual_label(row.event, escape_markdown),
+            render_table_cell(&row.event.text, furigana),
+            render_table_cell(&row.translation, None)
+        );
+    }
+    md
+}
+
+/// 原文と訳文を並べたDOCX文書を生成（表の構成は`render_bilingual_markdown`と同じ）
+/// 複数の文はセル内の段落に分ける
+pub fn render_bilingual_docx(
+    metadata: &SessionMetadata,
+    events: &[TranscriptionEvent],
+    translations: &[TranscriptionEvent],
+    furigana: Option<&Furigana>,
+) -> Result<Vec<u8>> {
+    use crate::docx::{Cell, DocxDocument, Run};
+
+    let mut doc = DocxDocument::new();
+    write_docx_header(&mut doc, metadata);
+
+    doc.heading(2, "文字起こし（対訳）");
+    let rows = bilingual_rows(events, translations);
+    if rows.is_empty() {
+        doc.paragraph(&[Run::plain("（確定した文字起こしはありません）")]);
+        return doc.to_bytes();
+    }
+
+    // Runはテキストを借用するため、ラベルと文の分割結果を先に用意する
+    let cells: Vec<(String, Vec<Vec<RubySegment>>, Vec<String>)> = rows
+        .iter()
+        .map(|row| {
+            (
+                bilingual_label(row.event, str::to_string),
+                table_sentences(&row.event.text)
+                    .iter()
+                    .map(|sentence| ruby_segments(sentence, furigana))
+                    .collect(),
+                table_sentences(&row.translation),
+            )
+        })
+        .collect();
+    let table: Vec<Vec<Cell>> = cells
+        .iter()
+        .map(|(label, original, translation)| {
+            vec![
+                vec![vec![Run::plain(label)]],
+                original
+                    .iter()
+                    .map(|segments| segments.iter().map(ruby_run).collect())
+                    .collect(),
+                translation
+                    .iter()
+                    .map(|sentence| vec![Run::plain(sentence)])
+                    .collect(),
+            ]
+        })
+        .collect();
+    doc.table(&BILINGUAL_COLUMNS, &table);
+    doc.to_bytes()
+}
+
+/// 原文と訳文を並べたHTML文書を生成（表の構成は`render_bilingual_markdown`と同じ）
+/// `furigana`指定時は原文の難読語に読み（<ruby>）を付ける
+pub fn render_bilingual_html(
+    metadata: &SessionMetadata,
+    events: &[TranscriptionEvent],
+    translations: &[TranscriptionEvent],
+    furigana: Option<&Furigana>,
+) -> String {
+    use std::fmt::Write as _;
+
+    let mut html = String::new();
+    write_html_header(&mut html, metadata);
+
+    html.push_str("<h2>文字起こし（対訳）</h2>\n");
+    let rows = bilingual_rows(events, translations);
+    if rows.is_empty() {
+        html.push_str("<p>（確定した文字起こしはありません）</p>\n");
+    } else {
+        let _ = writeln!(
+            html,
+            "<table>\n<thead>\n<tr><th>{}</th></tr>\n</thead>\n<tbody>",
+            BILINGUAL_COLUMNS.join("</th><th>")
+        );
+        for row in rows {
+            let original: Vec<String> = table_sentences(&row.event.text)
+                .iter()
+                .map(|sentence| render_html_text(sentence, furigana))
+                .collect();
+            let translation: Vec<String> = table_sentences(&row.translation)
+                .iter()
+                .map(|sentence| escape_html(sentence))
+                .collect();
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td>{}</td></tr>",
+                bilingual_label(row.event, escape_html),
+                original.join("<br>"),
+                translation.join("<br>")
+            );
+        }
+        html.push_str("</tbody>\n</table>\n");
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// 見出しとメタデータ一覧
+fn write_markdown_header(md: &mut String, metadata: &SessionMetadata) {
+    use std::fmt::Write as _;
+
+    let _ = writeln!(md, "# 議事録: {}\n", escape_markdown(&metadata.session_id));
+    for (label, value) in metadata_fields(metadata) {
+        let _ = writeln!(md, "- {}: {}", label, escape_markdown(&value));
+    }
+}
+
+/// エクスポート共通のメタデータ項目（ラベル, 値）
+fn metadata_fields(metadata: &SessionMetadata) -> Vec<(&'static str, String)> {
+    let mut fields = Vec::new();
+    if let Some(title) = &metadata.title {
+        fields.push(("タイトル", title.clone()));
+    }
+    if !metadata.tags.is_empty() {
+        fields.push(("タグ", metadata.tags.join(", ")));
+    }
+    fields.push(("開始", metadata.start_time.clone()));
+    fields.push(("終了", metadata.end_time.clone()));
+    fields.push(("録音時間", format_hms(metadata.duration_seconds * 1000)));
+    fields.push(("音声デバイス", metadata.audio_device.clone()));
+    fields.push(("モデル", metadata.model_size.clone()));
+    if let Some(lufs) = metadata.levels.as_ref().and_then(|l| l.integrated_lufs) {
+        fields.push(("ラウドネス", format!("{:.1} LUFS", lufs)));
+    }
+    fields.push((
+        "セグメント数",
+        format!(
+            "{}（{}文字）",
+            metadata.total_segments, metadata.total_characters
+        ),
+    ));
+    for warning in &metadata.warnings {
+        fields.push(("警告", warning.clone()));
+    }
+    fields
+}
+
+/// 確定セグメントのラベル（[HH:MM:SS]、話者ラベルがあれば併記）
+fn segment_label(event: &TranscriptionEvent) -> String {
+    let timestamp = format_hms(event.timestamp_ms);
+    match event
+        .speaker
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+    {
+        Some(speaker) => format!("[{}] {}:", timestamp, speaker),
+        None => format!("[{}]", timestamp),
+    }
+}
+
+/// テキストエクスポートのファイル名（セッションディレクトリ内）
+pub const TEXT_EXPORT_FILENAME: &str = "transcript.txt";
+
+/// DOCXエクスポートのファイル名（セッションディレクトリ内）
+pub const DOCX_EXPORT_FILENAME: &str = "transcript.docx";
+
+/// HTMLエクスポートのファイル名（セッションディレクトリ内）
+pub const HTML_EXPORT_FILENAME: &str = "transcript.html";
+
+/// セッションのプレーンテキスト文書を生成
+/// 書式なし：見出し・メタデータ一覧・確定セグメント1件につき1行
+/// `furigana`指定時は難読語の後に読みを括弧書きで付ける（例: 議事録（ぎじろく））
+pub fn render_session_text(
+    metadata: &SessionMetadata,
+    events: &[TranscriptionEvent],
+    furigana: Option<&Furigana>,
+) -> String {
+    use std::fmt::Write as _;
+
+    let mut text = String::new();
+    let _ = writeln!(text, "議事録: {}\n", metadata.session_id);
+    for (label, value) in metadata_fields(metadata) {
+        let _ = writeln!(text, "{}: {}", label, value);
+    }
+
+    text.push_str("\n文字起こし\n\n");
+    let finals = final_segments(events);
+    if finals.is_empty() {
+        text.push_str("（確定した文字起こしはありません）\n");
+    }
+    for event in finals {
+        let body: String = ruby_segments(&event.text.trim().replace(['\r', '\n'], " "), furigana)
+            .iter()
+            .map(|segment| match &segment.reading {
+                Some(reading) => format!("{}（{}）", segment.text, reading),
+                None => segment.text.clone(),
+            })
+            .collect();
+        let _ = writeln!(text, "{} {}", segment_label(event), body);
+    }
+    text
+}
+
+/// セッションのDOCX文書を生成（構成はMarkdownエクスポートと同じ）
+/// `furigana`指定時は難読語にルビを付ける
+pub fn render_session_docx(
+    metadata: &SessionMetadata,
+    events: &[TranscriptionEvent],
+    furigana: Option<&Furigana>,
+) -> Result<Vec<u8>> {
+    use crate::docx::{DocxDocument, Run};
+
+    let mut doc = DocxDocument::new();
+    write_docx_header(&mut doc, metadata);
+
+    doc.heading(2, "文字起こし");
+    let finals = final_segments(events);
+    if finals.is_empty() {
+        doc.paragraph(&[Run::plain("（確定した文字起こしはありません）")]);
+    }
+    for event in finals {
+        let label = segment_label(event);
+        let segments = ruby_segments(&format!(" {}", event.text.trim()), furigana);
+        let mut runs = vec![Run::bold(&label)];
+        runs.extend(segments.iter().map(ruby_run));
+        doc.paragraph(&runs);
+    }
+    doc.to_bytes()
+}
+
+/// DOCXの見出しとメタデータ一覧
+fn write_docx_header(doc: &mut crate::docx::DocxDocument, metadata: &SessionMetadata) {
+    use crate::docx::Run;
+
+    doc.heading(1, &format!("議事録: {}", metadata.session_id));
+    for (label, value) in metadata_fields(metadata) {
+        doc.paragraph(&[Run::bold(&format!("{}: ", label)), Run::plain(&value)]);
+    }
+}
+
+/// 読み付きの区間はルビ付き、それ以外は通常のDOCXテキストに
+fn ruby_run(segment: &RubySegment) -> crate::docx::Run<'_> {
+    match &segment.reading {
+        Some(reading) => crate::docx::Run::ruby(&segment.text, reading),
+        None => crate::docx::Run::plain(&segment.text),
     }
+}
 
-    /// 音声データ書き込み（i16サンプル配列）
-    /// Related requirement: STT-REQ-005.2
-    pub fn write_samples(&mut self, samples: &[i16]) -> Result<()> {
-        use std::io::Write;
+/// セッションのHTML文書を生成（構成はMarkdownエクスポートと同じ）
+/// `furigana`指定時は本文の難読語に読み（<ruby>）を付ける
+pub fn render_session_html(
+    metadata: &SessionMetadata,
+    events: &[TranscriptionEvent],
+    furigana: Option<&Furigana>,
+) -> String {
+    use std::fmt::Write as _;
 
-        for &sample in samples {
-            self.file.write_all(&sample.to_le_bytes())?;
+    let mut html = String::new();
+    write_html_header(&mut html, metadata);
+
+    html.push_str("<h2>文字起こし</h2>\n");
+
+    let finals = final_segments(events);
+    if finals.is_empty() {
+        html.push_str("<p>（確定した文字起こしはありません）</p>\n");
+    }
+    for event in finals {
+        let label = escape_html(&segment_label(event));
+        let sentences = crate::segmentation::split_sentences(&event.text);
+        if sentences.len() > 1 {
+            let _ = writeln!(html, "<p><strong>{}</strong></p>\n<ul>", label);
+            for sentence in sentences {
+                let _ = writeln!(html, "<li>{}</li>", render_html_text(&sentence, furigana));
+            }
+            html.push_str("</ul>\n");
+        } else {
+            let _ = writeln!(
+                html,
+                "<p><strong>{}</strong> {}</p>",
+                label,
+                render_html_text(event.text.trim(), furigana)
+            );
         }
-        self.samples_written += samples.len() as u32;
-        Ok(())
     }
+    html.push_str("</body>\n</html>\n");
+    html
+}
 
-    /// WAVファイルを閉じる（ヘッダー更新）
-    /// Related requirement: STT-REQ-005.2
-    pub fn close(mut self) -> Result<()> {
-        self.finalize()
+/// 空でない確定セグメント
+fn final_segments(events: &[TranscriptionEvent]) -> Vec<&TranscriptionEvent> {
+    events
+        .iter()
+        .filter(|e| e.is_final && !e.text.trim().is_empty())
+        .collect()
+}
+
+/// 表のセル：文ごとに<br>で改行（セル内の改行は表を壊すため）
+fn render_table_cell(text: &str, furigana: Option<&Furigana>) -> String {
+    table_sentences(text)
+        .iter()
+        .map(|sentence| render_text(sentence, furigana))
+        .collect::<Vec<_>>()
+        .join("<br>")
+}
+
+/// 表のセルに並べる文（改行は空白に置き換えてから分割）
+fn table_sentences(text: &str) -> Vec<String> {
+    crate::segmentation::split_sentences(&text.replace(['\r', '\n'], " "))
+}
+
+/// ミリ秒をHH:MM:SS形式に変換
+fn format_hms(ms: u64) -> String {
+    let secs = ms / 1000;
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+/// HTML文書の先頭（<body>まで）と見出し・メタデータ一覧
+fn write_html_header(html: &mut String, metadata: &SessionMetadata) {
+    use std::fmt::Write as _;
+
+    let title = escape_html(&format!("議事録: {}", metadata.session_id));
+    let _ = writeln!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"ja\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n<h1>{}</h1>\n<ul>",
+        title, title
+    );
+    for (label, value) in metadata_fields(metadata) {
+        let _ = writeln!(html, "<li>{}: {}</li>", label, escape_html(&value));
     }
+    html.push_str("</ul>\n");
+}
 
-    /// ヘッダー更新の内部実装
-    fn finalize(&mut self) -> Result<()> {
-        use std::io::{Seek, SeekFrom, Write};
+/// ふりがな指定時は難読語と読みに分割（未指定時は全体を1区間）
+fn ruby_segments(text: &str, furigana: Option<&Furigana>) -> Vec<RubySegment> {
+    match furigana {
+        Some(furigana) => furigana.annotate(text),
+        None => vec![RubySegment {
+            text: text.to_string(),
+            reading: None,
+        }],
+    }
+}
 
-        // ファイルサイズとデータサイズを更新
-        let data_size = self.samples_written * 2; // 16bit = 2 bytes
-        let file_size = data_size + 36; // RIFFヘッダー + fmtチャンク + dataヘッダー
+/// 本文をHTMLエスケープし、ふりがな指定時は難読語を<ruby>で囲む
+fn render_html_text(text: &str, furigana: Option<&Furigana>) -> String {
+    ruby_segments(text, furigana)
+        .iter()
+        .map(|segment| match &segment.reading {
+            Some(reading) => format!(
+                "<ruby>{}<rt>{}</rt></ruby>",
+                escape_html(&segment.text),
+                escape_html(reading)
+            ),
+            None => escape_html(&segment.text),
+        })
+        .collect()
+}
 
-        // RIFFチャンクサイズ更新（オフセット4）
-        self.file.seek(SeekFrom::Start(4))?;
-        self.file.write_all(&file_size.to_le_bytes())?;
+/// HTMLの特殊文字をエスケープ
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
 
-        // dataチャンクサイズ更新（オフセット40）
-        self.file.seek(SeekFrom::Start(40))?;
-        self.file.write_all(&data_size.to_le_bytes())?;
+/// 本文をエスケープし、ふりがな指定時は難読語を<ruby>で囲む
+fn render_text(text: &str, furigana: Option<&Furigana>) -> String {
+    let Some(furigana) = furigana else {
+        return escape_markdown(text);
+    };
+    furigana
+        .annotate(text)
+        .iter()
+        .map(|segment| match &segment.reading {
+            Some(reading) => format!(
+                "<ruby>{}<rt>{}</rt></ruby>",
+                escape_markdown(&segment.text),
+                escape_markdown(reading)
+            ),
+            None => escape_markdown(&segment.text),
+        })
+        .collect()
+}
 
-        self.file.sync_all()?;
-        Ok(())
+/// Markdownの書式文字をエスケープ（文字起こし中の*や_で書式が崩れないように）
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(
+            c,
+            '\\' | '*' | '_' | '`' | '[' | ']' | '<' | '>' | '#' | '|'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
     }
+    escaped
 }
 
-/// Drop実装: close()忘れ時の自動ヘッダー更新
-/// Related requirement: STT-REQ-005.2（異常終了時のデータ保護）
-impl Drop for AudioWriter {
-    fn drop(&mut self) {
-        // close()されていない場合でもヘッダー更新を試みる
-        let _ = self.finalize();
+/// session.json読み込み（未作成・破損・復号不可時はNone）
+fn read_session_metadata(
+    session_dir: &std::path::Path,
+    encryption: Option<&SessionEncryption>,
+) -> Option<SessionMetadata> {
+    read_metadata_file(&session_dir.join(METADATA_FILENAME), encryption).ok()
+}
+
+/// session.jsonファイル読み込み（暗号化されていれば復号）
+pub(crate) fn read_metadata_file(
+    path: &std::path::Path,
+    encryption: Option<&SessionEncryption>,
+) -> Result<SessionMetadata> {
+    let content = std::fs::read_to_string(path)?;
+    let json = crate::encryption::open_record(&content, METADATA_FILENAME, encryption)?;
+    Ok(serde_json::from_str(&json)?)
+}
+
+/// JSONL形式の文字起こしファイル読み込み（空行はスキップ）
+/// 暗号化された行は復号する（平文行との混在可）
+fn read_transcript_file(
+    path: &std::path::Path,
+    encryption: Option<&SessionEncryption>,
+) -> Result<Vec<TranscriptionEvent>> {
+    let content = std::fs::read_to_string(path)?;
+    let mut transcripts = Vec::new();
+    for line in content.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let line = crate::encryption::open_record(line, TRANSCRIPT_FILENAME, encryption)?;
+        let (event, _) = crate::transcript_schema::decode_line(&line)?;
+        transcripts.push(event);
     }
+    Ok(transcripts)
 }
 
 /// セッションメタデータ（session.json形式で保存）
@@ -485,14 +2075,108 @@ pub struct SessionMetadata {
     pub total_segments: u64,
     /// 総文字数
     pub total_characters: u64,
+    /// 終了処理の警告（強制終了など）。空なら正常終了
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    /// セッションのUUID（session_idの書式に依存しない安定した参照）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_uuid: Option<String>,
+    /// ユーザーが付けたタイトル（未設定ならNone）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// ユーザーが付けたタグ
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// セッション統計（語数・発話速度・無音率など、`session_stats`参照）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stats: Option<SessionStatistics>,
+    /// 入力レベル（適用ゲイン・統合ラウドネス、`loudness`参照）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub levels: Option<SessionLevels>,
+}
+
+/// セッションタイトルの最大文字数
+pub const MAX_SESSION_TITLE_CHARS: usize = 200;
+/// セッションあたりのタグ数上限
+pub const MAX_SESSION_TAGS: usize = 20;
+/// タグの最大文字数
+pub const MAX_SESSION_TAG_CHARS: usize = 50;
+
+/// タイトルを正規化（前後の空白を除去し、空ならNone）
+fn normalize_session_title(title: Option<&str>) -> Result<Option<String>> {
+    let title = title.map(str::trim).filter(|t| !t.is_empty());
+    if let Some(title) = title {
+        if title.chars().count() > MAX_SESSION_TITLE_CHARS {
+            anyhow::bail!(
+                "タイトルは{}文字以内にしてください",
+                MAX_SESSION_TITLE_CHARS
+            );
+        }
+        if title.chars().any(char::is_control) {
+            anyhow::bail!("タイトルに制御文字は使えません");
+        }
+    }
+    Ok(title.map(str::to_string))
+}
+
+/// タグを正規化（前後の空白除去・空タグ除外・重複除去、順序は保持）
+fn normalize_session_tags(tags: &[String]) -> Result<Vec<String>> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+        if tag.chars().count() > MAX_SESSION_TAG_CHARS {
+            anyhow::bail!(
+                "タグは{}文字以内にしてください: {}",
+                MAX_SESSION_TAG_CHARS,
+                tag
+            );
+        }
+        if tag.chars().any(char::is_control) {
+            anyhow::bail!("タグに制御文字は使えません");
+        }
+        if !normalized.iter().any(|t| t == tag) {
+            normalized.push(tag.to_string());
+        }
+    }
+    if normalized.len() > MAX_SESSION_TAGS {
+        anyhow::bail!("タグは{}個までです", MAX_SESSION_TAGS);
+    }
+    Ok(normalized)
+}
+
+/// ゴミ箱ディレクトリ名（recordings/直下）
+const TRASH_DIR_NAME: &str = ".trash";
+/// ゴミ箱内セッションの削除記録ファイル
+const TRASH_ENTRY_FILE: &str = "trash.json";
+
+/// ゴミ箱内のセッション
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct TrashedSession {
+    pub session_id: String,
+    /// 削除日時（エポックミリ秒）
+    pub deleted_at_ms: u64,
+    /// session.json（録音中断などで未作成ならNone）
+    pub metadata: Option<SessionMetadata>,
+}
+
+/// 文字起こしファイルの形式移行結果
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct SessionFormatMigration {
+    /// 置き換えたファイル名
+    pub migrated_files: Vec<String>,
+    /// 退避したファイル名（migrated_filesと同順）
+    pub backup_files: Vec<String>,
+    /// 現行スキーマに変換した行数
+    pub upgraded_lines: u64,
 }
 
 /// セッション読み込み結果
 /// Related requirement: STT-REQ-005.6
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct LoadedSession {
     pub metadata: SessionMetadata,
     pub transcripts: Vec<TranscriptionEvent>,
+    /// 文字起こしの全件数（`transcripts`が先頭ページのみの場合に大きい）
+    pub transcript_total: usize,
     pub audio_path: PathBuf,
 }
 
@@ -530,19 +2214,86 @@ pub struct TranscriptionEvent {
     pub text: String,
     /// 確定テキストかどうか（false = 部分テキスト）
     pub is_final: bool,
+    /// 話者ラベル（話者情報がある場合のみ）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker: Option<String>,
 }
 
 /// transcription.jsonlへのJSON Lines書き込み
+/// セッション時間1時間ごとにパートを切り替える（transcript_chunks参照）
 /// Related requirement: STT-REQ-005.3
 pub struct TranscriptWriter {
     file: std::fs::File,
+    /// 行単位の暗号化（暗号化無効時はNone）
+    cipher: Option<SessionCipher>,
+    session_dir: PathBuf,
+    /// 書き込み中のパート番号（1始まり）
+    part: u32,
+    /// パートごとの件数（複数パートになったらマニフェストとして保存）
+    manifest: crate::transcript_chunks::ChunkManifest,
+    /// このライターで追記した確定セグメント数
+    final_segments: u64,
+    /// このライターで追記した確定テキストの文字数
+    final_characters: u64,
 }
 
 impl TranscriptWriter {
     /// 新規TranscriptWriter作成（追記モード）
-    fn new(transcript_path: PathBuf) -> Result<Self> {
-        let file = open_file_append_owner_only(&transcript_path)?;
-        Ok(Self { file })
+    /// 既存パートがあれば最後のパートに追記する
+    fn new(session_dir: PathBuf, cipher: Option<SessionCipher>) -> Result<Self> {
+        use crate::transcript_chunks::{list_parts, load_manifest, part_file_name};
+
+        let parts = list_parts(&session_dir)?;
+        let part = parts.last().map_or(1, |(index, _)| *index);
+        // 壊れたマニフェストは作り直す（パートファイルが正）
+        let mut manifest = load_manifest(&session_dir)
+            .ok()
+            .flatten()
+            .unwrap_or_default();
+        // 最後のパートの件数はファイルから数え直す（異常終了でマニフェストが古い場合がある）
+        let existing = match parts.last() {
+            Some((_, path)) => std::fs::read_to_string(path)?
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .count() as u64,
+            None => 0,
+        };
+        manifest.part_mut(part).events = existing;
+
+        let file = open_file_append_owner_only(&session_dir.join(part_file_name(part)))?;
+        Ok(Self {
+            file,
+            cipher,
+            session_dir,
+            part,
+            manifest,
+            final_segments: 0,
+            final_characters: 0,
+        })
+    }
+
+    /// 次のパートへ切り替え（旧パートを同期し、マニフェストを更新してから開く）
+    fn roll_over(&mut self, part: u32) -> Result<()> {
+        self.file.sync_all()?;
+        self.manifest.part_mut(part);
+        crate::transcript_chunks::save_manifest(&self.session_dir, &self.manifest)?;
+        self.file = open_file_append_owner_only(
+            &self
+                .session_dir
+                .join(crate::transcript_chunks::part_file_name(part)),
+        )?;
+        self.part = part;
+        Ok(())
+    }
+
+    /// 追記済み確定セグメント数（session.jsonのtotal_segments用）
+    pub fn final_segments(&self) -> u64 {
+        self.final_segments
+    }
+
+    /// 追記済み確定テキスト文字数（session.jsonのtotal_characters用）
+    pub fn final_characters(&self) -> u64 {
+        self.final_characters
     }
 
     /// 文字起こし結果を追記
@@ -554,12 +2305,27 @@ impl TranscriptWriter {
     pub fn append_event(&mut self, event: &TranscriptionEvent) -> Result<()> {
         use std::io::Write;
 
-        let json_line = serde_json::to_string(event)?;
+        // パートは戻らない（切り替え直後に届いた前の時間帯の確定も新パートへ）
+        let part = crate::transcript_chunks::part_for_timestamp(event.timestamp_ms);
+        if part > self.part {
+            self.roll_over(part)?;
+        }
+
+        let mut json_line = crate::transcript_schema::encode_line(event)?;
+        if let Some(cipher) = &self.cipher {
+            json_line = cipher.seal(&json_line, TRANSCRIPT_FILENAME)?;
+        }
         writeln!(self.file, "{}", json_line)?;
 
         // flush()はカーネルバッファまで、sync_all()でディスク永続化
         self.file.flush()?;
         self.file.sync_all()?;
+        self.manifest.record_event(self.part, event.timestamp_ms);
+
+        if event.is_final {
+            self.final_segments += 1;
+            self.final_characters += event.text.chars().count() as u64;
+        }
 
         Ok(())
     }
@@ -569,9 +2335,12 @@ impl TranscriptWriter {
         self.finalize()
     }
 
-    /// ファイルの最終同期処理
+    /// ファイルの最終同期処理（分割済みならマニフェストも保存）
     fn finalize(&mut self) -> Result<()> {
         self.file.sync_all()?;
+        if self.manifest.parts.len() > 1 {
+            crate::transcript_chunks::save_manifest(&self.session_dir, &self.manifest)?;
+        }
         Ok(())
     }
 }
@@ -743,8 +2512,8 @@ mod tests {
         let audio_path = service.get_session_dir(session_id).join("audio.wav");
         let metadata = std::fs::metadata(&audio_path).expect("metadata should succeed");
 
-        // 期待サイズ = WAVヘッダー(44bytes) + サンプルデータ(16000 * 2bytes)
-        let expected_size = 44 + (16000 * 2);
+        // 期待サイズ = WAVヘッダー(80bytes) + サンプルデータ(16000 * 2bytes)
+        let expected_size = WAV_HEADER_LEN + (16000 * 2);
         assert_eq!(
             metadata.len(),
             expected_size,
@@ -780,10 +2549,101 @@ mod tests {
         let metadata = std::fs::metadata(&audio_path).expect("metadata should succeed");
 
         let total_samples = 10 * 1600;
-        let expected_size = 44 + (total_samples * 2);
+        let expected_size = WAV_HEADER_LEN + (total_samples * 2);
         assert_eq!(metadata.len(), expected_size);
     }
 
+    #[test]
+    fn test_audio_writer_write_pcm_bytes() {
+        let (service, _temp_dir) = setup_test_service();
+        let session_id = "pcm-session";
+        service.create_session(session_id).unwrap();
+
+        let mut writer = service.create_audio_writer(session_id).unwrap();
+        // 2サンプル + 端数1バイト（切り捨て）
+        writer
+            .write_pcm_bytes(&[0x01, 0x00, 0x02, 0x00, 0xff])
+            .unwrap();
+        assert_eq!(writer.samples_written(), 2);
+        writer.close().unwrap();
+
+        let audio_path = service.get_session_dir(session_id).join("audio.wav");
+        assert_eq!(
+            std::fs::metadata(&audio_path).unwrap().len(),
+            WAV_HEADER_LEN + 4
+        );
+    }
+
+    #[test]
+    fn test_repair_wav_header() {
+        let (service, _temp_dir) = setup_test_service();
+        let session_id = "crashed-session";
+        service.create_session(session_id).unwrap();
+        let audio_path = service.get_session_dir(session_id).join("audio.wav");
+
+        // 異常終了を再現: ヘッダーのサイズ欄が0のまま
+        let mut content = Vec::new();
+        content.extend_from_slice(b"RIFF");
+        content.extend_from_slice(&0u32.to_le_bytes());
+        content.extend_from_slice(b"WAVE");
+        content.extend_from_slice(&[0u8; 28]);
+        content.extend_from_slice(&0u32.to_le_bytes());
+        content.extend_from_slice(&[0u8; 20]); // 10サンプル
+        std::fs::write(&audio_path, &content).unwrap();
+
+        let samples = repair_wav_header(&audio_path).unwrap();
+        assert_eq!(samples, 10);
+
+        let repaired = std::fs::read(&audio_path).unwrap();
+        assert_eq!(u32::from_le_bytes(repaired[4..8].try_into().unwrap()), 56);
+        assert_eq!(u32::from_le_bytes(repaired[40..44].try_into().unwrap()), 20);
+    }
+
+    #[test]
+    fn test_rf64_promotion_and_repair() {
+        let (service, _temp_dir) = setup_test_service();
+        let session_id = "rf64-session";
+        service.create_session(session_id).unwrap();
+        let audio_path = service.get_session_dir(session_id).join("audio.wav");
+
+        let mut writer = service.create_audio_writer(session_id).unwrap();
+        writer.write_samples(&[1, 2, 3]).unwrap();
+        writer.close().unwrap();
+
+        // 4GB超のデータサイズでヘッダーを更新（実データは書かない）
+        let data_size = 5_000_000_000u64;
+        let mut file = std::fs::OpenOptions::new()
+            .write(true)
+            .open(&audio_path)
+            .unwrap();
+        write_wav_sizes(&mut file, data_size).unwrap();
+        drop(file);
+
+        let header = std::fs::read(&audio_path).unwrap();
+        let u32_at = |i: usize| u32::from_le_bytes(header[i..i + 4].try_into().unwrap());
+        let u64_at = |i: usize| u64::from_le_bytes(header[i..i + 8].try_into().unwrap());
+        assert_eq!(&header[0..4], b"RF64");
+        assert_eq!(u32_at(4), u32::MAX);
+        assert_eq!(&header[12..16], b"ds64");
+        assert_eq!(u64_at(20), data_size + 72);
+        assert_eq!(u64_at(28), data_size);
+        assert_eq!(u64_at(36), data_size / 2);
+        assert_eq!(u32_at(76), u32::MAX);
+        assert_eq!(wav_data_offset(&header), WAV_HEADER_LEN);
+
+        // RF64ヘッダーでもサンプルを読める
+        let mut samples = Vec::new();
+        read_wav_samples(&audio_path, |chunk| samples.extend_from_slice(chunk)).unwrap();
+        assert_eq!(samples, vec![1, 2, 3]);
+
+        // 修復は実ファイル長から再計算し、RIFFに戻す
+        assert_eq!(repair_wav_header(&audio_path).unwrap(), 3);
+        let repaired = std::fs::read(&audio_path).unwrap();
+        assert_eq!(&repaired[0..4], b"RIFF");
+        assert_eq!(&repaired[12..16], b"JUNK");
+        assert_eq!(u32::from_le_bytes(repaired[76..80].try_into().unwrap()), 6);
+    }
+
     #[test]
     fn test_audio_writer_drop_without_close() {
         use super::*;
@@ -811,21 +2671,21 @@ mod tests {
         // RIFFヘッダー確認
         assert_eq!(&file_content[0..4], b"RIFF");
 
-        // ファイルサイズ確認（5サンプル * 2バイト + 36 = 46バイト）
+        // ファイルサイズ確認（5サンプル * 2バイト + 72 = 82バイト）
         let file_size = u32::from_le_bytes([
             file_content[4],
             file_content[5],
             file_content[6],
             file_content[7],
         ]);
-        assert_eq!(file_size, 46); // 10 bytes data + 36 bytes header
+        assert_eq!(file_size, 82); // 10 bytes data + 72 bytes header
 
         // dataチャンクサイズ確認
         let data_size = u32::from_le_bytes([
-            file_content[40],
-            file_content[41],
-            file_content[42],
-            file_content[43],
+            file_content[76],
+            file_content[77],
+            file_content[78],
+            file_content[79],
         ]);
         assert_eq!(data_size, 10); // 5 samples * 2 bytes
     }
@@ -854,22 +2714,27 @@ mod tests {
         assert_eq!(&wav_data[8..12], b"WAVE", "WAVE format should be present");
 
         // Assert: fmtチャンク確認
-        assert_eq!(&wav_data[12..16], b"fmt ", "fmt chunk should be present");
-        let audio_format = u16::from_le_bytes([wav_data[20], wav_data[21]]);
+        assert_eq!(
+            &wav_data[12..16],
+            b"JUNK",
+            "ds64 reservation should be present"
+        );
+        assert_eq!(&wav_data[48..52], b"fmt ", "fmt chunk should be present");
+        let audio_format = u16::from_le_bytes([wav_data[56], wav_data[57]]);
         assert_eq!(audio_format, 1, "Audio format should be PCM (1)");
 
-        let num_channels = u16::from_le_bytes([wav_data[22], wav_data[23]]);
+        let num_channels = u16::from_le_bytes([wav_data[58], wav_data[59]]);
         assert_eq!(num_channels, 1, "Should be mono (1 channel)");
 
         let sample_rate =
-            u32::from_le_bytes([wav_data[24], wav_data[25], wav_data[26], wav_data[27]]);
+            u32::from_le_bytes([wav_data[60], wav_data[61], wav_data[62], wav_data[63]]);
         assert_eq!(sample_rate, 16000, "Sample rate should be 16kHz");
 
-        let bits_per_sample = u16::from_le_bytes([wav_data[34], wav_data[35]]);
+        let bits_per_sample = u16::from_le_bytes([wav_data[70], wav_data[71]]);
         assert_eq!(bits_per_sample, 16, "Bit depth should be 16bit");
 
         // Assert: dataチャンク確認
-        assert_eq!(&wav_data[36..40], b"data", "data chunk should be present");
+        assert_eq!(&wav_data[72..76], b"data", "data chunk should be present");
     }
 
     // === Task 6.3: 文字起こし結果保存機能のテスト ===
@@ -920,6 +2785,7 @@ mod tests {
             timestamp_ms: 1000,
             text: "これは部分".to_string(),
             is_final: false,
+            speaker: None,
         };
         writer
             .append_event(&event1)
@@ -930,6 +2796,7 @@ mod tests {
             timestamp_ms: 2000,
             text: "これは確定テキストです。".to_string(),
             is_final: true,
+            speaker: None,
         };
         writer
             .append_event(&event2)
@@ -976,6 +2843,7 @@ mod tests {
                 timestamp_ms: 1000,
                 text: "最初のイベント".to_string(),
                 is_final: false,
+                speaker: None,
             })
             .expect("append_event should succeed");
         writer1.close().expect("close should succeed");
@@ -989,6 +2857,7 @@ mod tests {
                 timestamp_ms: 2000,
                 text: "2番目のイベント".to_string(),
                 is_final: true,
+                speaker: None,
             })
             .expect("append_event should succeed");
         writer2.close().expect("close should succeed");
@@ -1025,11 +2894,13 @@ mod tests {
                 timestamp_ms: 1000,
                 text: "Hello".to_string(),
                 is_final: false,
+                speaker: None,
             };
             let event2 = TranscriptionEvent {
                 timestamp_ms: 2000,
                 text: "Hello world".to_string(),
                 is_final: true,
+                speaker: None,
             };
             writer.append_event(&event1).unwrap();
             writer.append_event(&event2).unwrap();
@@ -1056,6 +2927,7 @@ mod tests {
             timestamp_ms: 12345,
             text: "テストテキスト".to_string(),
             is_final: true,
+            speaker: None,
         };
 
         // Act: JSON変換
@@ -1074,6 +2946,136 @@ mod tests {
         assert_eq!(parsed, event);
     }
 
+    #[test]
+    fn test_transcript_writer_counts_final_segments() {
+        let (service, _temp_dir) = setup_test_service();
+        let session_id = "count-session";
+        service.create_session(session_id).unwrap();
+
+        let mut writer = service.create_transcript_writer(session_id).unwrap();
+        writer
+            .append_event(&TranscriptionEvent {
+                timestamp_ms: 100,
+                text: "部分".to_string(),
+                is_final: false,
+                speaker: None,
+            })
+            .unwrap();
+        writer
+            .append_event(&TranscriptionEvent {
+                timestamp_ms: 200,
+                text: "確定です".to_string(),
+                is_final: true,
+                speaker: None,
+            })
+            .unwrap();
+
+        assert_eq!(writer.final_segments(), 1);
+        assert_eq!(writer.final_characters(), 4);
+    }
+
+    #[test]
+    fn test_load_transcript_without_metadata() {
+        let (service, _temp_dir) = setup_test_service();
+        let session_id = "in-progress-session";
+        service.create_session(session_id).unwrap();
+
+        let mut writer = service.create_transcript_writer(session_id).unwrap();
+        writer
+            .append_event(&TranscriptionEvent {
+                timestamp_ms: 500,
+                text: "録音中".to_string(),
+                is_final: true,
+                speaker: None,
+            })
+            .unwrap();
+        writer.close().unwrap();
+
+        // session.jsonが無くても文字起こしは読み込める
+        let transcript = service.load_transcript(session_id).unwrap();
+        assert_eq!(transcript.len(), 1);
+        assert_eq!(transcript[0].text, "録音中");
+    }
+
+    #[test]
+    fn test_transcript_chunk_rollover_and_paging() {
+        use crate::transcript_chunks::{load_manifest, CHUNK_DURATION_MS};
+
+        let (service, _temp_dir) = setup_test_service();
+        let session_id = "long-session";
+        let session_dir = service.create_session(session_id).unwrap();
+        let event = |timestamp_ms: u64, text: &str| TranscriptionEvent {
+            timestamp_ms,
+            text: text.to_string(),
+            is_final: true,
+            speaker: None,
+        };
+
+        let mut writer = service.create_transcript_writer(session_id).unwrap();
+        writer.append_event(&event(1_000, "a")).unwrap();
+        writer.append_event(&event(2_000, "b")).unwrap();
+        writer
+            .append_event(&event(CHUNK_DURATION_MS + 5, "c"))
+            .unwrap();
+        // 切り替え後に届いた前の時間帯の確定は新パートへ
+        writer
+            .append_event(&event(CHUNK_DURATION_MS - 5, "d"))
+            .unwrap();
+        writer
+            .append_event(&event(2 * CHUNK_DURATION_MS + 1, "e"))
+            .unwrap();
+        writer.close().unwrap();
+
+        assert!(session_dir.join("transcription.part2.jsonl").exists());
+        assert!(session_dir.join("transcription.part3.jsonl").exists());
+        let manifest = load_manifest(&session_dir).unwrap().unwrap();
+        assert_eq!(
+            manifest.parts.iter().map(|p| p.events).collect::<Vec<_>>(),
+            [2, 2, 1]
+        );
+
+        // 結合読み込みは書き込み順
+        let texts: Vec<String> = service
+            .load_transcript(session_id)
+            .unwrap()
+            .into_iter()
+            .map(|e| e.text)
+            .collect();
+        assert_eq!(texts, ["a", "b", "c", "d", "e"]);
+
+        // ページ読み込み（パート境界をまたぐ）
+        let page = service.load_transcript_page(session_id, 1, 3).unwrap();
+        assert_eq!(page.total, 5);
+        assert_eq!(
+            page.events
+                .iter()
+                .map(|e| e.text.as_str())
+                .collect::<Vec<_>>(),
+            ["b", "c", "d"]
+        );
+        let tail = service.load_transcript_page(session_id, 4, 10).unwrap();
+        assert_eq!(tail.events.len(), 1);
+        assert_eq!(tail.total, 5);
+
+        // 再オープン時は最後のパートに追記
+        let mut writer = service.create_transcript_writer(session_id).unwrap();
+        writer
+            .append_event(&event(2 * CHUNK_DURATION_MS + 2, "f"))
+            .unwrap();
+        writer.close().unwrap();
+        let manifest = load_manifest(&session_dir).unwrap().unwrap();
+        assert_eq!(manifest.events_in(3), Some(2));
+    }
+
+    #[test]
+    fn test_format_iso8601_millis() {
+        assert_eq!(format_iso8601_millis(0), "1970-01-01T00:00:00.000Z");
+        assert_eq!(
+            format_iso8601_millis(1_760_369_445_123),
+            "2025-10-13T15:30:45.123Z"
+        );
+    }
+
     // === Task 6.4: セッションメタデータ保存機能のテスト ===
 
     #[test]
@@ -1096,6 +3098,12 @@ mod tests {
             model_size: "small".to_string(),
             total_segments: 150,
             total_characters: 12000,
+            warnings: Vec::new(),
+            session_uuid: None,
+            title: None,
+            tags: Vec::new(),
+            stats: None,
+            levels: None,
         };
 
         // Act: メタデータ保存
@@ -1126,6 +3134,12 @@ mod tests {
             model_size: "small".to_string(),
             total_segments: 150,
             total_characters: 12000,
+            warnings: Vec::new(),
+            session_uuid: None,
+            title: None,
+            tags: Vec::new(),
+            stats: None,
+            levels: None,
         };
 
         // Act: JSON変換
@@ -1176,6 +3190,12 @@ mod tests {
             model_size: "tiny".to_string(),
             total_segments: 50,
             total_characters: 3000,
+            warnings: Vec::new(),
+            session_uuid: None,
+            title: None,
+            tags: Vec::new(),
+            stats: None,
+            levels: None,
         };
         service
             .save_session_metadata(&metadata1)
@@ -1191,6 +3211,12 @@ mod tests {
             model_size: "small".to_string(),
             total_segments: 100,
             total_characters: 8000,
+            warnings: Vec::new(),
+            session_uuid: None,
+            title: None,
+            tags: Vec::new(),
+            stats: None,
+            levels: None,
         };
         service
             .save_session_metadata(&metadata2)
@@ -1204,6 +3230,78 @@ mod tests {
         assert_eq!(parsed, metadata2, "Should have latest metadata (overwrite)");
     }
 
+    #[test]
+    fn test_rename_and_tag_session() {
+        let (service, _temp_dir) = setup_test_service();
+        let session_id = "2025-10-13T10-00_abcd1234";
+        service.create_session(session_id).unwrap();
+
+        // session.json未作成（録音中）は変更不可
+        assert!(service.rename_session(session_id, Some("定例")).is_err());
+
+        let metadata = SessionMetadata {
+            session_id: session_id.to_string(),
+            start_time: "2025-10-13T10:00:00Z".to_string(),
+            end_time: "2025-10-13T10:30:00Z".to_string(),
+            duration_seconds: 1800,
+            audio_device: "default".to_string(),
+            model_size: "small".to_string(),
+            total_segments: 10,
+            total_characters: 300,
+            warnings: Vec::new(),
+            session_uuid: Some("1a2b3c4d-0000-4000-8000-000000000000".to_string()),
+            title: None,
+            tags: Vec::new(),
+            stats: None,
+            levels: None,
+        };
+        service.save_session_metadata(&metadata).unwrap();
+
+        let renamed = service
+            .rename_session(session_id, Some("  週次定例  "))
+            .unwrap();
+        assert_eq!(renamed.title.as_deref(), Some("週次定例"));
+        let tagged = service
+            .set_session_tags(
+                session_id,
+                &[
+                    "予算".to_string(),
+                    " ".to_string(),
+                    " 予算 ".to_string(),
+                    "営業".to_string(),
+                ],
+            )
+            .unwrap();
+        assert_eq!(tagged.tags, vec!["予算", "営業"]);
+
+        // 他のフィールドは保持される
+        let loaded = service.load_session(session_id).unwrap().metadata;
+        assert_eq!(
+            loaded,
+            SessionMetadata {
+                title: Some("週次定例".to_string()),
+                tags: vec!["予算".to_string(), "営業".to_string()],
+                ..metadata.clone()
+            }
+        );
+
+        // 空文字でタイトルを消去
+        assert_eq!(
+            service.rename_session(session_id, Some(" ")).unwrap().title,
+            None
+        );
+        assert!(service
+            .rename_session(session_id, Some(&"長".repeat(MAX_SESSION_TITLE_CHARS + 1)))
+            .is_err());
+        let too_many: Vec<String> = (0..=MAX_SESSION_TAGS).map(|i| i.to_string()).collect();
+        assert!(service.set_session_tags(session_id, &too_many).is_err());
+        assert!(service.rename_session("../escape", Some("x")).is_err());
+
+        // 旧形式のsession.json（title/tagsなし）も読める
+        let json = serde_json::to_string(&metadata).unwrap();
+        assert!(!json.contains("title") && !json.contains("tags"));
+    }
+
     #[test]
     fn test_session_metadata_iso8601_timestamps() {
         // Arrange
@@ -1216,6 +3314,12 @@ mod tests {
             model_size: "medium".to_string(),
             total_segments: 200,
             total_characters: 15000,
+            warnings: Vec::new(),
+            session_uuid: None,
+            title: None,
+            tags: Vec::new(),
+            stats: None,
+            levels: None,
         };
 
         // Act: JSON変換・逆変換
@@ -1260,6 +3364,12 @@ mod tests {
             model_size: "small".to_string(),
             total_segments: 10,
             total_characters: 500,
+            warnings: Vec::new(),
+            session_uuid: None,
+            title: None,
+            tags: Vec::new(),
+            stats: None,
+            levels: None,
         };
         let metadata2 = SessionMetadata {
             session_id: session2.clone(),
@@ -1270,6 +3380,12 @@ mod tests {
             model_size: "small".to_string(),
             total_segments: 5,
             total_characters: 250,
+            warnings: Vec::new(),
+            session_uuid: None,
+            title: None,
+            tags: Vec::new(),
+            stats: None,
+            levels: None,
         };
         let metadata3 = SessionMetadata {
             session_id: session3.clone(),
@@ -1280,6 +3396,12 @@ mod tests {
             model_size: "small".to_string(),
             total_segments: 15,
             total_characters: 750,
+            warnings: Vec::new(),
+            session_uuid: None,
+            title: None,
+            tags: Vec::new(),
+            stats: None,
+            levels: None,
         };
 
         storage.save_session_metadata(&metadata1).unwrap();
@@ -1319,6 +3441,12 @@ mod tests {
             model_size: "small".to_string(),
             total_segments: 10,
             total_characters: 500,
+            warnings: Vec::new(),
+            session_uuid: None,
+            title: None,
+            tags: Vec::new(),
+            stats: None,
+            levels: None,
         };
         storage.save_session_metadata(&metadata).unwrap();
 
@@ -1328,11 +3456,13 @@ mod tests {
             timestamp_ms: 1000,
             text: "Hello".to_string(),
             is_final: false,
+            speaker: None,
         };
         let event2 = TranscriptionEvent {
             timestamp_ms: 2000,
             text: "Hello world".to_string(),
             is_final: true,
+            speaker: None,
         };
         transcript_writer.append_event(&event1).unwrap();
         transcript_writer.append_event(&event2).unwrap();
@@ -1380,6 +3510,94 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_load_transcript_version() {
+        use super::*;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let storage = LocalStorageService::new(temp_dir.path().to_path_buf());
+        let session_id = "versioned-session";
+        let session_dir = storage.create_session(session_id).unwrap();
+
+        std::fs::write(
+            session_dir.join("transcription.v2.jsonl"),
+            "{\"timestamp_ms\":0,\"text\":\"再文字起こし\",\"is_final\":true}\n",
+        )
+        .unwrap();
+
+        let v2 = storage.load_transcript_version(session_id, "v2").unwrap();
+        assert_eq!(v2.len(), 1);
+        assert_eq!(v2[0].text, "再文字起こし");
+
+        // v1 = 録音時のtranscription.jsonl（未作成ならエラー）
+        assert!(storage.load_transcript_version(session_id, "v1").is_err());
+        assert!(storage.load_transcript_version(session_id, "v3").is_err());
+        // 保存したバージョンを読み戻せる（v1は上書き不可）
+        let v3 = vec![TranscriptionEvent {
+            timestamp_ms: 1_000,
+            text: "保存".to_string(),
+            is_final: true,
+            speaker: None,
+        }];
+        storage
+            .save_transcript_version(session_id, "v3", &v3)
+            .unwrap();
+        assert_eq!(
+            storage.load_transcript_version(session_id, "v3").unwrap(),
+            v3
+        );
+        assert!(storage
+            .save_transcript_version(session_id, "v1", &v3)
+            .is_err());
+        assert!(storage
+            .save_transcript_version("missing-session", "v3", &v3)
+            .is_err());
+
+        // パス操作は拒否
+        assert!(transcript_file_name("../v2").is_err());
+        assert!(transcript_file_name("V2").is_err());
+        // 分割パート名は予約済み
+        assert!(transcript_file_name("part2").is_err());
+    }
+
+    #[test]
+    fn test_migrate_session_format() {
+        use super::*;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let storage = LocalStorageService::new(temp_dir.path().to_path_buf());
+        let session_id = "legacy-session";
+        let session_dir = storage.create_session(session_id).unwrap();
+
+        // 旧スキーマ（録音時）と現行スキーマ（再文字起こし）が混在
+        let legacy = include_str!("../tests/fixtures/transcripts/v1_speaker.jsonl");
+        let current = include_str!("../tests/fixtures/transcripts/v2.jsonl");
+        std::fs::write(session_dir.join(TRANSCRIPT_FILENAME), legacy).unwrap();
+        std::fs::write(session_dir.join("transcription.en.jsonl"), current).unwrap();
+        let before = storage.load_transcript(session_id).unwrap();
+
+        let migration = storage.migrate_session_format(session_id).unwrap();
+        assert_eq!(migration.migrated_files, vec![TRANSCRIPT_FILENAME]);
+        assert_eq!(migration.backup_files, vec!["transcription.jsonl.bak"]);
+        assert_eq!(migration.upgraded_lines, 3);
+
+        // 元ファイルは退避、移行後も同じ内容として読める
+        let backup = std::fs::read_to_string(session_dir.join("transcription.jsonl.bak")).unwrap();
+        assert_eq!(backup, legacy);
+        let migrated = std::fs::read_to_string(session_dir.join(TRANSCRIPT_FILENAME)).unwrap();
+        assert_eq!(migrated, current);
+        assert_eq!(storage.load_transcript(session_id).unwrap(), before);
+
+        // 移行済みなら何もしない
+        assert_eq!(
+            storage.migrate_session_format(session_id).unwrap(),
+            SessionFormatMigration::default()
+        );
+        assert!(storage.migrate_session_format("missing-session").is_err());
+    }
+
     // ================================================================================
     // Task 6.6: ディスク容量監視と警告機能テスト (RED)
     // Related requirement: STT-REQ-005.7, STT-REQ-005.8
@@ -1506,6 +3724,7 @@ mod tests {
             timestamp_ms: 1000,
             text: "Test".to_string(),
             is_final: true,
+            speaker: None,
         };
         transcript_writer.append_event(&event).unwrap();
         transcript_writer.close().unwrap();
@@ -1520,6 +3739,12 @@ mod tests {
             model_size: "small".to_string(),
             total_segments: 1,
             total_characters: 4,
+            warnings: Vec::new(),
+            session_uuid: None,
+            title: None,
+            tags: Vec::new(),
+            stats: None,
+            levels: None,
         };
         handle.save_metadata(&metadata).unwrap();
 
@@ -1547,4 +3772,378 @@ mod tests {
             assert!(result.is_ok());
         }
     }
+
+    #[test]
+    fn test_custom_recordings_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_data_dir = temp_dir.path().join("app");
+        let recordings_dir = temp_dir.path().join("external").join("minutes");
+        let storage =
+            LocalStorageService::with_recordings_dir(app_data_dir.clone(), recordings_dir.clone());
+
+        let session_dir = storage.create_session("s1").unwrap();
+        assert_eq!(session_dir, recordings_dir.join("s1"));
+        assert_eq!(storage.get_trash_dir(), recordings_dir.join(TRASH_DIR_NAME));
+        assert_eq!(storage.app_data_dir(), app_data_dir.as_path());
+        assert!(!app_data_dir.join("recordings").exists());
+    }
+
+    #[test]
+    fn test_flac_session_audio() {
+        let (storage, _temp_dir) = setup_test_service();
+        let session_dir = storage.create_session("flac").unwrap();
+        let samples: Vec<i16> = (0..20_000)
+            .map(|i| ((i as f64 * 0.05).sin() * 4000.0) as i16)
+            .collect();
+
+        let mut writer = storage
+            .create_session_audio_writer("flac", AudioFormat::Flac)
+            .unwrap();
+        assert_eq!(writer.format(), AudioFormat::Flac);
+        let bytes: Vec<u8> = samples[..5_000]
+            .iter()
+            .flat_map(|s| s.to_le_bytes())
+            .collect();
+        writer.write_pcm_bytes(&bytes).unwrap();
+        writer.write_samples(&samples[5_000..]).unwrap();
+        assert_eq!(writer.samples_written(), samples.len() as u64);
+        writer.close().unwrap();
+
+        assert_eq!(
+            session_audio_path(&session_dir),
+            Some(session_dir.join("audio.flac"))
+        );
+        let mut decoded = Vec::new();
+        read_session_audio(&session_dir, |chunk| decoded.extend_from_slice(chunk)).unwrap();
+        assert_eq!(decoded, samples);
+        assert!(
+            std::fs::metadata(session_dir.join("audio.flac"))
+                .unwrap()
+                .len()
+                < (samples.len() * 2) as u64
+        );
+    }
+
+    #[test]
+    fn test_encrypted_session_roundtrip() {
+        let (plain, _temp_dir) = setup_test_service();
+        let encryption = SessionEncryption {
+            cipher: SessionCipher::from_key(&[3u8; 32]),
+            seal_writes: true,
+        };
+        let storage = plain.clone().with_encryption(Some(encryption.clone()));
+        let session_dir = storage.create_session("secret").unwrap();
+
+        // Plaintext line written before encryption was enabled
+        let event = |timestamp_ms, text: &str| TranscriptionEvent {
+            timestamp_ms,
+            text: text.to_string(),
+            is_final: true,
+            speaker: None,
+        };
+        let mut writer = plain.create_transcript_writer("secret").unwrap();
+        writer.append_event(&event(0, "平文")).unwrap();
+        writer.close().unwrap();
+        let mut writer = storage.create_transcript_writer("secret").unwrap();
+        writer.append_event(&event(1000, "機密の議題")).unwrap();
+        writer.close().unwrap();
+        let metadata = SessionMetadata {
+            session_id: "secret".to_string(),
+            start_time: "2025-01-01T00:00:00.000Z".to_string(),
+            end_time: "2025-01-01T00:00:02.000Z".to_string(),
+            duration_seconds: 2,
+            audio_device: "機密マイク".to_string(),
+            model_size: "small".to_string(),
+            total_segments: 2,
+            total_characters: 7,
+            warnings: Vec::new(),
+            session_uuid: None,
+            title: None,
+            tags: Vec::new(),
+            stats: None,
+            levels: None,
+        };
+        storage.save_session_metadata(&metadata).unwrap();
+
+        let on_disk = std::fs::read_to_string(session_dir.join(TRANSCRIPT_FILENAME)).unwrap()
+            + &std::fs::read_to_string(session_dir.join(METADATA_FILENAME)).unwrap();
+        assert!(!on_disk.contains("機密"));
+
+        let loaded = storage.load_session("secret").unwrap();
+        assert_eq!(loaded.metadata, metadata);
+        let texts: Vec<_> = loaded.transcripts.iter().map(|e| e.text.as_str()).collect();
+        assert_eq!(texts, vec!["平文", "機密の議題"]);
+        assert_eq!(storage.list_sessions().unwrap(), vec![metadata]);
+
+        // Without the key, sealed records cannot be read
+        assert!(plain.load_session("secret").is_err());
+        let reader = plain.with_encryption(Some(SessionEncryption {
+            seal_writes: false,
+            ..encryption
+        }));
+        assert_eq!(reader.load_transcript("secret").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_trash_restore_and_purge() {
+        let (storage, _temp_dir) = setup_test_service();
+        for id in ["keep", "old", "recent"] {
+            std::fs::create_dir_all(storage.get_session_dir(id)).unwrap();
+            std::fs::write(storage.get_session_dir(id).join("audio.wav"), b"RIFF").unwrap();
+        }
+
+        // 削除 → ゴミ箱へ移動（一覧からは消える）
+        storage.trash_session("old", 1_000).unwrap();
+        let trashed = storage.trash_session("recent", 5_000).unwrap();
+        assert_eq!(trashed.deleted_at_ms, 5_000);
+        assert!(!storage.get_session_dir("recent").exists());
+        assert!(storage.get_trash_dir().join("recent/audio.wav").exists());
+        let ids: Vec<String> = storage
+            .list_trashed_sessions()
+            .unwrap()
+            .into_iter()
+            .map(|t| t.session_id)
+            .collect();
+        assert_eq!(ids, vec!["recent", "old"]);
+
+        // 不正なID・存在しないセッションはエラー
+        assert!(storage.trash_session("../keep", 0).is_err());
+        assert!(storage.trash_session("missing", 0).is_err());
+
+        // 復元（削除記録は残らない）
+        storage.restore_session("recent").unwrap();
+        assert!(storage.get_session_dir("recent").join("audio.wav").exists());
+        assert!(!storage
+            .get_session_dir("recent")
+            .join("trash.json")
+            .exists());
+        assert!(storage.restore_session("recent").is_err());
+
+        // 保持期間切れのみ完全削除
+        storage.trash_session("recent", 5_000).unwrap();
+        assert_eq!(storage.purge_trash(3_000, 6_000).unwrap(), vec!["old"]);
+        assert_eq!(storage.list_trashed_sessions().unwrap().len(), 1);
+        assert!(storage.get_session_dir("keep").exists());
+    }
+
+    #[test]
+    fn test_export_session_markdown() {
+        let (storage, _temp_dir) = setup_test_service();
+        let session_id = "2025-10-13T10-00_abcd1234";
+        std::fs::create_dir_all(storage.get_session_dir(session_id)).unwrap();
+        storage
+            .save_session_metadata(&SessionMetadata {
+                session_id: session_id.to_string(),
+                start_time: "2025-10-13T10:00:00Z".to_string(),
+                end_time: "2025-10-13T11:02:03Z".to_string(),
+                duration_seconds: 3723,
+                audio_device: "default".to_string(),
+                model_size: "small".to_string(),
+                total_segments: 2,
+                total_characters: 12,
+                warnings: vec!["強制終了".to_string()],
+                session_uuid: None,
+                title: None,
+                tags: Vec::new(),
+                stats: None,
+                levels: None,
+            })
+            .unwrap();
+        let mut writer = storage.create_transcript_writer(session_id).unwrap();
+        for (timestamp_ms, text, is_final, speaker) in [
+            (65_000, "予算の話", true, None),
+            (66_000, "途中", false, None),
+            (3_661_000, "*重要* な決定", true, Some("話者A")),
+            (
+                3_700_000,
+                "次回は金曜です。資料は事前に共有します",
+                true,
+                None,
+            ),
+        ] {
+            writer
+                .append_event(&TranscriptionEvent {
+                    timestamp_ms,
+                    text: text.to_string(),
+                    is_final,
+                    speaker: speaker.map(str::to_string),
+                })
+                .unwrap();
+        }
+        writer.close().unwrap();
+
+        let path = storage.export_session_markdown(session_id, None).unwrap();
+        assert!(path.ends_with(MARKDOWN_EXPORT_FILENAME));
+        let markdown = std::fs::read_to_string(path).unwrap();
+        assert!(markdown.starts_with("# 議事録: 2025-10-13T10-00\\_abcd1234\n"));
+        assert!(markdown.contains("- 録音時間: 01:02:03\n"));
+        assert!(markdown.contains("- 警告: 強制終了\n"));
+        assert!(markdown.contains("\n**[00:01:05]** 予算の話\n"));
+        assert!(markdown.contains("\n**[01:01:01] 話者A:** \\*重要\\* な決定\n"));
+        assert!(
+            markdown.contains("\n**[01:01:40]**\n\n- 次回は金曜です。\n- 資料は事前に共有します\n")
+        );
+        assert!(!markdown.contains("途中"));
+
+        assert!(storage.export_session_markdown("../escape", None).is_err());
+
+        // Furigana on rare kanji (決 and 資 are not first/second-grade kanji)
+        let furigana = Furigana::new(
+            [
+                ("決定".to_string(), "ケッテイ".to_string()),
+                ("資料".to_string(), "しりょう".to_string()),
+            ],
+            "",
+        );
+        let path = storage
+            .export_session_markdown(session_id, Some(&furigana))
+            .unwrap();
+        let markdown = std::fs::read_to_string(path).unwrap();
+        assert!(markdown.contains("\\*重要\\* な<ruby>決定<rt>けってい</rt></ruby>\n"));
+        assert!(markdown.contains("- <ruby>資料<rt>しりょう</rt></ruby>は事前に共有します\n"));
+
+        // Side-by-side layout with a translated transcript version
+        let translations = [
+            (65_500, "About the budget"),
+            (3_661_000, "An *important* decision"),
+            (3_700_000, "Next meeting is on Friday."),
+            (3_702_000, "Materials will be shared in advance"),
+        ]
+        .map(|(timestamp_ms, text)| {
+            serde_json::to_string(&TranscriptionEvent {
+                timestamp_ms,
+                text: text.to_string(),
+                is_final: true,
+                speaker: None,
+            })
+            .unwrap()
+        })
+        .join("\n");
+        std::fs::write(
+            storage
+                .get_session_dir(session_id)
+                .join(transcript_file_name("en").unwrap()),
+            translations,
+        )
+        .unwrap();
+        let path = storage
+            .export_session_bilingual_markdown(session_id, "en", None)
+            .unwrap();
+        assert!(path.ends_with(BILINGUAL_MARKDOWN_EXPORT_FILENAME));
+        let markdown = std::fs::read_to_string(path).unwrap();
+        assert!(markdown.contains("- 録音時間: 01:02:03\n"));
+        assert!(markdown.contains("| [00:01:05] | 予算の話 | About the budget |\n"));
+        assert!(markdown
+            .contains("| [01:01:01] 話者A | \\*重要\\* な決定 | An \\*important\\* decision |\n"));
+        assert!(markdown.contains(
+            "| [01:01:40] | 次回は金曜です。<br>資料は事前に共有します | \
+             Next meeting is on Friday.<br>Materials will be shared in advance |\n"
+        ));
+
+        let path = storage
+            .export_session_bilingual_html(session_id, "en", Some(&furigana))
+            .unwrap();
+        assert!(path.ends_with(BILINGUAL_HTML_EXPORT_FILENAME));
+        let html = std::fs::read_to_string(path).unwrap();
+        assert!(html.contains("<li>録音時間: 01:02:03</li>\n"));
+        assert!(html.contains("<tr><th>時刻</th><th>原文</th><th>訳文</th></tr>\n"));
+        assert!(html
+            .contains("<tr><td>[00:01:05]</td><td>予算の話</td><td>About the budget</td></tr>\n"));
+        assert!(html.contains(
+            "<tr><td>[01:01:01] 話者A</td><td>*重要* な<ruby>決定<rt>けってい</rt></ruby></td>\
+             <td>An *important* decision</td></tr>\n"
+        ));
+        assert!(html.contains(
+            "<td>次回は金曜です。<br><ruby>資料<rt>しりょう</rt></ruby>は事前に共有します</td>\
+             <td>Next meeting is on Friday.<br>Materials will be shared in advance</td>"
+        ));
+
+        let path = storage
+            .export_session_bilingual_docx(session_id, "en", Some(&furigana))
+            .unwrap();
+        assert!(path.ends_with(BILINGUAL_DOCX_EXPORT_FILENAME));
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(path).unwrap()).unwrap();
+        let mut document = String::new();
+        std::io::Read::read_to_string(
+            &mut archive.by_name("word/document.xml").unwrap(),
+            &mut document,
+        )
+        .unwrap();
+        assert_eq!(document.matches("<w:tr>").count(), 4);
+        assert!(document.contains(">[01:01:01] 話者A</w:t>"));
+        assert!(document.contains("<w:t>けってい</w:t>"));
+        assert!(document.contains(">An *important* decision</w:t>"));
+        assert!(document.contains(
+            ">Next meeting is on Friday.</w:t></w:r></w:p><w:p><w:r><w:t xml:space=\"preserve\">Materials will be shared in advance</w:t>"
+        ));
+
+        assert!(storage
+            .export_session_bilingual_markdown(session_id, "v1", None)
+            .is_err());
+        assert!(storage
+            .export_session_bilingual_markdown(session_id, "missing", None)
+            .is_err());
+        assert!(storage
+            .export_session_bilingual_docx(session_id, "v1", None)
+            .is_err());
+
+        // Plain text and DOCX
+        let path = storage.export_session_text(session_id, None).unwrap();
+        assert!(path.ends_with(TEXT_EXPORT_FILENAME));
+        let text = std::fs::read_to_string(path).unwrap();
+        assert!(text.starts_with("議事録: 2025-10-13T10-00_abcd1234\n"));
+        assert!(text.contains("\n録音時間: 01:02:03\n"));
+        assert!(text.contains("\n[00:01:05] 予算の話\n"));
+        assert!(text.contains("\n[01:01:01] 話者A: *重要* な決定\n"));
+        assert!(!text.contains("途中"));
+
+        let path = storage
+            .export_session_text(session_id, Some(&furigana))
+            .unwrap();
+        let text = std::fs::read_to_string(path).unwrap();
+        assert!(text.contains("\n[01:01:01] 話者A: *重要* な決定（けってい）\n"));
+
+        let path = storage.export_session_docx(session_id, None).unwrap();
+        assert!(path.ends_with(DOCX_EXPORT_FILENAME));
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(path).unwrap()).unwrap();
+        let mut document = String::new();
+        std::io::Read::read_to_string(
+            &mut archive.by_name("word/document.xml").unwrap(),
+            &mut document,
+        )
+        .unwrap();
+        assert!(document.contains(">[01:01:01] 話者A:</w:t>"));
+        assert!(document.contains("> 次回は金曜です。資料は事前に共有します</w:t>"));
+
+        let path = storage
+            .export_session_docx(session_id, Some(&furigana))
+            .unwrap();
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(path).unwrap()).unwrap();
+        let mut document = String::new();
+        std::io::Read::read_to_string(
+            &mut archive.by_name("word/document.xml").unwrap(),
+            &mut document,
+        )
+        .unwrap();
+        assert!(document.contains("<w:t>けってい</w:t>"));
+        assert!(document.contains(
+            "<w:rubyBase><w:r><w:t xml:space=\"preserve\">決定</w:t></w:r></w:rubyBase>"
+        ));
+
+        // HTML with ruby
+        let path = storage
+            .export_session_html(session_id, Some(&furigana))
+            .unwrap();
+        assert!(path.ends_with(HTML_EXPORT_FILENAME));
+        let html = std::fs::read_to_string(path).unwrap();
+        assert!(html.starts_with("<!DOCTYPE html>\n"));
+        assert!(html.contains("<h1>議事録: 2025-10-13T10-00_abcd1234</h1>\n"));
+        assert!(html.contains("<li>録音時間: 01:02:03</li>\n"));
+        assert!(html.contains("<p><strong>[00:01:05]</strong> 予算の話</p>\n"));
+        assert!(html.contains(
+            "<p><strong>[01:01:01] 話者A:</strong> *重要* な<ruby>決定<rt>けってい</rt></ruby></p>\n"
+        ));
+        assert!(html.contains("<li><ruby>資料<rt>しりょう</rt></ruby>は事前に共有します</li>\n"));
+        assert!(!html.contains("途中"));
+    }
 }
@@ -0,0 +1,255 @@
+//! Storage Root Settings
+//!
+//! Recordings live in `[app_data_dir]/recordings` unless a custom root
+//! (e.g. a folder on an external drive) is configured. Settings, logs and
+//! diagnostics stay in the app data directory either way.
+//!
+//! Changing the root validates the new location first and can move the
+//! existing recordings (sessions and the trash) over. Moves use `rename`
+//! and fall back to copy + delete across filesystems; a session already
+//! present at the destination is left in place and reported.
+//!
+//! Persisted to `settings/storage_root.json` in app data directory.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Storage root configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageRootSettings {
+    /// Recordings directory (None = `[app_data_dir]/recordings`)
+    #[serde(default)]
+    pub recordings_root: Option<PathBuf>,
+    /// Settings version for future migrations
+    #[serde(default = "default_version")]
+    pub version: u32,
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+impl Default for StorageRootSettings {
+    fn default() -> Self {
+        Self {
+            recordings_root: None,
+            version: 1,
+        }
+    }
+}
+
+impl StorageRootSettings {
+    /// Effective recordings directory
+    pub fn recordings_dir(&self, app_data_dir: &Path) -> PathBuf {
+        self.recordings_root
+            .clone()
+            .unwrap_or_else(|| default_recordings_dir(app_data_dir))
+    }
+}
+
+pub fn default_recordings_dir(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join("recordings")
+}
+
+/// Check that `dir` can hold recordings
+///
+/// The directory must be absolute and writable; it is created if missing.
+pub fn validate_root(dir: &Path) -> Result<()> {
+    if !dir.is_absolute() {
+        anyhow::bail!("Storage root must be an absolute path: {:?}", dir);
+    }
+    if dir.exists() && !dir.is_dir() {
+        anyhow::bail!("Storage root is not a directory: {:?}", dir);
+    }
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create storage root: {:?}", dir))?;
+
+    let probe = dir.join(".write_test");
+    std::fs::write(&probe, b"ok")
+        .with_context(|| format!("Storage root is not writable: {:?}", dir))?;
+    let _ = std::fs::remove_file(&probe);
+    Ok(())
+}
+
+/// Outcome of moving recordings to a new root
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct MigrationReport {
+    /// Entries (session IDs, `.trash`) moved
+    pub moved: Vec<String>,
+    /// Entries left in the old root because the name exists in the new one
+    pub conflicts: Vec<String>,
+}
+
+/// Move every entry of `from` into `to`
+///
+/// `to` must not be inside `from` (or vice versa). Stops at the first I/O
+/// error; entries moved so far stay moved and are listed in the error.
+pub fn migrate_recordings(from: &Path, to: &Path) -> Result<MigrationReport> {
+    let mut report = MigrationReport::default();
+    if !from.exists() || same_dir(from, to) {
+        return Ok(report);
+    }
+    if to.starts_with(from) || from.starts_with(to) {
+        anyhow::bail!("Storage roots must not be nested: {:?} and {:?}", from, to);
+    }
+    std::fs::create_dir_all(to)?;
+
+    let mut entries: Vec<PathBuf> = std::fs::read_dir(from)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .collect();
+    entries.sort();
+
+    for source in entries {
+        let Some(name) = source.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        let destination = to.join(name);
+        if destination.exists() {
+            report.conflicts.push(name.to_string());
+            continue;
+        }
+        move_entry(&source, &destination).with_context(|| {
+            format!(
+                "Failed to move {:?} (already moved: {})",
+                source,
+                report.moved.join(", ")
+            )
+        })?;
+        report.moved.push(name.to_string());
+    }
+    Ok(report)
+}
+
+fn same_dir(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+/// Rename, or copy + delete when crossing filesystems
+pub(crate) fn move_entry(source: &Path, destination: &Path) -> Result<()> {
+    if std::fs::rename(source, destination).is_ok() {
+        return Ok(());
+    }
+    if let Err(e) = copy_recursive(source, destination) {
+        // Leave the source intact; drop the partial copy
+        let _ = if destination.is_dir() {
+            std::fs::remove_dir_all(destination)
+        } else {
+            std::fs::remove_file(destination)
+        };
+        return Err(e);
+    }
+    if source.is_dir() {
+        std::fs::remove_dir_all(source)?;
+    } else {
+        std::fs::remove_file(source)?;
+    }
+    Ok(())
+}
+
+fn copy_recursive(source: &Path, destination: &Path) -> Result<()> {
+    if source.is_dir() {
+        std::fs::create_dir_all(destination)?;
+        for entry in std::fs::read_dir(source)? {
+            let entry = entry?;
+            copy_recursive(&entry.path(), &destination.join(entry.file_name()))?;
+        }
+    } else {
+        std::fs::copy(source, destination)
+            .with_context(|| format!("Failed to copy {:?}", source))?;
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+pub const SETTINGS_STEM: &str = "storage_root";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_validate_root() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path().join("external").join("recordings");
+        validate_root(&root).unwrap();
+        assert!(root.is_dir());
+        assert!(!root.join(".write_test").exists());
+
+        assert!(validate_root(Path::new("relative/recordings")).is_err());
+        let file = dir.path().join("file");
+        std::fs::write(&file, "x").unwrap();
+        assert!(validate_root(&file).is_err());
+    }
+
+    #[test]
+    fn test_migrate_recordings_moves_sessions_and_reports_conflicts() {
+        let dir = TempDir::new().unwrap();
+        let from = dir.path().join("old");
+        let to = dir.path().join("new");
+        for id in ["s1", "s2", ".trash/s0"] {
+            std::fs::create_dir_all(from.join(id)).unwrap();
+            std::fs::write(from.join(id).join("session.json"), id).unwrap();
+        }
+        std::fs::create_dir_all(to.join("s2")).unwrap();
+
+        let report = migrate_recordings(&from, &to).unwrap();
+        assert_eq!(report.moved, vec![".trash", "s1"]);
+        assert_eq!(report.conflicts, vec!["s2"]);
+        assert_eq!(
+            std::fs::read_to_string(to.join(".trash/s0/session.json")).unwrap(),
+            ".trash/s0"
+        );
+        assert!(to.join("s1/session.json").exists());
+        assert!(from.join("s2/session.json").exists());
+
+        // Nested roots are refused
+        assert!(migrate_recordings(&to, &to.join("inner")).is_err());
+        // Missing source is a no-op
+        assert_eq!(
+            migrate_recordings(&dir.path().join("missing"), &to).unwrap(),
+            MigrationReport::default()
+        );
+    }
+
+    #[test]
+    fn test_copy_fallback() {
+        let dir = TempDir::new().unwrap();
+        let source = dir.path().join("a");
+        std::fs::create_dir_all(source.join("debug")).unwrap();
+        std::fs::write(source.join("debug").join("x.wav"), [1u8, 2]).unwrap();
+        let destination = dir.path().join("b");
+
+        copy_recursive(&source, &destination).unwrap();
+        assert_eq!(
+            std::fs::read(destination.join("debug").join("x.wav")).unwrap(),
+            vec![1u8, 2]
+        );
+    }
+
+    #[test]
+    fn test_recordings_dir() {
+        let data_dir = Path::new("/data");
+        assert_eq!(
+            StorageRootSettings::default().recordings_dir(data_dir),
+            data_dir.join("recordings")
+        );
+
+        let settings = StorageRootSettings {
+            recordings_root: Some(PathBuf::from("/mnt/external/minutes")),
+            ..Default::default()
+        };
+        assert_eq!(
+            settings.recordings_dir(data_dir),
+            PathBuf::from("/mnt/external/minutes")
+        );
+    }
+}
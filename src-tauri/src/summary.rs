@@ -0,0 +1,540 @@
+//! Meeting Summarization
+//!
+//! Summaries are generated by an LLM backend and streamed back while tokens
+//! arrive, so the first sections of a long meeting's summary are readable
+//! within seconds. Tokens are coalesced into `summary_partial` deltas (at most
+//! one per [`PARTIAL_FLUSH_INTERVAL`]), followed by a single
+//! `summary_complete` carrying the full text.
+//!
+//! Optional live mode keeps a rolling summary during the recording: every
+//! `live_interval_minutes` of final text, the running summary is updated with
+//! the new excerpt and broadcast as `live_summary`.
+//!
+//! Backend: Ollama-compatible `/api/generate` endpoint (streamed JSON Lines).
+//!
+//! Persisted to `settings/summary.json` in app data directory.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::storage::TranscriptionEvent;
+
+/// Minimum interval between two `summary_partial` events
+pub const PARTIAL_FLUSH_INTERVAL: Duration = Duration::from_millis(150);
+
+/// Summary file written into the session directory
+pub const SUMMARY_FILENAME: &str = "summary.md";
+
+// ============================================================================
+// Settings Struct
+// ============================================================================
+
+/// Summarization backend configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SummarySettings {
+    /// Streaming generate endpoint (Ollama `/api/generate` compatible)
+    #[serde(default = "default_endpoint")]
+    pub endpoint: String,
+    #[serde(default = "default_model")]
+    pub model: String,
+    /// Instruction placed before the transcript
+    #[serde(default = "default_instruction")]
+    pub instruction: String,
+    /// Keep a rolling summary while recording
+    #[serde(default)]
+    pub live_enabled: bool,
+    /// Minutes of final text between rolling summary updates
+    #[serde(default = "default_live_interval_minutes")]
+    pub live_interval_minutes: u32,
+    /// Settings version for future migrations
+    #[serde(default = "default_version")]
+    pub version: u32,
+}
+
+fn default_endpoint() -> String {
+    "http://localhost:11434/api/generate".to_string()
+}
+
+fn default_model() -> String {
+    "llama3.1".to_string()
+}
+
+fn default_instruction() -> String {
+    "Summarize the following meeting transcript in the language of the transcript. \
+     Use Markdown sections: Overview, Decisions, Action Items."
+        .to_string()
+}
+
+fn default_live_interval_minutes() -> u32 {
+    5
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+impl Default for SummarySettings {
+    fn default() -> Self {
+        Self {
+            endpoint: default_endpoint(),
+            model: default_model(),
+            instruction: default_instruction(),
+            live_enabled: false,
+            live_interval_minutes: default_live_interval_minutes(),
+            version: 1,
+        }
+    }
+}
+
+impl SummarySettings {
+    pub fn validate(&self) -> Result<()> {
+        if !(self.endpoint.starts_with("http://") || self.endpoint.starts_with("https://")) {
+            anyhow::bail!(
+                "Summary endpoint must start with http:// or https://: {}",
+                self.endpoint
+            );
+        }
+        if self.model.trim().is_empty() {
+            anyhow::bail!("Summary model must not be empty");
+        }
+        if self.live_interval_minutes == 0 {
+            anyhow::bail!("Live summary interval must be at least 1 minute");
+        }
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Prompt
+// ============================================================================
+
+/// Format final segments as `[mm:ss] text` lines
+pub fn format_transcript(events: &[TranscriptionEvent]) -> String {
+    events
+        .iter()
+        .filter(|e| e.is_final && !e.text.trim().is_empty())
+        .map(|e| {
+            let secs = e.timestamp_ms / 1000;
+            format!("[{:02}:{:02}] {}", secs / 60, secs % 60, e.text.trim())
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Build the prompt sent to the backend
+pub fn build_prompt(settings: &SummarySettings, transcript: &str) -> String {
+    format!(
+        "{}\n\nTranscript:\n{}",
+        settings.instruction.trim(),
+        transcript
+    )
+}
+
+/// Build the prompt that folds a new excerpt into the running summary
+pub fn build_rolling_prompt(previous_summary: &str, excerpt: &str) -> String {
+    let previous = if previous_summary.trim().is_empty() {
+        "(none yet)"
+    } else {
+        previous_summary.trim()
+    };
+    format!(
+        "You maintain a running summary of an ongoing meeting for participants who join late. \
+         Update the summary with the new transcript excerpt, in the language of the transcript. \
+         Keep it concise Markdown with sections: Overview, Decisions, Action Items, Open Topics. \
+         Reply with the updated summary only.\n\n\
+         Current summary:\n{}\n\nNew transcript excerpt:\n{}",
+        previous, excerpt
+    )
+}
+
+// ============================================================================
+// Rolling Summary
+// ============================================================================
+
+/// Rolling summary update to run against the backend
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RollingUpdate {
+    pub previous_summary: String,
+    /// New final text since the last update (`[mm:ss] text` lines)
+    pub excerpt: String,
+    /// Session time covered once this update is applied (ms)
+    pub covered_until_ms: u64,
+}
+
+/// Running summary of the active session
+///
+/// Final segments accumulate until they span the configured interval; then a
+/// single update is handed out. Segments arriving while it runs are kept for
+/// the next update.
+#[derive(Debug, Clone, Serialize)]
+pub struct RollingSummary {
+    /// Latest summary text (empty before the first update)
+    pub text: String,
+    /// Session time covered by `text` (ms)
+    pub covered_until_ms: u64,
+    /// Number of completed updates
+    pub update_count: u32,
+    #[serde(skip)]
+    interval_ms: u64,
+    #[serde(skip)]
+    pending: Vec<String>,
+    #[serde(skip)]
+    pending_since_ms: Option<u64>,
+    #[serde(skip)]
+    pending_until_ms: u64,
+    #[serde(skip)]
+    in_flight: bool,
+}
+
+impl RollingSummary {
+    pub fn new(interval_minutes: u32) -> Self {
+        Self {
+            text: String::new(),
+            covered_until_ms: 0,
+            update_count: 0,
+            interval_ms: interval_minutes as u64 * 60_000,
+            pending: Vec::new(),
+            pending_since_ms: None,
+            pending_until_ms: 0,
+            in_flight: false,
+        }
+    }
+
+    /// Add a final segment; returns an update once enough text has accumulated
+    pub fn push_final(&mut self, timestamp_ms: u64, text: &str) -> Option<RollingUpdate> {
+        let text = text.trim();
+        if !text.is_empty() {
+            let secs = timestamp_ms / 1000;
+            self.pending
+                .push(format!("[{:02}:{:02}] {}", secs / 60, secs % 60, text));
+            self.pending_since_ms.get_or_insert(timestamp_ms);
+            self.pending_until_ms = self.pending_until_ms.max(timestamp_ms);
+        }
+
+        let since = self.pending_since_ms?;
+        if self.in_flight || self.pending_until_ms.saturating_sub(since) < self.interval_ms {
+            return None;
+        }
+
+        self.in_flight = true;
+        self.pending_since_ms = None;
+        Some(RollingUpdate {
+            previous_summary: self.text.clone(),
+            excerpt: std::mem::take(&mut self.pending).join("\n"),
+            covered_until_ms: self.pending_until_ms,
+        })
+    }
+
+    /// Approximate heap usage (bytes), for the memory sentinel
+    pub fn approx_bytes(&self) -> usize {
+        self.text.capacity()
+            + self
+                .pending
+                .iter()
+                .map(|s| std::mem::size_of::<String>() + s.capacity())
+                .sum::<usize>()
+    }
+
+    /// Apply a finished update
+    pub fn complete(&mut self, update: &RollingUpdate, text: String) {
+        self.text = text;
+        self.covered_until_ms = update.covered_until_ms;
+        self.update_count += 1;
+        self.in_flight = false;
+    }
+
+    /// Return a failed update's excerpt to the queue (retried with the next one)
+    pub fn fail(&mut self, update: RollingUpdate) {
+        let mut pending = update
+            .excerpt
+            .lines()
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        pending.append(&mut self.pending);
+        self.pending = pending;
+        if !self.pending.is_empty() {
+            // Retry is due as soon as the next segment arrives
+            let due_since = update.covered_until_ms.saturating_sub(self.interval_ms);
+            self.pending_since_ms = Some(
+                self.pending_since_ms
+                    .map_or(due_since, |since| since.min(due_since)),
+            );
+        }
+        self.in_flight = false;
+    }
+}
+
+// ============================================================================
+// Streaming
+// ============================================================================
+
+#[derive(Debug, Serialize)]
+struct GenerateRequest<'a> {
+    model: &'a str,
+    prompt: &'a str,
+    stream: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct GenerateChunk {
+    #[serde(default)]
+    response: String,
+    #[serde(default)]
+    done: bool,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+/// Splits a byte stream into complete lines
+///
+/// Bytes are buffered until a newline, so multi-byte characters split across
+/// network chunks are decoded intact.
+#[derive(Debug, Default)]
+pub struct LineDecoder {
+    buffer: Vec<u8>,
+}
+
+impl LineDecoder {
+    /// Feed a chunk, returning every line completed by it
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<String> {
+        self.buffer.extend_from_slice(chunk);
+        let mut lines = Vec::new();
+        while let Some(pos) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=pos).collect();
+            let line = String::from_utf8_lossy(&line[..pos]).trim().to_string();
+            if !line.is_empty() {
+                lines.push(line);
+            }
+        }
+        lines
+    }
+
+    /// Remaining unterminated line, if any
+    pub fn finish(&mut self) -> Option<String> {
+        let line = String::from_utf8_lossy(&std::mem::take(&mut self.buffer))
+            .trim()
+            .to_string();
+        (!line.is_empty()).then_some(line)
+    }
+}
+
+/// Coalesces streamed tokens into partial deltas
+#[derive(Debug)]
+pub struct PartialBuffer {
+    pending: String,
+    last_flush: Instant,
+    interval: Duration,
+}
+
+impl PartialBuffer {
+    pub fn new(interval: Duration, now: Instant) -> Self {
+        Self {
+            pending: String::new(),
+            last_flush: now,
+            interval,
+        }
+    }
+
+    /// Add a token; returns a delta once the flush interval has passed
+    /// or a section boundary (blank line) was reached
+    pub fn push(&mut self, token: &str, now: Instant) -> Option<String> {
+        self.pending.push_str(token);
+        let section_end = self.pending.ends_with("\n\n");
+        if !self.pending.is_empty()
+            && (section_end || now.duration_since(self.last_flush) >= self.interval)
+        {
+            self.last_flush = now;
+            return Some(std::mem::take(&mut self.pending));
+        }
+        None
+    }
+
+    /// Flush whatever is left at the end of the stream
+    pub fn finish(&mut self) -> Option<String> {
+        (!self.pending.is_empty()).then(|| std::mem::take(&mut self.pending))
+    }
+}
+
+/// Stream a completion from the backend
+///
+/// `on_delta` receives coalesced deltas as they arrive; the full text is
+/// returned once the backend reports `done`.
+pub async fn stream_completion<F>(
+    client: &reqwest::Client,
+    settings: &SummarySettings,
+    prompt: &str,
+    mut on_delta: F,
+) -> Result<String>
+where
+    F: FnMut(&str),
+{
+    let mut response = client
+        .post(&settings.endpoint)
+        .json(&GenerateRequest {
+            model: &settings.model,
+            prompt,
+            stream: true,
+        })
+        .send()
+        .await
+        .context("Failed to reach summary backend")?
+        .error_for_status()
+        .context("Summary backend returned an error status")?;
+
+    let mut decoder = LineDecoder::default();
+    let mut partials = PartialBuffer::new(PARTIAL_FLUSH_INTERVAL, Instant::now());
+    let mut text = String::new();
+
+    let mut handle_line = |line: &str, text: &mut String| -> Result<bool> {
+        let chunk: GenerateChunk =
+            serde_json::from_str(line).context("Failed to parse summary backend chunk")?;
+        if let Some(error) = chunk.error {
+            anyhow::bail!("Summary backend error: {}", error);
+        }
+        text.push_str(&chunk.response);
+        if let Some(delta) = partials.push(&chunk.response, Instant::now()) {
+            on_delta(&delta);
+        }
+        Ok(chunk.done)
+    };
+
+    'stream: while let Some(bytes) = response
+        .chunk()
+        .await
+        .context("Summary stream interrupted")?
+    {
+        for line in decoder.push(&bytes) {
+            if handle_line(&line, &mut text)? {
+                break 'stream;
+            }
+        }
+    }
+    if let Some(line) = decoder.finish() {
+        handle_line(&line, &mut text)?;
+    }
+
+    if let Some(delta) = partials.finish() {
+        on_delta(&delta);
+    }
+    Ok(text)
+}
+
+/// Write the finished summary to `<session_dir>/summary.md`
+pub fn save_summary(session_dir: &Path, text: &str) -> Result<()> {
+    let path = session_dir.join(SUMMARY_FILENAME);
+    std::fs::write(&path, text).with_context(|| format!("Failed to write summary: {:?}", path))
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+pub const SETTINGS_STEM: &str = "summary";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_decoder_handles_split_chunks() {
+        let mut decoder = LineDecoder::default();
+        let line = "{\"response\":\"決定\",\"done\":false}\n".as_bytes();
+        // Split inside the multi-byte character
+        let (first, second) = line.split_at(15);
+
+        assert!(decoder.push(first).is_empty());
+        let lines = decoder.push(second);
+        assert_eq!(lines, vec!["{\"response\":\"決定\",\"done\":false}"]);
+
+        assert!(decoder.push(b"{\"done\":true}").is_empty());
+        assert_eq!(decoder.finish().as_deref(), Some("{\"done\":true}"));
+        assert_eq!(decoder.finish(), None);
+    }
+
+    #[test]
+    fn test_partial_buffer_coalesces_tokens() {
+        let start = Instant::now();
+        let mut buffer = PartialBuffer::new(Duration::from_millis(150), start);
+
+        assert_eq!(buffer.push("## Over", start), None);
+        assert_eq!(
+            buffer.push("view", start + Duration::from_millis(200)),
+            Some("## Overview".to_string())
+        );
+        // Section boundary flushes immediately
+        let t = start + Duration::from_millis(210);
+        assert_eq!(
+            buffer.push("Budget.\n\n", t),
+            Some("Budget.\n\n".to_string())
+        );
+        assert_eq!(buffer.push("## Dec", t), None);
+        assert_eq!(buffer.finish(), Some("## Dec".to_string()));
+        assert_eq!(buffer.finish(), None);
+    }
+
+    #[test]
+    fn test_format_transcript_uses_final_segments() {
+        let events = vec![
+            TranscriptionEvent {
+                timestamp_ms: 65_000,
+                text: "予算の話".to_string(),
+                is_final: true,
+                speaker: None,
+            },
+            TranscriptionEvent {
+                timestamp_ms: 66_000,
+                text: "途中".to_string(),
+                is_final: false,
+                speaker: None,
+            },
+        ];
+        assert_eq!(format_transcript(&events), "[01:05] 予算の話");
+    }
+
+    #[test]
+    fn test_rolling_summary_updates_every_interval() {
+        let mut rolling = RollingSummary::new(5);
+        assert!(rolling.push_final(0, "開始します").is_none());
+        assert!(rolling.push_final(240_000, "予算の話").is_none());
+
+        let update = rolling.push_final(300_000, "予算は承認").unwrap();
+        assert_eq!(update.previous_summary, "");
+        assert_eq!(update.excerpt.lines().count(), 3);
+        assert_eq!(update.covered_until_ms, 300_000);
+
+        // Segments during the update wait for the next one
+        assert!(rolling.push_final(700_000, "次の議題").is_none());
+        rolling.complete(&update, "## Overview\n予算".to_string());
+        assert_eq!(rolling.covered_until_ms, 300_000);
+        assert_eq!(rolling.update_count, 1);
+
+        let next = rolling.push_final(1_000_000, "まとめ").unwrap();
+        assert_eq!(next.previous_summary, "## Overview\n予算");
+        assert_eq!(next.excerpt.lines().count(), 2);
+    }
+
+    #[test]
+    fn test_rolling_summary_retries_failed_update() {
+        let mut rolling = RollingSummary::new(1);
+        rolling.push_final(0, "a");
+        let update = rolling.push_final(60_000, "b").unwrap();
+        rolling.fail(update);
+
+        // Failed excerpt is kept and retried with the next segment
+        let retry = rolling.push_final(61_000, "c").unwrap();
+        assert_eq!(retry.excerpt.lines().count(), 3);
+        assert!(retry.excerpt.ends_with("c"));
+    }
+
+    #[test]
+    fn test_settings_validation() {
+        assert!(SummarySettings::default().validate().is_ok());
+        let invalid = SummarySettings {
+            endpoint: "localhost:11434".to_string(),
+            ..Default::default()
+        };
+        assert!(invalid.validate().is_err());
+    }
+}
@@ -0,0 +1,102 @@
+//! Task Supervision
+//!
+//! Spawns pipeline tasks (IPC reader, audio sender, heartbeat) with panic
+//! isolation. A panic is caught at the task boundary, logged, and handed to a
+//! recovery callback instead of silently killing the task.
+
+use futures_util::FutureExt;
+use serde_json::json;
+use std::any::Any;
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use tokio::task::JoinHandle;
+
+/// Extract a readable message from a panic payload
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
+/// Spawn a supervised task
+///
+/// `on_panic` runs inside the same tokio task after the panic is caught.
+/// Recovery that tears down recording tasks (and may abort this one) must
+/// therefore spawn its own task.
+pub fn spawn_supervised<F, H, HF>(task_name: &'static str, future: F, on_panic: H) -> JoinHandle<()>
+where
+    F: Future<Output = ()> + Send + 'static,
+    H: FnOnce(String) -> HF + Send + 'static,
+    HF: Future<Output = ()> + Send + 'static,
+{
+    tokio::spawn(async move {
+        if let Err(payload) = AssertUnwindSafe(future).catch_unwind().await {
+            let message = panic_message(payload.as_ref());
+            log_error_details!(
+                "task_supervisor",
+                "task_panicked",
+                json!({
+                    "task": task_name,
+                    "panic": message
+                })
+            );
+            on_panic(message).await;
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    #[tokio::test]
+    async fn test_panic_invokes_recovery() {
+        let recovered = Arc::new(Mutex::new(None));
+        let recovered_clone = Arc::clone(&recovered);
+
+        let handle = spawn_supervised(
+            "test_task",
+            async {
+                panic!("boom");
+            },
+            move |message| async move {
+                *recovered_clone.lock().unwrap() = Some(message);
+            },
+        );
+
+        // Panic does not propagate to the JoinHandle
+        handle
+            .await
+            .expect("supervised task should not report a panic");
+        assert_eq!(recovered.lock().unwrap().as_deref(), Some("boom"));
+    }
+
+    #[tokio::test]
+    async fn test_normal_completion_skips_recovery() {
+        let called = Arc::new(AtomicBool::new(false));
+        let called_clone = Arc::clone(&called);
+
+        spawn_supervised("test_task", async {}, move |_| async move {
+            called_clone.store(true, Ordering::SeqCst);
+        })
+        .await
+        .unwrap();
+
+        assert!(!called.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_panic_message_formats() {
+        let owned: Box<dyn Any + Send> = Box::new(format!("index {}", 3));
+        assert_eq!(panic_message(owned.as_ref()), "index 3");
+
+        let other: Box<dyn Any + Send> = Box::new(42u32);
+        assert_eq!(panic_message(other.as_ref()), "unknown panic payload");
+    }
+}
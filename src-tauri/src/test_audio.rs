@@ -0,0 +1,449 @@
+//! Test Audio Generator
+//!
+//! Hidden `--gen-test-audio` mode of the main binary, for fixtures and
+//! demos. Synthesizes labeled 16-bit mono WAVs from speech-like segments
+//! (a carrier with a 5Hz syllable envelope, which VAD detects as speech),
+//! steady tones and silence — no TTS, fully deterministic.
+//!
+//! Presets reproduce the patterns of `tests/fixtures` (`short`, `long`,
+//! `silence`; at 16kHz and their own length, the same samples) and can be
+//! stretched to any duration and sample rate; `--cycle` builds
+//! speech/silence cycles of a given rhythm instead. Next to the WAV, a
+//! `.labels.json` lists every segment with its start and end, so E2E
+//! scenarios can assert where speech is without committing large binaries.
+//!
+//! ```bash
+//! meeting-minutes-automator --gen-test-audio --preset long --duration-secs 600 --out long_10m.wav
+//! meeting-minutes-automator --gen-test-audio --cycle 3000,800 --sample-rate 48000 --out cycles.wav
+//! ```
+//!
+//! Exit code: 0 written, 2 invalid arguments or write failure.
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Serialize;
+use std::f64::consts::PI;
+use std::path::{Path, PathBuf};
+
+const USAGE: &str = r#"--gen-test-audio - Synthesize labeled test audio
+
+OPTIONS:
+    --out <wav>                  Output WAV; labels go to <name>.labels.json (required)
+    --preset <name>              short, long, silence or tone (default: short)
+    --cycle <speech_ms>,<silence_ms>
+                                 Speech/silence cycles instead of a preset
+    --duration-secs <n>          Repeat the pattern to this length, fractions allowed
+                                 (default: one pattern)
+    --sample-rate <hz>           8000 to 48000 (default: 16000)
+"#;
+
+/// Amplitude of speech segments (as in `generate_test_audio.py`)
+const SPEECH_LEVEL: f64 = 0.8;
+/// Amplitude of tones
+const TONE_LEVEL: f64 = 0.5;
+/// Syllable rate of the speech envelope
+const SYLLABLE_HZ: f64 = 5.0;
+/// Carriers of successive `--cycle` speech segments
+const CYCLE_PITCHES_HZ: [f64; 4] = [440.0, 500.0, 600.0, 480.0];
+
+/// A stretch of generated audio
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum Segment {
+    /// Carrier with a syllable envelope (detected as speech)
+    Speech {
+        ms: u64,
+        freq_hz: f64,
+    },
+    /// Steady sine
+    Tone {
+        ms: u64,
+        freq_hz: f64,
+    },
+    Silence {
+        ms: u64,
+    },
+}
+
+impl Segment {
+    pub fn ms(&self) -> u64 {
+        match *self {
+            Segment::Speech { ms, .. } | Segment::Tone { ms, .. } | Segment::Silence { ms } => ms,
+        }
+    }
+
+    fn with_ms(self, ms: u64) -> Self {
+        match self {
+            Segment::Speech { freq_hz, .. } => Segment::Speech { ms, freq_hz },
+            Segment::Tone { freq_hz, .. } => Segment::Tone { ms, freq_hz },
+            Segment::Silence { .. } => Segment::Silence { ms },
+        }
+    }
+}
+
+/// Patterns of the committed fixtures
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Preset {
+    /// `test_audio_short.wav`: two utterances (3s)
+    Short,
+    /// `test_audio_long.wav`: four utterances of varying length (10s)
+    Long,
+    /// `test_audio_silence.wav`: no speech (2s)
+    Silence,
+    /// 1s 440Hz tone, 1s silence (the soak test's synthetic audio)
+    Tone,
+}
+
+impl Preset {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name {
+            "short" => Ok(Preset::Short),
+            "long" => Ok(Preset::Long),
+            "silence" => Ok(Preset::Silence),
+            "tone" => Ok(Preset::Tone),
+            other => bail!("Unknown preset: {}", other),
+        }
+    }
+
+    pub fn pattern(&self) -> Vec<Segment> {
+        let speech = |ms, freq_hz| Segment::Speech { ms, freq_hz };
+        let silence = |ms| Segment::Silence { ms };
+        match self {
+            Preset::Short => vec![
+                speech(1000, 440.0),
+                silence(500),
+                speech(1000, 550.0),
+                silence(500),
+            ],
+            Preset::Long => vec![
+                speech(2000, 440.0),
+                silence(600),
+                speech(1500, 500.0),
+                silence(600),
+                speech(2500, 600.0),
+                silence(600),
+                speech(1500, 480.0),
+                silence(700),
+            ],
+            Preset::Silence => vec![silence(2000)],
+            Preset::Tone => vec![
+                Segment::Tone {
+                    ms: 1000,
+                    freq_hz: 440.0,
+                },
+                silence(1000),
+            ],
+        }
+    }
+}
+
+/// Speech/silence cycles with rotating carriers
+pub fn cycle_pattern(speech_ms: u64, silence_ms: u64) -> Vec<Segment> {
+    CYCLE_PITCHES_HZ
+        .iter()
+        .flat_map(|&freq_hz| {
+            [
+                Segment::Speech {
+                    ms: speech_ms,
+                    freq_hz,
+                },
+                Segment::Silence { ms: silence_ms },
+            ]
+        })
+        .collect()
+}
+
+/// Repeat `pattern` to `duration_ms` (the last segment is cut short);
+/// the pattern once if None
+pub fn fit_to_duration(pattern: &[Segment], duration_ms: Option<u64>) -> Vec<Segment> {
+    let Some(duration_ms) = duration_ms else {
+        return pattern.to_vec();
+    };
+    let mut segments = Vec::new();
+    let mut total_ms = 0;
+    for segment in pattern.iter().filter(|segment| segment.ms() > 0).cycle() {
+        if total_ms >= duration_ms {
+            break;
+        }
+        let ms = segment.ms().min(duration_ms - total_ms);
+        segments.push(segment.with_ms(ms));
+        total_ms += ms;
+    }
+    segments
+}
+
+/// Position of a segment in the generated audio
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Label {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    #[serde(flatten)]
+    pub segment: Segment,
+}
+
+/// Samples of `segments` at `sample_rate`, with their labels
+pub fn synthesize(segments: &[Segment], sample_rate: u32) -> (Vec<i16>, Vec<Label>) {
+    let mut samples = Vec::new();
+    let mut labels = Vec::with_capacity(segments.len());
+    let mut start_ms = 0;
+    for segment in segments {
+        let count = (segment.ms() * sample_rate as u64 / 1000) as usize;
+        // Every segment starts at phase 0, like the fixture script
+        let step = segment.ms() as f64 / 1000.0 / count.max(1) as f64;
+        samples.extend((0..count).map(|i| {
+            let t = i as f64 * step;
+            match *segment {
+                Segment::Speech { freq_hz, .. } => {
+                    let carrier = (2.0 * PI * freq_hz * t).sin();
+                    let envelope = 0.5 + 0.5 * (2.0 * PI * SYLLABLE_HZ * t).sin();
+                    (carrier * envelope * i16::MAX as f64 * SPEECH_LEVEL) as i16
+                }
+                Segment::Tone { freq_hz, .. } => {
+                    (TONE_LEVEL * (2.0 * PI * freq_hz * t).sin() * i16::MAX as f64) as i16
+                }
+                Segment::Silence { .. } => 0,
+            }
+        }));
+        labels.push(Label {
+            start_ms,
+            end_ms: start_ms + segment.ms(),
+            segment: *segment,
+        });
+        start_ms += segment.ms();
+    }
+    (samples, labels)
+}
+
+/// Write 16-bit mono PCM with a 44-byte header (what the fixture helpers expect)
+pub fn write_wav(path: &Path, sample_rate: u32, samples: &[i16]) -> Result<()> {
+    let data_len = (samples.len() * 2) as u32;
+    let mut bytes = Vec::with_capacity(44 + samples.len() * 2);
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+    bytes.extend_from_slice(b"WAVEfmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // mono
+    bytes.extend_from_slice(&sample_rate.to_le_bytes());
+    bytes.extend_from_slice(&(sample_rate * 2).to_le_bytes());
+    bytes.extend_from_slice(&2u16.to_le_bytes());
+    bytes.extend_from_slice(&16u16.to_le_bytes());
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data_len.to_le_bytes());
+    for sample in samples {
+        bytes.extend_from_slice(&sample.to_le_bytes());
+    }
+    std::fs::write(path, bytes).with_context(|| format!("Failed to write WAV: {:?}", path))
+}
+
+/// Labels file next to `wav` (`short.wav` -> `short.labels.json`)
+pub fn labels_path(wav: &Path) -> PathBuf {
+    wav.with_extension("labels.json")
+}
+
+// ============================================================================
+// Command Line
+// ============================================================================
+
+/// Generator options (arguments following `--gen-test-audio`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenerateConfig {
+    pub out: PathBuf,
+    pub pattern: Vec<Segment>,
+    pub duration_ms: Option<u64>,
+    pub sample_rate: u32,
+}
+
+impl GenerateConfig {
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Self> {
+        let mut out = None;
+        let mut pattern = Preset::Short.pattern();
+        let mut duration_ms = None;
+        let mut sample_rate = 16_000;
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .ok_or_else(|| anyhow!("{} requires a value", name))
+            };
+            match arg.as_str() {
+                "--out" => out = Some(PathBuf::from(value("--out")?)),
+                "--preset" => pattern = Preset::parse(&value("--preset")?)?.pattern(),
+                "--cycle" => {
+                    let cycle = value("--cycle")?;
+                    let (speech_ms, silence_ms) = cycle
+                        .split_once(',')
+                        .and_then(|(speech, silence)| {
+                            Some((speech.trim().parse().ok()?, silence.trim().parse().ok()?))
+                        })
+                        .ok_or_else(|| anyhow!("--cycle expects <speech_ms>,<silence_ms>"))?;
+                    if speech_ms == 0 {
+                        bail!("--cycle needs speech longer than 0 ms");
+                    }
+                    pattern = cycle_pattern(speech_ms, silence_ms);
+                }
+                "--duration-secs" => {
+                    let secs: f64 = value("--duration-secs")?
+                        .parse()
+                        .context("Failed to parse --duration-secs")?;
+                    if !secs.is_finite() || secs <= 0.0 {
+                        bail!("--duration-secs must be greater than zero");
+                    }
+                    duration_ms = Some((secs * 1000.0).round() as u64);
+                }
+                "--sample-rate" => {
+                    sample_rate = value("--sample-rate")?
+                        .parse()
+                        .context("Failed to parse --sample-rate")?;
+                    if !(8_000..=48_000).contains(&sample_rate) {
+                        bail!("--sample-rate must be between 8000 and 48000");
+                    }
+                }
+                other => bail!("Unknown option: {}", other),
+            }
+        }
+        Ok(Self {
+            out: out.ok_or_else(|| anyhow!("--out is required"))?,
+            pattern,
+            duration_ms,
+            sample_rate,
+        })
+    }
+}
+
+/// Generate the WAV and its labels
+pub fn generate(config: &GenerateConfig) -> Result<Vec<Label>> {
+    let segments = fit_to_duration(&config.pattern, config.duration_ms);
+    let (samples, labels) = synthesize(&segments, config.sample_rate);
+    write_wav(&config.out, config.sample_rate, &samples)?;
+
+    let labels_path = labels_path(&config.out);
+    let json = serde_json::to_string_pretty(&labels).context("Failed to serialize labels")?;
+    std::fs::write(&labels_path, json)
+        .with_context(|| format!("Failed to write labels: {:?}", labels_path))?;
+    Ok(labels)
+}
+
+/// Entry point of `--gen-test-audio` (`args` follow the flag); returns the exit code
+pub fn run_from_args<I: IntoIterator<Item = String>>(args: I) -> i32 {
+    let args: Vec<String> = args.into_iter().collect();
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{}", USAGE);
+        return 0;
+    }
+    let config = match GenerateConfig::parse(args) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{:#}\n\n{}", e, USAGE);
+            return 2;
+        }
+    };
+    match generate(&config) {
+        Ok(labels) => {
+            let speech = labels
+                .iter()
+                .filter(|label| matches!(label.segment, Segment::Speech { .. }))
+                .count();
+            println!(
+                "Generated {:?}: {} ms at {} Hz, {} speech segments (labels: {:?})",
+                config.out,
+                labels.last().map(|label| label.end_ms).unwrap_or(0),
+                config.sample_rate,
+                speech,
+                labels_path(&config.out)
+            );
+            0
+        }
+        Err(e) => {
+            eprintln!("{:#}", e);
+            2
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_presets_match_committed_fixtures() {
+        let fixtures: [(Preset, &[u8]); 3] = [
+            (
+                Preset::Short,
+                include_bytes!("../tests/fixtures/test_audio_short.wav"),
+            ),
+            (
+                Preset::Long,
+                include_bytes!("../tests/fixtures/test_audio_long.wav"),
+            ),
+            (
+                Preset::Silence,
+                include_bytes!("../tests/fixtures/test_audio_silence.wav"),
+            ),
+        ];
+        let dir = TempDir::new().unwrap();
+        for (preset, fixture) in fixtures {
+            let path = dir.path().join("fixture.wav");
+            let (samples, _) = synthesize(&preset.pattern(), 16_000);
+            write_wav(&path, 16_000, &samples).unwrap();
+            let generated = std::fs::read(&path).unwrap();
+
+            assert_eq!(generated.len(), fixture.len(), "{:?}", preset);
+            assert_eq!(generated[..44], fixture[..44], "{:?}", preset);
+            // Same samples, give or take float rounding
+            let max_diff = generated[44..]
+                .chunks_exact(2)
+                .zip(fixture[44..].chunks_exact(2))
+                .map(|(a, b)| {
+                    let a = i16::from_le_bytes([a[0], a[1]]) as i32;
+                    let b = i16::from_le_bytes([b[0], b[1]]) as i32;
+                    (a - b).abs()
+                })
+                .max()
+                .unwrap_or(0);
+            assert!(max_diff <= 1, "{:?}: {}", preset, max_diff);
+        }
+    }
+
+    #[test]
+    fn test_generate_stretched_cycles_with_labels() {
+        let dir = TempDir::new().unwrap();
+        let out = dir.path().join("cycles.wav");
+        let config = GenerateConfig::parse(args(&[
+            "--cycle",
+            "1500,600",
+            "--duration-secs",
+            "5",
+            "--sample-rate",
+            "48000",
+            "--out",
+            out.to_str().unwrap(),
+        ]))
+        .unwrap();
+
+        let labels = generate(&config).unwrap();
+        // 1500 + 600 + 1500 + 600 + 800 (cut short)
+        assert_eq!(labels.len(), 5);
+        assert_eq!(labels[4].end_ms, 5_000);
+        assert_eq!(
+            labels[4].segment,
+            Segment::Speech {
+                ms: 800,
+                freq_hz: 600.0
+            }
+        );
+        assert_eq!(std::fs::metadata(&out).unwrap().len(), 44 + 5 * 48_000 * 2);
+        let json = std::fs::read_to_string(dir.path().join("cycles.labels.json")).unwrap();
+        assert!(json.contains("\"kind\": \"silence\""));
+
+        assert!(GenerateConfig::parse(args(&["--preset", "short"])).is_err());
+        assert!(GenerateConfig::parse(args(&["--out", "a.wav", "--cycle", "1500"])).is_err());
+        assert!(
+            GenerateConfig::parse(args(&["--out", "a.wav", "--sample-rate", "96000"])).is_err()
+        );
+    }
+}
@@ -0,0 +1,135 @@
+//! Session Timeline
+//!
+//! Non-audio context pushed by the frontend during a recording (slide changed,
+//! screen-share started, title edited). Timestamps are milliseconds relative
+//! to session start, so events line up with transcript segments and markers.
+//!
+//! Persisted as JSON Lines to `recordings/<session_id>/timeline.jsonl`.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::Path;
+
+const TIMELINE_FILENAME: &str = "timeline.jsonl";
+
+/// Maximum length of an event kind
+pub const MAX_KIND_LEN: usize = 64;
+
+/// Maximum serialized size of event data (bytes)
+pub const MAX_DATA_BYTES: usize = 16 * 1024;
+
+/// Single timeline event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimelineEvent {
+    /// Session-relative timestamp (ms)
+    pub timestamp_ms: u64,
+    /// Event kind (e.g. "slide_changed", "screen_share_started")
+    pub kind: String,
+    /// Free-form payload supplied by the frontend
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+    pub data: serde_json::Value,
+}
+
+/// Check event kind and payload size before persisting
+///
+/// Kinds are lowercase identifiers (`a-z`, `0-9`, `_`, `.`, `-`).
+pub fn validate_event(kind: &str, data: &serde_json::Value) -> Result<()> {
+    if kind.is_empty() || kind.len() > MAX_KIND_LEN {
+        anyhow::bail!(
+            "Event kind must be 1-{} characters: {:?}",
+            MAX_KIND_LEN,
+            kind
+        );
+    }
+    if !kind
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '_' | '.' | '-'))
+    {
+        anyhow::bail!("Event kind must be a lowercase identifier: {:?}", kind);
+    }
+
+    let data_len = serde_json::to_vec(data)?.len();
+    if data_len > MAX_DATA_BYTES {
+        anyhow::bail!(
+            "Event data is {} bytes (limit {} bytes)",
+            data_len,
+            MAX_DATA_BYTES
+        );
+    }
+    Ok(())
+}
+
+/// Append event to `<session_dir>/timeline.jsonl`
+pub fn append_event(session_dir: &Path, event: &TimelineEvent) -> Result<()> {
+    let path = session_dir.join(TIMELINE_FILENAME);
+    let mut line = serde_json::to_string(event).context("Failed to serialize timeline event")?;
+    line.push('\n');
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open timeline file: {:?}", path))?;
+    file.write_all(line.as_bytes())?;
+    file.flush()?;
+    Ok(())
+}
+
+/// Load all timeline events of a session (empty if none were recorded)
+pub fn load_timeline(session_dir: &Path) -> Result<Vec<TimelineEvent>> {
+    let path = session_dir.join(TIMELINE_FILENAME);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read timeline file: {:?}", path))?;
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| serde_json::from_str(line).context("Failed to parse timeline event"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_append_and_load_timeline() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(load_timeline(temp_dir.path()).unwrap().is_empty());
+
+        let slide = TimelineEvent {
+            timestamp_ms: 12_000,
+            kind: "slide_changed".to_string(),
+            data: json!({ "slide": 4 }),
+        };
+        let share = TimelineEvent {
+            timestamp_ms: 30_000,
+            kind: "screen_share_started".to_string(),
+            data: serde_json::Value::Null,
+        };
+        append_event(temp_dir.path(), &slide).unwrap();
+        append_event(temp_dir.path(), &share).unwrap();
+
+        let content = std::fs::read_to_string(temp_dir.path().join(TIMELINE_FILENAME)).unwrap();
+        assert!(!content.contains("\"data\":null"));
+
+        assert_eq!(load_timeline(temp_dir.path()).unwrap(), vec![slide, share]);
+    }
+
+    #[test]
+    fn test_validate_event() {
+        assert!(validate_event("title_edited", &json!({ "title": "Weekly" })).is_ok());
+        assert!(validate_event("", &serde_json::Value::Null).is_err());
+        assert!(validate_event("Slide Changed", &serde_json::Value::Null).is_err());
+        assert!(validate_event("../escape", &serde_json::Value::Null).is_err());
+
+        let oversized = json!({ "blob": "x".repeat(MAX_DATA_BYTES) });
+        assert!(validate_event("blob", &oversized).is_err());
+    }
+}
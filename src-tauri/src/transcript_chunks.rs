@@ -0,0 +1,254 @@
+//! Time-Boxed Transcript Chunks
+//!
+//! A single `transcription.jsonl` of a 4+ hour session has to be read in
+//! full by every reader. The recording transcript is therefore split by
+//! session time into hourly parts:
+//!
+//! | Part | File                        | Session time |
+//! |------|-----------------------------|--------------|
+//! | 1    | `transcription.jsonl`       | 0:00–1:00    |
+//! | 2    | `transcription.part2.jsonl` | 1:00–2:00    |
+//! | n    | `transcription.partN.jsonl` | n-1 h – n h  |
+//!
+//! Part 1 keeps the historic file name, so sessions under an hour look as
+//! before. An event goes to the part of its timestamp but never back to an
+//! earlier part (a final arriving just after a rollover stays in the new
+//! part), so concatenating the parts keeps the written order.
+//!
+//! Once a session has more than one part, `transcription.manifest.json`
+//! lists each part with its event count. It is rewritten on every rollover
+//! and when the writer closes. The part files remain the source of truth:
+//! readers discover parts from the directory and use the manifest only to
+//! skip closed parts while paging (`LocalStorageService::load_transcript_page`).
+//! Part names are reserved and can't be used as re-transcription versions.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::storage::TranscriptionEvent;
+
+/// Session time covered by one part
+pub const CHUNK_DURATION_MS: u64 = 60 * 60 * 1000;
+
+/// Manifest listing the parts of a chunked transcript
+pub const MANIFEST_FILENAME: &str = "transcription.manifest.json";
+
+/// Manifest layout version
+pub const MANIFEST_VERSION: u32 = 1;
+
+/// Events `get_session` returns before the client pages the rest
+pub const SESSION_PAGE_EVENTS: usize = 2000;
+
+/// Largest page `get_transcript_page` returns
+pub const MAX_PAGE_EVENTS: usize = 5000;
+
+/// File name of part `index` (1-based)
+pub fn part_file_name(index: u32) -> String {
+    if index <= 1 {
+        crate::storage::TRANSCRIPT_FILENAME.to_string()
+    } else {
+        format!("transcription.part{}.jsonl", index)
+    }
+}
+
+/// Part index of a file name (None for other files)
+pub fn part_index(file_name: &str) -> Option<u32> {
+    if file_name == crate::storage::TRANSCRIPT_FILENAME {
+        return Some(1);
+    }
+    file_name
+        .strip_prefix("transcription.")
+        .and_then(|rest| rest.strip_suffix(".jsonl"))
+        .and_then(part_version_index)
+}
+
+/// Index of a `partN` version name (N >= 2, no leading zeros)
+fn part_version_index(version: &str) -> Option<u32> {
+    let digits = version.strip_prefix("part")?;
+    if digits.starts_with('0') || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok().filter(|&index| index >= 2)
+}
+
+/// Whether a re-transcription version name would collide with a part file
+pub fn is_reserved_version(version: &str) -> bool {
+    version.starts_with("part") && version[4..].chars().all(|c| c.is_ascii_digit())
+}
+
+/// Part an event at `timestamp_ms` (session time) belongs to
+pub fn part_for_timestamp(timestamp_ms: u64) -> u32 {
+    u32::try_from(timestamp_ms / CHUNK_DURATION_MS + 1).unwrap_or(u32::MAX)
+}
+
+/// Part files present in a session directory, by index
+pub fn list_parts(session_dir: &Path) -> Result<Vec<(u32, PathBuf)>> {
+    if !session_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut parts: Vec<(u32, PathBuf)> = std::fs::read_dir(session_dir)?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let index = part_index(entry.file_name().to_str()?)?;
+            Some((index, entry.path()))
+        })
+        .collect();
+    parts.sort();
+    Ok(parts)
+}
+
+/// One part in the manifest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranscriptPart {
+    pub index: u32,
+    pub file: String,
+    /// Start of the part's time box (session time, ms)
+    pub start_ms: u64,
+    /// Events written to the part
+    pub events: u64,
+    /// Timestamp of the part's last event
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_event_ms: Option<u64>,
+}
+
+/// `transcription.manifest.json`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChunkManifest {
+    pub version: u32,
+    pub chunk_duration_ms: u64,
+    pub parts: Vec<TranscriptPart>,
+}
+
+impl Default for ChunkManifest {
+    fn default() -> Self {
+        Self {
+            version: MANIFEST_VERSION,
+            chunk_duration_ms: CHUNK_DURATION_MS,
+            parts: Vec::new(),
+        }
+    }
+}
+
+impl ChunkManifest {
+    /// Entry of part `index`, added when missing
+    pub fn part_mut(&mut self, index: u32) -> &mut TranscriptPart {
+        let position = match self.parts.binary_search_by_key(&index, |part| part.index) {
+            Ok(position) => position,
+            Err(position) => {
+                self.parts.insert(
+                    position,
+                    TranscriptPart {
+                        index,
+                        file: part_file_name(index),
+                        start_ms: (index as u64 - 1) * CHUNK_DURATION_MS,
+                        events: 0,
+                        last_event_ms: None,
+                    },
+                );
+                position
+            }
+        };
+        &mut self.parts[position]
+    }
+
+    /// Count an event written to part `index`
+    pub fn record_event(&mut self, index: u32, timestamp_ms: u64) {
+        let part = self.part_mut(index);
+        part.events += 1;
+        part.last_event_ms = Some(timestamp_ms);
+    }
+
+    /// Recorded event count of part `index`
+    pub fn events_in(&self, index: u32) -> Option<u64> {
+        self.parts
+            .iter()
+            .find(|part| part.index == index)
+            .map(|part| part.events)
+    }
+}
+
+/// Load the manifest of a session (None if the transcript isn't chunked)
+pub fn load_manifest(session_dir: &Path) -> Result<Option<ChunkManifest>> {
+    let path = session_dir.join(MANIFEST_FILENAME);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let json = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read transcript manifest: {:?}", path))?;
+    let manifest = serde_json::from_str(&json).context("Failed to parse transcript manifest")?;
+    Ok(Some(manifest))
+}
+
+/// Save the manifest of a session (replaced atomically)
+pub fn save_manifest(session_dir: &Path, manifest: &ChunkManifest) -> Result<()> {
+    let json = serde_json::to_string_pretty(manifest)
+        .context("Failed to serialize transcript manifest")?;
+    let temp_path = session_dir.join(format!("{}.tmp", MANIFEST_FILENAME));
+    crate::storage::write_file_owner_only(&temp_path, json.as_bytes())?;
+    std::fs::rename(&temp_path, session_dir.join(MANIFEST_FILENAME))
+        .context("Failed to replace transcript manifest")?;
+    Ok(())
+}
+
+/// A page of a session's transcript
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TranscriptPage {
+    /// Index of the first event in the whole transcript
+    pub offset: usize,
+    /// Events in the whole transcript
+    pub total: usize,
+    pub events: Vec<TranscriptionEvent>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_part_names() {
+        assert_eq!(part_file_name(1), "transcription.jsonl");
+        assert_eq!(part_file_name(3), "transcription.part3.jsonl");
+        assert_eq!(part_index("transcription.jsonl"), Some(1));
+        assert_eq!(part_index("transcription.part12.jsonl"), Some(12));
+        for other in [
+            "transcription.part1.jsonl",
+            "transcription.part02.jsonl",
+            "transcription.v2.jsonl",
+            "transcription.jsonl.bak",
+            MANIFEST_FILENAME,
+        ] {
+            assert_eq!(part_index(other), None, "{}", other);
+        }
+        assert!(is_reserved_version("part2"));
+        assert!(is_reserved_version("part"));
+        assert!(!is_reserved_version("parts"));
+
+        assert_eq!(part_for_timestamp(0), 1);
+        assert_eq!(part_for_timestamp(CHUNK_DURATION_MS - 1), 1);
+        assert_eq!(part_for_timestamp(4 * CHUNK_DURATION_MS + 5), 5);
+    }
+
+    #[test]
+    fn test_manifest_roundtrip() {
+        let dir = TempDir::new().unwrap();
+        assert_eq!(load_manifest(dir.path()).unwrap(), None);
+
+        let mut manifest = ChunkManifest::default();
+        manifest.record_event(2, CHUNK_DURATION_MS + 10);
+        manifest.record_event(1, 500);
+        manifest.record_event(1, 900);
+        save_manifest(dir.path(), &manifest).unwrap();
+
+        let loaded = load_manifest(dir.path()).unwrap().unwrap();
+        assert_eq!(loaded, manifest);
+        assert_eq!(
+            loaded.parts.iter().map(|p| p.index).collect::<Vec<_>>(),
+            [1, 2]
+        );
+        assert_eq!(loaded.events_in(1), Some(2));
+        assert_eq!(loaded.parts[1].file, "transcription.part2.jsonl");
+        assert_eq!(loaded.events_in(3), None);
+    }
+}
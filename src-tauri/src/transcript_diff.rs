@@ -0,0 +1,384 @@
+//! Transcript Comparison
+//!
+//! Compares two transcript versions of a session (e.g. the live
+//! `transcription.jsonl` and an offline re-transcription
+//! `transcription.v2.jsonl`). Final segments are aligned by time into
+//! regions, and each region gets a word-level diff.
+//!
+//! Tokens are ASCII words, or single characters for CJK text (which has no
+//! word separators). Each region reports the edit distance between the
+//! versions. When a reference transcript (e.g. a manually corrected version)
+//! is given, the word error rate of both versions against it and the
+//! accuracy delta are reported as well.
+
+use serde::{Deserialize, Serialize};
+
+use crate::storage::TranscriptionEvent;
+
+// ============================================================================
+// Tokens and Diff
+// ============================================================================
+
+/// Split text into comparison tokens
+///
+/// ASCII alphanumeric runs form one (lowercased) word; every other
+/// non-space, non-punctuation character is its own token.
+pub fn tokenize(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut word = String::new();
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() || (c == '\'' && !word.is_empty()) {
+            word.push(c.to_ascii_lowercase());
+            continue;
+        }
+        if !word.is_empty() {
+            tokens.push(std::mem::take(&mut word));
+        }
+        if !(c.is_whitespace() || c.is_ascii_punctuation() || is_cjk_punctuation(c)) {
+            tokens.push(c.to_string());
+        }
+    }
+    if !word.is_empty() {
+        tokens.push(word);
+    }
+    tokens
+}
+
+fn is_cjk_punctuation(c: char) -> bool {
+    matches!(c, '\u{3000}'..='\u{303F}' | '\u{FF01}'..='\u{FF0F}' | '\u{FF1A}'..='\u{FF20}' | '・')
+}
+
+/// Diff operation over consecutive tokens
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum DiffOp {
+    Equal { tokens: Vec<String> },
+    Delete { tokens: Vec<String> },
+    Insert { tokens: Vec<String> },
+}
+
+/// Word-level diff (LCS) from `a` to `b`
+pub fn diff_tokens(a: &[String], b: &[String]) -> Vec<DiffOp> {
+    // lcs[i][j] = LCS length of a[i..] and b[j..]
+    let mut lcs = vec![vec![0u32; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops: Vec<DiffOp> = Vec::new();
+    let mut push = |op: DiffOp| match (ops.last_mut(), op) {
+        (Some(DiffOp::Equal { tokens }), DiffOp::Equal { tokens: t })
+        | (Some(DiffOp::Delete { tokens }), DiffOp::Delete { tokens: t })
+        | (Some(DiffOp::Insert { tokens }), DiffOp::Insert { tokens: t }) => tokens.extend(t),
+        (_, op) => ops.push(op),
+    };
+
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            push(DiffOp::Equal {
+                tokens: vec![a[i].clone()],
+            });
+            i += 1;
+            j += 1;
+        } else if j == b.len() || (i < a.len() && lcs[i + 1][j] >= lcs[i][j + 1]) {
+            push(DiffOp::Delete {
+                tokens: vec![a[i].clone()],
+            });
+            i += 1;
+        } else {
+            push(DiffOp::Insert {
+                tokens: vec![b[j].clone()],
+            });
+            j += 1;
+        }
+    }
+    ops
+}
+
+/// Levenshtein distance over tokens (substitution = 1 edit)
+pub fn edit_distance(a: &[String], b: &[String]) -> usize {
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, token_a) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, token_b) in b.iter().enumerate() {
+            let substitution = prev[j] + usize::from(token_a != token_b);
+            current[j + 1] = substitution.min(prev[j + 1] + 1).min(current[j] + 1);
+        }
+        prev = current;
+    }
+    prev[b.len()]
+}
+
+/// Word error rate of `hypothesis` against `reference`
+pub fn word_error_rate(reference: &[String], hypothesis: &[String]) -> f64 {
+    if reference.is_empty() {
+        return if hypothesis.is_empty() { 0.0 } else { 1.0 };
+    }
+    edit_distance(reference, hypothesis) as f64 / reference.len() as f64
+}
+
+// ============================================================================
+// Alignment
+// ============================================================================
+
+/// Segment of `a` with the segments of `b` aligned to it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlignedRegion {
+    pub start_ms: u64,
+    pub a_text: String,
+    pub b_text: String,
+}
+
+/// Align final segments of two versions by time
+///
+/// A segment spans from its timestamp to the next segment of the same
+/// version. Regions follow the segments of `a`; each segment of `b` joins the
+/// region it overlaps most (segments before the first region join it).
+pub fn align_segments(a: &[TranscriptionEvent], b: &[TranscriptionEvent]) -> Vec<AlignedRegion> {
+    let (a, b) = (final_spans(a), final_spans(b));
+    if a.is_empty() {
+        return b
+            .into_iter()
+            .map(|(start_ms, _, text)| AlignedRegion {
+                start_ms,
+                a_text: String::new(),
+                b_text: text,
+            })
+            .collect();
+    }
+
+    let mut b_parts: Vec<Vec<String>> = vec![Vec::new(); a.len()];
+    for (b_start, b_end, text) in b {
+        let overlap = |&(a_start, a_end, _): &(u64, u64, String)| {
+            a_end.min(b_end).saturating_sub(a_start.max(b_start))
+        };
+        let best = a
+            .iter()
+            .enumerate()
+            .max_by_key(|(i, span)| (overlap(span), std::cmp::Reverse(*i)))
+            .filter(|(_, span)| overlap(span) > 0)
+            .map(|(i, _)| i)
+            // Zero-length overlap: region containing the start, else the first
+            .unwrap_or_else(|| a.iter().rposition(|span| span.0 <= b_start).unwrap_or(0));
+        b_parts[best].push(text);
+    }
+
+    a.into_iter()
+        .zip(b_parts)
+        .map(|((start_ms, _, a_text), b_parts)| AlignedRegion {
+            start_ms,
+            a_text,
+            b_text: b_parts.join(" "),
+        })
+        .collect()
+}
+
+/// Final segments sorted by time as `(start, end, text)`
+fn final_spans(events: &[TranscriptionEvent]) -> Vec<(u64, u64, String)> {
+    let mut finals: Vec<&TranscriptionEvent> = events.iter().filter(|e| e.is_final).collect();
+    finals.sort_by_key(|e| e.timestamp_ms);
+    finals
+        .iter()
+        .enumerate()
+        .map(|(i, e)| {
+            let end = finals.get(i + 1).map_or(u64::MAX, |next| next.timestamp_ms);
+            (e.timestamp_ms, end, e.text.trim().to_string())
+        })
+        .collect()
+}
+
+// ============================================================================
+// Report
+// ============================================================================
+
+/// Comparison of one aligned region
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegionDiff {
+    pub start_ms: u64,
+    pub a_text: String,
+    pub b_text: String,
+    pub ops: Vec<DiffOp>,
+    /// Token edits needed to turn `a_text` into `b_text`
+    pub edit_distance: usize,
+}
+
+/// Accuracy of both versions against a reference transcript
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccuracyDelta {
+    pub reference: String,
+    pub a_word_error_rate: f64,
+    pub b_word_error_rate: f64,
+    /// Positive when `b` is more accurate than `a`
+    pub improvement: f64,
+}
+
+/// Comparison of two transcript versions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptDiff {
+    pub a_version: String,
+    pub b_version: String,
+    pub regions: Vec<RegionDiff>,
+    pub a_token_count: usize,
+    pub b_token_count: usize,
+    /// Total edit distance divided by the token count of `a`
+    pub change_rate: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accuracy: Option<AccuracyDelta>,
+}
+
+fn all_tokens(events: &[TranscriptionEvent]) -> Vec<String> {
+    final_spans(events)
+        .iter()
+        .flat_map(|(_, _, text)| tokenize(text))
+        .collect()
+}
+
+/// Compare two versions, optionally scoring both against a reference
+pub fn compare(
+    a_version: &str,
+    a: &[TranscriptionEvent],
+    b_version: &str,
+    b: &[TranscriptionEvent],
+    reference: Option<(&str, &[TranscriptionEvent])>,
+) -> TranscriptDiff {
+    let regions: Vec<RegionDiff> = align_segments(a, b)
+        .into_iter()
+        .map(|region| {
+            let a_tokens = tokenize(&region.a_text);
+            let b_tokens = tokenize(&region.b_text);
+            RegionDiff {
+                start_ms: region.start_ms,
+                ops: diff_tokens(&a_tokens, &b_tokens),
+                edit_distance: edit_distance(&a_tokens, &b_tokens),
+                a_text: region.a_text,
+                b_text: region.b_text,
+            }
+        })
+        .collect();
+
+    let a_tokens = all_tokens(a);
+    let b_tokens = all_tokens(b);
+    let total_edits: usize = regions.iter().map(|r| r.edit_distance).sum();
+    let change_rate = if a_tokens.is_empty() {
+        if b_tokens.is_empty() {
+            0.0
+        } else {
+            1.0
+        }
+    } else {
+        total_edits as f64 / a_tokens.len() as f64
+    };
+
+    let accuracy = reference.map(|(name, events)| {
+        let reference_tokens = all_tokens(events);
+        let a_word_error_rate = word_error_rate(&reference_tokens, &a_tokens);
+        let b_word_error_rate = word_error_rate(&reference_tokens, &b_tokens);
+        AccuracyDelta {
+            reference: name.to_string(),
+            a_word_error_rate,
+            b_word_error_rate,
+            improvement: a_word_error_rate - b_word_error_rate,
+        }
+    });
+
+    TranscriptDiff {
+        a_version: a_version.to_string(),
+        b_version: b_version.to_string(),
+        regions,
+        a_token_count: a_tokens.len(),
+        b_token_count: b_tokens.len(),
+        change_rate,
+        accuracy,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(timestamp_ms: u64, text: &str) -> TranscriptionEvent {
+        TranscriptionEvent {
+            timestamp_ms,
+            text: text.to_string(),
+            is_final: true,
+            speaker: None,
+        }
+    }
+
+    fn tokens(text: &str) -> Vec<String> {
+        tokenize(text)
+    }
+
+    #[test]
+    fn test_tokenize_mixed_text() {
+        assert_eq!(
+            tokenize("Let's ship v2, 予算OK。"),
+            vec!["let's", "ship", "v2", "予", "算", "ok"]
+        );
+    }
+
+    #[test]
+    fn test_diff_tokens() {
+        let ops = diff_tokens(
+            &tokens("the budget was approved"),
+            &tokens("the budget is approved"),
+        );
+        assert_eq!(
+            ops,
+            vec![
+                DiffOp::Equal {
+                    tokens: vec!["the".into(), "budget".into()]
+                },
+                DiffOp::Delete {
+                    tokens: vec!["was".into()]
+                },
+                DiffOp::Insert {
+                    tokens: vec!["is".into()]
+                },
+                DiffOp::Equal {
+                    tokens: vec!["approved".into()]
+                },
+            ]
+        );
+        assert_eq!(edit_distance(&tokens("a b c"), &tokens("a x c d")), 2);
+    }
+
+    #[test]
+    fn test_align_segments_merges_overlaps() {
+        // v2 splits the first v1 segment in two
+        let v1 = vec![segment(0, "予算の話です"), segment(10_000, "次の議題")];
+        let v2 = vec![
+            segment(0, "予算の"),
+            segment(4_000, "話です"),
+            segment(10_500, "次の議題"),
+        ];
+        let regions = align_segments(&v1, &v2);
+        assert_eq!(regions.len(), 2);
+        assert_eq!(regions[0].a_text, "予算の話です");
+        assert_eq!(regions[0].b_text, "予算の 話です");
+        assert_eq!(regions[1].start_ms, 10_000);
+    }
+
+    #[test]
+    fn test_compare_reports_accuracy_delta() {
+        let v1 = vec![segment(0, "the budged was aproved")];
+        let v2 = vec![segment(0, "the budget was approved")];
+        let reference = vec![segment(0, "the budget was approved")];
+
+        let diff = compare("v1", &v1, "v2", &v2, Some(("corrected", &reference)));
+        assert_eq!(diff.regions.len(), 1);
+        assert_eq!(diff.change_rate, 0.5);
+
+        let accuracy = diff.accuracy.unwrap();
+        assert_eq!(accuracy.a_word_error_rate, 0.5);
+        assert_eq!(accuracy.b_word_error_rate, 0.0);
+        assert_eq!(accuracy.improvement, 0.5);
+    }
+}
@@ -0,0 +1,177 @@
+//! Transcript Line Schema Versions
+//!
+//! Every line of `transcription.jsonl` (and of re-transcribed
+//! `transcription.<version>.jsonl` files — unrelated to the schema version)
+//! is a `TranscriptionEvent`. Files written by older builds must stay
+//! readable as the event grows, so each line records its schema version in
+//! `schema`:
+//!
+//! | `schema`  | Fields                                                   |
+//! |-----------|----------------------------------------------------------|
+//! | 1 (absent)| `timestamp_ms`, `text`, `is_final`, optional `speaker`   |
+//! | 2         | as 1, plus `schema`                                      |
+//!
+//! (`speaker` was added before lines were versioned; lines with and without
+//! it are both version 1.)
+//!
+//! Reading upgrades a line in memory: its JSON object passes through one
+//! step per version (`UPGRADES`), then deserializes with serde defaults for
+//! optional fields it lacks. Lines from a newer build are rejected instead
+//! of misread. `LocalStorageService::migrate_session_format` rewrites a
+//! session's files in the current version, keeping a backup.
+//!
+//! A new optional field only needs `#[serde(default)]`. Renaming, retyping
+//! or changing the meaning of a field needs a version bump, an upgrade step
+//! and a fixture under `tests/fixtures/transcripts`.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::storage::TranscriptionEvent;
+
+/// Schema version written by this build
+pub const CURRENT_VERSION: u32 = 2;
+
+/// Field holding the schema version
+const VERSION_FIELD: &str = "schema";
+
+/// Upgrade step of one line object to the next version
+type Upgrade = fn(&mut Map<String, Value>);
+
+/// `UPGRADES[n - 1]` upgrades version `n` to `n + 1`
+const UPGRADES: [Upgrade; CURRENT_VERSION as usize - 1] = [upgrade_v1];
+
+/// Version 2 only added the version field itself
+fn upgrade_v1(_line: &mut Map<String, Value>) {}
+
+#[derive(Serialize)]
+struct VersionedLine<'a> {
+    schema: u32,
+    #[serde(flatten)]
+    event: &'a TranscriptionEvent,
+}
+
+/// JSON line of `event` in the current version
+pub fn encode_line(event: &TranscriptionEvent) -> Result<String> {
+    Ok(serde_json::to_string(&VersionedLine {
+        schema: CURRENT_VERSION,
+        event,
+    })?)
+}
+
+/// Event of a JSON line of any known version, with the line's version
+pub fn decode_line(line: &str) -> Result<(TranscriptionEvent, u32)> {
+    let value: Value = serde_json::from_str(line).context("Invalid transcript line")?;
+    let Value::Object(mut object) = value else {
+        anyhow::bail!("Transcript line is not a JSON object");
+    };
+    let version = match object.get(VERSION_FIELD) {
+        None => 1,
+        Some(version) => version
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .filter(|&version| version >= 1)
+            .with_context(|| format!("Invalid transcript schema version: {}", version))?,
+    };
+    if version > CURRENT_VERSION {
+        anyhow::bail!(
+            "Transcript schema version {} is newer than this app supports ({})",
+            version,
+            CURRENT_VERSION
+        );
+    }
+
+    for upgrade in &UPGRADES[version as usize - 1..] {
+        upgrade(&mut object);
+    }
+    let event = serde_json::from_value(Value::Object(object))
+        .with_context(|| format!("Invalid transcript line (schema version {})", version))?;
+    Ok((event, version))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fixture files of every historical version, with the version
+    const FIXTURES: [(&str, &str, u32); 3] = [
+        (
+            "v1.jsonl",
+            include_str!("../tests/fixtures/transcripts/v1.jsonl"),
+            1,
+        ),
+        (
+            "v1_speaker.jsonl",
+            include_str!("../tests/fixtures/transcripts/v1_speaker.jsonl"),
+            1,
+        ),
+        (
+            "v2.jsonl",
+            include_str!("../tests/fixtures/transcripts/v2.jsonl"),
+            2,
+        ),
+    ];
+
+    fn event(timestamp_ms: u64, text: &str, is_final: bool) -> TranscriptionEvent {
+        TranscriptionEvent {
+            timestamp_ms,
+            text: text.to_string(),
+            is_final,
+            speaker: None,
+        }
+    }
+
+    #[test]
+    fn test_fixtures_of_every_version_load() {
+        for (name, content, expected_version) in FIXTURES {
+            let decoded: Vec<(TranscriptionEvent, u32)> = content
+                .lines()
+                .map(|line| decode_line(line).unwrap())
+                .collect();
+            assert_eq!(decoded.len(), 3, "{}", name);
+            assert!(
+                decoded
+                    .iter()
+                    .all(|(_, version)| *version == expected_version),
+                "{}",
+                name
+            );
+
+            // Only the oldest fixture predates speaker labels
+            let speakers = name != "v1.jsonl";
+            let with_speaker = |mut e: TranscriptionEvent, label: &str| {
+                e.speaker = speakers.then(|| label.to_string());
+                e
+            };
+            let events: Vec<TranscriptionEvent> = decoded.into_iter().map(|(e, _)| e).collect();
+            assert_eq!(
+                events,
+                vec![
+                    with_speaker(event(1000, "会議を始めます", false), "A"),
+                    with_speaker(event(1500, "会議を始めます。", true), "A"),
+                    with_speaker(event(4200, "よろしくお願いします。", true), "B"),
+                ],
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_current_version_roundtrip_and_rejects() {
+        let original = TranscriptionEvent {
+            speaker: Some("A".to_string()),
+            ..event(12_345, "テスト", true)
+        };
+        let line = encode_line(&original).unwrap();
+        assert!(line.starts_with(r#"{"schema":2,"#));
+        assert_eq!(decode_line(&line).unwrap(), (original, CURRENT_VERSION));
+
+        let newer = r#"{"schema":3,"timestamp_ms":0,"text":"","is_final":true}"#;
+        assert!(decode_line(newer).is_err());
+        let invalid = r#"{"schema":0,"timestamp_ms":0,"text":"","is_final":true}"#;
+        assert!(decode_line(invalid).is_err());
+        assert!(decode_line("[]").is_err());
+    }
+}
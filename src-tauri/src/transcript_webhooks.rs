@@ -0,0 +1,236 @@
+//! Transcript Webhooks
+//!
+//! POSTs every final segment and the end of each session as JSON to the
+//! configured URLs, for no-code integrations (Zapier, n8n, ...) without an
+//! extension. Unlike routing rules there is no pattern: each webhook
+//! receives every event it subscribes to. Final segments are sent from the
+//! pipeline's `webhooks` stage, so they carry redacted text.
+//!
+//! A delivery that fails with a network error, a timeout, 408, 429 or a 5xx
+//! response is retried up to `max_retries` times with exponential backoff
+//! (`retry_delay`). Every delivery carries a `delivery_id`, identical across
+//! its retries, so receivers can drop duplicates.
+//!
+//! Persisted to `settings/transcript_webhooks.json` in app data directory.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Most webhooks
+pub const MAX_WEBHOOKS: usize = 16;
+
+/// Most retries of one delivery
+pub const MAX_RETRIES: u32 = 10;
+
+/// Backoff before the first retry (doubled per retry)
+const BASE_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Upper bound of the backoff
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(5 * 60);
+
+/// Event a webhook can subscribe to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TranscriptWebhookEvent {
+    FinalText,
+    SessionEnd,
+}
+
+/// One webhook endpoint
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranscriptWebhook {
+    /// http(s) URL receiving the POST
+    pub url: String,
+    /// Events to send (empty: every event)
+    #[serde(default)]
+    pub events: Vec<TranscriptWebhookEvent>,
+    /// Sent as `Authorization: Bearer <token>`
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+impl TranscriptWebhook {
+    pub fn accepts(&self, event: TranscriptWebhookEvent) -> bool {
+        self.events.is_empty() || self.events.contains(&event)
+    }
+}
+
+/// Webhook configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranscriptWebhookSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub webhooks: Vec<TranscriptWebhook>,
+    /// Retries of a failed delivery (0: no retry)
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    /// Settings version for future migrations
+    #[serde(default = "default_version")]
+    pub version: u32,
+}
+
+fn default_max_retries() -> u32 {
+    5
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+impl Default for TranscriptWebhookSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            webhooks: Vec::new(),
+            max_retries: default_max_retries(),
+            version: 1,
+        }
+    }
+}
+
+impl TranscriptWebhookSettings {
+    pub fn validate(&self) -> Result<()> {
+        if self.webhooks.len() > MAX_WEBHOOKS {
+            anyhow::bail!("At most {} webhooks are supported", MAX_WEBHOOKS);
+        }
+        if self.max_retries > MAX_RETRIES {
+            anyhow::bail!("At most {} retries are supported", MAX_RETRIES);
+        }
+        for webhook in &self.webhooks {
+            if !(webhook.url.starts_with("https://") || webhook.url.starts_with("http://")) {
+                anyhow::bail!("Webhook URL must start with http:// or https://");
+            }
+        }
+        Ok(())
+    }
+
+    /// Webhooks receiving `event` (none while disabled)
+    pub fn targets(&self, event: TranscriptWebhookEvent) -> Vec<&TranscriptWebhook> {
+        if !self.enabled {
+            return Vec::new();
+        }
+        self.webhooks.iter().filter(|w| w.accepts(event)).collect()
+    }
+}
+
+/// Webhook payload
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TranscriptWebhookPayload {
+    /// Same for every retry of a delivery
+    pub delivery_id: String,
+    #[serde(flatten)]
+    pub body: TranscriptWebhookBody,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum TranscriptWebhookBody {
+    FinalText {
+        session_id: String,
+        text: String,
+        /// Offset from the start of the session
+        timestamp_ms: u64,
+    },
+    SessionEnd {
+        session_id: String,
+        warnings: Vec<String>,
+    },
+}
+
+impl TranscriptWebhookBody {
+    pub fn event(&self) -> TranscriptWebhookEvent {
+        match self {
+            TranscriptWebhookBody::FinalText { .. } => TranscriptWebhookEvent::FinalText,
+            TranscriptWebhookBody::SessionEnd { .. } => TranscriptWebhookEvent::SessionEnd,
+        }
+    }
+
+    /// Payload with a fresh delivery id
+    pub fn into_payload(self) -> TranscriptWebhookPayload {
+        TranscriptWebhookPayload {
+            delivery_id: uuid::Uuid::new_v4().to_string(),
+            body: self,
+        }
+    }
+}
+
+/// Whether a response status is worth retrying
+pub fn is_retryable_status(status: u16) -> bool {
+    status == 408 || status == 429 || (500..600).contains(&status)
+}
+
+/// Backoff before retry `attempt` (1-based): 1s, 2s, 4s, ... up to 5 minutes
+pub fn retry_delay(attempt: u32) -> Duration {
+    BASE_RETRY_DELAY
+        .saturating_mul(1u32 << attempt.saturating_sub(1).min(16))
+        .min(MAX_RETRY_DELAY)
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+pub const SETTINGS_STEM: &str = "transcript_webhooks";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_targets_payload_and_settings() {
+        let mut settings = TranscriptWebhookSettings {
+            webhooks: vec![
+                TranscriptWebhook {
+                    url: "https://hooks.example/all".to_string(),
+                    events: Vec::new(),
+                    token: None,
+                },
+                TranscriptWebhook {
+                    url: "https://hooks.example/end".to_string(),
+                    events: vec![TranscriptWebhookEvent::SessionEnd],
+                    token: Some("secret".to_string()),
+                },
+            ],
+            ..Default::default()
+        };
+        assert!(settings
+            .targets(TranscriptWebhookEvent::SessionEnd)
+            .is_empty());
+        settings.enabled = true;
+        assert_eq!(
+            settings.targets(TranscriptWebhookEvent::SessionEnd).len(),
+            2
+        );
+        assert_eq!(settings.targets(TranscriptWebhookEvent::FinalText).len(), 1);
+        settings.validate().unwrap();
+
+        let payload = TranscriptWebhookBody::FinalText {
+            session_id: "s1".to_string(),
+            text: "こんにちは".to_string(),
+            timestamp_ms: 1500,
+        }
+        .into_payload();
+        let value = serde_json::to_value(&payload).unwrap();
+        assert_eq!(value["event"], json!("final_text"));
+        assert_eq!(value["text"], json!("こんにちは"));
+        assert_eq!(value["delivery_id"], json!(payload.delivery_id));
+
+        settings.webhooks[0].url = "ftp://hooks.example".to_string();
+        assert!(settings.validate().is_err());
+    }
+
+    #[test]
+    fn test_retry_policy() {
+        assert!(is_retryable_status(503));
+        assert!(is_retryable_status(429));
+        assert!(!is_retryable_status(404));
+        assert!(!is_retryable_status(200));
+
+        assert_eq!(retry_delay(1), Duration::from_secs(1));
+        assert_eq!(retry_delay(4), Duration::from_secs(8));
+        assert_eq!(retry_delay(30), MAX_RETRY_DELAY);
+    }
+}
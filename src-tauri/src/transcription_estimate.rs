@@ -0,0 +1,318 @@
+//! Transcription Cost and Impact Estimates
+//!
+//! Transcribing a 6-hour workshop again with a large model can keep the
+//! machine busy for hours; a cloud engine is fast but costs money and
+//! uploads the audio. `estimate_transcription` reports what to expect
+//! before a job is queued, from the audio length and the chosen engine:
+//!
+//! - Local engines (faster-whisper models): wall-clock time from the
+//!   model's real-time factor and the transcription workers that fit on
+//!   the machine's cores, the share of CPU (or the GPU) kept busy. When the
+//!   sidecar runs the same model, its measured real-time factor (health
+//!   checks) replaces the profile's.
+//! - Cloud engines (one per `cloud_stt::CloudProvider`, with the provider
+//!   name as id): turnaround time and cost from the provider's list price
+//!   per audio minute.
+//!
+//! Profiles are rough figures for typical hardware (int8 on CPU, float16
+//! on GPU) and list prices change; the estimate is a guide, not a quote.
+
+use crate::cloud_stt::CloudProvider;
+use serde::Serialize;
+
+/// Where an engine runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EngineKind {
+    Cpu,
+    Gpu,
+    Cloud,
+}
+
+/// Performance and price figures of a transcription engine
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EngineProfile {
+    pub id: &'static str,
+    pub label: &'static str,
+    pub kind: EngineKind,
+    /// Whisper model size (local engines; matched against the sidecar's)
+    pub model: Option<&'static str>,
+    /// Processing time per audio time of one worker
+    pub real_time_factor: f64,
+    /// CPU cores one worker keeps busy
+    pub cpu_cores_per_worker: f64,
+    /// List price per audio minute (cloud engines)
+    pub usd_per_minute: Option<f64>,
+}
+
+/// Local engines
+const LOCAL_ENGINES: &[EngineProfile] = &[
+    local("whisper-tiny", "Whisper tiny (CPU)", "tiny", 0.05),
+    local("whisper-base", "Whisper base (CPU)", "base", 0.08),
+    local("whisper-small", "Whisper small (CPU)", "small", 0.2),
+    local("whisper-medium", "Whisper medium (CPU)", "medium", 0.5),
+    local(
+        "whisper-large-v3",
+        "Whisper large-v3 (CPU)",
+        "large-v3",
+        1.0,
+    ),
+    EngineProfile {
+        id: "whisper-large-v3-gpu",
+        label: "Whisper large-v3 (GPU)",
+        kind: EngineKind::Gpu,
+        model: Some("large-v3"),
+        real_time_factor: 0.05,
+        cpu_cores_per_worker: 1.0,
+        usd_per_minute: None,
+    },
+];
+
+/// Known engines: the local ones, then one per cloud STT provider
+pub fn engines() -> Vec<EngineProfile> {
+    LOCAL_ENGINES
+        .iter()
+        .cloned()
+        .chain(CloudProvider::ALL.into_iter().map(cloud))
+        .collect()
+}
+
+const fn local(
+    id: &'static str,
+    label: &'static str,
+    model: &'static str,
+    real_time_factor: f64,
+) -> EngineProfile {
+    EngineProfile {
+        id,
+        label,
+        kind: EngineKind::Cpu,
+        model: Some(model),
+        real_time_factor,
+        // faster-whisper's default thread count
+        cpu_cores_per_worker: 4.0,
+        usd_per_minute: None,
+    }
+}
+
+fn cloud(provider: CloudProvider) -> EngineProfile {
+    // List prices of the default models (`cloud_client.py`)
+    let (label, usd_per_minute) = match provider {
+        CloudProvider::OpenAi => ("OpenAI Whisper API", 0.006),
+        CloudProvider::Azure => ("Azure OpenAI gpt-4o-mini-transcribe", 0.003),
+        CloudProvider::Deepgram => ("Deepgram Nova-2 (streaming)", 0.0058),
+    };
+    EngineProfile {
+        id: provider.as_str(),
+        label,
+        kind: EngineKind::Cloud,
+        model: None,
+        // Turnaround of a batch job, upload not included
+        real_time_factor: 0.1,
+        cpu_cores_per_worker: 0.0,
+        usd_per_minute: Some(usd_per_minute),
+    }
+}
+
+pub fn find_engine(id: &str) -> Option<EngineProfile> {
+    engines().into_iter().find(|engine| engine.id == id)
+}
+
+/// Source of the real-time factor used
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RtfSource {
+    /// The engine's profile
+    Profile,
+    /// Measured by the sidecar's health checks
+    Measured,
+}
+
+/// What the estimate depends on besides the engine
+#[derive(Debug, Clone, PartialEq)]
+pub struct EstimateInputs {
+    pub audio_ms: u64,
+    /// Configured transcription workers (`transcription_workers`)
+    pub workers: u32,
+    pub cpu_cores: usize,
+    /// Real-time factor reported by the sidecar, when it runs the same model
+    pub measured_real_time_factor: Option<f64>,
+    /// A recording is active (heavy jobs pause meanwhile)
+    pub recording: bool,
+}
+
+/// Expected duration, load and cost of a transcription job
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TranscriptionEstimate {
+    pub engine: String,
+    pub kind: EngineKind,
+    pub audio_ms: u64,
+    /// Expected wall-clock time of the job
+    pub duration_ms: u64,
+    pub real_time_factor: f64,
+    pub real_time_factor_source: RtfSource,
+    /// Workers transcribing in parallel
+    pub workers: u32,
+    /// Share of all CPU cores kept busy (0-100)
+    pub cpu_percent: u8,
+    /// GPU utilization (None when no GPU is used)
+    pub gpu_percent: Option<u8>,
+    /// Approximate cost in USD (cloud engines)
+    pub cost_usd: Option<f64>,
+    /// Caveats worth showing next to the numbers
+    pub notes: Vec<String>,
+}
+
+/// Estimate a transcription job of `engine`
+pub fn estimate(engine: &EngineProfile, inputs: &EstimateInputs) -> TranscriptionEstimate {
+    let mut notes = Vec::new();
+    let cpu_cores = inputs.cpu_cores.max(1);
+
+    let (real_time_factor, real_time_factor_source) =
+        match inputs.measured_real_time_factor.filter(|rtf| *rtf > 0.0) {
+            Some(measured) if engine.kind == EngineKind::Cpu => (measured, RtfSource::Measured),
+            _ => (engine.real_time_factor, RtfSource::Profile),
+        };
+
+    // Parallel segments only on CPU: one GPU, and cloud jobs are one upload
+    let workers = match engine.kind {
+        EngineKind::Cpu => {
+            let fit = ((cpu_cores as f64 / engine.cpu_cores_per_worker) as u32).max(1);
+            let workers = inputs.workers.clamp(1, fit);
+            if workers < inputs.workers {
+                notes.push(format!(
+                    "Only {} of {} workers fit on {} CPU cores",
+                    workers, inputs.workers, cpu_cores
+                ));
+            }
+            workers
+        }
+        EngineKind::Gpu | EngineKind::Cloud => 1,
+    };
+
+    let duration_ms = (inputs.audio_ms as f64 * real_time_factor / workers as f64).round() as u64;
+    let cpu_percent = ((workers as f64 * engine.cpu_cores_per_worker * 100.0 / cpu_cores as f64)
+        .round() as u64)
+        .min(100) as u8;
+    let gpu_percent = (engine.kind == EngineKind::Gpu).then_some(90);
+
+    let cost_usd = engine.usd_per_minute.map(|rate| {
+        // Billed per second of audio
+        let minutes = inputs.audio_ms.div_ceil(1000) as f64 / 60.0;
+        (minutes * rate * 100.0).round() / 100.0
+    });
+
+    match engine.kind {
+        EngineKind::Cloud => {
+            notes.push(format!(
+                "The audio is uploaded to {}; upload time is not included",
+                engine.label
+            ));
+            notes.push("Cost is based on list prices; check the provider's pricing".to_string());
+        }
+        EngineKind::Cpu | EngineKind::Gpu => {
+            if real_time_factor / workers as f64 > 1.0 {
+                notes.push(
+                    "Slower than real time: consider a smaller model or more workers".to_string(),
+                );
+            }
+            if inputs.recording {
+                notes.push("Transcription jobs pause while recording".to_string());
+            }
+        }
+    }
+
+    TranscriptionEstimate {
+        engine: engine.id.to_string(),
+        kind: engine.kind,
+        audio_ms: inputs.audio_ms,
+        duration_ms,
+        real_time_factor,
+        real_time_factor_source,
+        workers,
+        cpu_percent,
+        gpu_percent,
+        cost_usd,
+        notes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIX_HOURS_MS: u64 = 6 * 60 * 60 * 1000;
+
+    fn inputs(workers: u32, cpu_cores: usize) -> EstimateInputs {
+        EstimateInputs {
+            audio_ms: SIX_HOURS_MS,
+            workers,
+            cpu_cores,
+            measured_real_time_factor: None,
+            recording: false,
+        }
+    }
+
+    #[test]
+    fn test_local_estimate_uses_workers_that_fit() {
+        let engine = &find_engine("whisper-medium").unwrap();
+
+        let single = estimate(engine, &inputs(1, 8));
+        assert_eq!(single.duration_ms, SIX_HOURS_MS / 2);
+        assert_eq!(single.cpu_percent, 50);
+        assert_eq!(single.cost_usd, None);
+        assert!(single.notes.is_empty());
+
+        // 8 cores fit 2 workers of 4 threads
+        let pool = estimate(engine, &inputs(4, 8));
+        assert_eq!(pool.workers, 2);
+        assert_eq!(pool.duration_ms, SIX_HOURS_MS / 4);
+        assert_eq!(pool.cpu_percent, 100);
+        assert_eq!(pool.notes.len(), 1);
+
+        // The sidecar's measured speed wins over the profile
+        let measured = estimate(
+            engine,
+            &EstimateInputs {
+                measured_real_time_factor: Some(1.5),
+                recording: true,
+                ..inputs(1, 8)
+            },
+        );
+        assert_eq!(measured.real_time_factor_source, RtfSource::Measured);
+        assert_eq!(measured.duration_ms, SIX_HOURS_MS * 3 / 2);
+        assert_eq!(measured.notes.len(), 2);
+    }
+
+    #[test]
+    fn test_cloud_estimate_reports_cost() {
+        let engine = find_engine("openai").unwrap();
+        let estimate = estimate(
+            &engine,
+            &EstimateInputs {
+                measured_real_time_factor: Some(1.5),
+                ..inputs(4, 8)
+            },
+        );
+        // 360 minutes at $0.006
+        assert_eq!(estimate.cost_usd, Some(2.16));
+        assert_eq!(estimate.workers, 1);
+        assert_eq!(estimate.real_time_factor_source, RtfSource::Profile);
+        assert_eq!(estimate.gpu_percent, None);
+        assert!(find_engine("unknown").is_none());
+    }
+
+    #[test]
+    fn test_every_cloud_provider_has_an_estimate() {
+        for provider in CloudProvider::ALL {
+            let engine = find_engine(provider.as_str()).unwrap();
+            assert_eq!(engine.kind, EngineKind::Cloud);
+            assert!(estimate(&engine, &inputs(1, 8)).cost_usd.unwrap() > 0.0);
+        }
+        let cloud_engines = engines()
+            .into_iter()
+            .filter(|engine| engine.kind == EngineKind::Cloud)
+            .count();
+        assert_eq!(cloud_engines, CloudProvider::ALL.len());
+    }
+}
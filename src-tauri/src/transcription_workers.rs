@@ -0,0 +1,107 @@
+//! Transcription Worker Pool
+//!
+//! With a large model on a multi-core machine, one sidecar process can't
+//! keep up: final transcription of a speech segment holds the sidecar's
+//! event loop for as long as Whisper runs, while the next segment is
+//! already arriving. In worker-pool mode the sidecar is started with
+//! `--workers N` and spawns N transcription processes, each loading its
+//! own model. Every VAD segment's final transcription goes to the next
+//! free worker; VAD and partial results stay in the main process, and
+//! final results are still delivered in segment order.
+//!
+//! Each worker holds a full model in memory, so the worker count trades
+//! memory for throughput. `workers = 1` (default) keeps the single-process
+//! sidecar. Read when the sidecar starts.
+//!
+//! Settings are machine-wide; persisted to
+//! `settings/transcription_workers.json` in app data directory.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// Upper bound of the worker count (each worker loads a model)
+pub const MAX_WORKERS: u32 = 8;
+
+/// Worker pool configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranscriptionWorkerSettings {
+    /// Transcription processes (1 = no pool)
+    #[serde(default = "default_workers")]
+    pub workers: u32,
+    /// Settings version for future migrations
+    #[serde(default = "default_version")]
+    pub version: u32,
+}
+
+fn default_workers() -> u32 {
+    1
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+impl Default for TranscriptionWorkerSettings {
+    fn default() -> Self {
+        Self {
+            workers: default_workers(),
+            version: 1,
+        }
+    }
+}
+
+impl TranscriptionWorkerSettings {
+    pub fn validate(&self) -> Result<()> {
+        if self.workers == 0 || self.workers > MAX_WORKERS {
+            anyhow::bail!("workers must be between 1 and {}", MAX_WORKERS);
+        }
+        Ok(())
+    }
+
+    /// Whether the sidecar runs a worker pool
+    pub fn is_pool(&self) -> bool {
+        self.workers > 1
+    }
+
+    /// Sidecar command line arguments
+    pub fn sidecar_args(&self) -> Vec<String> {
+        if self.is_pool() {
+            vec!["--workers".to_string(), self.workers.to_string()]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+pub const SETTINGS_STEM: &str = "transcription_workers";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sidecar_args_and_validation() {
+        let single = TranscriptionWorkerSettings::default();
+        assert!(single.validate().is_ok());
+        assert!(single.sidecar_args().is_empty());
+
+        let pool = TranscriptionWorkerSettings {
+            workers: 4,
+            ..Default::default()
+        };
+        assert!(pool.validate().is_ok());
+        assert_eq!(pool.sidecar_args(), ["--workers", "4"]);
+
+        for workers in [0, MAX_WORKERS + 1] {
+            let settings = TranscriptionWorkerSettings {
+                workers,
+                ..Default::default()
+            };
+            assert!(settings.validate().is_err(), "{}", workers);
+        }
+    }
+}
@@ -0,0 +1,78 @@
+//! Session Trash Settings
+//!
+//! Deleted sessions are moved to `recordings/.trash/` (see
+//! `LocalStorageService::trash_session`) and can be restored until the
+//! retention window has passed. Expired sessions are purged at startup and
+//! whenever another session is deleted.
+//!
+//! Persisted to `settings/trash.json` in app data directory.
+
+use serde::{Deserialize, Serialize};
+
+/// Default retention of deleted sessions
+pub const DEFAULT_RETENTION_DAYS: u32 = 30;
+
+const MS_PER_DAY: u64 = 24 * 60 * 60 * 1000;
+
+/// Trash configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrashSettings {
+    /// Days a deleted session stays restorable (0 = purge on next sweep)
+    #[serde(default = "default_retention_days")]
+    pub retention_days: u32,
+    /// Settings version for future migrations
+    #[serde(default = "default_version")]
+    pub version: u32,
+}
+
+fn default_retention_days() -> u32 {
+    DEFAULT_RETENTION_DAYS
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+impl Default for TrashSettings {
+    fn default() -> Self {
+        Self {
+            retention_days: DEFAULT_RETENTION_DAYS,
+            version: 1,
+        }
+    }
+}
+
+impl TrashSettings {
+    pub fn retention_ms(&self) -> u64 {
+        self.retention_days as u64 * MS_PER_DAY
+    }
+
+    /// When a session deleted at `deleted_at_ms` gets purged
+    pub fn expires_at_ms(&self, deleted_at_ms: u64) -> u64 {
+        deleted_at_ms.saturating_add(self.retention_ms())
+    }
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+pub const SETTINGS_STEM: &str = "trash";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expiry_and_defaults() {
+        let settings = TrashSettings {
+            retention_days: 7,
+            ..Default::default()
+        };
+        assert_eq!(settings.expires_at_ms(1_000), 1_000 + 7 * MS_PER_DAY);
+
+        // Missing fields fall back to defaults
+        let settings: TrashSettings = serde_json::from_str("{}").unwrap();
+        assert_eq!(settings.retention_days, DEFAULT_RETENTION_DAYS);
+    }
+}
@@ -0,0 +1,122 @@
+//! Read-only Viewer Mode
+//!
+//! For a secondary machine that only consumes archives synced from the
+//! recorder: the app starts without the Python sidecar, audio adapters or
+//! the WebSocket server, and only commands for browsing, searching,
+//! playing back and exporting sessions are accepted. Every other command is
+//! rejected before it runs.
+//!
+//! Enabled with `--viewer` on the command line or the saved setting; both
+//! take effect at startup. The setting is machine-wide (not per workspace).
+//!
+//! Persisted to `settings/viewer_mode.json` in app data directory.
+
+use serde::{Deserialize, Serialize};
+
+/// Command line flag enabling viewer mode for one run
+pub const VIEWER_FLAG: &str = "--viewer";
+
+/// Commands accepted in viewer mode
+const ALLOWED_COMMANDS: &[&str] = &[
+    // Viewer mode itself
+    "get_viewer_mode",
+    "save_viewer_mode_settings",
+    "load_viewer_mode_settings",
+    "get_platform_info",
+    "get_health",
+    // Browsing
+    "get_session_list",
+    "get_session",
+    "compute_session_stats",
+    "get_session_activity",
+    "get_session_timeline",
+    "get_waveform",
+    "get_waveform_envelope",
+    "diff_transcripts",
+    "get_storage_root",
+    "list_workspaces",
+    "switch_workspace",
+    "import_archive",
+    "get_jobs",
+    "cancel_job",
+    "get_maintenance_status",
+    // Search
+    "search_in_session",
+    // Playback
+    "play_session_audio",
+    "stop_playback",
+    // Export
+    "export_session_chapters",
+    "export_session_markdown",
+    "export_session_bilingual_markdown",
+    "export_session_bilingual_docx",
+    "export_session_bilingual_html",
+    "export_session_text",
+    "export_session_docx",
+    "export_session_html",
+    "export_session_trimmed",
+    "redact_audio",
+    "batch_export_sessions",
+    "archive_session",
+    "load_furigana_settings",
+];
+
+/// Viewer mode configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ViewerModeSettings {
+    /// Start in viewer mode
+    #[serde(default)]
+    pub enabled: bool,
+    /// Settings version for future migrations
+    #[serde(default = "default_version")]
+    pub version: u32,
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+impl Default for ViewerModeSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            version: 1,
+        }
+    }
+}
+
+/// Whether `command` may run in viewer mode
+pub fn is_allowed(command: &str) -> bool {
+    ALLOWED_COMMANDS.contains(&command)
+}
+
+/// Whether `--viewer` was given on the command line
+pub fn requested<I: IntoIterator<Item = String>>(args: I) -> bool {
+    args.into_iter().any(|arg| arg == VIEWER_FLAG)
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+pub const SETTINGS_STEM: &str = "viewer_mode";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_viewer_mode_allowlist_and_settings() {
+        assert!(is_allowed("get_session_list"));
+        assert!(is_allowed("play_session_audio"));
+        assert!(is_allowed("export_session_markdown"));
+        assert!(is_allowed("save_viewer_mode_settings"));
+        assert!(!is_allowed("start_recording"));
+        assert!(!is_allowed("list_audio_devices"));
+        assert!(!is_allowed("delete_session"));
+
+        assert!(requested(["app".to_string(), "--viewer".to_string()]));
+        assert!(!requested(["app".to_string()]));
+        assert!(!ViewerModeSettings::default().enabled);
+    }
+}
@@ -0,0 +1,799 @@
+//! WASAPI Capture Tuning (Windows)
+//!
+//! The default capture stream runs in WASAPI shared mode with the audio
+//! engine's default buffer, which on some machines glitches periodically and
+//! shows up as `Stalled` events. `WasapiAdapter` can instead open the device
+//! itself with:
+//!
+//! - `buffer_ms`: the shared-mode buffer duration (0: engine default)
+//! - `exclusive`: exclusive-mode capture, bypassing the audio engine (other
+//!   apps can't use the microphone while recording)
+//!
+//! Captures are attempted in order — exclusive, shared with `buffer_ms`, then
+//! the default stream — so a device refusing exclusive mode or the buffer
+//! size still records. The capture in use, with its effective buffer size
+//! and the reasons of the failed attempts, is reported in the diagnostic
+//! bundle (`last_effective`).
+//!
+//! Settings are machine-wide (not per workspace) and take effect at the next
+//! recording start. Persisted to `settings/wasapi_capture.json` in app data
+//! directory.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+/// Shortest configurable buffer
+pub const MIN_BUFFER_MS: u32 = 3;
+
+/// Longest configurable buffer
+pub const MAX_BUFFER_MS: u32 = 500;
+
+/// Capture configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WasapiCaptureSettings {
+    /// Buffer duration in milliseconds (0: engine default)
+    #[serde(default)]
+    pub buffer_ms: u32,
+    /// Try exclusive-mode capture first
+    #[serde(default)]
+    pub exclusive: bool,
+    /// Settings version for future migrations
+    #[serde(default = "default_version")]
+    pub version: u32,
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+impl Default for WasapiCaptureSettings {
+    fn default() -> Self {
+        Self {
+            buffer_ms: 0,
+            exclusive: false,
+            version: 1,
+        }
+    }
+}
+
+impl WasapiCaptureSettings {
+    pub fn validate(&self) -> Result<()> {
+        if self.buffer_ms != 0 && !(MIN_BUFFER_MS..=MAX_BUFFER_MS).contains(&self.buffer_ms) {
+            anyhow::bail!(
+                "Buffer duration must be 0 (default) or {}-{} ms",
+                MIN_BUFFER_MS,
+                MAX_BUFFER_MS
+            );
+        }
+        Ok(())
+    }
+}
+
+/// WASAPI share mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CaptureMode {
+    Exclusive,
+    Shared,
+}
+
+/// One capture attempt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CaptureRequest {
+    pub mode: CaptureMode,
+    /// Buffer duration (0: device period in exclusive mode)
+    pub buffer_ms: u32,
+}
+
+/// Native captures to attempt, in order, before the default stream
+///
+/// Empty with the default settings: the default stream is used as is.
+pub fn capture_attempts(settings: &WasapiCaptureSettings) -> Vec<CaptureRequest> {
+    let mut attempts = Vec::new();
+    if settings.exclusive {
+        attempts.push(CaptureRequest {
+            mode: CaptureMode::Exclusive,
+            buffer_ms: settings.buffer_ms,
+        });
+    }
+    if settings.buffer_ms > 0 {
+        attempts.push(CaptureRequest {
+            mode: CaptureMode::Shared,
+            buffer_ms: settings.buffer_ms,
+        });
+    }
+    attempts
+}
+
+/// Sample format of the capture buffer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SampleFormat {
+    F32,
+    I16,
+    /// 32-bit container (also 24-bit samples, left-justified)
+    I32,
+}
+
+impl SampleFormat {
+    pub fn bytes(self) -> usize {
+        match self {
+            SampleFormat::I16 => 2,
+            SampleFormat::F32 | SampleFormat::I32 => 4,
+        }
+    }
+}
+
+/// Convert interleaved little-endian samples to f32 in [-1, 1]
+pub fn samples_to_f32(format: SampleFormat, bytes: &[u8], out: &mut Vec<f32>) {
+    out.clear();
+    match format {
+        SampleFormat::F32 => out.extend(
+            bytes
+                .chunks_exact(4)
+                .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])),
+        ),
+        SampleFormat::I16 => out.extend(
+            bytes
+                .chunks_exact(2)
+                .map(|b| i16::from_le_bytes([b[0], b[1]]) as f32 / 32768.0),
+        ),
+        SampleFormat::I32 => out.extend(
+            bytes
+                .chunks_exact(4)
+                .map(|b| i32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f32 / 2_147_483_648.0),
+        ),
+    }
+}
+
+/// Milliseconds to WASAPI reference time (100 ns units)
+pub fn ms_to_hns(ms: u32) -> i64 {
+    ms as i64 * 10_000
+}
+
+/// Duration of `frames` at `sample_rate`, in milliseconds
+pub fn frames_to_ms(frames: u32, sample_rate: u32) -> f64 {
+    if sample_rate == 0 {
+        return 0.0;
+    }
+    frames as f64 * 1000.0 / sample_rate as f64
+}
+
+/// Capture in use, for diagnostics
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EffectiveCapture {
+    pub device: String,
+    /// Opened directly through WASAPI (false: default stream)
+    pub native: bool,
+    pub mode: CaptureMode,
+    /// Buffer size reported by the device (unknown for the default stream)
+    pub buffer_frames: Option<u32>,
+    pub buffer_ms: Option<f64>,
+    pub sample_rate: u32,
+    pub channels: u16,
+    pub sample_format: Option<SampleFormat>,
+    /// Why the preceding attempts failed
+    pub fallback_reasons: Vec<String>,
+}
+
+static SETTINGS: Mutex<Option<WasapiCaptureSettings>> = Mutex::new(None);
+static LAST_EFFECTIVE: Mutex<Option<EffectiveCapture>> = Mutex::new(None);
+
+/// Apply settings from the next recording start
+pub fn set_settings(settings: WasapiCaptureSettings) {
+    *SETTINGS.lock().unwrap() = Some(settings);
+}
+
+pub fn current_settings() -> WasapiCaptureSettings {
+    SETTINGS.lock().unwrap().clone().unwrap_or_default()
+}
+
+/// Remember the capture of the current recording
+pub fn record_effective(capture: EffectiveCapture) {
+    *LAST_EFFECTIVE.lock().unwrap() = Some(capture);
+}
+
+/// Capture of the latest recording (None before any recording on Windows)
+pub fn last_effective() -> Option<EffectiveCapture> {
+    LAST_EFFECTIVE.lock().unwrap().clone()
+}
+
+// ============================================================================
+// Native capture
+// ============================================================================
+
+#[cfg(target_os = "windows")]
+pub mod native {
+    //! Event-driven WASAPI capture on a dedicated thread
+
+    use super::*;
+    use anyhow::{anyhow, bail};
+    use std::ptr;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{mpsc, Arc};
+    use std::thread::JoinHandle;
+    use winapi::shared::devpkey::DEVPKEY_Device_FriendlyName;
+    use winapi::shared::guiddef::{IsEqualGUID, GUID};
+    use winapi::shared::ksmedia::{KSDATAFORMAT_SUBTYPE_IEEE_FLOAT, KSDATAFORMAT_SUBTYPE_PCM};
+    use winapi::shared::minwindef::FALSE;
+    use winapi::shared::mmreg::{
+        WAVEFORMATEX, WAVEFORMATEXTENSIBLE, WAVE_FORMAT_EXTENSIBLE, WAVE_FORMAT_IEEE_FLOAT,
+        WAVE_FORMAT_PCM,
+    };
+    use winapi::shared::winerror::{FAILED, HRESULT, SUCCEEDED, S_OK};
+    use winapi::shared::wtypes::VT_LPWSTR;
+    use winapi::um::audioclient::{
+        IAudioCaptureClient, IAudioClient, AUDCLNT_BUFFERFLAGS_SILENT,
+        AUDCLNT_E_BUFFER_SIZE_NOT_ALIGNED,
+    };
+    use winapi::um::audiosessiontypes::{
+        AUDCLNT_SHAREMODE, AUDCLNT_SHAREMODE_EXCLUSIVE, AUDCLNT_SHAREMODE_SHARED,
+        AUDCLNT_STREAMFLAGS_EVENTCALLBACK,
+    };
+    use winapi::um::combaseapi::{
+        CoCreateInstance, CoInitializeEx, CoTaskMemFree, CoUninitialize, PropVariantClear,
+        CLSCTX_ALL,
+    };
+    use winapi::um::coml2api::STGM_READ;
+    use winapi::um::handleapi::CloseHandle;
+    use winapi::um::mmdeviceapi::{
+        eCapture, CLSID_MMDeviceEnumerator, IMMDevice, IMMDeviceCollection, IMMDeviceEnumerator,
+        DEVICE_STATE_ACTIVE,
+    };
+    use winapi::um::objbase::COINIT_MULTITHREADED;
+    use winapi::um::propidl::PROPVARIANT;
+    use winapi::um::propsys::IPropertyStore;
+    use winapi::um::synchapi::{CreateEventW, WaitForSingleObject};
+    use winapi::um::unknwnbase::IUnknown;
+    use winapi::um::winbase::WAIT_OBJECT_0;
+    use winapi::um::winnt::HANDLE;
+    use winapi::Interface;
+
+    /// Receives interleaved samples with the channel count and sample rate
+    pub type DataCallback = Box<dyn FnMut(&[f32], u16, u32) + Send>;
+
+    /// Receives a capture error (the capture has stopped)
+    pub type ErrorCallback = Box<dyn Fn(String) + Send>;
+
+    /// Running capture; stopped by `stop` (or signalled to stop on drop)
+    pub struct NativeCapture {
+        stop: Arc<AtomicBool>,
+        thread: Option<JoinHandle<()>>,
+    }
+
+    impl NativeCapture {
+        pub fn stop(mut self) {
+            self.stop.store(true, Ordering::Relaxed);
+            if let Some(thread) = self.thread.take() {
+                thread.join().ok();
+            }
+        }
+    }
+
+    impl Drop for NativeCapture {
+        fn drop(&mut self) {
+            self.stop.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Open `device_name` as requested and start capturing
+    ///
+    /// Returns once the stream runs, or with the reason it couldn't be opened.
+    pub fn start(
+        device_name: &str,
+        request: CaptureRequest,
+        mut on_data: DataCallback,
+        on_error: ErrorCallback,
+    ) -> Result<(NativeCapture, EffectiveCapture)> {
+        let (ready_tx, ready_rx) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = Arc::clone(&stop);
+        let device_name = device_name.to_string();
+
+        let thread = std::thread::spawn(move || unsafe {
+            // S_FALSE / RPC_E_CHANGED_MODE still leave COM usable on this thread
+            let com = CoInitializeEx(ptr::null_mut(), COINIT_MULTITHREADED);
+            match open(&device_name, request) {
+                Ok((stream, effective)) => {
+                    ready_tx.send(Ok(effective)).ok();
+                    if let Err(e) = run(&stream, &thread_stop, &mut on_data) {
+                        on_error(format!("{:#}", e));
+                    }
+                    (*stream.client.0).Stop();
+                    drop(stream);
+                }
+                Err(e) => {
+                    ready_tx.send(Err(e)).ok();
+                }
+            }
+            if SUCCEEDED(com) {
+                CoUninitialize();
+            }
+        });
+
+        match ready_rx.recv() {
+            Ok(Ok(effective)) => Ok((
+                NativeCapture {
+                    stop,
+                    thread: Some(thread),
+                },
+                effective,
+            )),
+            Ok(Err(e)) => {
+                thread.join().ok();
+                Err(e)
+            }
+            Err(_) => {
+                thread.join().ok();
+                Err(anyhow!("WASAPI capture thread exited"))
+            }
+        }
+    }
+
+    /// Owned COM interface pointer, released on drop
+    struct Com<T>(*mut T);
+
+    impl<T> Drop for Com<T> {
+        fn drop(&mut self) {
+            if !self.0.is_null() {
+                // Every COM interface starts with the IUnknown vtable
+                unsafe { (*(self.0 as *mut IUnknown)).Release() };
+            }
+        }
+    }
+
+    struct Event(HANDLE);
+
+    impl Drop for Event {
+        fn drop(&mut self) {
+            unsafe { CloseHandle(self.0) };
+        }
+    }
+
+    struct Stream {
+        // Declared before `client`: released first
+        capture: Com<IAudioCaptureClient>,
+        client: Com<IAudioClient>,
+        event: Event,
+        format: SampleFormat,
+        channels: u16,
+        sample_rate: u32,
+    }
+
+    fn check(hr: HRESULT, what: &str) -> Result<()> {
+        if FAILED(hr) {
+            bail!("{} failed (HRESULT {:#010x})", what, hr as u32);
+        }
+        Ok(())
+    }
+
+    unsafe fn open(
+        device_name: &str,
+        request: CaptureRequest,
+    ) -> Result<(Stream, EffectiveCapture)> {
+        let device = find_device(device_name)?;
+        let mut client = activate(&device)?;
+
+        let mut mix: *mut WAVEFORMATEX = ptr::null_mut();
+        check(
+            (*client.0).GetMixFormat(&mut mix),
+            "IAudioClient::GetMixFormat",
+        )?;
+        let mix_format = read_format(mix);
+        CoTaskMemFree(mix as *mut _);
+        let (mix_format, mix_sample_format) =
+            mix_format.ok_or_else(|| anyhow!("Unsupported mix format"))?;
+        let sample_rate = mix_format.Format.nSamplesPerSec;
+        let channels = mix_format.Format.nChannels;
+        crate::resampler::validate_sample_rate(sample_rate)
+            .map_err(|e| anyhow!("Unsupported audio device '{}': {}", device_name, e))?;
+
+        let (share_mode, format, sample_format) = match request.mode {
+            CaptureMode::Shared => (AUDCLNT_SHAREMODE_SHARED, mix_format, mix_sample_format),
+            CaptureMode::Exclusive => {
+                let channel_mask = mix_format.dwChannelMask;
+                let (format, sample_format) =
+                    exclusive_format(&client, sample_rate, channels, channel_mask)
+                        .ok_or_else(|| anyhow!("No exclusive-mode format at {} Hz", sample_rate))?;
+                (AUDCLNT_SHAREMODE_EXCLUSIVE, format, sample_format)
+            }
+        };
+
+        let mut duration = ms_to_hns(request.buffer_ms);
+        if share_mode == AUDCLNT_SHAREMODE_EXCLUSIVE {
+            let (mut default_period, mut min_period) = (0, 0);
+            check(
+                (*client.0).GetDevicePeriod(&mut default_period, &mut min_period),
+                "IAudioClient::GetDevicePeriod",
+            )?;
+            duration = if duration == 0 {
+                default_period
+            } else {
+                duration.max(min_period)
+            };
+        }
+
+        let mut hr = initialize(&client, share_mode, duration, &format);
+        if hr == AUDCLNT_E_BUFFER_SIZE_NOT_ALIGNED {
+            // Retry with the aligned size, on a fresh client as documented
+            let mut aligned_frames = 0;
+            check(
+                (*client.0).GetBufferSize(&mut aligned_frames),
+                "IAudioClient::GetBufferSize",
+            )?;
+            duration = (10_000_000.0 * aligned_frames as f64 / sample_rate as f64).round() as i64;
+            client = activate(&device)?;
+            hr = initialize(&client, share_mode, duration, &format);
+        }
+        check(hr, "IAudioClient::Initialize")?;
+
+        let event = CreateEventW(ptr::null_mut(), FALSE, FALSE, ptr::null());
+        if event.is_null() {
+            bail!("CreateEventW failed");
+        }
+        let event = Event(event);
+        check(
+            (*client.0).SetEventHandle(event.0),
+            "IAudioClient::SetEventHandle",
+        )?;
+
+        let mut buffer_frames = 0;
+        check(
+            (*client.0).GetBufferSize(&mut buffer_frames),
+            "IAudioClient::GetBufferSize",
+        )?;
+
+        let mut capture: *mut IAudioCaptureClient = ptr::null_mut();
+        check(
+            (*client.0).GetService(
+                &IAudioCaptureClient::uuidof(),
+                &mut capture as *mut _ as *mut _,
+            ),
+            "IAudioClient::GetService",
+        )?;
+        let capture = Com(capture);
+
+        check((*client.0).Start(), "IAudioClient::Start")?;
+
+        let effective = EffectiveCapture {
+            device: device_name.to_string(),
+            native: true,
+            mode: request.mode,
+            buffer_frames: Some(buffer_frames),
+            buffer_ms: Some(frames_to_ms(buffer_frames, sample_rate)),
+            sample_rate,
+            channels,
+            sample_format: Some(sample_format),
+            fallback_reasons: Vec::new(),
+        };
+        let stream = Stream {
+            capture,
+            client,
+            event,
+            format: sample_format,
+            channels,
+            sample_rate,
+        };
+        Ok((stream, effective))
+    }
+
+    unsafe fn initialize(
+        client: &Com<IAudioClient>,
+        share_mode: AUDCLNT_SHAREMODE,
+        duration: i64,
+        format: &WAVEFORMATEXTENSIBLE,
+    ) -> HRESULT {
+        // Exclusive event-driven mode needs periodicity == buffer duration
+        let periodicity = if share_mode == AUDCLNT_SHAREMODE_EXCLUSIVE {
+            duration
+        } else {
+            0
+        };
+        (*client.0).Initialize(
+            share_mode,
+            AUDCLNT_STREAMFLAGS_EVENTCALLBACK,
+            duration,
+            periodicity,
+            format as *const WAVEFORMATEXTENSIBLE as *const WAVEFORMATEX,
+            ptr::null(),
+        )
+    }
+
+    unsafe fn run(stream: &Stream, stop: &AtomicBool, on_data: &mut DataCallback) -> Result<()> {
+        let mut samples = Vec::new();
+        while !stop.load(Ordering::Relaxed) {
+            if WaitForSingleObject(stream.event.0, 100) != WAIT_OBJECT_0 {
+                continue;
+            }
+            loop {
+                let mut packet_frames = 0;
+                check(
+                    (*stream.capture.0).GetNextPacketSize(&mut packet_frames),
+                    "IAudioCaptureClient::GetNextPacketSize",
+                )?;
+                if packet_frames == 0 {
+                    break;
+                }
+
+                let mut data: *mut u8 = ptr::null_mut();
+                let mut frames = 0;
+                let mut flags = 0;
+                check(
+                    (*stream.capture.0).GetBuffer(
+                        &mut data,
+                        &mut frames,
+                        &mut flags,
+                        ptr::null_mut(),
+                        ptr::null_mut(),
+                    ),
+                    "IAudioCaptureClient::GetBuffer",
+                )?;
+                let len = frames as usize * stream.channels as usize;
+                if flags & AUDCLNT_BUFFERFLAGS_SILENT != 0 || data.is_null() {
+                    samples.clear();
+                    samples.resize(len, 0.0);
+                } else {
+                    let bytes = std::slice::from_raw_parts(data, len * stream.format.bytes());
+                    samples_to_f32(stream.format, bytes, &mut samples);
+                }
+                check(
+                    (*stream.capture.0).ReleaseBuffer(frames),
+                    "IAudioCaptureClient::ReleaseBuffer",
+                )?;
+
+                on_data(&samples, stream.channels, stream.sample_rate);
+            }
+        }
+        Ok(())
+    }
+
+    /// Active capture endpoint with the friendly name `name` (the device id)
+    unsafe fn find_device(name: &str) -> Result<Com<IMMDevice>> {
+        let mut enumerator: *mut IMMDeviceEnumerator = ptr::null_mut();
+        check(
+            CoCreateInstance(
+                &CLSID_MMDeviceEnumerator,
+                ptr::null_mut(),
+                CLSCTX_ALL,
+                &IMMDeviceEnumerator::uuidof(),
+                &mut enumerator as *mut _ as *mut _,
+            ),
+            "CoCreateInstance(MMDeviceEnumerator)",
+        )?;
+        let enumerator = Com(enumerator);
+
+        let mut collection: *mut IMMDeviceCollection = ptr::null_mut();
+        check(
+            (*enumerator.0).EnumAudioEndpoints(eCapture, DEVICE_STATE_ACTIVE, &mut collection),
+            "IMMDeviceEnumerator::EnumAudioEndpoints",
+        )?;
+        let collection = Com(collection);
+
+        // Declared `*const` in winapi, but written by the call
+        let mut count = 0;
+        check(
+            (*collection.0).GetCount(ptr::addr_of_mut!(count)),
+            "IMMDeviceCollection::GetCount",
+        )?;
+        for index in 0..count {
+            let mut device = ptr::null_mut();
+            if FAILED((*collection.0).Item(index, &mut device)) {
+                continue;
+            }
+            let device = Com(device);
+            if friendly_name(&device).as_deref() == Some(name) {
+                return Ok(device);
+            }
+        }
+        bail!("Device not found: {}", name)
+    }
+
+    unsafe fn friendly_name(device: &Com<IMMDevice>) -> Option<String> {
+        let mut store: *mut IPropertyStore = ptr::null_mut();
+        if FAILED((*device.0).OpenPropertyStore(STGM_READ, &mut store)) {
+            return None;
+        }
+        let store = Com(store);
+
+        let mut value: PROPVARIANT = std::mem::zeroed();
+        if FAILED((*store.0).GetValue(
+            &DEVPKEY_Device_FriendlyName as *const _ as *const _,
+            &mut value,
+        )) {
+            return None;
+        }
+        let name = if value.vt == VT_LPWSTR as u16 {
+            let wide = *value.data.pwszVal();
+            let len = (0..).take_while(|&i| *wide.offset(i) != 0).count();
+            Some(String::from_utf16_lossy(std::slice::from_raw_parts(
+                wide, len,
+            )))
+        } else {
+            None
+        };
+        PropVariantClear(&mut value);
+        name
+    }
+
+    unsafe fn activate(device: &Com<IMMDevice>) -> Result<Com<IAudioClient>> {
+        let mut client: *mut IAudioClient = ptr::null_mut();
+        check(
+            (*device.0).Activate(
+                &IAudioClient::uuidof(),
+                CLSCTX_ALL,
+                ptr::null_mut(),
+                &mut client as *mut _ as *mut _,
+            ),
+            "IMMDevice::Activate",
+        )?;
+        Ok(Com(client))
+    }
+
+    /// Copy of a device format, as WAVEFORMATEXTENSIBLE
+    unsafe fn read_format(
+        format: *const WAVEFORMATEX,
+    ) -> Option<(WAVEFORMATEXTENSIBLE, SampleFormat)> {
+        if format.is_null() {
+            return None;
+        }
+        let header = ptr::read_unaligned(format);
+        let (sub_format, valid_bits, channel_mask) = match header.wFormatTag {
+            WAVE_FORMAT_EXTENSIBLE => {
+                let ext = ptr::read_unaligned(format as *const WAVEFORMATEXTENSIBLE);
+                (ext.SubFormat, ext.Samples, ext.dwChannelMask)
+            }
+            WAVE_FORMAT_IEEE_FLOAT => (KSDATAFORMAT_SUBTYPE_IEEE_FLOAT, header.wBitsPerSample, 0),
+            WAVE_FORMAT_PCM => (KSDATAFORMAT_SUBTYPE_PCM, header.wBitsPerSample, 0),
+            _ => return None,
+        };
+        let sample_format = match header.wBitsPerSample {
+            32 if IsEqualGUID(&sub_format, &KSDATAFORMAT_SUBTYPE_IEEE_FLOAT) => SampleFormat::F32,
+            16 if IsEqualGUID(&sub_format, &KSDATAFORMAT_SUBTYPE_PCM) => SampleFormat::I16,
+            32 if IsEqualGUID(&sub_format, &KSDATAFORMAT_SUBTYPE_PCM) => SampleFormat::I32,
+            _ => return None,
+        };
+        Some((
+            extensible(
+                header.nSamplesPerSec,
+                header.nChannels,
+                sample_format,
+                valid_bits,
+                channel_mask,
+            ),
+            sample_format,
+        ))
+    }
+
+    /// First exclusive-mode format the device accepts at the mix rate
+    unsafe fn exclusive_format(
+        client: &Com<IAudioClient>,
+        sample_rate: u32,
+        channels: u16,
+        channel_mask: u32,
+    ) -> Option<(WAVEFORMATEXTENSIBLE, SampleFormat)> {
+        let candidates = [
+            (SampleFormat::F32, 32),
+            (SampleFormat::I32, 24),
+            (SampleFormat::I32, 32),
+            (SampleFormat::I16, 16),
+        ];
+        candidates
+            .into_iter()
+            .map(|(sample_format, valid_bits)| {
+                (
+                    extensible(
+                        sample_rate,
+                        channels,
+                        sample_format,
+                        valid_bits,
+                        channel_mask,
+                    ),
+                    sample_format,
+                )
+            })
+            .find(|(format, _)| {
+                (*client.0).IsFormatSupported(
+                    AUDCLNT_SHAREMODE_EXCLUSIVE,
+                    format as *const WAVEFORMATEXTENSIBLE as *const WAVEFORMATEX,
+                    ptr::null_mut(),
+                ) == S_OK
+            })
+    }
+
+    fn extensible(
+        sample_rate: u32,
+        channels: u16,
+        sample_format: SampleFormat,
+        valid_bits: u16,
+        channel_mask: u32,
+    ) -> WAVEFORMATEXTENSIBLE {
+        let bits = (sample_format.bytes() * 8) as u16;
+        let block_align = channels * bits / 8;
+        let sub_format: GUID = match sample_format {
+            SampleFormat::F32 => KSDATAFORMAT_SUBTYPE_IEEE_FLOAT,
+            SampleFormat::I16 | SampleFormat::I32 => KSDATAFORMAT_SUBTYPE_PCM,
+        };
+        WAVEFORMATEXTENSIBLE {
+            Format: WAVEFORMATEX {
+                wFormatTag: WAVE_FORMAT_EXTENSIBLE,
+                nChannels: channels,
+                nSamplesPerSec: sample_rate,
+                nAvgBytesPerSec: sample_rate * block_align as u32,
+                nBlockAlign: block_align,
+                wBitsPerSample: bits,
+                cbSize: (std::mem::size_of::<WAVEFORMATEXTENSIBLE>()
+                    - std::mem::size_of::<WAVEFORMATEX>()) as u16,
+            },
+            Samples: valid_bits,
+            dwChannelMask: channel_mask,
+            SubFormat: sub_format,
+        }
+    }
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+pub const SETTINGS_STEM: &str = "wasapi_capture";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attempts_and_settings() {
+        let mut settings = WasapiCaptureSettings::default();
+        assert!(capture_attempts(&settings).is_empty());
+
+        settings.buffer_ms = 40;
+        assert_eq!(
+            capture_attempts(&settings),
+            vec![CaptureRequest {
+                mode: CaptureMode::Shared,
+                buffer_ms: 40,
+            }]
+        );
+
+        settings.exclusive = true;
+        let modes: Vec<_> = capture_attempts(&settings)
+            .iter()
+            .map(|attempt| attempt.mode)
+            .collect();
+        assert_eq!(modes, vec![CaptureMode::Exclusive, CaptureMode::Shared]);
+        settings.validate().unwrap();
+
+        settings.buffer_ms = 1;
+        assert!(settings.validate().is_err());
+        settings.buffer_ms = MAX_BUFFER_MS + 1;
+        assert!(settings.validate().is_err());
+    }
+
+    #[test]
+    fn test_sample_conversion() {
+        let mut out = Vec::new();
+
+        let bytes: Vec<u8> = [0i16, 16384, -32768]
+            .iter()
+            .flat_map(|s| s.to_le_bytes())
+            .collect();
+        samples_to_f32(SampleFormat::I16, &bytes, &mut out);
+        assert_eq!(out, vec![0.0, 0.5, -1.0]);
+
+        let bytes: Vec<u8> = [0.25f32, -0.75]
+            .iter()
+            .flat_map(|s| s.to_le_bytes())
+            .collect();
+        samples_to_f32(SampleFormat::F32, &bytes, &mut out);
+        assert_eq!(out, vec![0.25, -0.75]);
+
+        // 24-bit sample in a 32-bit container
+        let bytes = (0x40_0000i32 << 8).to_le_bytes();
+        samples_to_f32(SampleFormat::I32, &bytes, &mut out);
+        assert_eq!(out, vec![0.5]);
+
+        assert_eq!(ms_to_hns(20), 200_000);
+        assert_eq!(frames_to_ms(480, 48_000), 10.0);
+    }
+}
@@ -0,0 +1,400 @@
+//! Waveform Peaks
+//!
+//! Min/max peak data for instant scrubbing in the playback UI, stored as an
+//! audiowaveform-compatible binary file (`waveform.dat`, version 1, 8-bit)
+//! next to `audio.wav`. Generated at session finalize, or lazily on first
+//! open for older sessions.
+//!
+//! The returned [`WaveformData`] serializes to the audiowaveform JSON format
+//! (version 2), so it can be handed to peaks.js-style renderers directly.
+//!
+//! [`envelope`] computes a fixed number of peak/RMS buckets instead (e.g.
+//! 1000 for an overview bar), decoded from the audio on request.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+pub const WAVEFORM_FILENAME: &str = "waveform.dat";
+
+/// Resolution stored on disk; coarser resolutions are derived from it
+pub const BASE_SAMPLES_PER_PIXEL: u32 = 256;
+
+/// Session audio sample rate (see `storage::AudioWriter`)
+const SAMPLE_RATE: u32 = 16000;
+
+const DAT_HEADER_LEN: usize = 20;
+/// `flags` bit 0: 8-bit resolution
+const DAT_FLAG_8BIT: u32 = 1;
+
+/// Waveform peaks (audiowaveform JSON layout)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WaveformData {
+    pub version: u32,
+    pub channels: u32,
+    pub sample_rate: u32,
+    pub samples_per_pixel: u32,
+    pub bits: u32,
+    /// Number of min/max pairs
+    pub length: u32,
+    /// Interleaved min/max pairs
+    pub data: Vec<i8>,
+    /// Session time of the first pair (non-zero for windowed requests)
+    #[serde(default)]
+    pub start_ms: u64,
+}
+
+impl WaveformData {
+    fn new(samples_per_pixel: u32, data: Vec<i8>, start_ms: u64) -> Self {
+        Self {
+            version: 2,
+            channels: 1,
+            sample_rate: SAMPLE_RATE,
+            samples_per_pixel,
+            bits: 8,
+            length: (data.len() / 2) as u32,
+            data,
+            start_ms,
+        }
+    }
+
+    /// Derive a coarser resolution and/or a time window
+    ///
+    /// `samples_per_pixel` is rounded to a multiple of the stored resolution.
+    pub fn resample(
+        &self,
+        samples_per_pixel: u32,
+        start_ms: Option<u64>,
+        end_ms: Option<u64>,
+    ) -> WaveformData {
+        let factor = (samples_per_pixel / self.samples_per_pixel).max(1) as usize;
+        let ms_to_pair = |ms: u64| {
+            (ms * self.sample_rate as u64 / 1000 / self.samples_per_pixel as u64) as usize
+        };
+
+        let pairs = self.data.len() / 2;
+        let first = start_ms.map(ms_to_pair).unwrap_or(0).min(pairs);
+        // Align the window to whole output pixels
+        let first = first - first % factor;
+        let last = end_ms.map(ms_to_pair).unwrap_or(pairs).clamp(first, pairs);
+
+        let data = self.data[first * 2..last * 2]
+            .chunks(factor * 2)
+            .flat_map(|chunk| {
+                let min = chunk.iter().step_by(2).copied().min().unwrap_or(0);
+                let max = chunk.iter().skip(1).step_by(2).copied().max().unwrap_or(0);
+                [min, max]
+            })
+            .collect();
+        let start_ms =
+            first as u64 * self.samples_per_pixel as u64 * 1000 / self.sample_rate as u64;
+        WaveformData::new(self.samples_per_pixel * factor as u32, data, start_ms)
+    }
+}
+
+/// Accumulates min/max pairs over a stream of samples
+#[derive(Debug)]
+pub struct PeakAccumulator {
+    samples_per_pixel: u32,
+    count: u32,
+    min: i8,
+    max: i8,
+    data: Vec<i8>,
+}
+
+impl PeakAccumulator {
+    pub fn new(samples_per_pixel: u32) -> Self {
+        Self {
+            samples_per_pixel: samples_per_pixel.max(1),
+            count: 0,
+            min: i8::MAX,
+            max: i8::MIN,
+            data: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, samples: &[i16]) {
+        for &sample in samples {
+            let value = (sample >> 8) as i8;
+            self.min = self.min.min(value);
+            self.max = self.max.max(value);
+            self.count += 1;
+            if self.count == self.samples_per_pixel {
+                self.close_pixel();
+            }
+        }
+    }
+
+    pub fn finish(mut self) -> WaveformData {
+        if self.count > 0 {
+            self.close_pixel();
+        }
+        WaveformData::new(self.samples_per_pixel, self.data, 0)
+    }
+
+    fn close_pixel(&mut self) {
+        self.data.push(self.min);
+        self.data.push(self.max);
+        self.count = 0;
+        self.min = i8::MAX;
+        self.max = i8::MIN;
+    }
+}
+
+// ============================================================================
+// Peak / RMS Envelope
+// ============================================================================
+
+/// Default number of envelope buckets
+pub const DEFAULT_ENVELOPE_BUCKETS: usize = 1000;
+
+/// Most envelope buckets per request
+pub const MAX_ENVELOPE_BUCKETS: usize = 20_000;
+
+/// Peak and RMS level per bucket, normalized to 0.0..=1.0
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WaveformEnvelope {
+    pub duration_ms: u64,
+    /// Session time covered by each bucket (the last one may be shorter)
+    pub bucket_ms: f64,
+    pub peak: Vec<f32>,
+    pub rms: Vec<f32>,
+}
+
+/// Peak and sum of squares over `BASE_SAMPLES_PER_PIXEL` samples
+#[derive(Debug, Clone, Copy, Default)]
+struct LevelBlock {
+    peak: u16,
+    sum_squares: f64,
+    count: u32,
+}
+
+/// Accumulates level blocks over a stream of samples
+///
+/// Blocks keep memory small (16 bytes per 256 samples) until the bucket
+/// count is applied in [`EnvelopeAccumulator::finish`].
+#[derive(Debug, Default)]
+pub struct EnvelopeAccumulator {
+    blocks: Vec<LevelBlock>,
+    current: LevelBlock,
+    samples: u64,
+}
+
+impl EnvelopeAccumulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, samples: &[i16]) {
+        for &sample in samples {
+            self.current.peak = self.current.peak.max(sample.unsigned_abs());
+            self.current.sum_squares += sample as f64 * sample as f64;
+            self.current.count += 1;
+            if self.current.count == BASE_SAMPLES_PER_PIXEL {
+                self.blocks.push(std::mem::take(&mut self.current));
+            }
+        }
+        self.samples += samples.len() as u64;
+    }
+
+    /// Fold into at most `buckets` buckets (fewer for very short audio)
+    pub fn finish(mut self, buckets: usize) -> WaveformEnvelope {
+        if self.current.count > 0 {
+            self.blocks.push(self.current);
+        }
+        let duration_ms = self.samples * 1000 / SAMPLE_RATE as u64;
+        let buckets = buckets
+            .clamp(1, MAX_ENVELOPE_BUCKETS)
+            .min(self.blocks.len());
+        if buckets == 0 {
+            return WaveformEnvelope {
+                duration_ms,
+                bucket_ms: 0.0,
+                peak: Vec::new(),
+                rms: Vec::new(),
+            };
+        }
+
+        let (mut peak, mut rms) = (Vec::with_capacity(buckets), Vec::with_capacity(buckets));
+        for bucket in 0..buckets {
+            let first = bucket * self.blocks.len() / buckets;
+            let last = (bucket + 1) * self.blocks.len() / buckets;
+            let blocks = &self.blocks[first..last];
+            let max = blocks.iter().map(|b| b.peak).max().unwrap_or(0);
+            let sum_squares: f64 = blocks.iter().map(|b| b.sum_squares).sum();
+            let count: u64 = blocks.iter().map(|b| b.count as u64).sum();
+            peak.push((max as f32 / 32768.0).min(1.0));
+            rms.push(((sum_squares / count.max(1) as f64).sqrt() / 32768.0) as f32);
+        }
+        WaveformEnvelope {
+            duration_ms,
+            bucket_ms: self.blocks.len() as f64 * BASE_SAMPLES_PER_PIXEL as f64 * 1000.0
+                / SAMPLE_RATE as f64
+                / buckets as f64,
+            peak,
+            rms,
+        }
+    }
+}
+
+/// Peak/RMS envelope of the session's audio (WAV, FLAC or Opus) in `buckets`
+pub fn envelope(session_dir: &Path, buckets: usize) -> Result<WaveformEnvelope> {
+    let mut levels = EnvelopeAccumulator::new();
+    crate::storage::read_session_audio(session_dir, |samples| levels.push(samples))
+        .with_context(|| format!("Failed to read session audio: {:?}", session_dir))?;
+    Ok(levels.finish(buckets))
+}
+
+// ============================================================================
+// .dat Format
+// ============================================================================
+
+/// Encode as audiowaveform binary (version 1, 8-bit)
+pub fn encode_dat(waveform: &WaveformData) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(DAT_HEADER_LEN + waveform.data.len());
+    bytes.extend(1i32.to_le_bytes());
+    bytes.extend(DAT_FLAG_8BIT.to_le_bytes());
+    bytes.extend((waveform.sample_rate as i32).to_le_bytes());
+    bytes.extend((waveform.samples_per_pixel as i32).to_le_bytes());
+    bytes.extend(waveform.length.to_le_bytes());
+    bytes.extend(waveform.data.iter().map(|&v| v as u8));
+    bytes
+}
+
+/// Decode audiowaveform binary (version 1, 8-bit)
+pub fn decode_dat(bytes: &[u8]) -> Result<WaveformData> {
+    if bytes.len() < DAT_HEADER_LEN {
+        anyhow::bail!("Waveform file is shorter than its header");
+    }
+    let word = |i: usize| u32::from_le_bytes(bytes[i * 4..i * 4 + 4].try_into().unwrap());
+    if word(0) != 1 {
+        anyhow::bail!("Unsupported waveform file version: {}", word(0));
+    }
+    if word(1) & DAT_FLAG_8BIT == 0 {
+        anyhow::bail!("Only 8-bit waveform files are supported");
+    }
+    let length = word(4) as usize;
+    let data = bytes
+        .get(DAT_HEADER_LEN..DAT_HEADER_LEN + length * 2)
+        .context("Waveform file is truncated")?;
+
+    let mut waveform = WaveformData::new(word(3), data.iter().map(|&v| v as i8).collect(), 0);
+    waveform.sample_rate = word(2);
+    Ok(waveform)
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+/// Generate `waveform.dat` from the session's audio (WAV, FLAC or Opus)
+pub fn generate(session_dir: &Path) -> Result<WaveformData> {
+    let mut peaks = PeakAccumulator::new(BASE_SAMPLES_PER_PIXEL);
+    crate::storage::read_session_audio(session_dir, |samples| peaks.push(samples))
+        .with_context(|| format!("Failed to read session audio: {:?}", session_dir))?;
+    let waveform = peaks.finish();
+
+    let path = session_dir.join(WAVEFORM_FILENAME);
+    std::fs::write(&path, encode_dat(&waveform))
+        .with_context(|| format!("Failed to write waveform file: {:?}", path))?;
+    Ok(waveform)
+}
+
+/// Load `waveform.dat`, generating it first if missing or older than the audio
+pub fn load_or_generate(session_dir: &Path) -> Result<WaveformData> {
+    let path = session_dir.join(WAVEFORM_FILENAME);
+    let modified = |p: &Path| std::fs::metadata(p).and_then(|m| m.modified()).ok();
+    let audio_path = crate::storage::session_audio_path(session_dir);
+    match (modified(&path), audio_path.and_then(|p| modified(&p))) {
+        (Some(dat), Some(wav)) if dat >= wav => {
+            let bytes = std::fs::read(&path)
+                .with_context(|| format!("Failed to read waveform file: {:?}", path))?;
+            decode_dat(&bytes)
+        }
+        _ => generate(session_dir),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn ramp_waveform() -> WaveformData {
+        // 8 pixels with peaks -1..-8 / 1..8
+        let samples: Vec<i16> = (1..=8i16)
+            .flat_map(|i| {
+                let mut pixel = vec![0i16; BASE_SAMPLES_PER_PIXEL as usize];
+                pixel[0] = -(i << 8);
+                pixel[1] = i << 8;
+                pixel
+            })
+            .collect();
+        let mut peaks = PeakAccumulator::new(BASE_SAMPLES_PER_PIXEL);
+        peaks.push(&samples);
+        peaks.finish()
+    }
+
+    #[test]
+    fn test_peaks_and_resample() {
+        let waveform = ramp_waveform();
+        assert_eq!(waveform.length, 8);
+        assert_eq!(&waveform.data[..4], &[-1, 1, -2, 2]);
+
+        let coarse = waveform.resample(1000, None, None);
+        assert_eq!(coarse.samples_per_pixel, 768);
+        assert_eq!(coarse.data, vec![-3, 3, -6, 6, -8, 8]);
+
+        // 16ms per pixel: 40ms..80ms covers pixels 2..5
+        let window = waveform.resample(256, Some(40), Some(80));
+        assert_eq!(window.start_ms, 32);
+        assert_eq!(window.data, vec![-3, 3, -4, 4, -5, 5]);
+        assert!(waveform.resample(256, Some(10_000), None).data.is_empty());
+    }
+
+    #[test]
+    fn test_envelope_buckets() {
+        // 4 blocks: silence, full-scale square wave, two blocks at 1/4 scale
+        let mut levels = EnvelopeAccumulator::new();
+        let block = BASE_SAMPLES_PER_PIXEL as usize;
+        levels.push(&vec![0i16; block]);
+        levels.push(&[i16::MIN, i16::MAX].repeat(block / 2));
+        levels.push(&vec![8192i16; block * 2]);
+
+        let envelope = levels.finish(2);
+        assert_eq!(envelope.duration_ms, 64);
+        assert_eq!(envelope.bucket_ms, 32.0);
+        assert_eq!(envelope.peak, vec![1.0, 0.25]);
+        assert!((envelope.rms[0] - std::f32::consts::FRAC_1_SQRT_2).abs() < 0.001);
+        assert_eq!(envelope.rms[1], 0.25);
+
+        // Never more buckets than blocks
+        let mut short = EnvelopeAccumulator::new();
+        short.push(&[100; 10]);
+        assert_eq!(short.finish(DEFAULT_ENVELOPE_BUCKETS).peak.len(), 1);
+        assert!(EnvelopeAccumulator::new().finish(10).peak.is_empty());
+    }
+
+    #[test]
+    fn test_dat_roundtrip() {
+        let waveform = ramp_waveform();
+        let bytes = encode_dat(&waveform);
+        assert_eq!(bytes.len(), DAT_HEADER_LEN + 16);
+        assert_eq!(decode_dat(&bytes).unwrap(), waveform);
+        assert!(decode_dat(&bytes[..DAT_HEADER_LEN + 3]).is_err());
+    }
+
+    #[test]
+    fn test_load_or_generate_from_wav() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut wav = vec![0u8; 44];
+        wav.extend((0..600i16).flat_map(|i| (i * 50).to_le_bytes()));
+        std::fs::write(temp_dir.path().join("audio.wav"), wav).unwrap();
+
+        let waveform = load_or_generate(temp_dir.path()).unwrap();
+        assert!(temp_dir.path().join(WAVEFORM_FILENAME).exists());
+        assert_eq!(waveform.length, 3);
+        assert_eq!(waveform.data[5], ((599 * 50) >> 8) as i8);
+        assert_eq!(load_or_generate(temp_dir.path()).unwrap(), waveform);
+    }
+}
@@ -1,22 +1,39 @@
 // WebSocket Server for Chrome Extension Communication
 // Task 6: WebSocket Server Implementation
 
+use crate::websocket_audio::{AudioStreamSettings, AudioTap, AUDIO_QUEUE_CAPACITY};
+use crate::websocket_control::{
+    ControlCommand, ControlError, RecordingStatus, RemoteControlSettings, MAX_AUTH_FAILURES,
+};
+use crate::websocket_heartbeat::{Heartbeat, HeartbeatAction, PING_INTERVAL};
+use crate::websocket_limits::{encode_frames, WebSocketSettings};
+use crate::websocket_queue::{ClientQueue, PushOutcome, QueueStats};
+use crate::websocket_replay::ReplayBuffer;
+use crate::websocket_retry::{
+    is_transient_io, retry_delay, ClientFailures, Jitter, MAX_SEND_RETRIES,
+};
+use crate::websocket_subscription::{MessageKind, Subscription};
 use anyhow::{anyhow, Result};
 use futures_util::stream::SplitSink;
 use futures_util::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::ops::RangeInclusive;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
 use tauri::{AppHandle, Emitter};
 use tokio::net::TcpListener;
 use tokio::net::TcpStream;
 use tokio::sync::{mpsc, Mutex};
 use tokio::task::JoinHandle;
-use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+use tokio_tungstenite::tungstenite::protocol::CloseFrame;
+use tokio_tungstenite::tungstenite::{Error as WsError, Message};
 use tokio_tungstenite::WebSocketStream;
 use tokio_tungstenite::{
     accept_hdr_async,
     tungstenite::handshake::server::{ErrorResponse, Request, Response},
 };
+use tokio_util::sync::CancellationToken;
 
 /// WebSocket message types for Chrome extension communication
 /// All messages include: messageId, sessionId, timestamp for traceability
@@ -88,7 +105,107 @@ pub enum WebSocketMessage {
         data: Option<serde_json::Value>,
     },
 
-    /// Google Docs sync status (DOCS-REQ-007: Phase 4)
+    /// Keyword watchlist hit in a final segment
+    #[serde(rename = "keyword-alert")]
+    KeywordAlert {
+        #[serde(rename = "messageId")]
+        message_id: String,
+        #[serde(rename = "sessionId")]
+        session_id: String,
+        /// Watchlist name the keyword belongs to
+        watchlist: String,
+        /// Matched keyword
+        keyword: String,
+        /// Segment excerpt around the match
+        context: String,
+        timestamp: u64,
+    },
+
+    /// Streamed summary delta (appended to the text received so far)
+    #[serde(rename = "summary_partial")]
+    SummaryPartial {
+        #[serde(rename = "messageId")]
+        message_id: String,
+        #[serde(rename = "sessionId")]
+        session_id: String,
+        /// Sequence number within the summary (starts at 0)
+        seq: u64,
+        delta: String,
+        timestamp: u64,
+    },
+
+    /// Finished summary (full text)
+    #[serde(rename = "summary_complete")]
+    SummaryComplete {
+        #[serde(rename = "messageId")]
+        message_id: String,
+        #[serde(rename = "sessionId")]
+        session_id: String,
+        text: String,
+        timestamp: u64,
+    },
+
+    /// Rolling summary of the ongoing meeting (replaces the previous one)
+    #[serde(rename = "live_summary")]
+    LiveSummary {
+        #[serde(rename = "messageId")]
+        message_id: String,
+        #[serde(rename = "sessionId")]
+        session_id: String,
+        text: String,
+        /// Session time covered by the summary (ms)
+        #[serde(rename = "coveredUntilMs")]
+        covered_until_ms: u64,
+        timestamp: u64,
+    },
+
+    /// Sent by a reconnecting client: replay the transcriptions it missed
+    /// (see `websocket_replay`)
+    #[serde(rename = "resume")]
+    Resume {
+        #[serde(rename = "sessionId")]
+        session_id: String,
+        /// Last message the client received; None replays everything buffered
+        #[serde(
+            rename = "lastMessageId",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        last_message_id: Option<String>,
+    },
+
+    /// Sent by a client to receive only some message types (see
+    /// `websocket_subscription`); an empty list restores everything
+    #[serde(rename = "subscribe")]
+    Subscribe {
+        #[serde(default)]
+        types: Vec<MessageKind>,
+    },
+
+    /// Sent by a client to start/stop recording or query the status (see
+    /// `websocket_control`)
+    #[serde(rename = "control")]
+    Control {
+        #[serde(rename = "requestId")]
+        request_id: String,
+        token: String,
+        command: ControlCommand,
+    },
+
+    /// Answer to a `control` message, sent to its sender only
+    #[serde(rename = "control_result")]
+    ControlResult {
+        #[serde(rename = "requestId")]
+        request_id: String,
+        ok: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+        /// Recording status after the command
+        #[serde(skip_serializing_if = "Option::is_none")]
+        status: Option<RecordingStatus>,
+        timestamp: u64,
+    },
+
     #[serde(rename = "docsSync")]
     DocsSync {
         event: DocsSyncEventType,
@@ -116,9 +233,239 @@ pub enum DocsSyncEventType {
 
 type WsWriter = SplitSink<WebSocketStream<TcpStream>, Message>;
 
+/// Outgoing messages queued per client before the client counts as slow
+/// (see `websocket_queue` for what is dropped then)
+const CLIENT_QUEUE_CAPACITY: usize = 256;
+
+/// Messages queued for the broadcast task
+const BROADCAST_QUEUE_CAPACITY: usize = 1024;
+
+/// How long a closing writer waits for the close handshake
+const CLOSE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Source of client IDs (unique per process)
+static NEXT_CLIENT_ID: AtomicU64 = AtomicU64::new(1);
+
 /// WebSocket connection handle
+/// The socket writer is owned by a per-connection writer task fed by `queue`
 struct WebSocketConnection {
-    writer: Arc<Mutex<WsWriter>>,
+    /// Client ID (`get_ws_clients`)
+    id: u64,
+    /// Origin header of the handshake (empty when absent)
+    origin: String,
+    /// Connect time (Unix ms)
+    connected_at: u64,
+    /// Text and binary messages written to the socket
+    messages_sent: Arc<AtomicU64>,
+    /// Messages for the writer (bounded, drops partials first)
+    queue: Arc<ClientQueue<Message>>,
+    /// Payload bytes queued but not yet written to the socket
+    queued_bytes: Arc<AtomicU64>,
+    /// Delivery failures (see `websocket_retry`)
+    failures: Arc<std::sync::Mutex<ClientFailures>>,
+    /// Cancelled to disconnect the client (ends both writer and reader)
+    closing: CancellationToken,
+    /// Message types the client subscribed to
+    subscription: std::sync::Mutex<Subscription>,
+}
+
+impl WebSocketConnection {
+    /// Spawn the writer task for a connection
+    fn spawn(mut writer: WsWriter, origin: String) -> Arc<Self> {
+        let messages_sent = Arc::new(AtomicU64::new(0));
+        let writer_messages_sent = Arc::clone(&messages_sent);
+        let queue = Arc::new(ClientQueue::new(CLIENT_QUEUE_CAPACITY));
+        let writer_queue = Arc::clone(&queue);
+        let queued_bytes = Arc::new(AtomicU64::new(0));
+        let failures = Arc::new(std::sync::Mutex::new(ClientFailures::default()));
+        let closing = CancellationToken::new();
+        let writer_queued_bytes = Arc::clone(&queued_bytes);
+        let writer_failures = Arc::clone(&failures);
+        let writer_closing = closing.clone();
+        tokio::spawn(async move {
+            let mut jitter = Jitter::from_time();
+            let mut close_frame = None;
+            'write: loop {
+                let frames = tokio::select! {
+                    frames = writer_queue.pop() => match frames {
+                        Some(frames) => frames,
+                        None => break,
+                    },
+                    _ = writer_closing.cancelled() => {
+                        close_frame = Some(Self::retry_later_frame());
+                        break;
+                    }
+                };
+                writer_queued_bytes.fetch_sub(Self::frames_len(&frames), Ordering::Relaxed);
+                for msg in frames {
+                    let is_data = msg.is_text() || msg.is_binary();
+                    match Self::write_with_retry(&mut writer, msg, &mut jitter).await {
+                        Ok(retries) => {
+                            writer_failures.lock().unwrap().record_success(retries);
+                            if is_data {
+                                writer_messages_sent.fetch_add(1, Ordering::Relaxed);
+                            }
+                        }
+                        Err(e) if Self::is_retryable(&e) => {
+                            let disconnect = writer_failures
+                                .lock()
+                                .unwrap()
+                                .record_failure(MAX_SEND_RETRIES);
+                            eprintln!(
+                                "WebSocket write failed after {} retries: {:?}",
+                                MAX_SEND_RETRIES, e
+                            );
+                            if disconnect {
+                                eprintln!(
+                                    "Disconnecting persistently failing client: {:?}",
+                                    writer_failures.lock().unwrap()
+                                );
+                                close_frame = Some(Self::retry_later_frame());
+                                break 'write;
+                            }
+                        }
+                        Err(e) => {
+                            eprintln!("WebSocket write error: {:?}", e);
+                            break 'write;
+                        }
+                    }
+                }
+            }
+            // Also ends the reader, which removes the connection
+            writer_queue.close();
+            writer_closing.cancel();
+            let close = async {
+                if let Some(frame) = close_frame {
+                    let _ = writer.send(Message::Close(Some(frame))).await;
+                }
+                let _ = writer.close().await;
+            };
+            let _ = tokio::time::timeout(CLOSE_TIMEOUT, close).await;
+        });
+
+        Arc::new(Self {
+            id: NEXT_CLIENT_ID.fetch_add(1, Ordering::Relaxed),
+            origin,
+            connected_at: WebSocketServer::timestamp(),
+            messages_sent,
+            queue,
+            queued_bytes,
+            failures,
+            closing,
+            subscription: std::sync::Mutex::new(Subscription::default()),
+        })
+    }
+
+    /// Payload bytes of a message's frames
+    fn frames_len(frames: &[Message]) -> u64 {
+        frames.iter().map(|msg| msg.len() as u64).sum()
+    }
+
+    /// Write one message, retrying transient failures with jittered backoff
+    ///
+    /// tungstenite queues the frame unless it reports `WriteBufferFull`
+    /// (which hands the message back), so only that case resends; after an
+    /// I/O error the retry just flushes. Returns the retries needed.
+    async fn write_with_retry(
+        writer: &mut WsWriter,
+        msg: Message,
+        jitter: &mut Jitter,
+    ) -> std::result::Result<u32, WsError> {
+        let mut pending = Some(msg);
+        let mut attempt = 0;
+        loop {
+            let result = match pending.take() {
+                Some(msg) => match writer.feed(msg).await {
+                    Ok(()) => writer.flush().await,
+                    Err(e) => Err(e),
+                },
+                None => writer.flush().await,
+            };
+            match result {
+                Ok(()) => return Ok(attempt),
+                Err(e) if attempt >= MAX_SEND_RETRIES || !Self::is_retryable(&e) => return Err(e),
+                Err(WsError::WriteBufferFull(msg)) => pending = Some(msg),
+                Err(_) => {} // Frame is queued; retry the flush
+            }
+            attempt += 1;
+            tokio::time::sleep(retry_delay(attempt, jitter.next_unit())).await;
+        }
+    }
+
+    fn is_retryable(error: &WsError) -> bool {
+        match error {
+            WsError::WriteBufferFull(_) => true,
+            WsError::Io(e) => is_transient_io(e.kind()),
+            _ => false,
+        }
+    }
+
+    /// Close frame for a disconnected failing client (1013: try again later)
+    fn retry_later_frame() -> CloseFrame<'static> {
+        CloseFrame {
+            code: CloseCode::Again,
+            reason: "client not keeping up".into(),
+        }
+    }
+
+    /// A broadcast was dropped for this client; returns true once the client
+    /// has failed persistently and is being disconnected
+    fn record_dropped(&self) -> bool {
+        let disconnect = self.failures.lock().unwrap().record_failure(0);
+        if disconnect && !self.closing.is_cancelled() {
+            eprintln!(
+                "Disconnecting persistently failing client: {:?}",
+                self.failures.lock().unwrap()
+            );
+            self.closing.cancel();
+        }
+        disconnect
+    }
+}
+
+impl Drop for WebSocketConnection {
+    fn drop(&mut self) {
+        // The writer drains what is queued, then exits
+        self.queue.close();
+    }
+}
+
+/// Delivery counters of one connected client
+#[derive(Debug, Clone, Serialize)]
+pub struct ClientDeliveryStats {
+    #[serde(flatten)]
+    pub queue: QueueStats,
+    /// Payload bytes queued but not yet written
+    pub queued_bytes: u64,
+}
+
+/// A connected client, for debugging delivery (`get_ws_clients`)
+#[derive(Debug, Clone, Serialize)]
+pub struct WsClientInfo {
+    pub id: u64,
+    /// Origin header of the handshake (empty when absent)
+    pub origin: String,
+    /// Connect time (Unix ms)
+    pub connected_at: u64,
+    /// Subscribed message types (empty: every type)
+    pub subscriptions: Vec<MessageKind>,
+    /// Text and binary messages written to the socket
+    pub messages_sent: u64,
+    /// Queue depth and drop counters
+    #[serde(flatten)]
+    pub queue: QueueStats,
+    /// Payload bytes queued but not yet written
+    pub queued_bytes: u64,
+}
+
+/// Delivery counters of the WebSocket server
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct WebSocketDeliveryStats {
+    pub clients: Vec<ClientDeliveryStats>,
+    /// Partials dropped before fan-out because the broadcast queue was full
+    pub broadcast_dropped_partials: u64,
+    /// PCM chunks dropped before fan-out because the audio queue was full
+    pub audio_dropped_chunks: u64,
 }
 
 /// WebSocket server for Chrome extension communication
@@ -128,8 +475,25 @@ pub struct WebSocketServer {
     server_handle: Option<JoinHandle<()>>,
     connections: Arc<Mutex<Vec<Arc<WebSocketConnection>>>>,
     session_id: String,
-    message_id_counter: Arc<std::sync::atomic::AtomicU64>,
     app_handle: Option<AppHandle>,
+    /// Queue of the broadcast task (None until the server is started)
+    broadcast_tx: Option<mpsc::Sender<WebSocketMessage>>,
+    broadcast_handle: Option<JoinHandle<()>>,
+    /// Partials dropped because the broadcast queue was full
+    broadcast_dropped_partials: AtomicU64,
+    /// Message size limits (see `websocket_limits`), read by the broadcast task
+    settings: Arc<RwLock<WebSocketSettings>>,
+    /// Recent final transcriptions for resuming clients (`websocket_replay`)
+    replay: Arc<std::sync::Mutex<ReplayBuffer>>,
+    /// Remote control settings (`websocket_control`), read per control message
+    control: Arc<RwLock<RemoteControlSettings>>,
+    /// Queue of the PCM fan-out task (`websocket_audio`)
+    audio_tx: Option<mpsc::Sender<bytes::Bytes>>,
+    audio_handle: Option<JoinHandle<()>>,
+    /// PCM stream enabled in the settings, read by every `AudioTap`
+    audio_enabled: Arc<AtomicBool>,
+    /// PCM chunks dropped because the audio queue was full
+    audio_dropped: Arc<AtomicU64>,
 }
 
 impl WebSocketServer {
@@ -140,11 +504,49 @@ impl WebSocketServer {
             server_handle: None,
             connections: Arc::new(Mutex::new(Vec::new())),
             session_id: uuid::Uuid::new_v4().to_string(),
-            message_id_counter: Arc::new(std::sync::atomic::AtomicU64::new(0)),
             app_handle: None,
+            broadcast_tx: None,
+            broadcast_handle: None,
+            broadcast_dropped_partials: AtomicU64::new(0),
+            settings: Arc::new(RwLock::new(WebSocketSettings::default())),
+            replay: Arc::new(std::sync::Mutex::new(ReplayBuffer::new())),
+            control: Arc::new(RwLock::new(RemoteControlSettings::default())),
+            audio_tx: None,
+            audio_handle: None,
+            audio_enabled: Arc::new(AtomicBool::new(false)),
+            audio_dropped: Arc::new(AtomicU64::new(0)),
         }
     }
 
+    /// Apply message size limits (takes effect for the next broadcast)
+    pub fn set_settings(&self, settings: WebSocketSettings) {
+        *self.settings.write().unwrap() = settings;
+    }
+
+    /// Apply remote control settings (takes effect for the next control message)
+    pub fn set_control_settings(&self, settings: RemoteControlSettings) {
+        *self.control.write().unwrap() = settings;
+    }
+
+    /// Apply PCM stream settings (takes effect for the next captured chunk)
+    pub fn set_audio_settings(&self, settings: &AudioStreamSettings) {
+        self.audio_enabled
+            .store(settings.enabled, Ordering::Relaxed);
+    }
+
+    /// Tap for the recording callback to stream PCM to `audio` subscribers
+    ///
+    /// None before the server is started.
+    pub fn audio_tap(&self) -> Option<AudioTap> {
+        self.audio_tx.as_ref().map(|tx| {
+            AudioTap::new(
+                tx.clone(),
+                Arc::clone(&self.audio_enabled),
+                Arc::clone(&self.audio_dropped),
+            )
+        })
+    }
+
     pub fn new_with_app_handle(app_handle: AppHandle) -> Self {
         let mut server = Self::new();
         server.app_handle = Some(app_handle);
@@ -162,8 +564,15 @@ impl WebSocketServer {
     /// Start the WebSocket server
     /// Tries ports 9001-9100 until one succeeds
     pub async fn start(&mut self) -> Result<u16> {
-        // Try ports 9001-9100
-        for port in 9001..=9100 {
+        self.start_in(crate::websocket_port::EXTENSION_PORT_RANGE)
+            .await
+    }
+
+    /// Start the WebSocket server on the first free port of `ports`, in order
+    /// (see `websocket_port`)
+    pub async fn start_in(&mut self, ports: RangeInclusive<u16>) -> Result<u16> {
+        let (first, last) = (*ports.start(), *ports.end());
+        for port in ports {
             match self.try_start_on_port(port).await {
                 Ok(()) => {
                     self.port = Some(port);
@@ -173,7 +582,12 @@ impl WebSocketServer {
             }
         }
 
-        Err(anyhow!("No available ports in range 9001-9100"))
+        Err(anyhow!("No available ports in range {}-{}", first, last))
+    }
+
+    /// Port the server listens on (None until started)
+    pub fn port(&self) -> Option<u16> {
+        self.port
     }
 
     /// Try to start server on a specific port
@@ -184,8 +598,10 @@ impl WebSocketServer {
         let (shutdown_tx, mut shutdown_rx) = mpsc::channel::<()>(1);
         let connections = Arc::clone(&self.connections);
         let session_id = self.session_id.clone();
-        let message_id_counter = Arc::clone(&self.message_id_counter);
         let app_handle = self.app_handle.clone();
+        let replay = Arc::clone(&self.replay);
+        let settings = Arc::clone(&self.settings);
+        let control = Arc::clone(&self.control);
 
         // Spawn server task
         let handle = tokio::spawn(async move {
@@ -195,10 +611,12 @@ impl WebSocketServer {
                         if let Ok((stream, _)) = accept_result {
                             let conn_list = Arc::clone(&connections);
                             let sess_id = session_id.clone();
-                            let msg_counter = Arc::clone(&message_id_counter);
                             let app_clone = app_handle.clone();
+                            let replay = Arc::clone(&replay);
+                            let settings = Arc::clone(&settings);
+                            let control = Arc::clone(&control);
                             tokio::spawn(async move {
-                                if let Err(e) = Self::handle_connection(stream, conn_list, sess_id, msg_counter, app_clone).await {
+                                if let Err(e) = Self::handle_connection(stream, conn_list, sess_id, app_clone, replay, settings, control).await {
                                     eprintln!("WebSocket connection error: {:?}", e);
                                 }
                             });
@@ -214,6 +632,23 @@ impl WebSocketServer {
         self.shutdown_tx = Some(shutdown_tx);
         self.server_handle = Some(handle);
 
+        let (broadcast_tx, broadcast_rx) = mpsc::channel(BROADCAST_QUEUE_CAPACITY);
+        self.broadcast_handle = Some(tokio::spawn(Self::run_broadcast_task(
+            broadcast_rx,
+            Arc::clone(&self.connections),
+            self.session_id.clone(),
+            Arc::clone(&self.settings),
+            Arc::clone(&self.replay),
+        )));
+        self.broadcast_tx = Some(broadcast_tx);
+
+        let (audio_tx, audio_rx) = mpsc::channel(AUDIO_QUEUE_CAPACITY);
+        self.audio_handle = Some(tokio::spawn(Self::run_audio_task(
+            audio_rx,
+            Arc::clone(&self.connections),
+        )));
+        self.audio_tx = Some(audio_tx);
+
         Ok(())
     }
 
@@ -275,10 +710,13 @@ impl WebSocketServer {
         stream: TcpStream,
         connections: Arc<Mutex<Vec<Arc<WebSocketConnection>>>>,
         session_id: String,
-        message_id_counter: Arc<std::sync::atomic::AtomicU64>,
         app_handle: Option<AppHandle>,
+        replay: Arc<std::sync::Mutex<ReplayBuffer>>,
+        settings: Arc<RwLock<WebSocketSettings>>,
+        control: Arc<RwLock<RemoteControlSettings>>,
     ) -> Result<()> {
         // Accept with Origin header validation
+        let mut client_origin = String::new();
         let ws_stream = accept_hdr_async(stream, |req: &Request, response: Response| {
             // Get Origin header
             let origin = req
@@ -286,6 +724,7 @@ impl WebSocketServer {
                 .get("Origin")
                 .and_then(|v| v.to_str().ok())
                 .unwrap_or("");
+            client_origin = origin.to_string();
 
             // Verify origin
             if !Self::verify_origin(origin) {
@@ -299,9 +738,7 @@ impl WebSocketServer {
         .await?;
         let (writer, mut reader) = ws_stream.split();
 
-        let conn = Arc::new(WebSocketConnection {
-            writer: Arc::new(Mutex::new(writer)),
-        });
+        let conn = WebSocketConnection::spawn(writer, client_origin);
 
         // Add to connection list
         {
@@ -310,10 +747,7 @@ impl WebSocketServer {
         }
 
         // Send connected message with all required fields
-        let message_id = {
-            let id = message_id_counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-            format!("ws-{}", id)
-        };
+        let message_id = crate::message_id::next_id(crate::message_id::WEBSOCKET, &session_id);
 
         let connected_msg = WebSocketMessage::Connected {
             message_id,
@@ -321,12 +755,44 @@ impl WebSocketServer {
             timestamp: Self::timestamp(),
         };
         let json = serde_json::to_string(&connected_msg)?;
-        let mut writer_guard = conn.writer.lock().await;
-        writer_guard.send(Message::Text(json)).await?;
-        drop(writer_guard);
+        conn.queued_bytes
+            .fetch_add(json.len() as u64, Ordering::Relaxed);
+        if !conn.queue.push_wait(vec![Message::Text(json)]).await {
+            return Err(anyhow!("WebSocket writer closed before connected message"));
+        }
 
         // Read messages (keep-alive + docsSync events from Chrome extension)
-        while let Some(msg) = reader.next().await {
+        // until the client leaves, stops answering pings, or the server
+        // disconnects it
+        let mut heartbeat = Heartbeat::new(std::time::Instant::now());
+        let mut auth_failures = 0;
+        let mut ping_timer =
+            tokio::time::interval_at(tokio::time::Instant::now() + PING_INTERVAL, PING_INTERVAL);
+        ping_timer.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            let msg = tokio::select! {
+                msg = reader.next() => match msg {
+                    Some(msg) => msg,
+                    None => break,
+                },
+                _ = ping_timer.tick() => {
+                    let now = std::time::Instant::now();
+                    match heartbeat.on_tick(now) {
+                        HeartbeatAction::Ping => Self::send_ping(&conn),
+                        HeartbeatAction::Disconnect => {
+                            println!(
+                                r#"{{"event":"websocket_client_timeout","silent_ms":{}}}"#,
+                                heartbeat.silent_for(now).as_millis()
+                            );
+                            conn.closing.cancel();
+                            break;
+                        }
+                    }
+                    continue;
+                }
+                _ = conn.closing.cancelled() => break,
+            };
+            heartbeat.record_activity(std::time::Instant::now());
             match msg {
                 Ok(Message::Text(text)) => {
                     // Try to parse as WebSocketMessage
@@ -343,6 +809,16 @@ impl WebSocketServer {
                             );
 
                             if let Some(app) = app_handle.as_ref() {
+                                // Syncing to another document restarts its character budget
+                                if let (
+                                    DocsSyncEventType::DocsSyncStarted
+                                    | DocsSyncEventType::DocsSyncSuccess,
+                                    Some(document_id),
+                                ) = (&event, document_id.as_deref())
+                                {
+                                    crate::commands::docs_document_synced(app, document_id);
+                                }
+
                                 let payload = serde_json::json!({
                                     "event": event,
                                     "document_id": document_id,
@@ -356,6 +832,69 @@ impl WebSocketServer {
                                 }
                             }
                         }
+                        Ok(WebSocketMessage::Resume {
+                            session_id,
+                            last_message_id,
+                        }) => {
+                            let replayed = Self::send_replay(
+                                &conn,
+                                &replay,
+                                &settings,
+                                &session_id,
+                                last_message_id.as_deref(),
+                            )
+                            .await;
+                            println!(
+                                "{}",
+                                serde_json::json!({
+                                    "event": "websocket_resume",
+                                    "session_id": session_id,
+                                    "replayed": replayed,
+                                })
+                            );
+                        }
+                        Ok(WebSocketMessage::Subscribe { types }) => {
+                            println!(
+                                r#"{{"event":"websocket_subscribe","types":{}}}"#,
+                                serde_json::to_string(&types).unwrap_or_default()
+                            );
+                            *conn.subscription.lock().unwrap() = Subscription::new(&types);
+                        }
+                        Ok(WebSocketMessage::Control {
+                            request_id,
+                            token,
+                            command,
+                        }) => {
+                            let authorized = control.read().unwrap().authorize(&token);
+                            let result = match (authorized, app_handle.as_ref()) {
+                                (Err(e), _) => Err(e),
+                                (Ok(()), Some(app)) => {
+                                    crate::commands::run_remote_control(app, command)
+                                        .await
+                                        .map_err(ControlError::Failed)
+                                }
+                                (Ok(()), None) => Err(ControlError::Failed(
+                                    "Remote control is unavailable".to_string(),
+                                )),
+                            };
+                            if result == Err(ControlError::Unauthorized) {
+                                auth_failures += 1;
+                            }
+                            println!(
+                                "{}",
+                                serde_json::json!({
+                                    "event": "websocket_control",
+                                    "request_id": request_id,
+                                    "error": result.as_ref().err().map(|e| e.to_string()),
+                                })
+                            );
+                            Self::send_control_result(&conn, request_id, result).await;
+                            if auth_failures >= MAX_AUTH_FAILURES {
+                                eprintln!("[WebSocket] Disconnecting client after repeated invalid control tokens");
+                                conn.closing.cancel();
+                                break;
+                            }
+                        }
                         Ok(_) => {
                             // Other message types - log for debugging
                             println!("[WebSocket] Received message: {}", text);
@@ -367,7 +906,7 @@ impl WebSocketServer {
                 }
                 Ok(Message::Close(_)) => break,
                 Err(_) => break,
-                _ => {} // Ignore ping/pong/binary
+                _ => {} // Ping/pong only refresh the heartbeat; binary is ignored
             }
         }
 
@@ -380,35 +919,315 @@ impl WebSocketServer {
         Ok(())
     }
 
+    /// Queue a heartbeat ping (skipped when the client's queue is full; the
+    /// timeout applies either way)
+    fn send_ping(conn: &WebSocketConnection) {
+        let ping = Message::Ping(Vec::new());
+        let len = ping.len() as u64;
+        conn.queued_bytes.fetch_add(len, Ordering::Relaxed);
+        if !conn.queue.try_push(vec![ping]) {
+            conn.queued_bytes.fetch_sub(len, Ordering::Relaxed);
+        }
+    }
+
+    /// Replay the buffered transcriptions a resuming client missed, followed
+    /// by a `replay_gap` notification if some were already evicted
+    ///
+    /// Only message types the client subscribed to are sent. Returns the
+    /// number of buffered messages found.
+    async fn send_replay(
+        conn: &WebSocketConnection,
+        replay: &std::sync::Mutex<ReplayBuffer>,
+        settings: &RwLock<WebSocketSettings>,
+        session_id: &str,
+        last_message_id: Option<&str>,
+    ) -> usize {
+        let replay = replay.lock().unwrap().since(session_id, last_message_id);
+        let limits = settings.read().unwrap().clone();
+        let subscription = conn.subscription.lock().unwrap().clone();
+        let replayed = replay.messages.len();
+        let mut messages = replay.messages;
+        if replay.gap {
+            messages.push(WebSocketMessage::Notification {
+                message_id: crate::message_id::next_id(crate::message_id::WEBSOCKET, session_id),
+                session_id: session_id.to_string(),
+                notification_type: "replay_gap".to_string(),
+                message: "Some transcriptions are no longer buffered and were not replayed"
+                    .to_string(),
+                timestamp: Self::timestamp(),
+                data: None,
+            });
+        }
+
+        // The gap notice is filtered too (a finals-only client gets none)
+        for message in messages.iter().filter(|m| subscription.accepts(m)) {
+            let frames: Vec<Message> = match encode_frames(message, &limits) {
+                Ok(encoded) => encoded.frames.into_iter().map(Message::Text).collect(),
+                Err(e) => {
+                    eprintln!("Replay serialize error: {:?}", e);
+                    continue;
+                }
+            };
+            let len = WebSocketConnection::frames_len(&frames);
+            conn.queued_bytes.fetch_add(len, Ordering::Relaxed);
+            // Waits for queue room: a resuming client is expected to catch up
+            if !conn.queue.push_wait(frames).await {
+                conn.queued_bytes.fetch_sub(len, Ordering::Relaxed);
+                return replayed;
+            }
+        }
+        replayed
+    }
+
+    /// Answer a control message (to its sender only)
+    async fn send_control_result(
+        conn: &WebSocketConnection,
+        request_id: String,
+        result: Result<RecordingStatus, ControlError>,
+    ) {
+        let (error, status) = match result {
+            Ok(status) => (None, Some(status)),
+            Err(e) => (Some(e.to_string()), None),
+        };
+        let message = WebSocketMessage::ControlResult {
+            request_id,
+            ok: error.is_none(),
+            error,
+            status,
+            timestamp: Self::timestamp(),
+        };
+        let json = match serde_json::to_string(&message) {
+            Ok(json) => json,
+            Err(e) => {
+                eprintln!("Control result serialize error: {:?}", e);
+                return;
+            }
+        };
+        let len = json.len() as u64;
+        conn.queued_bytes.fetch_add(len, Ordering::Relaxed);
+        if !conn.queue.push_wait(vec![Message::Text(json)]).await {
+            conn.queued_bytes.fetch_sub(len, Ordering::Relaxed);
+        }
+    }
+
     /// Broadcast a message to all connected clients
-    /// Includes performance metrics logging (AC-NFR-PERF.4)
+    ///
+    /// Only enqueues the message: serialization and fan-out run on the
+    /// broadcast task, so callers never wait on client sockets. A partial
+    /// transcription is dropped rather than waited for when the broadcast
+    /// queue is full, so callers holding the server lock don't stall.
+    /// No-op before the server is started.
     pub async fn broadcast(&self, message: WebSocketMessage) -> Result<()> {
-        let start = std::time::Instant::now();
+        let Some(tx) = &self.broadcast_tx else {
+            return Ok(());
+        };
+        let message = if MessageKind::of(&message) == Some(MessageKind::Partial) {
+            match tx.try_send(message) {
+                Ok(()) => return Ok(()),
+                Err(mpsc::error::TrySendError::Full(_)) => {
+                    self.broadcast_dropped_partials
+                        .fetch_add(1, Ordering::Relaxed);
+                    return Ok(());
+                }
+                Err(mpsc::error::TrySendError::Closed(message)) => message,
+            }
+        } else {
+            message
+        };
+        tx.send(message)
+            .await
+            .map_err(|_| anyhow!("WebSocket broadcast task stopped"))
+    }
+
+    /// Broadcast task: serialize each message once (chunked per
+    /// `websocket_limits`), then hand the frames to every client's writer
+    /// without blocking. A slow client drops partials first, then other
+    /// messages (`websocket_queue`), instead of delaying the others, and is
+    /// disconnected once it keeps dropping essential messages
+    /// (`websocket_retry`).
+    /// Includes performance metrics logging (AC-NFR-PERF.4)
+    async fn run_broadcast_task(
+        mut rx: mpsc::Receiver<WebSocketMessage>,
+        connections: Arc<Mutex<Vec<Arc<WebSocketConnection>>>>,
+        session_id: String,
+        settings: Arc<RwLock<WebSocketSettings>>,
+        replay: Arc<std::sync::Mutex<ReplayBuffer>>,
+    ) {
+        while let Some(message) = rx.recv().await {
+            let start = std::time::Instant::now();
+            replay.lock().unwrap().record(&message);
+
+            let limits = settings.read().unwrap().clone();
+            let frames: Vec<Message> = match encode_frames(&message, &limits) {
+                Ok(encoded) => {
+                    if encoded.truncated_bytes > 0 {
+                        eprintln!(
+                            "Broadcast segment truncated by {} bytes (max_segment_bytes={})",
+                            encoded.truncated_bytes, limits.max_segment_bytes
+                        );
+                    }
+                    encoded.frames.into_iter().map(Message::Text).collect()
+                }
+                Err(e) => {
+                    eprintln!("Broadcast serialize error: {:?}", e);
+                    continue;
+                }
+            };
+
+            // Snapshot the client list so sends happen outside the lock
+            let conns: Vec<Arc<WebSocketConnection>> = connections.lock().await.clone();
+            let conn_count = conns.len();
+            let mut closed = Vec::new();
+
+            let partial = MessageKind::of(&message) == Some(MessageKind::Partial);
+            let frames_len = WebSocketConnection::frames_len(&frames);
+            for conn in conns {
+                if !conn.subscription.lock().unwrap().accepts(&message) {
+                    continue;
+                }
+                // Counted before the push so the writer never decrements first
+                conn.queued_bytes.fetch_add(frames_len, Ordering::Relaxed);
+                match conn.queue.push(frames.clone(), partial) {
+                    PushOutcome::Queued => {}
+                    PushOutcome::QueuedEvicting(evicted) => {
+                        conn.queued_bytes.fetch_sub(
+                            WebSocketConnection::frames_len(&evicted),
+                            Ordering::Relaxed,
+                        );
+                        Self::log_dropped(&conn.queue.stats());
+                    }
+                    PushOutcome::Dropped => {
+                        conn.queued_bytes.fetch_sub(frames_len, Ordering::Relaxed);
+                        Self::log_dropped(&conn.queue.stats());
+                        // Only essential messages count as delivery failures
+                        if !partial && conn.record_dropped() {
+                            closed.push(conn);
+                        }
+                    }
+                    PushOutcome::Closed => {
+                        conn.queued_bytes.fetch_sub(frames_len, Ordering::Relaxed);
+                        closed.push(conn);
+                    }
+                }
+            }
+
+            if !closed.is_empty() {
+                connections
+                    .lock()
+                    .await
+                    .retain(|c| !closed.iter().any(|gone| Arc::ptr_eq(c, gone)));
+            }
 
-        let json = serde_json::to_string(&message)?;
-        let msg = Message::Text(json);
+            // Log performance metrics (AC-NFR-PERF.4)
+            let elapsed_ms = start.elapsed().as_millis() as u64;
+            println!(
+                r#"{{"metric":"websocket_broadcast_ms","value":{},"timestamp":{},"session_id":"{}","connections":{}}}"#,
+                elapsed_ms,
+                Self::timestamp(),
+                session_id,
+                conn_count
+            );
+        }
+    }
 
-        let conns = self.connections.lock().await;
-        let conn_count = conns.len();
+    /// PCM fan-out task: each chunk becomes one binary frame for the
+    /// clients subscribed to `audio`, queued as droppable so a slow client
+    /// loses audio before anything else. Runs until every `AudioTap` and
+    /// the server's sender are gone.
+    async fn run_audio_task(
+        mut rx: mpsc::Receiver<bytes::Bytes>,
+        connections: Arc<Mutex<Vec<Arc<WebSocketConnection>>>>,
+    ) {
+        while let Some(chunk) = rx.recv().await {
+            let conns: Vec<Arc<WebSocketConnection>> = connections
+                .lock()
+                .await
+                .iter()
+                .filter(|conn| conn.subscription.lock().unwrap().accepts_audio())
+                .cloned()
+                .collect();
+            if conns.is_empty() {
+                continue;
+            }
 
-        for conn in conns.iter() {
-            let mut writer = conn.writer.lock().await;
-            if let Err(e) = writer.send(msg.clone()).await {
-                eprintln!("Broadcast send error: {:?}", e);
+            let frames = vec![Message::Binary(chunk.to_vec())];
+            let frames_len = WebSocketConnection::frames_len(&frames);
+            for conn in conns {
+                conn.queued_bytes.fetch_add(frames_len, Ordering::Relaxed);
+                match conn.queue.push(frames.clone(), true) {
+                    PushOutcome::Queued => {}
+                    PushOutcome::QueuedEvicting(evicted) => {
+                        conn.queued_bytes.fetch_sub(
+                            WebSocketConnection::frames_len(&evicted),
+                            Ordering::Relaxed,
+                        );
+                    }
+                    // Counted in the client's queue stats; closed clients
+                    // are removed by the broadcast task
+                    PushOutcome::Dropped | PushOutcome::Closed => {
+                        conn.queued_bytes.fetch_sub(frames_len, Ordering::Relaxed);
+                    }
+                }
             }
         }
+    }
+
+    /// Log a slow client's drops (the first, then every 100th)
+    fn log_dropped(stats: &QueueStats) {
+        let dropped = stats.dropped_partials + stats.dropped_essential;
+        if dropped == 1 || dropped % 100 == 0 {
+            eprintln!(
+                "Broadcast dropped for slow client (partials: {}, essential: {})",
+                stats.dropped_partials, stats.dropped_essential
+            );
+        }
+    }
 
-        // Log performance metrics (AC-NFR-PERF.4)
-        let elapsed_ms = start.elapsed().as_millis() as u64;
-        println!(
-            r#"{{"metric":"websocket_broadcast_ms","value":{},"timestamp":{},"session_id":"{}","connections":{}}}"#,
-            elapsed_ms,
-            Self::timestamp(),
-            self.session_id,
-            conn_count
-        );
+    /// Queue lengths and drop counters of every client
+    pub async fn delivery_stats(&self) -> WebSocketDeliveryStats {
+        let clients = self
+            .connections
+            .lock()
+            .await
+            .iter()
+            .map(|conn| ClientDeliveryStats {
+                queue: conn.queue.stats(),
+                queued_bytes: conn.queued_bytes.load(Ordering::Relaxed),
+            })
+            .collect();
+        WebSocketDeliveryStats {
+            clients,
+            broadcast_dropped_partials: self.broadcast_dropped_partials.load(Ordering::Relaxed),
+            audio_dropped_chunks: self.audio_dropped.load(Ordering::Relaxed),
+        }
+    }
 
-        Ok(())
+    /// Every connected client with its subscription and delivery counters
+    pub async fn clients(&self) -> Vec<WsClientInfo> {
+        self.connections
+            .lock()
+            .await
+            .iter()
+            .map(|conn| WsClientInfo {
+                id: conn.id,
+                origin: conn.origin.clone(),
+                connected_at: conn.connected_at,
+                subscriptions: conn.subscription.lock().unwrap().kinds(),
+                messages_sent: conn.messages_sent.load(Ordering::Relaxed),
+                queue: conn.queue.stats(),
+                queued_bytes: conn.queued_bytes.load(Ordering::Relaxed),
+            })
+            .collect()
+    }
+
+    /// Payload bytes queued for all clients but not yet written
+    pub async fn queued_bytes(&self) -> u64 {
+        self.connections
+            .lock()
+            .await
+            .iter()
+            .map(|conn| conn.queued_bytes.load(Ordering::Relaxed))
+            .sum()
     }
 
     /// Stop the WebSocket server
@@ -423,6 +1242,18 @@ impl WebSocketServer {
             let _ = handle.await;
         }
 
+        // Close the broadcast queue; the task exits after draining it
+        self.broadcast_tx = None;
+        if let Some(handle) = self.broadcast_handle.take() {
+            let _ = handle.await;
+        }
+
+        // Taps held by a recording keep the audio queue open, so abort
+        self.audio_tx = None;
+        if let Some(handle) = self.audio_handle.take() {
+            handle.abort();
+        }
+
         // Clear connections
         {
             let mut conns = self.connections.lock().await;
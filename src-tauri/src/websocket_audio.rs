@@ -0,0 +1,118 @@
+//! Binary PCM Monitoring Stream over WebSocket
+//!
+//! Lets a WebSocket client tap the live recording feed — for a remote VU
+//! meter or an external recorder. A client opts in by subscribing to the
+//! `audio` type (`websocket_subscription`), which is never part of the
+//! default every-type subscription:
+//!
+//! ```json
+//! {"type": "subscribe", "types": ["audio"]}
+//! ```
+//!
+//! While recording, it then receives binary frames holding the mixed
+//! recording input exactly as it is sent for transcription: 16 kHz mono,
+//! signed 16-bit little-endian samples, one frame per capture chunk. There
+//! is no header; text messages keep flowing for the other subscribed types.
+//!
+//! The capture callback hands chunks over through an `AudioTap` without
+//! blocking. Chunks are dropped when the fan-out queue is full, and a slow
+//! client drops audio frames before any other message (`websocket_queue`).
+//!
+//! Disabled by default. Settings are machine-wide and apply immediately,
+//! also to a running recording. Persisted to `settings/websocket_audio.json`
+//! in app data directory.
+
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+/// Sample rate of the streamed PCM
+pub const SAMPLE_RATE: u32 = 16_000;
+
+/// Chunks waiting for the fan-out task; more are dropped
+pub const AUDIO_QUEUE_CAPACITY: usize = 64;
+
+/// PCM stream configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AudioStreamSettings {
+    /// Stream recorded audio to clients subscribed to `audio`
+    #[serde(default)]
+    pub enabled: bool,
+    /// Settings version for future migrations
+    #[serde(default = "default_version")]
+    pub version: u32,
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+impl Default for AudioStreamSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            version: 1,
+        }
+    }
+}
+
+/// Hands captured chunks to the WebSocket server from the audio callback
+#[derive(Clone)]
+pub struct AudioTap {
+    tx: mpsc::Sender<Bytes>,
+    enabled: Arc<AtomicBool>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl AudioTap {
+    pub fn new(tx: mpsc::Sender<Bytes>, enabled: Arc<AtomicBool>, dropped: Arc<AtomicU64>) -> Self {
+        Self {
+            tx,
+            enabled,
+            dropped,
+        }
+    }
+
+    /// Offer a chunk without blocking (no-op while the stream is disabled;
+    /// counted as dropped when the queue is full)
+    pub fn send(&self, chunk: &Bytes) {
+        if !self.enabled.load(Ordering::Relaxed) {
+            return;
+        }
+        // Cloning Bytes only bumps a reference count
+        if let Err(mpsc::error::TrySendError::Full(_)) = self.tx.try_send(chunk.clone()) {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+pub const SETTINGS_STEM: &str = "websocket_audio";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tap_forwards_only_when_enabled() {
+        let (tx, mut rx) = mpsc::channel(1);
+        let enabled = Arc::new(AtomicBool::new(false));
+        let dropped = Arc::new(AtomicU64::new(0));
+        let tap = AudioTap::new(tx, Arc::clone(&enabled), Arc::clone(&dropped));
+        let chunk = Bytes::from_static(&[1, 0, 2, 0]);
+
+        tap.send(&chunk);
+        assert!(rx.try_recv().is_err());
+
+        enabled.store(true, Ordering::Relaxed);
+        tap.send(&chunk);
+        tap.send(&chunk); // queue full
+        assert_eq!(rx.try_recv().unwrap(), chunk);
+        assert_eq!(dropped.load(Ordering::Relaxed), 1);
+    }
+}
@@ -0,0 +1,178 @@
+//! Remote Control over WebSocket
+//!
+//! Lets a WebSocket client (the Chrome extension) start and stop recording
+//! without the desktop window focused. A client sends
+//!
+//! ```json
+//! {"type": "control", "requestId": "1", "token": "…",
+//!  "command": {"action": "start_recording", "deviceId": "…"}}
+//! ```
+//!
+//! with `action` one of `start_recording`, `stop_recording` or `get_status`,
+//! and receives a `control_result` with the same `requestId` (to that client
+//! only) carrying the recording status or the error. Commands run the same
+//! code as the desktop `start_recording` / `stop_recording` commands, and the
+//! desktop window is told through the `remote_control` event.
+//!
+//! Every control message needs the token from the settings, so a web page
+//! that can open the socket still can't control recording. A connection
+//! sending `MAX_AUTH_FAILURES` bad tokens is disconnected.
+//!
+//! Disabled by default. Settings are machine-wide; persisted to
+//! `settings/websocket_control.json` in app data directory.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// Shortest accepted token
+const MIN_TOKEN_LEN: usize = 16;
+
+/// Bad tokens a connection may send before it is disconnected
+pub const MAX_AUTH_FAILURES: u32 = 5;
+
+/// Remote control configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemoteControlSettings {
+    /// Accept control messages
+    #[serde(default)]
+    pub enabled: bool,
+    /// Token required in every control message (generated when empty)
+    #[serde(default)]
+    pub token: String,
+    /// Settings version for future migrations
+    #[serde(default = "default_version")]
+    pub version: u32,
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+impl Default for RemoteControlSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            token: String::new(),
+            version: 1,
+        }
+    }
+}
+
+impl RemoteControlSettings {
+    pub fn validate(&self) -> Result<()> {
+        if self.enabled && self.token.len() < MIN_TOKEN_LEN {
+            anyhow::bail!("Token must be at least {} characters", MIN_TOKEN_LEN);
+        }
+        Ok(())
+    }
+
+    /// Check whether a control message carrying `token` may run
+    pub fn authorize(&self, token: &str) -> Result<(), ControlError> {
+        if !self.enabled {
+            return Err(ControlError::Disabled);
+        }
+        if self.token.is_empty()
+            || !crate::http_api::constant_time_eq(token.as_bytes(), self.token.as_bytes())
+        {
+            return Err(ControlError::Unauthorized);
+        }
+        Ok(())
+    }
+}
+
+/// Recording command sent by a client
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum ControlCommand {
+    StartRecording {
+        #[serde(rename = "deviceId")]
+        device_id: String,
+    },
+    StopRecording,
+    GetStatus,
+}
+
+/// Recording status reported after a command
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordingStatus {
+    pub recording: bool,
+    #[serde(rename = "sessionId", skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    #[serde(rename = "deviceId", skip_serializing_if = "Option::is_none")]
+    pub device_id: Option<String>,
+    /// Google Docs character budget of the session
+    #[serde(rename = "docsBudget", skip_serializing_if = "Option::is_none")]
+    pub docs_budget: Option<crate::docs_budget::DocsBudgetStatus>,
+}
+
+/// Why a control message was refused or failed
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ControlError {
+    #[error("Remote control is disabled")]
+    Disabled,
+    #[error("Invalid control token")]
+    Unauthorized,
+    #[error("{0}")]
+    Failed(String),
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+pub const SETTINGS_STEM: &str = "websocket_control";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_authorize_and_settings() {
+        let mut settings = RemoteControlSettings::default();
+        assert_eq!(settings.authorize(""), Err(ControlError::Disabled));
+
+        settings.enabled = true;
+        assert!(settings.validate().is_err());
+        assert_eq!(settings.authorize(""), Err(ControlError::Unauthorized));
+
+        settings.token = crate::http_api::generate_token();
+        settings.validate().unwrap();
+        let token = settings.token.clone();
+        assert_eq!(settings.authorize(&token), Ok(()));
+        assert_eq!(
+            settings.authorize("0123456789abcdef"),
+            Err(ControlError::Unauthorized)
+        );
+    }
+
+    #[test]
+    fn test_command_wire_format() {
+        let start: ControlCommand =
+            serde_json::from_value(json!({"action": "start_recording", "deviceId": "mic-1"}))
+                .unwrap();
+        assert_eq!(
+            start,
+            ControlCommand::StartRecording {
+                device_id: "mic-1".to_string()
+            }
+        );
+        let stop: ControlCommand =
+            serde_json::from_value(json!({"action": "stop_recording"})).unwrap();
+        assert_eq!(stop, ControlCommand::StopRecording);
+        assert!(
+            serde_json::from_value::<ControlCommand>(json!({"action": "format_disk"})).is_err()
+        );
+
+        let status = RecordingStatus {
+            recording: true,
+            session_id: Some("s1".to_string()),
+            device_id: None,
+            docs_budget: None,
+        };
+        assert_eq!(
+            serde_json::to_value(&status).unwrap(),
+            json!({"recording": true, "sessionId": "s1"})
+        );
+    }
+}
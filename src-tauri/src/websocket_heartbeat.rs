@@ -0,0 +1,103 @@
+//! Heartbeats for WebSocket clients
+//!
+//! The server pings every client each `PING_INTERVAL`. Any frame from the
+//! client (the pong or a message of its own) counts as a sign of life. A
+//! client silent for `PONG_TIMEOUT` is taken to have crashed or been
+//! suspended and is disconnected, which drops it from the broadcast list
+//! instead of letting it pile up and slow every broadcast.
+//!
+//! Browsers answer pings on their own, so clients need no changes.
+
+use std::time::{Duration, Instant};
+
+/// Time between pings
+pub const PING_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Silence after which a client is disconnected (three missed pongs)
+pub const PONG_TIMEOUT: Duration = Duration::from_secs(45);
+
+/// What to do on a heartbeat tick
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeartbeatAction {
+    Ping,
+    /// Client silent for longer than the timeout
+    Disconnect,
+}
+
+/// Liveness of one client
+#[derive(Debug, Clone)]
+pub struct Heartbeat {
+    last_seen: Instant,
+    timeout: Duration,
+}
+
+impl Heartbeat {
+    pub fn new(now: Instant) -> Self {
+        Self::with_timeout(now, PONG_TIMEOUT)
+    }
+
+    pub fn with_timeout(now: Instant, timeout: Duration) -> Self {
+        Self {
+            last_seen: now,
+            timeout,
+        }
+    }
+
+    /// A frame arrived from the client
+    pub fn record_activity(&mut self, now: Instant) {
+        self.last_seen = now;
+    }
+
+    /// Time since the client was last heard from
+    pub fn silent_for(&self, now: Instant) -> Duration {
+        now.saturating_duration_since(self.last_seen)
+    }
+
+    pub fn on_tick(&self, now: Instant) -> HeartbeatAction {
+        if self.silent_for(now) >= self.timeout {
+            HeartbeatAction::Disconnect
+        } else {
+            HeartbeatAction::Ping
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heartbeat_disconnects_silent_client() {
+        let start = Instant::now();
+        let mut heartbeat = Heartbeat::new(start);
+        assert_eq!(
+            heartbeat.on_tick(start + PING_INTERVAL),
+            HeartbeatAction::Ping
+        );
+        assert_eq!(
+            heartbeat.on_tick(start + PING_INTERVAL * 2),
+            HeartbeatAction::Ping
+        );
+
+        // A pong resets the clock
+        heartbeat.record_activity(start + PING_INTERVAL * 2);
+        assert_eq!(
+            heartbeat.on_tick(start + PONG_TIMEOUT),
+            HeartbeatAction::Ping
+        );
+        assert_eq!(
+            heartbeat.on_tick(start + PING_INTERVAL * 2 + PONG_TIMEOUT),
+            HeartbeatAction::Disconnect
+        );
+        assert_eq!(
+            heartbeat.silent_for(start + PING_INTERVAL * 3),
+            PING_INTERVAL
+        );
+
+        let short = Heartbeat::with_timeout(start, Duration::from_secs(1));
+        assert_eq!(
+            short.on_tick(start + Duration::from_secs(1)),
+            HeartbeatAction::Disconnect
+        );
+    }
+}
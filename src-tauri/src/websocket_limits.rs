@@ -0,0 +1,271 @@
+//! WebSocket Message Size Limits
+//!
+//! Some clients handle very large frames poorly, and a final segment of
+//! minutes of uninterrupted speech can get large. Transcription messages
+//! whose JSON exceeds `max_message_bytes` are split into parts that each fit:
+//! every part keeps the original `messageId` and fields, carries a slice of
+//! `text`, and adds `part` (1-based) and `totalParts`. Clients reassemble by
+//! concatenating `text` of parts 1..=totalParts with the same `messageId`.
+//! Unchunked messages carry neither field, so existing clients are unaffected.
+//!
+//! As a guard against unbounded single-segment growth, text beyond
+//! `max_segment_bytes` is not broadcast (the message gets `truncated: true`);
+//! the transcript file keeps the full text.
+//!
+//! Persisted to `settings/websocket.json` in app data directory.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::websocket::WebSocketMessage;
+
+/// Smallest accepted `max_message_bytes` (room for the fields plus some text)
+pub const MIN_MESSAGE_BYTES: usize = 1024;
+
+/// Placeholder part number used to reserve room for `part`/`totalParts`
+const PART_PLACEHOLDER: u64 = 999_999;
+
+/// Outgoing message limits
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebSocketSettings {
+    /// Largest transcription frame (JSON bytes); larger ones are chunked
+    #[serde(default = "default_max_message_bytes")]
+    pub max_message_bytes: usize,
+    /// Longest transcription text broadcast per segment (UTF-8 bytes)
+    #[serde(default = "default_max_segment_bytes")]
+    pub max_segment_bytes: usize,
+    /// Settings version for future migrations
+    #[serde(default = "default_version")]
+    pub version: u32,
+}
+
+fn default_max_message_bytes() -> usize {
+    16 * 1024
+}
+
+fn default_max_segment_bytes() -> usize {
+    256 * 1024
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+impl Default for WebSocketSettings {
+    fn default() -> Self {
+        Self {
+            max_message_bytes: default_max_message_bytes(),
+            max_segment_bytes: default_max_segment_bytes(),
+            version: 1,
+        }
+    }
+}
+
+/// Frames of one broadcast message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncodedFrames {
+    pub frames: Vec<String>,
+    /// Text bytes cut off by `max_segment_bytes`
+    pub truncated_bytes: usize,
+}
+
+/// Serialize a message into one or more frames within the limits
+///
+/// Only transcription messages are chunked or truncated; other messages
+/// are a single frame regardless of size.
+pub fn encode_frames(
+    message: &WebSocketMessage,
+    settings: &WebSocketSettings,
+) -> serde_json::Result<EncodedFrames> {
+    let json = serde_json::to_string(message)?;
+    let WebSocketMessage::Transcription {
+        text: full_text, ..
+    } = message
+    else {
+        return Ok(single(json));
+    };
+
+    let text = truncate_to_boundary(full_text, settings.max_segment_bytes);
+    let truncated_bytes = full_text.len() - text.len();
+    let max_message_bytes = settings.max_message_bytes.max(MIN_MESSAGE_BYTES);
+    if truncated_bytes == 0 && json.len() <= max_message_bytes {
+        return Ok(single(json));
+    }
+
+    let mut value = serde_json::to_value(message)?;
+    if truncated_bytes > 0 {
+        value["truncated"] = Value::Bool(true);
+    }
+
+    // Room left for text once the other fields (and part numbers) are in
+    value["text"] = Value::String(String::new());
+    value["part"] = PART_PLACEHOLDER.into();
+    value["totalParts"] = PART_PLACEHOLDER.into();
+    let overhead = serde_json::to_string(&value)?.len();
+    let budget = max_message_bytes.saturating_sub(overhead).max(64);
+
+    let chunks = split_escaped(text, budget);
+    let total = chunks.len();
+    let obj = value
+        .as_object_mut()
+        .expect("transcription serializes as an object");
+    if total == 1 {
+        obj.remove("part");
+        obj.remove("totalParts");
+    }
+
+    let mut frames = Vec::with_capacity(total);
+    for (i, chunk) in chunks.into_iter().enumerate() {
+        value["text"] = Value::String(chunk.to_string());
+        if total > 1 {
+            value["part"] = (i as u64 + 1).into();
+            value["totalParts"] = (total as u64).into();
+        }
+        frames.push(serde_json::to_string(&value)?);
+    }
+    Ok(EncodedFrames {
+        frames,
+        truncated_bytes,
+    })
+}
+
+fn single(json: String) -> EncodedFrames {
+    EncodedFrames {
+        frames: vec![json],
+        truncated_bytes: 0,
+    }
+}
+
+/// Longest prefix of `text` within `max_bytes`, cut at a char boundary
+fn truncate_to_boundary(text: &str, max_bytes: usize) -> &str {
+    if text.len() <= max_bytes {
+        return text;
+    }
+    let mut end = max_bytes;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+/// Bytes a char takes inside a JSON string (as serde_json escapes it)
+fn escaped_len(c: char) -> usize {
+    match c {
+        '"' | '\\' | '\n' | '\r' | '\t' | '\u{8}' | '\u{c}' => 2,
+        c if (c as u32) < 0x20 => 6,
+        c => c.len_utf8(),
+    }
+}
+
+/// Split text into pieces whose escaped length fits `budget`
+fn split_escaped(text: &str, budget: usize) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    let mut size = 0;
+    for (i, c) in text.char_indices() {
+        let len = escaped_len(c);
+        if size + len > budget && i > start {
+            chunks.push(&text[start..i]);
+            start = i;
+            size = 0;
+        }
+        size += len;
+    }
+    chunks.push(&text[start..]);
+    chunks
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+pub const SETTINGS_STEM: &str = "websocket";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transcription(text: &str) -> WebSocketMessage {
+        WebSocketMessage::Transcription {
+            message_id: "ws-1".to_string(),
+            session_id: "s".to_string(),
+            text: text.to_string(),
+            timestamp: 1,
+            is_partial: Some(false),
+            confidence: Some(0.9),
+            language: Some("ja".to_string()),
+            processing_time_ms: None,
+        }
+    }
+
+    fn settings(max_message_bytes: usize, max_segment_bytes: usize) -> WebSocketSettings {
+        WebSocketSettings {
+            max_message_bytes,
+            max_segment_bytes,
+            ..Default::default()
+        }
+    }
+
+    fn parse(frame: &str) -> Value {
+        serde_json::from_str(frame).unwrap()
+    }
+
+    #[test]
+    fn test_small_message_is_unchanged() {
+        let message = transcription("こんにちは");
+        let encoded = encode_frames(&message, &WebSocketSettings::default()).unwrap();
+        assert_eq!(
+            encoded.frames,
+            vec![serde_json::to_string(&message).unwrap()]
+        );
+        assert!(parse(&encoded.frames[0]).get("part").is_none());
+    }
+
+    #[test]
+    fn test_long_final_is_chunked_and_reassembles() {
+        // Multi-byte text and characters that need escaping
+        let text = "会議の\"議事録\"です。\n".repeat(200);
+        let limits = settings(MIN_MESSAGE_BYTES, usize::MAX);
+        let encoded = encode_frames(&transcription(&text), &limits).unwrap();
+
+        assert!(encoded.frames.len() > 1);
+        assert_eq!(encoded.truncated_bytes, 0);
+        let mut reassembled = String::new();
+        for (i, frame) in encoded.frames.iter().enumerate() {
+            assert!(frame.len() <= MIN_MESSAGE_BYTES, "frame {} too large", i);
+            let value = parse(frame);
+            assert_eq!(value["messageId"], "ws-1");
+            assert_eq!(value["language"], "ja");
+            assert_eq!(value["part"], i as u64 + 1);
+            assert_eq!(value["totalParts"], encoded.frames.len() as u64);
+            reassembled.push_str(value["text"].as_str().unwrap());
+        }
+        assert_eq!(reassembled, text);
+    }
+
+    #[test]
+    fn test_segment_guard_truncates_at_char_boundary() {
+        let text = "あ".repeat(100); // 300 bytes
+        let encoded = encode_frames(&transcription(&text), &settings(16 * 1024, 100)).unwrap();
+
+        assert_eq!(encoded.frames.len(), 1);
+        assert_eq!(encoded.truncated_bytes, 201);
+        let value = parse(&encoded.frames[0]);
+        assert_eq!(value["text"], "あ".repeat(33));
+        assert_eq!(value["truncated"], true);
+        assert!(value.get("part").is_none());
+    }
+
+    #[test]
+    fn test_other_messages_are_never_chunked() {
+        let message = WebSocketMessage::SummaryComplete {
+            message_id: "ws-2".to_string(),
+            session_id: "s".to_string(),
+            text: "x".repeat(10_000),
+            timestamp: 1,
+        };
+        let encoded = encode_frames(&message, &settings(MIN_MESSAGE_BYTES, 10)).unwrap();
+        assert_eq!(encoded.frames.len(), 1);
+        assert_eq!(encoded.truncated_bytes, 0);
+    }
+}
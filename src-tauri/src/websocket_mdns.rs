@@ -0,0 +1,179 @@
+//! mDNS / DNS-SD Advertisement of the WebSocket Server
+//!
+//! Companion apps find the server by browsing for `_meeting-minutes._tcp`
+//! instead of hardcoding `localhost:9001`. The service record carries the
+//! listening port, and its TXT record:
+//!
+//! - `proto`: WebSocket message protocol version (`PROTOCOL_VERSION`)
+//! - `app`: application version
+//! - `path`: WebSocket path (`/`)
+//! - `pid`: process ID, to tell several running instances apart
+//!
+//! The server only accepts connections from the same machine (it binds
+//! `127.0.0.1`), so peers on the LAN can see the advertisement but must
+//! connect through the host itself.
+//!
+//! Disabled by default. Settings are machine-wide; the advertisement starts
+//! or stops as soon as they are saved. Persisted to
+//! `settings/websocket_mdns.json` in app data directory.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// DNS-SD service type
+pub const SERVICE_TYPE: &str = "_meeting-minutes._tcp.local.";
+
+/// WebSocket message protocol version (bump on incompatible changes)
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Longest DNS label (instance and host names)
+const MAX_LABEL_LEN: usize = 63;
+
+/// mDNS advertisement configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MdnsSettings {
+    /// Advertise the WebSocket server
+    #[serde(default)]
+    pub enabled: bool,
+    /// Settings version for future migrations
+    #[serde(default = "default_version")]
+    pub version: u32,
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+impl Default for MdnsSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            version: 1,
+        }
+    }
+}
+
+/// Host label for `hostname`: lowercase letters, digits and dashes
+pub fn host_label(hostname: &str) -> String {
+    let label: String = hostname
+        .split('.')
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .take(MAX_LABEL_LEN)
+        .collect();
+    let label = label.trim_matches('-');
+    if label.is_empty() {
+        "meeting-minutes".to_string()
+    } else {
+        label.to_string()
+    }
+}
+
+/// Service instance name, unique per host and port
+pub fn instance_name(hostname: &str, port: u16) -> String {
+    let suffix = format!(" ({})", port);
+    let host = hostname.split('.').next().unwrap_or_default();
+    let mut name = format!("Meeting Minutes Automator on {}", host);
+    while name.len() + suffix.len() > MAX_LABEL_LEN {
+        name.pop();
+    }
+    name + &suffix
+}
+
+/// TXT record of the advertisement
+pub fn txt_properties(pid: u32) -> HashMap<String, String> {
+    HashMap::from([
+        ("proto".to_string(), PROTOCOL_VERSION.to_string()),
+        ("app".to_string(), env!("CARGO_PKG_VERSION").to_string()),
+        ("path".to_string(), "/".to_string()),
+        ("pid".to_string(), pid.to_string()),
+    ])
+}
+
+/// Running advertisement; withdrawn by `stop`
+pub struct MdnsAdvertiser {
+    daemon: mdns_sd::ServiceDaemon,
+    fullname: String,
+    port: u16,
+}
+
+impl MdnsAdvertiser {
+    /// Advertise the WebSocket server listening on `port`
+    pub fn start(port: u16) -> Result<Self> {
+        let hostname = sys_info::hostname().unwrap_or_default();
+        let host = format!("{}.local.", host_label(&hostname));
+        let info = mdns_sd::ServiceInfo::new(
+            SERVICE_TYPE,
+            &instance_name(&hostname, port),
+            &host,
+            "",
+            port,
+            txt_properties(std::process::id()),
+        )
+        .context("Invalid mDNS service record")?
+        .enable_addr_auto();
+        let fullname = info.get_fullname().to_string();
+
+        let daemon = mdns_sd::ServiceDaemon::new().context("Failed to start mDNS responder")?;
+        daemon
+            .register(info)
+            .context("Failed to register mDNS service")?;
+
+        Ok(Self {
+            daemon,
+            fullname,
+            port,
+        })
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Withdraw the advertisement (sends goodbye packets) and stop responding
+    pub fn stop(self) {
+        let _ = self.daemon.unregister(&self.fullname);
+        let _ = self.daemon.shutdown();
+    }
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+pub const SETTINGS_STEM: &str = "websocket_mdns";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names_are_valid_labels() {
+        assert_eq!(host_label("Studio Mac_Pro.lan"), "studio-mac-pro");
+        assert_eq!(host_label(""), "meeting-minutes");
+        assert_eq!(host_label(&"x".repeat(100)).len(), MAX_LABEL_LEN);
+
+        assert_eq!(
+            instance_name("studio.lan", 9001),
+            "Meeting Minutes Automator on studio (9001)"
+        );
+        let long = instance_name(&"h".repeat(100), 9002);
+        assert!(long.len() <= MAX_LABEL_LEN);
+        assert!(long.ends_with(" (9002)"));
+
+        let txt = txt_properties(42);
+        assert_eq!(txt["proto"], PROTOCOL_VERSION.to_string());
+        assert_eq!(txt["app"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(txt["pid"], "42");
+    }
+}
@@ -0,0 +1,242 @@
+//! WebSocket Port Selection and Discovery
+//!
+//! The server listens on the preferred `port`, or on the first free port of
+//! the next `fallback_ports` ports when it is taken (another app instance, or
+//! an unrelated program). With the defaults that is 9001-9010, tried in order,
+//! so the same machine always ends up with the same assignment. Every
+//! candidate lies in 9001-9100, the range the Chrome extension scans, so a
+//! fallback never hides the server from the extension.
+//!
+//! The chosen port is reported by `get_websocket_port` and written to a
+//! discovery file per running instance,
+//! `[app_data_dir]/discovery/websocket-<pid>.json`, for local tools that
+//! need to connect. Files are removed on exit; files left behind by a crash
+//! are pruned once their port is free again.
+//!
+//! Settings are machine-wide (not per workspace) and take effect at the next
+//! start. Persisted to `settings/websocket_port.json` in app data directory.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+
+/// Ports scanned by the Chrome extension
+pub const EXTENSION_PORT_RANGE: RangeInclusive<u16> = 9001..=9100;
+
+/// Directory of the discovery files (in the app data directory)
+pub const DISCOVERY_SUBDIR: &str = "discovery";
+
+/// Listening port configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebSocketPortSettings {
+    /// Preferred port
+    #[serde(default = "default_port")]
+    pub port: u16,
+    /// Ports after `port` tried in order when it is taken
+    #[serde(default = "default_fallback_ports")]
+    pub fallback_ports: u16,
+    /// Settings version for future migrations
+    #[serde(default = "default_version")]
+    pub version: u32,
+}
+
+fn default_port() -> u16 {
+    *EXTENSION_PORT_RANGE.start()
+}
+
+fn default_fallback_ports() -> u16 {
+    9
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+impl Default for WebSocketPortSettings {
+    fn default() -> Self {
+        Self {
+            port: default_port(),
+            fallback_ports: default_fallback_ports(),
+            version: 1,
+        }
+    }
+}
+
+impl WebSocketPortSettings {
+    pub fn validate(&self) -> Result<()> {
+        let (first, last) = (*EXTENSION_PORT_RANGE.start(), *EXTENSION_PORT_RANGE.end());
+        if !EXTENSION_PORT_RANGE.contains(&self.port) {
+            anyhow::bail!(
+                "Port must be {}-{} (the range the Chrome extension scans)",
+                first,
+                last
+            );
+        }
+        if self.port as u32 + self.fallback_ports as u32 > last as u32 {
+            anyhow::bail!(
+                "Fallback ports must end at {} at the latest (at most {} after port {})",
+                last,
+                last - self.port,
+                self.port
+            );
+        }
+        Ok(())
+    }
+
+    /// Ports tried in order
+    pub fn candidates(&self) -> RangeInclusive<u16> {
+        self.port..=self.port.saturating_add(self.fallback_ports)
+    }
+}
+
+/// Port chosen by a running instance
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PortDiscovery {
+    pub port: u16,
+    /// Process ID of the instance
+    pub pid: u32,
+    /// Active workspace at startup
+    pub workspace: String,
+    /// Start time (Unix ms)
+    pub started_at: u64,
+}
+
+// ============================================================================
+// Discovery files
+// ============================================================================
+
+/// Discovery file of the instance with `pid`
+pub fn discovery_path(app_data_dir: &Path, pid: u32) -> PathBuf {
+    app_data_dir
+        .join(DISCOVERY_SUBDIR)
+        .join(format!("websocket-{}.json", pid))
+}
+
+/// Write the discovery file of this instance, pruning stale ones
+pub fn write_discovery(app_data_dir: &Path, discovery: &PortDiscovery) -> Result<PathBuf> {
+    let dir = app_data_dir.join(DISCOVERY_SUBDIR);
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create discovery directory: {:?}", dir))?;
+    prune_stale(app_data_dir, discovery.pid);
+
+    let path = discovery_path(app_data_dir, discovery.pid);
+    let json =
+        serde_json::to_string_pretty(discovery).context("Failed to serialize port discovery")?;
+    std::fs::write(&path, json)
+        .with_context(|| format!("Failed to write discovery file: {:?}", path))?;
+    Ok(path)
+}
+
+/// Remove the discovery file of the instance with `pid` (if any)
+pub fn remove_discovery(app_data_dir: &Path, pid: u32) -> Result<()> {
+    let path = discovery_path(app_data_dir, pid);
+    match std::fs::remove_file(&path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+            Err(e).with_context(|| format!("Failed to remove discovery file: {:?}", path))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Discovery files of the running instances, by port
+pub fn read_discovery(app_data_dir: &Path) -> Result<Vec<PortDiscovery>> {
+    let dir = app_data_dir.join(DISCOVERY_SUBDIR);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut found: Vec<PortDiscovery> = std::fs::read_dir(&dir)
+        .with_context(|| format!("Failed to read discovery directory: {:?}", dir))?
+        .filter_map(|entry| std::fs::read_to_string(entry.ok()?.path()).ok())
+        .filter_map(|json| serde_json::from_str(&json).ok())
+        .collect();
+    found.sort_by_key(|d| d.port);
+    Ok(found)
+}
+
+/// Remove files of other instances whose port nobody listens on anymore
+fn prune_stale(app_data_dir: &Path, own_pid: u32) {
+    let Ok(found) = read_discovery(app_data_dir) else {
+        return;
+    };
+    for discovery in found {
+        if discovery.pid != own_pid && !is_listening(discovery.port) {
+            let _ = remove_discovery(app_data_dir, discovery.pid);
+        }
+    }
+}
+
+/// Whether something listens on the local port
+fn is_listening(port: u16) -> bool {
+    std::net::TcpListener::bind(("127.0.0.1", port)).is_err()
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+pub const SETTINGS_STEM: &str = "websocket_port";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_port_settings_candidates_and_validation() {
+        let defaults = WebSocketPortSettings::default();
+        defaults.validate().unwrap();
+        assert_eq!(defaults.candidates(), 9001..=9010);
+
+        let settings = |port, fallback_ports| WebSocketPortSettings {
+            port,
+            fallback_ports,
+            ..Default::default()
+        };
+        settings(9050, 50).validate().unwrap();
+        assert!(settings(9051, 50).validate().is_err());
+        assert!(settings(8080, 0).validate().is_err());
+        assert!(settings(9101, 0).validate().is_err());
+        assert!(settings(9001, u16::MAX).validate().is_err());
+    }
+
+    #[test]
+    fn test_discovery_files() {
+        let dir = TempDir::new().unwrap();
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let live_port = listener.local_addr().unwrap().port();
+        let free_port = {
+            let probe = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            probe.local_addr().unwrap().port()
+        };
+        let discovery = |port, pid| PortDiscovery {
+            port,
+            pid,
+            workspace: "default".to_string(),
+            started_at: 0,
+        };
+
+        // A live instance, and one that crashed (its port is free again)
+        write_discovery(dir.path(), &discovery(live_port, 2)).unwrap();
+        write_discovery(dir.path(), &discovery(free_port, 1)).unwrap();
+        assert_eq!(read_discovery(dir.path()).unwrap().len(), 2);
+
+        // Writing prunes the crashed instance only
+        let path = write_discovery(dir.path(), &discovery(live_port, 3)).unwrap();
+        assert_eq!(path, discovery_path(dir.path(), 3));
+        let mut pids: Vec<u32> = read_discovery(dir.path())
+            .unwrap()
+            .iter()
+            .map(|d| d.pid)
+            .collect();
+        pids.sort();
+        assert_eq!(pids, [2, 3]);
+
+        remove_discovery(dir.path(), 3).unwrap();
+        remove_discovery(dir.path(), 3).unwrap();
+        assert_eq!(
+            read_discovery(dir.path()).unwrap(),
+            [discovery(live_port, 2)]
+        );
+    }
+}
@@ -0,0 +1,253 @@
+//! Bounded per-client send queue with a drop-partials-first policy
+//!
+//! Each WebSocket client gets a queue of at most `capacity` messages (a
+//! message is all frames of one broadcast, so a chunked message is kept or
+//! dropped as a whole). When a slow client's queue is full:
+//!
+//! 1. the oldest queued partial transcription is evicted to make room — a
+//!    newer partial or the final supersedes it anyway
+//! 2. with no partial left to evict, an incoming partial is dropped
+//! 3. only then is an incoming essential message (final, notification, ...)
+//!    dropped, which counts as a delivery failure (`websocket_retry`)
+//!
+//! Pushing never waits, so one laggy consumer can't delay the broadcast to
+//! the others. Drops are counted per client (`QueueStats`).
+
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use tokio::sync::Notify;
+
+/// Result of a push
+#[derive(Debug, PartialEq, Eq)]
+pub enum PushOutcome<T> {
+    Queued,
+    /// Queued after evicting the oldest partial (its frames are returned)
+    QueuedEvicting(Vec<T>),
+    /// Queue full of essential messages: the incoming message was dropped
+    Dropped,
+    /// The writer has stopped
+    Closed,
+}
+
+/// Queue counters, for diagnostics
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct QueueStats {
+    /// Messages waiting for the writer
+    pub queued: usize,
+    /// Partials evicted or dropped
+    pub dropped_partials: u64,
+    /// Essential messages dropped
+    pub dropped_essential: u64,
+}
+
+struct Entry<T> {
+    frames: Vec<T>,
+    partial: bool,
+}
+
+struct Inner<T> {
+    entries: VecDeque<Entry<T>>,
+    closed: bool,
+    dropped_partials: u64,
+    dropped_essential: u64,
+}
+
+/// Bounded multi-producer, single-consumer queue of messages
+pub struct ClientQueue<T> {
+    inner: Mutex<Inner<T>>,
+    capacity: usize,
+    /// Signalled when a message is queued or the queue is closed
+    items: Notify,
+    /// Signalled when a message is taken (room for `push_wait`)
+    space: Notify,
+}
+
+impl<T> ClientQueue<T> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Mutex::new(Inner {
+                entries: VecDeque::new(),
+                closed: false,
+                dropped_partials: 0,
+                dropped_essential: 0,
+            }),
+            capacity: capacity.max(1),
+            items: Notify::new(),
+            space: Notify::new(),
+        }
+    }
+
+    /// Queue a message without waiting, applying the drop policy when full
+    pub fn push(&self, frames: Vec<T>, partial: bool) -> PushOutcome<T> {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.closed {
+            return PushOutcome::Closed;
+        }
+        let mut evicted = None;
+        if inner.entries.len() >= self.capacity {
+            match inner.entries.iter().position(|entry| entry.partial) {
+                Some(index) => {
+                    evicted = inner.entries.remove(index).map(|entry| entry.frames);
+                    inner.dropped_partials += 1;
+                }
+                None if partial => {
+                    inner.dropped_partials += 1;
+                    return PushOutcome::Dropped;
+                }
+                None => {
+                    inner.dropped_essential += 1;
+                    return PushOutcome::Dropped;
+                }
+            }
+        }
+        inner.entries.push_back(Entry { frames, partial });
+        drop(inner);
+        self.items.notify_one();
+        match evicted {
+            Some(frames) => PushOutcome::QueuedEvicting(frames),
+            None => PushOutcome::Queued,
+        }
+    }
+
+    /// Queue a message only if there is room (nothing is evicted)
+    pub fn try_push(&self, frames: Vec<T>) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.closed || inner.entries.len() >= self.capacity {
+            return false;
+        }
+        inner.entries.push_back(Entry {
+            frames,
+            partial: false,
+        });
+        drop(inner);
+        self.items.notify_one();
+        true
+    }
+
+    /// Queue an essential message, waiting for room; false once closed
+    pub async fn push_wait(&self, frames: Vec<T>) -> bool {
+        loop {
+            let space = self.space.notified();
+            tokio::pin!(space);
+            space.as_mut().enable();
+            {
+                let mut inner = self.inner.lock().unwrap();
+                if inner.closed {
+                    return false;
+                }
+                if inner.entries.len() < self.capacity {
+                    inner.entries.push_back(Entry {
+                        frames,
+                        partial: false,
+                    });
+                    drop(inner);
+                    self.items.notify_one();
+                    return true;
+                }
+            }
+            space.await;
+        }
+    }
+
+    /// Next message; None once closed and drained
+    pub async fn pop(&self) -> Option<Vec<T>> {
+        loop {
+            let items = self.items.notified();
+            tokio::pin!(items);
+            items.as_mut().enable();
+            {
+                let mut inner = self.inner.lock().unwrap();
+                if let Some(entry) = inner.entries.pop_front() {
+                    drop(inner);
+                    self.space.notify_one();
+                    return Some(entry.frames);
+                }
+                if inner.closed {
+                    return None;
+                }
+            }
+            items.await;
+        }
+    }
+
+    /// Refuse further messages (already queued ones can still be popped)
+    pub fn close(&self) {
+        self.inner.lock().unwrap().closed = true;
+        self.items.notify_waiters();
+        self.space.notify_waiters();
+    }
+
+    pub fn stats(&self) -> QueueStats {
+        let inner = self.inner.lock().unwrap();
+        QueueStats {
+            queued: inner.entries.len(),
+            dropped_partials: inner.dropped_partials,
+            dropped_essential: inner.dropped_essential,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_full_queue_drops_partials_first() {
+        let queue = ClientQueue::new(3);
+        assert_eq!(queue.push(vec!["p1"], true), PushOutcome::Queued);
+        assert_eq!(queue.push(vec!["f1"], false), PushOutcome::Queued);
+        assert_eq!(queue.push(vec!["p2"], true), PushOutcome::Queued);
+
+        // Oldest partial makes room, whatever comes in
+        assert_eq!(
+            queue.push(vec!["f2"], false),
+            PushOutcome::QueuedEvicting(vec!["p1"])
+        );
+        assert_eq!(
+            queue.push(vec!["p3"], true),
+            PushOutcome::QueuedEvicting(vec!["p2"])
+        );
+        assert_eq!(
+            queue.push(vec!["f3"], false),
+            PushOutcome::QueuedEvicting(vec!["p3"])
+        );
+
+        // Only finals left: incoming messages are dropped
+        assert_eq!(queue.push(vec!["p4"], true), PushOutcome::Dropped);
+        assert_eq!(queue.push(vec!["f4"], false), PushOutcome::Dropped);
+        assert!(!queue.try_push(vec!["ping"]));
+
+        assert_eq!(
+            queue.stats(),
+            QueueStats {
+                queued: 3,
+                dropped_partials: 4,
+                dropped_essential: 1,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pop_waits_and_drains_after_close() {
+        let queue = std::sync::Arc::new(ClientQueue::new(1));
+        let reader = {
+            let queue = queue.clone();
+            tokio::spawn(async move {
+                let mut received = Vec::new();
+                while let Some(frames) = queue.pop().await {
+                    received.extend(frames);
+                }
+                received
+            })
+        };
+
+        assert!(queue.push_wait(vec![1, 2]).await);
+        // Waits until the reader made room
+        assert!(queue.push_wait(vec![3]).await);
+        assert!(queue.push_wait(vec![4]).await);
+        queue.close();
+        assert_eq!(queue.push(vec![5], false), PushOutcome::Closed);
+        assert_eq!(reader.await.unwrap(), vec![1, 2, 3, 4]);
+    }
+}
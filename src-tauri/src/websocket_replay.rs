@@ -0,0 +1,189 @@
+//! Replay of missed transcriptions for reconnecting WebSocket clients
+//!
+//! The server keeps the most recent final transcription messages of each
+//! session (`REPLAY_CAPACITY` per session, for the `MAX_REPLAY_SESSIONS`
+//! most recently active sessions). A client that reconnects after a brief
+//! disconnect sends
+//!
+//! ```json
+//! {"type": "resume", "sessionId": "...", "lastMessageId": "ws-..."}
+//! ```
+//!
+//! with the last message ID it received, and gets every buffered
+//! transcription after it, in order, before or interleaved with live
+//! messages (clients drop `messageId`s they already have). Without
+//! `lastMessageId`, everything buffered is replayed. When messages the
+//! client never received have already been evicted, a `replay_gap`
+//! notification follows the replay.
+//!
+//! Partial results are not buffered: they are superseded by the final one.
+//! Message IDs of one session sort in issue order (see `message_id`), so
+//! "after" is a plain string comparison.
+
+use std::collections::VecDeque;
+
+use crate::websocket::WebSocketMessage;
+
+/// Final transcriptions kept per session
+pub const REPLAY_CAPACITY: usize = 500;
+
+/// Sessions whose transcriptions are kept
+pub const MAX_REPLAY_SESSIONS: usize = 8;
+
+/// Buffered transcriptions of one session
+#[derive(Debug, Default)]
+struct SessionReplay {
+    session_id: String,
+    messages: VecDeque<(String, WebSocketMessage)>,
+    /// ID of the newest evicted message
+    evicted_through: Option<String>,
+}
+
+/// Messages to replay to a resuming client
+#[derive(Debug, Default)]
+pub struct Replay {
+    pub messages: Vec<WebSocketMessage>,
+    /// Messages after `last_message_id` were evicted and cannot be replayed
+    pub gap: bool,
+}
+
+/// Recent final transcriptions per session
+#[derive(Debug, Default)]
+pub struct ReplayBuffer {
+    /// Most recently active session last
+    sessions: VecDeque<SessionReplay>,
+}
+
+impl ReplayBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep `message` if it is a final transcription
+    pub fn record(&mut self, message: &WebSocketMessage) {
+        let WebSocketMessage::Transcription {
+            message_id,
+            session_id,
+            is_partial,
+            ..
+        } = message
+        else {
+            return;
+        };
+        if *is_partial == Some(true) {
+            return;
+        }
+
+        // Move the session to the back (most recently active)
+        let session = match self
+            .sessions
+            .iter()
+            .position(|s| &s.session_id == session_id)
+        {
+            Some(index) => self.sessions.remove(index).unwrap_or_default(),
+            None => {
+                if self.sessions.len() == MAX_REPLAY_SESSIONS {
+                    self.sessions.pop_front();
+                }
+                SessionReplay {
+                    session_id: session_id.clone(),
+                    ..SessionReplay::default()
+                }
+            }
+        };
+        self.sessions.push_back(session);
+        let Some(session) = self.sessions.back_mut() else {
+            return;
+        };
+
+        if session.messages.len() == REPLAY_CAPACITY {
+            if let Some((evicted_id, _)) = session.messages.pop_front() {
+                session.evicted_through = Some(evicted_id);
+            }
+        }
+        session
+            .messages
+            .push_back((message_id.clone(), message.clone()));
+    }
+
+    /// Buffered transcriptions of `session_id` after `last_message_id`
+    pub fn since(&self, session_id: &str, last_message_id: Option<&str>) -> Replay {
+        let Some(session) = self.sessions.iter().find(|s| s.session_id == session_id) else {
+            return Replay::default();
+        };
+        let after = |id: &str| last_message_id.is_none_or(|last| id > last);
+        Replay {
+            messages: session
+                .messages
+                .iter()
+                .filter(|(id, _)| after(id))
+                .map(|(_, message)| message.clone())
+                .collect(),
+            gap: session.evicted_through.as_deref().is_some_and(after),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transcription(session_id: &str, seq: usize, is_partial: bool) -> WebSocketMessage {
+        WebSocketMessage::Transcription {
+            message_id: format!("ws-{}-{:06}", session_id, seq),
+            session_id: session_id.to_string(),
+            text: format!("segment {}", seq),
+            timestamp: seq as u64,
+            is_partial: Some(is_partial),
+            confidence: None,
+            language: None,
+            processing_time_ms: None,
+        }
+    }
+
+    fn ids(replay: &Replay) -> Vec<String> {
+        replay
+            .messages
+            .iter()
+            .filter_map(|message| match message {
+                WebSocketMessage::Transcription { message_id, .. } => Some(message_id.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_replay_since_last_message() {
+        let mut buffer = ReplayBuffer::new();
+        for seq in 1..=3 {
+            buffer.record(&transcription("a", seq, false));
+        }
+        buffer.record(&transcription("a", 4, true));
+        buffer.record(&transcription("b", 1, false));
+
+        let replay = buffer.since("a", Some("ws-a-000001"));
+        assert_eq!(ids(&replay), ["ws-a-000002", "ws-a-000003"]);
+        assert!(!replay.gap);
+        assert_eq!(buffer.since("a", None).messages.len(), 3);
+        assert!(buffer.since("a", Some("ws-a-000003")).messages.is_empty());
+        assert!(buffer.since("unknown", None).messages.is_empty());
+
+        // Evicted messages the client never received leave a gap
+        for seq in 4..=REPLAY_CAPACITY + 2 {
+            buffer.record(&transcription("a", seq, false));
+        }
+        let replay = buffer.since("a", Some("ws-a-000001"));
+        assert!(replay.gap);
+        assert_eq!(replay.messages.len(), REPLAY_CAPACITY);
+        let replay = buffer.since("a", Some("ws-a-000010"));
+        assert!(!replay.gap);
+        assert_eq!(replay.messages.len(), REPLAY_CAPACITY + 2 - 10);
+
+        // Least recently active sessions are dropped
+        for session in 0..MAX_REPLAY_SESSIONS {
+            buffer.record(&transcription(&format!("s{}", session), 1, false));
+        }
+        assert!(buffer.since("b", None).messages.is_empty());
+        assert!(buffer.since("a", None).messages.is_empty());
+    }
+}
@@ -0,0 +1,150 @@
+//! Retries and failure accounting for WebSocket client writes
+//!
+//! A write that fails with a transient error (tungstenite's write buffer
+//! full, interrupted or timed-out socket I/O) is retried up to
+//! `MAX_SEND_RETRIES` times with exponential backoff and jitter, so a brief
+//! hiccup no longer loses that transcript for the client.
+//!
+//! Each client keeps a failure record. A delivery fails when its write is
+//! given up after the retries, or when a broadcast is dropped because the
+//! client's queue is full; any successful write ends the run. A client
+//! reaching `MAX_CONSECUTIVE_FAILURES` failures in a row is disconnected
+//! with a close frame (1013, try again later), so the extension reconnects
+//! instead of silently missing transcripts.
+
+use std::io::ErrorKind;
+use std::time::Duration;
+
+/// Retries of one write after the first attempt
+pub const MAX_SEND_RETRIES: u32 = 3;
+
+/// Backoff before the first retry (doubled per retry, before jitter)
+pub const BASE_RETRY_DELAY_MS: u64 = 20;
+
+/// Upper bound of the backoff before jitter
+pub const MAX_RETRY_DELAY_MS: u64 = 500;
+
+/// Failed deliveries in a row before the client is disconnected
+pub const MAX_CONSECUTIVE_FAILURES: u32 = 32;
+
+/// I/O errors worth retrying
+pub fn is_transient_io(kind: ErrorKind) -> bool {
+    matches!(
+        kind,
+        ErrorKind::WouldBlock | ErrorKind::Interrupted | ErrorKind::TimedOut
+    )
+}
+
+/// Backoff before retry `attempt` (1-based)
+///
+/// "Equal jitter": half of the exponential delay is fixed, the other half
+/// scaled by `jitter` in [0, 1), so clients failing together spread out.
+pub fn retry_delay(attempt: u32, jitter: f64) -> Duration {
+    let exponential = BASE_RETRY_DELAY_MS
+        .saturating_mul(1u64 << attempt.saturating_sub(1).min(16))
+        .min(MAX_RETRY_DELAY_MS);
+    let half = exponential / 2;
+    let jittered = half + (half as f64 * jitter.clamp(0.0, 1.0)) as u64;
+    Duration::from_millis(jittered)
+}
+
+/// Small xorshift generator for backoff jitter (not for anything secret)
+#[derive(Debug, Clone)]
+pub struct Jitter(u64);
+
+impl Jitter {
+    pub fn new(seed: u64) -> Self {
+        // xorshift is stuck at zero
+        Self(seed.max(1))
+    }
+
+    /// Seeded from the clock, so connections get different sequences
+    pub fn from_time() -> Self {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0);
+        Self::new(nanos ^ 0x9E37_79B9_7F4A_7C15)
+    }
+
+    /// Next value in [0, 1)
+    pub fn next_unit(&mut self) -> f64 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        (x >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Delivery failures of one client
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClientFailures {
+    /// Failed deliveries since the last successful write
+    pub consecutive: u32,
+    /// Failed deliveries over the connection's lifetime
+    pub total: u64,
+    /// Retries performed (successful or not)
+    pub retries: u64,
+}
+
+impl ClientFailures {
+    /// A write went through after `retries` retries
+    pub fn record_success(&mut self, retries: u32) {
+        self.consecutive = 0;
+        self.retries += retries as u64;
+    }
+
+    /// A delivery failed; returns true once the client should be disconnected
+    pub fn record_failure(&mut self, retries: u32) -> bool {
+        self.consecutive += 1;
+        self.total += 1;
+        self.retries += retries as u64;
+        self.consecutive >= MAX_CONSECUTIVE_FAILURES
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay_grows_and_is_capped() {
+        assert_eq!(retry_delay(1, 0.0), Duration::from_millis(10));
+        assert_eq!(retry_delay(2, 0.0), Duration::from_millis(20));
+        assert_eq!(retry_delay(3, 0.999), Duration::from_millis(79));
+        assert_eq!(retry_delay(30, 0.0), Duration::from_millis(250));
+        assert!(retry_delay(30, 1.0) <= Duration::from_millis(MAX_RETRY_DELAY_MS));
+
+        let mut jitter = Jitter::new(42);
+        for _ in 0..1_000 {
+            let value = jitter.next_unit();
+            assert!((0.0..1.0).contains(&value));
+        }
+        assert_ne!(Jitter::new(1).next_unit(), Jitter::new(2).next_unit());
+    }
+
+    #[test]
+    fn test_consecutive_failures_lead_to_disconnect() {
+        let mut failures = ClientFailures::default();
+        for _ in 1..MAX_CONSECUTIVE_FAILURES {
+            assert!(!failures.record_failure(MAX_SEND_RETRIES));
+        }
+        failures.record_success(1);
+        assert_eq!(failures.consecutive, 0);
+
+        for _ in 1..MAX_CONSECUTIVE_FAILURES {
+            assert!(!failures.record_failure(0));
+        }
+        assert!(failures.record_failure(0));
+        assert_eq!(failures.total, 2 * MAX_CONSECUTIVE_FAILURES as u64 - 1);
+        assert_eq!(
+            failures.retries,
+            (MAX_CONSECUTIVE_FAILURES as u64 - 1) * MAX_SEND_RETRIES as u64 + 1
+        );
+
+        assert!(is_transient_io(ErrorKind::Interrupted));
+        assert!(!is_transient_io(ErrorKind::BrokenPipe));
+    }
+}
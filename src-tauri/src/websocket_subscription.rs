@@ -0,0 +1,175 @@
+//! Per-client WebSocket subscription filters
+//!
+//! By default a client receives every message. Lightweight consumers can
+//! narrow that down with a subscribe control message:
+//!
+//! ```json
+//! {"type": "subscribe", "types": ["final", "notification"]}
+//! ```
+//!
+//! Types: `partial` and `final` (transcriptions), `error`, `notification`,
+//! `keyword_alert`, `summary` (streamed and complete summaries),
+//! `live_summary` and `docs_sync`. Examples: finals only `["final"]`, no
+//! partials (every type but `partial`), notifications only
+//! `["notification"]`. An empty list subscribes to everything again.
+//!
+//! `audio` (binary PCM frames, see `websocket_audio`) is only delivered to
+//! clients that list it; the every-type subscription leaves it out.
+//!
+//! `connected` is always delivered. Replayed messages (`websocket_replay`)
+//! go through the same filter.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+use crate::websocket::WebSocketMessage;
+
+/// Message type a client can subscribe to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageKind {
+    Partial,
+    Final,
+    Error,
+    Notification,
+    KeywordAlert,
+    Summary,
+    LiveSummary,
+    DocsSync,
+    /// Binary PCM frames (opt-in only)
+    Audio,
+}
+
+impl MessageKind {
+    /// Kind of an outgoing message; None for messages every client gets
+    pub fn of(message: &WebSocketMessage) -> Option<Self> {
+        match message {
+            WebSocketMessage::Connected { .. }
+            | WebSocketMessage::Resume { .. }
+            | WebSocketMessage::Subscribe { .. }
+            | WebSocketMessage::Control { .. }
+            | WebSocketMessage::ControlResult { .. } => None,
+            WebSocketMessage::Transcription { is_partial, .. } => {
+                Some(if *is_partial == Some(true) {
+                    Self::Partial
+                } else {
+                    Self::Final
+                })
+            }
+            WebSocketMessage::Error { .. } => Some(Self::Error),
+            WebSocketMessage::Notification { .. } => Some(Self::Notification),
+            WebSocketMessage::KeywordAlert { .. } => Some(Self::KeywordAlert),
+            WebSocketMessage::SummaryPartial { .. } | WebSocketMessage::SummaryComplete { .. } => {
+                Some(Self::Summary)
+            }
+            WebSocketMessage::LiveSummary { .. } => Some(Self::LiveSummary),
+            WebSocketMessage::DocsSync { .. } => Some(Self::DocsSync),
+        }
+    }
+}
+
+/// Message types a client receives
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Subscription {
+    /// None: every type
+    kinds: Option<BTreeSet<MessageKind>>,
+}
+
+impl Subscription {
+    /// Subscription to `kinds` (empty: every type)
+    pub fn new(kinds: &[MessageKind]) -> Self {
+        Self {
+            kinds: (!kinds.is_empty()).then(|| kinds.iter().copied().collect()),
+        }
+    }
+
+    /// Subscribed types (empty: every type)
+    pub fn kinds(&self) -> Vec<MessageKind> {
+        self.kinds
+            .as_ref()
+            .map(|kinds| kinds.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Whether binary PCM frames are subscribed (never by default)
+    pub fn accepts_audio(&self) -> bool {
+        self.kinds
+            .as_ref()
+            .is_some_and(|kinds| kinds.contains(&MessageKind::Audio))
+    }
+
+    pub fn accepts(&self, message: &WebSocketMessage) -> bool {
+        match (&self.kinds, MessageKind::of(message)) {
+            (Some(kinds), Some(kind)) => kinds.contains(&kind),
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transcription(is_partial: Option<bool>) -> WebSocketMessage {
+        WebSocketMessage::Transcription {
+            message_id: "ws-s-1".to_string(),
+            session_id: "s".to_string(),
+            text: "hello".to_string(),
+            timestamp: 0,
+            is_partial,
+            confidence: None,
+            language: None,
+            processing_time_ms: None,
+        }
+    }
+
+    #[test]
+    fn test_subscription_filters_message_types() {
+        let notification = WebSocketMessage::Notification {
+            message_id: "ws-s-2".to_string(),
+            session_id: "s".to_string(),
+            notification_type: "model_change".to_string(),
+            message: "switched".to_string(),
+            timestamp: 0,
+            data: None,
+        };
+        let connected = WebSocketMessage::Connected {
+            message_id: "ws-s-0".to_string(),
+            session_id: "s".to_string(),
+            timestamp: 0,
+        };
+
+        let all = Subscription::default();
+        assert!(all.accepts(&transcription(Some(true))));
+        assert_eq!(Subscription::new(&[]), all);
+        assert!(all.kinds().is_empty());
+        assert!(!all.accepts_audio());
+
+        let finals_only = Subscription::new(&[MessageKind::Final]);
+        assert!(finals_only.accepts(&transcription(Some(false))));
+        assert!(finals_only.accepts(&transcription(None)));
+        assert!(!finals_only.accepts(&transcription(Some(true))));
+        assert!(!finals_only.accepts(&notification));
+        assert!(finals_only.accepts(&connected));
+
+        let request: WebSocketMessage = serde_json::from_str(
+            r#"{"type":"subscribe","types":["notification","keyword_alert"]}"#,
+        )
+        .unwrap();
+        let WebSocketMessage::Subscribe { types } = request else {
+            panic!("not a subscribe message");
+        };
+        let notifications_only = Subscription::new(&types);
+        assert_eq!(
+            notifications_only.kinds(),
+            vec![MessageKind::Notification, MessageKind::KeywordAlert]
+        );
+        assert!(notifications_only.accepts(&notification));
+        assert!(!notifications_only.accepts(&transcription(None)));
+
+        let audio = Subscription::new(&[MessageKind::Audio, MessageKind::Final]);
+        assert!(audio.accepts_audio());
+        assert!(audio.accepts(&transcription(None)));
+        assert!(!audio.accepts(&notification));
+    }
+}
@@ -0,0 +1,205 @@
+//! Workspaces
+//!
+//! A shared machine (e.g. a meeting-room PC) can keep several teams apart
+//! with named workspaces. Each workspace has its own data directory, so its
+//! settings (including integration tokens), recordings and the keychain
+//! entry of its encryption key are separate from every other workspace.
+//!
+//! The `default` workspace is the app data directory itself, so existing
+//! installs keep their data. Named workspaces live in
+//! `[app_data_dir]/workspaces/<name>`. Logs and this file are shared.
+//!
+//! The workspace is picked at startup with `--workspace <name>` (falling
+//! back to the last active one) and switched at runtime with
+//! `switch_workspace`.
+//!
+//! Persisted to `settings/workspaces.json` in app data directory.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Workspace living in the app data directory itself
+pub const DEFAULT_WORKSPACE: &str = "default";
+
+/// Directory of the named workspaces (in the app data directory)
+pub const WORKSPACES_SUBDIR: &str = "workspaces";
+
+/// Most named workspaces
+pub const MAX_WORKSPACES: usize = 32;
+
+const MAX_NAME_LEN: usize = 64;
+
+/// Named workspace
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Workspace {
+    pub name: String,
+    /// Creation time (Unix ms)
+    pub created_at: u64,
+}
+
+/// Workspace configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkspaceSettings {
+    /// Named workspaces (`default` is implicit)
+    #[serde(default)]
+    pub workspaces: Vec<Workspace>,
+    /// Workspace used at startup
+    #[serde(default = "default_active")]
+    pub active: String,
+    /// Settings version for future migrations
+    #[serde(default = "default_version")]
+    pub version: u32,
+}
+
+fn default_active() -> String {
+    DEFAULT_WORKSPACE.to_string()
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+impl Default for WorkspaceSettings {
+    fn default() -> Self {
+        Self {
+            workspaces: Vec::new(),
+            active: DEFAULT_WORKSPACE.to_string(),
+            version: 1,
+        }
+    }
+}
+
+impl WorkspaceSettings {
+    pub fn validate(&self) -> Result<()> {
+        if self.workspaces.len() > MAX_WORKSPACES {
+            anyhow::bail!("At most {} workspaces are supported", MAX_WORKSPACES);
+        }
+        for (i, workspace) in self.workspaces.iter().enumerate() {
+            validate_name(&workspace.name)?;
+            if workspace.name == DEFAULT_WORKSPACE {
+                anyhow::bail!("'{}' is reserved", DEFAULT_WORKSPACE);
+            }
+            if self.workspaces[..i]
+                .iter()
+                .any(|other| other.name == workspace.name)
+            {
+                anyhow::bail!("Duplicate workspace: {}", workspace.name);
+            }
+        }
+        if !self.contains(&self.active) {
+            anyhow::bail!("Unknown active workspace: {}", self.active);
+        }
+        Ok(())
+    }
+
+    /// Whether `name` is the default or a named workspace
+    pub fn contains(&self, name: &str) -> bool {
+        name == DEFAULT_WORKSPACE || self.workspaces.iter().any(|w| w.name == name)
+    }
+
+    /// Add a named workspace
+    pub fn create(&mut self, name: &str, created_at: u64) -> Result<()> {
+        validate_name(name)?;
+        if self.contains(name) {
+            anyhow::bail!("Workspace already exists: {}", name);
+        }
+        if self.workspaces.len() >= MAX_WORKSPACES {
+            anyhow::bail!("At most {} workspaces are supported", MAX_WORKSPACES);
+        }
+        self.workspaces.push(Workspace {
+            name: name.to_string(),
+            created_at,
+        });
+        Ok(())
+    }
+}
+
+/// Workspace names: 1-64 ASCII letters, digits, `-` and `_`
+///
+/// Names become directory names and keychain entry names.
+pub fn validate_name(name: &str) -> Result<()> {
+    if name.is_empty() || name.len() > MAX_NAME_LEN {
+        anyhow::bail!("Workspace name must be 1-{} characters", MAX_NAME_LEN);
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        anyhow::bail!(
+            "Workspace name may only contain letters, digits, '-' and '_': {}",
+            name
+        );
+    }
+    Ok(())
+}
+
+/// Data directory of a workspace (settings, recordings, heartbeat)
+pub fn data_dir(app_data_dir: &Path, workspace: &str) -> PathBuf {
+    if workspace == DEFAULT_WORKSPACE {
+        app_data_dir.to_path_buf()
+    } else {
+        app_data_dir.join(WORKSPACES_SUBDIR).join(workspace)
+    }
+}
+
+/// Workspace requested on the command line (`--workspace <name>` or
+/// `--workspace=<name>`)
+pub fn requested_workspace<I: IntoIterator<Item = String>>(args: I) -> Option<String> {
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--workspace" {
+            return args.next();
+        }
+        if let Some(name) = arg.strip_prefix("--workspace=") {
+            return Some(name.to_string());
+        }
+    }
+    None
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+pub const SETTINGS_STEM: &str = "workspaces";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_create_workspaces_and_dirs() {
+        let dir = TempDir::new().unwrap();
+        let mut settings = WorkspaceSettings::default();
+        assert_eq!(settings.active, DEFAULT_WORKSPACE);
+        assert_eq!(data_dir(dir.path(), DEFAULT_WORKSPACE), dir.path());
+
+        settings.create("team-a", 1).unwrap();
+        assert!(settings.create("team-a", 2).is_err());
+        assert!(settings.create(DEFAULT_WORKSPACE, 2).is_err());
+        assert!(settings.create("../escape", 2).is_err());
+        assert!(settings.create("", 2).is_err());
+        settings.active = "team-a".to_string();
+        settings.validate().unwrap();
+        assert_eq!(
+            data_dir(dir.path(), "team-a"),
+            dir.path().join("workspaces").join("team-a")
+        );
+
+        settings.active = "unknown".to_string();
+        assert!(settings.validate().is_err());
+
+        let args = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            requested_workspace(args(&["app", "--workspace", "team-a"])),
+            Some("team-a".to_string())
+        );
+        assert_eq!(
+            requested_workspace(args(&["app", "--workspace=team-b"])),
+            Some("team-b".to_string())
+        );
+        assert_eq!(requested_workspace(args(&["app"])), None);
+    }
+}
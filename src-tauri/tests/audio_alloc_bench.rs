@@ -0,0 +1,117 @@
+// Audio Hot Path Allocation Benchmark
+//
+// Counts heap allocations per second of audio on the send path
+// (batch → IPC JSON) and in the mixer, comparing the legacy Vec/Value
+// path with the Bytes/borrowed path.
+//
+// Run: cargo test --release --test audio_alloc_bench -- --ignored --nocapture
+
+use meeting_minutes_automator_lib::input_mixer::{InputMixer, BYTES_PER_FRAME};
+use meeting_minutes_automator_lib::ipc_protocol::{
+    encode_audio_stream_request, IpcMessage, PROTOCOL_VERSION,
+};
+use meeting_minutes_automator_lib::multi_input_manager::{InputBuffer, InputConfig, InputRole};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(layout.size() as u64, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// (allocations, bytes) performed by `f`
+fn measure(f: impl FnOnce()) -> (u64, u64) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let bytes = ALLOCATED_BYTES.load(Ordering::Relaxed);
+    f();
+    (
+        ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        ALLOCATED_BYTES.load(Ordering::Relaxed) - bytes,
+    )
+}
+
+/// 250ms batch at 16kHz mono 16-bit (sender batching interval)
+const BATCH_BYTES: usize = 8000;
+/// Batches per second of audio
+const BATCHES_PER_SECOND: usize = 4;
+/// Seconds of audio per measurement
+const SECONDS: usize = 10;
+
+#[test]
+#[ignore] // Benchmark: run explicitly with --ignored --nocapture
+fn bench_sender_encoding_allocations() {
+    let batch_buffer: Vec<u8> = (0..BATCH_BYTES).map(|i| (i % 251) as u8).collect();
+    let batches = BATCHES_PER_SECOND * SECONDS;
+
+    // Legacy: copy batch into Vec, then into a serde_json::Value array
+    let (legacy_allocs, legacy_bytes) = measure(|| {
+        for i in 0..batches {
+            let batch_data = batch_buffer.to_vec();
+            let message = IpcMessage::Request {
+                id: format!("audio-{}", i),
+                version: PROTOCOL_VERSION.to_string(),
+                method: "process_audio_stream".to_string(),
+                params: serde_json::json!({ "audio_data": batch_data }),
+            };
+            std::hint::black_box(serde_json::to_string(&message).unwrap());
+        }
+    });
+
+    // Current: serialize straight from the borrowed slice
+    let (current_allocs, current_bytes) = measure(|| {
+        for i in 0..batches {
+            let id = format!("audio-{}", i);
+            std::hint::black_box(encode_audio_stream_request(&id, &batch_buffer).unwrap());
+        }
+    });
+
+    println!(
+        "sender encoding per second of audio: legacy {} allocs / {} KB, current {} allocs / {} KB",
+        legacy_allocs / SECONDS as u64,
+        legacy_bytes / SECONDS as u64 / 1024,
+        current_allocs / SECONDS as u64,
+        current_bytes / SECONDS as u64 / 1024,
+    );
+    assert!(current_allocs < legacy_allocs);
+    assert!(current_bytes < legacy_bytes);
+}
+
+#[test]
+#[ignore] // Benchmark: run explicitly with --ignored --nocapture
+fn bench_mixer_output_allocations() {
+    let frames = 100 * SECONDS; // 10ms frames
+    let buffer = Arc::new(InputBuffer::new(BYTES_PER_FRAME * frames));
+    buffer.push(&vec![0x10u8; BYTES_PER_FRAME * frames]);
+    let inputs = vec![(InputConfig::new("mic-1", InputRole::Microphone), buffer)];
+    let mut mixer = InputMixer::new();
+
+    // Frames are consumed and dropped immediately (ring buffer push copies)
+    let (allocs, bytes) = measure(|| {
+        for _ in 0..frames {
+            let frame = mixer.mix_frame(&inputs).unwrap();
+            std::hint::black_box(&frame[..]);
+        }
+    });
+
+    println!(
+        "mixer per second of audio: {} allocs / {} KB",
+        allocs / SECONDS as u64,
+        bytes / SECONDS as u64 / 1024,
+    );
+}
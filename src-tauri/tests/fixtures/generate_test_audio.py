@@ -18,6 +18,10 @@ Generated Files:
 - test_audio_short.wav: 3 seconds (1s speech, 0.5s silence, 1s speech, 0.5s silence)
 - test_audio_long.wav: 10 seconds (multiple speech/silence cycles)
 - test_audio_silence.wav: 2 seconds (pure silence for no_speech testing)
+
+Other durations and sample rates (with segment labels):
+  meeting-minutes-automator --gen-test-audio --preset long --duration-secs 600 --out long_10m.wav
+(see src/test_audio.rs; its presets produce the same samples as this script)
 """
 
 import numpy as np
@@ -160,3 +160,62 @@ async fn it_websocket_server_multiple_broadcasts() {
 
     server.stop().await.expect("Should stop server");
 }
+
+#[tokio::test]
+async fn it_websocket_slow_client_does_not_delay_others() {
+    // Test: A client that stops reading must not stall delivery to other clients
+    let mut server = WebSocketServer::new();
+    let port = server.start().await.expect("Should start server");
+    let url = format!("ws://127.0.0.1:{}", port);
+
+    // Slow client: connects but never reads
+    let (_slow_stream, _) = connect_async(&url)
+        .await
+        .expect("Failed to connect slow client");
+
+    let (fast_stream, _) = connect_async(&url)
+        .await
+        .expect("Failed to connect fast client");
+    let (mut _write, mut read) = fast_stream.split();
+    let _ = read.next().await; // connected
+
+    const MESSAGES: usize = 400;
+    let reader = tokio::spawn(async move {
+        let mut received = 0;
+        while received < MESSAGES {
+            match timeout(Duration::from_secs(5), read.next()).await {
+                Ok(Some(Ok(Message::Text(_)))) => received += 1,
+                _ => break,
+            }
+        }
+        received
+    });
+
+    // 16KB partials: the slow client's socket buffers fill up quickly
+    let padding = "x".repeat(16 * 1024);
+    for i in 0..MESSAGES {
+        let msg = WebSocketMessage::Transcription {
+            message_id: format!("msg-{}", i),
+            session_id: "test-session".to_string(),
+            text: padding.clone(),
+            timestamp: i as u64,
+            is_partial: Some(true),
+            confidence: None,
+            language: None,
+            processing_time_ms: None,
+        };
+        timeout(Duration::from_millis(100), server.broadcast(msg))
+            .await
+            .expect("Broadcast must not block on a slow client")
+            .expect("Should broadcast");
+        tokio::time::sleep(Duration::from_millis(1)).await;
+    }
+
+    let received = reader.await.expect("Reader task failed");
+    assert_eq!(
+        received, MESSAGES,
+        "Fast client should receive every message"
+    );
+
+    server.stop().await.expect("Should stop server");
+}
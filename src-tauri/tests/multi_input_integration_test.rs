@@ -56,6 +56,7 @@ impl AudioDeviceAdapter for MockAudioAdapter {
                 name: "Mock Microphone".to_string(),
                 sample_rate: 48000,
                 channels: 2,
+                max_channels: 2,
                 is_loopback: false,
             },
             AudioDeviceInfo {
@@ -63,6 +64,7 @@ impl AudioDeviceAdapter for MockAudioAdapter {
                 name: "Mock Loopback".to_string(),
                 sample_rate: 48000,
                 channels: 2,
+                max_channels: 2,
                 is_loopback: true,
             },
         ])
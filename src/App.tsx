@@ -19,6 +19,7 @@ interface AudioDeviceInfo {
   name: string;
   sample_rate: number;
   channels: number;
+  max_channels: number;
   is_loopback: boolean;
 }
 
@@ -490,6 +491,27 @@ function App() {
     };
   }, []);
 
+  // Recording started/stopped by a WebSocket client (remote control)
+  useEffect(() => {
+    const unlistenPromise = listen<{
+      recording: boolean;
+      sessionId?: string;
+      deviceId?: string;
+    }>("remote_control", (event) => {
+      const payload = event.payload;
+      setIsRecording(payload.recording);
+      setStatusMsg(
+        payload.recording
+          ? `Recording started remotely (${payload.deviceId ?? "unknown device"})`
+          : "Recording stopped remotely"
+      );
+    });
+
+    return () => {
+      unlistenPromise.then((unlisten) => unlisten());
+    };
+  }, []);
+
   return (
     <main className="container">
       <h1>Meeting Minutes Automator</h1>
@@ -531,7 +553,8 @@ function App() {
               >
                 {audioDevices.map((device) => (
                   <option key={device.id} value={device.id}>
-                    {device.name} ({device.sample_rate / 1000}kHz, {device.channels}ch)
+                    {device.name} ({device.sample_rate / 1000}kHz, {device.channels}ch
+                    {device.max_channels > device.channels ? `, ${device.max_channels} inputs` : ""})
                     {device.is_loopback ? " [Loopback]" : ""}
                   </option>
                 ))}